@@ -20,13 +20,21 @@ use rocketmq_common::common::broker::broker_config::BrokerConfig;
 use rocketmq_common::common::server::config::ServerConfig;
 use rocketmq_rust::wait_for_signal;
 use rocketmq_store::config::message_store_config::MessageStoreConfig;
+use rocketmq_store::message_store::local_file_message_store::LocalFileMessageStore;
 use tracing::error;
 use tracing::info;
 
 use crate::broker_runtime::BrokerRuntime;
+use crate::broker_runtime::TieredLocalFileMessageStore;
 
 pub struct BrokerBootstrap {
-    broker_runtime: BrokerRuntime,
+    broker_runtime: MessageStoreRuntime,
+}
+
+/// The broker runtime, typed by the message store picked from the config.
+enum MessageStoreRuntime {
+    LocalFile(BrokerRuntime<LocalFileMessageStore>),
+    Tiered(BrokerRuntime<TieredLocalFileMessageStore>),
 }
 
 impl BrokerBootstrap {
@@ -36,17 +44,26 @@ impl BrokerBootstrap {
             return;
         }
         let (shutdown_tx, shutdown_rx) = tokio::sync::broadcast::channel(1);
-        self.broker_runtime.shutdown_rx = Some(shutdown_rx);
+        match &mut self.broker_runtime {
+            MessageStoreRuntime::LocalFile(runtime) => runtime.shutdown_rx = Some(shutdown_rx),
+            MessageStoreRuntime::Tiered(runtime) => runtime.shutdown_rx = Some(shutdown_rx),
+        }
 
         tokio::join!(self.start(), wait_for_signal_inner(shutdown_tx));
     }
 
     async fn initialize(&mut self) -> bool {
-        self.broker_runtime.initialize().await
+        match &mut self.broker_runtime {
+            MessageStoreRuntime::LocalFile(runtime) => runtime.initialize().await,
+            MessageStoreRuntime::Tiered(runtime) => runtime.initialize().await,
+        }
     }
 
     async fn start(&mut self) {
-        self.broker_runtime.start().await;
+        match &mut self.broker_runtime {
+            MessageStoreRuntime::LocalFile(runtime) => runtime.start().await,
+            MessageStoreRuntime::Tiered(runtime) => runtime.start().await,
+        }
     }
 }
 
@@ -91,13 +108,24 @@ impl Builder {
     }
 
     pub fn build(self) -> BrokerBootstrap {
-        BrokerBootstrap {
-            broker_runtime: BrokerRuntime::new(
-                Arc::new(self.broker_config),
-                Arc::new(self.message_store_config),
-                Arc::new(self.server_config),
-            ),
-        }
+        let tiered_store_enable = self.message_store_config.tiered_store_enable;
+        let broker_config = Arc::new(self.broker_config);
+        let message_store_config = Arc::new(self.message_store_config);
+        let server_config = Arc::new(self.server_config);
+        let broker_runtime = if tiered_store_enable {
+            MessageStoreRuntime::Tiered(BrokerRuntime::new(
+                broker_config,
+                message_store_config,
+                server_config,
+            ))
+        } else {
+            MessageStoreRuntime::LocalFile(BrokerRuntime::new(
+                broker_config,
+                message_store_config,
+                server_config,
+            ))
+        };
+        BrokerBootstrap { broker_runtime }
     }
 }
 
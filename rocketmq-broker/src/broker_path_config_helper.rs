@@ -136,6 +136,24 @@ pub fn get_message_request_mode_path(root_dir: &str) -> String {
         .into_owned()
 }
 
+/// Returns the path of the file keeping the broker id applied from the controller.
+pub fn get_broker_identity_path(root_dir: &str) -> String {
+    PathBuf::from(root_dir)
+        .join("config")
+        .join("brokerIdentity.json")
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Returns the path of the plain ACL accounts file.
+pub fn get_plain_acl_config_path(root_dir: &str) -> String {
+    PathBuf::from(root_dir)
+        .join("config")
+        .join("plain_acl.json")
+        .to_string_lossy()
+        .into_owned()
+}
+
 #[cfg(test)]
 mod test {
     use std::path::PathBuf;
@@ -24,6 +24,7 @@ use std::time::Duration;
 
 use cheetah_string::CheetahString;
 use rocketmq_common::common::broker::broker_config::BrokerConfig;
+use rocketmq_common::common::broker::broker_role::BrokerRole;
 use rocketmq_common::common::config::TopicConfig;
 use rocketmq_common::common::config_manager::ConfigManager;
 use rocketmq_common::common::constant::PermName;
@@ -32,6 +33,8 @@ use rocketmq_common::common::server::config::ServerConfig;
 use rocketmq_common::common::statistics::state_getter::StateGetter;
 use rocketmq_common::TimeUtils::get_current_millis;
 use rocketmq_common::UtilAll::compute_next_morning_time_millis;
+use rocketmq_remoting::acl::plain_access_validator::PlainAccessValidator;
+use rocketmq_remoting::acl::plain_permission_manager::PlainPermissionManager;
 use rocketmq_remoting::base::channel_event_listener::ChannelEventListener;
 use rocketmq_remoting::protocol::body::broker_body::broker_member_group::BrokerMemberGroup;
 use rocketmq_remoting::protocol::body::topic_info_wrapper::topic_config_wrapper::TopicConfigAndMappingSerializeWrapper;
@@ -42,21 +45,31 @@ use rocketmq_remoting::protocol::static_topic::topic_queue_mapping_detail::Topic
 use rocketmq_remoting::protocol::DataVersion;
 use rocketmq_remoting::remoting_server::server::RocketMQServer;
 use rocketmq_remoting::runtime::config::client_config::TokioClientConfig;
+use rocketmq_remoting::runtime::RPCHook;
 use rocketmq_runtime::RocketMQRuntime;
 use rocketmq_rust::ArcMut;
 use rocketmq_store::base::commit_log_dispatcher::CommitLogDispatcher;
+use rocketmq_store::base::message_arriving_listener::MessageArrivingListener;
 use rocketmq_store::base::message_store::MessageStore;
 use rocketmq_store::base::store_enum::StoreType;
 use rocketmq_store::config::message_store_config::MessageStoreConfig;
 use rocketmq_store::message_store::local_file_message_store::LocalFileMessageStore;
 use rocketmq_store::stats::broker_stats::BrokerStats;
 use rocketmq_store::stats::broker_stats_manager::BrokerStatsManager;
+use rocketmq_store::store_error::StoreError;
+use rocketmq_store::tiered::tiered_backend::TieredBackend;
+use rocketmq_store::tiered::tiered_message_store::TieredMessageStore;
+use rocketmq_store::tiered::tiered_store_config::TieredStoreConfig;
+use rocketmq_store::timer::timer_checkpoint::TimerCheckpoint;
 use rocketmq_store::timer::timer_message_store::TimerMessageStore;
+use rocketmq_store::timer::timer_metrics::TimerMetrics;
+use tracing::error;
 use tracing::info;
 use tracing::warn;
 
 use crate::broker::broker_hook::BrokerShutdownHook;
 use crate::broker::broker_pre_online_service::BrokerPreOnlineService;
+use crate::broker_path_config_helper;
 use crate::client::client_housekeeping_service::ClientHousekeepingService;
 use crate::client::consumer_ids_change_listener::ConsumerIdsChangeListener;
 use crate::client::default_consumer_ids_change_listener::DefaultConsumerIdsChangeListener;
@@ -89,6 +102,8 @@ use crate::processor::consumer_manage_processor::ConsumerManageProcessor;
 use crate::processor::default_pull_message_result_handler::DefaultPullMessageResultHandler;
 use crate::processor::end_transaction_processor::EndTransactionProcessor;
 use crate::processor::notification_processor::NotificationProcessor;
+use crate::processor::peek_message_processor::PeekMessageProcessor;
+use crate::processor::polling_info_processor::PollingInfoProcessor;
 use crate::processor::pop_inflight_message_counter::PopInflightMessageCounter;
 use crate::processor::pop_message_processor::PopMessageProcessor;
 use crate::processor::pull_message_processor::PullMessageProcessor;
@@ -109,9 +124,79 @@ use crate::transaction::queue::transactional_message_bridge::TransactionalMessag
 use crate::transaction::transaction_metrics_flush_service::TransactionMetricsFlushService;
 use crate::transaction::transactional_message_check_service::TransactionalMessageCheckService;
 
-pub(crate) struct BrokerRuntime {
+const MIN_HA_ADDRESS_LENGTH: usize = 6;
+
+/// The message store a [`BrokerRuntime`] runs on, built on top of the local file store.
+pub(crate) trait BrokerMessageStore: MessageStore {
+    fn from_local_file_store(
+        local_file_store: ArcMut<LocalFileMessageStore>,
+        broker_config: &BrokerConfig,
+        message_store_config: &MessageStoreConfig,
+    ) -> Result<ArcMut<Self>, StoreError>;
+
+    fn set_message_arriving_listener(
+        &mut self,
+        message_arriving_listener: Option<
+            Arc<Box<dyn MessageArrivingListener + Sync + Send + 'static>>,
+        >,
+    );
+}
+
+impl BrokerMessageStore for LocalFileMessageStore {
+    fn from_local_file_store(
+        local_file_store: ArcMut<LocalFileMessageStore>,
+        _broker_config: &BrokerConfig,
+        _message_store_config: &MessageStoreConfig,
+    ) -> Result<ArcMut<Self>, StoreError> {
+        Ok(local_file_store)
+    }
+
+    fn set_message_arriving_listener(
+        &mut self,
+        message_arriving_listener: Option<
+            Arc<Box<dyn MessageArrivingListener + Sync + Send + 'static>>,
+        >,
+    ) {
+        LocalFileMessageStore::set_message_arriving_listener(self, message_arriving_listener);
+    }
+}
+
+/// The store used when `tiered_store_enable` is set.
+pub(crate) type TieredLocalFileMessageStore =
+    TieredMessageStore<LocalFileMessageStore, TieredBackend>;
+
+impl BrokerMessageStore for TieredLocalFileMessageStore {
+    fn from_local_file_store(
+        local_file_store: ArcMut<LocalFileMessageStore>,
+        broker_config: &BrokerConfig,
+        message_store_config: &MessageStoreConfig,
+    ) -> Result<ArcMut<Self>, StoreError> {
+        let tiered_config = TieredStoreConfig {
+            broker_name: broker_config.broker_identity.broker_name.clone(),
+            upload_interval_ms: message_store_config.tiered_upload_interval_ms,
+            file_reserved_time_hours: message_store_config.tiered_file_reserved_time_hours,
+        };
+        Ok(ArcMut::new(TieredMessageStore::new(
+            local_file_store,
+            TieredBackend::new(message_store_config)?,
+            tiered_config,
+        )))
+    }
+
+    fn set_message_arriving_listener(
+        &mut self,
+        message_arriving_listener: Option<
+            Arc<Box<dyn MessageArrivingListener + Sync + Send + 'static>>,
+        >,
+    ) {
+        self.next_mut()
+            .set_message_arriving_listener(message_arriving_listener);
+    }
+}
+
+pub(crate) struct BrokerRuntime<MS: BrokerMessageStore> {
     #[cfg(feature = "local_file_store")]
-    inner: ArcMut<BrokerRuntimeInner<LocalFileMessageStore>>,
+    inner: ArcMut<BrokerRuntimeInner<MS>>,
     broker_runtime: Option<RocketMQRuntime>,
     shutdown_hook: Option<BrokerShutdownHook>,
     consumer_ids_change_listener: Arc<Box<dyn ConsumerIdsChangeListener + Send + Sync + 'static>>,
@@ -121,7 +206,7 @@ pub(crate) struct BrokerRuntime {
     pub(crate) shutdown_rx: Option<tokio::sync::broadcast::Receiver<()>>,
 }
 
-impl BrokerRuntime {
+impl<MS: BrokerMessageStore> BrokerRuntime<MS> {
     pub(crate) fn new(
         broker_config: Arc<BrokerConfig>,
         message_store_config: Arc<MessageStoreConfig>,
@@ -144,9 +229,12 @@ impl BrokerRuntime {
             broker_config.get_broker_addr().into(),
         );
         let producer_manager = ProducerManager::new();
+        let consumer_filter_manager = ConsumerFilterManager::new(broker_config.clone());
         let consumer_ids_change_listener: Arc<
             Box<dyn ConsumerIdsChangeListener + Send + Sync + 'static>,
-        > = Arc::new(Box::new(DefaultConsumerIdsChangeListener {}));
+        > = Arc::new(Box::new(DefaultConsumerIdsChangeListener::new(
+            consumer_filter_manager.clone(),
+        )));
         let consumer_manager = ConsumerManager::new_with_broker_stats(
             consumer_ids_change_listener.clone(),
             broker_config.clone(),
@@ -156,7 +244,10 @@ impl BrokerRuntime {
         let pop_inflight_message_counter =
             PopInflightMessageCounter::new(should_start_time.clone());
 
-        let mut inner = ArcMut::new(BrokerRuntimeInner::<LocalFileMessageStore> {
+        let cold_data_cg_ctr_service =
+            ColdDataCgCtrService::new(broker_config.clone(), message_store_config.clone());
+
+        let mut inner = ArcMut::new(BrokerRuntimeInner::<MS> {
             shutdown: Arc::new(AtomicBool::new(false)),
             store_host,
             broker_addr: CheetahString::from(broker_address),
@@ -167,7 +258,7 @@ impl BrokerRuntime {
             topic_queue_mapping_manager,
             consumer_offset_manager: Default::default(),
             subscription_group_manager: None,
-            consumer_filter_manager: Some(Default::default()),
+            consumer_filter_manager: Some(consumer_filter_manager),
             consumer_order_info_manager: None,
             message_store: None,
             broker_stats: None,
@@ -194,7 +285,7 @@ impl BrokerRuntime {
             replicas_manager: None,
             broker_fast_failure: BrokerFastFailure,
             cold_data_pull_request_hold_service: None,
-            cold_data_cg_ctr_service: None,
+            cold_data_cg_ctr_service: Some(cold_data_cg_ctr_service),
             is_schedule_service_start: Arc::new(Default::default()),
             is_transaction_check_service_start: Arc::new(Default::default()),
             client_housekeeping_service: None,
@@ -203,6 +294,8 @@ impl BrokerRuntime {
             notification_processor: None,
             broker_attached_plugins: vec![],
             transactional_message_service: None,
+            plain_permission_manager: None,
+            rpc_hooks: vec![],
         });
         let mut stats_manager = BrokerStatsManager::new(inner.broker_config.clone());
         stats_manager.set_producer_state_getter(Arc::new(ProducerStateGetter {
@@ -244,6 +337,11 @@ impl BrokerRuntime {
         self.inner.broker_config()
     }
 
+    #[cfg(test)]
+    pub(crate) fn inner(&self) -> &ArcMut<BrokerRuntimeInner<MS>> {
+        &self.inner
+    }
+
     pub(crate) fn message_store_config(&self) -> &MessageStoreConfig {
         self.inner.message_store_config()
     }
@@ -398,7 +496,7 @@ impl BrokerRuntime {
     }
 }
 
-impl BrokerRuntime {
+impl<MS: BrokerMessageStore> BrokerRuntime<MS> {
     pub(crate) async fn initialize(&mut self) -> bool {
         let mut result = self.initialize_metadata();
         if !result {
@@ -426,25 +524,74 @@ impl BrokerRuntime {
     }
 
     async fn initialize_message_store(&mut self) -> bool {
-        if self.inner.message_store_config.store_type == StoreType::LocalFile {
-            info!("Use local file as message store");
-            let mut message_store = ArcMut::new(LocalFileMessageStore::new(
+        let store_type = self.inner.message_store_config.store_type;
+        if store_type == StoreType::LocalFile || store_type == StoreType::RocksDB {
+            if store_type == StoreType::RocksDB {
+                info!("Use local file as message store, with consume queues in RocksDB");
+            } else {
+                info!("Use local file as message store");
+            }
+            let mut local_file_store = ArcMut::new(LocalFileMessageStore::new(
                 self.inner.message_store_config.clone(),
                 self.inner.broker_config.clone(),
                 self.inner.topic_config_manager().topic_config_table(),
                 self.inner.broker_stats_manager.clone(),
                 false,
             ));
-            let message_store_clone = message_store.clone();
-            message_store.set_message_store_arc(message_store_clone);
+            let local_file_store_clone = local_file_store.clone();
+            local_file_store.set_message_store_arc(local_file_store_clone);
             if self.inner.message_store_config.is_timer_wheel_enable() {
-                let time_message_store = TimerMessageStore::new(Some(message_store.clone()));
-                message_store.set_timer_message_store(Arc::new(time_message_store));
+                let store_path_root_dir =
+                    self.inner.message_store_config.store_path_root_dir.as_str();
+                let timer_checkpoint = match TimerCheckpoint::new(
+                    broker_path_config_helper::get_timer_check_path(store_path_root_dir),
+                ) {
+                    Ok(timer_checkpoint) => timer_checkpoint,
+                    Err(e) => {
+                        error!("Failed to create timer checkpoint: {}", e);
+                        return false;
+                    }
+                };
+                let timer_metrics = TimerMetrics::new(
+                    broker_path_config_helper::get_timer_metrics_path(store_path_root_dir),
+                );
+                let timer_message_store = match TimerMessageStore::new(
+                    Some(local_file_store.clone()),
+                    self.inner.message_store_config.clone(),
+                    timer_checkpoint,
+                    timer_metrics,
+                ) {
+                    Ok(timer_message_store) => timer_message_store,
+                    Err(e) => {
+                        error!("Failed to create timer message store: {}", e);
+                        return false;
+                    }
+                };
+                local_file_store.set_timer_message_store(Arc::new(timer_message_store.clone()));
+                self.inner.timer_message_store = Some(timer_message_store);
             }
+            if self.inner.message_store_config.tiered_store_enable {
+                info!(
+                    "Offload sealed files to {} tiered storage",
+                    self.inner
+                        .message_store_config
+                        .tiered_backend_type
+                        .get_backend_type()
+                );
+            }
+            let message_store = match MS::from_local_file_store(
+                local_file_store,
+                &self.inner.broker_config,
+                &self.inner.message_store_config,
+            ) {
+                Ok(message_store) => message_store,
+                Err(e) => {
+                    error!("Failed to create message store: {}", e);
+                    return false;
+                }
+            };
             self.inner.broker_stats = Some(BrokerStats::new(message_store.clone()));
             self.inner.message_store = Some(message_store);
-        } else if self.inner.message_store_config.store_type == StoreType::RocksDB {
-            unimplemented!("Use RocksDB as message store unimplemented");
         } else {
             warn!("Unknown store type");
             return false;
@@ -463,22 +610,20 @@ impl BrokerRuntime {
         let mut result: bool = true;
 
         if self.inner.broker_config().enable_controller_mode {
-            info!("Start controller mode(Support for future versions)");
-            unimplemented!("tart controller mode(Support for future versions")
+            info!("Start controller mode");
+            self.inner.replicas_manager = Some(ReplicasManager::new(self.inner.clone()));
         }
         if self.inner.message_store.is_some() {
             self.register_message_store_hook();
             // load message store
-            self.inner.message_store.as_mut().unwrap().load().await;
+            if !self.inner.message_store.as_mut().unwrap().load().await {
+                error!("Failed to load the message store");
+                return false;
+            }
         }
 
-        if self
-            .inner
-            .broker_config
-            .timer_wheel_config
-            .timer_wheel_enable
-        {
-            result &= self.inner.timer_message_store.as_mut().unwrap().load();
+        if let Some(timer_message_store) = self.inner.timer_message_store.as_ref() {
+            result &= timer_message_store.load();
         }
 
         //scheduleMessageService load after messageStore load success
@@ -489,7 +634,9 @@ impl BrokerRuntime {
             self.initialize_resources();
             self.initialize_scheduled_tasks().await;
             self.initial_transaction();
-            self.initial_acl();
+            if !self.initial_acl() {
+                return false;
+            }
             self.initial_rpc_hooks();
             self.initial_request_pipeline();
         }
@@ -523,10 +670,7 @@ impl BrokerRuntime {
 
     fn init_processor(
         &mut self,
-    ) -> BrokerRequestProcessor<
-        LocalFileMessageStore,
-        DefaultTransactionalMessageService<LocalFileMessageStore>,
-    > {
+    ) -> BrokerRequestProcessor<MS, DefaultTransactionalMessageService<MS>> {
         let send_message_processor = SendMessageProcessor::new(
             self.inner
                 .transactional_message_service
@@ -558,6 +702,10 @@ impl BrokerRuntime {
             pull_message_processor.clone(),
             self.inner.clone(),
         ));
+        self.inner.cold_data_pull_request_hold_service = Some(ColdDataPullRequestHoldService::new(
+            pull_message_processor.clone(),
+            self.inner.message_store_config.clone(),
+        ));
 
         let inner = self.inner.clone();
         self.inner
@@ -583,15 +731,18 @@ impl BrokerRuntime {
         BrokerRequestProcessor {
             send_message_processor: ArcMut::new(send_message_processor),
             pull_message_processor,
-            peek_message_processor: Default::default(),
+            peek_message_processor: ArcMut::new(PeekMessageProcessor::new(self.inner.clone())),
             pop_message_processor: pop_message_processor.clone(),
             ack_message_processor,
             change_invisible_time_processor: ArcMut::new(ChangeInvisibleTimeProcessor::new(
-                pop_message_processor,
+                pop_message_processor.clone(),
                 self.inner.clone(),
             )),
             notification_processor,
-            polling_info_processor: Default::default(),
+            polling_info_processor: ArcMut::new(PollingInfoProcessor::new(
+                pop_message_processor,
+                self.inner.clone(),
+            )),
             reply_message_processor: ArcMut::new(reply_message_processor),
             admin_broker_processor: ArcMut::new(admin_broker_processor),
             client_manage_processor: ArcMut::new(ClientManageProcessor::new(self.inner.clone())),
@@ -724,6 +875,25 @@ impl BrokerRuntime {
                 }
             });
 
+        if !self.inner.message_store_config.enable_dleger_commit_log
+            && !self.inner.message_store_config.duplication_enable
+            && !self.inner.broker_config.enable_controller_mode
+            && self.inner.message_store_config.broker_role == BrokerRole::Slave
+        {
+            match self.inner.message_store_config.ha_master_address.clone() {
+                Some(ha_master_address) if ha_master_address.len() >= MIN_HA_ADDRESS_LENGTH => {
+                    if let Some(message_store) = self.inner.message_store.as_ref() {
+                        message_store
+                            .update_ha_master_address(&CheetahString::from(ha_master_address));
+                    }
+                    self.inner.update_master_haserver_addr_periodically = false;
+                }
+                _ => {
+                    self.inner.update_master_haserver_addr_periodically = true;
+                }
+            }
+        }
+
         if self.inner.broker_config.enable_controller_mode {
             self.inner.update_master_haserver_addr_periodically = true;
         }
@@ -772,7 +942,30 @@ impl BrokerRuntime {
         self.inner.transaction_metrics_flush_service = Some(TransactionMetricsFlushService);
     }
 
-    fn initial_acl(&mut self) {}
+    /// Loads the acl config when acl is enabled, returning `false` if the broker must not start
+    /// because the config could not be loaded.
+    fn initial_acl(&mut self) -> bool {
+        if !self.inner.broker_config.acl_enable {
+            info!("The broker does not enable acl");
+            return true;
+        }
+        let config_path = broker_path_config_helper::get_plain_acl_config_path(
+            self.inner.message_store_config.store_path_root_dir.as_str(),
+        );
+        let permission_manager = Arc::new(PlainPermissionManager::new(config_path));
+        if let Err(e) = permission_manager.load() {
+            error!("Failed to load acl config: {}", e);
+            return false;
+        }
+        self.inner
+            .rpc_hooks
+            .push(Arc::new(PlainAccessValidator::new(
+                permission_manager.clone(),
+            )));
+        self.inner.plain_permission_manager = Some(permission_manager);
+        info!("The broker enables acl");
+        true
+    }
 
     fn initial_rpc_hooks(&mut self) {}
 
@@ -797,7 +990,10 @@ impl BrokerRuntime {
         let request_processor = self.init_processor();
         let fast_request_processor = request_processor.clone();
 
-        let server = RocketMQServer::new(self.inner.server_config.clone());
+        let mut server = RocketMQServer::new(self.inner.server_config.clone());
+        for hook in self.inner.rpc_hooks.iter() {
+            server.register_rpc_hook(hook.clone());
+        }
         //start nomarl broker remoting_server
         let client_housekeeping_service_main = self
             .inner
@@ -806,18 +1002,27 @@ impl BrokerRuntime {
             .map(|item| item as Arc<dyn ChannelEventListener>);
         let client_housekeeping_service_fast = client_housekeeping_service_main.clone();
         tokio::spawn(async move {
-            server
+            if let Err(e) = server
                 .run(request_processor, client_housekeeping_service_main)
                 .await
+            {
+                error!("Failed to start broker remoting server: {}", e);
+            }
         });
         //start fast broker remoting_server
         let mut fast_server_config = self.inner.server_config.as_ref().clone();
         fast_server_config.listen_port = self.inner.server_config.listen_port - 2;
-        let fast_server = RocketMQServer::new(Arc::new(fast_server_config));
+        let mut fast_server = RocketMQServer::new(Arc::new(fast_server_config));
+        for hook in self.inner.rpc_hooks.iter() {
+            fast_server.register_rpc_hook(hook.clone());
+        }
         tokio::spawn(async move {
-            fast_server
+            if let Err(e) = fast_server
                 .run(fast_request_processor, client_housekeeping_service_fast)
                 .await
+            {
+                error!("Failed to start broker fast remoting server: {}", e);
+            }
         });
 
         if let Some(pop_message_processor) = self.inner.pop_message_processor.as_mut() {
@@ -1028,7 +1233,32 @@ impl BrokerRuntime {
         }
     }
 
-    pub(crate) fn schedule_send_heartbeat(&mut self) {}
+    pub(crate) fn schedule_send_heartbeat(&mut self) {
+        if !self.inner.broker_config.enable_controller_mode {
+            return;
+        }
+        let broker_runtime_inner = self.inner.clone();
+        self.broker_runtime
+            .as_ref()
+            .unwrap()
+            .get_handle()
+            .spawn(async move {
+                let period = Duration::from_millis(
+                    broker_runtime_inner.broker_config.broker_heartbeat_interval,
+                );
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                loop {
+                    let current_execution_time = tokio::time::Instant::now();
+                    if let Some(replicas_manager) = broker_runtime_inner.replicas_manager.as_ref() {
+                        replicas_manager.send_heartbeat_to_controller().await;
+                    }
+                    let next_execution_time = current_execution_time + period;
+                    let delay =
+                        next_execution_time.saturating_duration_since(tokio::time::Instant::now());
+                    tokio::time::sleep(delay).await;
+                }
+            });
+    }
 
     pub(crate) async fn start_service_without_condition(&mut self) {
         info!(
@@ -1089,7 +1319,7 @@ impl BrokerRuntime {
                 broker_addr.clone(),
                 broker_name,
                 broker_id,
-                broker_addr,
+                self.inner.get_ha_server_addr(),
                 topic_config_wrapper,
                 vec![],
                 oneway,
@@ -1207,7 +1437,7 @@ impl<MS: MessageStore> BrokerRuntimeInner<MS> {
                 broker_addr.clone(),
                 broker_name,
                 broker_id,
-                broker_addr,
+                this.get_ha_server_addr(),
                 topic_config_wrapper,
                 vec![],
                 oneway,
@@ -1226,10 +1456,20 @@ impl<MS: MessageStore> BrokerRuntimeInner<MS> {
 
     pub(self) fn handle_register_broker_result(
         &mut self,
-        _register_broker_result: Vec<RegisterBrokerResult>,
+        register_broker_result: Vec<RegisterBrokerResult>,
         _check_order_config: bool,
     ) {
-        warn!("handle_register_broker_result not implemented");
+        let Some(register_broker_result) = register_broker_result.into_iter().next() else {
+            return;
+        };
+        if self.update_master_haserver_addr_periodically
+            && !register_broker_result.ha_server_addr.is_empty()
+        {
+            if let Some(message_store) = self.message_store.as_ref() {
+                message_store.update_ha_master_address(&register_broker_result.ha_server_addr);
+                message_store.update_master_address(&register_broker_result.master_addr);
+            }
+        }
     }
 }
 
@@ -1341,9 +1581,9 @@ pub(crate) struct BrokerRuntimeInner<MS: MessageStore> {
     topic_route_info_manager: Option<TopicRouteInfoManager<MS>>,
     escape_bridge: Option<EscapeBridge<MS>>,
     pop_inflight_message_counter: PopInflightMessageCounter,
-    replicas_manager: Option<ReplicasManager>,
+    replicas_manager: Option<ReplicasManager<MS>>,
     broker_fast_failure: BrokerFastFailure,
-    cold_data_pull_request_hold_service: Option<ColdDataPullRequestHoldService>,
+    cold_data_pull_request_hold_service: Option<ColdDataPullRequestHoldService<MS>>,
     cold_data_cg_ctr_service: Option<ColdDataCgCtrService>,
     is_schedule_service_start: Arc<AtomicBool>,
     is_transaction_check_service_start: Arc<AtomicBool>,
@@ -1354,6 +1594,8 @@ pub(crate) struct BrokerRuntimeInner<MS: MessageStore> {
     notification_processor: Option<ArcMut<NotificationProcessor<MS>>>,
     broker_attached_plugins: Vec<Arc<dyn BrokerAttachedPlugin>>,
     transactional_message_service: Option<ArcMut<DefaultTransactionalMessageService<MS>>>,
+    plain_permission_manager: Option<Arc<PlainPermissionManager>>,
+    rpc_hooks: Vec<Arc<dyn RPCHook>>,
 }
 
 impl<MS: MessageStore> BrokerRuntimeInner<MS> {
@@ -1506,6 +1748,11 @@ impl<MS: MessageStore> BrokerRuntimeInner<MS> {
         &mut self.broker_member_group
     }
 
+    #[inline]
+    pub fn plain_permission_manager(&self) -> Option<&Arc<PlainPermissionManager>> {
+        self.plain_permission_manager.as_ref()
+    }
+
     #[inline]
     pub fn transactional_message_service_mut(
         &mut self,
@@ -1760,6 +2007,18 @@ impl<MS: MessageStore> BrokerRuntimeInner<MS> {
         unsafe { self.pull_request_hold_service.as_ref().unwrap_unchecked() }
     }
 
+    #[inline]
+    pub fn cold_data_pull_request_hold_service(
+        &self,
+    ) -> &Option<ColdDataPullRequestHoldService<MS>> {
+        &self.cold_data_pull_request_hold_service
+    }
+
+    #[inline]
+    pub fn cold_data_cg_ctr_service(&self) -> &Option<ColdDataCgCtrService> {
+        &self.cold_data_cg_ctr_service
+    }
+
     #[inline]
     pub fn rebalance_lock_manager(&self) -> &RebalanceLockManager {
         &self.rebalance_lock_manager
@@ -2153,7 +2412,7 @@ impl<MS: MessageStore> BrokerRuntimeInner<MS> {
                 broker_addr.clone(),
                 broker_name,
                 broker_id,
-                broker_addr,
+                this.get_ha_server_addr(),
                 topic_config_wrapper,
                 vec![],
                 oneway,
@@ -2170,6 +2429,19 @@ impl<MS: MessageStore> BrokerRuntimeInner<MS> {
     pub fn get_broker_addr(&self) -> &CheetahString {
         &self.broker_addr
     }
+
+    pub fn get_ha_server_addr(&self) -> CheetahString {
+        let ip = self
+            .broker_config
+            .broker_ip2
+            .as_ref()
+            .unwrap_or(&self.broker_config.broker_ip1);
+        CheetahString::from_string(format!(
+            "{}:{}",
+            ip, self.message_store_config.ha_listen_port
+        ))
+    }
+
     pub fn sync_broker_member_group(&self) {
         warn!("sync_broker_member_group not implemented");
     }
@@ -2272,3 +2544,145 @@ fn need_register(
 ) -> bool {
     unimplemented!()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use bytes::Bytes;
+    use rocketmq_common::common::message::message_decoder;
+    use rocketmq_common::common::message::message_ext_broker_inner::MessageExtBrokerInner;
+    use rocketmq_common::common::message::message_single::Message;
+    use rocketmq_common::common::message::MessageTrait;
+    use rocketmq_store::base::message_status_enum::GetMessageStatus;
+    use rocketmq_store::base::message_status_enum::PutMessageStatus;
+    use rocketmq_store::config::flush_disk_type::FlushDiskType;
+    use rocketmq_store::queue::single_consume_queue::CQ_STORE_UNIT_SIZE;
+
+    use super::*;
+
+    const TOPIC: &str = "TieredTopic";
+    /// Messages per commit log file and units per consume queue file, so that a sealed commit
+    /// log file and a sealed consume queue file cover the same messages.
+    const MESSAGES_PER_FILE: usize = 3;
+
+    fn new_tiered_runtime(root: &Path) -> BrokerRuntime<TieredLocalFileMessageStore> {
+        let broker_config = BrokerConfig {
+            store_path_root_dir: root.join("config").to_string_lossy().into_owned().into(),
+            ..BrokerConfig::default()
+        };
+        let message_store_config = MessageStoreConfig {
+            store_path_root_dir: root.join("store").to_string_lossy().into_owned().into(),
+            flush_disk_type: FlushDiskType::AsyncFlush,
+            mapped_file_size_commit_log: 1024,
+            mapped_file_size_consume_queue: MESSAGES_PER_FILE * CQ_STORE_UNIT_SIZE as usize,
+            max_hash_slot_num: 64,
+            max_index_num: 256,
+            timer_wheel_enable: false,
+            // the test removes the oldest local files itself, keep the store from reclaiming
+            // more of them when the disk is nearly full
+            disk_space_warning_level_ratio: 100,
+            disk_space_clean_forcibly_ratio: 100,
+            clean_file_forcibly_enable: false,
+            tiered_store_enable: true,
+            tiered_store_file_path: Some(
+                root.join("objects").to_string_lossy().into_owned().into(),
+            ),
+            tiered_upload_interval_ms: 3_600_000,
+            ..MessageStoreConfig::default()
+        };
+        BrokerRuntime::new(
+            Arc::new(broker_config),
+            Arc::new(message_store_config),
+            Arc::new(ServerConfig::default()),
+        )
+    }
+
+    /// Stops the runtime threads, which must not be dropped inside the test runtime.
+    fn stop_runtime<MS: BrokerMessageStore>(runtime: &mut BrokerRuntime<MS>) {
+        if let Some(broker_runtime) = runtime.broker_runtime.take() {
+            broker_runtime.shutdown();
+        }
+    }
+
+    /// A message of about 300 bytes, so that `MESSAGES_PER_FILE` of them fill a commit log
+    /// file.
+    fn message(index: usize) -> MessageExtBrokerInner {
+        let message = Message::new(TOPIC, body(index).as_bytes());
+        let mut msg = MessageExtBrokerInner {
+            properties_string: message_decoder::message_properties_to_string(
+                message.get_properties(),
+            ),
+            ..MessageExtBrokerInner::default()
+        };
+        msg.message_ext_inner.set_message_inner(message);
+        msg.message_ext_inner
+            .set_born_host("127.0.0.1:10000".parse().unwrap());
+        msg.message_ext_inner
+            .set_store_host("127.0.0.1:10911".parse().unwrap());
+        msg
+    }
+
+    fn body(index: usize) -> String {
+        format!("body-{index:04}-{}", "x".repeat(190))
+    }
+
+    /// Removes the oldest local file of `dir`, which must have been offloaded already.
+    fn remove_first_file(dir: &Path) {
+        let mut files: Vec<_> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        files.sort();
+        assert!(files.len() > 1, "{} holds no sealed file", dir.display());
+        fs::remove_file(&files[0]).unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn tiered_broker_reads_offloaded_messages() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let topic = CheetahString::from_static_str(TOPIC);
+        let group = CheetahString::from_static_str("TieredGroup");
+
+        let mut runtime = new_tiered_runtime(temp_dir.path());
+        assert!(runtime.initialize().await);
+        let message_store = runtime.inner.message_store_unchecked_mut();
+        assert!(message_store.is_tiered_message_store());
+        message_store.start().unwrap();
+        for i in 0..20 {
+            let result = message_store.put_message(message(i)).await;
+            assert_eq!(result.put_message_status(), PutMessageStatus::PutOk);
+        }
+        while message_store.get_max_offset_in_queue(&topic, 0) < 20 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        // the upload service may have offloaded some files already, upload the rest now
+        message_store.upload_sealed_segments().await;
+        message_store.shutdown();
+        stop_runtime(&mut runtime);
+
+        // Restart with the oldest local files gone, as if they had expired locally.
+        let store_root = temp_dir.path().join("store");
+        remove_first_file(&store_root.join("commitlog"));
+        remove_first_file(&store_root.join("consumequeue").join(TOPIC).join("0"));
+        let mut runtime = new_tiered_runtime(temp_dir.path());
+        assert!(runtime.initialize().await);
+        let message_store = runtime.inner.message_store_unchecked();
+        assert!(message_store.next().get_min_phy_offset() > 0);
+        assert!(message_store.next().get_min_offset_in_queue(&topic, 0) > 0);
+        assert_eq!(message_store.get_min_offset_in_queue(&topic, 0), 0);
+
+        let result = message_store
+            .get_message(&group, &topic, 0, 0, 1, None)
+            .await
+            .unwrap();
+        assert_eq!(result.status(), Some(GetMessageStatus::Found));
+        assert_eq!(result.message_count(), 1);
+        let mut buffer = Bytes::copy_from_slice(result.message_mapped_list()[0].get_buffer());
+        let message =
+            message_decoder::decode(&mut buffer, true, false, false, false, false).unwrap();
+        assert_eq!(message.get_body().unwrap().as_ref(), body(0).as_bytes());
+        stop_runtime(&mut runtime);
+    }
+}
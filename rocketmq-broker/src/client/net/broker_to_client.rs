@@ -16,11 +16,19 @@
  */
 use cheetah_string::CheetahString;
 use rocketmq_common::common::message::message_ext::MessageExt;
+use rocketmq_common::common::mq_version::RocketMqVersion;
 use rocketmq_common::MessageDecoder;
 use rocketmq_remoting::code::request_code::RequestCode;
 use rocketmq_remoting::net::channel::Channel;
+use rocketmq_remoting::protocol::body::reset_offset_body::ResetOffsetBody;
 use rocketmq_remoting::protocol::header::check_transaction_state_request_header::CheckTransactionStateRequestHeader;
+use rocketmq_remoting::protocol::header::reset_offset_request_header::ResetOffsetRequestHeader;
 use rocketmq_remoting::protocol::remoting_command::RemotingCommand;
+use rocketmq_remoting::protocol::RemotingSerializable;
+use tracing::info;
+use tracing::warn;
+
+use crate::client::consumer_group_info::ConsumerGroupInfo;
 
 #[derive(Default, Clone)]
 pub struct Broker2Client;
@@ -72,4 +80,57 @@ impl Broker2Client {
             },
         }
     }
+
+    /// Pushes the reset offsets of a consumer group to every online client of the group, so that
+    /// they drop their in-flight messages and continue from the new offsets.
+    pub async fn reset_offset(
+        &self,
+        consumer_group_info: &ConsumerGroupInfo,
+        request_header: ResetOffsetRequestHeader,
+        body: &ResetOffsetBody,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        let mut request = RemotingCommand::create_request_command(
+            RequestCode::ResetConsumerClientOffset,
+            request_header.clone(),
+        );
+        request.set_body_mut_ref(body.encode()?);
+        let channel_infos = consumer_group_info
+            .get_channel_info_table()
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect::<Vec<_>>();
+        for (channel, channel_info) in channel_infos {
+            let version = channel_info.version();
+            if version < RocketMqVersion::V307Snapshot as i32 {
+                warn!(
+                    "[reset-offset] the client does not support this feature. group={}, \
+                     clientId={}, version={}",
+                    request_header.group,
+                    channel_info.client_id(),
+                    version
+                );
+                continue;
+            }
+            let Some(channel) = channel.upgrade() else {
+                continue;
+            };
+            match channel.send_one_way(request.clone(), 5000).await {
+                Ok(_) => info!(
+                    "[reset-offset] reset offset success. topic={}, group={}, clientId={}",
+                    request_header.topic,
+                    request_header.group,
+                    channel_info.client_id()
+                ),
+                Err(e) => warn!(
+                    "[reset-offset] reset offset exception. topic={}, group={}, clientId={}, \
+                     error={}",
+                    request_header.topic,
+                    request_header.group,
+                    channel_info.client_id(),
+                    e
+                ),
+            }
+        }
+        Ok(())
+    }
 }
@@ -15,21 +15,250 @@
  * limitations under the License.
  */
 
-use tracing::warn;
+use std::sync::atomic::AtomicI64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
 
-#[derive(Default)]
-pub struct ColdDataCgCtrService;
+use cheetah_string::CheetahString;
+use dashmap::DashMap;
+use rocketmq_common::common::broker::broker_config::BrokerConfig;
+use rocketmq_common::common::mix_all;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_store::config::message_store_config::MessageStoreConfig;
+use serde::Serialize;
+use serde_json::json;
+use tokio::sync::Notify;
+use tracing::info;
+
+/// Cold-read bytes are accumulated over this period and then reset.
+const ACC_PERIOD_MILLIS: u64 = 5_000;
+/// Interval between checks when cold data flow control is disabled.
+const DISABLED_CHECK_INTERVAL_MILLIS: u64 = 20_000;
+/// Runtime entries without any cold read for this long are dropped.
+const ACC_EXPIRED_MILLIS: u64 = 60_000;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AccAndTimeStamp {
+    cold_acc: i64,
+    create_time_mills: u64,
+    last_cold_read_time_mills: u64,
+}
+
+impl AccAndTimeStamp {
+    fn new(now: u64) -> Self {
+        Self {
+            cold_acc: 0,
+            create_time_mills: now,
+            last_cold_read_time_mills: now,
+        }
+    }
+}
+
+/// Tracks cold-read bytes per consumer group and decides which groups need to be flow
+/// controlled.
+///
+/// A group is flow controlled when its own cold-read bytes in the current period reach its
+/// threshold while the broker-wide cold-read bytes reach the global threshold, so groups that
+/// only read hot data are never affected.
+#[derive(Clone)]
+pub struct ColdDataCgCtrService {
+    cg_cold_threshold_map_runtime: Arc<DashMap<CheetahString, AccAndTimeStamp>>,
+    cg_cold_threshold_map_config: Arc<DashMap<CheetahString, i64>>,
+    global_acc: Arc<AtomicI64>,
+    broker_config: Arc<BrokerConfig>,
+    message_store_config: Arc<MessageStoreConfig>,
+    shutdown: Arc<Notify>,
+}
 
 impl ColdDataCgCtrService {
-    pub fn start(&mut self) {
-        warn!("ColdDataCgCtrService started not implemented");
+    pub fn new(
+        broker_config: Arc<BrokerConfig>,
+        message_store_config: Arc<MessageStoreConfig>,
+    ) -> Self {
+        Self {
+            cg_cold_threshold_map_runtime: Arc::new(DashMap::new()),
+            cg_cold_threshold_map_config: Arc::new(DashMap::new()),
+            global_acc: Arc::new(AtomicI64::new(0)),
+            broker_config,
+            message_store_config,
+            shutdown: Arc::new(Notify::new()),
+        }
     }
 
-    pub fn is_cg_need_cold_data_flow_ctr(&self, _consumer_group: &str) -> bool {
-        false
+    pub fn start(&mut self) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let interval = if service.message_store_config.cold_data_flow_control_enable {
+                    ACC_PERIOD_MILLIS
+                } else {
+                    DISABLED_CHECK_INTERVAL_MILLIS
+                };
+                tokio::select! {
+                    _ = tokio::time::sleep(Duration::from_millis(interval)) => {}
+                    _ = service.shutdown.notified() => {
+                        info!("ColdDataCgCtrService: shutdown..........");
+                        break;
+                    }
+                }
+                service.clear_data_acc();
+            }
+        });
     }
 
     pub fn shutdown(&mut self) {
-        warn!("ColdDataCgCtrService shutdown not implemented");
+        self.shutdown.notify_waiters();
+    }
+
+    /// Records `cold_data_to_acc` bytes read from the cold area by `consumer_group`.
+    pub fn cold_acc(&self, consumer_group: &CheetahString, cold_data_to_acc: i64) {
+        if cold_data_to_acc <= 0 {
+            return;
+        }
+        self.global_acc
+            .fetch_add(cold_data_to_acc, Ordering::Relaxed);
+        let now = get_current_millis();
+        let mut acc = self
+            .cg_cold_threshold_map_runtime
+            .entry(consumer_group.clone())
+            .or_insert_with(|| AccAndTimeStamp::new(now));
+        acc.cold_acc += cold_data_to_acc;
+        acc.last_cold_read_time_mills = now;
+    }
+
+    pub fn is_cg_need_cold_data_flow_ctr(&self, consumer_group: &str) -> bool {
+        if !self.message_store_config.cold_data_flow_control_enable {
+            return false;
+        }
+        if mix_all::is_sys_consumer_group_for_no_cold_read_limit(consumer_group) {
+            return false;
+        }
+        let Some(acc) = self.cg_cold_threshold_map_runtime.get(consumer_group) else {
+            return false;
+        };
+        if acc.cold_acc < self.get_threshold_by_consumer_group(consumer_group) {
+            return false;
+        }
+        self.global_acc.load(Ordering::Relaxed) >= self.broker_config.global_cold_read_threshold
+    }
+
+    pub fn add_or_update_group_config(&self, consumer_group: CheetahString, threshold: i64) {
+        self.cg_cold_threshold_map_config
+            .insert(consumer_group, threshold);
+    }
+
+    pub fn remove_group_config(&self, consumer_group: &str) {
+        self.cg_cold_threshold_map_config.remove(consumer_group);
+    }
+
+    /// Returns the current cold-read accounting as a JSON document.
+    pub fn get_cold_data_flow_ctr_info(&self) -> String {
+        let runtime_table = self
+            .cg_cold_threshold_map_runtime
+            .iter()
+            .map(|entry| {
+                (
+                    entry.key().to_string(),
+                    serde_json::to_value(entry.value()).unwrap_or_default(),
+                )
+            })
+            .collect::<serde_json::Map<_, _>>();
+        let config_table = self
+            .cg_cold_threshold_map_config
+            .iter()
+            .map(|entry| (entry.key().to_string(), json!(*entry.value())))
+            .collect::<serde_json::Map<_, _>>();
+        json!({
+            "runtimeTable": runtime_table,
+            "configTable": config_table,
+            "cgColdReadThreshold": self.broker_config.cg_cold_read_threshold,
+            "globalColdReadThreshold": self.broker_config.global_cold_read_threshold,
+            "globalAcc": self.global_acc.load(Ordering::Relaxed),
+        })
+        .to_string()
+    }
+
+    fn get_threshold_by_consumer_group(&self, consumer_group: &str) -> i64 {
+        self.cg_cold_threshold_map_config
+            .get(consumer_group)
+            .map(|threshold| *threshold)
+            .unwrap_or(self.broker_config.cg_cold_read_threshold)
+    }
+
+    fn clear_data_acc(&self) {
+        info!(
+            "clearDataAcc cgColdThresholdMapRuntime key size: {}",
+            self.cg_cold_threshold_map_runtime.len()
+        );
+        let now = get_current_millis();
+        self.cg_cold_threshold_map_runtime.retain(|_, acc| {
+            acc.cold_acc = 0;
+            now.saturating_sub(acc.last_cold_read_time_mills) < ACC_EXPIRED_MILLIS
+        });
+        self.global_acc.store(0, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_service(flow_control_enable: bool) -> ColdDataCgCtrService {
+        let broker_config = BrokerConfig {
+            cg_cold_read_threshold: 100,
+            global_cold_read_threshold: 150,
+            ..Default::default()
+        };
+        let message_store_config = MessageStoreConfig {
+            cold_data_flow_control_enable: flow_control_enable,
+            ..Default::default()
+        };
+        ColdDataCgCtrService::new(Arc::new(broker_config), Arc::new(message_store_config))
+    }
+
+    #[test]
+    fn flow_ctr_requires_group_and_global_threshold() {
+        let service = new_service(true);
+        let slow = CheetahString::from_static_str("slow_group");
+        let hot = CheetahString::from_static_str("hot_group");
+
+        service.cold_acc(&slow, 120);
+        assert!(!service.is_cg_need_cold_data_flow_ctr("slow_group"));
+
+        service.cold_acc(&hot, 40);
+        assert!(service.is_cg_need_cold_data_flow_ctr("slow_group"));
+        assert!(!service.is_cg_need_cold_data_flow_ctr("hot_group"));
+
+        service.add_or_update_group_config(hot.clone(), 10);
+        assert!(service.is_cg_need_cold_data_flow_ctr("hot_group"));
+        service.remove_group_config("hot_group");
+        assert!(!service.is_cg_need_cold_data_flow_ctr("hot_group"));
+
+        service.clear_data_acc();
+        assert!(!service.is_cg_need_cold_data_flow_ctr("slow_group"));
+    }
+
+    #[test]
+    fn flow_ctr_disabled() {
+        let service = new_service(false);
+        let group = CheetahString::from_static_str("group");
+        service.cold_acc(&group, 1_000);
+        assert!(!service.is_cg_need_cold_data_flow_ctr("group"));
+    }
+
+    #[test]
+    fn cold_data_flow_ctr_info() {
+        let service = new_service(true);
+        service.cold_acc(&CheetahString::from_static_str("group"), 42);
+        service.add_or_update_group_config(CheetahString::from_static_str("group"), 7);
+        let info: serde_json::Value =
+            serde_json::from_str(&service.get_cold_data_flow_ctr_info()).unwrap();
+        assert_eq!(info["globalAcc"], 42);
+        assert_eq!(info["runtimeTable"]["group"]["coldAcc"], 42);
+        assert_eq!(info["configTable"]["group"], 7);
+        assert_eq!(info["cgColdReadThreshold"], 100);
+        assert_eq!(info["globalColdReadThreshold"], 150);
     }
 }
@@ -14,17 +14,133 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_rust::ArcMut;
+use rocketmq_store::base::message_store::MessageStore;
+use rocketmq_store::config::message_store_config::MessageStoreConfig;
+use tokio::sync::Notify;
+use tracing::info;
 use tracing::warn;
 
+use crate::long_polling::pull_request::PullRequest;
+use crate::processor::pull_message_processor::PullMessageProcessor;
+
 pub const NO_SUSPEND_KEY: &str = "_noSuspend_";
-#[derive(Default)]
-pub struct ColdDataPullRequestHoldService {}
 
-impl ColdDataPullRequestHoldService {
+const COLD_HOLD_TIMEOUT_MILLIS: u64 = 3_000;
+const COLD_HOLD_QUEUE_CAPACITY: usize = 10_000;
+const CHECK_INTERVAL_MILLIS: u64 = 5_000;
+const DISABLED_CHECK_INTERVAL_MILLIS: u64 = 20_000;
+
+/// Holds pull requests of consumer groups that are flow controlled for reading cold data and
+/// replays them once they have been held for `COLD_HOLD_TIMEOUT_MILLIS`.
+///
+/// Replayed requests carry [`NO_SUSPEND_KEY`] so that they are served (with a single message)
+/// instead of being suspended again.
+pub struct ColdDataPullRequestHoldService<MS: MessageStore> {
+    pull_request_cold_hold_queue: Arc<parking_lot::Mutex<VecDeque<PullRequest>>>,
+    pull_message_processor: ArcMut<PullMessageProcessor<MS>>,
+    message_store_config: Arc<MessageStoreConfig>,
+    shutdown: Arc<Notify>,
+}
+
+impl<MS> ColdDataPullRequestHoldService<MS>
+where
+    MS: MessageStore,
+{
+    pub fn new(
+        pull_message_processor: ArcMut<PullMessageProcessor<MS>>,
+        message_store_config: Arc<MessageStoreConfig>,
+    ) -> Self {
+        Self {
+            pull_request_cold_hold_queue: Arc::new(parking_lot::Mutex::new(VecDeque::new())),
+            pull_message_processor,
+            message_store_config,
+            shutdown: Arc::new(Notify::new()),
+        }
+    }
+
     pub fn start(&mut self) {
-        warn!("ColdDataPullRequestHoldService started not implemented");
+        let queue = self.pull_request_cold_hold_queue.clone();
+        let pull_message_processor = self.pull_message_processor.clone();
+        let message_store_config = self.message_store_config.clone();
+        let shutdown = self.shutdown.clone();
+        tokio::spawn(async move {
+            loop {
+                let interval = if message_store_config.cold_data_flow_control_enable {
+                    CHECK_INTERVAL_MILLIS
+                } else {
+                    DISABLED_CHECK_INTERVAL_MILLIS
+                };
+                tokio::select! {
+                    _ = tokio::time::sleep(Duration::from_millis(interval)) => {}
+                    _ = shutdown.notified() => {
+                        info!("ColdDataPullRequestHoldService: shutdown..........");
+                        break;
+                    }
+                }
+                check_cold_data_pull_request(&queue, &pull_message_processor);
+            }
+        });
     }
+
     pub fn shutdown(&mut self) {
-        warn!("ColdDataPullRequestHoldService shutdown not implemented");
+        self.shutdown.notify_waiters();
+    }
+
+    /// Holds `pull_request` until it is replayed. Requests are dropped when the hold queue is
+    /// full, leaving the client to retry after its pull timeout.
+    pub fn suspend_cold_data_read_request(&self, pull_request: PullRequest) {
+        if !self.message_store_config.cold_data_flow_control_enable {
+            return;
+        }
+        let mut queue = self.pull_request_cold_hold_queue.lock();
+        if queue.len() >= COLD_HOLD_QUEUE_CAPACITY {
+            warn!(
+                "ColdDataPullRequestHoldService hold queue is full, drop pull request, opaque: {}",
+                pull_request.request_command().opaque()
+            );
+            return;
+        }
+        queue.push_back(pull_request);
+    }
+}
+
+fn check_cold_data_pull_request<MS: MessageStore>(
+    queue: &parking_lot::Mutex<VecDeque<PullRequest>>,
+    pull_message_processor: &ArcMut<PullMessageProcessor<MS>>,
+) {
+    let now = get_current_millis();
+    let (expired, queue_size) = {
+        let mut queue = queue.lock();
+        let queue_size = queue.len();
+        let (expired, held): (VecDeque<_>, VecDeque<_>) = queue
+            .drain(..)
+            .partition(|request| now >= request.suspend_timestamp() + COLD_HOLD_TIMEOUT_MILLIS);
+        *queue = held;
+        (expired, queue_size)
+    };
+    let succ_total = expired.len();
+    for mut pull_request in expired {
+        pull_request
+            .request_command_mut()
+            .add_ext_field(NO_SUSPEND_KEY, "1");
+        pull_message_processor.execute_request_when_wakeup(
+            pull_message_processor.clone(),
+            pull_request.client_channel().clone(),
+            pull_request.connection_handler_context().clone(),
+            pull_request.request_command().clone(),
+        );
+    }
+    if queue_size > 0 {
+        info!(
+            "checkColdPullRequest-info-finish, queueSize: {} successTotal: {}",
+            queue_size, succ_total
+        );
     }
 }
@@ -14,4 +14,7 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+pub(crate) mod controller_client;
+#[cfg(test)]
+pub(crate) mod local_controller;
 pub(crate) mod replicas_manager;
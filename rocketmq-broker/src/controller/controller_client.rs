@@ -0,0 +1,258 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::future::Future;
+use std::pin::Pin;
+
+use cheetah_string::CheetahString;
+use parking_lot::Mutex;
+use rocketmq_error::RocketMQResult;
+use rocketmq_error::RocketmqError;
+use rocketmq_remoting::code::response_code::ResponseCode;
+use rocketmq_remoting::protocol::body::elect_master_response_body::ElectMasterResponseBody;
+use rocketmq_remoting::protocol::body::sync_state_set::SyncStateSet;
+use rocketmq_remoting::protocol::header::broker::broker_heartbeat_request_header::BrokerHeartbeatRequestHeader;
+use rocketmq_remoting::protocol::header::controller::alter_sync_state_set_request_header::AlterSyncStateSetRequestHeader;
+use rocketmq_remoting::protocol::header::controller::apply_broker_id_request_header::ApplyBrokerIdRequestHeader;
+use rocketmq_remoting::protocol::header::controller::elect_master_request_header::ElectMasterRequestHeader;
+use rocketmq_remoting::protocol::header::controller::get_next_broker_id_request_header::GetNextBrokerIdRequestHeader;
+use rocketmq_remoting::protocol::header::controller::get_next_broker_id_request_header::GetNextBrokerIdResponseHeader;
+use rocketmq_remoting::protocol::header::controller::get_replica_info_request_header::GetReplicaInfoRequestHeader;
+use rocketmq_remoting::protocol::header::controller::get_replica_info_request_header::GetReplicaInfoResponseHeader;
+use rocketmq_remoting::protocol::header::controller::register_broker_to_controller_request_header::RegisterBrokerToControllerRequestHeader;
+use rocketmq_remoting::protocol::header::controller::register_broker_to_controller_request_header::RegisterBrokerToControllerResponseHeader;
+use rocketmq_remoting::protocol::header::elect_master_response_header::ElectMasterResponseHeader;
+use rocketmq_rust::ArcMut;
+use rocketmq_store::base::message_store::MessageStore;
+use tracing::warn;
+
+use crate::broker_runtime::BrokerRuntimeInner;
+
+pub(crate) type ControllerResult<'a, T> =
+    Pin<Box<dyn Future<Output = RocketMQResult<T>> + Send + 'a>>;
+
+/// The requests a broker sends to the controller in controller mode.
+///
+/// Failures reported by the controller are returned as
+/// [`RocketmqError::MQBrokerError`] carrying the controller response code.
+pub(crate) trait ControllerClient: Send + Sync {
+    fn get_next_broker_id(
+        &self,
+        request: GetNextBrokerIdRequestHeader,
+    ) -> ControllerResult<'_, GetNextBrokerIdResponseHeader>;
+
+    fn apply_broker_id(&self, request: ApplyBrokerIdRequestHeader) -> ControllerResult<'_, ()>;
+
+    fn register_broker_to_controller(
+        &self,
+        request: RegisterBrokerToControllerRequestHeader,
+    ) -> ControllerResult<'_, (RegisterBrokerToControllerResponseHeader, SyncStateSet)>;
+
+    fn get_replica_info(
+        &self,
+        request: GetReplicaInfoRequestHeader,
+    ) -> ControllerResult<'_, (GetReplicaInfoResponseHeader, SyncStateSet)>;
+
+    /// Elects a master, an alive master is returned as is.
+    fn elect_master(
+        &self,
+        request: ElectMasterRequestHeader,
+    ) -> ControllerResult<'_, (ElectMasterResponseHeader, ElectMasterResponseBody)>;
+
+    fn alter_sync_state_set(
+        &self,
+        request: AlterSyncStateSetRequestHeader,
+        sync_state_set: SyncStateSet,
+    ) -> ControllerResult<'_, SyncStateSet>;
+
+    fn send_heartbeat(&self, request: BrokerHeartbeatRequestHeader) -> ControllerResult<'_, ()>;
+}
+
+/// Returns the controller response code carried by `error`, if any.
+pub(crate) fn controller_response_code(error: &RocketmqError) -> Option<ResponseCode> {
+    match error {
+        RocketmqError::MQBrokerError(code, _, _) => Some(ResponseCode::from(*code)),
+        _ => None,
+    }
+}
+
+/// Talks to the controllers listed in `BrokerConfig::controller_addr` through the broker outer
+/// API. Requests go to the controller that answered last and fail over to the others when it
+/// cannot be reached or is no longer the leader.
+pub(crate) struct RemoteControllerClient<MS: MessageStore> {
+    broker_runtime_inner: ArcMut<BrokerRuntimeInner<MS>>,
+    controller_addresses: Vec<CheetahString>,
+    leader_index: Mutex<usize>,
+}
+
+impl<MS: MessageStore> RemoteControllerClient<MS> {
+    pub fn new(broker_runtime_inner: ArcMut<BrokerRuntimeInner<MS>>) -> Self {
+        let controller_addresses = broker_runtime_inner
+            .broker_config()
+            .controller_addr
+            .split(';')
+            .map(str::trim)
+            .filter(|addr| !addr.is_empty())
+            .map(CheetahString::from)
+            .collect();
+        Self {
+            broker_runtime_inner,
+            controller_addresses,
+            leader_index: Mutex::new(0),
+        }
+    }
+
+    async fn invoke<'a, T, F, Fut>(&'a self, call: F) -> RocketMQResult<T>
+    where
+        F: Fn(&'a ArcMut<BrokerRuntimeInner<MS>>, CheetahString) -> Fut,
+        Fut: Future<Output = RocketMQResult<T>> + 'a,
+    {
+        if self.controller_addresses.is_empty() {
+            return Err(RocketmqError::IllegalArgument(
+                "controllerAddr is not configured".to_string(),
+            ));
+        }
+        let start = *self.leader_index.lock();
+        let mut last_error = None;
+        for i in 0..self.controller_addresses.len() {
+            let index = (start + i) % self.controller_addresses.len();
+            let address = self.controller_addresses[index].clone();
+            match call(&self.broker_runtime_inner, address.clone()).await {
+                Ok(value) => {
+                    *self.leader_index.lock() = index;
+                    return Ok(value);
+                }
+                Err(error) => {
+                    let retry = match controller_response_code(&error) {
+                        Some(code) => code == ResponseCode::ControllerNotLeader,
+                        None => true,
+                    };
+                    if !retry {
+                        return Err(error);
+                    }
+                    warn!("request to controller {} failed: {}", address, error);
+                    last_error = Some(error);
+                }
+            }
+        }
+        Err(last_error.unwrap())
+    }
+}
+
+impl<MS: MessageStore> ControllerClient for RemoteControllerClient<MS> {
+    fn get_next_broker_id(
+        &self,
+        request: GetNextBrokerIdRequestHeader,
+    ) -> ControllerResult<'_, GetNextBrokerIdResponseHeader> {
+        Box::pin(self.invoke(move |inner, address| {
+            let request = request.clone();
+            async move {
+                inner
+                    .broker_outer_api()
+                    .get_next_broker_id(&address, request)
+                    .await
+            }
+        }))
+    }
+
+    fn apply_broker_id(&self, request: ApplyBrokerIdRequestHeader) -> ControllerResult<'_, ()> {
+        Box::pin(self.invoke(move |inner, address| {
+            let request = request.clone();
+            async move {
+                inner
+                    .broker_outer_api()
+                    .apply_broker_id(&address, request)
+                    .await
+            }
+        }))
+    }
+
+    fn register_broker_to_controller(
+        &self,
+        request: RegisterBrokerToControllerRequestHeader,
+    ) -> ControllerResult<'_, (RegisterBrokerToControllerResponseHeader, SyncStateSet)> {
+        Box::pin(self.invoke(move |inner, address| {
+            let request = request.clone();
+            async move {
+                inner
+                    .broker_outer_api()
+                    .register_broker_to_controller(&address, request)
+                    .await
+            }
+        }))
+    }
+
+    fn get_replica_info(
+        &self,
+        request: GetReplicaInfoRequestHeader,
+    ) -> ControllerResult<'_, (GetReplicaInfoResponseHeader, SyncStateSet)> {
+        Box::pin(self.invoke(move |inner, address| {
+            let request = request.clone();
+            async move {
+                inner
+                    .broker_outer_api()
+                    .get_replica_info(&address, request)
+                    .await
+            }
+        }))
+    }
+
+    fn elect_master(
+        &self,
+        request: ElectMasterRequestHeader,
+    ) -> ControllerResult<'_, (ElectMasterResponseHeader, ElectMasterResponseBody)> {
+        Box::pin(self.invoke(move |inner, address| {
+            let request = request.clone();
+            async move {
+                inner
+                    .broker_outer_api()
+                    .elect_master(&address, request)
+                    .await
+            }
+        }))
+    }
+
+    fn alter_sync_state_set(
+        &self,
+        request: AlterSyncStateSetRequestHeader,
+        sync_state_set: SyncStateSet,
+    ) -> ControllerResult<'_, SyncStateSet> {
+        Box::pin(self.invoke(move |inner, address| {
+            let request = request.clone();
+            let sync_state_set = sync_state_set.clone();
+            async move {
+                inner
+                    .broker_outer_api()
+                    .alter_sync_state_set(&address, request, sync_state_set)
+                    .await
+            }
+        }))
+    }
+
+    fn send_heartbeat(&self, request: BrokerHeartbeatRequestHeader) -> ControllerResult<'_, ()> {
+        // Heartbeats are one way, they go to every controller so that a newly elected leader
+        // knows the broker is alive.
+        Box::pin(async move {
+            for address in &self.controller_addresses {
+                self.broker_runtime_inner
+                    .broker_outer_api()
+                    .send_heartbeat_to_controller(address, request.clone(), 3000)
+                    .await;
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,612 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use cheetah_string::CheetahString;
+use parking_lot::Mutex;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_error::RocketMQResult;
+use rocketmq_error::RocketmqError;
+use rocketmq_remoting::code::response_code::ResponseCode;
+use rocketmq_remoting::protocol::body::elect_master_response_body::ElectMasterResponseBody;
+use rocketmq_remoting::protocol::body::sync_state_set::SyncStateSet;
+use rocketmq_remoting::protocol::header::broker::broker_heartbeat_request_header::BrokerHeartbeatRequestHeader;
+use rocketmq_remoting::protocol::header::controller::alter_sync_state_set_request_header::AlterSyncStateSetRequestHeader;
+use rocketmq_remoting::protocol::header::controller::apply_broker_id_request_header::ApplyBrokerIdRequestHeader;
+use rocketmq_remoting::protocol::header::controller::elect_master_request_header::ElectMasterRequestHeader;
+use rocketmq_remoting::protocol::header::controller::get_next_broker_id_request_header::GetNextBrokerIdRequestHeader;
+use rocketmq_remoting::protocol::header::controller::get_next_broker_id_request_header::GetNextBrokerIdResponseHeader;
+use rocketmq_remoting::protocol::header::controller::get_replica_info_request_header::GetReplicaInfoRequestHeader;
+use rocketmq_remoting::protocol::header::controller::get_replica_info_request_header::GetReplicaInfoResponseHeader;
+use rocketmq_remoting::protocol::header::controller::register_broker_to_controller_request_header::RegisterBrokerToControllerRequestHeader;
+use rocketmq_remoting::protocol::header::controller::register_broker_to_controller_request_header::RegisterBrokerToControllerResponseHeader;
+use rocketmq_remoting::protocol::header::elect_master_response_header::ElectMasterResponseHeader;
+
+use crate::controller::controller_client::ControllerClient;
+use crate::controller::controller_client::ControllerResult;
+
+const DEFAULT_HEARTBEAT_TIMEOUT_MILLS: i64 = 10 * 1000;
+
+struct BrokerLiveInfo {
+    epoch: i32,
+    max_offset: i64,
+    election_priority: i32,
+    last_update_timestamp: u64,
+    heartbeat_timeout_mills: i64,
+}
+
+#[derive(Default)]
+struct ReplicaInfo {
+    next_broker_id: i64,
+    broker_addresses: HashMap<i64, CheetahString>,
+    register_check_codes: HashMap<i64, CheetahString>,
+    master_broker_id: Option<i64>,
+    master_epoch: i32,
+    sync_state_set: HashSet<i64>,
+    sync_state_set_epoch: i32,
+}
+
+impl ReplicaInfo {
+    fn master_address(&self) -> Option<CheetahString> {
+        self.master_broker_id
+            .and_then(|id| self.broker_addresses.get(&id).cloned())
+    }
+
+    fn sync_state_set(&self) -> SyncStateSet {
+        SyncStateSet::new(self.sync_state_set.clone(), self.sync_state_set_epoch)
+    }
+}
+
+#[derive(Default)]
+struct ControllerState {
+    replicas: HashMap<CheetahString, ReplicaInfo>,
+    live_brokers: HashMap<(CheetahString, i64), BrokerLiveInfo>,
+}
+
+impl ControllerState {
+    fn is_alive(&self, broker_name: &CheetahString, broker_id: i64) -> bool {
+        self.live_brokers
+            .get(&(broker_name.clone(), broker_id))
+            .is_some_and(|info| {
+                get_current_millis() as i64 - (info.last_update_timestamp as i64)
+                    < info.heartbeat_timeout_mills
+            })
+    }
+
+    /// Picks the new master among the alive candidates, preferring the ones with the newest
+    /// epoch, then the largest offset, then the lowest election priority.
+    fn choose_master(
+        &self,
+        broker_name: &CheetahString,
+        candidates: &HashSet<i64>,
+        old_master: Option<i64>,
+        prefer: Option<i64>,
+    ) -> Option<i64> {
+        let valid: Vec<i64> = candidates
+            .iter()
+            .copied()
+            .filter(|id| self.is_alive(broker_name, *id))
+            .collect();
+        if let Some(prefer) = prefer {
+            return valid.contains(&prefer).then_some(prefer);
+        }
+        if let Some(old_master) = old_master {
+            if valid.contains(&old_master) {
+                return Some(old_master);
+            }
+        }
+        valid.into_iter().max_by(|a, b| {
+            let a_info = &self.live_brokers[&(broker_name.clone(), *a)];
+            let b_info = &self.live_brokers[&(broker_name.clone(), *b)];
+            a_info
+                .epoch
+                .cmp(&b_info.epoch)
+                .then(a_info.max_offset.cmp(&b_info.max_offset))
+                .then(b_info.election_priority.cmp(&a_info.election_priority))
+                .then(b.cmp(a))
+        })
+    }
+}
+
+/// An in-process controller, it keeps the replica metadata of every broker group in memory and
+/// elects masters like the controller of a cluster would.
+pub(crate) struct LocalController {
+    enable_elect_unclean_master: bool,
+    state: Mutex<ControllerState>,
+}
+
+impl LocalController {
+    pub fn new(enable_elect_unclean_master: bool) -> Self {
+        Self {
+            enable_elect_unclean_master,
+            state: Mutex::new(ControllerState::default()),
+        }
+    }
+
+    /// Marks `broker_id` as dead, as if its heartbeats had timed out.
+    pub fn expire_broker(&self, broker_name: &CheetahString, broker_id: i64) {
+        self.state
+            .lock()
+            .live_brokers
+            .remove(&(broker_name.clone(), broker_id));
+    }
+
+    /// Elects a new master for every group whose master is no longer alive.
+    pub fn scan_inactive_masters(&self) {
+        let broker_names: Vec<CheetahString> = {
+            let state = self.state.lock();
+            state
+                .replicas
+                .iter()
+                .filter(|(broker_name, replica)| {
+                    replica
+                        .master_broker_id
+                        .is_some_and(|master| !state.is_alive(broker_name, master))
+                })
+                .map(|(broker_name, _)| broker_name.clone())
+                .collect()
+        };
+        for broker_name in broker_names {
+            let _ = self.do_elect_master(&broker_name, None, None);
+        }
+    }
+
+    /// `requester` is the broker asking for the election, the first master of a group is the
+    /// first broker that asks.
+    fn do_elect_master(
+        &self,
+        broker_name: &CheetahString,
+        requester: Option<i64>,
+        prefer: Option<i64>,
+    ) -> RocketMQResult<(ElectMasterResponseHeader, ElectMasterResponseBody)> {
+        let mut state = self.state.lock();
+        let Some(replica) = state.replicas.get(broker_name) else {
+            return Err(controller_error(
+                ResponseCode::ControllerBrokerMetadataNotExist,
+                format!("broker {broker_name} is not registered"),
+            ));
+        };
+        let old_master = replica.master_broker_id;
+        let first_time_for_elect = replica.master_epoch == 0;
+        let mut new_master = match requester {
+            Some(requester) if first_time_for_elect => {
+                state.choose_master(broker_name, &HashSet::from([requester]), None, prefer)
+            }
+            _ => state.choose_master(broker_name, &replica.sync_state_set, old_master, prefer),
+        };
+        if new_master.is_none() && prefer.is_none() && self.enable_elect_unclean_master {
+            let all_replicas = replica.broker_addresses.keys().copied().collect();
+            new_master = state.choose_master(broker_name, &all_replicas, None, None);
+        }
+        let replica = state.replicas.get_mut(broker_name).unwrap();
+        let still_exist = new_master.is_some() && new_master == old_master;
+        match new_master {
+            Some(new_master) => {
+                if !still_exist {
+                    replica.master_broker_id = Some(new_master);
+                    replica.master_epoch += 1;
+                    replica.sync_state_set = HashSet::from([new_master]);
+                    replica.sync_state_set_epoch += 1;
+                }
+            }
+            None => {
+                replica.master_broker_id = None;
+                return Err(controller_error(
+                    ResponseCode::ControllerMasterNotAvailable,
+                    format!("no alive broker of {broker_name} can be elected as master"),
+                ));
+            }
+        }
+        let header = ElectMasterResponseHeader {
+            master_broker_id: replica.master_broker_id,
+            master_address: replica.master_address(),
+            master_epoch: Some(replica.master_epoch),
+            sync_state_set_epoch: Some(replica.sync_state_set_epoch),
+        };
+        let body = ElectMasterResponseBody {
+            broker_member_group: None,
+            sync_state_set: replica.sync_state_set.clone(),
+        };
+        Ok((header, body))
+    }
+}
+
+fn controller_error(code: ResponseCode, remark: String) -> RocketmqError {
+    RocketmqError::MQBrokerError(i32::from(code), remark, "local-controller".to_string())
+}
+
+impl ControllerClient for LocalController {
+    fn get_next_broker_id(
+        &self,
+        request: GetNextBrokerIdRequestHeader,
+    ) -> ControllerResult<'_, GetNextBrokerIdResponseHeader> {
+        let mut state = self.state.lock();
+        let replica = state
+            .replicas
+            .entry(request.broker_name.clone())
+            .or_insert_with(|| ReplicaInfo {
+                next_broker_id: 1,
+                ..Default::default()
+            });
+        let response = GetNextBrokerIdResponseHeader {
+            cluster_name: Some(request.cluster_name),
+            broker_name: Some(request.broker_name),
+            next_broker_id: Some(replica.next_broker_id),
+        };
+        Box::pin(async move { Ok(response) })
+    }
+
+    fn apply_broker_id(&self, request: ApplyBrokerIdRequestHeader) -> ControllerResult<'_, ()> {
+        let mut state = self.state.lock();
+        let replica = state
+            .replicas
+            .entry(request.broker_name.clone())
+            .or_insert_with(|| ReplicaInfo {
+                next_broker_id: 1,
+                ..Default::default()
+            });
+        let result = match replica.register_check_codes.get(&request.applied_broker_id) {
+            Some(check_code) if *check_code == request.register_check_code => Ok(()),
+            Some(_) => Err(controller_error(
+                ResponseCode::ControllerBrokerIdInvalid,
+                format!("broker id {} has been applied", request.applied_broker_id),
+            )),
+            None if request.applied_broker_id == replica.next_broker_id => {
+                replica
+                    .register_check_codes
+                    .insert(request.applied_broker_id, request.register_check_code);
+                replica.next_broker_id += 1;
+                Ok(())
+            }
+            None => Err(controller_error(
+                ResponseCode::ControllerBrokerIdInvalid,
+                format!(
+                    "broker id {} is not the next broker id {}",
+                    request.applied_broker_id, replica.next_broker_id
+                ),
+            )),
+        };
+        Box::pin(async move { result })
+    }
+
+    fn register_broker_to_controller(
+        &self,
+        request: RegisterBrokerToControllerRequestHeader,
+    ) -> ControllerResult<'_, (RegisterBrokerToControllerResponseHeader, SyncStateSet)> {
+        let mut state = self.state.lock();
+        let result = match state.replicas.get_mut(&request.broker_name) {
+            Some(replica)
+                if replica
+                    .register_check_codes
+                    .contains_key(&request.broker_id) =>
+            {
+                replica
+                    .broker_addresses
+                    .insert(request.broker_id, request.broker_address);
+                let header = RegisterBrokerToControllerResponseHeader {
+                    cluster_name: Some(request.cluster_name),
+                    broker_name: Some(request.broker_name),
+                    master_broker_id: replica.master_broker_id,
+                    master_address: replica.master_address(),
+                    master_epoch: Some(replica.master_epoch),
+                    sync_state_set_epoch: Some(replica.sync_state_set_epoch),
+                };
+                Ok((header, replica.sync_state_set()))
+            }
+            _ => Err(controller_error(
+                ResponseCode::ControllerBrokerMetadataNotExist,
+                format!("broker id {} has not been applied", request.broker_id),
+            )),
+        };
+        Box::pin(async move { result })
+    }
+
+    fn get_replica_info(
+        &self,
+        request: GetReplicaInfoRequestHeader,
+    ) -> ControllerResult<'_, (GetReplicaInfoResponseHeader, SyncStateSet)> {
+        self.scan_inactive_masters();
+        let state = self.state.lock();
+        let result = match state.replicas.get(&request.broker_name) {
+            Some(replica) => Ok((
+                GetReplicaInfoResponseHeader {
+                    master_broker_id: replica.master_broker_id,
+                    master_address: replica.master_address(),
+                    master_epoch: Some(replica.master_epoch),
+                },
+                replica.sync_state_set(),
+            )),
+            None => Err(controller_error(
+                ResponseCode::ControllerBrokerMetadataNotExist,
+                format!("broker {} is not registered", request.broker_name),
+            )),
+        };
+        Box::pin(async move { result })
+    }
+
+    fn elect_master(
+        &self,
+        request: ElectMasterRequestHeader,
+    ) -> ControllerResult<'_, (ElectMasterResponseHeader, ElectMasterResponseBody)> {
+        let prefer = request.designate_elect.then_some(request.broker_id);
+        let result = self.do_elect_master(&request.broker_name, Some(request.broker_id), prefer);
+        Box::pin(async move { result })
+    }
+
+    fn alter_sync_state_set(
+        &self,
+        request: AlterSyncStateSetRequestHeader,
+        sync_state_set: SyncStateSet,
+    ) -> ControllerResult<'_, SyncStateSet> {
+        let mut state = self.state.lock();
+        let all_alive = sync_state_set
+            .sync_state_set
+            .iter()
+            .all(|id| state.is_alive(&request.broker_name, *id));
+        let result = match state.replicas.get_mut(&request.broker_name) {
+            None => Err(controller_error(
+                ResponseCode::ControllerBrokerMetadataNotExist,
+                format!("broker {} is not registered", request.broker_name),
+            )),
+            Some(replica)
+                if replica.master_broker_id != Some(request.master_broker_id)
+                    || replica.master_epoch != request.master_epoch =>
+            {
+                Err(controller_error(
+                    ResponseCode::ControllerFencedMasterEpoch,
+                    format!(
+                        "broker {} is not the master of epoch {}",
+                        request.master_broker_id, request.master_epoch
+                    ),
+                ))
+            }
+            Some(replica)
+                if replica.sync_state_set_epoch != sync_state_set.sync_state_set_epoch =>
+            {
+                Err(controller_error(
+                    ResponseCode::ControllerFencedSyncStateSetEpoch,
+                    format!(
+                        "sync state set epoch {} is not {}",
+                        sync_state_set.sync_state_set_epoch, replica.sync_state_set_epoch
+                    ),
+                ))
+            }
+            Some(_)
+                if !all_alive
+                    || !sync_state_set
+                        .sync_state_set
+                        .contains(&request.master_broker_id) =>
+            {
+                Err(controller_error(
+                    ResponseCode::ControllerInvalidReplicas,
+                    "sync state set must contain the master and alive brokers only".to_string(),
+                ))
+            }
+            Some(replica) => {
+                replica.sync_state_set = sync_state_set.sync_state_set;
+                replica.sync_state_set_epoch += 1;
+                Ok(replica.sync_state_set())
+            }
+        };
+        Box::pin(async move { result })
+    }
+
+    fn send_heartbeat(&self, request: BrokerHeartbeatRequestHeader) -> ControllerResult<'_, ()> {
+        if let Some(broker_id) = request.broker_id {
+            self.state.lock().live_brokers.insert(
+                (request.broker_name, broker_id),
+                BrokerLiveInfo {
+                    epoch: request.epoch.unwrap_or_default(),
+                    max_offset: request.max_offset.unwrap_or_default(),
+                    election_priority: request.election_priority.unwrap_or(i32::MAX),
+                    last_update_timestamp: get_current_millis(),
+                    heartbeat_timeout_mills: request
+                        .heartbeat_timeout_mills
+                        .unwrap_or(DEFAULT_HEARTBEAT_TIMEOUT_MILLS),
+                },
+            );
+        }
+        Box::pin(async { Ok(()) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn join(controller: &LocalController, broker_id: i64, epoch: i32, max_offset: i64) {
+        let broker_name = CheetahString::from_static_str("broker-a");
+        let next = controller
+            .get_next_broker_id(GetNextBrokerIdRequestHeader {
+                cluster_name: "cluster".into(),
+                broker_name: broker_name.clone(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(next.next_broker_id, Some(broker_id));
+        controller
+            .apply_broker_id(ApplyBrokerIdRequestHeader {
+                cluster_name: "cluster".into(),
+                broker_name: broker_name.clone(),
+                applied_broker_id: broker_id,
+                register_check_code: format!("127.0.0.1:{broker_id};0").into(),
+            })
+            .await
+            .unwrap();
+        controller
+            .register_broker_to_controller(RegisterBrokerToControllerRequestHeader {
+                cluster_name: "cluster".into(),
+                broker_name: broker_name.clone(),
+                broker_id,
+                broker_address: format!("127.0.0.1:{broker_id}").into(),
+                invoke_time: None,
+            })
+            .await
+            .unwrap();
+        heartbeat(controller, broker_id, epoch, max_offset).await;
+    }
+
+    async fn heartbeat(controller: &LocalController, broker_id: i64, epoch: i32, max_offset: i64) {
+        controller
+            .send_heartbeat(BrokerHeartbeatRequestHeader {
+                cluster_name: "cluster".into(),
+                broker_addr: format!("127.0.0.1:{broker_id}").into(),
+                broker_name: "broker-a".into(),
+                broker_id: Some(broker_id),
+                epoch: Some(epoch),
+                max_offset: Some(max_offset),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+    }
+
+    fn elect_request(broker_id: i64) -> ElectMasterRequestHeader {
+        ElectMasterRequestHeader::new("cluster", "broker-a", broker_id)
+    }
+
+    #[tokio::test]
+    async fn first_elected_master_is_kept_while_alive() {
+        let controller = LocalController::new(false);
+        join(&controller, 1, 0, 0).await;
+        join(&controller, 2, 0, 0).await;
+
+        let (header, body) = controller.elect_master(elect_request(2)).await.unwrap();
+        assert_eq!(header.master_broker_id, Some(2));
+        assert_eq!(header.master_epoch, Some(1));
+        assert_eq!(body.sync_state_set, HashSet::from([2]));
+
+        let (header, _) = controller.elect_master(elect_request(1)).await.unwrap();
+        assert_eq!(header.master_broker_id, Some(2));
+        assert_eq!(header.master_epoch, Some(1));
+    }
+
+    #[tokio::test]
+    async fn applying_a_taken_broker_id_fails() {
+        let controller = LocalController::new(false);
+        join(&controller, 1, 0, 0).await;
+        let error = controller
+            .apply_broker_id(ApplyBrokerIdRequestHeader {
+                cluster_name: "cluster".into(),
+                broker_name: "broker-a".into(),
+                applied_broker_id: 1,
+                register_check_code: "127.0.0.1:9;0".into(),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            RocketmqError::MQBrokerError(code, _, _)
+                if code == i32::from(ResponseCode::ControllerBrokerIdInvalid)
+        ));
+    }
+
+    #[tokio::test]
+    async fn dead_master_is_replaced_by_in_sync_replica() {
+        let controller = LocalController::new(false);
+        join(&controller, 1, 0, 0).await;
+        join(&controller, 2, 0, 0).await;
+        join(&controller, 3, 0, 0).await;
+        controller
+            .elect_master(ElectMasterRequestHeader {
+                designate_elect: true,
+                ..elect_request(1)
+            })
+            .await
+            .unwrap();
+        controller
+            .alter_sync_state_set(
+                AlterSyncStateSetRequestHeader {
+                    broker_name: "broker-a".into(),
+                    master_broker_id: 1,
+                    master_epoch: 1,
+                    invoke_time: None,
+                },
+                SyncStateSet::new(HashSet::from([1, 3]), 1),
+            )
+            .await
+            .unwrap();
+
+        // Broker 2 has more data but is not in sync, so broker 3 takes over.
+        heartbeat(&controller, 2, 1, 200).await;
+        heartbeat(&controller, 3, 1, 100).await;
+        controller.expire_broker(&"broker-a".into(), 1);
+        let (header, sync_state_set) = controller
+            .get_replica_info(GetReplicaInfoRequestHeader {
+                broker_name: "broker-a".into(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(header.master_broker_id, Some(3));
+        assert_eq!(header.master_epoch, Some(2));
+        assert_eq!(header.master_address, Some("127.0.0.1:3".into()));
+        assert_eq!(sync_state_set, SyncStateSet::new(HashSet::from([3]), 3));
+    }
+
+    #[tokio::test]
+    async fn unclean_election_picks_the_replica_with_most_data() {
+        let controller = LocalController::new(true);
+        join(&controller, 1, 0, 0).await;
+        join(&controller, 2, 1, 50).await;
+        join(&controller, 3, 1, 80).await;
+        controller
+            .elect_master(ElectMasterRequestHeader {
+                designate_elect: true,
+                ..elect_request(1)
+            })
+            .await
+            .unwrap();
+        controller.expire_broker(&"broker-a".into(), 1);
+
+        let (header, _) = controller.elect_master(elect_request(2)).await.unwrap();
+        assert_eq!(header.master_broker_id, Some(3));
+    }
+
+    #[tokio::test]
+    async fn stale_sync_state_set_epoch_is_fenced() {
+        let controller = LocalController::new(false);
+        join(&controller, 1, 0, 0).await;
+        join(&controller, 2, 0, 0).await;
+        let (header, _) = controller
+            .elect_master(ElectMasterRequestHeader {
+                designate_elect: true,
+                ..elect_request(1)
+            })
+            .await
+            .unwrap();
+        let request = AlterSyncStateSetRequestHeader {
+            broker_name: "broker-a".into(),
+            master_broker_id: 1,
+            master_epoch: header.master_epoch.unwrap(),
+            invoke_time: None,
+        };
+        controller
+            .alter_sync_state_set(request.clone(), SyncStateSet::new(HashSet::from([1, 2]), 1))
+            .await
+            .unwrap();
+        let error = controller
+            .alter_sync_state_set(request, SyncStateSet::new(HashSet::from([1]), 1))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            RocketmqError::MQBrokerError(code, _, _)
+                if code == i32::from(ResponseCode::ControllerFencedSyncStateSetEpoch)
+        ));
+    }
+}
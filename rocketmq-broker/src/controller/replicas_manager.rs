@@ -14,17 +14,769 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::collections::HashSet;
+use std::sync::atomic::AtomicI64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use cheetah_string::CheetahString;
+use parking_lot::Mutex;
+use rocketmq_common::common::broker::broker_config::BrokerConfig;
+use rocketmq_common::common::broker::broker_role::BrokerRole;
+use rocketmq_common::common::mix_all;
+use rocketmq_common::FileUtils;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_error::RocketMQResult;
+use rocketmq_remoting::protocol::body::sync_state_set::SyncStateSet;
+use rocketmq_remoting::protocol::header::broker::broker_heartbeat_request_header::BrokerHeartbeatRequestHeader;
+use rocketmq_remoting::protocol::header::controller::alter_sync_state_set_request_header::AlterSyncStateSetRequestHeader;
+use rocketmq_remoting::protocol::header::controller::apply_broker_id_request_header::ApplyBrokerIdRequestHeader;
+use rocketmq_remoting::protocol::header::controller::elect_master_request_header::ElectMasterRequestHeader;
+use rocketmq_remoting::protocol::header::controller::get_next_broker_id_request_header::GetNextBrokerIdRequestHeader;
+use rocketmq_remoting::protocol::header::controller::get_replica_info_request_header::GetReplicaInfoRequestHeader;
+use rocketmq_remoting::protocol::header::controller::register_broker_to_controller_request_header::RegisterBrokerToControllerRequestHeader;
+use rocketmq_rust::ArcMut;
+use rocketmq_store::base::message_store::MessageStore;
+use rocketmq_store::ha::auto_switch::auto_switch_ha_service::AutoSwitchHAService;
+use rocketmq_store::ha::ha_service::HAService;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::sync::watch;
+use tracing::error;
+use tracing::info;
 use tracing::warn;
 
+use crate::broker_path_config_helper::get_broker_identity_path;
+use crate::broker_runtime::BrokerRuntimeInner;
+use crate::controller::controller_client::ControllerClient;
+use crate::controller::controller_client::RemoteControllerClient;
+
+const START_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Initial,
+    BrokerIdApplied,
+    RegisterToControllerDone,
+    Running,
+    Shutdown,
+}
+
+/// What a broker becomes once the controller has told it who the master is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Role {
+    Master,
+    Slave {
+        master_broker_id: i64,
+        master_address: CheetahString,
+    },
+}
+
+impl Role {
+    fn of(
+        local_broker_id: i64,
+        master_broker_id: Option<i64>,
+        master_address: Option<CheetahString>,
+    ) -> Option<Self> {
+        let master_broker_id = master_broker_id?;
+        if master_broker_id == local_broker_id {
+            return Some(Role::Master);
+        }
+        match master_address {
+            Some(master_address) if !master_address.is_empty() => Some(Role::Slave {
+                master_broker_id,
+                master_address,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// The broker id applied from the controller, kept so that a restarted broker comes back with
+/// the same identity.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BrokerMetadata {
+    cluster_name: CheetahString,
+    broker_name: CheetahString,
+    broker_id: i64,
+    register_check_code: CheetahString,
+}
+
+impl BrokerMetadata {
+    fn load(path: &str) -> Option<Self> {
+        let content = FileUtils::file_to_string(path).ok()?;
+        if content.is_empty() {
+            return None;
+        }
+        serde_json::from_str(&content)
+            .inspect_err(|e| error!("parse broker metadata {} failed: {}", path, e))
+            .ok()
+    }
+
+    fn persist(&self, path: &str) -> RocketMQResult<()> {
+        let content = serde_json::to_string(self)
+            .map_err(|e| rocketmq_error::RocketmqError::JsonError(e.to_string()))?;
+        FileUtils::string_to_file(&content, path)?;
+        Ok(())
+    }
+}
+
 #[derive(Default)]
-pub struct ReplicasManager {}
+struct ReplicaState {
+    master_broker_id: Option<i64>,
+    master_address: CheetahString,
+    master_epoch: i32,
+    sync_state_set: HashSet<i64>,
+    sync_state_set_epoch: i32,
+}
+
+/// Drives a broker in controller mode: it registers the broker to the controller, follows the
+/// master elected by the controller, and reports changes of the in-sync replicas while it is
+/// the master.
+pub struct ReplicasManager<MS: MessageStore> {
+    inner: Arc<ReplicasManagerInner<MS>>,
+}
+
+struct ReplicasManagerInner<MS: MessageStore> {
+    broker_runtime_inner: ArcMut<BrokerRuntimeInner<MS>>,
+    controller_client: Arc<dyn ControllerClient>,
+    state: Mutex<State>,
+    broker_controller_id: AtomicI64,
+    replica_state: tokio::sync::Mutex<ReplicaState>,
+    shutdown: watch::Sender<bool>,
+}
+
+impl<MS: MessageStore> ReplicasManager<MS> {
+    pub fn new(broker_runtime_inner: ArcMut<BrokerRuntimeInner<MS>>) -> Self {
+        let controller_client = Arc::new(RemoteControllerClient::new(broker_runtime_inner.clone()));
+        let (shutdown, _) = watch::channel(false);
+        Self {
+            inner: Arc::new(ReplicasManagerInner {
+                broker_runtime_inner,
+                controller_client,
+                state: Mutex::new(State::Initial),
+                broker_controller_id: AtomicI64::new(-1),
+                replica_state: tokio::sync::Mutex::new(ReplicaState::default()),
+                shutdown,
+            }),
+        }
+    }
 
-impl ReplicasManager {
     pub fn start(&mut self) {
-        warn!("ReplicasManager started not implemented");
+        let inner = self.inner.clone();
+        if let Some(ha_service) = inner.auto_switch_ha_service() {
+            let weak = Arc::downgrade(&inner);
+            ha_service.register_sync_state_set_changed_listener(Arc::new(move |sync_state_set| {
+                if let Some(inner) = weak.upgrade() {
+                    tokio::spawn(async move {
+                        inner.report_sync_state_set_changed(sync_state_set).await;
+                    });
+                }
+            }));
+        }
+        tokio::spawn(async move {
+            let mut shutdown = inner.shutdown.subscribe();
+            while !inner.start_basic_service().await {
+                warn!(
+                    "Failed to start replicas manager, retry after {:?}",
+                    START_RETRY_INTERVAL
+                );
+                tokio::select! {
+                    _ = tokio::time::sleep(START_RETRY_INTERVAL) => {}
+                    _ = shutdown.changed() => return,
+                }
+            }
+            inner.schedule_sync_broker_metadata();
+            inner.schedule_check_sync_state_set();
+        });
     }
 
     pub fn shutdown(&mut self) {
-        warn!("ReplicasManager shutdown not implemented");
+        *self.inner.state.lock() = State::Shutdown;
+        let _ = self.inner.shutdown.send(true);
+    }
+
+    /// Sends the broker heartbeat to the controllers, it carries what the controller needs to
+    /// pick the next master.
+    pub async fn send_heartbeat_to_controller(&self) {
+        self.inner.send_heartbeat_to_controller().await;
+    }
+}
+
+impl<MS: MessageStore> ReplicasManagerInner<MS> {
+    fn broker_config(&self) -> &BrokerConfig {
+        self.broker_runtime_inner.broker_config()
+    }
+
+    fn broker_controller_id(&self) -> i64 {
+        self.broker_controller_id.load(Ordering::Acquire)
+    }
+
+    fn auto_switch_ha_service(&self) -> Option<&AutoSwitchHAService> {
+        self.broker_runtime_inner
+            .message_store()
+            .as_ref()?
+            .get_ha_service()?
+            .get_auto_switch_ha_service()
+    }
+
+    async fn start_basic_service(&self) -> bool {
+        let state = *self.state.lock();
+        match state {
+            State::Shutdown => return true,
+            State::Initial => {
+                if !self.apply_broker_id().await {
+                    return false;
+                }
+                *self.state.lock() = State::BrokerIdApplied;
+            }
+            _ => {}
+        }
+        if *self.state.lock() == State::BrokerIdApplied {
+            if !self.register_broker_to_controller().await {
+                return false;
+            }
+            *self.state.lock() = State::RegisterToControllerDone;
+        }
+        if *self.state.lock() == State::RegisterToControllerDone {
+            self.send_heartbeat_to_controller().await;
+            let has_master = self.replica_state.lock().await.master_broker_id.is_some();
+            if !has_master && !self.broker_elect().await {
+                return false;
+            }
+            *self.state.lock() = State::Running;
+        }
+        true
+    }
+
+    /// Restores the broker id from the metadata file, or applies a new one from the controller.
+    async fn apply_broker_id(&self) -> bool {
+        let broker_config = self.broker_config();
+        let cluster_name = broker_config.broker_identity.broker_cluster_name.clone();
+        let broker_name = broker_config.broker_identity.broker_name.clone();
+        let path = get_broker_identity_path(
+            self.broker_runtime_inner
+                .message_store_config()
+                .store_path_root_dir
+                .as_str(),
+        );
+        let metadata = match BrokerMetadata::load(&path) {
+            Some(metadata) => metadata,
+            None => {
+                let next_broker_id = match self
+                    .controller_client
+                    .get_next_broker_id(GetNextBrokerIdRequestHeader {
+                        cluster_name: cluster_name.clone(),
+                        broker_name: broker_name.clone(),
+                    })
+                    .await
+                {
+                    Ok(response) => response.next_broker_id.unwrap_or(-1),
+                    Err(e) => {
+                        error!("Failed to get next broker id from controller: {}", e);
+                        return false;
+                    }
+                };
+                // The check code is written down before applying, applying it again after a
+                // crash is accepted by the controller.
+                let metadata = BrokerMetadata {
+                    cluster_name: cluster_name.clone(),
+                    broker_name: broker_name.clone(),
+                    broker_id: next_broker_id,
+                    register_check_code: CheetahString::from_string(format!(
+                        "{};{}",
+                        self.broker_runtime_inner.get_broker_addr(),
+                        get_current_millis()
+                    )),
+                };
+                if let Err(e) = metadata.persist(&path) {
+                    error!("Failed to persist broker metadata to {}: {}", path, e);
+                    return false;
+                }
+                metadata
+            }
+        };
+        if let Err(e) = self
+            .controller_client
+            .apply_broker_id(ApplyBrokerIdRequestHeader {
+                cluster_name,
+                broker_name,
+                applied_broker_id: metadata.broker_id,
+                register_check_code: metadata.register_check_code,
+            })
+            .await
+        {
+            error!(
+                "Failed to apply broker id {} from controller: {}",
+                metadata.broker_id, e
+            );
+            return false;
+        }
+        self.broker_controller_id
+            .store(metadata.broker_id, Ordering::Release);
+        if let Some(ha_service) = self.auto_switch_ha_service() {
+            ha_service.set_local_broker_id(metadata.broker_id);
+        }
+        info!("Broker id {} applied from controller", metadata.broker_id);
+        true
+    }
+
+    async fn register_broker_to_controller(&self) -> bool {
+        let broker_config = self.broker_config();
+        let request = RegisterBrokerToControllerRequestHeader {
+            cluster_name: broker_config.broker_identity.broker_cluster_name.clone(),
+            broker_name: broker_config.broker_identity.broker_name.clone(),
+            broker_id: self.broker_controller_id(),
+            broker_address: self.broker_runtime_inner.get_broker_addr().clone(),
+            invoke_time: Some(get_current_millis()),
+        };
+        let (response, sync_state_set) = match self
+            .controller_client
+            .register_broker_to_controller(request)
+            .await
+        {
+            Ok(result) => result,
+            Err(e) => {
+                error!("Failed to register broker to controller: {}", e);
+                return false;
+            }
+        };
+        match Role::of(
+            self.broker_controller_id(),
+            response.master_broker_id,
+            response.master_address,
+        ) {
+            Some(role) => {
+                self.change_role(
+                    role,
+                    response.master_epoch.unwrap_or_default(),
+                    sync_state_set,
+                )
+                .await
+            }
+            None => true,
+        }
+    }
+
+    /// Asks the controller to elect a master and follows the result.
+    async fn broker_elect(&self) -> bool {
+        let broker_config = self.broker_config();
+        let request = ElectMasterRequestHeader::new(
+            broker_config.broker_identity.broker_cluster_name.clone(),
+            broker_config.broker_identity.broker_name.clone(),
+            self.broker_controller_id(),
+        );
+        let (response, body) = match self.controller_client.elect_master(request).await {
+            Ok(result) => result,
+            Err(e) => {
+                error!("Failed to elect master through controller: {}", e);
+                return false;
+            }
+        };
+        let Some(role) = Role::of(
+            self.broker_controller_id(),
+            response.master_broker_id,
+            response.master_address,
+        ) else {
+            warn!("Now no master in broker set");
+            return false;
+        };
+        let sync_state_set = SyncStateSet::new(
+            body.sync_state_set,
+            response.sync_state_set_epoch.unwrap_or_default(),
+        );
+        self.change_role(
+            role,
+            response.master_epoch.unwrap_or_default(),
+            sync_state_set,
+        )
+        .await
+    }
+
+    async fn change_role(
+        &self,
+        role: Role,
+        master_epoch: i32,
+        sync_state_set: SyncStateSet,
+    ) -> bool {
+        match role {
+            Role::Master => self.change_to_master(master_epoch, sync_state_set).await,
+            Role::Slave {
+                master_broker_id,
+                master_address,
+            } => {
+                self.change_to_slave(master_address, master_epoch, master_broker_id)
+                    .await
+            }
+        }
+    }
+
+    async fn change_to_master(&self, new_master_epoch: i32, sync_state_set: SyncStateSet) -> bool {
+        let mut replica_state = self.replica_state.lock().await;
+        if new_master_epoch <= replica_state.master_epoch {
+            return true;
+        }
+        info!(
+            "Begin to change to master, brokerName:{}, replicas:{}, new Epoch:{}",
+            self.broker_config().broker_identity.broker_name,
+            self.broker_runtime_inner.get_broker_addr(),
+            new_master_epoch
+        );
+        self.change_sync_state_set(&mut replica_state, sync_state_set);
+        let Some(ha_service) = self
+            .broker_runtime_inner
+            .message_store()
+            .as_ref()
+            .and_then(|message_store| message_store.get_ha_service())
+        else {
+            error!("Message store has no HA service, can not change to master");
+            return false;
+        };
+        match ha_service.change_to_master(new_master_epoch).await {
+            Ok(true) => {}
+            Ok(false) => {
+                error!(
+                    "Failed to change HA service to master, epoch {}",
+                    new_master_epoch
+                );
+                return false;
+            }
+            Err(e) => {
+                error!("Failed to change HA service to master: {}", e);
+                return false;
+            }
+        }
+
+        let mut broker_config = self.broker_config().clone();
+        broker_config.broker_identity.broker_id = mix_all::MASTER_ID;
+        let mut message_store_config = self.broker_runtime_inner.message_store_config().clone();
+        message_store_config.broker_role = BrokerRole::SyncMaster;
+        let broker_runtime_inner = self.broker_runtime_inner.mut_from_ref();
+        broker_runtime_inner.set_broker_config(broker_config);
+        broker_runtime_inner.set_message_store_config(message_store_config);
+        broker_runtime_inner
+            .change_special_service_status(true)
+            .await;
+
+        replica_state.master_address = self.broker_runtime_inner.get_broker_addr().clone();
+        replica_state.master_broker_id = Some(self.broker_controller_id());
+        replica_state.master_epoch = new_master_epoch;
+        drop(replica_state);
+
+        self.register_broker_to_name_server().await;
+        info!(
+            "Change broker {} to master success, masterEpoch {}",
+            self.broker_controller_id(),
+            new_master_epoch
+        );
+        true
+    }
+
+    async fn change_to_slave(
+        &self,
+        new_master_address: CheetahString,
+        new_master_epoch: i32,
+        new_master_broker_id: i64,
+    ) -> bool {
+        let mut replica_state = self.replica_state.lock().await;
+        if new_master_epoch <= replica_state.master_epoch {
+            return true;
+        }
+        info!(
+            "Begin to change to slave, brokerName={}, brokerId={}, newMasterBrokerId={}, \
+             newMasterAddress={}, newMasterEpoch={}",
+            self.broker_config().broker_identity.broker_name,
+            self.broker_controller_id(),
+            new_master_broker_id,
+            new_master_address,
+            new_master_epoch
+        );
+
+        let mut broker_config = self.broker_config().clone();
+        broker_config.broker_identity.broker_id = self.broker_controller_id() as u64;
+        let mut message_store_config = self.broker_runtime_inner.message_store_config().clone();
+        message_store_config.broker_role = BrokerRole::Slave;
+        let broker_runtime_inner = self.broker_runtime_inner.mut_from_ref();
+        broker_runtime_inner.set_broker_config(broker_config);
+        broker_runtime_inner.set_message_store_config(message_store_config);
+        broker_runtime_inner
+            .change_special_service_status(false)
+            .await;
+
+        replica_state.master_address = new_master_address.clone();
+        replica_state.master_broker_id = Some(new_master_broker_id);
+        replica_state.master_epoch = new_master_epoch;
+        replica_state.sync_state_set.clear();
+
+        let Some(message_store) = self.broker_runtime_inner.message_store().as_ref() else {
+            return false;
+        };
+        let Some(ha_service) = message_store.get_ha_service() else {
+            error!("Message store has no HA service, can not change to slave");
+            return false;
+        };
+        match ha_service
+            .change_to_slave(
+                new_master_address.as_str(),
+                new_master_epoch,
+                Some(self.broker_controller_id()),
+            )
+            .await
+        {
+            Ok(true) => {}
+            Ok(false) => {
+                error!(
+                    "Failed to change HA service to slave, epoch {}",
+                    new_master_epoch
+                );
+                return false;
+            }
+            Err(e) => {
+                error!("Failed to change HA service to slave: {}", e);
+                return false;
+            }
+        }
+        message_store.update_master_address(&new_master_address);
+        drop(replica_state);
+
+        match self
+            .broker_runtime_inner
+            .broker_outer_api()
+            .retrieve_broker_ha_info(&new_master_address)
+            .await
+        {
+            Ok(ha_info) => {
+                if let Some(master_ha_address) = ha_info.master_ha_address {
+                    message_store.update_ha_master_address(&master_ha_address);
+                }
+            }
+            Err(e) => warn!(
+                "Failed to retrieve HA info from master {}: {}",
+                new_master_address, e
+            ),
+        }
+        self.register_broker_to_name_server().await;
+        info!(
+            "Change broker {} to slave, newMasterBrokerId={}, newMasterAddress={}, \
+             newMasterEpoch={}",
+            self.broker_controller_id(),
+            new_master_broker_id,
+            new_master_address,
+            new_master_epoch
+        );
+        true
+    }
+
+    fn change_sync_state_set(
+        &self,
+        replica_state: &mut ReplicaState,
+        sync_state_set: SyncStateSet,
+    ) {
+        if sync_state_set.sync_state_set_epoch <= replica_state.sync_state_set_epoch {
+            return;
+        }
+        info!(
+            "SyncStateSet changed from {:?} to {:?}",
+            replica_state.sync_state_set, sync_state_set.sync_state_set
+        );
+        replica_state.sync_state_set_epoch = sync_state_set.sync_state_set_epoch;
+        replica_state.sync_state_set = sync_state_set.sync_state_set.clone();
+        if let Some(ha_service) = self.auto_switch_ha_service() {
+            ha_service.set_sync_state_set(sync_state_set.sync_state_set);
+        }
+    }
+
+    async fn register_broker_to_name_server(&self) {
+        let this = self.broker_runtime_inner.clone();
+        self.broker_runtime_inner
+            .register_broker_all_inner(this, true, false, true)
+            .await;
+    }
+
+    pub async fn send_heartbeat_to_controller(&self) {
+        let broker_controller_id = self.broker_controller_id();
+        if broker_controller_id < 0 {
+            return;
+        }
+        let Some(message_store) = self.broker_runtime_inner.message_store().as_ref() else {
+            return;
+        };
+        let broker_config = self.broker_config();
+        let request = BrokerHeartbeatRequestHeader {
+            cluster_name: broker_config.broker_identity.broker_cluster_name.clone(),
+            broker_addr: self.broker_runtime_inner.get_broker_addr().clone(),
+            broker_name: broker_config.broker_identity.broker_name.clone(),
+            broker_id: Some(broker_controller_id),
+            epoch: self
+                .auto_switch_ha_service()
+                .map(|ha_service| ha_service.get_last_epoch()),
+            max_offset: Some(message_store.get_max_phy_offset()),
+            confirm_offset: Some(message_store.get_confirm_offset()),
+            heartbeat_timeout_mills: Some(broker_config.controller_heartbeat_timeout_mills as i64),
+            election_priority: Some(broker_config.broker_election_priority),
+        };
+        if let Err(e) = self.controller_client.send_heartbeat(request).await {
+            warn!("Failed to send heartbeat to controller: {}", e);
+        }
+    }
+
+    /// Follows the master recorded by the controller, a broker that missed an election learns
+    /// about it here.
+    async fn sync_broker_metadata(&self) {
+        let request = GetReplicaInfoRequestHeader {
+            broker_name: self.broker_config().broker_identity.broker_name.clone(),
+        };
+        let (info, sync_state_set) = match self.controller_client.get_replica_info(request).await {
+            Ok(result) => result,
+            Err(e) => {
+                warn!("Failed to get replica info from controller: {}", e);
+                return;
+            }
+        };
+        let new_master_epoch = info.master_epoch.unwrap_or_default();
+        let (master_epoch, is_master) = {
+            let replica_state = self.replica_state.lock().await;
+            (
+                replica_state.master_epoch,
+                replica_state.master_broker_id == Some(self.broker_controller_id()),
+            )
+        };
+        if new_master_epoch > master_epoch {
+            match Role::of(
+                self.broker_controller_id(),
+                info.master_broker_id,
+                info.master_address,
+            ) {
+                Some(role) => {
+                    self.change_role(role, new_master_epoch, sync_state_set)
+                        .await;
+                }
+                None => {
+                    self.broker_elect().await;
+                }
+            }
+        } else if new_master_epoch == master_epoch && info.master_broker_id.is_none() {
+            self.broker_elect().await;
+        } else if new_master_epoch == master_epoch && is_master {
+            let mut replica_state = self.replica_state.lock().await;
+            self.change_sync_state_set(&mut replica_state, sync_state_set);
+        }
+    }
+
+    /// Removes the replicas that fell behind from the sync state set while this broker is the
+    /// master.
+    async fn check_sync_state_set(&self) {
+        let Some(ha_service) = self.auto_switch_ha_service() else {
+            return;
+        };
+        if !ha_service.is_master() {
+            return;
+        }
+        let mut new_sync_state_set = ha_service.maybe_shrink_sync_state_set();
+        new_sync_state_set.insert(self.broker_controller_id());
+        {
+            let replica_state = self.replica_state.lock().await;
+            if replica_state.sync_state_set == new_sync_state_set {
+                return;
+            }
+        }
+        self.report_sync_state_set_changed(new_sync_state_set).await;
+    }
+
+    async fn report_sync_state_set_changed(&self, new_sync_state_set: HashSet<i64>) {
+        let mut replica_state = self.replica_state.lock().await;
+        if replica_state.master_broker_id != Some(self.broker_controller_id()) {
+            return;
+        }
+        let request = AlterSyncStateSetRequestHeader {
+            broker_name: self.broker_config().broker_identity.broker_name.clone(),
+            master_broker_id: self.broker_controller_id(),
+            master_epoch: replica_state.master_epoch,
+            invoke_time: Some(get_current_millis()),
+        };
+        let sync_state_set =
+            SyncStateSet::new(new_sync_state_set, replica_state.sync_state_set_epoch);
+        match self
+            .controller_client
+            .alter_sync_state_set(request, sync_state_set)
+            .await
+        {
+            Ok(result) => self.change_sync_state_set(&mut replica_state, result),
+            Err(e) => error!(
+                "Failed to report sync state set change to controller: {}",
+                e
+            ),
+        }
+    }
+
+    fn schedule_sync_broker_metadata(self: &Arc<Self>) {
+        let period = Duration::from_millis(self.broker_config().sync_broker_metadata_period);
+        self.schedule(period, |inner| async move {
+            inner.sync_broker_metadata().await;
+        });
+    }
+
+    fn schedule_check_sync_state_set(self: &Arc<Self>) {
+        let period = Duration::from_millis(self.broker_config().check_sync_state_set_period);
+        self.schedule(period, |inner| async move {
+            inner.check_sync_state_set().await;
+        });
+    }
+
+    fn schedule<F, Fut>(self: &Arc<Self>, period: Duration, task: F)
+    where
+        F: Fn(Arc<Self>) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + Send,
+    {
+        let inner = self.clone();
+        let mut shutdown = self.shutdown.subscribe();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.tick().await;
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => task(inner.clone()).await,
+                    _ = shutdown.changed() => break,
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn role_follows_master_reported_by_controller() {
+        assert_eq!(Role::of(1, Some(1), None), Some(Role::Master));
+        assert_eq!(
+            Role::of(2, Some(1), Some("127.0.0.1:10911".into())),
+            Some(Role::Slave {
+                master_broker_id: 1,
+                master_address: "127.0.0.1:10911".into(),
+            })
+        );
+        assert_eq!(Role::of(2, Some(1), Some(CheetahString::empty())), None);
+        assert_eq!(Role::of(2, None, None), None);
+    }
+
+    #[test]
+    fn broker_metadata_survives_restart() {
+        let dir = std::env::temp_dir().join(format!("broker_metadata_{}", get_current_millis()));
+        let path = get_broker_identity_path(dir.to_str().unwrap());
+        assert_eq!(BrokerMetadata::load(&path), None);
+
+        let metadata = BrokerMetadata {
+            cluster_name: "DefaultCluster".into(),
+            broker_name: "broker-a".into(),
+            broker_id: 3,
+            register_check_code: "127.0.0.1:10911;1".into(),
+        };
+        metadata.persist(&path).unwrap();
+        assert_eq!(BrokerMetadata::load(&path), Some(metadata));
+        let _ = std::fs::remove_dir_all(dir);
     }
 }
@@ -506,8 +506,8 @@ where
                 let status = result1.status();
                 let mut list = decode_msg_list(result1, de_compress_body);
                 if list.is_empty() {
-                    let need_retry = status.unwrap() == GetMessageStatus::OffsetFoundNull;
-                    //   && message_store.is_tiered_message_store();
+                    let need_retry = status.unwrap() == GetMessageStatus::OffsetFoundNull
+                        && message_store.is_tiered_message_store();
                     warn!(
                         "Can not get msg, topic {}, offset {}, queueId {}, needRetry {},",
                         topic,
@@ -614,9 +614,7 @@ fn decode_msg_list(
 ) -> Vec<MessageExt> {
     let mut found_list = Vec::new();
     for bb in get_message_result.message_mapped_list() {
-        let data = &bb.mapped_file.as_ref().unwrap().get_mapped_file()
-            [bb.start_offset as usize..(bb.start_offset + bb.size as u64) as usize];
-        let mut bytes = Bytes::copy_from_slice(data);
+        let mut bytes = Bytes::copy_from_slice(bb.get_buffer());
         let msg_ext =
             message_decoder::decode(&mut bytes, true, de_compress_body, false, false, false);
         if let Some(msg_ext) = msg_ext {
@@ -20,6 +20,7 @@ use std::fmt::Formatter;
 use std::sync::Arc;
 
 use cheetah_string::CheetahString;
+use rocketmq_common::TimeUtils::get_current_millis;
 use rocketmq_filter::expression::Expression;
 use rocketmq_filter::utils::bloom_filter_data::BloomFilterData;
 use serde::Deserialize;
@@ -108,6 +109,30 @@ impl ConsumerFilterData {
     pub fn compiled_expression(&self) -> &Option<Arc<Box<dyn Expression + Send + Sync + 'static>>> {
         &self.compiled_expression
     }
+
+    pub fn set_compiled_expression(
+        &mut self,
+        compiled_expression: Option<Arc<Box<dyn Expression + Send + Sync + 'static>>>,
+    ) {
+        self.compiled_expression = compiled_expression;
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.dead_time >= self.born_time
+    }
+
+    pub fn how_long_after_death(&self) -> u64 {
+        if self.is_dead() {
+            get_current_millis().saturating_sub(self.dead_time)
+        } else {
+            0
+        }
+    }
+
+    /// Check whether the message is stored after this consumer was born.
+    pub fn is_msg_in_live(&self, msg_store_time: u64) -> bool {
+        msg_store_time > self.born_time
+    }
 }
 
 impl Debug for ConsumerFilterData {
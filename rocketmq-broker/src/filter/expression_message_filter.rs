@@ -21,6 +21,7 @@ use bytes::Bytes;
 use cheetah_string::CheetahString;
 use rocketmq_common::common::filter::expression_type::ExpressionType;
 use rocketmq_common::common::message::message_decoder;
+use rocketmq_filter::utils::bits_array::BitsArray;
 use rocketmq_remoting::protocol::heartbeat::subscription_data::SubscriptionData;
 use rocketmq_store::consume_queue::consume_queue_ext::CqExtUnit;
 use rocketmq_store::filter::MessageFilter;
@@ -59,7 +60,6 @@ impl ExpressionMessageFilter {
     }
 }
 
-#[allow(unused_variables)]
 impl MessageFilter for ExpressionMessageFilter {
     fn is_matched_by_consume_queue(
         &self,
@@ -80,12 +80,47 @@ impl MessageFilter for ExpressionMessageFilter {
             if subscription_data.sub_string.as_str() == SubscriptionData::SUB_ALL {
                 return true;
             }
-            subscription_data
+            return subscription_data
                 .code_set
-                .contains(&(tags_code.unwrap() as i32))
-        } else {
-            unimplemented!("SQL92 expression type is not supported yet.")
+                .contains(&(tags_code.unwrap() as i32));
+        }
+
+        // no expression or no bloom
+        let Some(consumer_filter_data) = self.consumer_filter_data.as_ref() else {
+            return true;
+        };
+        if consumer_filter_data.expression().is_none()
+            || consumer_filter_data.compiled_expression().is_none()
+        {
+            return true;
         }
+        let Some(bloom_filter_data) = consumer_filter_data.bloom_filter_data() else {
+            return true;
+        };
+
+        // message is before consumer
+        let Some(cq_ext_unit) = cq_ext_unit else {
+            return true;
+        };
+        if !consumer_filter_data.is_msg_in_live(cq_ext_unit.msg_store_time() as u64) {
+            return true;
+        }
+
+        let Some(filter_bit_map) = cq_ext_unit.filter_bit_map() else {
+            return true;
+        };
+        let Some(bloom_filter) = self.consumer_filter_manager.bloom_filter() else {
+            return true;
+        };
+        if !self.bloom_data_valid
+            || filter_bit_map.is_empty()
+            || filter_bit_map.len() * 8 != bloom_filter_data.bit_num() as usize
+        {
+            return true;
+        }
+
+        let bits_array = BitsArray::from_bytes(filter_bit_map, None);
+        bloom_filter.is_hit(bloom_filter_data, &bits_array)
     }
 
     fn is_matched_by_commit_log(
@@ -115,11 +150,12 @@ impl MessageFilter for ExpressionMessageFilter {
         }
 
         let temp_properties = match (properties, msg_buffer) {
+            (Some(properties), _) => Some(properties.clone()),
             (None, Some(bytes)) => {
                 let mut bytes_ = Bytes::copy_from_slice(bytes);
                 message_decoder::decode_properties(&mut bytes_)
             }
-            _ => None,
+            (None, None) => None,
         };
         let context = MessageEvaluationContext::new(&temp_properties);
         if let Some(filter) = real_filter_data.compiled_expression() {
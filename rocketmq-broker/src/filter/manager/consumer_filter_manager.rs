@@ -15,6 +15,7 @@
  * limitations under the License.
  */
 
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -22,12 +23,19 @@ use cheetah_string::CheetahString;
 use rocketmq_common::common::broker::broker_config::BrokerConfig;
 use rocketmq_common::common::config_manager::ConfigManager;
 use rocketmq_common::common::filter::expression_type::ExpressionType;
+use rocketmq_common::utils::serde_json_utils::SerdeJsonUtils;
 use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_filter::filter_factory::FilterFactory;
 use rocketmq_filter::utils::bloom_filter::BloomFilter;
+use rocketmq_remoting::protocol::heartbeat::subscription_data::SubscriptionData;
+use tracing::error;
+use tracing::info;
+use tracing::warn;
 
 use crate::broker_path_config_helper::get_consumer_filter_path;
 use crate::filter::consumer_filter_data::ConsumerFilterData;
 use crate::filter::manager::consumer_filter_wrapper::ConsumerFilterWrapper;
+use crate::filter::manager::consumer_filter_wrapper::FilterDataMapByTopic;
 
 const MS_24_HOUR: u64 = Duration::from_hours(24).as_millis() as u64;
 
@@ -57,34 +65,64 @@ impl ConsumerFilterManager {
     }
 }
 
-//Fully implemented will be removed
-#[allow(unused_variables)]
 impl ConfigManager for ConsumerFilterManager {
-    fn decode0(&mut self, key: &[u8], body: &[u8]) {
-        todo!()
-    }
-
-    fn stop(&mut self) -> bool {
-        todo!()
-    }
-
     fn config_file_path(&self) -> String {
         get_consumer_filter_path(self.broker_config.store_path_root_dir.as_str())
     }
 
-    fn encode(&mut self) -> String {
-        todo!()
-    }
-
     fn encode_pretty(&self, pretty_format: bool) -> String {
-        "".to_string()
+        let mut wrapper = self.consumer_filter_wrapper.write();
+        Self::clean(&mut wrapper);
+        if pretty_format {
+            SerdeJsonUtils::to_json_pretty(&*wrapper).expect("encode failed")
+        } else {
+            SerdeJsonUtils::to_json(&*wrapper).expect("encode failed")
+        }
     }
 
-    fn decode(&self, json_string: &str) {}
+    fn decode(&self, json_string: &str) {
+        if json_string.is_empty() {
+            return;
+        }
+        let mut load: ConsumerFilterWrapper = match SerdeJsonUtils::from_json_str(json_string) {
+            Ok(load) => load,
+            Err(e) => {
+                error!("decode ConsumerFilterManager failed: {}", e);
+                return;
+            }
+        };
+        for filter_data_map in load.filter_data_by_topic.values_mut() {
+            for filter_data in filter_data_map.group_filter_data_mut().values_mut() {
+                let (Some(expression), Some(type_)) =
+                    (filter_data.expression(), filter_data.expression_type())
+                else {
+                    continue;
+                };
+                match FilterFactory::instance().compile(type_.as_str(), expression.as_str()) {
+                    Ok(compiled) => filter_data.set_compiled_expression(Some(Arc::new(compiled))),
+                    Err(e) => error!("load filter data error, {}, {}", filter_data, e),
+                }
+                // check whether bloom filter is changed
+                // if changed, ignore the bit map calculated before.
+                if let Some(bloom_filter) = self.bloom_filter.as_ref() {
+                    if !bloom_filter.is_valid(filter_data.bloom_filter_data()) {
+                        info!(
+                            "Bloom filter is changed!So ignore all filter data persisted! {:?}, {}",
+                            bloom_filter.m(),
+                            filter_data
+                        );
+                        return;
+                    }
+                }
+            }
+        }
+        *self.consumer_filter_wrapper.write() = load;
+    }
 }
 
-#[allow(unused_variables)]
 impl ConsumerFilterManager {
+    /// Build consumer filter data. Returns `None` for tag type subscriptions or if the
+    /// expression could not be compiled.
     pub fn build(
         topic: CheetahString,
         consumer_group: CheetahString,
@@ -95,39 +133,124 @@ impl ConsumerFilterManager {
         if ExpressionType::is_tag_type(type_.as_deref()) {
             return None;
         }
+        let (Some(expression), Some(type_)) = (expression, type_) else {
+            return None;
+        };
+
+        let compiled_expression =
+            match FilterFactory::instance().compile(type_.as_str(), expression.as_str()) {
+                Ok(compiled_expression) => compiled_expression,
+                Err(e) => {
+                    error!(
+                        "parse error: expr={}, topic={}, group={}, error={}",
+                        expression, topic, consumer_group, e
+                    );
+                    return None;
+                }
+            };
 
         let mut consumer_filter_data = ConsumerFilterData::default();
         consumer_filter_data.set_topic(topic);
         consumer_filter_data.set_consumer_group(consumer_group);
         consumer_filter_data.set_born_time(get_current_millis());
         consumer_filter_data.set_dead_time(0);
-        consumer_filter_data.set_expression(expression);
-        consumer_filter_data.set_expression_type(type_);
+        consumer_filter_data.set_expression(Some(expression));
+        consumer_filter_data.set_expression_type(Some(type_));
         consumer_filter_data.set_client_version(client_version);
-
-        /*        let filter_factory = FilterFactory;
-                match filter_factory.get(&type_).compile(&expression) {
-                    Ok(compiled_expression) => {
-                        consumer_filter_data.set_compiled_expression(compiled_expression);
-                    }
-                    Err(e) => {
-                        eprintln!(
-                            "parse error: expr={}, topic={}, group={}, error={}",
-                            expression, topic, consumer_group, e
-                        );
-                        return None;
-                    }
-                }
-        */
+        consumer_filter_data.set_compiled_expression(Some(Arc::new(compiled_expression)));
         Some(consumer_filter_data)
     }
 
+    /// Register the subscriptions of `consumer_group`, and mark the filter data of topics no
+    /// longer subscribed as dead.
+    pub fn register_subscriptions(
+        &self,
+        consumer_group: &CheetahString,
+        sub_list: &HashSet<SubscriptionData>,
+    ) {
+        for subscription_data in sub_list {
+            self.register(
+                &subscription_data.topic,
+                consumer_group,
+                &subscription_data.sub_string,
+                &subscription_data.expression_type,
+                subscription_data.sub_version as u64,
+            );
+        }
+
+        // make illegal topic dead.
+        let mut wrapper = self.consumer_filter_wrapper.write();
+        for filter_data_map in wrapper.filter_data_by_topic.values_mut() {
+            let Some(filter_data) = filter_data_map
+                .group_filter_data_mut()
+                .get_mut(consumer_group)
+            else {
+                continue;
+            };
+            let exist = sub_list
+                .iter()
+                .any(|subscription_data| subscription_data.topic == *filter_data.topic());
+            if !exist && !filter_data.is_dead() {
+                filter_data.set_dead_time(get_current_millis());
+                info!(
+                    "Consumer's filter data is dead, topic not subscribed: {}",
+                    filter_data
+                );
+            }
+        }
+    }
+
+    pub fn register(
+        &self,
+        topic: &CheetahString,
+        consumer_group: &CheetahString,
+        expression: &CheetahString,
+        type_: &CheetahString,
+        client_version: u64,
+    ) -> bool {
+        if ExpressionType::is_tag_type(Some(type_.as_str())) {
+            return false;
+        }
+        if expression.is_empty() {
+            return false;
+        }
+        let bloom_filter_data = self
+            .bloom_filter
+            .as_ref()
+            .map(|bloom_filter| bloom_filter.generate(&format!("{consumer_group}#{topic}")));
+        let mut wrapper = self.consumer_filter_wrapper.write();
+        wrapper
+            .filter_data_by_topic
+            .entry(topic.clone())
+            .or_insert_with(|| FilterDataMapByTopic::new(topic.clone()))
+            .register(
+                consumer_group,
+                expression,
+                type_,
+                bloom_filter_data,
+                client_version,
+            )
+    }
+
+    pub fn unregister(&self, consumer_group: &CheetahString) {
+        let mut wrapper = self.consumer_filter_wrapper.write();
+        for filter_data_map in wrapper.filter_data_by_topic.values_mut() {
+            filter_data_map.unregister(consumer_group);
+        }
+    }
+
     pub fn get_consumer_filter_data(
         &self,
         topic: &CheetahString,
         consumer_group: &CheetahString,
     ) -> Option<ConsumerFilterData> {
-        None
+        self.consumer_filter_wrapper
+            .read()
+            .filter_data_by_topic
+            .get(topic)?
+            .group_filter_data()
+            .get(consumer_group)
+            .cloned()
     }
 
     pub fn bloom_filter(&self) -> Option<&BloomFilter> {
@@ -135,6 +258,137 @@ impl ConsumerFilterManager {
     }
 
     pub fn get(&self, topic: &CheetahString) -> Option<Vec<ConsumerFilterData>> {
-        unimplemented!("get method not implemented");
+        let wrapper = self.consumer_filter_wrapper.read();
+        let filter_data_map = wrapper.filter_data_by_topic.get(topic)?;
+        if filter_data_map.group_filter_data().is_empty() {
+            return None;
+        }
+        Some(
+            filter_data_map
+                .group_filter_data()
+                .values()
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Remove filter data which has been dead for more than 24 hours.
+    fn clean(wrapper: &mut ConsumerFilterWrapper) {
+        wrapper.filter_data_by_topic.retain(|_, filter_data_map| {
+            filter_data_map
+                .group_filter_data_mut()
+                .retain(|_, filter_data| {
+                    let alive = filter_data.how_long_after_death() < MS_24_HOUR;
+                    if !alive {
+                        warn!("Remove filter consumer {}, died too long!", filter_data);
+                    }
+                    alive
+                });
+            !filter_data_map.group_filter_data().is_empty()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sql92() -> CheetahString {
+        CheetahString::from_static_str(ExpressionType::SQL92)
+    }
+
+    #[test]
+    fn build_compiles_sql92_expression() {
+        let data = ConsumerFilterManager::build(
+            CheetahString::from_static_str("topic"),
+            CheetahString::from_static_str("group"),
+            Some(CheetahString::from_static_str("a > 1")),
+            Some(sql92()),
+            1,
+        )
+        .unwrap();
+        assert!(data.compiled_expression().is_some());
+        assert!(!data.is_dead());
+    }
+
+    #[test]
+    fn build_returns_none_for_tag_or_invalid_expression() {
+        let topic = CheetahString::from_static_str("topic");
+        let group = CheetahString::from_static_str("group");
+        assert!(ConsumerFilterManager::build(
+            topic.clone(),
+            group.clone(),
+            Some(CheetahString::from_static_str("*")),
+            Some(CheetahString::from_static_str(ExpressionType::TAG)),
+            1,
+        )
+        .is_none());
+        assert!(ConsumerFilterManager::build(
+            topic,
+            group,
+            Some(CheetahString::from_static_str("a >")),
+            Some(sql92()),
+            1,
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn register_get_and_unregister() {
+        let manager = ConsumerFilterManager::new(Arc::new(BrokerConfig::default()));
+        let topic = CheetahString::from_static_str("topic");
+        let group = CheetahString::from_static_str("group");
+        let expression = CheetahString::from_static_str("a > 1");
+
+        assert!(manager.register(&topic, &group, &expression, &sql92(), 1));
+        let data = manager.get_consumer_filter_data(&topic, &group).unwrap();
+        assert!(manager
+            .bloom_filter()
+            .unwrap()
+            .is_valid(data.bloom_filter_data()));
+        assert_eq!(manager.get(&topic).unwrap().len(), 1);
+
+        // an older client version is ignored
+        assert!(!manager.register(
+            &topic,
+            &group,
+            &CheetahString::from_static_str("b > 1"),
+            &sql92(),
+            0
+        ));
+
+        manager.unregister(&group);
+        assert!(manager
+            .get_consumer_filter_data(&topic, &group)
+            .unwrap()
+            .is_dead());
+
+        // registering again brings it back alive
+        assert!(manager.register(&topic, &group, &expression, &sql92(), 1));
+        assert!(!manager
+            .get_consumer_filter_data(&topic, &group)
+            .unwrap()
+            .is_dead());
+    }
+
+    #[test]
+    fn encode_and_decode_recompiles_expressions() {
+        let manager = ConsumerFilterManager::new(Arc::new(BrokerConfig::default()));
+        let topic = CheetahString::from_static_str("topic");
+        let group = CheetahString::from_static_str("group");
+        manager.register(
+            &topic,
+            &group,
+            &CheetahString::from_static_str("a > 1"),
+            &sql92(),
+            1,
+        );
+        let json = manager.encode_pretty(false);
+
+        let loaded = ConsumerFilterManager::new(Arc::new(BrokerConfig::default()));
+        loaded.decode(&json);
+        let data = loaded.get_consumer_filter_data(&topic, &group).unwrap();
+        assert!(data.compiled_expression().is_some());
+        assert_eq!(data.client_version(), 1);
     }
 }
@@ -16,20 +16,150 @@
  */
 use std::collections::HashMap;
 
+use cheetah_string::CheetahString;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_filter::utils::bloom_filter_data::BloomFilterData;
 use serde::Deserialize;
 use serde::Serialize;
+use tracing::info;
+use tracing::warn;
 
 use crate::filter::consumer_filter_data::ConsumerFilterData;
+use crate::filter::manager::consumer_filter_manager::ConsumerFilterManager;
 
 #[derive(Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct ConsumerFilterWrapper {
-    filter_data_by_topic: HashMap<String /* Topic */, FilterDataMapByTopic>,
+    pub(crate) filter_data_by_topic: HashMap<CheetahString /* Topic */, FilterDataMapByTopic>,
 }
 
 #[derive(Clone, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct FilterDataMapByTopic {
-    filter_data_map: HashMap<String /* consumer group */, ConsumerFilterData>,
-    topic: String,
+    group_filter_data: HashMap<CheetahString /* consumer group */, ConsumerFilterData>,
+    topic: CheetahString,
+}
+
+impl FilterDataMapByTopic {
+    pub fn new(topic: CheetahString) -> Self {
+        FilterDataMapByTopic {
+            group_filter_data: HashMap::new(),
+            topic,
+        }
+    }
+
+    pub fn group_filter_data(&self) -> &HashMap<CheetahString, ConsumerFilterData> {
+        &self.group_filter_data
+    }
+
+    pub fn group_filter_data_mut(&mut self) -> &mut HashMap<CheetahString, ConsumerFilterData> {
+        &mut self.group_filter_data
+    }
+
+    pub fn topic(&self) -> &CheetahString {
+        &self.topic
+    }
+
+    pub fn unregister(&mut self, consumer_group: &CheetahString) {
+        if let Some(data) = self.group_filter_data.get_mut(consumer_group) {
+            if data.is_dead() {
+                return;
+            }
+            data.set_dead_time(get_current_millis());
+        }
+    }
+
+    /// Register the filter data of `consumer_group`, returns `true` if the registered data is
+    /// alive after this call.
+    pub fn register(
+        &mut self,
+        consumer_group: &CheetahString,
+        expression: &CheetahString,
+        type_: &CheetahString,
+        bloom_filter_data: Option<BloomFilterData>,
+        client_version: u64,
+    ) -> bool {
+        let Some(old) = self.group_filter_data.get_mut(consumer_group) else {
+            let consumer_filter_data = ConsumerFilterManager::build(
+                self.topic.clone(),
+                consumer_group.clone(),
+                Some(expression.clone()),
+                Some(type_.clone()),
+                client_version,
+            );
+            let Some(mut consumer_filter_data) = consumer_filter_data else {
+                return false;
+            };
+            consumer_filter_data.set_bloom_filter_data(bloom_filter_data);
+            info!("New consumer filter registered: {}", consumer_filter_data);
+            self.group_filter_data
+                .insert(consumer_group.clone(), consumer_filter_data);
+            return true;
+        };
+
+        if client_version <= old.client_version() {
+            if old.expression_type() != Some(type_) || old.expression() != Some(expression) {
+                warn!(
+                    "Ignore consumer({} : {}) filter, because of version {} <= {}, but maybe info \
+                     changed!old={:?}:{:?}, ignored={}:{}",
+                    consumer_group,
+                    self.topic,
+                    client_version,
+                    old.client_version(),
+                    old.expression_type(),
+                    old.expression(),
+                    type_,
+                    expression
+                );
+            }
+            if old.is_dead() {
+                Self::re_alive(old);
+                return true;
+            }
+            return false;
+        }
+
+        let changed = old.expression() != Some(expression)
+            || old.expression_type() != Some(type_)
+            || old.bloom_filter_data() != bloom_filter_data.as_ref();
+        if !changed {
+            old.set_client_version(client_version);
+            if old.is_dead() {
+                Self::re_alive(old);
+            }
+            return true;
+        }
+
+        match ConsumerFilterManager::build(
+            self.topic.clone(),
+            consumer_group.clone(),
+            Some(expression.clone()),
+            Some(type_.clone()),
+            client_version,
+        ) {
+            Some(mut consumer_filter_data) => {
+                consumer_filter_data.set_bloom_filter_data(bloom_filter_data);
+                info!(
+                    "Consumer filter info change, old: {}, new: {}",
+                    old, consumer_filter_data
+                );
+                *old = consumer_filter_data;
+                true
+            }
+            None => {
+                // new expression compile error, remove old, let client report error.
+                self.group_filter_data.remove(consumer_group);
+                false
+            }
+        }
+    }
+
+    fn re_alive(filter_data: &mut ConsumerFilterData) {
+        let old_dead_time = filter_data.dead_time();
+        filter_data.set_dead_time(0);
+        info!(
+            "Re alive consumer filter: {}, oldDeadTime: {}",
+            filter_data, old_dead_time
+        );
+    }
 }
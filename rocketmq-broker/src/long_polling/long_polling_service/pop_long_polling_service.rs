@@ -537,6 +537,13 @@ impl<MS: MessageStore, RP: RequestProcessor + Sync + 'static> PopLongPollingServ
         pop_request
     }
 
+    /// Returns how many pop requests are parked under the given polling key.
+    pub fn get_polling_num(&self, key: &str) -> i32 {
+        self.polling_map
+            .get(key)
+            .map_or(0, |queue| queue.len() as i32)
+    }
+
     pub fn set_processor(&mut self, processor: ArcMut<RP>) {
         self.processor = Some(processor);
     }
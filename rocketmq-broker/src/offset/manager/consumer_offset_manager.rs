@@ -253,6 +253,40 @@ impl ConsumerOffsetManager {
             .insert(queue_id, offset);
     }
 
+    /// Records a reset offset for the queue, so the next pull of the group is redirected to it, and
+    /// commits the same offset to the offset table.
+    pub fn assign_reset_offset(
+        &self,
+        topic: &CheetahString,
+        group: &CheetahString,
+        queue_id: i32,
+        offset: i64,
+    ) {
+        if topic.is_empty() || group.is_empty() || queue_id < 0 || offset < 0 {
+            warn!(
+                "Illegal arguments when assigning reset offset. Topic={}, group={}, queueId={}, \
+                 offset={}",
+                topic, group, queue_id, offset
+            );
+            return;
+        }
+        let key = CheetahString::from_string(format!("{topic}{TOPIC_GROUP_SEPARATOR}{group}"));
+        self.consumer_offset_wrapper
+            .reset_offset_table
+            .write()
+            .entry(key.clone())
+            .or_default()
+            .insert(queue_id, offset);
+        if let Some(offset_map) = self
+            .consumer_offset_wrapper
+            .offset_table
+            .write()
+            .get_mut(&key)
+        {
+            offset_map.insert(queue_id, offset);
+        }
+    }
+
     pub fn query_then_erase_reset_offset(
         &self,
         topic: &CheetahString,
@@ -46,10 +46,25 @@ use rocketmq_remoting::clients::RemotingClient;
 use rocketmq_remoting::code::request_code::RequestCode;
 use rocketmq_remoting::code::response_code::ResponseCode;
 use rocketmq_remoting::protocol::body::broker_body::register_broker_body::RegisterBrokerBody;
+use rocketmq_remoting::protocol::body::elect_master_response_body::ElectMasterResponseBody;
 use rocketmq_remoting::protocol::body::kv_table::KVTable;
 use rocketmq_remoting::protocol::body::response::lock_batch_response_body::LockBatchResponseBody;
+use rocketmq_remoting::protocol::body::sync_state_set::SyncStateSet;
 use rocketmq_remoting::protocol::body::topic_info_wrapper::topic_config_wrapper::TopicConfigAndMappingSerializeWrapper;
+use rocketmq_remoting::protocol::header::broker::broker_heartbeat_request_header::BrokerHeartbeatRequestHeader;
 use rocketmq_remoting::protocol::header::client_request_header::GetRouteInfoRequestHeader;
+use rocketmq_remoting::protocol::header::controller::alter_sync_state_set_request_header::AlterSyncStateSetRequestHeader;
+use rocketmq_remoting::protocol::header::controller::apply_broker_id_request_header::ApplyBrokerIdRequestHeader;
+use rocketmq_remoting::protocol::header::controller::elect_master_request_header::ElectMasterRequestHeader;
+use rocketmq_remoting::protocol::header::controller::exchange_ha_info_request_header::ExchangeHAInfoRequestHeader;
+use rocketmq_remoting::protocol::header::controller::exchange_ha_info_request_header::ExchangeHAInfoResponseHeader;
+use rocketmq_remoting::protocol::header::controller::get_next_broker_id_request_header::GetNextBrokerIdRequestHeader;
+use rocketmq_remoting::protocol::header::controller::get_next_broker_id_request_header::GetNextBrokerIdResponseHeader;
+use rocketmq_remoting::protocol::header::controller::get_replica_info_request_header::GetReplicaInfoRequestHeader;
+use rocketmq_remoting::protocol::header::controller::get_replica_info_request_header::GetReplicaInfoResponseHeader;
+use rocketmq_remoting::protocol::header::controller::register_broker_to_controller_request_header::RegisterBrokerToControllerRequestHeader;
+use rocketmq_remoting::protocol::header::controller::register_broker_to_controller_request_header::RegisterBrokerToControllerResponseHeader;
+use rocketmq_remoting::protocol::header::elect_master_response_header::ElectMasterResponseHeader;
 use rocketmq_remoting::protocol::header::lock_batch_mq_request_header::LockBatchMqRequestHeader;
 use rocketmq_remoting::protocol::header::message_operation_header::send_message_request_header::SendMessageRequestHeader;
 use rocketmq_remoting::protocol::header::message_operation_header::send_message_request_header_v2::SendMessageRequestHeaderV2;
@@ -615,6 +630,188 @@ impl BrokerOuterAPI {
             ))
         }
     }
+
+    pub async fn get_next_broker_id(
+        &self,
+        controller_address: &CheetahString,
+        request_header: GetNextBrokerIdRequestHeader,
+    ) -> rocketmq_error::RocketMQResult<GetNextBrokerIdResponseHeader> {
+        let request = RemotingCommand::create_request_command(
+            RequestCode::ControllerGetNextBrokerId,
+            request_header,
+        );
+        let response = self
+            .invoke_controller(controller_address, request, &[])
+            .await?;
+        response.decode_command_custom_header::<GetNextBrokerIdResponseHeader>()
+    }
+
+    pub async fn apply_broker_id(
+        &self,
+        controller_address: &CheetahString,
+        request_header: ApplyBrokerIdRequestHeader,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        let request = RemotingCommand::create_request_command(
+            RequestCode::ControllerApplyBrokerId,
+            request_header,
+        );
+        self.invoke_controller(controller_address, request, &[])
+            .await?;
+        Ok(())
+    }
+
+    pub async fn register_broker_to_controller(
+        &self,
+        controller_address: &CheetahString,
+        request_header: RegisterBrokerToControllerRequestHeader,
+    ) -> rocketmq_error::RocketMQResult<(RegisterBrokerToControllerResponseHeader, SyncStateSet)>
+    {
+        let request = RemotingCommand::create_request_command(
+            RequestCode::ControllerRegisterBroker,
+            request_header,
+        );
+        let response = self
+            .invoke_controller(controller_address, request, &[])
+            .await?;
+        let response_header =
+            response.decode_command_custom_header::<RegisterBrokerToControllerResponseHeader>()?;
+        let sync_state_set = match response.get_body() {
+            Some(body) => SyncStateSet::decode(body)?,
+            None => SyncStateSet::default(),
+        };
+        Ok((response_header, sync_state_set))
+    }
+
+    pub async fn get_replica_info(
+        &self,
+        controller_address: &CheetahString,
+        request_header: GetReplicaInfoRequestHeader,
+    ) -> rocketmq_error::RocketMQResult<(GetReplicaInfoResponseHeader, SyncStateSet)> {
+        let request = RemotingCommand::create_request_command(
+            RequestCode::ControllerGetReplicaInfo,
+            request_header,
+        );
+        let response = self
+            .invoke_controller(controller_address, request, &[])
+            .await?;
+        let response_header =
+            response.decode_command_custom_header::<GetReplicaInfoResponseHeader>()?;
+        let sync_state_set = match response.get_body() {
+            Some(body) => SyncStateSet::decode(body)?,
+            None => SyncStateSet::default(),
+        };
+        Ok((response_header, sync_state_set))
+    }
+
+    /// Asks the controller to elect a master, an existing master is reported back the same way
+    /// as a newly elected one.
+    pub async fn elect_master(
+        &self,
+        controller_address: &CheetahString,
+        request_header: ElectMasterRequestHeader,
+    ) -> rocketmq_error::RocketMQResult<(ElectMasterResponseHeader, ElectMasterResponseBody)> {
+        let request = RemotingCommand::create_request_command(
+            RequestCode::ControllerElectMaster,
+            request_header,
+        );
+        let response = self
+            .invoke_controller(
+                controller_address,
+                request,
+                &[ResponseCode::ControllerMasterStillExist],
+            )
+            .await?;
+        let response_header =
+            response.decode_command_custom_header::<ElectMasterResponseHeader>()?;
+        let response_body = match response.get_body() {
+            Some(body) => ElectMasterResponseBody::decode(body)?,
+            None => ElectMasterResponseBody::default(),
+        };
+        Ok((response_header, response_body))
+    }
+
+    pub async fn alter_sync_state_set(
+        &self,
+        controller_address: &CheetahString,
+        request_header: AlterSyncStateSetRequestHeader,
+        sync_state_set: SyncStateSet,
+    ) -> rocketmq_error::RocketMQResult<SyncStateSet> {
+        let request = RemotingCommand::create_request_command(
+            RequestCode::ControllerAlterSyncStateSet,
+            request_header,
+        )
+        .set_body(sync_state_set.encode()?);
+        let response = self
+            .invoke_controller(controller_address, request, &[])
+            .await?;
+        match response.get_body() {
+            Some(body) => SyncStateSet::decode(body),
+            None => Err(RocketmqError::MQBrokerError(
+                response.code(),
+                "alter sync state set response without body".to_string(),
+                controller_address.to_string(),
+            )),
+        }
+    }
+
+    pub async fn send_heartbeat_to_controller(
+        &self,
+        controller_address: &CheetahString,
+        request_header: BrokerHeartbeatRequestHeader,
+        timeout_millis: u64,
+    ) {
+        let request =
+            RemotingCommand::create_request_command(RequestCode::BrokerHeartbeat, request_header);
+        self.remoting_client
+            .invoke_oneway(controller_address, request, timeout_millis)
+            .await;
+    }
+
+    /// Retrieves the HA address and the flushed offset of the master at `master_address`.
+    pub async fn retrieve_broker_ha_info(
+        &self,
+        master_address: &CheetahString,
+    ) -> rocketmq_error::RocketMQResult<ExchangeHAInfoResponseHeader> {
+        let request = RemotingCommand::create_request_command(
+            RequestCode::ExchangeBrokerHaInfo,
+            ExchangeHAInfoRequestHeader::default(),
+        );
+        let response = self
+            .remoting_client
+            .invoke_async(Some(master_address), request, 3000)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            response.decode_command_custom_header::<ExchangeHAInfoResponseHeader>()
+        } else {
+            Err(RocketmqError::MQBrokerError(
+                response.code(),
+                response.remark().map_or("".to_string(), |s| s.to_string()),
+                master_address.to_string(),
+            ))
+        }
+    }
+
+    async fn invoke_controller(
+        &self,
+        controller_address: &CheetahString,
+        request: RemotingCommand,
+        accepted_codes: &[ResponseCode],
+    ) -> rocketmq_error::RocketMQResult<RemotingCommand> {
+        let response = self
+            .remoting_client
+            .invoke_async(Some(controller_address), request, 3000)
+            .await?;
+        let code = ResponseCode::from(response.code());
+        if code == ResponseCode::Success || accepted_codes.contains(&code) {
+            Ok(response)
+        } else {
+            Err(RocketmqError::MQBrokerError(
+                response.code(),
+                response.remark().map_or("".to_string(), |s| s.to_string()),
+                controller_address.to_string(),
+            ))
+        }
+    }
 }
 
 fn process_pull_result(
@@ -63,12 +63,12 @@ pub(crate) mod send_message_processor;
 pub struct BrokerRequestProcessor<MS: MessageStore, TS> {
     pub(crate) send_message_processor: ArcMut<SendMessageProcessor<MS, TS>>,
     pub(crate) pull_message_processor: ArcMut<PullMessageProcessor<MS>>,
-    pub(crate) peek_message_processor: ArcMut<PeekMessageProcessor>,
+    pub(crate) peek_message_processor: ArcMut<PeekMessageProcessor<MS>>,
     pub(crate) pop_message_processor: ArcMut<PopMessageProcessor<MS>>,
     pub(crate) ack_message_processor: ArcMut<AckMessageProcessor<MS>>,
     pub(crate) change_invisible_time_processor: ArcMut<ChangeInvisibleTimeProcessor<MS>>,
     pub(crate) notification_processor: ArcMut<NotificationProcessor<MS>>,
-    pub(crate) polling_info_processor: ArcMut<PollingInfoProcessor>,
+    pub(crate) polling_info_processor: ArcMut<PollingInfoProcessor<MS>>,
     pub(crate) reply_message_processor: ArcMut<ReplyMessageProcessor<MS, TS>>,
     pub(crate) query_message_processor: ArcMut<QueryMessageProcessor<MS>>,
     pub(crate) client_manage_processor: ArcMut<ClientManageProcessor<MS>>,
@@ -183,6 +183,20 @@ where
                     .await;
             }
 
+            RequestCode::PeekMessage => {
+                return self
+                    .peek_message_processor
+                    .process_request(channel, ctx, request_code, request)
+                    .await;
+            }
+
+            RequestCode::PollingInfo => {
+                return self
+                    .polling_info_processor
+                    .process_request(channel, ctx, request_code, request)
+                    .await;
+            }
+
             RequestCode::PopMessage => {
                 /*return self
                 .pop_message_processor
@@ -25,16 +25,22 @@ use rocketmq_store::base::message_store::MessageStore;
 use tracing::warn;
 
 use crate::broker_runtime::BrokerRuntimeInner;
+use crate::processor::admin_broker_processor::acl_request_handler::AclRequestHandler;
 use crate::processor::admin_broker_processor::batch_mq_handler::BatchMqHandler;
 use crate::processor::admin_broker_processor::broker_config_request_handler::BrokerConfigRequestHandler;
+use crate::processor::admin_broker_processor::cold_data_flow_ctr_request_handler::ColdDataFlowCtrRequestHandler;
 use crate::processor::admin_broker_processor::consumer_request_handler::ConsumerRequestHandler;
+use crate::processor::admin_broker_processor::ha_request_handler::HARequestHandler;
 use crate::processor::admin_broker_processor::offset_request_handler::OffsetRequestHandler;
 use crate::processor::admin_broker_processor::subscription_group_handler::SubscriptionGroupHandler;
 use crate::processor::admin_broker_processor::topic_request_handler::TopicRequestHandler;
 
+mod acl_request_handler;
 mod batch_mq_handler;
 mod broker_config_request_handler;
+mod cold_data_flow_ctr_request_handler;
 mod consumer_request_handler;
+mod ha_request_handler;
 mod offset_request_handler;
 mod subscription_group_handler;
 mod topic_request_handler;
@@ -46,6 +52,9 @@ pub struct AdminBrokerProcessor<MS: MessageStore> {
     offset_request_handler: OffsetRequestHandler<MS>,
     batch_mq_handler: BatchMqHandler<MS>,
     subscription_group_handler: SubscriptionGroupHandler<MS>,
+    ha_request_handler: HARequestHandler<MS>,
+    acl_request_handler: AclRequestHandler<MS>,
+    cold_data_flow_ctr_request_handler: ColdDataFlowCtrRequestHandler<MS>,
 
     broker_runtime_inner: ArcMut<BrokerRuntimeInner<MS>>,
 }
@@ -60,6 +69,10 @@ impl<MS: MessageStore> AdminBrokerProcessor<MS> {
         let batch_mq_handler = BatchMqHandler::new(broker_runtime_inner.clone());
         let subscription_group_handler =
             SubscriptionGroupHandler::new(broker_runtime_inner.clone());
+        let ha_request_handler = HARequestHandler::new(broker_runtime_inner.clone());
+        let acl_request_handler = AclRequestHandler::new(broker_runtime_inner.clone());
+        let cold_data_flow_ctr_request_handler =
+            ColdDataFlowCtrRequestHandler::new(broker_runtime_inner.clone());
         AdminBrokerProcessor {
             topic_request_handler,
             broker_config_request_handler,
@@ -67,6 +80,9 @@ impl<MS: MessageStore> AdminBrokerProcessor<MS> {
             offset_request_handler,
             batch_mq_handler,
             subscription_group_handler,
+            ha_request_handler,
+            acl_request_handler,
+            cold_data_flow_ctr_request_handler,
             broker_runtime_inner,
         }
     }
@@ -146,6 +162,16 @@ impl<MS: MessageStore> AdminBrokerProcessor<MS> {
                     .get_broker_runtime_info(channel, ctx, request_code, request)
                     .await
             }
+            RequestCode::DeleteExpiredCommitlog => {
+                self.broker_config_request_handler
+                    .delete_expired_commit_log(channel, ctx, request_code, request)
+                    .await
+            }
+            RequestCode::CheckRocksdbCqWriteProgress => {
+                self.broker_config_request_handler
+                    .check_rocksdb_cq_write_progress(channel, ctx, request_code, request)
+                    .await
+            }
             RequestCode::QueryTopicConsumeByWho => {
                 self.topic_request_handler
                     .query_topic_consume_by_who(channel, ctx, request_code, request)
@@ -166,7 +192,17 @@ impl<MS: MessageStore> AdminBrokerProcessor<MS> {
                     .get_min_offset(channel, ctx, request_code, request)
                     .await
             }
+            RequestCode::SearchOffsetByTimestamp => {
+                self.offset_request_handler
+                    .search_offset_by_timestamp(channel, ctx, request_code, request)
+                    .await
+            }
 
+            RequestCode::InvokeBrokerToResetOffset => {
+                self.offset_request_handler
+                    .reset_offset(channel, ctx, request_code, request)
+                    .await
+            }
             RequestCode::LockBatchMq => {
                 self.batch_mq_handler
                     .lock_natch_mq(channel, ctx, request_code, request)
@@ -183,6 +219,91 @@ impl<MS: MessageStore> AdminBrokerProcessor<MS> {
                     .update_and_create_subscription_group(channel, ctx, request_code, request)
                     .await
             }
+            RequestCode::ExchangeBrokerHaInfo => {
+                self.ha_request_handler
+                    .exchange_ha_info(channel, ctx, request_code, request)
+                    .await
+            }
+            RequestCode::UpdateAndCreateAclConfig => {
+                self.acl_request_handler
+                    .update_and_create_acl_config(channel, ctx, request_code, request)
+                    .await
+            }
+            RequestCode::DeleteAclConfig => {
+                self.acl_request_handler
+                    .delete_acl_config(channel, ctx, request_code, request)
+                    .await
+            }
+            RequestCode::UpdateGlobalWhiteAddrsConfig => {
+                self.acl_request_handler
+                    .update_global_white_addrs_config(channel, ctx, request_code, request)
+                    .await
+            }
+            RequestCode::AuthCreateUser => {
+                self.acl_request_handler
+                    .create_user(channel, ctx, request_code, request)
+                    .await
+            }
+            RequestCode::AuthUpdateUser => {
+                self.acl_request_handler
+                    .update_user(channel, ctx, request_code, request)
+                    .await
+            }
+            RequestCode::AuthDeleteUser => {
+                self.acl_request_handler
+                    .delete_user(channel, ctx, request_code, request)
+                    .await
+            }
+            RequestCode::AuthGetUser => {
+                self.acl_request_handler
+                    .get_user(channel, ctx, request_code, request)
+                    .await
+            }
+            RequestCode::AuthListUser => {
+                self.acl_request_handler
+                    .list_users(channel, ctx, request_code, request)
+                    .await
+            }
+            RequestCode::AuthCreateAcl => {
+                self.acl_request_handler
+                    .create_acl(channel, ctx, request_code, request)
+                    .await
+            }
+            RequestCode::AuthUpdateAcl => {
+                self.acl_request_handler
+                    .update_acl(channel, ctx, request_code, request)
+                    .await
+            }
+            RequestCode::AuthDeleteAcl => {
+                self.acl_request_handler
+                    .delete_acl(channel, ctx, request_code, request)
+                    .await
+            }
+            RequestCode::AuthGetAcl => {
+                self.acl_request_handler
+                    .get_acl(channel, ctx, request_code, request)
+                    .await
+            }
+            RequestCode::AuthListAcl => {
+                self.acl_request_handler
+                    .list_acl(channel, ctx, request_code, request)
+                    .await
+            }
+            RequestCode::UpdateColdDataFlowCtrConfig => {
+                self.cold_data_flow_ctr_request_handler
+                    .update_cold_data_flow_ctr_group_config(channel, ctx, request_code, request)
+                    .await
+            }
+            RequestCode::RemoveColdDataFlowCtrConfig => {
+                self.cold_data_flow_ctr_request_handler
+                    .remove_cold_data_flow_ctr_group_config(channel, ctx, request_code, request)
+                    .await
+            }
+            RequestCode::GetColdDataFlowCtrInfo => {
+                self.cold_data_flow_ctr_request_handler
+                    .get_cold_data_flow_ctr_info(channel, ctx, request_code, request)
+                    .await
+            }
             _ => Some(get_unknown_cmd_response(request_code)),
         }
     }
@@ -0,0 +1,371 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+
+use cheetah_string::CheetahString;
+use rocketmq_common::common::base::plain_access_config::PlainAccessConfig;
+use rocketmq_common::utils::serde_json_utils::SerdeJsonUtils;
+use rocketmq_error::RocketmqError;
+use rocketmq_remoting::acl::plain_permission_manager::PlainPermissionManager;
+use rocketmq_remoting::code::request_code::RequestCode;
+use rocketmq_remoting::code::response_code::ResponseCode;
+use rocketmq_remoting::net::channel::Channel;
+use rocketmq_remoting::protocol::body::acl_info::AclInfo;
+use rocketmq_remoting::protocol::body::user_info::UserInfo;
+use rocketmq_remoting::protocol::command_custom_header::FromMap;
+use rocketmq_remoting::protocol::header::acl_request_header::CreateAccessConfigRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::CreateAclRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::CreateUserRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::DeleteAccessConfigRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::DeleteAclRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::DeleteUserRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::GetAclRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::GetUserRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::ListAclsRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::ListUsersRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::UpdateAclRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::UpdateGlobalWhiteAddrsConfigRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::UpdateUserRequestHeader;
+use rocketmq_remoting::protocol::remoting_command::RemotingCommand;
+use rocketmq_remoting::runtime::connection_handler_context::ConnectionHandlerContext;
+use rocketmq_rust::ArcMut;
+use rocketmq_store::base::message_store::MessageStore;
+use serde::Serialize;
+use tracing::info;
+use tracing::warn;
+
+use crate::broker_runtime::BrokerRuntimeInner;
+
+type HandlerResult = Result<RemotingCommand, RemotingCommand>;
+
+#[derive(Clone)]
+pub(super) struct AclRequestHandler<MS: MessageStore> {
+    broker_runtime_inner: ArcMut<BrokerRuntimeInner<MS>>,
+}
+
+impl<MS: MessageStore> AclRequestHandler<MS> {
+    pub fn new(broker_runtime_inner: ArcMut<BrokerRuntimeInner<MS>>) -> Self {
+        Self {
+            broker_runtime_inner,
+        }
+    }
+}
+
+impl<MS: MessageStore> AclRequestHandler<MS> {
+    pub async fn update_and_create_acl_config(
+        &mut self,
+        _channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        Some(self.handle(|manager| {
+            let header = decode_header::<CreateAccessConfigRequestHeader>(&request)?;
+            let config = PlainAccessConfig {
+                access_key: Some(header.access_key),
+                secret_key: header.secret_key,
+                white_remote_address: header.white_remote_address,
+                admin: header.admin.unwrap_or_default(),
+                default_topic_perm: header.default_topic_perm,
+                default_group_perm: header.default_group_perm,
+                topic_perms: split_perms(header.topic_perms.as_deref()),
+                group_perms: split_perms(header.group_perms.as_deref()),
+            };
+            info!("update and create acl config: {}", config);
+            manager.update_access_config(config).map_err(system_error)?;
+            Ok(RemotingCommand::create_response_command())
+        }))
+    }
+
+    pub async fn delete_acl_config(
+        &mut self,
+        _channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        Some(self.handle(|manager| {
+            let header = decode_header::<DeleteAccessConfigRequestHeader>(&request)?;
+            info!("delete acl config of accessKey={}", header.access_key);
+            if !manager
+                .delete_access_config(header.access_key.as_str())
+                .map_err(system_error)?
+            {
+                return Err(system_error(format!(
+                    "The accessKey {} is not exist",
+                    header.access_key
+                )));
+            }
+            Ok(RemotingCommand::create_response_command())
+        }))
+    }
+
+    pub async fn update_global_white_addrs_config(
+        &mut self,
+        _channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        Some(self.handle(|manager| {
+            let header = decode_header::<UpdateGlobalWhiteAddrsConfigRequestHeader>(&request)?;
+            info!(
+                "update global white addrs config: {}",
+                header.global_white_addrs
+            );
+            manager
+                .update_global_white_addrs(split_perms(Some(header.global_white_addrs.as_str())))
+                .map_err(system_error)?;
+            Ok(RemotingCommand::create_response_command())
+        }))
+    }
+
+    pub async fn create_user(
+        &mut self,
+        _channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        Some(self.handle(|manager| {
+            let header = decode_header::<CreateUserRequestHeader>(&request)?;
+            let mut user = decode_body::<UserInfo>(&request)?;
+            user.username = Some(header.username);
+            manager.create_user(&user).map_err(system_error)?;
+            Ok(RemotingCommand::create_response_command())
+        }))
+    }
+
+    pub async fn update_user(
+        &mut self,
+        _channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        Some(self.handle(|manager| {
+            let header = decode_header::<UpdateUserRequestHeader>(&request)?;
+            let mut user = decode_body::<UserInfo>(&request)?;
+            user.username = Some(header.username);
+            manager.update_user(&user).map_err(system_error)?;
+            Ok(RemotingCommand::create_response_command())
+        }))
+    }
+
+    pub async fn delete_user(
+        &mut self,
+        _channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        Some(self.handle(|manager| {
+            let header = decode_header::<DeleteUserRequestHeader>(&request)?;
+            manager
+                .delete_user(header.username.as_str())
+                .map_err(system_error)?;
+            Ok(RemotingCommand::create_response_command())
+        }))
+    }
+
+    pub async fn get_user(
+        &mut self,
+        _channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        Some(self.handle(|manager| {
+            let header = decode_header::<GetUserRequestHeader>(&request)?;
+            let user = manager.get_user(header.username.as_str()).ok_or_else(|| {
+                system_error(format!("The user of {} is not exist", header.username))
+            })?;
+            json_response(&user)
+        }))
+    }
+
+    pub async fn list_users(
+        &mut self,
+        _channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        Some(self.handle(|manager| {
+            let header = decode_header::<ListUsersRequestHeader>(&request)?;
+            json_response(&manager.list_users(header.filter.as_deref()))
+        }))
+    }
+
+    pub async fn create_acl(
+        &mut self,
+        _channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        Some(self.handle(|manager| {
+            let header = decode_header::<CreateAclRequestHeader>(&request)?;
+            update_acl(manager, header.subject, &request)
+        }))
+    }
+
+    pub async fn update_acl(
+        &mut self,
+        _channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        Some(self.handle(|manager| {
+            let header = decode_header::<UpdateAclRequestHeader>(&request)?;
+            update_acl(manager, header.subject, &request)
+        }))
+    }
+
+    pub async fn delete_acl(
+        &mut self,
+        _channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        Some(self.handle(|manager| {
+            let header = decode_header::<DeleteAclRequestHeader>(&request)?;
+            manager
+                .delete_acl(header.subject.as_str(), header.resource.as_deref())
+                .map_err(system_error)?;
+            Ok(RemotingCommand::create_response_command())
+        }))
+    }
+
+    pub async fn get_acl(
+        &mut self,
+        _channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        Some(self.handle(|manager| {
+            let header = decode_header::<GetAclRequestHeader>(&request)?;
+            let acl = manager.get_acl(header.subject.as_str()).ok_or_else(|| {
+                system_error(format!("The subject of {} is not exist", header.subject))
+            })?;
+            json_response(&acl)
+        }))
+    }
+
+    pub async fn list_acl(
+        &mut self,
+        _channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        Some(self.handle(|manager| {
+            let header = decode_header::<ListAclsRequestHeader>(&request)?;
+            json_response(&manager.list_acls(
+                header.subject_filter.as_deref(),
+                header.resource_filter.as_deref(),
+            ))
+        }))
+    }
+
+    fn handle(
+        &self,
+        f: impl FnOnce(&Arc<PlainPermissionManager>) -> HandlerResult,
+    ) -> RemotingCommand {
+        let Some(manager) = self.broker_runtime_inner.plain_permission_manager() else {
+            warn!("acl request is rejected because the broker does not enable acl");
+            return RemotingCommand::create_response_command_with_code_remark(
+                ResponseCode::SystemError,
+                "The broker does not enable acl",
+            );
+        };
+        f(manager).unwrap_or_else(|response| response)
+    }
+}
+
+fn update_acl(
+    manager: &PlainPermissionManager,
+    subject: CheetahString,
+    request: &RemotingCommand,
+) -> HandlerResult {
+    let mut acl = decode_body::<AclInfo>(request)?;
+    acl.subject = Some(subject);
+    manager.update_acl(&acl).map_err(system_error)?;
+    Ok(RemotingCommand::create_response_command())
+}
+
+fn split_perms(perms: Option<&str>) -> Vec<CheetahString> {
+    perms
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|perm| !perm.is_empty())
+        .map(CheetahString::from)
+        .collect()
+}
+
+fn decode_header<T>(request: &RemotingCommand) -> Result<T, RemotingCommand>
+where
+    T: FromMap<Target = T, Error = RocketmqError>,
+{
+    request
+        .decode_command_custom_header::<T>()
+        .map_err(|e| system_error(format!("decode request header failed: {e}")))
+}
+
+fn decode_body<T: serde::de::DeserializeOwned>(
+    request: &RemotingCommand,
+) -> Result<T, RemotingCommand> {
+    let body = request
+        .get_body()
+        .ok_or_else(|| system_error("The request body is empty"))?;
+    SerdeJsonUtils::decode::<T>(body).map_err(system_error)
+}
+
+fn json_response<T: Serialize>(value: &T) -> HandlerResult {
+    let body = SerdeJsonUtils::to_json(value).map_err(system_error)?;
+    Ok(RemotingCommand::create_response_command().set_body(body))
+}
+
+fn system_error(error: impl std::fmt::Display) -> RemotingCommand {
+    RemotingCommand::create_response_command_with_code_remark(
+        ResponseCode::SystemError,
+        error.to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_perms_skips_blank_entries() {
+        let perms = split_perms(Some("topicA=PUB, topicB=SUB,,"));
+        assert_eq!(
+            perms,
+            vec![
+                CheetahString::from("topicA=PUB"),
+                CheetahString::from("topicB=SUB")
+            ]
+        );
+        assert!(split_perms(None).is_empty());
+    }
+}
@@ -22,12 +22,15 @@ use rocketmq_common::common::mix_all;
 use rocketmq_common::common::mq_version::RocketMqVersion;
 use rocketmq_remoting::code::request_code::RequestCode;
 use rocketmq_remoting::net::channel::Channel;
+use rocketmq_remoting::protocol::body::check_rocksdb_cqwrite_progress_response_body::CheckRocksdbCqWriteProgressResponseBody;
 use rocketmq_remoting::protocol::body::kv_table::KVTable;
+use rocketmq_remoting::protocol::header::check_rocksdb_cq_write_progress_request_header::CheckRocksdbCqWriteProgressRequestHeader;
 use rocketmq_remoting::protocol::remoting_command::RemotingCommand;
 use rocketmq_remoting::runtime::connection_handler_context::ConnectionHandlerContext;
 use rocketmq_rust::ArcMut;
 use rocketmq_store::base::message_store::MessageStore;
 use sysinfo::Disks;
+use tracing::warn;
 
 use crate::broker_runtime::BrokerRuntimeInner;
 
@@ -88,6 +91,45 @@ impl<MS: MessageStore> BrokerConfigRequestHandler<MS> {
         Some(response)
     }
 
+    pub async fn delete_expired_commit_log(
+        &mut self,
+        _channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        _request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        warn!("invoke deleteExpiredCommitLog start.");
+        if let Some(message_store) = self.broker_runtime_inner.message_store() {
+            message_store.execute_delete_files_manually();
+        }
+        warn!("invoke deleteExpiredCommitLog end.");
+        Some(RemotingCommand::create_response_command())
+    }
+
+    pub async fn check_rocksdb_cq_write_progress(
+        &mut self,
+        _channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        let request_header = request
+            .decode_command_custom_header::<CheckRocksdbCqWriteProgressRequestHeader>()
+            .unwrap();
+        let diff_result = self
+            .broker_runtime_inner
+            .message_store()
+            .as_ref()
+            .map(|message_store| {
+                message_store.check_rocksdb_cq_write_progress(&request_header.topic)
+            });
+        let body = CheckRocksdbCqWriteProgressResponseBody { diff_result };
+        Some(
+            RemotingCommand::create_response_command()
+                .set_body(serde_json::to_string(&body).unwrap()),
+        )
+    }
+
     pub async fn get_broker_runtime_info(
         &mut self,
         _channel: Channel,
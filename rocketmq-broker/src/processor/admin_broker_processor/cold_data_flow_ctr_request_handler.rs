@@ -0,0 +1,147 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use cheetah_string::CheetahString;
+use rocketmq_common::common::mix_all;
+use rocketmq_remoting::code::request_code::RequestCode;
+use rocketmq_remoting::code::response_code::ResponseCode;
+use rocketmq_remoting::net::channel::Channel;
+use rocketmq_remoting::protocol::remoting_command::RemotingCommand;
+use rocketmq_remoting::runtime::connection_handler_context::ConnectionHandlerContext;
+use rocketmq_rust::ArcMut;
+use rocketmq_store::base::message_store::MessageStore;
+use tracing::info;
+
+use crate::broker_runtime::BrokerRuntimeInner;
+use crate::coldctr::cold_data_cg_ctr_service::ColdDataCgCtrService;
+
+#[derive(Clone)]
+pub(super) struct ColdDataFlowCtrRequestHandler<MS: MessageStore> {
+    broker_runtime_inner: ArcMut<BrokerRuntimeInner<MS>>,
+}
+
+impl<MS: MessageStore> ColdDataFlowCtrRequestHandler<MS> {
+    pub fn new(broker_runtime_inner: ArcMut<BrokerRuntimeInner<MS>>) -> Self {
+        Self {
+            broker_runtime_inner,
+        }
+    }
+}
+
+impl<MS: MessageStore> ColdDataFlowCtrRequestHandler<MS> {
+    /// The body is a properties document mapping consumer groups to their cold-read thresholds.
+    pub async fn update_cold_data_flow_ctr_group_config(
+        &mut self,
+        channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        info!(
+            "updateColdDataFlowCtrGroupConfig called by {}",
+            channel.remote_address()
+        );
+        let service = match self.cold_data_cg_ctr_service() {
+            Ok(service) => service,
+            Err(response) => return Some(response),
+        };
+        let Some(body) = request.get_body() else {
+            return Some(RemotingCommand::create_response_command());
+        };
+        let body = String::from_utf8_lossy(body.as_ref());
+        let Some(properties) = mix_all::string_to_properties(&body) else {
+            return Some(RemotingCommand::create_response_command_with_code_remark(
+                ResponseCode::SystemError,
+                "string2Properties error",
+            ));
+        };
+        let mut thresholds = Vec::with_capacity(properties.len());
+        for (consumer_group, threshold) in properties {
+            match threshold.trim().parse::<i64>() {
+                Ok(threshold) => thresholds.push((consumer_group, threshold)),
+                Err(_) => {
+                    return Some(RemotingCommand::create_response_command_with_code_remark(
+                        ResponseCode::SystemError,
+                        format!("invalid cold read threshold {threshold} of {consumer_group}"),
+                    ));
+                }
+            }
+        }
+        for (consumer_group, threshold) in thresholds {
+            service.add_or_update_group_config(consumer_group, threshold);
+        }
+        Some(RemotingCommand::create_response_command())
+    }
+
+    /// The body is the name of the consumer group whose threshold is removed.
+    pub async fn remove_cold_data_flow_ctr_group_config(
+        &mut self,
+        channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        info!(
+            "removeColdDataFlowCtrGroupConfig called by {}",
+            channel.remote_address()
+        );
+        let service = match self.cold_data_cg_ctr_service() {
+            Ok(service) => service,
+            Err(response) => return Some(response),
+        };
+        let consumer_group = request
+            .get_body()
+            .map(|body| CheetahString::from(String::from_utf8_lossy(body.as_ref()).trim()))
+            .unwrap_or_default();
+        if consumer_group.is_empty() {
+            return Some(RemotingCommand::create_response_command_with_code_remark(
+                ResponseCode::SystemError,
+                "consumerGroup is blank",
+            ));
+        }
+        service.remove_group_config(&consumer_group);
+        Some(RemotingCommand::create_response_command())
+    }
+
+    pub async fn get_cold_data_flow_ctr_info(
+        &mut self,
+        _channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        _request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        let service = match self.cold_data_cg_ctr_service() {
+            Ok(service) => service,
+            Err(response) => return Some(response),
+        };
+        Some(
+            RemotingCommand::create_response_command()
+                .set_body(service.get_cold_data_flow_ctr_info()),
+        )
+    }
+
+    fn cold_data_cg_ctr_service(&self) -> Result<&ColdDataCgCtrService, RemotingCommand> {
+        self.broker_runtime_inner
+            .cold_data_cg_ctr_service()
+            .as_ref()
+            .ok_or_else(|| {
+                RemotingCommand::create_response_command_with_code_remark(
+                    ResponseCode::SystemError,
+                    "ColdDataCgCtrService is not available",
+                )
+            })
+    }
+}
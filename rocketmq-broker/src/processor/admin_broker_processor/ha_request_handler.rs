@@ -0,0 +1,91 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use rocketmq_common::common::mix_all;
+use rocketmq_remoting::code::request_code::RequestCode;
+use rocketmq_remoting::code::response_code::ResponseCode;
+use rocketmq_remoting::net::channel::Channel;
+use rocketmq_remoting::protocol::header::controller::exchange_ha_info_request_header::ExchangeHAInfoRequestHeader;
+use rocketmq_remoting::protocol::header::controller::exchange_ha_info_request_header::ExchangeHAInfoResponseHeader;
+use rocketmq_remoting::protocol::remoting_command::RemotingCommand;
+use rocketmq_remoting::runtime::connection_handler_context::ConnectionHandlerContext;
+use rocketmq_rust::ArcMut;
+use rocketmq_store::base::message_store::MessageStore;
+
+use crate::broker_runtime::BrokerRuntimeInner;
+
+#[derive(Clone)]
+pub(super) struct HARequestHandler<MS: MessageStore> {
+    broker_runtime_inner: ArcMut<BrokerRuntimeInner<MS>>,
+}
+
+impl<MS: MessageStore> HARequestHandler<MS> {
+    pub fn new(broker_runtime_inner: ArcMut<BrokerRuntimeInner<MS>>) -> Self {
+        Self {
+            broker_runtime_inner,
+        }
+    }
+}
+
+impl<MS: MessageStore> HARequestHandler<MS> {
+    /// A request carrying the master HA address tells a slave where to replicate from, an empty
+    /// one asks the master for its HA info.
+    pub async fn exchange_ha_info(
+        &mut self,
+        _channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        let request_header =
+            match request.decode_command_custom_header::<ExchangeHAInfoRequestHeader>() {
+                Ok(header) => header,
+                Err(e) => {
+                    return Some(RemotingCommand::create_response_command_with_code_remark(
+                        ResponseCode::SystemError,
+                        format!("decode ExchangeHAInfoRequestHeader failed: {}", e),
+                    ));
+                }
+            };
+        let message_store = self.broker_runtime_inner.message_store().as_ref()?;
+        match request_header.master_ha_address {
+            Some(master_ha_address) if !master_ha_address.is_empty() => {
+                message_store.update_ha_master_address(&master_ha_address);
+                if let Some(master_address) = request_header.master_address {
+                    message_store.update_master_address(&master_address);
+                }
+                Some(RemotingCommand::create_response_command())
+            }
+            _ if self
+                .broker_runtime_inner
+                .broker_config()
+                .broker_identity
+                .broker_id
+                == mix_all::MASTER_ID =>
+            {
+                let response_header = ExchangeHAInfoResponseHeader {
+                    master_ha_address: Some(self.broker_runtime_inner.get_ha_server_addr()),
+                    master_flush_offset: Some(message_store.get_broker_init_max_offset()),
+                    master_address: Some(self.broker_runtime_inner.get_broker_addr().clone()),
+                };
+                Some(RemotingCommand::create_response_command_with_header(
+                    response_header,
+                ))
+            }
+            _ => Some(RemotingCommand::create_response_command()),
+        }
+    }
+}
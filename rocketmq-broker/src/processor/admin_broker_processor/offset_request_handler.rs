@@ -14,24 +14,38 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::collections::HashMap;
+
+use cheetah_string::CheetahString;
+use rocketmq_common::common::boundary_type::BoundaryType;
+use rocketmq_common::common::broker::broker_role::BrokerRole;
+use rocketmq_common::common::message::message_queue::MessageQueue;
 use rocketmq_remoting::code::request_code::RequestCode;
 use rocketmq_remoting::code::response_code::ResponseCode;
 use rocketmq_remoting::net::channel::Channel;
+use rocketmq_remoting::protocol::body::reset_offset_body::ResetOffsetBody;
 use rocketmq_remoting::protocol::header::get_max_offset_request_header::GetMaxOffsetRequestHeader;
 use rocketmq_remoting::protocol::header::get_max_offset_response_header::GetMaxOffsetResponseHeader;
 use rocketmq_remoting::protocol::header::get_min_offset_request_header::GetMinOffsetRequestHeader;
 use rocketmq_remoting::protocol::header::get_min_offset_response_header::GetMinOffsetResponseHeader;
 use rocketmq_remoting::protocol::header::message_operation_header::TopicRequestHeaderTrait;
+use rocketmq_remoting::protocol::header::reset_offset_request_header::ResetOffsetRequestHeader;
+use rocketmq_remoting::protocol::header::search_offset_request_header::SearchOffsetRequestHeader;
+use rocketmq_remoting::protocol::header::search_offset_response_header::SearchOffsetResponseHeader;
 use rocketmq_remoting::protocol::remoting_command::RemotingCommand;
 use rocketmq_remoting::protocol::static_topic::topic_queue_mapping_context::TopicQueueMappingContext;
 use rocketmq_remoting::protocol::static_topic::topic_queue_mapping_utils::TopicQueueMappingUtils;
+use rocketmq_remoting::protocol::RemotingSerializable;
 use rocketmq_remoting::rpc::rpc_client::RpcClient;
 use rocketmq_remoting::rpc::rpc_request::RpcRequest;
 use rocketmq_remoting::runtime::connection_handler_context::ConnectionHandlerContext;
 use rocketmq_rust::ArcMut;
 use rocketmq_store::base::message_store::MessageStore;
+use tracing::info;
+use tracing::warn;
 
 use crate::broker_runtime::BrokerRuntimeInner;
+use crate::client::net::broker_to_client::Broker2Client;
 
 #[derive(Clone)]
 pub(super) struct OffsetRequestHandler<MS: MessageStore> {
@@ -117,6 +131,217 @@ impl<MS: MessageStore> OffsetRequestHandler<MS> {
             response_header,
         ))
     }
+    pub async fn search_offset_by_timestamp(
+        &mut self,
+        _channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        let request_header = match request
+            .decode_command_custom_header::<SearchOffsetRequestHeader>()
+        {
+            Ok(header) => header,
+            Err(e) => {
+                return Some(
+                    RemotingCommand::create_response_command_with_code(ResponseCode::SystemError)
+                        .set_remark(format!("decode SearchOffsetRequestHeader failed: {e}")),
+                );
+            }
+        };
+        let boundary_type = request_header
+            .boundary_type
+            .as_ref()
+            .and_then(|name| BoundaryType::get_type(name.as_str()))
+            .unwrap_or(BoundaryType::Lower);
+        let offset = self
+            .broker_runtime_inner
+            .message_store()
+            .as_ref()
+            .unwrap()
+            .get_offset_in_queue_by_time_with_boundary(
+                &request_header.topic,
+                request_header.queue_id,
+                request_header.timestamp,
+                boundary_type,
+            );
+        Some(RemotingCommand::create_response_command_with_header(
+            SearchOffsetResponseHeader { offset },
+        ))
+    }
+
+    pub async fn reset_offset(
+        &mut self,
+        channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        _request_code: RequestCode,
+        request: RemotingCommand,
+    ) -> Option<RemotingCommand> {
+        let request_header = match request
+            .decode_command_custom_header::<ResetOffsetRequestHeader>()
+        {
+            Ok(header) => header,
+            Err(e) => {
+                return Some(
+                    RemotingCommand::create_response_command_with_code(ResponseCode::SystemError)
+                        .set_remark(format!("decode ResetOffsetRequestHeader failed: {e}")),
+                );
+            }
+        };
+        info!(
+            "[reset-offset] reset offset started by {}. topic={}, group={}, timestamp={}, \
+             queueId={}, offset={:?}, isForce={}",
+            channel.remote_address(),
+            request_header.topic,
+            request_header.group,
+            request_header.timestamp,
+            request_header.queue_id,
+            request_header.offset,
+            request_header.is_force
+        );
+        if BrokerRole::Slave == self.broker_runtime_inner.message_store_config().broker_role {
+            return Some(
+                RemotingCommand::create_response_command_with_code(ResponseCode::SystemError)
+                    .set_remark("Can not reset offset in slave broker"),
+            );
+        }
+        let topic = &request_header.topic;
+        let group = &request_header.group;
+        let Some(topic_config) = self
+            .broker_runtime_inner
+            .topic_config_manager()
+            .select_topic_config(topic)
+        else {
+            return Some(
+                RemotingCommand::create_response_command_with_code(ResponseCode::TopicNotExist)
+                    .set_remark(format!("Topic {topic} does not exist")),
+            );
+        };
+
+        let message_store = self.broker_runtime_inner.message_store().as_ref().unwrap();
+        let consumer_offset_manager = self.broker_runtime_inner.consumer_offset_manager();
+        let broker_name = &self.broker_runtime_inner.broker_config().broker_name;
+        let queue_ids = if request_header.queue_id >= 0 {
+            vec![request_header.queue_id]
+        } else {
+            (0..topic_config.read_queue_nums as i32).collect()
+        };
+        let mut offset_table = HashMap::with_capacity(queue_ids.len());
+        for queue_id in queue_ids {
+            let explicit_offset = request_header
+                .offset
+                .filter(|offset| request_header.queue_id >= 0 && *offset != -1);
+            let offset = match explicit_offset {
+                Some(offset) => {
+                    let min_offset = message_store.get_min_offset_in_queue(topic, queue_id);
+                    let max_offset = message_store.get_max_offset_in_queue(topic, queue_id);
+                    if offset < min_offset || offset > max_offset {
+                        return Some(
+                            RemotingCommand::create_response_command_with_code(
+                                ResponseCode::SystemError,
+                            )
+                            .set_remark(format!(
+                                "Target offset {offset} not in consume queue range \
+                                 [{min_offset}-{max_offset}]"
+                            )),
+                        );
+                    }
+                    offset
+                }
+                None => {
+                    let timestamp_offset = if request_header.timestamp == -1 {
+                        message_store.get_max_offset_in_queue(topic, queue_id)
+                    } else {
+                        message_store.get_offset_in_queue_by_time(
+                            topic,
+                            queue_id,
+                            request_header.timestamp,
+                        )
+                    }
+                    .max(0);
+                    // Without force, only rewind: a consumer behind the target keeps its offset.
+                    let consumer_offset =
+                        consumer_offset_manager.query_offset(group, topic, queue_id);
+                    if request_header.is_force
+                        || consumer_offset < 0
+                        || timestamp_offset < consumer_offset
+                    {
+                        timestamp_offset
+                    } else {
+                        consumer_offset
+                    }
+                }
+            };
+            offset_table.insert(
+                MessageQueue::from_parts(topic.clone(), broker_name.clone(), queue_id),
+                offset,
+            );
+        }
+
+        let client_host = CheetahString::from_string(channel.remote_address().to_string());
+        for (mq, offset) in &offset_table {
+            if self
+                .broker_runtime_inner
+                .broker_config()
+                .use_server_side_reset_offset
+            {
+                consumer_offset_manager.assign_reset_offset(
+                    topic,
+                    group,
+                    mq.get_queue_id(),
+                    *offset,
+                );
+            } else {
+                consumer_offset_manager.commit_offset(
+                    client_host.clone(),
+                    group,
+                    topic,
+                    mq.get_queue_id(),
+                    *offset,
+                );
+            }
+        }
+
+        let body = ResetOffsetBody { offset_table };
+        match self
+            .broker_runtime_inner
+            .consumer_manager()
+            .get_consumer_group_info(group)
+        {
+            Some(consumer_group_info)
+                if !consumer_group_info.get_channel_info_table().is_empty() =>
+            {
+                if let Err(e) = Broker2Client
+                    .reset_offset(&consumer_group_info, request_header.clone(), &body)
+                    .await
+                {
+                    warn!(
+                        "[reset-offset] notify clients failed. group={}, error={}",
+                        group, e
+                    );
+                }
+            }
+            _ => info!(
+                "[reset-offset] consumer group {} is not online, the new offsets take effect when \
+                 it starts",
+                group
+            ),
+        }
+
+        let mut response = RemotingCommand::create_response_command();
+        match body.encode() {
+            Ok(body) => response.set_body_mut_ref(body),
+            Err(e) => {
+                return Some(
+                    response
+                        .set_code(ResponseCode::SystemError)
+                        .set_remark(format!("encode ResetOffsetBody failed: {e}")),
+                );
+            }
+        }
+        Some(response)
+    }
+
     /*
     async fn handle_get_min_offset(
         &mut self,
@@ -14,22 +14,263 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use bytes::Bytes;
+use bytes::BytesMut;
+use cheetah_string::CheetahString;
+use rand::Rng;
+use rocketmq_common::common::constant::PermName;
+use rocketmq_common::common::FAQUrl;
 use rocketmq_remoting::code::request_code::RequestCode;
+use rocketmq_remoting::code::response_code::ResponseCode;
 use rocketmq_remoting::net::channel::Channel;
+use rocketmq_remoting::protocol::header::peek_message_request_header::PeekMessageRequestHeader;
+use rocketmq_remoting::protocol::header::pop_message_response_header::PopMessageResponseHeader;
 use rocketmq_remoting::protocol::remoting_command::RemotingCommand;
 use rocketmq_remoting::runtime::connection_handler_context::ConnectionHandlerContext;
+use rocketmq_rust::ArcMut;
+use rocketmq_store::base::get_message_result::GetMessageResult;
+use rocketmq_store::base::message_status_enum::GetMessageStatus;
+use rocketmq_store::base::message_store::MessageStore;
 
-#[derive(Default)]
-pub struct PeekMessageProcessor {}
+use crate::broker_runtime::BrokerRuntimeInner;
 
-impl PeekMessageProcessor {
+/// Serves `PEEK_MESSAGE`: reads messages from the head of a queue on behalf of a consumer
+/// group without appending pop checkpoints or moving the committed consume offset.
+pub struct PeekMessageProcessor<MS: MessageStore> {
+    broker_runtime_inner: ArcMut<BrokerRuntimeInner<MS>>,
+}
+
+impl<MS: MessageStore> PeekMessageProcessor<MS> {
+    pub fn new(broker_runtime_inner: ArcMut<BrokerRuntimeInner<MS>>) -> Self {
+        Self {
+            broker_runtime_inner,
+        }
+    }
+}
+
+impl<MS> PeekMessageProcessor<MS>
+where
+    MS: MessageStore + Send + Sync + 'static,
+{
     pub async fn process_request(
         &mut self,
-        _channel: Channel,
+        channel: Channel,
         _ctx: ConnectionHandlerContext,
         _request_code: RequestCode,
-        _request: RemotingCommand,
+        request: RemotingCommand,
     ) -> rocketmq_error::RocketMQResult<Option<RemotingCommand>> {
-        unimplemented!("PeekMessageProcessor.process_request")
+        let request_header = request.decode_command_custom_header::<PeekMessageRequestHeader>()?;
+        let broker_config = self.broker_runtime_inner.broker_config();
+
+        if !PermName::is_readable(broker_config.broker_permission) {
+            return Ok(Some(
+                RemotingCommand::create_response_command_with_code_remark(
+                    ResponseCode::NoPermission,
+                    format!(
+                        "the broker[{}] peeking message is forbidden",
+                        broker_config.broker_ip1
+                    ),
+                ),
+            ));
+        }
+
+        let Some(topic_config) = self
+            .broker_runtime_inner
+            .topic_config_manager()
+            .select_topic_config(&request_header.topic)
+        else {
+            return Ok(Some(
+                RemotingCommand::create_response_command_with_code_remark(
+                    ResponseCode::TopicNotExist,
+                    format!(
+                        "topic[{}] not exist, apply first please! {}",
+                        request_header.topic,
+                        FAQUrl::suggest_todo(FAQUrl::APPLY_TOPIC_URL)
+                    ),
+                ),
+            ));
+        };
+        if !PermName::is_readable(topic_config.perm) {
+            return Ok(Some(
+                RemotingCommand::create_response_command_with_code_remark(
+                    ResponseCode::NoPermission,
+                    format!(
+                        "the topic[{}] peeking message is forbidden",
+                        request_header.topic
+                    ),
+                ),
+            ));
+        }
+        if request_header.queue_id >= topic_config.read_queue_nums as i32 {
+            return Ok(Some(
+                RemotingCommand::create_response_command_with_code_remark(
+                    ResponseCode::SystemError,
+                    format!(
+                        "queueId[{}] is illegal, topic:[{}] topicConfig.readQueueNums:[{}] \
+                         consumer:[{}]",
+                        request_header.queue_id,
+                        request_header.topic,
+                        topic_config.read_queue_nums,
+                        channel.remote_address()
+                    ),
+                ),
+            ));
+        }
+
+        let Some(subscription_group_config) = self
+            .broker_runtime_inner
+            .subscription_group_manager()
+            .find_subscription_group_config(&request_header.consumer_group)
+        else {
+            return Ok(Some(
+                RemotingCommand::create_response_command_with_code_remark(
+                    ResponseCode::SubscriptionGroupNotExist,
+                    format!(
+                        "subscription group [{}] does not exist, {}",
+                        request_header.consumer_group,
+                        FAQUrl::suggest_todo(FAQUrl::SUBSCRIPTION_GROUP_NOT_EXIST)
+                    ),
+                ),
+            ));
+        };
+        if !subscription_group_config.consume_enable() {
+            return Ok(Some(
+                RemotingCommand::create_response_command_with_code_remark(
+                    ResponseCode::NoPermission,
+                    format!(
+                        "subscription group no permission, {}",
+                        request_header.consumer_group
+                    ),
+                ),
+            ));
+        }
+
+        let mut get_message_result =
+            GetMessageResult::new_result_size(request_header.max_msg_nums as usize);
+        let mut rest_num = 0;
+        if request_header.queue_id < 0 {
+            // peek every readable queue, starting from a random one so that a single hot queue
+            // does not always fill the whole batch
+            let read_queue_nums = topic_config.read_queue_nums as i32;
+            let random_q = rand::rng().random_range(0..100);
+            for i in 0..read_queue_nums {
+                let queue_id = (random_q + i) % read_queue_nums;
+                rest_num = self
+                    .peek_msg_from_queue(
+                        &request_header,
+                        &request_header.topic,
+                        queue_id,
+                        rest_num,
+                        &mut get_message_result,
+                    )
+                    .await;
+            }
+        } else {
+            rest_num = self
+                .peek_msg_from_queue(
+                    &request_header,
+                    &request_header.topic,
+                    request_header.queue_id,
+                    rest_num,
+                    &mut get_message_result,
+                )
+                .await;
+        }
+
+        let mut response = if get_message_result.message_mapped_list().is_empty() {
+            get_message_result.set_status(Some(GetMessageStatus::NoMessageInQueue));
+            RemotingCommand::create_response_command_with_code(ResponseCode::PullNotFound)
+        } else {
+            get_message_result.set_status(Some(GetMessageStatus::Found));
+            let mut response = RemotingCommand::create_response_command();
+            if let Some(body) = self.read_get_message_result(&get_message_result) {
+                response.set_body_mut_ref(body);
+            }
+            response
+        };
+        response.set_opaque_mut(request.opaque());
+        response.set_remark_mut(get_message_result.status().unwrap().to_string());
+        // peek never hands out a checkpoint, so the pop specific fields stay zero
+        response.set_command_custom_header_ref(PopMessageResponseHeader {
+            rest_num: rest_num as u64,
+            ..Default::default()
+        });
+        Ok(Some(response))
+    }
+
+    /// Appends up to the remaining batch size of messages from one queue to
+    /// `get_message_result`, starting at the group's committed offset (or the queue's minimum
+    /// offset when the group has never committed), and returns the updated rest number.
+    async fn peek_msg_from_queue(
+        &self,
+        request_header: &PeekMessageRequestHeader,
+        topic: &CheetahString,
+        queue_id: i32,
+        rest_num: i64,
+        get_message_result: &mut GetMessageResult,
+    ) -> i64 {
+        let message_store = self.broker_runtime_inner.message_store().as_ref().unwrap();
+        let mut offset = self
+            .broker_runtime_inner
+            .consumer_offset_manager()
+            .query_offset(&request_header.consumer_group, topic, queue_id);
+        if offset < 0 {
+            offset = message_store.get_min_offset_in_queue(topic, queue_id);
+        }
+        let rest_num = rest_num + message_store.get_max_offset_in_queue(topic, queue_id) - offset;
+        let remaining = request_header.max_msg_nums as i32
+            - get_message_result.message_mapped_list().len() as i32;
+        if remaining <= 0 {
+            return rest_num;
+        }
+
+        let mut result = message_store
+            .get_message(
+                &request_header.consumer_group,
+                topic,
+                queue_id,
+                offset,
+                remaining,
+                None,
+            )
+            .await;
+        // the committed offset may lag behind the store, retry from where the store suggests
+        if let Some(status) = result.as_ref().and_then(|result| result.status()) {
+            if matches!(
+                status,
+                GetMessageStatus::OffsetTooSmall
+                    | GetMessageStatus::OffsetOverflowBadly
+                    | GetMessageStatus::OffsetFoundNull
+            ) {
+                let next_begin_offset = result.as_ref().unwrap().next_begin_offset();
+                result = message_store
+                    .get_message(
+                        &request_header.consumer_group,
+                        topic,
+                        queue_id,
+                        next_begin_offset,
+                        remaining,
+                        None,
+                    )
+                    .await;
+            }
+        }
+        if let Some(result) = result {
+            for mapped_buffer in result.message_mapped_vec() {
+                get_message_result.add_message_inner(mapped_buffer);
+            }
+        }
+        rest_num
+    }
+
+    fn read_get_message_result(&self, get_message_result: &GetMessageResult) -> Option<Bytes> {
+        let mut bytes_mut =
+            BytesMut::with_capacity(get_message_result.buffer_total_size() as usize);
+        for msg in get_message_result.message_mapped_list() {
+            let data = &msg.mapped_file.as_ref().unwrap().get_mapped_file()
+                [msg.start_offset as usize..(msg.start_offset + msg.size as u64) as usize];
+            bytes_mut.extend_from_slice(data);
+        }
+        Some(bytes_mut.freeze())
     }
 }
@@ -14,22 +14,288 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::net::SocketAddr;
+
+use cheetah_string::CheetahString;
+use rocketmq_common::common::constant::PermName;
+use rocketmq_common::common::key_builder::KeyBuilder;
+use rocketmq_common::common::FAQUrl;
 use rocketmq_remoting::code::request_code::RequestCode;
+use rocketmq_remoting::code::response_code::ResponseCode;
 use rocketmq_remoting::net::channel::Channel;
+use rocketmq_remoting::protocol::header::polling_info_request_header::PollingInfoRequestHeader;
+use rocketmq_remoting::protocol::header::polling_info_response_header::PollingInfoResponseHeader;
 use rocketmq_remoting::protocol::remoting_command::RemotingCommand;
 use rocketmq_remoting::runtime::connection_handler_context::ConnectionHandlerContext;
+use rocketmq_rust::ArcMut;
+use rocketmq_store::base::message_store::MessageStore;
+
+use crate::broker_runtime::BrokerRuntimeInner;
+use crate::processor::pop_message_processor::PopMessageProcessor;
+
+/// Serves `POLLING_INFO`: reports how many POP long-polling requests are parked for a
+/// topic/group/queue.
+pub struct PollingInfoProcessor<MS: MessageStore> {
+    pop_message_processor: ArcMut<PopMessageProcessor<MS>>,
+    broker_runtime_inner: ArcMut<BrokerRuntimeInner<MS>>,
+}
 
-#[derive(Default)]
-pub struct PollingInfoProcessor {}
+impl<MS: MessageStore> PollingInfoProcessor<MS> {
+    pub fn new(
+        pop_message_processor: ArcMut<PopMessageProcessor<MS>>,
+        broker_runtime_inner: ArcMut<BrokerRuntimeInner<MS>>,
+    ) -> Self {
+        Self {
+            pop_message_processor,
+            broker_runtime_inner,
+        }
+    }
+}
 
-impl PollingInfoProcessor {
+impl<MS> PollingInfoProcessor<MS>
+where
+    MS: MessageStore + Send + Sync + 'static,
+{
     pub async fn process_request(
         &mut self,
-        _channel: Channel,
+        channel: Channel,
         _ctx: ConnectionHandlerContext,
         _request_code: RequestCode,
-        _request: RemotingCommand,
+        request: RemotingCommand,
     ) -> rocketmq_error::RocketMQResult<Option<RemotingCommand>> {
-        todo!()
+        let request_header = request.decode_command_custom_header::<PollingInfoRequestHeader>()?;
+        let response = self.polling_info(&request_header, channel.remote_address());
+        Ok(Some(response.set_opaque(request.opaque())))
+    }
+
+    fn polling_info(
+        &self,
+        request_header: &PollingInfoRequestHeader,
+        remote_address: SocketAddr,
+    ) -> RemotingCommand {
+        let broker_config = self.broker_runtime_inner.broker_config();
+        if !PermName::is_readable(broker_config.broker_permission) {
+            return RemotingCommand::create_response_command_with_code_remark(
+                ResponseCode::NoPermission,
+                format!(
+                    "the broker[{}] getting polling info is forbidden",
+                    broker_config.broker_ip1
+                ),
+            );
+        }
+
+        let Some(topic_config) = self
+            .broker_runtime_inner
+            .topic_config_manager()
+            .select_topic_config(&request_header.topic)
+        else {
+            return RemotingCommand::create_response_command_with_code_remark(
+                ResponseCode::TopicNotExist,
+                format!(
+                    "topic[{}] not exist, apply first please! {}",
+                    request_header.topic,
+                    FAQUrl::suggest_todo(FAQUrl::APPLY_TOPIC_URL)
+                ),
+            );
+        };
+        if !PermName::is_readable(topic_config.perm) {
+            return RemotingCommand::create_response_command_with_code_remark(
+                ResponseCode::NoPermission,
+                format!(
+                    "the topic[{}] getting polling info is forbidden",
+                    request_header.topic
+                ),
+            );
+        }
+        if request_header.queue_id >= topic_config.read_queue_nums as i32 {
+            return RemotingCommand::create_response_command_with_code_remark(
+                ResponseCode::SystemError,
+                format!(
+                    "queueId[{}] is illegal, topic:[{}] topicConfig.readQueueNums:[{}] \
+                     consumer:[{}]",
+                    request_header.queue_id,
+                    request_header.topic,
+                    topic_config.read_queue_nums,
+                    remote_address
+                ),
+            );
+        }
+
+        let Some(subscription_group_config) = self
+            .broker_runtime_inner
+            .subscription_group_manager()
+            .find_subscription_group_config(&request_header.consumer_group)
+        else {
+            return RemotingCommand::create_response_command_with_code_remark(
+                ResponseCode::SubscriptionGroupNotExist,
+                format!(
+                    "subscription group [{}] does not exist, {}",
+                    request_header.consumer_group,
+                    FAQUrl::suggest_todo(FAQUrl::SUBSCRIPTION_GROUP_NOT_EXIST)
+                ),
+            );
+        };
+        if !subscription_group_config.consume_enable() {
+            return RemotingCommand::create_response_command_with_code_remark(
+                ResponseCode::NoPermission,
+                format!(
+                    "subscription group no permission, {}",
+                    request_header.consumer_group
+                ),
+            );
+        }
+
+        let key = CheetahString::from_string(KeyBuilder::build_polling_key(
+            &request_header.topic,
+            &request_header.consumer_group,
+            request_header.queue_id,
+        ));
+        let polling_num = self
+            .pop_message_processor
+            .pop_long_polling_service()
+            .get_polling_num(&key);
+        RemotingCommand::create_response_command_with_header(PollingInfoResponseHeader {
+            polling_num,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rocketmq_common::common::broker::broker_config::BrokerConfig;
+    use rocketmq_common::common::config::TopicConfig;
+    use rocketmq_common::common::server::config::ServerConfig;
+    use rocketmq_remoting::protocol::subscription::subscription_group_config::SubscriptionGroupConfig;
+    use rocketmq_store::config::message_store_config::MessageStoreConfig;
+    use rocketmq_store::message_store::local_file_message_store::LocalFileMessageStore;
+
+    use super::*;
+    use crate::broker_runtime::BrokerRuntime;
+
+    const TOPIC: &str = "PollingTopic";
+    const WRITE_ONLY_TOPIC: &str = "WriteOnlyTopic";
+    const GROUP: &str = "PollingGroup";
+
+    /// Builds a broker that is never started. It spawns tasks on creation, so tests enter a
+    /// tokio runtime instead of running inside one, which would forbid dropping the broker's
+    /// own runtime threads.
+    fn new_runtime(
+        temp_dir: &tempfile::TempDir,
+        broker_config: BrokerConfig,
+    ) -> BrokerRuntime<LocalFileMessageStore> {
+        let root = temp_dir.path().to_string_lossy().into_owned();
+        let runtime = BrokerRuntime::new(
+            Arc::new(BrokerConfig {
+                store_path_root_dir: root.clone().into(),
+                auto_create_subscription_group: false,
+                ..broker_config
+            }),
+            Arc::new(MessageStoreConfig {
+                store_path_root_dir: root.into(),
+                ..MessageStoreConfig::default()
+            }),
+            Arc::new(ServerConfig::default()),
+        );
+        let mut inner = runtime.inner().clone();
+        let topic_config_manager = inner.topic_config_manager();
+        topic_config_manager.put_topic_config(TopicConfig::with_queues(TOPIC, 4, 4));
+        topic_config_manager.put_topic_config(TopicConfig::with_perm(
+            WRITE_ONLY_TOPIC,
+            4,
+            4,
+            PermName::PERM_WRITE,
+        ));
+        let mut subscription_group_config = SubscriptionGroupConfig::default();
+        subscription_group_config.set_group_name(GROUP.into());
+        inner
+            .subscription_group_manager_mut()
+            .update_subscription_group_config(&mut subscription_group_config);
+        runtime
+    }
+
+    fn polling_info(
+        runtime: &BrokerRuntime<LocalFileMessageStore>,
+        topic: &str,
+        group: &str,
+        queue_id: i32,
+    ) -> RemotingCommand {
+        let inner = runtime.inner().clone();
+        let processor =
+            PollingInfoProcessor::new(PopMessageProcessor::new_arc_mut(inner.clone()), inner);
+        processor.polling_info(
+            &PollingInfoRequestHeader {
+                consumer_group: group.into(),
+                topic: topic.into(),
+                queue_id,
+                topic_request_header: None,
+            },
+            "127.0.0.1:10000".parse().unwrap(),
+        )
+    }
+
+    #[test]
+    fn reports_parked_polling_requests() {
+        let tokio_runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = tokio_runtime.enter();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let runtime = new_runtime(&temp_dir, BrokerConfig::default());
+        let response = polling_info(&runtime, TOPIC, GROUP, 0);
+        assert_eq!(response.code(), ResponseCode::Success as i32);
+        let header = response
+            .read_custom_header_ref::<PollingInfoResponseHeader>()
+            .unwrap();
+        assert_eq!(header.polling_num, 0);
+    }
+
+    #[test]
+    fn missing_read_permission_is_denied() {
+        let tokio_runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = tokio_runtime.enter();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let runtime = new_runtime(
+            &temp_dir,
+            BrokerConfig {
+                broker_permission: PermName::PERM_WRITE,
+                ..BrokerConfig::default()
+            },
+        );
+        let response = polling_info(&runtime, TOPIC, GROUP, 0);
+        assert_eq!(response.code(), ResponseCode::NoPermission as i32);
+        assert!(response.remark().unwrap().contains("polling info"));
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let runtime = new_runtime(&temp_dir, BrokerConfig::default());
+        let response = polling_info(&runtime, WRITE_ONLY_TOPIC, GROUP, 0);
+        assert_eq!(response.code(), ResponseCode::NoPermission as i32);
+        assert!(response.remark().unwrap().contains(WRITE_ONLY_TOPIC));
+    }
+
+    #[test]
+    fn unknown_topic_or_group_is_rejected() {
+        let tokio_runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = tokio_runtime.enter();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let runtime = new_runtime(&temp_dir, BrokerConfig::default());
+        assert_eq!(
+            polling_info(&runtime, "UnknownTopic", GROUP, 0).code(),
+            ResponseCode::TopicNotExist as i32
+        );
+        assert_eq!(
+            polling_info(&runtime, TOPIC, "UnknownGroup", 0).code(),
+            ResponseCode::SubscriptionGroupNotExist as i32
+        );
+    }
+
+    #[test]
+    fn queue_id_beyond_read_queues_is_rejected() {
+        let tokio_runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = tokio_runtime.enter();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let runtime = new_runtime(&temp_dir, BrokerConfig::default());
+        let response = polling_info(&runtime, TOPIC, GROUP, 4);
+        assert_eq!(response.code(), ResponseCode::SystemError as i32);
+        assert!(response.remark().unwrap().contains("queueId[4] is illegal"));
     }
 }
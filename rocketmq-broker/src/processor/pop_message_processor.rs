@@ -402,8 +402,8 @@ where
             let message_filter =
                 if !ExpressionType::is_tag_type(Some(subscription_data.expression_type.as_str())) {
                     let consumer_filter_data = ConsumerFilterManager::build(
-                        request_header.consumer_group.clone(),
                         request_header.topic.clone(),
+                        request_header.consumer_group.clone(),
                         request_header.exp.clone(),
                         request_header.exp_type.clone(),
                         get_current_millis(),
@@ -1533,6 +1533,12 @@ impl<MS: MessageStore> PopMessageProcessor<MS> {
         )
     }
 
+    pub(crate) fn pop_long_polling_service(
+        &self,
+    ) -> &ArcMut<PopLongPollingService<MS, PopMessageProcessor<MS>>> {
+        &self.pop_long_polling_service
+    }
+
     pub fn pop_buffer_merge_service(&self) -> &ArcMut<PopBufferMergeService<MS>> {
         &self.pop_buffer_merge_service
     }
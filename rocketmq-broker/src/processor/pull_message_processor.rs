@@ -54,18 +54,17 @@ use tracing::warn;
 
 use crate::broker_runtime::BrokerRuntimeInner;
 use crate::client::consumer_group_info::ConsumerGroupInfo;
-use crate::coldctr::cold_data_cg_ctr_service::ColdDataCgCtrService;
 use crate::coldctr::cold_data_pull_request_hold_service::NO_SUSPEND_KEY;
 use crate::filter::expression_for_retry_message_filter::ExpressionForRetryMessageFilter;
 use crate::filter::expression_message_filter::ExpressionMessageFilter;
 use crate::filter::manager::consumer_filter_manager::ConsumerFilterManager;
+use crate::long_polling::pull_request::PullRequest;
 use crate::processor::default_pull_message_result_handler::DefaultPullMessageResultHandler;
 use crate::processor::pull_message_result_handler::PullMessageResultHandler;
 
 pub struct PullMessageProcessor<MS: MessageStore> {
     pull_message_result_handler: ArcMut<DefaultPullMessageResultHandler<MS>>,
     // write message to consume client runtime
-    cold_data_cg_ctr_service: Arc<ColdDataCgCtrService>,
     write_message_runtime: Arc<RocketMQRuntime>,
     // write message to consume client lock
     write_message_lock: Arc<Mutex<()>>,
@@ -83,7 +82,6 @@ where
         let cpus = num_cpus::get();
         Self {
             pull_message_result_handler,
-            cold_data_cg_ctr_service: Arc::new(Default::default()),
             write_message_runtime: Arc::new(RocketMQRuntime::new_multi(
                 cpus,
                 "write_consumer_message_runtime",
@@ -689,12 +687,67 @@ where
             )))
         };
 
-        //ColdDataFlow not implement
-
         cfg_if::cfg_if! {
             if #[cfg(feature = "local_file_store")] {
-                if self.cold_data_cg_ctr_service.is_cg_need_cold_data_flow_ctr(request_header.consumer_group.as_str()) {
-                    unimplemented!("ColdDataFlow not implement")
+                let cg_need_cold_data_flow_ctr = self
+                    .broker_runtime_inner
+                    .cold_data_cg_ctr_service()
+                    .as_ref()
+                    .is_some_and(|service| {
+                        service.is_cg_need_cold_data_flow_ctr(request_header.consumer_group.as_str())
+                    });
+                if cg_need_cold_data_flow_ctr {
+                    let is_msg_logic_cold = self
+                        .broker_runtime_inner
+                        .message_store()
+                        .as_ref()
+                        .unwrap()
+                        .is_msg_in_cold_area(
+                            &request_header.consumer_group,
+                            &request_header.topic,
+                            request_header.queue_id,
+                            request_header.queue_offset,
+                        );
+                    if is_msg_logic_cold {
+                        let consume_type = self
+                            .broker_runtime_inner
+                            .consumer_manager()
+                            .get_consumer_group_info(&request_header.consumer_group)
+                            .map(|info| info.get_consume_type());
+                        match consume_type {
+                            Some(ConsumeType::ConsumePassively) => {
+                                return Some(
+                                    response.set_code(ResponseCode::SystemBusy).set_remark(
+                                        "This consumer group is reading cold data. It has been \
+                                         flow control",
+                                    ),
+                                );
+                            }
+                            Some(ConsumeType::ConsumeActively) => {
+                                if broker_allow_flow_ctr_suspend {
+                                    let pull_request = PullRequest::new(
+                                        request,
+                                        channel,
+                                        ctx,
+                                        1000,
+                                        get_current_millis(),
+                                        request_header.queue_offset,
+                                        subscription_data,
+                                        message_filter,
+                                    );
+                                    if let Some(service) = self
+                                        .broker_runtime_inner
+                                        .cold_data_pull_request_hold_service()
+                                    {
+                                        service.suspend_cold_data_read_request(pull_request);
+                                    }
+                                    return None;
+                                }
+                                request_header.max_msg_nums = 1;
+                            }
+                            _ => {}
+                        }
+                    }
                 }
             }
         }
@@ -767,6 +820,12 @@ where
                                 .set_remark("store getMessage return None"),
                         );
                     }
+                    if let (Some(service), Some(result)) = (
+                        self.broker_runtime_inner.cold_data_cg_ctr_service(),
+                        result.as_ref(),
+                    ) {
+                        service.cold_acc(group, result.cold_data_sum());
+                    }
                     result
                 }
             };
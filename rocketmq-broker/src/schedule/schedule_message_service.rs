@@ -50,7 +50,7 @@ use rocketmq_store::base::message_result::PutMessageResult;
 use rocketmq_store::base::message_status_enum::PutMessageStatus;
 use rocketmq_store::base::message_store::MessageStore;
 use rocketmq_store::queue::consume_queue_store::ConsumeQueueStoreTrait;
-use rocketmq_store::queue::local_file_consume_queue_store::ConsumeQueueStore;
+use rocketmq_store::queue::consume_queue_store_impl::ConsumeQueueStoreImpl;
 use rocketmq_store::store_path_config_helper::get_delay_offset_store_path;
 use tokio::sync::Mutex;
 use tracing::error;
@@ -314,8 +314,8 @@ impl<MS: MessageStore> ScheduleMessageService<MS> {
                 .broker_controller
                 .message_store_unchecked()
                 .get_queue_store()
-                .downcast_ref::<ConsumeQueueStore>()
-                .expect("Failed to downcast to ConsumeQueueStore")
+                .downcast_ref::<ConsumeQueueStoreImpl>()
+                .expect("Failed to downcast to ConsumeQueueStoreImpl")
                 .find_or_create_consume_queue(&topic, queue_id);
 
             if let Some(current_delay_offset) = self.offset_table.get(delay_level) {
@@ -14,10 +14,16 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::process;
+
 use clap::Parser;
 use rocketmq_cli::command_line::Commands;
 use rocketmq_cli::command_line::RootCli;
 use rocketmq_cli::content_show::print_content;
+use rocketmq_cli::inspect::commit_log;
+use rocketmq_cli::inspect::consume_queue;
+use rocketmq_cli::inspect::index_file;
+use rocketmq_store::config::message_store_config::MessageStoreConfig;
 
 fn main() {
     let cli = RootCli::parse();
@@ -25,5 +31,56 @@ fn main() {
         Commands::ReadMessageLog { config, from, to } => {
             print_content(from, to, config);
         }
+        Commands::DumpCommitLog {
+            path,
+            from,
+            to,
+            body,
+            verify,
+        } => {
+            if verify {
+                exit_on_result(commit_log::verify(&path));
+            } else {
+                exit_on_result(commit_log::dump(&path, from, to, body).map(|_| true));
+            }
+        }
+        Commands::DumpConsumeQueue {
+            path,
+            from,
+            to,
+            verify,
+        } => {
+            if verify {
+                exit_on_result(consume_queue::verify(&path));
+            } else {
+                exit_on_result(consume_queue::dump(&path, from, to).map(|_| true));
+            }
+        }
+        Commands::DumpIndex {
+            path,
+            hash_slot_num,
+            verify,
+        } => {
+            let hash_slot_num =
+                hash_slot_num.unwrap_or(MessageStoreConfig::default().max_hash_slot_num as usize);
+            if verify {
+                exit_on_result(index_file::verify(&path, hash_slot_num));
+            } else {
+                exit_on_result(index_file::dump(&path, hash_slot_num).map(|_| true));
+            }
+        }
+    }
+}
+
+/// Exits with 1 when the file can not be read or holds corrupt data, so scripts can tell healthy
+/// files apart.
+fn exit_on_result(result: std::io::Result<bool>) {
+    match result {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
     }
 }
@@ -64,4 +64,94 @@ pub enum Commands {
         )]
         to: Option<u32>,
     },
+
+    #[command(
+        arg_required_else_help = true,
+        author = "mxsm",
+        version = "0.2.0",
+        about = "decode a commit log file to JSON lines, or verify its entries"
+    )]
+    DumpCommitLog {
+        #[arg(short, long, value_name = "FILE", help = "commit log file path")]
+        path: PathBuf,
+
+        #[arg(
+            short = 'f',
+            long,
+            value_name = "FROM",
+            help = "The number of the first message to print, start from 0"
+        )]
+        from: Option<u32>,
+
+        #[arg(
+            short = 't',
+            long,
+            value_name = "TO",
+            help = "The number of the message to stop printing at, defaults to the end of the file"
+        )]
+        to: Option<u32>,
+
+        #[arg(short, long, help = "print message bodies as UTF-8 text")]
+        body: bool,
+
+        #[arg(
+            long,
+            help = "check magic codes, entry sizes and body CRCs, and report torn or corrupt                     entries instead of printing messages"
+        )]
+        verify: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        author = "mxsm",
+        version = "0.2.0",
+        about = "dump the units of a consume queue file, or verify them"
+    )]
+    DumpConsumeQueue {
+        #[arg(short, long, value_name = "FILE", help = "consume queue file path")]
+        path: PathBuf,
+
+        #[arg(
+            short = 'f',
+            long,
+            value_name = "FROM",
+            help = "The number of the first unit to print, start from 0"
+        )]
+        from: Option<u32>,
+
+        #[arg(
+            short = 't',
+            long,
+            value_name = "TO",
+            help = "The number of the unit to stop printing at, defaults to the end of the file"
+        )]
+        to: Option<u32>,
+
+        #[arg(
+            long,
+            help = "check units for torn writes, gaps and unordered physical offsets"
+        )]
+        verify: bool,
+    },
+    #[command(
+        arg_required_else_help = true,
+        author = "mxsm",
+        version = "0.2.0",
+        about = "dump the header and entries of an index file, or verify them"
+    )]
+    DumpIndex {
+        #[arg(short, long, value_name = "FILE", help = "index file path")]
+        path: PathBuf,
+
+        #[arg(
+            short = 's',
+            long,
+            value_name = "NUM",
+            help = "The number of hash slots the broker was configured with, defaults to the \
+                    store default"
+        )]
+        hash_slot_num: Option<usize>,
+
+        #[arg(long, help = "check the header, hash slots and entry chains")]
+        verify: bool,
+    },
 }
@@ -0,0 +1,66 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Offline inspection of broker store files.
+//!
+//! Files are read into memory instead of being memory mapped through the store, so inspecting a
+//! crashed broker never writes to the files under investigation.
+
+pub mod commit_log;
+pub mod consume_queue;
+pub mod index_file;
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use bytes::Bytes;
+
+/// Reads the whole store file and the offset encoded in its file name.
+pub(crate) fn read_store_file(path: &Path) -> io::Result<(Bytes, Option<i64>)> {
+    let data = Bytes::from(fs::read(path)?);
+    Ok((data, file_from_offset(path)))
+}
+
+/// Commit log and consume queue files are named after the offset of their first byte. `None`
+/// means the file was renamed, so offsets can only be reported relative to the file.
+pub(crate) fn file_from_offset(path: &Path) -> Option<i64> {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.parse::<i64>().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn file_from_offset_parses_numeric_file_names() {
+        assert_eq!(
+            file_from_offset(&PathBuf::from("/store/commitlog/00000000001073741824")),
+            Some(1073741824)
+        );
+        assert_eq!(
+            file_from_offset(&PathBuf::from(
+                "/store/consumequeue/TopicTest/0/00000000000000000000"
+            )),
+            Some(0)
+        );
+        assert_eq!(file_from_offset(&PathBuf::from("/tmp/backup.log")), None);
+    }
+}
@@ -0,0 +1,531 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+use bytes::Buf;
+use bytes::Bytes;
+use rocketmq_common::common::message::message_decoder;
+use rocketmq_common::common::message::message_decoder::BLANK_MAGIC_CODE;
+use rocketmq_common::common::message::message_ext::MessageExt;
+use rocketmq_common::common::message::MessageTrait;
+use rocketmq_common::common::message::MessageVersion;
+use rocketmq_common::common::sys_flag::message_sys_flag::MessageSysFlag;
+use rocketmq_common::CRC32Utils::crc32;
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::inspect::read_store_file;
+
+/// Size of the total size and magic code fields leading every entry.
+const ENTRY_PREFIX_SIZE: usize = 8;
+
+#[derive(Debug)]
+pub enum CommitLogEntry {
+    Message {
+        position: usize,
+        message: Box<MessageExt>,
+    },
+    Corrupt {
+        position: usize,
+        reason: String,
+    },
+}
+
+/// How the scan of a commit log file ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EndOfData {
+    /// The scan has not finished yet.
+    #[default]
+    Pending,
+    /// The rest of the file was never written.
+    Unwritten,
+    /// The file was sealed with a blank end-of-file entry.
+    BlankPadding,
+    /// The last entry ends exactly at the end of the file.
+    EndOfFile,
+    /// A corrupt entry stopped the scan and no valid entry was found after it.
+    Corrupt,
+}
+
+impl fmt::Display for EndOfData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let desc = match self {
+            EndOfData::Pending => "pending",
+            EndOfData::Unwritten => "unwritten tail",
+            EndOfData::BlankPadding => "blank end-of-file padding",
+            EndOfData::EndOfFile => "end of file",
+            EndOfData::Corrupt => "corrupt entry",
+        };
+        write!(f, "{desc}")
+    }
+}
+
+/// Walks the entries of one commit log file.
+///
+/// Every entry is checked against the commit log layout before it is handed to
+/// [`message_decoder::decode`], so torn or corrupt entries are reported instead of panicking.
+pub struct CommitLogReader {
+    data: Bytes,
+    file_from_offset: Option<i64>,
+    position: usize,
+    resync: bool,
+    end: EndOfData,
+}
+
+impl CommitLogReader {
+    /// With `resync` the reader searches for the next valid entry after a corrupt one, otherwise
+    /// the first corrupt entry ends the scan.
+    pub fn new(data: Bytes, file_from_offset: Option<i64>, resync: bool) -> Self {
+        Self {
+            data,
+            file_from_offset,
+            position: 0,
+            resync,
+            end: EndOfData::Pending,
+        }
+    }
+
+    pub fn end(&self) -> EndOfData {
+        self.end
+    }
+
+    /// Position right after the last entry read.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    fn expected_physical_offset(&self, position: usize) -> Option<i64> {
+        self.file_from_offset.map(|offset| offset + position as i64)
+    }
+
+    fn check_at(&self, position: usize) -> Result<usize, String> {
+        check_entry(
+            &self.data[position..],
+            self.expected_physical_offset(position),
+        )
+    }
+
+    /// Finds the next position after `position` holding a valid entry.
+    fn next_valid_position(&self, position: usize) -> Option<usize> {
+        (position + 1..self.data.len().saturating_sub(ENTRY_PREFIX_SIZE - 1)).find(|candidate| {
+            let magic_code = (&self.data[candidate + 4..candidate + 8]).get_i32();
+            MessageVersion::value_of_magic_code(magic_code).is_ok()
+                && self.check_at(*candidate).is_ok()
+        })
+    }
+}
+
+impl Iterator for CommitLogReader {
+    type Item = CommitLogEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.end != EndOfData::Pending {
+            return None;
+        }
+        let position = self.position;
+        let remaining = &self.data[position..];
+        if remaining.is_empty() {
+            self.end = EndOfData::EndOfFile;
+            return None;
+        }
+        if remaining.len() < ENTRY_PREFIX_SIZE || (&remaining[..4]).get_i32() == 0 {
+            if remaining.iter().all(|byte| *byte == 0) {
+                self.end = EndOfData::Unwritten;
+                return None;
+            }
+        } else if (&remaining[4..8]).get_i32() == BLANK_MAGIC_CODE {
+            self.end = EndOfData::BlankPadding;
+            return None;
+        }
+
+        match self.check_at(position) {
+            Ok(total_size) => {
+                self.position += total_size;
+                let mut entry = self.data.slice(position..position + total_size);
+                // the layout has been checked, so decoding can not run out of bytes
+                match message_decoder::decode(&mut entry, true, false, false, false, false) {
+                    Some(message) => Some(CommitLogEntry::Message {
+                        position,
+                        message: Box::new(message),
+                    }),
+                    None => Some(CommitLogEntry::Corrupt {
+                        position,
+                        reason: "entry can not be decoded".to_string(),
+                    }),
+                }
+            }
+            Err(reason) => {
+                match self
+                    .resync
+                    .then(|| self.next_valid_position(position))
+                    .flatten()
+                {
+                    Some(next_position) => self.position = next_position,
+                    None => self.end = EndOfData::Corrupt,
+                }
+                Some(CommitLogEntry::Corrupt { position, reason })
+            }
+        }
+    }
+}
+
+/// Checks the entry at the start of `buf` against the commit log layout and returns its size.
+pub fn check_entry(buf: &[u8], expected_physical_offset: Option<i64>) -> Result<usize, String> {
+    if buf.len() < ENTRY_PREFIX_SIZE {
+        return Err(format!("torn entry, only {} bytes left", buf.len()));
+    }
+    let total_size = (&buf[..4]).get_i32();
+    let magic_code = (&buf[4..8]).get_i32();
+    let version = MessageVersion::value_of_magic_code(magic_code)
+        .map_err(|_| format!("illegal magic code 0x{magic_code:X}"))?;
+    if total_size < ENTRY_PREFIX_SIZE as i32 {
+        return Err(format!("illegal total size {total_size}"));
+    }
+    let total_size = total_size as usize;
+    if total_size > buf.len() {
+        return Err(format!(
+            "torn entry, total size {total_size} but only {} bytes left",
+            buf.len()
+        ));
+    }
+
+    let mut fields = FieldReader::new(&buf[ENTRY_PREFIX_SIZE..total_size]);
+    let body_crc = fields.take(4)?.get_u32();
+    // queue id, flag, queue offset
+    fields.take(4 + 4 + 8)?;
+    let physical_offset = fields.take(8)?.get_i64();
+    let sys_flag = fields.take(4)?.get_i32();
+    // born timestamp and born host
+    fields.take(8)?;
+    fields.take(host_length(sys_flag, MessageSysFlag::BORNHOST_V6_FLAG))?;
+    // store timestamp and store host
+    fields.take(8)?;
+    fields.take(host_length(
+        sys_flag,
+        MessageSysFlag::STOREHOSTADDRESS_V6_FLAG,
+    ))?;
+    // reconsume times, prepared transaction offset
+    fields.take(4 + 8)?;
+    let body_length = fields.take(4)?.get_i32();
+    if body_length < 0 {
+        return Err(format!("illegal body length {body_length}"));
+    }
+    let body = fields.take(body_length as usize)?;
+    let topic_length = match version {
+        MessageVersion::V1 => fields.take(1)?.get_u8() as usize,
+        MessageVersion::V2 => fields.take(2)?.get_i16() as usize,
+    };
+    fields.take(topic_length)?;
+    let properties_length = fields.take(2)?.get_i16();
+    if properties_length < 0 {
+        return Err(format!("illegal properties length {properties_length}"));
+    }
+    fields.take(properties_length as usize)?;
+    if fields.remaining() != 0 {
+        return Err(format!(
+            "total size {total_size} does not match its fields, {} bytes left over",
+            fields.remaining()
+        ));
+    }
+
+    let computed_crc = crc32(body);
+    if computed_crc != body_crc {
+        return Err(format!(
+            "body CRC mismatch, stored {body_crc} but computed {computed_crc}"
+        ));
+    }
+    if let Some(expected) = expected_physical_offset {
+        if physical_offset != expected {
+            return Err(format!(
+                "physical offset {physical_offset} does not match its position {expected}"
+            ));
+        }
+    }
+    Ok(total_size)
+}
+
+fn host_length(sys_flag: i32, v6_flag: i32) -> usize {
+    // address and port
+    if sys_flag & v6_flag == 0 {
+        4 + 4
+    } else {
+        16 + 4
+    }
+}
+
+struct FieldReader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> FieldReader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if len > self.buf.len() {
+            return Err(format!(
+                "torn entry, a field needs {len} bytes but only {} bytes are left",
+                self.buf.len()
+            ));
+        }
+        let (field, rest) = self.buf.split_at(len);
+        self.buf = rest;
+        Ok(field)
+    }
+
+    fn remaining(&self) -> usize {
+        self.buf.len()
+    }
+}
+
+/// Prints the messages of a commit log file as JSON lines, from the `from`-th message (start
+/// from 0) until the `to`-th one.
+pub fn dump(path: &Path, from: Option<u32>, to: Option<u32>, with_body: bool) -> io::Result<()> {
+    let (data, file_from_offset) = read_store_file(path)?;
+    let from = from.unwrap_or_default() as usize;
+    let to = to.map_or(usize::MAX, |to| to as usize);
+    let mut reader = CommitLogReader::new(data, file_from_offset, false);
+    for (index, entry) in reader.by_ref().enumerate().take(to).skip(from) {
+        match entry {
+            CommitLogEntry::Message { position, message } => {
+                println!("{}", message_to_json(position, &message, with_body));
+            }
+            CommitLogEntry::Corrupt { position, reason } => {
+                eprintln!("corrupt entry #{index} at position {position}: {reason}");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Summary of a verify run.
+#[derive(Debug, Default)]
+pub struct VerifyReport {
+    pub messages: usize,
+    pub corrupt_entries: Vec<(usize, String)>,
+    pub end: EndOfData,
+    pub end_position: usize,
+}
+
+impl VerifyReport {
+    pub fn is_ok(&self) -> bool {
+        self.corrupt_entries.is_empty()
+    }
+}
+
+pub fn verify_data(data: Bytes, file_from_offset: Option<i64>) -> VerifyReport {
+    let mut report = VerifyReport::default();
+    let mut reader = CommitLogReader::new(data, file_from_offset, true);
+    for entry in reader.by_ref() {
+        match entry {
+            CommitLogEntry::Message { .. } => report.messages += 1,
+            CommitLogEntry::Corrupt { position, reason } => {
+                report.corrupt_entries.push((position, reason))
+            }
+        }
+    }
+    report.end = reader.end();
+    report.end_position = reader.position();
+    report
+}
+
+/// Checks the magic code, layout and body CRC of every entry and prints the problems found.
+/// Returns whether the file is healthy.
+pub fn verify(path: &Path) -> io::Result<bool> {
+    let (data, file_from_offset) = read_store_file(path)?;
+    let report = verify_data(data, file_from_offset);
+    println!("file: {}", path.display());
+    println!("valid messages: {}", report.messages);
+    println!(
+        "scan ended at position {} ({})",
+        report.end_position, report.end
+    );
+    for (position, reason) in &report.corrupt_entries {
+        let offset = file_from_offset.map_or(String::new(), |offset| {
+            format!(", commit log offset {}", offset + *position as i64)
+        });
+        println!("CORRUPT at position {position}{offset}: {reason}");
+    }
+    Ok(report.is_ok())
+}
+
+fn message_to_json(position: usize, message: &MessageExt, with_body: bool) -> Value {
+    let properties: Map<String, Value> = message
+        .get_properties()
+        .iter()
+        .map(|(key, value)| (key.to_string(), Value::String(value.to_string())))
+        .collect();
+    let mut value = json!({
+        "position": position,
+        "commitLogOffset": message.commit_log_offset(),
+        "storeSize": message.store_size(),
+        "msgId": message.msg_id().as_str(),
+        "topic": message.get_topic().as_str(),
+        "queueId": message.queue_id(),
+        "queueOffset": message.queue_offset(),
+        "sysFlag": message.sys_flag(),
+        "bodyCRC": message.body_crc(),
+        "bornTimestamp": message.born_timestamp(),
+        "bornHost": message.born_host().to_string(),
+        "storeTimestamp": message.store_timestamp(),
+        "storeHost": message.store_host().to_string(),
+        "reconsumeTimes": message.reconsume_times(),
+        "preparedTransactionOffset": message.prepared_transaction_offset(),
+        "properties": properties,
+    });
+    if with_body {
+        value["body"] = Value::String(message.get_body().map_or(String::new(), |body| {
+            String::from_utf8_lossy(body).into_owned()
+        }));
+    }
+    value
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use bytes::BufMut;
+    use bytes::BytesMut;
+    use rocketmq_common::common::message::MESSAGE_MAGIC_CODE_V1;
+
+    use super::*;
+
+    /// Encodes a V1 commit log entry with IPv4 hosts.
+    pub(crate) fn encode_entry(physical_offset: i64, topic: &str, body: &[u8]) -> Bytes {
+        let properties = "TAGS\u{0001}TagA\u{0002}";
+        let total_size = 4
+            + 4
+            + 4
+            + 4
+            + 4
+            + 8
+            + 8
+            + 4
+            + 8
+            + 8
+            + 8
+            + 8
+            + 4
+            + 8
+            + 4
+            + body.len()
+            + 1
+            + topic.len()
+            + 2
+            + properties.len();
+        let mut buf = BytesMut::with_capacity(total_size);
+        buf.put_i32(total_size as i32);
+        buf.put_i32(MESSAGE_MAGIC_CODE_V1);
+        buf.put_u32(crc32(body));
+        buf.put_i32(1); // queue id
+        buf.put_i32(0); // flag
+        buf.put_i64(7); // queue offset
+        buf.put_i64(physical_offset);
+        buf.put_i32(0); // sys flag
+        buf.put_i64(1_700_000_000_000);
+        buf.put_slice(&[127, 0, 0, 1]);
+        buf.put_i32(50000);
+        buf.put_i64(1_700_000_000_001);
+        buf.put_slice(&[127, 0, 0, 1]);
+        buf.put_i32(10911);
+        buf.put_i32(0); // reconsume times
+        buf.put_i64(0); // prepared transaction offset
+        buf.put_i32(body.len() as i32);
+        buf.put_slice(body);
+        buf.put_u8(topic.len() as u8);
+        buf.put_slice(topic.as_bytes());
+        buf.put_i16(properties.len() as i16);
+        buf.put_slice(properties.as_bytes());
+        buf.freeze()
+    }
+
+    fn commit_log(entries: &[Bytes], tail: &[u8]) -> Bytes {
+        let mut buf = BytesMut::new();
+        for entry in entries {
+            buf.put_slice(entry);
+        }
+        buf.put_slice(tail);
+        buf.freeze()
+    }
+
+    #[test]
+    fn reads_messages_until_unwritten_tail() {
+        let first = encode_entry(0, "TopicTest", b"hello");
+        let second = encode_entry(first.len() as i64, "TopicTest", b"world");
+        let data = commit_log(&[first.clone(), second], &[0; 64]);
+
+        let mut reader = CommitLogReader::new(data, Some(0), false);
+        let messages: Vec<CommitLogEntry> = reader.by_ref().collect();
+        assert_eq!(messages.len(), 2);
+        match &messages[1] {
+            CommitLogEntry::Message { position, message } => {
+                assert_eq!(*position, first.len());
+                assert_eq!(message.get_topic().as_str(), "TopicTest");
+                assert_eq!(message.get_tags().unwrap().as_str(), "TagA");
+                assert_eq!(message.get_body().unwrap().as_ref(), b"world");
+            }
+            other => panic!("unexpected entry {other:?}"),
+        }
+        assert_eq!(reader.end(), EndOfData::Unwritten);
+
+        let json = message_to_json(0, &MessageExt::default(), true);
+        assert_eq!(json["body"], Value::String(String::new()));
+    }
+
+    #[test]
+    fn verify_reports_crc_mismatch_and_resyncs() {
+        let first = encode_entry(0, "TopicTest", b"hello");
+        let mut corrupted = BytesMut::from(encode_entry(first.len() as i64, "TopicTest", b"world"));
+        let body_position = corrupted
+            .windows(5)
+            .position(|window| window == b"world")
+            .unwrap();
+        corrupted[body_position] = b'W';
+        let corrupted = corrupted.freeze();
+        let third = encode_entry((first.len() + corrupted.len()) as i64, "TopicTest", b"!");
+        let mut blank = BytesMut::new();
+        blank.put_i32(32);
+        blank.put_i32(BLANK_MAGIC_CODE);
+        let data = commit_log(&[first.clone(), corrupted, third], &blank);
+
+        let report = verify_data(data, Some(0));
+        assert_eq!(report.messages, 2);
+        assert_eq!(report.corrupt_entries.len(), 1);
+        assert_eq!(report.corrupt_entries[0].0, first.len());
+        assert!(report.corrupt_entries[0].1.contains("CRC"));
+        assert_eq!(report.end, EndOfData::BlankPadding);
+    }
+
+    #[test]
+    fn verify_reports_torn_tail() {
+        let first = encode_entry(1024, "TopicTest", b"hello");
+        let second = encode_entry(1024 + first.len() as i64, "TopicTest", b"world");
+        let data = commit_log(&[first, second.slice(..second.len() - 3)], &[]);
+
+        let report = verify_data(data.clone(), Some(1024));
+        assert_eq!(report.messages, 1);
+        assert_eq!(report.end, EndOfData::Corrupt);
+        assert!(report.corrupt_entries[0].1.starts_with("torn entry"));
+
+        // the physical offset check catches files which were renamed or misplaced
+        let report = verify_data(data, Some(0));
+        assert!(report.corrupt_entries[0].1.contains("physical offset"));
+    }
+}
@@ -0,0 +1,202 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::io;
+use std::path::Path;
+
+use bytes::Buf;
+use rocketmq_store::queue::single_consume_queue::CQ_STORE_UNIT_SIZE;
+use rocketmq_store::queue::CqUnit;
+use tabled::Table;
+use tabled::Tabled;
+
+use crate::inspect::read_store_file;
+
+const UNIT_SIZE: usize = CQ_STORE_UNIT_SIZE as usize;
+
+/// Parses every complete unit of a consume queue file. The queue offset of a unit is derived from
+/// the file name when it holds the offset of the file.
+pub fn parse_units(data: &[u8], file_from_offset: Option<i64>) -> Vec<CqUnit> {
+    let first_queue_offset = file_from_offset.unwrap_or_default() / CQ_STORE_UNIT_SIZE as i64;
+    data.chunks_exact(UNIT_SIZE)
+        .enumerate()
+        .map(|(index, mut unit)| CqUnit {
+            queue_offset: first_queue_offset + index as i64,
+            pos: unit.get_i64(),
+            size: unit.get_i32(),
+            tags_code: unit.get_i64(),
+            ..CqUnit::default()
+        })
+        .collect()
+}
+
+/// Units written by the broker, as opposed to the zeroed tail of the file and the blank units
+/// used to fill the head of a queue.
+fn is_written(unit: &CqUnit) -> bool {
+    unit.pos >= 0 && unit.size > 0 && unit.size != i32::MAX
+}
+
+fn is_blank(unit: &CqUnit) -> bool {
+    unit.pos == 0 && unit.size == i32::MAX && unit.tags_code == 0
+}
+
+fn is_zeroed(unit: &CqUnit) -> bool {
+    unit.pos == 0 && unit.size == 0 && unit.tags_code == 0
+}
+
+#[derive(Tabled)]
+struct UnitPrint {
+    #[tabled(rename = "QueueOffset")]
+    queue_offset: i64,
+    #[tabled(rename = "PhyOffset")]
+    phy_offset: i64,
+    #[tabled(rename = "Size")]
+    size: i32,
+    #[tabled(rename = "TagsCode")]
+    tags_code: i64,
+}
+
+/// Prints the written units of a consume queue file, from the `from`-th unit (start from 0)
+/// until the `to`-th one.
+pub fn dump(path: &Path, from: Option<u32>, to: Option<u32>) -> io::Result<()> {
+    let (data, file_from_offset) = read_store_file(path)?;
+    let from = from.unwrap_or_default() as usize;
+    let to = to.map_or(usize::MAX, |to| to as usize);
+    let rows: Vec<UnitPrint> = parse_units(&data, file_from_offset)
+        .into_iter()
+        .take(to)
+        .skip(from)
+        .filter(|unit| !is_zeroed(unit))
+        .map(|unit| UnitPrint {
+            queue_offset: unit.queue_offset,
+            phy_offset: unit.pos,
+            size: unit.size,
+            tags_code: unit.tags_code,
+        })
+        .collect();
+    println!("file: {}, units: {}", path.display(), rows.len());
+    println!("{}", Table::new(rows));
+    Ok(())
+}
+
+/// Problems found in a consume queue file.
+pub fn verify_data(data: &[u8], file_from_offset: Option<i64>) -> Vec<String> {
+    let mut problems = Vec::new();
+    let units = parse_units(data, file_from_offset);
+    let mut end_of_data: Option<i64> = None;
+    let mut last_phy_offset: Option<i64> = None;
+    for unit in &units {
+        if is_blank(unit) {
+            continue;
+        }
+        if is_zeroed(unit) {
+            end_of_data.get_or_insert(unit.queue_offset);
+            continue;
+        }
+        if !is_written(unit) {
+            problems.push(format!(
+                "queue offset {}: illegal unit, phy offset {}, size {}",
+                unit.queue_offset, unit.pos, unit.size
+            ));
+            continue;
+        }
+        if let Some(end) = end_of_data {
+            problems.push(format!(
+                "queue offset {}: unit written after the end of data at queue offset {end}",
+                unit.queue_offset
+            ));
+        }
+        if let Some(last) = last_phy_offset {
+            if unit.pos <= last {
+                problems.push(format!(
+                    "queue offset {}: phy offset {} is not greater than the previous one {last}",
+                    unit.queue_offset, unit.pos
+                ));
+            }
+        }
+        last_phy_offset = Some(unit.pos);
+    }
+    let trailing = data.len() % UNIT_SIZE;
+    if trailing != 0 && data[data.len() - trailing..].iter().any(|byte| *byte != 0) {
+        problems.push(format!(
+            "torn unit, the last {trailing} bytes do not form a complete unit"
+        ));
+    }
+    problems
+}
+
+/// Checks the units of a consume queue file and prints the problems found. Returns whether the
+/// file is healthy.
+pub fn verify(path: &Path) -> io::Result<bool> {
+    let (data, file_from_offset) = read_store_file(path)?;
+    let problems = verify_data(&data, file_from_offset);
+    let written = parse_units(&data, file_from_offset)
+        .iter()
+        .filter(|unit| is_written(unit))
+        .count();
+    println!("file: {}", path.display());
+    println!("written units: {written}");
+    for problem in &problems {
+        println!("CORRUPT {problem}");
+    }
+    Ok(problems.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BufMut;
+    use bytes::BytesMut;
+
+    use super::*;
+
+    fn put_unit(buf: &mut BytesMut, phy_offset: i64, size: i32, tags_code: i64) {
+        buf.put_i64(phy_offset);
+        buf.put_i32(size);
+        buf.put_i64(tags_code);
+    }
+
+    #[test]
+    fn parse_units_derives_queue_offsets_from_file_name() {
+        let mut buf = BytesMut::new();
+        put_unit(&mut buf, 100, 200, 42);
+        put_unit(&mut buf, 300, 150, 43);
+        let units = parse_units(&buf, Some(6_000_000));
+        assert_eq!(units.len(), 2);
+        assert_eq!(units[0].queue_offset, 300_000);
+        assert_eq!(units[1].queue_offset, 300_001);
+        assert_eq!(units[1].pos, 300);
+        assert_eq!(units[1].size, 150);
+        assert_eq!(units[1].tags_code, 43);
+        assert!(verify_data(&buf, Some(6_000_000)).is_empty());
+    }
+
+    #[test]
+    fn verify_reports_disorder_gaps_and_torn_units() {
+        let mut buf = BytesMut::new();
+        put_unit(&mut buf, 0, i32::MAX, 0);
+        put_unit(&mut buf, 500, 100, 1);
+        put_unit(&mut buf, 400, 100, 1);
+        put_unit(&mut buf, 0, 0, 0);
+        put_unit(&mut buf, 600, 100, 1);
+        buf.put_i64(700);
+
+        let problems = verify_data(&buf, None);
+        assert_eq!(problems.len(), 3, "{problems:?}");
+        assert!(problems[0].starts_with("queue offset 2: phy offset 400"));
+        assert!(problems[1].contains("after the end of data at queue offset 3"));
+        assert!(problems[2].starts_with("torn unit"));
+    }
+}
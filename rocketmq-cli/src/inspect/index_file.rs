@@ -0,0 +1,283 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use bytes::Buf;
+use rocketmq_store::index::index_file::HASH_SLOT_SIZE;
+use rocketmq_store::index::index_file::INDEX_SIZE;
+use rocketmq_store::index::index_header::INDEX_HEADER_SIZE;
+use tabled::Table;
+use tabled::Tabled;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct IndexHeaderView {
+    pub begin_timestamp: i64,
+    pub end_timestamp: i64,
+    pub begin_phy_offset: i64,
+    pub end_phy_offset: i64,
+    pub hash_slot_count: i32,
+    pub index_count: i32,
+}
+
+#[derive(Debug, PartialEq, Eq, Tabled)]
+pub struct IndexEntryView {
+    #[tabled(rename = "Index")]
+    pub index: usize,
+    #[tabled(rename = "KeyHash")]
+    pub key_hash: i32,
+    #[tabled(rename = "PhyOffset")]
+    pub phy_offset: i64,
+    #[tabled(rename = "TimeDiff(s)")]
+    pub time_diff: i32,
+    #[tabled(rename = "PrevIndex")]
+    pub prev_index: i32,
+}
+
+/// An index file as laid out by the store: the header, `hash_slot_num` hash slots and the
+/// entries. Entry 0 is never written, so valid entries range from 1 until the index count.
+pub struct IndexFileView<'a> {
+    data: &'a [u8],
+    hash_slot_num: usize,
+}
+
+impl<'a> IndexFileView<'a> {
+    pub fn new(data: &'a [u8], hash_slot_num: usize) -> Result<Self, String> {
+        if data.len() < INDEX_HEADER_SIZE + hash_slot_num * HASH_SLOT_SIZE {
+            return Err(format!(
+                "file size {} is smaller than the header and {hash_slot_num} hash slots",
+                data.len()
+            ));
+        }
+        Ok(Self {
+            data,
+            hash_slot_num,
+        })
+    }
+
+    pub fn header(&self) -> IndexHeaderView {
+        let mut buf = &self.data[..INDEX_HEADER_SIZE];
+        IndexHeaderView {
+            begin_timestamp: buf.get_i64(),
+            end_timestamp: buf.get_i64(),
+            begin_phy_offset: buf.get_i64(),
+            end_phy_offset: buf.get_i64(),
+            hash_slot_count: buf.get_i32(),
+            index_count: buf.get_i32(),
+        }
+    }
+
+    /// Number of entries the file has room for, including the unused entry 0.
+    pub fn capacity(&self) -> usize {
+        (self.data.len() - self.entries_position()) / INDEX_SIZE
+    }
+
+    pub fn slot(&self, slot: usize) -> i32 {
+        let position = INDEX_HEADER_SIZE + slot * HASH_SLOT_SIZE;
+        (&self.data[position..position + HASH_SLOT_SIZE]).get_i32()
+    }
+
+    /// Entries from 1 until the index count of the header, as far as the file holds them.
+    pub fn entries(&self) -> impl Iterator<Item = IndexEntryView> + '_ {
+        let index_count = self.header().index_count.max(1) as usize;
+        (1..index_count.min(self.capacity())).map(|index| self.entry(index))
+    }
+
+    fn entries_position(&self) -> usize {
+        INDEX_HEADER_SIZE + self.hash_slot_num * HASH_SLOT_SIZE
+    }
+
+    fn entry(&self, index: usize) -> IndexEntryView {
+        let position = self.entries_position() + index * INDEX_SIZE;
+        let mut buf = &self.data[position..position + INDEX_SIZE];
+        IndexEntryView {
+            index,
+            key_hash: buf.get_i32(),
+            phy_offset: buf.get_i64(),
+            time_diff: buf.get_i32(),
+            prev_index: buf.get_i32(),
+        }
+    }
+
+    /// Checks the header, the hash slots and the entry chains.
+    pub fn verify(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let header = self.header();
+        let index_count = header.index_count.max(1);
+        if index_count as usize > self.capacity() {
+            problems.push(format!(
+                "header index count {index_count} exceeds the file capacity of {} entries, the \
+                 file is truncated or the hash slot number is wrong",
+                self.capacity()
+            ));
+        }
+        if header.hash_slot_count < 0 || header.hash_slot_count as usize > self.hash_slot_num {
+            problems.push(format!(
+                "header hash slot count {} is out of range [0, {}]",
+                header.hash_slot_count, self.hash_slot_num
+            ));
+        }
+        for slot in 0..self.hash_slot_num {
+            let value = self.slot(slot);
+            if value < 0 || value >= index_count {
+                problems.push(format!(
+                    "hash slot {slot} points to index {value} out of range [0, {index_count})"
+                ));
+            }
+        }
+        for entry in self.entries() {
+            if entry.prev_index < 0 || entry.prev_index as usize >= entry.index {
+                problems.push(format!(
+                    "index {}: previous index {} does not precede it",
+                    entry.index, entry.prev_index
+                ));
+            }
+            if entry.phy_offset < header.begin_phy_offset
+                || entry.phy_offset > header.end_phy_offset
+            {
+                problems.push(format!(
+                    "index {}: phy offset {} is out of the header range [{}, {}]",
+                    entry.index, entry.phy_offset, header.begin_phy_offset, header.end_phy_offset
+                ));
+            }
+            if entry.time_diff < 0 {
+                problems.push(format!(
+                    "index {}: negative time diff {}",
+                    entry.index, entry.time_diff
+                ));
+            }
+        }
+        problems
+    }
+}
+
+/// Prints the header and the entries of an index file.
+pub fn dump(path: &Path, hash_slot_num: usize) -> io::Result<()> {
+    let data = fs::read(path)?;
+    let view = IndexFileView::new(&data, hash_slot_num)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    println!("file: {}", path.display());
+    println!("{:?}", view.header());
+    let entries: Vec<IndexEntryView> = view.entries().collect();
+    println!("{}", Table::new(entries));
+    Ok(())
+}
+
+/// Checks an index file and prints the problems found. Returns whether the file is healthy.
+pub fn verify(path: &Path, hash_slot_num: usize) -> io::Result<bool> {
+    let data = fs::read(path)?;
+    println!("file: {}", path.display());
+    let problems = match IndexFileView::new(&data, hash_slot_num) {
+        Ok(view) => {
+            println!("{:?}", view.header());
+            view.verify()
+        }
+        Err(problem) => vec![problem],
+    };
+    for problem in &problems {
+        println!("CORRUPT {problem}");
+    }
+    Ok(problems.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BufMut;
+    use bytes::BytesMut;
+
+    use super::*;
+
+    const HASH_SLOT_NUM: usize = 4;
+
+    /// Builds an index file holding `entries` of (key hash, phy offset, time diff), chained the
+    /// way the store chains keys falling into the same slot.
+    fn index_file(entries: &[(i32, i64, i32)], capacity: usize) -> BytesMut {
+        let mut slots = [0i32; HASH_SLOT_NUM];
+        let mut body = BytesMut::new();
+        body.put_bytes(0, INDEX_SIZE);
+        for (index, (key_hash, phy_offset, time_diff)) in entries.iter().enumerate() {
+            let slot = *key_hash as usize % HASH_SLOT_NUM;
+            body.put_i32(*key_hash);
+            body.put_i64(*phy_offset);
+            body.put_i32(*time_diff);
+            body.put_i32(slots[slot]);
+            slots[slot] = index as i32 + 1;
+        }
+        body.put_bytes(0, (capacity - entries.len() - 1) * INDEX_SIZE);
+
+        let mut buf = BytesMut::new();
+        buf.put_i64(1_700_000_000_000);
+        buf.put_i64(1_700_000_010_000);
+        buf.put_i64(entries.first().map_or(0, |entry| entry.1));
+        buf.put_i64(entries.last().map_or(0, |entry| entry.1));
+        buf.put_i32(slots.iter().filter(|slot| **slot != 0).count() as i32);
+        buf.put_i32(entries.len() as i32 + 1);
+        for slot in slots {
+            buf.put_i32(slot);
+        }
+        buf.put_slice(&body);
+        buf
+    }
+
+    #[test]
+    fn reads_header_and_entries() {
+        let data = index_file(&[(1, 100, 0), (5, 200, 3), (2, 300, 9)], 8);
+        let view = IndexFileView::new(&data, HASH_SLOT_NUM).unwrap();
+        let header = view.header();
+        assert_eq!(header.index_count, 4);
+        assert_eq!(header.hash_slot_count, 2);
+        assert_eq!(view.capacity(), 8);
+
+        let entries: Vec<IndexEntryView> = view.entries().collect();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(
+            entries[1],
+            IndexEntryView {
+                index: 2,
+                key_hash: 5,
+                phy_offset: 200,
+                time_diff: 3,
+                prev_index: 1,
+            }
+        );
+        assert_eq!(view.slot(1), 2);
+        assert!(view.verify().is_empty());
+    }
+
+    #[test]
+    fn verify_reports_broken_chains_and_truncation() {
+        let mut data = index_file(&[(1, 100, 0), (5, 200, 3)], 4);
+        // point the second entry at itself and move its offset out of the header range
+        let position = INDEX_HEADER_SIZE + HASH_SLOT_NUM * HASH_SLOT_SIZE + 2 * INDEX_SIZE;
+        data[position + 4..position + 12].copy_from_slice(&900i64.to_be_bytes());
+        data[position + 16..position + 20].copy_from_slice(&2i32.to_be_bytes());
+
+        let problems = IndexFileView::new(&data, HASH_SLOT_NUM).unwrap().verify();
+        assert_eq!(problems.len(), 2, "{problems:?}");
+        assert!(problems[0].contains("previous index 2"));
+        assert!(problems[1].contains("phy offset 900"));
+
+        let truncated = &data[..data.len() - 2 * INDEX_SIZE];
+        let problems = IndexFileView::new(truncated, HASH_SLOT_NUM)
+            .unwrap()
+            .verify();
+        assert!(problems[0].contains("exceeds the file capacity"));
+
+        assert!(IndexFileView::new(&data[..20], HASH_SLOT_NUM).is_err());
+    }
+}
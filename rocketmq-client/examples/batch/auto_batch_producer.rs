@@ -0,0 +1,60 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use rocketmq_client_rust::producer::default_mq_producer::DefaultMQProducer;
+use rocketmq_client_rust::producer::mq_producer::MQProducer;
+use rocketmq_common::common::message::message_single::Message;
+use rocketmq_error::RocketMQResult;
+use rocketmq_rust::rocketmq;
+
+pub const PRODUCER_GROUP: &str = "AutoBatchProducerGroupName";
+pub const DEFAULT_NAMESRVADDR: &str = "127.0.0.1:9876";
+pub const TOPIC: &str = "TopicTest";
+pub const TAG: &str = "TagA";
+
+#[rocketmq::main]
+pub async fn main() -> RocketMQResult<()> {
+    //init logger
+    rocketmq_common::log::init_logger();
+
+    // small messages sent concurrently are batched by the producer
+    let mut producer = DefaultMQProducer::builder()
+        .producer_group(PRODUCER_GROUP.to_string())
+        .name_server_addr(DEFAULT_NAMESRVADDR.to_string())
+        .auto_batch(true)
+        .batch_max_delay_ms(10)
+        .batch_max_bytes(32 * 1024)
+        .build();
+    producer.start().await?;
+
+    let mut handles = Vec::new();
+    for i in 0..100 {
+        let mut producer = producer.clone();
+        handles.push(tokio::spawn(async move {
+            let message = Message::with_tags(TOPIC, TAG, format!("Hello world {i}").as_bytes());
+            producer.send(message).await
+        }));
+    }
+    for handle in handles {
+        match handle.await {
+            Ok(Ok(send_result)) => println!("send result: {}", send_result),
+            Ok(Err(e)) => println!("send failed: {}", e),
+            Err(e) => println!("send task failed: {}", e),
+        }
+    }
+    producer.shutdown().await;
+    Ok(())
+}
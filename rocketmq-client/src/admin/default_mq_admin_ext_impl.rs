@@ -16,25 +16,38 @@
  */
 
 #![allow(dead_code)]
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::env;
+use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 
 use cheetah_string::CheetahString;
 use lazy_static::lazy_static;
+use rand::seq::IndexedRandom;
 use rocketmq_common::common::base::plain_access_config::PlainAccessConfig;
 use rocketmq_common::common::base::service_state::ServiceState;
 use rocketmq_common::common::config::TopicConfig;
+use rocketmq_common::common::key_builder::KeyBuilder;
 use rocketmq_common::common::message::message_enum::MessageRequestMode;
+use rocketmq_common::common::message::message_ext::MessageExt;
 use rocketmq_common::common::message::message_queue::MessageQueue;
 use rocketmq_common::common::mix_all;
+use rocketmq_common::common::topic::TopicValidator;
 use rocketmq_common::common::FAQUrl;
+use rocketmq_error::mq_client_err;
 use rocketmq_error::ClientErr;
+use rocketmq_remoting::acl::acl_converter;
+use rocketmq_remoting::code::response_code::ResponseCode;
 use rocketmq_remoting::protocol::admin::consume_stats::ConsumeStats;
 use rocketmq_remoting::protocol::admin::topic_stats_table::TopicStatsTable;
+use rocketmq_remoting::protocol::body::acl_info::AclInfo;
+use rocketmq_remoting::protocol::body::acl_info::PolicyEntryInfo;
+use rocketmq_remoting::protocol::body::acl_info::PolicyInfo;
 use rocketmq_remoting::protocol::body::broker_body::cluster_info::ClusterInfo;
+use rocketmq_remoting::protocol::body::check_rocksdb_cqwrite_progress_response_body::CheckRocksdbCqWriteProgressResponseBody;
 use rocketmq_remoting::protocol::body::consume_message_directly_result::ConsumeMessageDirectlyResult;
 use rocketmq_remoting::protocol::body::consumer_connection::ConsumerConnection;
 use rocketmq_remoting::protocol::body::consumer_running_info::ConsumerRunningInfo;
@@ -43,6 +56,10 @@ use rocketmq_remoting::protocol::body::kv_table::KVTable;
 use rocketmq_remoting::protocol::body::producer_connection::ProducerConnection;
 use rocketmq_remoting::protocol::body::topic::topic_list::TopicList;
 use rocketmq_remoting::protocol::body::topic_info_wrapper::TopicConfigSerializeWrapper;
+use rocketmq_remoting::protocol::body::user_info::UserInfo;
+use rocketmq_remoting::protocol::header::peek_message_request_header::PeekMessageRequestHeader;
+use rocketmq_remoting::protocol::header::polling_info_request_header::PollingInfoRequestHeader;
+use rocketmq_remoting::protocol::header::update_consumer_offset_header::UpdateConsumerOffsetRequestHeader;
 use rocketmq_remoting::protocol::heartbeat::subscription_data::SubscriptionData;
 use rocketmq_remoting::protocol::route::topic_route_data::TopicRouteData;
 use rocketmq_remoting::protocol::static_topic::topic_queue_mapping_detail::TopicQueueMappingDetail;
@@ -50,11 +67,15 @@ use rocketmq_remoting::protocol::subscription::subscription_group_config::Subscr
 use rocketmq_remoting::runtime::RPCHook;
 use rocketmq_rust::ArcMut;
 use tracing::info;
+use tracing::warn;
 
 use crate::admin::mq_admin_ext_async::MQAdminExt;
 use crate::admin::mq_admin_ext_async_inner::MQAdminExtInnerImpl;
 use crate::base::client_config::ClientConfig;
+use crate::base::query_result::QueryResult;
 use crate::common::admin_tool_result::AdminToolResult;
+use crate::common::admin_tools_result_code_enum::AdminToolsResultCodeEnum;
+use crate::consumer::pop_result::PopResult;
 use crate::factory::mq_client_instance::MQClientInstance;
 use crate::implementation::mq_client_manager::MQClientManager;
 
@@ -116,6 +137,99 @@ impl DefaultMQAdminExtImpl {
     pub fn set_inner(&mut self, inner: ArcMut<DefaultMQAdminExtImpl>) {
         self.inner = Some(inner);
     }
+
+    /// Picks a random broker address serving `topic`, if the topic has a route.
+    async fn select_broker_addr_of_topic(
+        &self,
+        topic: CheetahString,
+    ) -> rocketmq_error::RocketMQResult<Option<CheetahString>> {
+        Ok(self
+            .examine_topic_route_info(topic)
+            .await?
+            .and_then(|route| {
+                route
+                    .broker_datas
+                    .choose(&mut rand::rng())
+                    .and_then(|broker_data| broker_data.select_broker_addr())
+            }))
+    }
+
+    /// Resolves the broker addresses an operation should be sent to: `addr` when given, otherwise
+    /// every broker of `cluster`, or of all clusters when `cluster` is absent.
+    async fn broker_addrs_of_cluster(
+        &self,
+        cluster: Option<CheetahString>,
+        addr: Option<CheetahString>,
+    ) -> rocketmq_error::RocketMQResult<Vec<CheetahString>> {
+        if let Some(addr) = addr.filter(|addr| !addr.is_empty()) {
+            return Ok(vec![addr]);
+        }
+
+        let cluster_info = self.examine_broker_cluster_info().await?;
+        let broker_addr_table = cluster_info.broker_addr_table.unwrap_or_default();
+        let cluster_addr_table = cluster_info.cluster_addr_table.unwrap_or_default();
+        let clusters: Vec<&CheetahString> = match cluster.as_ref().filter(|c| !c.is_empty()) {
+            Some(cluster) => vec![cluster],
+            None => cluster_addr_table.keys().collect(),
+        };
+        Ok(clusters
+            .into_iter()
+            .filter_map(|cluster_name| cluster_addr_table.get(cluster_name))
+            .flatten()
+            .filter_map(|broker_name| broker_addr_table.get(broker_name))
+            .flat_map(|broker_data| broker_data.broker_addrs().values().cloned())
+            .collect())
+    }
+
+    /// Finds the message identified by `msg_id`, which may be either an offset message id or a
+    /// unique key.
+    async fn resolve_message(
+        &self,
+        cluster_name: Option<&CheetahString>,
+        topic: &CheetahString,
+        msg_id: &CheetahString,
+    ) -> rocketmq_error::RocketMQResult<MessageExt> {
+        let mq_admin_impl = &self.client_instance.as_ref().unwrap().mq_admin_impl;
+        resolve_message_by_id(
+            msg_id,
+            || async { mq_admin_impl.clone().view_message(topic, msg_id).await },
+            || async {
+                mq_admin_impl
+                    .clone()
+                    .query_message_by_uniq_key(cluster_name, topic, msg_id, 0, i64::MAX)
+                    .await
+            },
+        )
+        .await
+    }
+
+    /// Asks a consumer client to consume `msg_id`. The request is routed through the broker that
+    /// stores the message and always carries the offset message id the broker can look up.
+    async fn consume_message_directly_in_cluster(
+        &self,
+        cluster_name: Option<CheetahString>,
+        consumer_group: CheetahString,
+        client_id: CheetahString,
+        topic: CheetahString,
+        msg_id: CheetahString,
+    ) -> rocketmq_error::RocketMQResult<ConsumeMessageDirectlyResult> {
+        let message = self
+            .resolve_message(cluster_name.as_ref(), &topic, &msg_id)
+            .await?;
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .consume_message_directly(
+                &message.store_host().to_string().into(),
+                &consumer_group,
+                &client_id,
+                &topic,
+                message.msg_id(),
+                self.timeout_millis.as_millis() as u64 * 3,
+            )
+            .await
+    }
 }
 
 #[allow(unused_variables)]
@@ -172,7 +286,11 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
                 Ok(())
             }
             ServiceState::Running | ServiceState::ShutdownAlready | ServiceState::StartFailed => {
-                unimplemented!()
+                mq_client_err!(format!(
+                    "The AdminExt service state not OK, maybe started once, {:?}{}",
+                    self.service_state,
+                    FAQUrl::suggest_todo(FAQUrl::CLIENT_SERVICE_NOT_OK)
+                ))
             }
         }
     }
@@ -198,7 +316,16 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         broker_container_addr: CheetahString,
         broker_config: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .add_broker(
+                &broker_container_addr,
+                &broker_config,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn remove_broker_from_container(
@@ -208,7 +335,18 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         broker_name: CheetahString,
         broker_id: u64,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .remove_broker(
+                &broker_container_addr,
+                &cluster_name,
+                &broker_name,
+                broker_id,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn update_broker_config(
@@ -216,14 +354,28 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         broker_addr: CheetahString,
         properties: HashMap<CheetahString, CheetahString>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .update_broker_config(
+                &broker_addr,
+                &properties,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn get_broker_config(
         &self,
         broker_addr: CheetahString,
     ) -> rocketmq_error::RocketMQResult<HashMap<CheetahString, CheetahString>> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .get_broker_config(&broker_addr, self.timeout_millis.as_millis() as u64)
+            .await
     }
 
     async fn create_and_update_topic_config(
@@ -231,7 +383,17 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         addr: CheetahString,
         config: TopicConfig,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .create_topic(
+                &addr,
+                TopicValidator::AUTO_CREATE_TOPIC_KEY_TOPIC,
+                &config,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn create_and_update_topic_config_list(
@@ -239,7 +401,16 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         addr: CheetahString,
         topic_config_list: Vec<TopicConfig>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .create_topic_list(
+                &addr,
+                topic_config_list,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn create_and_update_plain_access_config(
@@ -247,7 +418,12 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         addr: CheetahString,
         config: PlainAccessConfig,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .create_plain_access_config(&addr, config, self.timeout_millis.as_millis() as u64)
+            .await
     }
 
     async fn delete_plain_access_config(
@@ -255,7 +431,12 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         addr: CheetahString,
         access_key: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .delete_plain_access_config(&addr, access_key, self.timeout_millis.as_millis() as u64)
+            .await
     }
 
     async fn update_global_white_addr_config(
@@ -264,14 +445,29 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         global_white_addrs: CheetahString,
         acl_file_full_path: Option<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .update_global_white_addrs_config(
+                &addr,
+                global_white_addrs,
+                acl_file_full_path,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn examine_broker_cluster_acl_version_info(
         &self,
         addr: CheetahString,
     ) -> rocketmq_error::RocketMQResult<CheetahString> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .get_broker_cluster_acl_version(&addr, self.timeout_millis.as_millis() as u64)
+            .await
     }
 
     async fn create_and_update_subscription_group_config(
@@ -279,7 +475,12 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         addr: CheetahString,
         config: SubscriptionGroupConfig,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .create_subscription_group(&addr, &config, self.timeout_millis.as_millis() as u64)
+            .await
     }
 
     async fn create_and_update_subscription_group_config_list(
@@ -287,7 +488,11 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         broker_addr: CheetahString,
         configs: Vec<SubscriptionGroupConfig>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        for config in configs {
+            self.create_and_update_subscription_group_config(broker_addr.clone(), config)
+                .await?;
+        }
+        Ok(())
     }
 
     async fn examine_subscription_group_config(
@@ -295,7 +500,12 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         addr: CheetahString,
         group: CheetahString,
     ) -> rocketmq_error::RocketMQResult<SubscriptionGroupConfig> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .get_subscription_group_config(&addr, &group, self.timeout_millis.as_millis() as u64)
+            .await
     }
 
     async fn examine_topic_stats(
@@ -303,32 +513,116 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         topic: CheetahString,
         broker_addr: Option<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<TopicStatsTable> {
-        todo!()
+        let mq_client_api = self
+            .client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl();
+        let timeout_millis = self.timeout_millis.as_millis() as u64;
+        if let Some(broker_addr) = broker_addr.filter(|addr| !addr.is_empty()) {
+            return mq_client_api
+                .get_topic_stats_info(&broker_addr, &topic, timeout_millis)
+                .await;
+        }
+
+        let topic_route_data = self.examine_topic_route_info(topic.clone()).await?;
+        let mut offset_table = HashMap::new();
+        for broker_data in topic_route_data
+            .iter()
+            .flat_map(|route| route.broker_datas.iter())
+        {
+            let Some(addr) = broker_data.select_broker_addr() else {
+                continue;
+            };
+            let topic_stats_table = mq_client_api
+                .get_topic_stats_info(&addr, &topic, timeout_millis)
+                .await?;
+            offset_table.extend(topic_stats_table.get_offset_table());
+        }
+        if offset_table.is_empty() {
+            return mq_client_err!("Not found the topic stats info");
+        }
+        let mut topic_stats_table = TopicStatsTable::new();
+        topic_stats_table.set_offset_table(offset_table);
+        Ok(topic_stats_table)
     }
 
     async fn examine_topic_stats_concurrent(
         &self,
         topic: CheetahString,
     ) -> AdminToolResult<TopicStatsTable> {
-        todo!()
+        let topic_route_data = match self.examine_topic_route_info(topic.clone()).await {
+            Ok(Some(topic_route_data)) => topic_route_data,
+            Ok(None) => {
+                return AdminToolResult::failure(
+                    AdminToolsResultCodeEnum::TopicRouteInfoNotExist,
+                    "router info not found.".to_string(),
+                )
+            }
+            Err(e) => return admin_tool_failure(e),
+        };
+        let mq_client_api = self
+            .client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl();
+        let timeout_millis = self.timeout_millis.as_millis() as u64;
+        let futures = topic_route_data
+            .broker_datas
+            .iter()
+            .filter_map(|broker_data| broker_data.select_broker_addr())
+            .map(|addr| {
+                let mq_client_api = mq_client_api.clone();
+                let topic = topic.clone();
+                async move {
+                    mq_client_api
+                        .get_topic_stats_info(&addr, &topic, timeout_millis)
+                        .await
+                }
+            });
+        let mut offset_table = HashMap::new();
+        for result in futures::future::join_all(futures).await {
+            match result {
+                Ok(topic_stats_table) => offset_table.extend(topic_stats_table.get_offset_table()),
+                Err(e) => return admin_tool_failure(e),
+            }
+        }
+        let mut topic_stats_table = TopicStatsTable::new();
+        topic_stats_table.set_offset_table(offset_table);
+        AdminToolResult::success(topic_stats_table)
     }
 
     async fn fetch_all_topic_list(&self) -> rocketmq_error::RocketMQResult<TopicList> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .get_topic_list_from_name_server(self.timeout_millis.as_millis() as u64)
+            .await
     }
 
     async fn fetch_topics_by_cluster(
         &self,
         cluster_name: CheetahString,
     ) -> rocketmq_error::RocketMQResult<TopicList> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .get_topics_by_cluster(&cluster_name, self.timeout_millis.as_millis() as u64)
+            .await
     }
 
     async fn fetch_broker_runtime_stats(
         &self,
         broker_addr: CheetahString,
     ) -> rocketmq_error::RocketMQResult<KVTable> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .get_broker_runtime_info(&broker_addr, self.timeout_millis.as_millis() as u64)
+            .await
     }
 
     async fn examine_consume_stats(
@@ -339,11 +633,103 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         broker_addr: Option<CheetahString>,
         timeout_millis: Option<u64>,
     ) -> rocketmq_error::RocketMQResult<ConsumeStats> {
-        todo!()
+        let mq_client_api = self
+            .client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl();
+        let timeout_millis = timeout_millis.unwrap_or(self.timeout_millis.as_millis() as u64 * 3);
+        if let Some(broker_addr) = broker_addr.filter(|addr| !addr.is_empty()) {
+            return mq_client_api
+                .get_consume_stats(
+                    &broker_addr,
+                    &consumer_group,
+                    topic.as_ref(),
+                    timeout_millis,
+                )
+                .await;
+        }
+
+        let mut route_topics = vec![CheetahString::from(mix_all::get_retry_topic(
+            consumer_group.as_str(),
+        ))];
+        if let Some(topic) = topic.as_ref() {
+            route_topics.push(topic.clone());
+            route_topics.push(CheetahString::from(KeyBuilder::build_pop_retry_topic(
+                topic.as_str(),
+                consumer_group.as_str(),
+                false,
+            )));
+        }
+        let mut topic_route_data = None;
+        let route_topics_len = route_topics.len();
+        for (index, route_topic) in route_topics.into_iter().enumerate() {
+            match self.examine_topic_route_info(route_topic).await {
+                Ok(Some(route)) => {
+                    topic_route_data = Some(route);
+                    break;
+                }
+                Ok(None) => {}
+                Err(e) if index == route_topics_len - 1 => return Err(e),
+                Err(_) => {}
+            }
+        }
+
+        let mut result = ConsumeStats::new();
+        for broker_data in topic_route_data
+            .iter()
+            .flat_map(|route| route.broker_datas.iter())
+        {
+            if cluster_name
+                .as_ref()
+                .is_some_and(|cluster_name| cluster_name.as_str() != broker_data.cluster())
+            {
+                continue;
+            }
+            let Some(addr) = broker_data.select_broker_addr() else {
+                continue;
+            };
+            let consume_stats = mq_client_api
+                .get_consume_stats(&addr, &consumer_group, topic.as_ref(), timeout_millis)
+                .await?;
+            result.offset_table.extend(consume_stats.offset_table);
+            result.consume_tps += consume_stats.consume_tps;
+        }
+        if result.offset_table.is_empty() {
+            return mq_client_err!(
+                ResponseCode::ConsumerNotOnline,
+                "Not found the consumer group consume stats, because return offset table is \
+                 empty, maybe the consumer not consume any message"
+                    .to_string()
+            );
+        }
+        Ok(result)
+    }
+
+    async fn check_rocksdb_cq_write_progress(
+        &self,
+        broker_addr: CheetahString,
+        topic: CheetahString,
+    ) -> rocketmq_error::RocketMQResult<CheckRocksdbCqWriteProgressResponseBody> {
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .check_rocksdb_cq_write_progress(
+                &broker_addr,
+                topic,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn examine_broker_cluster_info(&self) -> rocketmq_error::RocketMQResult<ClusterInfo> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .get_broker_cluster_info(self.timeout_millis.as_millis() as u64)
+            .await
     }
 
     async fn examine_topic_route_info(
@@ -365,7 +751,45 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         consumer_group: CheetahString,
         broker_addr: Option<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<ConsumerConnection> {
-        todo!()
+        let mq_client_api = self
+            .client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl();
+        let timeout_millis = self.timeout_millis.as_millis() as u64;
+        let addr = match broker_addr.filter(|addr| !addr.is_empty()) {
+            Some(addr) => Some(addr),
+            None => {
+                let retry_topic = mix_all::get_retry_topic(consumer_group.as_str());
+                self.examine_topic_route_info(retry_topic.into())
+                    .await?
+                    .and_then(|route| {
+                        route
+                            .broker_datas
+                            .choose(&mut rand::rng())
+                            .and_then(|broker_data| broker_data.select_broker_addr())
+                    })
+            }
+        };
+        let result = match addr.as_ref() {
+            Some(addr) => {
+                mq_client_api
+                    .get_consumer_connection_list(addr, &consumer_group, timeout_millis)
+                    .await?
+            }
+            None => ConsumerConnection::new(),
+        };
+        if result.get_connection_set().is_empty() {
+            warn!(
+                "the consumer group not online. brokerAddr={:?}, group={}",
+                addr, consumer_group
+            );
+            return mq_client_err!(
+                ResponseCode::ConsumerNotOnline,
+                "Not found the consumer group connection".to_string()
+            );
+        }
+        Ok(result)
     }
 
     async fn examine_producer_connection_info(
@@ -373,7 +797,25 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         producer_group: CheetahString,
         topic: CheetahString,
     ) -> rocketmq_error::RocketMQResult<ProducerConnection> {
-        todo!()
+        let result = match self.select_broker_addr_of_topic(topic).await? {
+            Some(addr) => {
+                self.client_instance
+                    .as_ref()
+                    .unwrap()
+                    .get_mq_client_api_impl()
+                    .get_producer_connection_list(
+                        &addr,
+                        &producer_group,
+                        self.timeout_millis.as_millis() as u64 * 3,
+                    )
+                    .await?
+            }
+            None => ProducerConnection::default(),
+        };
+        if result.connection_set.is_empty() {
+            return mq_client_err!("Not found the producer group connection");
+        }
+        Ok(result)
     }
 
     async fn get_name_server_address_list(&self) -> Vec<CheetahString> {
@@ -425,7 +867,12 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         key: CheetahString,
         value: CheetahString,
     ) {
-        todo!()
+        if let Err(e) = self
+            .create_and_update_kv_config(namespace, key, value)
+            .await
+        {
+            warn!("put kv config failed: {}", e);
+        }
     }
 
     async fn get_kv_config(
@@ -433,14 +880,26 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         namespace: CheetahString,
         key: CheetahString,
     ) -> rocketmq_error::RocketMQResult<CheetahString> {
-        todo!()
+        Ok(self
+            .client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .get_kv_config_value(&namespace, &key, self.timeout_millis.as_millis() as u64)
+            .await?
+            .unwrap_or_default())
     }
 
     async fn get_kv_list_by_namespace(
         &self,
         namespace: CheetahString,
     ) -> rocketmq_error::RocketMQResult<KVTable> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .get_kv_list_by_namespace(&namespace, self.timeout_millis.as_millis() as u64)
+            .await
     }
 
     async fn delete_topic(
@@ -448,7 +907,32 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         topic_name: CheetahString,
         cluster_name: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        let cluster_info = self.examine_broker_cluster_info().await?;
+        let broker_addr_table = cluster_info.broker_addr_table.unwrap_or_default();
+        let broker_addrs = cluster_info
+            .cluster_addr_table
+            .unwrap_or_default()
+            .get(&cluster_name)
+            .into_iter()
+            .flatten()
+            .filter_map(|broker_name| broker_addr_table.get(broker_name))
+            .flat_map(|broker_data| broker_data.broker_addrs().values().cloned())
+            .collect::<HashSet<CheetahString>>();
+        self.delete_topic_in_broker(broker_addrs, topic_name.clone())
+            .await?;
+
+        let name_server_set = self
+            .get_name_server_address_list()
+            .await
+            .into_iter()
+            .collect::<HashSet<CheetahString>>();
+        self.delete_topic_in_name_server(name_server_set, Some(cluster_name), topic_name.clone())
+            .await?;
+        for namespace in &self.kv_namespace_to_delete_list {
+            self.delete_kv_config(namespace.clone(), topic_name.clone())
+                .await?;
+        }
+        Ok(())
     }
 
     async fn delete_topic_in_broker(
@@ -456,7 +940,17 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         addrs: HashSet<CheetahString>,
         topic: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        let mq_client_api = self
+            .client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl();
+        for addr in addrs {
+            mq_client_api
+                .delete_topic_in_broker(&addr, &topic, self.timeout_millis.as_millis() as u64)
+                .await?;
+        }
+        Ok(())
     }
 
     async fn delete_topic_in_name_server(
@@ -465,7 +959,30 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         cluster_name: Option<CheetahString>,
         topic: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        let addrs = if addrs.is_empty() {
+            self.get_name_server_address_list()
+                .await
+                .into_iter()
+                .collect()
+        } else {
+            addrs
+        };
+        let mq_client_api = self
+            .client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl();
+        for addr in addrs {
+            mq_client_api
+                .delete_topic_in_name_server(
+                    &addr,
+                    cluster_name.as_ref(),
+                    &topic,
+                    self.timeout_millis.as_millis() as u64,
+                )
+                .await?;
+        }
+        Ok(())
     }
 
     async fn delete_subscription_group(
@@ -474,7 +991,17 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         group_name: CheetahString,
         remove_offset: Option<bool>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .delete_subscription_group(
+                &addr,
+                &group_name,
+                remove_offset.unwrap_or(false),
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn create_and_update_kv_config(
@@ -517,7 +1044,43 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         timestamp: u64,
         is_force: bool,
     ) -> rocketmq_error::RocketMQResult<HashMap<MessageQueue, u64>> {
-        todo!()
+        let mq_client_api = self
+            .client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl();
+        let topic_route_data = self.examine_topic_route_info(topic.clone()).await?;
+        let mut all_offset_table = HashMap::new();
+        for broker_data in topic_route_data
+            .iter()
+            .flat_map(|route| route.broker_datas.iter())
+        {
+            if cluster_name
+                .as_ref()
+                .is_some_and(|cluster_name| cluster_name.as_str() != broker_data.cluster())
+            {
+                continue;
+            }
+            let Some(addr) = broker_data.select_broker_addr() else {
+                continue;
+            };
+            let offset_table = mq_client_api
+                .invoke_broker_to_reset_offset(
+                    &addr,
+                    &topic,
+                    &group,
+                    timestamp,
+                    is_force,
+                    self.timeout_millis.as_millis() as u64,
+                )
+                .await?;
+            all_offset_table.extend(
+                offset_table
+                    .into_iter()
+                    .map(|(mq, offset)| (mq, offset.max(0) as u64)),
+            );
+        }
+        Ok(all_offset_table)
     }
 
     async fn reset_offset_new(
@@ -526,7 +1089,9 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         topic: CheetahString,
         timestamp: u64,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.reset_offset_by_timestamp(None, topic, consumer_group, timestamp, true)
+            .await
+            .map(|_| ())
     }
 
     async fn get_consume_status(
@@ -535,7 +1100,33 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         group: CheetahString,
         client_addr: CheetahString,
     ) -> rocketmq_error::RocketMQResult<HashMap<CheetahString, HashMap<MessageQueue, u64>>> {
-        todo!()
+        let Some(addr) = self.select_broker_addr_of_topic(topic.clone()).await? else {
+            return Ok(HashMap::new());
+        };
+        let client_addr = Some(&client_addr).filter(|client_addr| !client_addr.is_empty());
+        let consumer_table = self
+            .client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .invoke_broker_to_get_consumer_status(
+                &addr,
+                &topic,
+                &group,
+                client_addr,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await?;
+        Ok(consumer_table
+            .into_iter()
+            .map(|(client_id, offset_table)| {
+                let offset_table = offset_table
+                    .into_iter()
+                    .map(|(mq, offset)| (mq, offset.max(0) as u64))
+                    .collect();
+                (client_id, offset_table)
+            })
+            .collect())
     }
 
     async fn create_or_update_order_conf(
@@ -544,28 +1135,139 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         value: CheetahString,
         is_cluster: bool,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        if is_cluster {
+            return self
+                .create_and_update_kv_config(
+                    CheetahString::from_static_str(NAMESPACE_ORDER_TOPIC_CONFIG),
+                    key,
+                    value,
+                )
+                .await;
+        }
+
+        // Only replace the entry of this broker, keep the order conf of the other brokers.
+        let old_order_confs = self
+            .get_kv_config(
+                CheetahString::from_static_str(NAMESPACE_ORDER_TOPIC_CONFIG),
+                key.clone(),
+            )
+            .await
+            .unwrap_or_default();
+        let mut order_conf_map = BTreeMap::new();
+        for old_order_conf in old_order_confs.split(';').filter(|conf| !conf.is_empty()) {
+            let broker_name = old_order_conf.split(':').next().unwrap_or_default();
+            order_conf_map.insert(broker_name.to_string(), old_order_conf.to_string());
+        }
+        let broker_name = value.split(':').next().unwrap_or_default();
+        order_conf_map.insert(broker_name.to_string(), value.to_string());
+        let new_order_conf = order_conf_map.into_values().collect::<Vec<_>>().join(";");
+        self.create_and_update_kv_config(
+            CheetahString::from_static_str(NAMESPACE_ORDER_TOPIC_CONFIG),
+            key,
+            new_order_conf.into(),
+        )
+        .await
     }
 
     async fn query_topic_consume_by_who(
         &self,
         topic: CheetahString,
     ) -> rocketmq_error::RocketMQResult<GroupList> {
-        todo!()
+        let topic_route_data = self.examine_topic_route_info(topic.clone()).await?;
+        let Some(addr) = topic_route_data
+            .iter()
+            .flat_map(|route| route.broker_datas.iter())
+            .find_map(|broker_data| broker_data.select_broker_addr())
+        else {
+            return Ok(GroupList::default());
+        };
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .query_topic_consume_by_who(&addr, &topic, self.timeout_millis.as_millis() as u64)
+            .await
     }
 
     async fn query_topics_by_consumer(
         &self,
         group: CheetahString,
     ) -> rocketmq_error::RocketMQResult<TopicList> {
-        todo!()
+        let mq_client_api = self
+            .client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl();
+        let retry_topic = mix_all::get_retry_topic(group.as_str());
+        let topic_route_data = self.examine_topic_route_info(retry_topic.into()).await?;
+        let mut topics = HashSet::new();
+        let mut result = TopicList::default();
+        for addr in topic_route_data
+            .iter()
+            .flat_map(|route| route.broker_datas.iter())
+            .filter_map(|broker_data| broker_data.select_broker_addr())
+        {
+            let topic_list = mq_client_api
+                .query_topics_by_consumer(&addr, &group, self.timeout_millis.as_millis() as u64)
+                .await?;
+            for topic in topic_list.topic_list {
+                if topics.insert(topic.clone()) {
+                    result.topic_list.push(topic);
+                }
+            }
+        }
+        Ok(result)
     }
 
     async fn query_topics_by_consumer_concurrent(
         &self,
         group: CheetahString,
     ) -> AdminToolResult<TopicList> {
-        todo!()
+        let retry_topic = mix_all::get_retry_topic(group.as_str());
+        let topic_route_data = match self.examine_topic_route_info(retry_topic.into()).await {
+            Ok(Some(topic_route_data)) => topic_route_data,
+            Ok(None) => {
+                return AdminToolResult::failure(
+                    AdminToolsResultCodeEnum::TopicRouteInfoNotExist,
+                    "router info not found.".to_string(),
+                )
+            }
+            Err(e) => return admin_tool_failure(e),
+        };
+        let mq_client_api = self
+            .client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl();
+        let timeout_millis = self.timeout_millis.as_millis() as u64;
+        let futures = topic_route_data
+            .broker_datas
+            .iter()
+            .filter_map(|broker_data| broker_data.select_broker_addr())
+            .map(|addr| {
+                let mq_client_api = mq_client_api.clone();
+                let group = group.clone();
+                async move {
+                    mq_client_api
+                        .query_topics_by_consumer(&addr, &group, timeout_millis)
+                        .await
+                }
+            });
+        let mut topics = HashSet::new();
+        let mut result = TopicList::default();
+        for topic_list in futures::future::join_all(futures).await {
+            match topic_list {
+                Ok(topic_list) => {
+                    for topic in topic_list.topic_list {
+                        if topics.insert(topic.clone()) {
+                            result.topic_list.push(topic);
+                        }
+                    }
+                }
+                Err(e) => return admin_tool_failure(e),
+            }
+        }
+        AdminToolResult::success(result)
     }
 
     async fn query_subscription(
@@ -573,7 +1275,23 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         group: CheetahString,
         topic: CheetahString,
     ) -> rocketmq_error::RocketMQResult<SubscriptionData> {
-        todo!()
+        let retry_topic = mix_all::get_retry_topic(group.as_str());
+        let Some(addr) = self.select_broker_addr_of_topic(retry_topic.into()).await? else {
+            return mq_client_err!(format!(
+                "Not found the subscription of group {group} for topic {topic}"
+            ));
+        };
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .query_subscription_by_consumer(
+                &addr,
+                &group,
+                &topic,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn clean_expired_consumer_queue(
@@ -581,7 +1299,19 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         cluster: Option<CheetahString>,
         addr: Option<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<bool> {
-        todo!()
+        let mq_client_api = self
+            .client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl();
+        let mut result = false;
+        for broker_addr in self.broker_addrs_of_cluster(cluster, addr).await? {
+            let cleaned = mq_client_api
+                .clean_expired_consume_queue(&broker_addr, self.timeout_millis.as_millis() as u64)
+                .await?;
+            result = cleaned || result;
+        }
+        Ok(result)
     }
 
     async fn delete_expired_commit_log(
@@ -589,7 +1319,19 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         cluster: Option<CheetahString>,
         addr: Option<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<bool> {
-        todo!()
+        let mq_client_api = self
+            .client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl();
+        let mut result = false;
+        for broker_addr in self.broker_addrs_of_cluster(cluster, addr).await? {
+            let deleted = mq_client_api
+                .delete_expired_commit_log(&broker_addr, self.timeout_millis.as_millis() as u64)
+                .await?;
+            result = deleted || result;
+        }
+        Ok(result)
     }
 
     async fn clean_unused_topic(
@@ -597,7 +1339,19 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         cluster: Option<CheetahString>,
         addr: Option<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<bool> {
-        todo!()
+        let mq_client_api = self
+            .client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl();
+        let mut result = false;
+        for broker_addr in self.broker_addrs_of_cluster(cluster, addr).await? {
+            let cleaned = mq_client_api
+                .clean_unused_topic(&broker_addr, self.timeout_millis.as_millis() as u64)
+                .await?;
+            result = cleaned || result;
+        }
+        Ok(result)
     }
 
     async fn get_consumer_running_info(
@@ -605,9 +1359,26 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         consumer_group: CheetahString,
         client_id: CheetahString,
         jstack: bool,
-        metrics: Option<bool>,
     ) -> rocketmq_error::RocketMQResult<ConsumerRunningInfo> {
-        todo!()
+        let retry_topic = mix_all::get_retry_topic(consumer_group.as_str());
+        let Some(addr) = self.select_broker_addr_of_topic(retry_topic.into()).await? else {
+            return mq_client_err!(
+                ResponseCode::ConsumerNotOnline,
+                format!("Not found the broker of the consumer group {consumer_group}")
+            );
+        };
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .get_consumer_running_info(
+                &addr,
+                &consumer_group,
+                &client_id,
+                jstack,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn consume_message_directly(
@@ -617,7 +1388,8 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         topic: CheetahString,
         msg_id: CheetahString,
     ) -> rocketmq_error::RocketMQResult<ConsumeMessageDirectlyResult> {
-        todo!()
+        self.consume_message_directly_in_cluster(None, consumer_group, client_id, topic, msg_id)
+            .await
     }
 
     async fn consume_message_directly_ext(
@@ -628,7 +1400,63 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         topic: CheetahString,
         msg_id: CheetahString,
     ) -> rocketmq_error::RocketMQResult<ConsumeMessageDirectlyResult> {
-        todo!()
+        self.consume_message_directly_in_cluster(
+            Some(cluster_name),
+            consumer_group,
+            client_id,
+            topic,
+            msg_id,
+        )
+        .await
+    }
+
+    async fn view_message(
+        &self,
+        topic: CheetahString,
+        msg_id: CheetahString,
+    ) -> rocketmq_error::RocketMQResult<MessageExt> {
+        self.resolve_message(None, &topic, &msg_id).await
+    }
+
+    async fn query_message(
+        &self,
+        cluster_name: Option<CheetahString>,
+        topic: CheetahString,
+        msg_id: CheetahString,
+    ) -> rocketmq_error::RocketMQResult<MessageExt> {
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .mq_admin_impl
+            .clone()
+            .query_message_by_uniq_key(cluster_name.as_ref(), &topic, &msg_id, 0, i64::MAX)
+            .await
+    }
+
+    async fn query_message_by_key(
+        &self,
+        cluster_name: Option<CheetahString>,
+        topic: CheetahString,
+        key: CheetahString,
+        max_num: i32,
+        begin: i64,
+        end: i64,
+    ) -> rocketmq_error::RocketMQResult<QueryResult> {
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .mq_admin_impl
+            .clone()
+            .query_message(
+                cluster_name.as_ref(),
+                &topic,
+                &key,
+                max_num,
+                begin,
+                end,
+                false,
+            )
+            .await
     }
 
     async fn clone_group_offset(
@@ -638,21 +1466,72 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         topic: CheetahString,
         is_offline: bool,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        let mq_client_api = self
+            .client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl();
+        let retry_topic = mix_all::get_retry_topic(src_group.as_str());
+        let topic_route_data = self.examine_topic_route_info(retry_topic.into()).await?;
+        for addr in topic_route_data
+            .iter()
+            .flat_map(|route| route.broker_datas.iter())
+            .filter_map(|broker_data| broker_data.select_broker_addr())
+        {
+            mq_client_api
+                .clone_group_offset(
+                    &addr,
+                    &src_group,
+                    &dest_group,
+                    &topic,
+                    is_offline,
+                    self.timeout_millis.as_millis() as u64 * 3,
+                )
+                .await?;
+        }
+        Ok(())
     }
 
     async fn get_cluster_list(
         &self,
         topic: String,
     ) -> rocketmq_error::RocketMQResult<HashSet<CheetahString>> {
-        todo!()
+        let cluster_info = self.examine_broker_cluster_info().await?;
+        let topic_route_data = self.examine_topic_route_info(topic.into()).await?;
+        let cluster_addr_table = cluster_info.cluster_addr_table.unwrap_or_default();
+        let mut cluster_set = HashSet::new();
+        for broker_data in topic_route_data
+            .iter()
+            .flat_map(|route| route.broker_datas.iter())
+        {
+            for (cluster_name, broker_names) in &cluster_addr_table {
+                if broker_names.contains(broker_data.broker_name()) {
+                    cluster_set.insert(cluster_name.clone());
+                }
+            }
+        }
+        Ok(cluster_set)
     }
 
     async fn get_topic_cluster_list(
         &self,
         topic: String,
     ) -> rocketmq_error::RocketMQResult<HashSet<CheetahString>> {
-        todo!()
+        let cluster_info = self.examine_broker_cluster_info().await?;
+        let topic_route_data = self.examine_topic_route_info(topic.into()).await?;
+        let Some(broker_data) = topic_route_data
+            .as_ref()
+            .and_then(|route| route.broker_datas.first())
+        else {
+            return Ok(HashSet::new());
+        };
+        Ok(cluster_info
+            .cluster_addr_table
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, broker_names)| broker_names.contains(broker_data.broker_name()))
+            .map(|(cluster_name, _)| cluster_name)
+            .collect())
     }
 
     async fn get_all_topic_config(
@@ -660,7 +1539,12 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         broker_addr: CheetahString,
         timeout_millis: u64,
     ) -> rocketmq_error::RocketMQResult<TopicConfigSerializeWrapper> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .get_all_topic_config(&broker_addr, timeout_millis)
+            .await
     }
 
     async fn get_user_topic_config(
@@ -669,7 +1553,28 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         special_topic: bool,
         timeout_millis: u64,
     ) -> rocketmq_error::RocketMQResult<TopicConfigSerializeWrapper> {
-        todo!()
+        let mut topic_config_wrapper = self
+            .get_all_topic_config(broker_addr.clone(), timeout_millis)
+            .await?;
+        let system_topic_list = self
+            .client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .get_system_topic_list_from_broker(&broker_addr, timeout_millis)
+            .await?;
+        let mut topic_config_table = topic_config_wrapper
+            .topic_config_table()
+            .cloned()
+            .unwrap_or_default();
+        topic_config_table.retain(|topic, _| {
+            !(system_topic_list.topic_list.contains(topic)
+                || !special_topic
+                    && (topic.starts_with(mix_all::RETRY_GROUP_TOPIC_PREFIX)
+                        || topic.starts_with(mix_all::DLQ_GROUP_TOPIC_PREFIX)))
+        });
+        topic_config_wrapper.set_topic_config_table(Some(topic_config_table));
+        Ok(topic_config_wrapper)
     }
 
     async fn update_consume_offset(
@@ -679,7 +1584,23 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         mq: MessageQueue,
         offset: u64,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        let request_header = UpdateConsumerOffsetRequestHeader {
+            consumer_group: consume_group,
+            topic: mq.get_topic_cs().clone(),
+            queue_id: mq.get_queue_id(),
+            commit_offset: offset as i64,
+            topic_request_header: None,
+        };
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .update_consumer_offset(
+                &broker_addr,
+                request_header,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn update_name_server_config(
@@ -721,7 +1642,18 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         topic: CheetahString,
         msg_id: CheetahString,
     ) -> rocketmq_error::RocketMQResult<bool> {
-        todo!()
+        let message = self.resolve_message(None, &topic, &msg_id).await?;
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .resume_check_half_message(
+                &message.store_host().to_string().into(),
+                &topic,
+                message.msg_id(),
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn set_message_request_mode(
@@ -762,7 +1694,23 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         queue_id: i32,
         reset_offset: u64,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        let request_header = UpdateConsumerOffsetRequestHeader {
+            consumer_group,
+            topic: topic_name,
+            queue_id,
+            commit_offset: reset_offset as i64,
+            topic_request_header: None,
+        };
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .update_consumer_offset(
+                &broker_addr,
+                request_header,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn examine_topic_config(
@@ -770,7 +1718,12 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         addr: CheetahString,
         topic: CheetahString,
     ) -> rocketmq_error::RocketMQResult<TopicConfig> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .get_topic_config(&addr, &topic, self.timeout_millis.as_millis() as u64)
+            .await
     }
 
     async fn create_static_topic(
@@ -781,7 +1734,71 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         mapping_detail: TopicQueueMappingDetail,
         force: bool,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .create_static_topic(
+                &addr,
+                &default_topic,
+                &topic_config,
+                &mapping_detail,
+                force,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
+    }
+
+    async fn peek_message(
+        &self,
+        broker_addr: CheetahString,
+        consumer_group: CheetahString,
+        topic: CheetahString,
+        queue_id: i32,
+        max_msg_nums: u32,
+    ) -> rocketmq_error::RocketMQResult<PopResult> {
+        let request_header = PeekMessageRequestHeader {
+            consumer_group,
+            topic,
+            queue_id,
+            max_msg_nums,
+            topic_request_header: None,
+        };
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .peek_message(
+                &broker_addr,
+                request_header,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
+    }
+
+    async fn get_polling_info(
+        &self,
+        broker_addr: CheetahString,
+        consumer_group: CheetahString,
+        topic: CheetahString,
+        queue_id: i32,
+    ) -> rocketmq_error::RocketMQResult<i32> {
+        let request_header = PollingInfoRequestHeader {
+            consumer_group,
+            topic,
+            queue_id,
+            topic_request_header: None,
+        };
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .get_polling_info(
+                &broker_addr,
+                request_header,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn reset_master_flush_offset(
@@ -789,7 +1806,16 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         broker_addr: CheetahString,
         master_flush_offset: u64,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .reset_master_flush_offset(
+                &broker_addr,
+                master_flush_offset,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn get_controller_config(
@@ -797,7 +1823,12 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         controller_servers: Vec<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<HashMap<CheetahString, HashMap<CheetahString, CheetahString>>>
     {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .get_controller_config(&controller_servers, self.timeout_millis.as_millis() as u64)
+            .await
     }
 
     async fn update_controller_config(
@@ -805,7 +1836,16 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         properties: HashMap<CheetahString, CheetahString>,
         controllers: Vec<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .update_controller_config(
+                &properties,
+                &controllers,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn clean_controller_broker_data(
@@ -816,7 +1856,19 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         broker_controller_ids_to_clean: Option<CheetahString>,
         is_clean_living_broker: bool,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .clean_controller_broker_data(
+                &controller_addr,
+                &cluster_name,
+                &broker_name,
+                broker_controller_ids_to_clean,
+                is_clean_living_broker,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn update_cold_data_flow_ctr_group_config(
@@ -824,7 +1876,16 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         broker_addr: CheetahString,
         properties: HashMap<CheetahString, CheetahString>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .update_cold_data_flow_ctr_group_config(
+                &broker_addr,
+                &properties,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn remove_cold_data_flow_ctr_group_config(
@@ -832,14 +1893,28 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         broker_addr: CheetahString,
         consumer_group: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .remove_cold_data_flow_ctr_group_config(
+                &broker_addr,
+                &consumer_group,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn get_cold_data_flow_ctr_info(
         &self,
         broker_addr: CheetahString,
     ) -> rocketmq_error::RocketMQResult<CheetahString> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .get_cold_data_flow_ctr_info(&broker_addr, self.timeout_millis.as_millis() as u64)
+            .await
     }
 
     async fn set_commit_log_read_ahead_mode(
@@ -847,7 +1922,16 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         broker_addr: CheetahString,
         mode: CheetahString,
     ) -> rocketmq_error::RocketMQResult<CheetahString> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .set_commit_log_read_ahead_mode(
+                &broker_addr,
+                &mode,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn create_user(
@@ -857,7 +1941,22 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         password: CheetahString,
         user_type: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        let user_info = UserInfo {
+            username: Some(username),
+            password: Some(password),
+            user_type: Some(user_type),
+            user_status: None,
+        };
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .create_user(
+                &broker_addr,
+                &user_info,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn update_user(
@@ -868,7 +1967,22 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         user_type: CheetahString,
         user_status: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        let user_info = UserInfo {
+            username: Some(username),
+            password: Some(password),
+            user_type: Some(user_type),
+            user_status: Some(user_status),
+        };
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .update_user(
+                &broker_addr,
+                &user_info,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn delete_user(
@@ -876,7 +1990,16 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         broker_addr: CheetahString,
         username: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .delete_user(
+                &broker_addr,
+                username,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn create_acl(
@@ -888,7 +2011,17 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         source_ips: Vec<CheetahString>,
         decision: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        let acl_info = build_acl_info(subject, resources, actions, source_ips, decision);
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .create_acl(
+                &broker_addr,
+                &acl_info,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn update_acl(
@@ -900,7 +2033,17 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         source_ips: Vec<CheetahString>,
         decision: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        let acl_info = build_acl_info(subject, resources, actions, source_ips, decision);
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .update_acl(
+                &broker_addr,
+                &acl_info,
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
     }
 
     async fn delete_acl(
@@ -909,6 +2052,153 @@ impl MQAdminExt for DefaultMQAdminExtImpl {
         subject: CheetahString,
         resource: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.client_instance
+            .as_ref()
+            .unwrap()
+            .get_mq_client_api_impl()
+            .delete_acl(
+                &broker_addr,
+                subject,
+                (!resource.is_empty()).then_some(resource),
+                self.timeout_millis.as_millis() as u64,
+            )
+            .await
+    }
+}
+
+/// Builds a custom policy granting `actions` on every resource of `resources`.
+fn build_acl_info(
+    subject: CheetahString,
+    resources: Vec<CheetahString>,
+    actions: Vec<CheetahString>,
+    source_ips: Vec<CheetahString>,
+    decision: CheetahString,
+) -> AclInfo {
+    let actions = CheetahString::from(
+        actions
+            .iter()
+            .map(|action| action.as_str())
+            .collect::<Vec<_>>()
+            .join(","),
+    );
+    let entries = resources
+        .into_iter()
+        .map(|resource| PolicyEntryInfo {
+            resource: Some(resource),
+            actions: Some(actions.clone()),
+            source_ips: Some(source_ips.clone()),
+            decision: Some(decision.clone()),
+        })
+        .collect();
+    AclInfo {
+        subject: Some(subject),
+        policies: Some(vec![PolicyInfo {
+            policy_type: Some(CheetahString::from_static_str(
+                acl_converter::POLICY_TYPE_CUSTOM,
+            )),
+            entries: Some(entries),
+        }]),
+    }
+}
+
+/// Maps a client error onto the admin tool result code that best describes it.
+fn admin_tool_failure<T>(error: rocketmq_error::RocketmqError) -> AdminToolResult<T> {
+    let code = match &error {
+        rocketmq_error::RocketmqError::MQClientBrokerError(_) => {
+            AdminToolsResultCodeEnum::MQBrokerError
+        }
+        rocketmq_error::RocketmqError::MQClientErr(_) => AdminToolsResultCodeEnum::MQClientError,
+        _ => AdminToolsResultCodeEnum::RemotingError,
+    };
+    AdminToolResult::failure(code, error.to_string())
+}
+
+/// Extracts the store host encoded in an offset message id.
+fn looks_like_offset_msg_id(msg_id: &str) -> bool {
+    (msg_id.len() == 32 || msg_id.len() == 56) && msg_id.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Looks a message up as an offset message id first and falls back to a unique key query, since
+/// a 32 character unique key is indistinguishable from an IPv4 offset message id.
+async fn resolve_message_by_id<ViewFut, QueryFut>(
+    msg_id: &CheetahString,
+    view_by_offset_msg_id: impl FnOnce() -> ViewFut,
+    query_by_uniq_key: impl FnOnce() -> QueryFut,
+) -> rocketmq_error::RocketMQResult<MessageExt>
+where
+    ViewFut: Future<Output = rocketmq_error::RocketMQResult<MessageExt>>,
+    QueryFut: Future<Output = rocketmq_error::RocketMQResult<MessageExt>>,
+{
+    if looks_like_offset_msg_id(msg_id.as_str()) {
+        match view_by_offset_msg_id().await {
+            Ok(message) => return Ok(message),
+            Err(e) => warn!(
+                "view message by offset msg id {} failed, fall back to uniq key query: {}",
+                msg_id, e
+            ),
+        }
+    }
+    query_by_uniq_key().await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use rocketmq_common::common::message::message_client_id_setter::MessageClientIDSetter;
+
+    use super::*;
+
+    fn stored_message(store_host: &str, msg_id: &str) -> MessageExt {
+        let mut message = MessageExt::default();
+        message.set_store_host(store_host.parse::<SocketAddr>().unwrap());
+        message.set_msg_id(msg_id.into());
+        message
+    }
+
+    #[tokio::test]
+    async fn resolve_message_by_id_uses_offset_msg_id_lookup() {
+        let msg_id = CheetahString::from_static_str("7F0000010000277E000000000000C350");
+        let message = resolve_message_by_id(
+            &msg_id,
+            || async { Ok(stored_message("127.0.0.1:10110", msg_id.as_str())) },
+            || async { panic!("an offset msg id must not be queried as a uniq key") },
+        )
+        .await
+        .unwrap();
+        assert_eq!(message.store_host().to_string(), "127.0.0.1:10110");
+    }
+
+    #[tokio::test]
+    async fn resolve_message_by_id_falls_back_to_uniq_key() {
+        let uniq_key = CheetahString::from_string(MessageClientIDSetter::create_uniq_id());
+        assert_eq!(uniq_key.len(), 32);
+        let message = resolve_message_by_id(
+            &uniq_key,
+            || async { mq_client_err!("connect to fake broker address timed out") },
+            || async {
+                Ok(stored_message(
+                    "10.0.0.8:10911",
+                    "0A00000800002A9F000000000000C350",
+                ))
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(message.store_host().to_string(), "10.0.0.8:10911");
+        assert_eq!(message.msg_id(), "0A00000800002A9F000000000000C350");
+    }
+
+    #[tokio::test]
+    async fn resolve_message_by_id_skips_offset_lookup_for_non_hex_id() {
+        let msg_id = CheetahString::from_static_str("not-a-message-id");
+        let message = resolve_message_by_id(
+            &msg_id,
+            || async { panic!("a non hex id must not be decoded as an offset msg id") },
+            || async { Ok(stored_message("10.0.0.8:10911", "")) },
+        )
+        .await
+        .unwrap();
+        assert_eq!(message.store_host().to_string(), "10.0.0.8:10911");
     }
 }
@@ -21,10 +21,12 @@ use cheetah_string::CheetahString;
 use rocketmq_common::common::base::plain_access_config::PlainAccessConfig;
 use rocketmq_common::common::config::TopicConfig;
 use rocketmq_common::common::message::message_enum::MessageRequestMode;
+use rocketmq_common::common::message::message_ext::MessageExt;
 use rocketmq_common::common::message::message_queue::MessageQueue;
 use rocketmq_remoting::protocol::admin::consume_stats::ConsumeStats;
 use rocketmq_remoting::protocol::admin::topic_stats_table::TopicStatsTable;
 use rocketmq_remoting::protocol::body::broker_body::cluster_info::ClusterInfo;
+use rocketmq_remoting::protocol::body::check_rocksdb_cqwrite_progress_response_body::CheckRocksdbCqWriteProgressResponseBody;
 use rocketmq_remoting::protocol::body::consume_message_directly_result::ConsumeMessageDirectlyResult;
 use rocketmq_remoting::protocol::body::consumer_connection::ConsumerConnection;
 use rocketmq_remoting::protocol::body::consumer_running_info::ConsumerRunningInfo;
@@ -38,7 +40,9 @@ use rocketmq_remoting::protocol::route::topic_route_data::TopicRouteData;
 use rocketmq_remoting::protocol::static_topic::topic_queue_mapping_detail::TopicQueueMappingDetail;
 use rocketmq_remoting::protocol::subscription::subscription_group_config::SubscriptionGroupConfig;
 
+use crate::base::query_result::QueryResult;
 use crate::common::admin_tool_result::AdminToolResult;
+use crate::consumer::pop_result::PopResult;
 
 #[allow(dead_code)]
 #[trait_variant::make(MQAdminExt: Send)]
@@ -156,11 +160,11 @@ pub trait MQAdminExtLocal: Sync {
         timeout_millis: Option<u64>,
     ) -> rocketmq_error::RocketMQResult<ConsumeStats>;
 
-    /*async fn check_rocksdb_cq_write_progress(
+    async fn check_rocksdb_cq_write_progress(
         &self,
         broker_addr: CheetahString,
         topic: CheetahString,
-    ) ->rocketmq_error::RocketMQResult<CheckRocksdbCqWriteProgressResponseBody>;*/
+    ) -> rocketmq_error::RocketMQResult<CheckRocksdbCqWriteProgressResponseBody>;
 
     async fn examine_broker_cluster_info(&self) -> rocketmq_error::RocketMQResult<ClusterInfo>;
 
@@ -364,7 +368,6 @@ pub trait MQAdminExtLocal: Sync {
         consumer_group: CheetahString,
         client_id: CheetahString,
         jstack: bool,
-        metrics: Option<bool>,
     ) -> rocketmq_error::RocketMQResult<ConsumerRunningInfo>;
 
     async fn consume_message_directly(
@@ -384,6 +387,29 @@ pub trait MQAdminExtLocal: Sync {
         msg_id: CheetahString,
     ) -> rocketmq_error::RocketMQResult<ConsumeMessageDirectlyResult>;
 
+    async fn view_message(
+        &self,
+        topic: CheetahString,
+        msg_id: CheetahString,
+    ) -> rocketmq_error::RocketMQResult<MessageExt>;
+
+    async fn query_message(
+        &self,
+        cluster_name: Option<CheetahString>,
+        topic: CheetahString,
+        msg_id: CheetahString,
+    ) -> rocketmq_error::RocketMQResult<MessageExt>;
+
+    async fn query_message_by_key(
+        &self,
+        cluster_name: Option<CheetahString>,
+        topic: CheetahString,
+        key: CheetahString,
+        max_num: i32,
+        begin: i64,
+        end: i64,
+    ) -> rocketmq_error::RocketMQResult<QueryResult>;
+
     /*async fn message_track_detail(
         &self,
         msg: MessageExt,
@@ -520,6 +546,28 @@ pub trait MQAdminExtLocal: Sync {
         force: bool,
     ) -> rocketmq_error::RocketMQResult<()>;
 
+    /// Reads up to `max_msg_nums` messages from the head of a queue on behalf of
+    /// `consumer_group`, without creating pop checkpoints or moving its consume offset.
+    /// A negative `queue_id` peeks every readable queue of the topic.
+    async fn peek_message(
+        &self,
+        broker_addr: CheetahString,
+        consumer_group: CheetahString,
+        topic: CheetahString,
+        queue_id: i32,
+        max_msg_nums: u32,
+    ) -> rocketmq_error::RocketMQResult<PopResult>;
+
+    /// Returns how many POP long-polling requests of `consumer_group` are parked on the broker
+    /// for the given queue.
+    async fn get_polling_info(
+        &self,
+        broker_addr: CheetahString,
+        consumer_group: CheetahString,
+        topic: CheetahString,
+        queue_id: i32,
+    ) -> rocketmq_error::RocketMQResult<i32>;
+
     /*async fn update_and_get_group_read_forbidden(
         &self,
         broker_addr: CheetahString,
@@ -528,13 +576,6 @@ pub trait MQAdminExtLocal: Sync {
         readable: Option<bool>,
     ) ->rocketmq_error::RocketMQResult<GroupForbidden>;
 
-    async fn query_message(
-        &self,
-        cluster_name: CheetahString,
-        topic: CheetahString,
-        msg_id: CheetahString,
-    ) ->rocketmq_error::RocketMQResult<MessageExt>;
-
     async fn get_broker_ha_status(&self, broker_addr: CheetahString) ->rocketmq_error::RocketMQResult<HARuntimeInfo>;
 
     async fn get_in_sync_state_data(
@@ -22,9 +22,12 @@ use rocketmq_common::common::base::plain_access_config::PlainAccessConfig;
 use rocketmq_common::common::config::TopicConfig;
 use rocketmq_common::common::message::message_enum::MessageRequestMode;
 use rocketmq_common::common::message::message_queue::MessageQueue;
+use rocketmq_error::mq_client_err;
+use rocketmq_remoting::code::response_code::ResponseCode;
 use rocketmq_remoting::protocol::admin::consume_stats::ConsumeStats;
 use rocketmq_remoting::protocol::admin::topic_stats_table::TopicStatsTable;
 use rocketmq_remoting::protocol::body::broker_body::cluster_info::ClusterInfo;
+use rocketmq_remoting::protocol::body::check_rocksdb_cqwrite_progress_response_body::CheckRocksdbCqWriteProgressResponseBody;
 use rocketmq_remoting::protocol::body::consume_message_directly_result::ConsumeMessageDirectlyResult;
 use rocketmq_remoting::protocol::body::consumer_connection::ConsumerConnection;
 use rocketmq_remoting::protocol::body::consumer_running_info::ConsumerRunningInfo;
@@ -40,6 +43,7 @@ use rocketmq_remoting::protocol::subscription::subscription_group_config::Subscr
 use rocketmq_rust::ArcMut;
 
 use crate::admin::default_mq_admin_ext_impl::DefaultMQAdminExtImpl;
+use crate::admin::mq_admin_ext_async::MQAdminExt;
 use crate::common::admin_tool_result::AdminToolResult;
 
 #[derive(Clone)]
@@ -53,7 +57,9 @@ impl MQAdminExtInnerImpl {
         broker_container_addr: CheetahString,
         broker_config: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .add_broker_to_container(broker_container_addr, broker_config)
+            .await
     }
 
     async fn remove_broker_from_container(
@@ -63,7 +69,14 @@ impl MQAdminExtInnerImpl {
         broker_name: CheetahString,
         broker_id: u64,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .remove_broker_from_container(
+                broker_container_addr,
+                cluster_name,
+                broker_name,
+                broker_id,
+            )
+            .await
     }
 
     async fn update_broker_config(
@@ -71,14 +84,16 @@ impl MQAdminExtInnerImpl {
         broker_addr: CheetahString,
         properties: HashMap<CheetahString, CheetahString>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .update_broker_config(broker_addr, properties)
+            .await
     }
 
     async fn get_broker_config(
         &self,
         broker_addr: CheetahString,
     ) -> rocketmq_error::RocketMQResult<HashMap<CheetahString, CheetahString>> {
-        unimplemented!()
+        self.inner.get_broker_config(broker_addr).await
     }
 
     async fn create_and_update_topic_config(
@@ -86,7 +101,9 @@ impl MQAdminExtInnerImpl {
         addr: CheetahString,
         config: TopicConfig,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .create_and_update_topic_config(addr, config)
+            .await
     }
 
     async fn create_and_update_topic_config_list(
@@ -94,7 +111,9 @@ impl MQAdminExtInnerImpl {
         addr: CheetahString,
         topic_config_list: Vec<TopicConfig>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .create_and_update_topic_config_list(addr, topic_config_list)
+            .await
     }
 
     async fn create_and_update_plain_access_config(
@@ -102,7 +121,9 @@ impl MQAdminExtInnerImpl {
         addr: CheetahString,
         config: PlainAccessConfig,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .create_and_update_plain_access_config(addr, config)
+            .await
     }
 
     async fn delete_plain_access_config(
@@ -110,7 +131,9 @@ impl MQAdminExtInnerImpl {
         addr: CheetahString,
         access_key: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .delete_plain_access_config(addr, access_key)
+            .await
     }
 
     async fn update_global_white_addr_config(
@@ -119,14 +142,18 @@ impl MQAdminExtInnerImpl {
         global_white_addrs: CheetahString,
         acl_file_full_path: Option<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .update_global_white_addr_config(addr, global_white_addrs, acl_file_full_path)
+            .await
     }
 
     async fn examine_broker_cluster_acl_version_info(
         &self,
         addr: CheetahString,
     ) -> rocketmq_error::RocketMQResult<CheetahString> {
-        unimplemented!()
+        self.inner
+            .examine_broker_cluster_acl_version_info(addr)
+            .await
     }
 
     async fn create_and_update_subscription_group_config(
@@ -134,7 +161,9 @@ impl MQAdminExtInnerImpl {
         addr: CheetahString,
         config: SubscriptionGroupConfig,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .create_and_update_subscription_group_config(addr, config)
+            .await
     }
 
     async fn create_and_update_subscription_group_config_list(
@@ -142,7 +171,9 @@ impl MQAdminExtInnerImpl {
         broker_addr: CheetahString,
         configs: Vec<SubscriptionGroupConfig>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .create_and_update_subscription_group_config_list(broker_addr, configs)
+            .await
     }
 
     async fn examine_subscription_group_config(
@@ -150,7 +181,9 @@ impl MQAdminExtInnerImpl {
         addr: CheetahString,
         group: CheetahString,
     ) -> rocketmq_error::RocketMQResult<SubscriptionGroupConfig> {
-        unimplemented!()
+        self.inner
+            .examine_subscription_group_config(addr, group)
+            .await
     }
 
     async fn examine_topic_stats(
@@ -158,32 +191,32 @@ impl MQAdminExtInnerImpl {
         topic: CheetahString,
         broker_addr: Option<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<TopicStatsTable> {
-        unimplemented!()
+        self.inner.examine_topic_stats(topic, broker_addr).await
     }
 
     async fn examine_topic_stats_concurrent(
         &self,
         topic: CheetahString,
     ) -> AdminToolResult<TopicStatsTable> {
-        unimplemented!()
+        self.inner.examine_topic_stats_concurrent(topic).await
     }
 
     async fn fetch_all_topic_list(&self) -> rocketmq_error::RocketMQResult<TopicList> {
-        unimplemented!()
+        self.inner.fetch_all_topic_list().await
     }
 
     async fn fetch_topics_by_cluster(
         &self,
         cluster_name: CheetahString,
     ) -> rocketmq_error::RocketMQResult<TopicList> {
-        unimplemented!()
+        self.inner.fetch_topics_by_cluster(cluster_name).await
     }
 
     async fn fetch_broker_runtime_stats(
         &self,
         broker_addr: CheetahString,
     ) -> rocketmq_error::RocketMQResult<KVTable> {
-        unimplemented!()
+        self.inner.fetch_broker_runtime_stats(broker_addr).await
     }
 
     async fn examine_consume_stats(
@@ -194,24 +227,42 @@ impl MQAdminExtInnerImpl {
         broker_addr: Option<CheetahString>,
         timeout_millis: Option<u64>,
     ) -> rocketmq_error::RocketMQResult<ConsumeStats> {
-        unimplemented!()
+        self.inner
+            .examine_consume_stats(
+                consumer_group,
+                topic,
+                cluster_name,
+                broker_addr,
+                timeout_millis,
+            )
+            .await
     }
 
-    /*async fn check_rocksdb_cq_write_progress(
+    async fn check_rocksdb_cq_write_progress(
         &self,
         broker_addr: CheetahString,
         topic: CheetahString,
-    ) -> rocketmq_error::RocketMQResult<CheckRocksdbCqWriteProgressResponseBody>{ unimplemented!()}*/
+    ) -> rocketmq_error::RocketMQResult<CheckRocksdbCqWriteProgressResponseBody> {
+        self.inner
+            .check_rocksdb_cq_write_progress(broker_addr, topic)
+            .await
+    }
 
     async fn examine_broker_cluster_info(&self) -> rocketmq_error::RocketMQResult<ClusterInfo> {
-        unimplemented!()
+        self.inner.examine_broker_cluster_info().await
     }
 
     async fn examine_topic_route_info(
         &self,
         topic: CheetahString,
     ) -> rocketmq_error::RocketMQResult<TopicRouteData> {
-        unimplemented!()
+        match self.inner.examine_topic_route_info(topic.clone()).await? {
+            Some(topic_route_data) => Ok(topic_route_data),
+            None => mq_client_err!(
+                ResponseCode::TopicNotExist,
+                format!("No topic route info in name server for the topic: {topic}")
+            ),
+        }
     }
 
     async fn examine_consumer_connection_info(
@@ -219,7 +270,9 @@ impl MQAdminExtInnerImpl {
         consumer_group: CheetahString,
         broker_addr: Option<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<ConsumerConnection> {
-        unimplemented!()
+        self.inner
+            .examine_consumer_connection_info(consumer_group, broker_addr)
+            .await
     }
 
     async fn examine_producer_connection_info(
@@ -227,7 +280,9 @@ impl MQAdminExtInnerImpl {
         producer_group: CheetahString,
         topic: CheetahString,
     ) -> rocketmq_error::RocketMQResult<ProducerConnection> {
-        unimplemented!()
+        self.inner
+            .examine_producer_connection_info(producer_group, topic)
+            .await
     }
 
     /* async fn get_all_producer_info(
@@ -236,7 +291,7 @@ impl MQAdminExtInnerImpl {
     ) -> rocketmq_error::RocketMQResult<ProducerTableInfo>{ unimplemented!()}*/
 
     async fn get_name_server_address_list(&self) -> Vec<CheetahString> {
-        unimplemented!()
+        self.inner.get_name_server_address_list().await
     }
 
     async fn wipe_write_perm_of_broker(
@@ -244,7 +299,9 @@ impl MQAdminExtInnerImpl {
         namesrv_addr: CheetahString,
         broker_name: CheetahString,
     ) -> rocketmq_error::RocketMQResult<i32> {
-        unimplemented!()
+        self.inner
+            .wipe_write_perm_of_broker(namesrv_addr, broker_name)
+            .await
     }
 
     async fn add_write_perm_of_broker(
@@ -252,7 +309,9 @@ impl MQAdminExtInnerImpl {
         namesrv_addr: CheetahString,
         broker_name: CheetahString,
     ) -> rocketmq_error::RocketMQResult<i32> {
-        unimplemented!()
+        self.inner
+            .add_write_perm_of_broker(namesrv_addr, broker_name)
+            .await
     }
 
     async fn put_kv_config(
@@ -261,7 +320,7 @@ impl MQAdminExtInnerImpl {
         key: CheetahString,
         value: CheetahString,
     ) {
-        unimplemented!()
+        self.inner.put_kv_config(namespace, key, value).await
     }
 
     async fn get_kv_config(
@@ -269,14 +328,14 @@ impl MQAdminExtInnerImpl {
         namespace: CheetahString,
         key: CheetahString,
     ) -> rocketmq_error::RocketMQResult<CheetahString> {
-        unimplemented!()
+        self.inner.get_kv_config(namespace, key).await
     }
 
     async fn get_kv_list_by_namespace(
         &self,
         namespace: CheetahString,
     ) -> rocketmq_error::RocketMQResult<KVTable> {
-        unimplemented!()
+        self.inner.get_kv_list_by_namespace(namespace).await
     }
 
     async fn delete_topic(
@@ -284,7 +343,7 @@ impl MQAdminExtInnerImpl {
         topic_name: CheetahString,
         cluster_name: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner.delete_topic(topic_name, cluster_name).await
     }
 
     async fn delete_topic_in_broker(
@@ -292,7 +351,7 @@ impl MQAdminExtInnerImpl {
         addrs: HashSet<CheetahString>,
         topic: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner.delete_topic_in_broker(addrs, topic).await
     }
 
     /*async fn delete_topic_in_broker_concurrent(
@@ -307,7 +366,9 @@ impl MQAdminExtInnerImpl {
         cluster_name: Option<CheetahString>,
         topic: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .delete_topic_in_name_server(addrs, cluster_name, topic)
+            .await
     }
 
     async fn delete_subscription_group(
@@ -316,7 +377,9 @@ impl MQAdminExtInnerImpl {
         group_name: CheetahString,
         remove_offset: Option<bool>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .delete_subscription_group(addr, group_name, remove_offset)
+            .await
     }
 
     async fn create_and_update_kv_config(
@@ -325,7 +388,9 @@ impl MQAdminExtInnerImpl {
         key: CheetahString,
         value: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .create_and_update_kv_config(namespace, key, value)
+            .await
     }
 
     async fn delete_kv_config(
@@ -333,7 +398,7 @@ impl MQAdminExtInnerImpl {
         namespace: CheetahString,
         key: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner.delete_kv_config(namespace, key).await
     }
 
     /*async fn reset_offset_by_timestamp_old(
@@ -352,7 +417,9 @@ impl MQAdminExtInnerImpl {
         timestamp: u64,
         is_force: bool,
     ) -> rocketmq_error::RocketMQResult<HashMap<MessageQueue, u64>> {
-        unimplemented!()
+        self.inner
+            .reset_offset_by_timestamp(cluster_name, topic, group, timestamp, is_force)
+            .await
     }
 
     async fn reset_offset_new(
@@ -361,7 +428,9 @@ impl MQAdminExtInnerImpl {
         topic: CheetahString,
         timestamp: u64,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .reset_offset_new(consumer_group, topic, timestamp)
+            .await
     }
 
     /*async fn reset_offset_new_concurrent(
@@ -377,7 +446,9 @@ impl MQAdminExtInnerImpl {
         group: CheetahString,
         client_addr: CheetahString,
     ) -> rocketmq_error::RocketMQResult<HashMap<CheetahString, HashMap<MessageQueue, u64>>> {
-        unimplemented!()
+        self.inner
+            .get_consume_status(topic, group, client_addr)
+            .await
     }
 
     async fn create_or_update_order_conf(
@@ -386,28 +457,30 @@ impl MQAdminExtInnerImpl {
         value: CheetahString,
         is_cluster: bool,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .create_or_update_order_conf(key, value, is_cluster)
+            .await
     }
 
     async fn query_topic_consume_by_who(
         &self,
         topic: CheetahString,
     ) -> rocketmq_error::RocketMQResult<GroupList> {
-        unimplemented!()
+        self.inner.query_topic_consume_by_who(topic).await
     }
 
     async fn query_topics_by_consumer(
         &self,
         group: CheetahString,
     ) -> rocketmq_error::RocketMQResult<TopicList> {
-        unimplemented!()
+        self.inner.query_topics_by_consumer(group).await
     }
 
     async fn query_topics_by_consumer_concurrent(
         &self,
         group: CheetahString,
     ) -> AdminToolResult<TopicList> {
-        unimplemented!()
+        self.inner.query_topics_by_consumer_concurrent(group).await
     }
 
     async fn query_subscription(
@@ -415,7 +488,7 @@ impl MQAdminExtInnerImpl {
         group: CheetahString,
         topic: CheetahString,
     ) -> rocketmq_error::RocketMQResult<SubscriptionData> {
-        unimplemented!()
+        self.inner.query_subscription(group, topic).await
     }
 
     /*async fn query_consume_time_span(
@@ -435,7 +508,7 @@ impl MQAdminExtInnerImpl {
         cluster: Option<CheetahString>,
         addr: Option<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<bool> {
-        unimplemented!()
+        self.inner.clean_expired_consumer_queue(cluster, addr).await
     }
 
     async fn delete_expired_commit_log(
@@ -443,7 +516,7 @@ impl MQAdminExtInnerImpl {
         cluster: Option<CheetahString>,
         addr: Option<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<bool> {
-        unimplemented!()
+        self.inner.delete_expired_commit_log(cluster, addr).await
     }
 
     async fn clean_unused_topic(
@@ -451,7 +524,7 @@ impl MQAdminExtInnerImpl {
         cluster: Option<CheetahString>,
         addr: Option<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<bool> {
-        unimplemented!()
+        self.inner.clean_unused_topic(cluster, addr).await
     }
 
     async fn get_consumer_running_info(
@@ -459,9 +532,10 @@ impl MQAdminExtInnerImpl {
         consumer_group: CheetahString,
         client_id: CheetahString,
         jstack: bool,
-        metrics: Option<bool>,
     ) -> rocketmq_error::RocketMQResult<ConsumerRunningInfo> {
-        unimplemented!()
+        self.inner
+            .get_consumer_running_info(consumer_group, client_id, jstack)
+            .await
     }
 
     async fn consume_message_directly(
@@ -471,7 +545,9 @@ impl MQAdminExtInnerImpl {
         topic: CheetahString,
         msg_id: CheetahString,
     ) -> rocketmq_error::RocketMQResult<ConsumeMessageDirectlyResult> {
-        unimplemented!()
+        self.inner
+            .consume_message_directly(consumer_group, client_id, topic, msg_id)
+            .await
     }
 
     async fn consume_message_directly_ext(
@@ -482,7 +558,9 @@ impl MQAdminExtInnerImpl {
         topic: CheetahString,
         msg_id: CheetahString,
     ) -> rocketmq_error::RocketMQResult<ConsumeMessageDirectlyResult> {
-        unimplemented!()
+        self.inner
+            .consume_message_directly_ext(cluster_name, consumer_group, client_id, topic, msg_id)
+            .await
     }
 
     /*async fn message_track_detail(
@@ -502,7 +580,9 @@ impl MQAdminExtInnerImpl {
         topic: CheetahString,
         is_offline: bool,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .clone_group_offset(src_group, dest_group, topic, is_offline)
+            .await
     }
 
     /*async fn view_broker_stats_data(
@@ -516,7 +596,7 @@ impl MQAdminExtInnerImpl {
         &self,
         topic: String,
     ) -> rocketmq_error::RocketMQResult<HashSet<CheetahString>> {
-        unimplemented!()
+        self.inner.get_cluster_list(topic).await
     }
 
     /*async fn fetch_consume_stats_in_broker(
@@ -530,7 +610,7 @@ impl MQAdminExtInnerImpl {
         &self,
         topic: String,
     ) -> rocketmq_error::RocketMQResult<HashSet<CheetahString>> {
-        unimplemented!()
+        self.inner.get_topic_cluster_list(topic).await
     }
 
     /*async fn get_all_subscription_group(
@@ -550,7 +630,9 @@ impl MQAdminExtInnerImpl {
         broker_addr: CheetahString,
         timeout_millis: u64,
     ) -> rocketmq_error::RocketMQResult<TopicConfigSerializeWrapper> {
-        unimplemented!()
+        self.inner
+            .get_all_topic_config(broker_addr, timeout_millis)
+            .await
     }
 
     async fn get_user_topic_config(
@@ -559,7 +641,9 @@ impl MQAdminExtInnerImpl {
         special_topic: bool,
         timeout_millis: u64,
     ) -> rocketmq_error::RocketMQResult<TopicConfigSerializeWrapper> {
-        unimplemented!()
+        self.inner
+            .get_user_topic_config(broker_addr, special_topic, timeout_millis)
+            .await
     }
 
     async fn update_consume_offset(
@@ -569,7 +653,9 @@ impl MQAdminExtInnerImpl {
         mq: MessageQueue,
         offset: u64,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .update_consume_offset(broker_addr, consume_group, mq, offset)
+            .await
     }
 
     async fn update_name_server_config(
@@ -577,7 +663,9 @@ impl MQAdminExtInnerImpl {
         properties: HashMap<CheetahString, CheetahString>,
         name_servers: Vec<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .update_name_server_config(properties, Some(name_servers))
+            .await
     }
 
     async fn get_name_server_config(
@@ -585,7 +673,7 @@ impl MQAdminExtInnerImpl {
         name_servers: Vec<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<HashMap<CheetahString, HashMap<CheetahString, CheetahString>>>
     {
-        unimplemented!()
+        self.inner.get_name_server_config(name_servers).await
     }
 
     /*async fn query_consume_queue(
@@ -603,7 +691,7 @@ impl MQAdminExtInnerImpl {
         topic: CheetahString,
         msg_id: CheetahString,
     ) -> rocketmq_error::RocketMQResult<bool> {
-        unimplemented!()
+        self.inner.resume_check_half_message(topic, msg_id).await
     }
 
     async fn set_message_request_mode(
@@ -615,7 +703,16 @@ impl MQAdminExtInnerImpl {
         pop_work_group_size: i32,
         timeout_millis: u64,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .set_message_request_mode(
+                broker_addr,
+                topic,
+                consumer_group,
+                mode,
+                pop_work_group_size,
+                timeout_millis,
+            )
+            .await
     }
 
     async fn reset_offset_by_queue_id(
@@ -626,7 +723,15 @@ impl MQAdminExtInnerImpl {
         queue_id: i32,
         reset_offset: u64,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .reset_offset_by_queue_id(
+                broker_addr,
+                consumer_group,
+                topic_name,
+                queue_id,
+                reset_offset,
+            )
+            .await
     }
 
     async fn examine_topic_config(
@@ -634,7 +739,7 @@ impl MQAdminExtInnerImpl {
         addr: CheetahString,
         topic: CheetahString,
     ) -> rocketmq_error::RocketMQResult<TopicConfig> {
-        unimplemented!()
+        self.inner.examine_topic_config(addr, topic).await
     }
 
     async fn create_static_topic(
@@ -645,7 +750,9 @@ impl MQAdminExtInnerImpl {
         mapping_detail: TopicQueueMappingDetail,
         force: bool,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .create_static_topic(addr, default_topic, topic_config, mapping_detail, force)
+            .await
     }
 
     /*async fn update_and_get_group_read_forbidden(
@@ -686,7 +793,9 @@ impl MQAdminExtInnerImpl {
         broker_addr: CheetahString,
         master_flush_offset: u64,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .reset_master_flush_offset(broker_addr, master_flush_offset)
+            .await
     }
 
     async fn get_controller_config(
@@ -694,7 +803,7 @@ impl MQAdminExtInnerImpl {
         controller_servers: Vec<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<HashMap<CheetahString, HashMap<CheetahString, CheetahString>>>
     {
-        unimplemented!()
+        self.inner.get_controller_config(controller_servers).await
     }
 
     async fn update_controller_config(
@@ -702,7 +811,9 @@ impl MQAdminExtInnerImpl {
         properties: HashMap<CheetahString, CheetahString>,
         controllers: Vec<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .update_controller_config(properties, controllers)
+            .await
     }
 
     /*async fn elect_master(
@@ -721,7 +832,15 @@ impl MQAdminExtInnerImpl {
         broker_controller_ids_to_clean: Option<CheetahString>,
         is_clean_living_broker: bool,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .clean_controller_broker_data(
+                controller_addr,
+                cluster_name,
+                broker_name,
+                broker_controller_ids_to_clean,
+                is_clean_living_broker,
+            )
+            .await
     }
 
     async fn update_cold_data_flow_ctr_group_config(
@@ -729,7 +848,9 @@ impl MQAdminExtInnerImpl {
         broker_addr: CheetahString,
         properties: HashMap<CheetahString, CheetahString>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .update_cold_data_flow_ctr_group_config(broker_addr, properties)
+            .await
     }
 
     async fn remove_cold_data_flow_ctr_group_config(
@@ -737,14 +858,16 @@ impl MQAdminExtInnerImpl {
         broker_addr: CheetahString,
         consumer_group: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .remove_cold_data_flow_ctr_group_config(broker_addr, consumer_group)
+            .await
     }
 
     async fn get_cold_data_flow_ctr_info(
         &self,
         broker_addr: CheetahString,
     ) -> rocketmq_error::RocketMQResult<CheetahString> {
-        unimplemented!()
+        self.inner.get_cold_data_flow_ctr_info(broker_addr).await
     }
 
     async fn set_commit_log_read_ahead_mode(
@@ -752,7 +875,9 @@ impl MQAdminExtInnerImpl {
         broker_addr: CheetahString,
         mode: CheetahString,
     ) -> rocketmq_error::RocketMQResult<CheetahString> {
-        unimplemented!()
+        self.inner
+            .set_commit_log_read_ahead_mode(broker_addr, mode)
+            .await
     }
 
     async fn create_user(
@@ -762,7 +887,9 @@ impl MQAdminExtInnerImpl {
         password: CheetahString,
         user_type: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .create_user(broker_addr, username, password, user_type)
+            .await
     }
 
     /*async fn create_user_with_info(
@@ -779,7 +906,9 @@ impl MQAdminExtInnerImpl {
         user_type: CheetahString,
         user_status: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .update_user(broker_addr, username, password, user_type, user_status)
+            .await
     }
 
     /* async fn update_user_with_info(
@@ -793,7 +922,7 @@ impl MQAdminExtInnerImpl {
         broker_addr: CheetahString,
         username: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner.delete_user(broker_addr, username).await
     }
 
     /*async fn get_user(
@@ -817,7 +946,16 @@ impl MQAdminExtInnerImpl {
         source_ips: Vec<CheetahString>,
         decision: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .create_acl(
+                broker_addr,
+                subject,
+                resources,
+                actions,
+                source_ips,
+                decision,
+            )
+            .await
     }
 
     /*async fn create_acl_with_info(
@@ -835,7 +973,16 @@ impl MQAdminExtInnerImpl {
         source_ips: Vec<CheetahString>,
         decision: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner
+            .update_acl(
+                broker_addr,
+                subject,
+                resources,
+                actions,
+                source_ips,
+                decision,
+            )
+            .await
     }
 
     /*async fn update_acl_with_info(
@@ -850,7 +997,7 @@ impl MQAdminExtInnerImpl {
         subject: CheetahString,
         resource: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        unimplemented!()
+        self.inner.delete_acl(broker_addr, subject, resource).await
     }
 
     /*async fn get_acl(
@@ -19,6 +19,8 @@ pub(crate) mod ack_result;
 pub(crate) mod ack_status;
 pub mod allocate_message_queue_strategy;
 pub(crate) mod consumer_impl;
+pub mod default_lite_pull_consumer;
+pub mod default_lite_pull_consumer_builder;
 pub mod default_mq_push_consumer;
 pub mod default_mq_push_consumer_builder;
 pub mod listener;
@@ -30,8 +32,8 @@ pub mod mq_consumer;
 pub(crate) mod mq_consumer_inner;
 pub mod mq_push_consumer;
 pub(crate) mod pop_callback;
-pub(crate) mod pop_result;
-pub(crate) mod pop_status;
+pub mod pop_result;
+pub mod pop_status;
 pub(crate) mod pull_callback;
 pub mod pull_result;
 pub mod pull_status;
@@ -0,0 +1,292 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use rocketmq_common::common::message::message_queue::MessageQueue;
+
+use crate::consumer::consumer_impl::process_queue::ProcessQueue;
+
+/// Per-queue bookkeeping of a lite pull consumer.
+///
+/// `pull_offset` is where the next pull starts, `consume_offset` is the offset the user has
+/// consumed up to (the one that gets committed) and `seek_offset` is a pending seek that
+/// overrides `pull_offset` on the next pull. `generation` is bumped on every seek so that
+/// results of pulls issued before the seek can be recognized and dropped.
+struct MessageQueueState {
+    process_queue: Arc<ProcessQueue>,
+    paused: bool,
+    pull_offset: i64,
+    consume_offset: i64,
+    seek_offset: i64,
+    generation: u64,
+}
+
+impl MessageQueueState {
+    fn new(process_queue: Arc<ProcessQueue>) -> Self {
+        MessageQueueState {
+            process_queue,
+            paused: false,
+            pull_offset: -1,
+            consume_offset: -1,
+            seek_offset: -1,
+            generation: 0,
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct AssignedMessageQueue {
+    assigned_message_queue_state: RwLock<HashMap<MessageQueue, MessageQueueState>>,
+}
+
+impl AssignedMessageQueue {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn message_queues(&self) -> HashSet<MessageQueue> {
+        self.assigned_message_queue_state
+            .read()
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    pub(crate) fn contains(&self, mq: &MessageQueue) -> bool {
+        self.assigned_message_queue_state.read().contains_key(mq)
+    }
+
+    pub(crate) fn is_paused(&self, mq: &MessageQueue) -> bool {
+        self.assigned_message_queue_state
+            .read()
+            .get(mq)
+            .is_some_and(|state| state.paused)
+    }
+
+    pub(crate) fn pause(&self, mqs: &[MessageQueue]) {
+        let mut table = self.assigned_message_queue_state.write();
+        for mq in mqs {
+            if let Some(state) = table.get_mut(mq) {
+                state.paused = true;
+            }
+        }
+    }
+
+    pub(crate) fn resume(&self, mqs: &[MessageQueue]) {
+        let mut table = self.assigned_message_queue_state.write();
+        for mq in mqs {
+            if let Some(state) = table.get_mut(mq) {
+                state.paused = false;
+            }
+        }
+    }
+
+    pub(crate) fn get_process_queue(&self, mq: &MessageQueue) -> Option<Arc<ProcessQueue>> {
+        self.assigned_message_queue_state
+            .read()
+            .get(mq)
+            .map(|state| state.process_queue.clone())
+    }
+
+    pub(crate) fn get_pull_offset(&self, mq: &MessageQueue) -> i64 {
+        self.assigned_message_queue_state
+            .read()
+            .get(mq)
+            .map_or(-1, |state| state.pull_offset)
+    }
+
+    pub(crate) fn get_consume_offset(&self, mq: &MessageQueue) -> i64 {
+        self.assigned_message_queue_state
+            .read()
+            .get(mq)
+            .map_or(-1, |state| state.consume_offset)
+    }
+
+    pub(crate) fn get_seek_offset(&self, mq: &MessageQueue) -> i64 {
+        self.assigned_message_queue_state
+            .read()
+            .get(mq)
+            .map_or(-1, |state| state.seek_offset)
+    }
+
+    pub(crate) fn get_generation(&self, mq: &MessageQueue) -> Option<u64> {
+        self.assigned_message_queue_state
+            .read()
+            .get(mq)
+            .map(|state| state.generation)
+    }
+
+    /// Updates the pull offset unless a seek happened since `generation` was read.
+    pub(crate) fn update_pull_offset(&self, mq: &MessageQueue, offset: i64, generation: u64) {
+        if let Some(state) = self.assigned_message_queue_state.write().get_mut(mq) {
+            if state.generation == generation {
+                state.pull_offset = offset;
+            }
+        }
+    }
+
+    pub(crate) fn update_consume_offset(&self, mq: &MessageQueue, offset: i64) {
+        if let Some(state) = self.assigned_message_queue_state.write().get_mut(mq) {
+            state.consume_offset = offset;
+        }
+    }
+
+    /// Records a pending seek and invalidates pulls that are still in flight.
+    pub(crate) fn set_seek_offset(&self, mq: &MessageQueue, offset: i64) {
+        if let Some(state) = self.assigned_message_queue_state.write().get_mut(mq) {
+            state.seek_offset = offset;
+            state.generation += 1;
+        }
+    }
+
+    /// Takes the pending seek offset, if any, making it the new pull and consume offset.
+    pub(crate) fn take_seek_offset(&self, mq: &MessageQueue) -> Option<i64> {
+        let mut table = self.assigned_message_queue_state.write();
+        let state = table.get_mut(mq)?;
+        if state.seek_offset < 0 {
+            return None;
+        }
+        let offset = state.seek_offset;
+        state.seek_offset = -1;
+        state.pull_offset = offset;
+        state.consume_offset = offset;
+        Some(offset)
+    }
+
+    /// Replaces the queues of `topic` with `assigned`, as decided by a rebalance.
+    ///
+    /// Process queues are shared with the rebalance table when present there, so that
+    /// dropping a queue during rebalance is visible to the pull task.
+    pub(crate) fn update_assigned_message_queue_with_topic(
+        &self,
+        topic: &str,
+        assigned: &HashSet<MessageQueue>,
+        process_queue_table: &HashMap<MessageQueue, Arc<ProcessQueue>>,
+    ) {
+        let mut table = self.assigned_message_queue_state.write();
+        table.retain(|mq, state| {
+            if mq.get_topic() == topic && !assigned.contains(mq) {
+                state.process_queue.set_dropped(true);
+                return false;
+            }
+            true
+        });
+        for mq in assigned {
+            table.entry(mq.clone()).or_insert_with(|| {
+                let process_queue = process_queue_table
+                    .get(mq)
+                    .cloned()
+                    .unwrap_or_else(|| Arc::new(ProcessQueue::new()));
+                MessageQueueState::new(process_queue)
+            });
+        }
+    }
+
+    /// Replaces every assigned queue with `assigned`, as requested by `assign`.
+    pub(crate) fn update_assigned_message_queue(&self, assigned: &HashSet<MessageQueue>) {
+        let mut table = self.assigned_message_queue_state.write();
+        table.retain(|mq, state| {
+            if !assigned.contains(mq) {
+                state.process_queue.set_dropped(true);
+                return false;
+            }
+            true
+        });
+        for mq in assigned {
+            table
+                .entry(mq.clone())
+                .or_insert_with(|| MessageQueueState::new(Arc::new(ProcessQueue::new())));
+        }
+    }
+
+    pub(crate) fn remove_assigned_message_queue(&self, topic: &str) {
+        self.assigned_message_queue_state
+            .write()
+            .retain(|mq, state| {
+                if mq.get_topic() == topic {
+                    state.process_queue.set_dropped(true);
+                    return false;
+                }
+                true
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mq(topic: &str, queue_id: i32) -> MessageQueue {
+        MessageQueue::from_parts(topic, "broker-a", queue_id)
+    }
+
+    #[test]
+    fn update_assigned_message_queue_with_topic_keeps_other_topics() {
+        let assigned = AssignedMessageQueue::new();
+        assigned.update_assigned_message_queue(&HashSet::from([mq("a", 0), mq("b", 0)]));
+        let dropped_pq = assigned.get_process_queue(&mq("a", 0)).unwrap();
+
+        let shared_pq = Arc::new(ProcessQueue::new());
+        let table = HashMap::from([(mq("a", 1), shared_pq.clone())]);
+        assigned.update_assigned_message_queue_with_topic(
+            "a",
+            &HashSet::from([mq("a", 1)]),
+            &table,
+        );
+
+        assert_eq!(
+            assigned.message_queues(),
+            HashSet::from([mq("a", 1), mq("b", 0)])
+        );
+        assert!(dropped_pq.is_dropped());
+        assert!(Arc::ptr_eq(
+            &assigned.get_process_queue(&mq("a", 1)).unwrap(),
+            &shared_pq
+        ));
+    }
+
+    #[test]
+    fn seek_invalidates_in_flight_pull() {
+        let assigned = AssignedMessageQueue::new();
+        let queue = mq("a", 0);
+        assigned.update_assigned_message_queue(&HashSet::from([queue.clone()]));
+        let generation = assigned.get_generation(&queue).unwrap();
+
+        assigned.set_seek_offset(&queue, 42);
+        assigned.update_pull_offset(&queue, 100, generation);
+        assert_eq!(assigned.get_pull_offset(&queue), -1);
+
+        assert_eq!(assigned.take_seek_offset(&queue), Some(42));
+        assert_eq!(assigned.take_seek_offset(&queue), None);
+        assert_eq!(assigned.get_pull_offset(&queue), 42);
+        assert_eq!(assigned.get_consume_offset(&queue), 42);
+    }
+
+    #[test]
+    fn pause_and_resume() {
+        let assigned = AssignedMessageQueue::new();
+        let queue = mq("a", 0);
+        assigned.update_assigned_message_queue(&HashSet::from([queue.clone()]));
+        assigned.pause(std::slice::from_ref(&queue));
+        assert!(assigned.is_paused(&queue));
+        assigned.resume(std::slice::from_ref(&queue));
+        assert!(!assigned.is_paused(&queue));
+    }
+}
@@ -36,6 +36,7 @@ use tracing::warn;
 
 use crate::base::client_config::ClientConfig;
 use crate::consumer::consumer_impl::consume_message_service::ConsumeMessageServiceTrait;
+use crate::consumer::consumer_impl::consume_task_pool::ConsumeTaskPool;
 use crate::consumer::consumer_impl::default_mq_push_consumer_impl::DefaultMQPushConsumerImpl;
 use crate::consumer::consumer_impl::pop_process_queue::PopProcessQueue;
 use crate::consumer::consumer_impl::process_queue::ProcessQueue;
@@ -53,6 +54,7 @@ pub struct ConsumeMessageConcurrentlyService {
     pub(crate) consumer_group: CheetahString,
     pub(crate) message_listener: ArcBoxMessageListenerConcurrently,
     pub(crate) consume_runtime: RocketMQRuntime,
+    pub(crate) consume_task_pool: ConsumeTaskPool,
 }
 
 impl ConsumeMessageConcurrentlyService {
@@ -65,16 +67,21 @@ impl ConsumeMessageConcurrentlyService {
     ) -> Self {
         let consume_thread = consumer_config.consume_thread_max;
         let consumer_group_tag = format!("{}_{}", "ConsumeMessageThread_", consumer_group);
+        let consume_runtime =
+            RocketMQRuntime::new_multi(consume_thread as usize, consumer_group_tag.as_str());
+        let consume_task_pool = ConsumeTaskPool::new(
+            consume_runtime.get_handle().clone(),
+            consumer_config.consume_thread_min as usize,
+            consume_thread as usize,
+        );
         Self {
             default_mqpush_consumer_impl,
             client_config,
             consumer_config,
             consumer_group,
             message_listener,
-            consume_runtime: RocketMQRuntime::new_multi(
-                consume_thread as usize,
-                consumer_group_tag.as_str(),
-            ),
+            consume_runtime,
+            consume_task_pool,
         }
     }
 }
@@ -117,6 +124,21 @@ impl ConsumeMessageConcurrentlyService {
                 ack_index = -1;
             }
         }
+        if let Some(consumer_stats_manager) = self
+            .default_mqpush_consumer_impl
+            .as_ref()
+            .and_then(|consumer| consumer.consumer_stats_manager())
+        {
+            let ok = (ack_index + 1) as u64;
+            let failed = consume_request.msgs.len() as u64 - ok;
+            let topic = consume_request.message_queue.get_topic();
+            consumer_stats_manager.inc_consume_ok_tps(self.consumer_group.as_str(), topic, ok);
+            consumer_stats_manager.inc_consume_failed_tps(
+                self.consumer_group.as_str(),
+                topic,
+                failed,
+            );
+        }
 
         match self.consumer_config.message_model {
             MessageModel::Broadcasting => {
@@ -225,7 +247,25 @@ impl ConsumeMessageServiceTrait for ConsumeMessageConcurrentlyService {
     }
 
     async fn shutdown(&mut self, await_terminate_millis: u64) {
-        // todo!()
+        self.consume_task_pool
+            .shutdown(await_terminate_millis)
+            .await;
+    }
+
+    fn update_core_pool_size(&self, core_pool_size: usize) {
+        self.consume_task_pool.update_core_pool_size(core_pool_size);
+    }
+
+    fn inc_core_pool_size(&self) {
+        self.consume_task_pool.inc_core_pool_size();
+    }
+
+    fn dec_core_pool_size(&self) {
+        self.consume_task_pool.dec_core_pool_size();
+    }
+
+    fn get_core_pool_size(&self) -> usize {
+        self.consume_task_pool.core_pool_size()
     }
 
     async fn consume_message_directly(
@@ -296,8 +336,7 @@ impl ConsumeMessageServiceTrait for ConsumeMessageConcurrentlyService {
                 default_mqpush_consumer_impl: self.default_mqpush_consumer_impl.clone(),
             };
 
-            self.consume_runtime
-                .get_handle()
+            self.consume_task_pool
                 .spawn(async move { consume_request.run(this).await });
         } else {
             msgs.chunks(consume_batch_size as usize)
@@ -313,7 +352,7 @@ impl ConsumeMessageServiceTrait for ConsumeMessageConcurrentlyService {
                         default_mqpush_consumer_impl: self.default_mqpush_consumer_impl.clone(),
                     };
                     let consume_message_concurrently_service = this.clone();
-                    self.consume_runtime.get_handle().spawn(async move {
+                    self.consume_task_pool.spawn(async move {
                         consume_request
                             .run(consume_message_concurrently_service)
                             .await
@@ -419,6 +458,14 @@ impl ConsumeRequest {
         }
 
         let consume_rt = begin_timestamp.elapsed().as_millis() as u64;
+        if let Some(consumer_stats_manager) = default_mqpush_consumer_impl.consumer_stats_manager()
+        {
+            consumer_stats_manager.inc_consume_rt(
+                self.consumer_group.as_str(),
+                self.message_queue.get_topic(),
+                consume_rt,
+            );
+        }
 
         let return_type = if let Some(s) = status {
             if consume_rt > default_mqpush_consumer_impl.consumer_config.consume_timeout * 60 * 1000
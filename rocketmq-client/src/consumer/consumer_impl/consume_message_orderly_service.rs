@@ -39,6 +39,7 @@ use tracing::warn;
 
 use crate::base::client_config::ClientConfig;
 use crate::consumer::consumer_impl::consume_message_service::ConsumeMessageServiceTrait;
+use crate::consumer::consumer_impl::consume_task_pool::ConsumeTaskPool;
 use crate::consumer::consumer_impl::default_mq_push_consumer_impl::DefaultMQPushConsumerImpl;
 use crate::consumer::consumer_impl::pop_process_queue::PopProcessQueue;
 use crate::consumer::consumer_impl::process_queue::ProcessQueue;
@@ -67,6 +68,7 @@ pub struct ConsumeMessageOrderlyService {
     pub(crate) consumer_group: CheetahString,
     pub(crate) message_listener: ArcBoxMessageListenerOrderly,
     pub(crate) consume_runtime: RocketMQRuntime,
+    pub(crate) consume_task_pool: ConsumeTaskPool,
     pub(crate) stopped: AtomicBool,
     pub(crate) global_lock: Arc<RocketMQTokioMutex<()>>,
     pub(crate) message_queue_lock: MessageQueueLock,
@@ -82,16 +84,21 @@ impl ConsumeMessageOrderlyService {
     ) -> Self {
         let consume_thread = consumer_config.consume_thread_max;
         let consumer_group_tag = format!("{}_{}", "ConsumeMessageThread_", consumer_group);
+        let consume_runtime =
+            RocketMQRuntime::new_multi(consume_thread as usize, consumer_group_tag.as_str());
+        let consume_task_pool = ConsumeTaskPool::new(
+            consume_runtime.get_handle().clone(),
+            consumer_config.consume_thread_min as usize,
+            consume_thread as usize,
+        );
         Self {
             default_mqpush_consumer_impl,
             client_config,
             consumer_config,
             consumer_group,
             message_listener,
-            consume_runtime: RocketMQRuntime::new_multi(
-                consume_thread as usize,
-                consumer_group_tag.as_str(),
-            ),
+            consume_runtime,
+            consume_task_pool,
             stopped: AtomicBool::new(false),
             global_lock: Arc::new(Default::default()),
             message_queue_lock: Default::default(),
@@ -289,6 +296,31 @@ impl ConsumeMessageOrderlyService {
         context: &ConsumeOrderlyContext,
         consume_request: &mut ConsumeRequest,
     ) -> bool {
+        if let Some(consumer_stats_manager) = self
+            .default_mqpush_consumer_impl
+            .as_ref()
+            .and_then(|consumer| consumer.consumer_stats_manager())
+        {
+            let consume_ok = match status {
+                ConsumeOrderlyStatus::Success | ConsumeOrderlyStatus::Commit => true,
+                ConsumeOrderlyStatus::Rollback => context.is_auto_commit(),
+                ConsumeOrderlyStatus::SuspendCurrentQueueAMoment => false,
+            };
+            let topic = consume_request.message_queue.get_topic();
+            if consume_ok {
+                consumer_stats_manager.inc_consume_ok_tps(
+                    self.consumer_group.as_str(),
+                    topic,
+                    msgs.len() as u64,
+                );
+            } else {
+                consumer_stats_manager.inc_consume_failed_tps(
+                    self.consumer_group.as_str(),
+                    topic,
+                    msgs.len() as u64,
+                );
+            }
+        }
         let (continue_consume, commit_offset) = if context.is_auto_commit() {
             match status {
                 ConsumeOrderlyStatus::Success
@@ -382,11 +414,32 @@ impl ConsumeMessageServiceTrait for ConsumeMessageOrderlyService {
     }
 
     async fn shutdown(&mut self, await_terminate_millis: u64) {
+        self.stopped
+            .store(true, std::sync::atomic::Ordering::Release);
+        self.consume_task_pool
+            .shutdown(await_terminate_millis)
+            .await;
         if MessageModel::Clustering == self.consumer_config.message_model {
             self.unlock_all_mq().await;
         }
     }
 
+    fn update_core_pool_size(&self, core_pool_size: usize) {
+        self.consume_task_pool.update_core_pool_size(core_pool_size);
+    }
+
+    fn inc_core_pool_size(&self) {
+        self.consume_task_pool.inc_core_pool_size();
+    }
+
+    fn dec_core_pool_size(&self) {
+        self.consume_task_pool.dec_core_pool_size();
+    }
+
+    fn get_core_pool_size(&self) -> usize {
+        self.consume_task_pool.core_pool_size()
+    }
+
     #[allow(deprecated)]
     async fn consume_message_directly(
         &self,
@@ -461,7 +514,7 @@ impl ConsumeMessageServiceTrait for ConsumeMessageOrderlyService {
             default_mqpush_consumer_impl: self.default_mqpush_consumer_impl.clone(),
             consumer_group: self.consumer_group.clone(),
         };
-        self.consume_runtime.get_handle().spawn(async move {
+        self.consume_task_pool.spawn(async move {
             consume_request.run(this).await;
         });
     }
@@ -515,6 +568,16 @@ impl ConsumeRequest {
                     );
                     break;
                 }
+                if consume_message_orderly_service_inner
+                    .stopped
+                    .load(std::sync::atomic::Ordering::Acquire)
+                {
+                    warn!(
+                        "the consume service is stopped, stop consuming {}",
+                        self.message_queue
+                    );
+                    break;
+                }
                 if MessageModel::Clustering == default_mqpush_consumer_impl.message_model()
                     && !self.process_queue.is_locked()
                 {
@@ -636,6 +699,15 @@ impl ConsumeRequest {
                     );
                 }
                 let consume_rt = begin_timestamp.elapsed().as_millis() as u64;
+                if let Some(consumer_stats_manager) =
+                    default_mqpush_consumer_impl.consumer_stats_manager()
+                {
+                    consumer_stats_manager.inc_consume_rt(
+                        self.consumer_group.as_str(),
+                        self.message_queue.get_topic(),
+                        consume_rt,
+                    );
+                }
                 let return_type = match status {
                     None => {
                         if has_exception {
@@ -39,6 +39,7 @@ use crate::base::client_config::ClientConfig;
 use crate::consumer::ack_callback::AckCallback;
 use crate::consumer::ack_result::AckResult;
 use crate::consumer::consumer_impl::consume_message_service::ConsumeMessageServiceTrait;
+use crate::consumer::consumer_impl::consume_task_pool::ConsumeTaskPool;
 use crate::consumer::consumer_impl::default_mq_push_consumer_impl::DefaultMQPushConsumerImpl;
 use crate::consumer::consumer_impl::pop_process_queue::PopProcessQueue;
 use crate::consumer::consumer_impl::process_queue::ProcessQueue;
@@ -56,6 +57,7 @@ pub struct ConsumeMessagePopConcurrentlyService {
     pub(crate) consumer_group: CheetahString,
     pub(crate) message_listener: ArcBoxMessageListenerConcurrently,
     pub(crate) pop_consume_runtime: RocketMQRuntime,
+    pub(crate) consume_task_pool: ConsumeTaskPool,
 }
 
 impl ConsumeMessagePopConcurrentlyService {
@@ -68,16 +70,21 @@ impl ConsumeMessagePopConcurrentlyService {
     ) -> Self {
         let consume_thread = consumer_config.consume_thread_max;
         let consumer_group_tag = format!("{}_{}", "PopConsumeMessageThread_", consumer_group);
+        let pop_consume_runtime =
+            RocketMQRuntime::new_multi(consume_thread as usize, consumer_group_tag.as_str());
+        let consume_task_pool = ConsumeTaskPool::new(
+            pop_consume_runtime.get_handle().clone(),
+            consumer_config.consume_thread_min as usize,
+            consume_thread as usize,
+        );
         Self {
             default_mqpush_consumer_impl,
             client_config,
             consumer_config,
             consumer_group,
             message_listener,
-            pop_consume_runtime: RocketMQRuntime::new_multi(
-                consume_thread as usize,
-                consumer_group_tag.as_str(),
-            ),
+            pop_consume_runtime,
+            consume_task_pool,
         }
     }
 }
@@ -88,7 +95,25 @@ impl ConsumeMessageServiceTrait for ConsumeMessagePopConcurrentlyService {
     }
 
     async fn shutdown(&mut self, await_terminate_millis: u64) {
-        todo!()
+        self.consume_task_pool
+            .shutdown(await_terminate_millis)
+            .await;
+    }
+
+    fn update_core_pool_size(&self, core_pool_size: usize) {
+        self.consume_task_pool.update_core_pool_size(core_pool_size);
+    }
+
+    fn inc_core_pool_size(&self) {
+        self.consume_task_pool.inc_core_pool_size();
+    }
+
+    fn dec_core_pool_size(&self) {
+        self.consume_task_pool.dec_core_pool_size();
+    }
+
+    fn get_core_pool_size(&self) -> usize {
+        self.consume_task_pool.core_pool_size()
     }
 
     async fn consume_message_directly(
@@ -170,7 +195,7 @@ impl ConsumeMessageServiceTrait for ConsumeMessagePopConcurrentlyService {
         if msgs.len() < consume_batch_size as usize {
             let mut request =
                 ConsumeRequest::new(msgs, Arc::new(process_queue.clone()), message_queue.clone());
-            self.pop_consume_runtime.get_handle().spawn(async move {
+            self.consume_task_pool.spawn(async move {
                 request.run(this).await;
             });
         } else {
@@ -183,7 +208,7 @@ impl ConsumeMessageServiceTrait for ConsumeMessagePopConcurrentlyService {
                         message_queue.clone(),
                     );
                     let pop_consume_message_concurrently_service = this.clone();
-                    self.pop_consume_runtime.get_handle().spawn(async move {
+                    self.consume_task_pool.spawn(async move {
                         consume_request
                             .run(pop_consume_message_concurrently_service)
                             .await
@@ -210,17 +235,26 @@ impl ConsumeMessagePopConcurrentlyService {
                 if ack_index >= consume_request.msgs.len() as i32 {
                     ack_index = consume_request.msgs.len() as i32 - 1;
                 }
-                /*int ok = ackIndex + 1;
-                int failed = consumeRequest.getMsgs().size() - ok;
-                this.getConsumerStatsManager().incConsumeOKTPS(consumerGroup, topic, ok);
-                this.getConsumerStatsManager().incConsumeFailedTPS(consumerGroup, topic, failed);*/
             }
             ConsumeConcurrentlyStatus::ReconsumeLater => {
-                //this.getConsumerStatsManager().incConsumeFailedTPS(consumerGroup, topic, failed);
-                // Java code
                 ack_index = -1;
             }
         }
+        if let Some(consumer_stats_manager) = self
+            .default_mqpush_consumer_impl
+            .as_ref()
+            .and_then(|consumer| consumer.consumer_stats_manager())
+        {
+            let ok = (ack_index + 1) as u64;
+            let failed = consume_request.msgs.len() as u64 - ok;
+            let topic = consume_request.message_queue.get_topic();
+            consumer_stats_manager.inc_consume_ok_tps(self.consumer_group.as_str(), topic, ok);
+            consumer_stats_manager.inc_consume_failed_tps(
+                self.consumer_group.as_str(),
+                topic,
+                failed,
+            );
+        }
 
         //ack if consume success
         for i in 0..ack_index {
@@ -455,6 +489,14 @@ impl ConsumeRequest {
             }
         }
         let consume_rt = begin_timestamp.elapsed().as_millis() as u64;
+        if let Some(consumer_stats_manager) = default_mqpush_consumer_impl.consumer_stats_manager()
+        {
+            consumer_stats_manager.inc_consume_rt(
+                self.consumer_group.as_str(),
+                self.message_queue.get_topic(),
+                consume_rt,
+            );
+        }
         let return_type = match status {
             None => {
                 if has_exception {
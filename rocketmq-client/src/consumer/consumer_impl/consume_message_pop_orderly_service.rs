@@ -32,6 +32,7 @@ use tracing::warn;
 
 use crate::base::client_config::ClientConfig;
 use crate::consumer::consumer_impl::consume_message_service::ConsumeMessageServiceTrait;
+use crate::consumer::consumer_impl::consume_task_pool::ConsumeTaskPool;
 use crate::consumer::consumer_impl::default_mq_push_consumer_impl::DefaultMQPushConsumerImpl;
 use crate::consumer::consumer_impl::pop_process_queue::PopProcessQueue;
 use crate::consumer::consumer_impl::process_queue::ProcessQueue;
@@ -48,6 +49,7 @@ pub struct ConsumeMessagePopOrderlyService {
     pub(crate) consumer_group: CheetahString,
     pub(crate) message_listener: ArcBoxMessageListenerOrderly,
     pub(crate) consume_runtime: RocketMQRuntime,
+    pub(crate) consume_task_pool: ConsumeTaskPool,
     pub(self) consume_request_set: HashSet<ConsumeRequest>,
     pub(crate) message_queue_lock: MessageQueueLock,
     pub(crate) consume_request_lock: MessageQueueLock,
@@ -63,16 +65,21 @@ impl ConsumeMessagePopOrderlyService {
     ) -> Self {
         let consume_thread = consumer_config.consume_thread_max;
         let consumer_group_tag = format!("{}_{}", "PopConsumeMessageThread_", consumer_group);
+        let consume_runtime =
+            RocketMQRuntime::new_multi(consume_thread as usize, consumer_group_tag.as_str());
+        let consume_task_pool = ConsumeTaskPool::new(
+            consume_runtime.get_handle().clone(),
+            consumer_config.consume_thread_min as usize,
+            consume_thread as usize,
+        );
         Self {
             default_mqpush_consumer_impl,
             client_config,
             consumer_config,
             consumer_group,
             message_listener,
-            consume_runtime: RocketMQRuntime::new_multi(
-                consume_thread as usize,
-                consumer_group_tag.as_str(),
-            ),
+            consume_runtime,
+            consume_task_pool,
             consume_request_set: Default::default(),
             message_queue_lock: Default::default(),
             consume_request_lock: Default::default(),
@@ -96,7 +103,7 @@ impl ConsumeMessagePopOrderlyService {
         let _lock = lock.lock().await;
         let is_new_req = self.consume_request_set.insert(request.clone());
         if is_new_req || force {
-            self.consume_runtime.get_handle().spawn(async move {
+            self.consume_task_pool.spawn(async move {
                 request.run(this).await;
             });
         }
@@ -107,7 +114,25 @@ impl ConsumeMessageServiceTrait for ConsumeMessagePopOrderlyService {
     fn start(&mut self, this: ArcMut<Self>) {}
 
     async fn shutdown(&mut self, await_terminate_millis: u64) {
-        todo!()
+        self.consume_task_pool
+            .shutdown(await_terminate_millis)
+            .await;
+    }
+
+    fn update_core_pool_size(&self, core_pool_size: usize) {
+        self.consume_task_pool.update_core_pool_size(core_pool_size);
+    }
+
+    fn inc_core_pool_size(&self) {
+        self.consume_task_pool.inc_core_pool_size();
+    }
+
+    fn dec_core_pool_size(&self) {
+        self.consume_task_pool.dec_core_pool_size();
+    }
+
+    fn get_core_pool_size(&self) -> usize {
+        self.consume_task_pool.core_pool_size()
     }
 
     #[allow(deprecated)]
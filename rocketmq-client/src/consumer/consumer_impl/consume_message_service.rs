@@ -67,23 +67,64 @@ where
     }
 
     pub async fn shutdown(&mut self, await_terminate_millis: u64) {
-        todo!()
+        if let Some(consume_message_concurrently_service) =
+            &mut self.consume_message_concurrently_service
+        {
+            consume_message_concurrently_service
+                .shutdown(await_terminate_millis)
+                .await;
+        }
+        if let Some(consume_message_orderly_service) = &mut self.consume_message_orderly_service {
+            consume_message_orderly_service
+                .shutdown(await_terminate_millis)
+                .await;
+        }
     }
 
     pub fn update_core_pool_size(&self, core_pool_size: usize) {
-        todo!()
+        if let Some(consume_message_concurrently_service) =
+            &self.consume_message_concurrently_service
+        {
+            consume_message_concurrently_service.update_core_pool_size(core_pool_size);
+        } else if let Some(consume_message_orderly_service) = &self.consume_message_orderly_service
+        {
+            consume_message_orderly_service.update_core_pool_size(core_pool_size);
+        }
     }
 
     pub fn inc_core_pool_size(&self) {
-        todo!()
+        if let Some(consume_message_concurrently_service) =
+            &self.consume_message_concurrently_service
+        {
+            consume_message_concurrently_service.inc_core_pool_size();
+        } else if let Some(consume_message_orderly_service) = &self.consume_message_orderly_service
+        {
+            consume_message_orderly_service.inc_core_pool_size();
+        }
     }
 
     pub fn dec_core_pool_size(&self) {
-        todo!()
+        if let Some(consume_message_concurrently_service) =
+            &self.consume_message_concurrently_service
+        {
+            consume_message_concurrently_service.dec_core_pool_size();
+        } else if let Some(consume_message_orderly_service) = &self.consume_message_orderly_service
+        {
+            consume_message_orderly_service.dec_core_pool_size();
+        }
     }
 
     pub fn get_core_pool_size(&self) -> usize {
-        todo!()
+        if let Some(consume_message_concurrently_service) =
+            &self.consume_message_concurrently_service
+        {
+            consume_message_concurrently_service.get_core_pool_size()
+        } else if let Some(consume_message_orderly_service) = &self.consume_message_orderly_service
+        {
+            consume_message_orderly_service.get_core_pool_size()
+        } else {
+            0
+        }
     }
 
     pub async fn consume_message_directly(
@@ -200,23 +241,70 @@ where
     }
 
     pub async fn shutdown(&mut self, await_terminate_millis: u64) {
-        todo!()
+        if let Some(consume_message_pop_concurrently_service) =
+            &mut self.consume_message_pop_concurrently_service
+        {
+            consume_message_pop_concurrently_service
+                .shutdown(await_terminate_millis)
+                .await;
+        }
+        if let Some(consume_message_pop_orderly_service) =
+            &mut self.consume_message_pop_orderly_service
+        {
+            consume_message_pop_orderly_service
+                .shutdown(await_terminate_millis)
+                .await;
+        }
     }
 
-    fn update_core_pool_size(&self, core_pool_size: usize) {
-        todo!()
+    pub fn update_core_pool_size(&self, core_pool_size: usize) {
+        if let Some(consume_message_pop_concurrently_service) =
+            &self.consume_message_pop_concurrently_service
+        {
+            consume_message_pop_concurrently_service.update_core_pool_size(core_pool_size);
+        } else if let Some(consume_message_pop_orderly_service) =
+            &self.consume_message_pop_orderly_service
+        {
+            consume_message_pop_orderly_service.update_core_pool_size(core_pool_size);
+        }
     }
 
-    fn inc_core_pool_size(&self) {
-        todo!()
+    pub fn inc_core_pool_size(&self) {
+        if let Some(consume_message_pop_concurrently_service) =
+            &self.consume_message_pop_concurrently_service
+        {
+            consume_message_pop_concurrently_service.inc_core_pool_size();
+        } else if let Some(consume_message_pop_orderly_service) =
+            &self.consume_message_pop_orderly_service
+        {
+            consume_message_pop_orderly_service.inc_core_pool_size();
+        }
     }
 
-    fn dec_core_pool_size(&self) {
-        todo!()
+    pub fn dec_core_pool_size(&self) {
+        if let Some(consume_message_pop_concurrently_service) =
+            &self.consume_message_pop_concurrently_service
+        {
+            consume_message_pop_concurrently_service.dec_core_pool_size();
+        } else if let Some(consume_message_pop_orderly_service) =
+            &self.consume_message_pop_orderly_service
+        {
+            consume_message_pop_orderly_service.dec_core_pool_size();
+        }
     }
 
-    fn get_core_pool_size(&self) -> usize {
-        todo!()
+    pub fn get_core_pool_size(&self) -> usize {
+        if let Some(consume_message_pop_concurrently_service) =
+            &self.consume_message_pop_concurrently_service
+        {
+            consume_message_pop_concurrently_service.get_core_pool_size()
+        } else if let Some(consume_message_pop_orderly_service) =
+            &self.consume_message_pop_orderly_service
+        {
+            consume_message_pop_orderly_service.get_core_pool_size()
+        } else {
+            0
+        }
     }
 
     pub(crate) async fn consume_message_directly(
@@ -0,0 +1,271 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::future::Future;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use tokio::runtime::Handle;
+use tokio::sync::OwnedSemaphorePermit;
+use tokio::sync::Semaphore;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+use tracing::info;
+use tracing::warn;
+
+/// Runs consume requests on the consume runtime while bounding how many of them execute at
+/// once.
+///
+/// The bound plays the role of the Java consumer's thread pool core size: a runtime's worker
+/// count is fixed once built, so concurrency is resized through semaphore permits instead.
+pub(crate) struct ConsumeTaskPool {
+    handle: Handle,
+    semaphore: Arc<Semaphore>,
+    core_pool_size: Mutex<usize>,
+    max_pool_size: usize,
+    /// Permits to retire as running tasks release them, left over when shrinking below the
+    /// number of tasks in flight.
+    permits_to_retire: Arc<AtomicUsize>,
+    tracker: TaskTracker,
+    cancel_token: CancellationToken,
+}
+
+impl ConsumeTaskPool {
+    pub(crate) fn new(handle: Handle, core_pool_size: usize, max_pool_size: usize) -> Self {
+        let max_pool_size = max_pool_size.max(1);
+        let core_pool_size = core_pool_size.clamp(1, max_pool_size);
+        Self {
+            handle,
+            semaphore: Arc::new(Semaphore::new(core_pool_size)),
+            core_pool_size: Mutex::new(core_pool_size),
+            max_pool_size,
+            permits_to_retire: Arc::new(AtomicUsize::new(0)),
+            tracker: TaskTracker::new(),
+            cancel_token: CancellationToken::new(),
+        }
+    }
+
+    /// Queues `task`; it starts once fewer than the core pool size tasks are running.
+    ///
+    /// Tasks submitted after [`shutdown`](Self::shutdown) began are rejected.
+    pub(crate) fn spawn<F>(&self, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        if self.tracker.is_closed() {
+            warn!("consume task pool is shut down, reject consume task");
+            return;
+        }
+        let semaphore = self.semaphore.clone();
+        let permits_to_retire = self.permits_to_retire.clone();
+        let cancel_token = self.cancel_token.clone();
+        self.tracker.spawn_on(
+            async move {
+                tokio::select! {
+                    _ = cancel_token.cancelled() => {}
+                    _ = async move {
+                        let Ok(permit) = semaphore.acquire_owned().await else {
+                            return;
+                        };
+                        let _permit = PoolPermit {
+                            permit: Some(permit),
+                            permits_to_retire,
+                        };
+                        task.await;
+                    } => {}
+                }
+            },
+            &self.handle,
+        );
+    }
+
+    /// Sets how many tasks may run at once. Sizes of zero or above the maximum pool size are
+    /// ignored.
+    pub(crate) fn update_core_pool_size(&self, core_pool_size: usize) {
+        if core_pool_size == 0 || core_pool_size > self.max_pool_size {
+            warn!(
+                "ignore core pool size {}, it must be within [1, {}]",
+                core_pool_size, self.max_pool_size
+            );
+            return;
+        }
+        let mut current = self.core_pool_size.lock();
+        if core_pool_size > *current {
+            let grow = core_pool_size - *current;
+            // Cancel pending retirements before handing out new permits.
+            let pending = self
+                .permits_to_retire
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |pending| {
+                    Some(pending - pending.min(grow))
+                })
+                .unwrap_or_default();
+            self.semaphore.add_permits(grow - pending.min(grow));
+        } else if core_pool_size < *current {
+            let shrink = *current - core_pool_size;
+            let forgotten = self.semaphore.forget_permits(shrink);
+            self.permits_to_retire
+                .fetch_add(shrink - forgotten, Ordering::AcqRel);
+        }
+        *current = core_pool_size;
+    }
+
+    pub(crate) fn inc_core_pool_size(&self) {
+        let core_pool_size = self.core_pool_size();
+        self.update_core_pool_size(core_pool_size + 1);
+    }
+
+    pub(crate) fn dec_core_pool_size(&self) {
+        let core_pool_size = self.core_pool_size();
+        self.update_core_pool_size(core_pool_size - 1);
+    }
+
+    #[inline]
+    pub(crate) fn core_pool_size(&self) -> usize {
+        *self.core_pool_size.lock()
+    }
+
+    /// Rejects new tasks and waits up to `await_terminate_millis` for queued and running ones to
+    /// finish; whatever is left afterwards is cancelled.
+    pub(crate) async fn shutdown(&self, await_terminate_millis: u64) {
+        self.tracker.close();
+        let drained = tokio::time::timeout(
+            Duration::from_millis(await_terminate_millis),
+            self.tracker.wait(),
+        )
+        .await;
+        if drained.is_err() {
+            warn!(
+                "{} consume tasks still running after {}ms, cancel them",
+                self.tracker.len(),
+                await_terminate_millis
+            );
+            self.cancel_token.cancel();
+            self.tracker.wait().await;
+        } else {
+            info!("consume task pool drained");
+        }
+    }
+}
+
+/// Returns its permit to the pool on drop, unless the pool shrank in the meantime.
+struct PoolPermit {
+    permit: Option<OwnedSemaphorePermit>,
+    permits_to_retire: Arc<AtomicUsize>,
+}
+
+impl Drop for PoolPermit {
+    fn drop(&mut self) {
+        let retire = self
+            .permits_to_retire
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |pending| {
+                pending.checked_sub(1)
+            })
+            .is_ok();
+        if let (true, Some(permit)) = (retire, self.permit.take()) {
+            permit.forget();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn run_tasks(pool: &ConsumeTaskPool, tasks: usize) -> usize {
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        for _ in 0..tasks {
+            let running = running.clone();
+            let peak = peak.clone();
+            pool.spawn(async move {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+            });
+        }
+        while !pool.tracker.is_empty() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        peak.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrency_follows_core_pool_size() {
+        let pool = ConsumeTaskPool::new(Handle::current(), 2, 8);
+        assert_eq!(run_tasks(&pool, 10).await, 2);
+
+        pool.update_core_pool_size(4);
+        assert_eq!(pool.core_pool_size(), 4);
+        assert_eq!(run_tasks(&pool, 10).await, 4);
+
+        pool.dec_core_pool_size();
+        pool.dec_core_pool_size();
+        pool.dec_core_pool_size();
+        assert_eq!(pool.core_pool_size(), 1);
+        assert_eq!(run_tasks(&pool, 5).await, 1);
+
+        pool.update_core_pool_size(0);
+        pool.update_core_pool_size(9);
+        assert_eq!(pool.core_pool_size(), 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn shrinking_below_running_tasks_retires_permits() {
+        let pool = ConsumeTaskPool::new(Handle::current(), 4, 8);
+        let gate = Arc::new(Semaphore::new(0));
+        for _ in 0..4 {
+            let gate = gate.clone();
+            pool.spawn(async move {
+                let _ = gate.acquire().await;
+            });
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        pool.update_core_pool_size(1);
+        gate.add_permits(4);
+        while !pool.tracker.is_empty() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert_eq!(pool.semaphore.available_permits(), 1);
+        assert_eq!(run_tasks(&pool, 4).await, 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn shutdown_drains_then_cancels() {
+        let pool = ConsumeTaskPool::new(Handle::current(), 1, 1);
+        let finished = Arc::new(AtomicUsize::new(0));
+        for delay in [10, 10_000] {
+            let finished = finished.clone();
+            pool.spawn(async move {
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                finished.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        pool.shutdown(200).await;
+        assert_eq!(finished.load(Ordering::SeqCst), 1);
+        assert!(pool.tracker.is_empty());
+
+        let finished_ = finished.clone();
+        pool.spawn(async move {
+            finished_.fetch_add(1, Ordering::SeqCst);
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(finished.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,1341 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use cheetah_string::CheetahString;
+use parking_lot::Mutex;
+use parking_lot::RwLock;
+use rocketmq_common::common::base::service_state::ServiceState;
+use rocketmq_common::common::consumer::consume_from_where::ConsumeFromWhere;
+use rocketmq_common::common::message::message_ext::MessageExt;
+use rocketmq_common::common::message::message_queue::MessageQueue;
+use rocketmq_common::common::message::MessageTrait;
+use rocketmq_common::common::mix_all::DEFAULT_CONSUMER_GROUP;
+use rocketmq_common::common::sys_flag::pull_sys_flag::PullSysFlag;
+use rocketmq_common::common::FAQUrl;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_error::mq_client_err;
+use rocketmq_remoting::protocol::body::consumer_running_info::ConsumerRunningInfo;
+use rocketmq_remoting::protocol::filter::filter_api::FilterAPI;
+use rocketmq_remoting::protocol::heartbeat::consume_type::ConsumeType;
+use rocketmq_remoting::protocol::heartbeat::message_model::MessageModel;
+use rocketmq_remoting::protocol::heartbeat::subscription_data::SubscriptionData;
+use rocketmq_remoting::protocol::namespace_util::NamespaceUtil;
+use rocketmq_rust::ArcMut;
+use tokio::runtime::Handle;
+use tokio::sync::Notify;
+use tracing::error;
+use tracing::info;
+use tracing::warn;
+
+use crate::base::client_config::ClientConfig;
+use crate::base::validators::Validators;
+use crate::consumer::consumer_impl::assigned_message_queue::AssignedMessageQueue;
+use crate::consumer::consumer_impl::process_queue::ProcessQueue;
+use crate::consumer::consumer_impl::pull_api_wrapper::PullAPIWrapper;
+use crate::consumer::consumer_impl::pull_request_ext::PullResultExt;
+use crate::consumer::consumer_impl::re_balance::rebalance_lite_pull_impl::RebalanceLitePullImpl;
+use crate::consumer::consumer_impl::re_balance::Rebalance;
+use crate::consumer::default_lite_pull_consumer::LitePullConsumerConfig;
+use crate::consumer::message_selector::MessageSelector;
+use crate::consumer::mq_consumer_inner::MQConsumerInner;
+use crate::consumer::mq_consumer_inner::MQConsumerInnerImpl;
+use crate::consumer::pull_callback::PullCallback;
+use crate::consumer::pull_status::PullStatus;
+use crate::consumer::store::local_file_offset_store::LocalFileOffsetStore;
+use crate::consumer::store::offset_store::OffsetStore;
+use crate::consumer::store::read_offset_type::ReadOffsetType;
+use crate::consumer::store::remote_broker_offset_store::RemoteBrokerOffsetStore;
+use crate::consumer::topic_message_queue_change_listener::TopicMessageQueueChangeListener;
+use crate::factory::mq_client_instance::MQClientInstance;
+use crate::implementation::communication_mode::CommunicationMode;
+use crate::implementation::mq_client_manager::MQClientManager;
+
+const PULL_TIME_DELAY_MILLS_WHEN_PAUSE: u64 = 1000;
+const PULL_TIME_DELAY_MILLS_WHEN_FLOW_CONTROL: u64 = 50;
+const MIN_AUTOCOMMIT_INTERVAL_MILLIS: u64 = 1000;
+const TOPIC_METADATA_CHECK_INITIAL_DELAY_MILLIS: u64 = 10 * 1000;
+const _1MB: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SubscriptionType {
+    None,
+    Subscribe,
+    Assign,
+}
+
+/// A batch of prefetched messages waiting to be handed out by `poll`.
+struct ConsumeRequest {
+    messages: Vec<ArcMut<MessageExt>>,
+    message_queue: MessageQueue,
+    process_queue: Arc<ProcessQueue>,
+}
+
+/// Lite pull consumers pull synchronously, so the callback is never invoked.
+struct SyncPullCallback;
+
+impl PullCallback for SyncPullCallback {
+    async fn on_success(&mut self, _pull_result: PullResultExt) {}
+
+    fn on_exception(&mut self, _e: Box<dyn std::error::Error + Send>) {}
+}
+
+pub struct DefaultLitePullConsumerImpl {
+    client_config: ArcMut<ClientConfig>,
+    consumer_config: ArcMut<LitePullConsumerConfig>,
+    pub(crate) rebalance_impl: ArcMut<RebalanceLitePullImpl>,
+    service_state: ArcMut<ServiceState>,
+    pub(crate) client_instance: Option<ArcMut<MQClientInstance>>,
+    pull_api_wrapper: Option<ArcMut<PullAPIWrapper>>,
+    pub(crate) offset_store: Option<ArcMut<OffsetStore>>,
+    assigned_message_queue: AssignedMessageQueue,
+    subscription_type: Mutex<SubscriptionType>,
+    topic_to_sub_expression: RwLock<HashMap<CheetahString, CheetahString>>,
+    consume_request_cache: Mutex<VecDeque<ConsumeRequest>>,
+    consume_request_notify: Notify,
+    task_table: Mutex<HashMap<MessageQueue, Arc<AtomicBool>>>,
+    topic_message_queue_change_listener_map:
+        RwLock<HashMap<CheetahString, Arc<dyn TopicMessageQueueChangeListener>>>,
+    message_queues_for_topic: RwLock<HashMap<CheetahString, HashSet<MessageQueue>>>,
+    next_auto_commit_deadline: AtomicU64,
+    consume_request_flow_control_times: AtomicU64,
+    queue_flow_control_times: AtomicU64,
+    queue_max_span_flow_control_times: AtomicU64,
+    default_lite_pull_consumer_impl: Option<ArcMut<DefaultLitePullConsumerImpl>>,
+}
+
+impl DefaultLitePullConsumerImpl {
+    pub fn new(
+        client_config: ArcMut<ClientConfig>,
+        consumer_config: ArcMut<LitePullConsumerConfig>,
+    ) -> Self {
+        let mut this = Self {
+            client_config,
+            consumer_config: consumer_config.clone(),
+            rebalance_impl: ArcMut::new(RebalanceLitePullImpl::new(consumer_config)),
+            service_state: ArcMut::new(ServiceState::CreateJust),
+            client_instance: None,
+            pull_api_wrapper: None,
+            offset_store: None,
+            assigned_message_queue: AssignedMessageQueue::new(),
+            subscription_type: Mutex::new(SubscriptionType::None),
+            topic_to_sub_expression: RwLock::new(HashMap::new()),
+            consume_request_cache: Mutex::new(VecDeque::new()),
+            consume_request_notify: Notify::new(),
+            task_table: Mutex::new(HashMap::new()),
+            topic_message_queue_change_listener_map: RwLock::new(HashMap::new()),
+            message_queues_for_topic: RwLock::new(HashMap::new()),
+            next_auto_commit_deadline: AtomicU64::new(0),
+            consume_request_flow_control_times: AtomicU64::new(0),
+            queue_flow_control_times: AtomicU64::new(0),
+            queue_max_span_flow_control_times: AtomicU64::new(0),
+            default_lite_pull_consumer_impl: None,
+        };
+        let wrapper = ArcMut::downgrade(&this.rebalance_impl);
+        this.rebalance_impl.set_rebalance_impl(wrapper);
+        this
+    }
+
+    pub fn set_default_lite_pull_consumer_impl(
+        &mut self,
+        default_lite_pull_consumer_impl: ArcMut<DefaultLitePullConsumerImpl>,
+    ) {
+        self.rebalance_impl
+            .set_default_lite_pull_consumer_impl(default_lite_pull_consumer_impl.clone());
+        self.default_lite_pull_consumer_impl = Some(default_lite_pull_consumer_impl);
+    }
+
+    #[inline]
+    pub fn is_running(&self) -> bool {
+        *self.service_state == ServiceState::Running
+    }
+}
+
+impl DefaultLitePullConsumerImpl {
+    pub async fn start(&mut self) -> rocketmq_error::RocketMQResult<()> {
+        match *self.service_state {
+            ServiceState::CreateJust => {
+                info!(
+                    "the lite pull consumer [{}] start beginning. message_model={}, isUnitMode={}",
+                    self.consumer_config.consumer_group,
+                    self.consumer_config.message_model,
+                    self.consumer_config.unit_mode
+                );
+                *self.service_state = ServiceState::StartFailed;
+                self.check_config()?;
+                if self.consumer_config.message_model == MessageModel::Clustering {
+                    self.client_config.change_instance_name_to_pid();
+                }
+                let client_instance = MQClientManager::get_instance()
+                    .get_or_create_mq_client_instance(
+                        self.client_config.as_ref().clone(),
+                        self.consumer_config.rpc_hook.clone(),
+                    );
+                self.client_instance = Some(client_instance.clone());
+                self.rebalance_impl
+                    .set_consumer_group(self.consumer_config.consumer_group.clone());
+                self.rebalance_impl
+                    .set_message_model(self.consumer_config.message_model);
+                self.rebalance_impl.set_allocate_message_queue_strategy(
+                    self.consumer_config
+                        .allocate_message_queue_strategy
+                        .clone()
+                        .expect(
+                            "allocate_message_queue_strategy is null, please set it before start",
+                        ),
+                );
+                self.rebalance_impl
+                    .set_mq_client_factory(client_instance.clone());
+                self.pull_api_wrapper = Some(ArcMut::new(PullAPIWrapper::new(
+                    client_instance.clone(),
+                    self.consumer_config.consumer_group.clone(),
+                    self.consumer_config.unit_mode,
+                )));
+                let offset_store = match self.consumer_config.message_model {
+                    MessageModel::Broadcasting => {
+                        OffsetStore::new_with_local(LocalFileOffsetStore::new(
+                            client_instance.clone(),
+                            self.consumer_config.consumer_group.clone(),
+                        ))
+                    }
+                    MessageModel::Clustering => {
+                        OffsetStore::new_with_remote(RemoteBrokerOffsetStore::new(
+                            client_instance.clone(),
+                            self.consumer_config.consumer_group.clone(),
+                        ))
+                    }
+                };
+                offset_store.load().await?;
+                self.offset_store = Some(ArcMut::new(offset_store));
+
+                let registered = self
+                    .client_instance
+                    .as_mut()
+                    .unwrap()
+                    .register_consumer(
+                        &self.consumer_config.consumer_group,
+                        MQConsumerInnerImpl::new_lite_pull(
+                            self.default_lite_pull_consumer_impl
+                                .clone()
+                                .expect("default_lite_pull_consumer_impl is None"),
+                        ),
+                    )
+                    .await;
+                if !registered {
+                    return mq_client_err!(format!(
+                        "The consumer group[{}] has been created before, specify another name \
+                         please.{}",
+                        self.consumer_config.consumer_group,
+                        FAQUrl::suggest_todo(FAQUrl::GROUP_NAME_DUPLICATE_URL)
+                    ));
+                }
+                let cloned = self.client_instance.as_mut().cloned().unwrap();
+                self.client_instance.as_mut().unwrap().start(cloned).await?;
+                self.next_auto_commit_deadline.store(
+                    get_current_millis() + self.consumer_config.auto_commit_interval_millis,
+                    Ordering::Release,
+                );
+                info!(
+                    "the lite pull consumer [{}] start OK",
+                    self.consumer_config.consumer_group
+                );
+                *self.service_state = ServiceState::Running;
+            }
+            ServiceState::Running => {
+                return mq_client_err!("The LitePullConsumer service state is Running");
+            }
+            ServiceState::ShutdownAlready => {
+                return mq_client_err!("The LitePullConsumer service state is ShutdownAlready");
+            }
+            ServiceState::StartFailed => {
+                return mq_client_err!(format!(
+                    "The LitePullConsumer service state not OK, maybe started once,{:?},{}",
+                    *self.service_state,
+                    FAQUrl::suggest_todo(FAQUrl::CLIENT_SERVICE_NOT_OK)
+                ));
+            }
+        }
+        self.start_schedule_task();
+        self.operate_after_running().await
+    }
+
+    pub async fn shutdown(&mut self) {
+        match *self.service_state {
+            ServiceState::Running => {
+                self.persist_consumer_offset().await;
+                let client = self.client_instance.as_mut().unwrap();
+                client
+                    .unregister_consumer(self.consumer_config.consumer_group.clone())
+                    .await;
+                for (_, cancelled) in self.task_table.lock().drain() {
+                    cancelled.store(true, Ordering::Release);
+                }
+                self.consume_request_cache.lock().clear();
+                self.client_instance.as_mut().unwrap().shutdown().await;
+                self.rebalance_impl.destroy();
+                *self.service_state = ServiceState::ShutdownAlready;
+                info!(
+                    "the lite pull consumer [{}] shutdown OK",
+                    self.consumer_config.consumer_group
+                );
+            }
+            _ => {
+                warn!(
+                    "the lite pull consumer [{}] is not running, state: {:?}, do nothing",
+                    self.consumer_config.consumer_group, *self.service_state
+                );
+            }
+        }
+    }
+
+    fn check_config(&self) -> rocketmq_error::RocketMQResult<()> {
+        Validators::check_group(self.consumer_config.consumer_group.as_str())?;
+        if self.consumer_config.consumer_group == DEFAULT_CONSUMER_GROUP {
+            return mq_client_err!(format!(
+                "consumer_group can not equal {} please specify another one.{}",
+                DEFAULT_CONSUMER_GROUP,
+                FAQUrl::suggest_todo(FAQUrl::CLIENT_PARAMETER_CHECK_URL)
+            ));
+        }
+        if self
+            .consumer_config
+            .allocate_message_queue_strategy
+            .is_none()
+        {
+            return mq_client_err!(format!(
+                "allocate_message_queue_strategy is null{}",
+                FAQUrl::suggest_todo(FAQUrl::CLIENT_PARAMETER_CHECK_URL)
+            ));
+        }
+        if self.consumer_config.consumer_timeout_millis_when_suspend
+            < self.consumer_config.broker_suspend_max_time_millis
+        {
+            return mq_client_err!(format!(
+                "Long polling mode, the consumer consumer_timeout_millis_when_suspend must \
+                 greater than broker_suspend_max_time_millis{}",
+                FAQUrl::suggest_todo(FAQUrl::CLIENT_PARAMETER_CHECK_URL)
+            ));
+        }
+        if self.consumer_config.auto_commit_interval_millis < MIN_AUTOCOMMIT_INTERVAL_MILLIS {
+            return mq_client_err!(format!(
+                "auto_commit_interval_millis Out of range [{}, +∞){}",
+                MIN_AUTOCOMMIT_INTERVAL_MILLIS,
+                FAQUrl::suggest_todo(FAQUrl::CLIENT_PARAMETER_CHECK_URL)
+            ));
+        }
+        if self.consumer_config.pull_batch_size < 1 || self.consumer_config.pull_batch_size > 1024 {
+            return mq_client_err!(format!(
+                "pull_batch_size Out of range [1, 1024]{}",
+                FAQUrl::suggest_todo(FAQUrl::CLIENT_PARAMETER_CHECK_URL)
+            ));
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn make_sure_state_ok(&self) -> rocketmq_error::RocketMQResult<()> {
+        if *self.service_state != ServiceState::Running {
+            return mq_client_err!(format!(
+                "The consumer service state not OK, {},{}",
+                *self.service_state,
+                FAQUrl::suggest_todo(FAQUrl::CLIENT_SERVICE_NOT_OK)
+            ));
+        }
+        Ok(())
+    }
+
+    async fn operate_after_running(&mut self) -> rocketmq_error::RocketMQResult<()> {
+        match *self.subscription_type.lock() {
+            SubscriptionType::Assign => {
+                let message_queues = self.assigned_message_queue.message_queues();
+                self.update_assign_pull_task(&message_queues);
+            }
+            SubscriptionType::Subscribe | SubscriptionType::None => {}
+        }
+        if *self.subscription_type.lock() == SubscriptionType::Subscribe {
+            self.update_topic_subscribe_info_when_subscription_changed()
+                .await;
+            let client_instance = self.client_instance.as_mut().unwrap();
+            client_instance
+                .send_heartbeat_to_all_broker_with_lock()
+                .await;
+            client_instance.re_balance_immediately();
+        }
+        let topics = self
+            .topic_message_queue_change_listener_map
+            .read()
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        for topic in topics {
+            let message_queues = self.fetch_message_queues(&topic).await?;
+            self.message_queues_for_topic
+                .write()
+                .insert(topic, message_queues.into_iter().collect());
+        }
+        Ok(())
+    }
+
+    fn start_schedule_task(&self) {
+        let mut this = self.default_lite_pull_consumer_impl.clone().unwrap();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(
+                TOPIC_METADATA_CHECK_INITIAL_DELAY_MILLIS,
+            ))
+            .await;
+            while this.is_running() {
+                this.fetch_topic_message_queues_and_compare().await;
+                tokio::time::sleep(Duration::from_millis(
+                    this.consumer_config.topic_metadata_check_interval_millis,
+                ))
+                .await;
+            }
+        });
+    }
+
+    async fn fetch_topic_message_queues_and_compare(&mut self) {
+        let listeners = self
+            .topic_message_queue_change_listener_map
+            .read()
+            .iter()
+            .map(|(topic, listener)| (topic.clone(), listener.clone()))
+            .collect::<Vec<_>>();
+        for (topic, listener) in listeners {
+            let new_message_queues = match self.fetch_message_queues(&topic).await {
+                Ok(message_queues) => message_queues.into_iter().collect::<HashSet<_>>(),
+                Err(e) => {
+                    error!(
+                        "scheduled fetch message queues of topic {} failed: {}",
+                        topic, e
+                    );
+                    continue;
+                }
+            };
+            let changed = self
+                .message_queues_for_topic
+                .read()
+                .get(&topic)
+                .is_none_or(|old| *old != new_message_queues);
+            if changed {
+                self.message_queues_for_topic
+                    .write()
+                    .insert(topic.clone(), new_message_queues.clone());
+                listener.on_changed(topic.as_str(), new_message_queues);
+            }
+        }
+    }
+
+    async fn update_topic_subscribe_info_when_subscription_changed(&mut self) {
+        let sub_table = self.rebalance_impl.get_subscription_inner();
+        let topics = sub_table.read().await.keys().cloned().collect::<Vec<_>>();
+        let client = self.client_instance.as_mut().unwrap();
+        for topic in &topics {
+            client
+                .update_topic_route_info_from_name_server_topic(topic)
+                .await;
+        }
+    }
+
+    fn set_subscription_type(
+        &self,
+        subscription_type: SubscriptionType,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        let mut current = self.subscription_type.lock();
+        if *current == SubscriptionType::None {
+            *current = subscription_type;
+        } else if *current != subscription_type {
+            return mq_client_err!("Subscribe and assign are mutually exclusive.");
+        }
+        Ok(())
+    }
+
+    pub async fn subscribe(
+        &mut self,
+        topic: CheetahString,
+        sub_expression: CheetahString,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        let subscription_data = match FilterAPI::build_subscription_data(&topic, &sub_expression) {
+            Ok(subscription_data) => subscription_data,
+            Err(e) => return mq_client_err!(format!("buildSubscriptionData exception, {}", e)),
+        };
+        self.subscribe_inner(topic, subscription_data).await
+    }
+
+    pub async fn subscribe_with_selector(
+        &mut self,
+        topic: CheetahString,
+        selector: Option<MessageSelector>,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        let Some(selector) = selector else {
+            return self
+                .subscribe(
+                    topic,
+                    CheetahString::from_static_str(SubscriptionData::SUB_ALL),
+                )
+                .await;
+        };
+        let subscription_data = match FilterAPI::build_subscription_data_with_expression_type(
+            &topic,
+            &CheetahString::from_slice(selector.get_expression()),
+            Some(CheetahString::from_slice(selector.get_expression_type())),
+        ) {
+            Ok(subscription_data) => subscription_data,
+            Err(e) => return mq_client_err!(format!("buildSubscriptionData exception, {}", e)),
+        };
+        self.subscribe_inner(topic, subscription_data).await
+    }
+
+    async fn subscribe_inner(
+        &mut self,
+        topic: CheetahString,
+        subscription_data: SubscriptionData,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        if topic.is_empty() {
+            return mq_client_err!("Topic can not be null or empty.");
+        }
+        self.set_subscription_type(SubscriptionType::Subscribe)?;
+        self.rebalance_impl
+            .rebalance_impl_inner
+            .put_subscription_data(&topic, subscription_data)
+            .await;
+        if self.is_running() {
+            self.client_instance
+                .as_mut()
+                .unwrap()
+                .send_heartbeat_to_all_broker_with_lock()
+                .await;
+            self.update_topic_subscribe_info_when_subscription_changed()
+                .await;
+        }
+        Ok(())
+    }
+
+    pub async fn unsubscribe(&mut self, topic: CheetahString) {
+        self.rebalance_impl
+            .rebalance_impl_inner
+            .remove_subscription_data(&topic)
+            .await;
+        self.task_table.lock().retain(|mq, cancelled| {
+            if mq.get_topic() == topic.as_str() {
+                cancelled.store(true, Ordering::Release);
+                return false;
+            }
+            true
+        });
+        self.consume_request_cache
+            .lock()
+            .retain(|request| request.message_queue.get_topic() != topic.as_str());
+        self.assigned_message_queue
+            .remove_assigned_message_queue(topic.as_str());
+    }
+
+    pub fn assignment(&self) -> rocketmq_error::RocketMQResult<HashSet<MessageQueue>> {
+        self.make_sure_state_ok()?;
+        Ok(self.assigned_message_queue.message_queues())
+    }
+
+    pub async fn assign(&mut self, message_queues: Vec<MessageQueue>) {
+        if message_queues.is_empty() {
+            error!("Message queues can not be null or empty.");
+            return;
+        }
+        if let Err(e) = self.set_subscription_type(SubscriptionType::Assign) {
+            error!("assign message queues failed: {}", e);
+            return;
+        }
+        let message_queues = message_queues.into_iter().collect::<HashSet<_>>();
+        self.assigned_message_queue
+            .update_assigned_message_queue(&message_queues);
+        if self.is_running() {
+            self.update_assign_pull_task(&message_queues);
+        }
+    }
+
+    pub fn set_sub_expression_for_assign(
+        &self,
+        topic: CheetahString,
+        sub_expression: CheetahString,
+    ) {
+        self.topic_to_sub_expression
+            .write()
+            .insert(topic, sub_expression);
+    }
+
+    pub fn pause(&self, message_queues: &[MessageQueue]) {
+        self.assigned_message_queue.pause(message_queues);
+    }
+
+    pub fn resume(&self, message_queues: &[MessageQueue]) {
+        self.assigned_message_queue.resume(message_queues);
+    }
+
+    /// Invoked by rebalance in subscribe mode once the allocation of `topic` has changed.
+    pub(crate) async fn update_assign_queue_and_start_pull_task(
+        &mut self,
+        topic: &str,
+        mq_all: &HashSet<MessageQueue>,
+        mq_divided: &HashSet<MessageQueue>,
+    ) {
+        let message_queues = match self.consumer_config.message_model {
+            MessageModel::Broadcasting => mq_all,
+            MessageModel::Clustering => mq_divided,
+        };
+        let process_queue_table = self
+            .rebalance_impl
+            .rebalance_impl_inner
+            .process_queue_table
+            .read()
+            .await
+            .clone();
+        self.assigned_message_queue
+            .update_assigned_message_queue_with_topic(topic, message_queues, &process_queue_table);
+        self.task_table.lock().retain(|mq, cancelled| {
+            if mq.get_topic() == topic && !message_queues.contains(mq) {
+                cancelled.store(true, Ordering::Release);
+                return false;
+            }
+            true
+        });
+        self.start_pull_task(message_queues);
+    }
+
+    fn update_assign_pull_task(&self, message_queues: &HashSet<MessageQueue>) {
+        self.task_table.lock().retain(|mq, cancelled| {
+            if !message_queues.contains(mq) {
+                cancelled.store(true, Ordering::Release);
+                return false;
+            }
+            true
+        });
+        self.start_pull_task(message_queues);
+    }
+
+    fn start_pull_task(&self, message_queues: &HashSet<MessageQueue>) {
+        let mut task_table = self.task_table.lock();
+        for mq in message_queues {
+            if task_table.contains_key(mq) {
+                continue;
+            }
+            let cancelled = Arc::new(AtomicBool::new(false));
+            task_table.insert(mq.clone(), cancelled.clone());
+            let this = self.default_lite_pull_consumer_impl.clone().unwrap();
+            let mq = mq.clone();
+            tokio::spawn(async move {
+                Self::pull_task_loop(this, mq, cancelled).await;
+            });
+        }
+    }
+
+    async fn pull_task_loop(
+        mut this: ArcMut<Self>,
+        message_queue: MessageQueue,
+        cancelled: Arc<AtomicBool>,
+    ) {
+        while !cancelled.load(Ordering::Acquire) && this.is_running() {
+            match this.pull_message_once(&message_queue, &cancelled).await {
+                Some(0) => {}
+                Some(delay) => tokio::time::sleep(Duration::from_millis(delay)).await,
+                None => break,
+            }
+        }
+        let mut task_table = this.task_table.lock();
+        if task_table
+            .get(&message_queue)
+            .is_some_and(|current| Arc::ptr_eq(current, &cancelled))
+        {
+            task_table.remove(&message_queue);
+        }
+    }
+
+    /// Runs one pull for `message_queue` and returns the delay before the next one, or `None`
+    /// once the queue is no longer assigned.
+    async fn pull_message_once(
+        &mut self,
+        message_queue: &MessageQueue,
+        cancelled: &AtomicBool,
+    ) -> Option<u64> {
+        let process_queue = self
+            .assigned_message_queue
+            .get_process_queue(message_queue)?;
+        if process_queue.is_dropped() {
+            info!(
+                "The message queue not be able to poll, because it's dropped. group={}, \
+                 messageQueue={}",
+                self.consumer_config.consumer_group, message_queue
+            );
+            return None;
+        }
+        process_queue.set_last_pull_timestamp(get_current_millis());
+        if self.assigned_message_queue.is_paused(message_queue) {
+            return Some(PULL_TIME_DELAY_MILLS_WHEN_PAUSE);
+        }
+        if self.is_flow_controlled(message_queue, &process_queue).await {
+            return Some(PULL_TIME_DELAY_MILLS_WHEN_FLOW_CONTROL);
+        }
+
+        let generation = self.assigned_message_queue.get_generation(message_queue)?;
+        let offset = match self.next_pull_offset(message_queue).await {
+            Ok(offset) => offset,
+            Err(e) => {
+                error!("Failed to get next pull offset of {}: {}", message_queue, e);
+                return Some(self.consumer_config.pull_time_delay_millis_when_exception);
+            }
+        };
+        let subscription_data = match self
+            .subscription_data_of(message_queue.get_topic_cs())
+            .await
+        {
+            Some(subscription_data) => subscription_data,
+            None => return Some(PULL_TIME_DELAY_MILLS_WHEN_PAUSE),
+        };
+        let pull_result = match self
+            .pull_sync_impl(
+                message_queue,
+                &subscription_data,
+                offset,
+                self.consumer_config.pull_batch_size,
+                true,
+            )
+            .await
+        {
+            Ok(pull_result) => pull_result,
+            Err(e) => {
+                warn!(
+                    "An error occurred in pull message process of {}: {}",
+                    message_queue, e
+                );
+                return Some(self.consumer_config.pull_time_delay_millis_when_exception);
+            }
+        };
+        if cancelled.load(Ordering::Acquire) {
+            return None;
+        }
+        if self.assigned_message_queue.get_generation(message_queue) != Some(generation) {
+            // a seek happened while pulling, the result is stale
+            return Some(0);
+        }
+        match pull_result.pull_result.pull_status {
+            PullStatus::Found => {
+                if let Some(messages) = pull_result.pull_result.msg_found_list {
+                    if !messages.is_empty() {
+                        process_queue.put_message(messages.clone()).await;
+                        let request = ConsumeRequest {
+                            messages,
+                            message_queue: message_queue.clone(),
+                            process_queue: process_queue.clone(),
+                        };
+                        if let Err(request) = self.submit_consume_request(request, generation) {
+                            process_queue.remove_message(&request.messages).await;
+                        }
+                    }
+                }
+            }
+            PullStatus::OffsetIllegal => {
+                warn!(
+                    "The pull request offset illegal, {}, next begin offset: {}",
+                    message_queue, pull_result.pull_result.next_begin_offset
+                );
+            }
+            PullStatus::NoNewMsg | PullStatus::NoMatchedMsg => {}
+        }
+        self.assigned_message_queue.update_pull_offset(
+            message_queue,
+            pull_result.pull_result.next_begin_offset as i64,
+            generation,
+        );
+        Some(0)
+    }
+
+    async fn is_flow_controlled(
+        &self,
+        message_queue: &MessageQueue,
+        process_queue: &ProcessQueue,
+    ) -> bool {
+        let cached_requests = self.consume_request_cache.lock().len() as u64;
+        if cached_requests * self.consumer_config.pull_batch_size as u64
+            > self.consumer_config.pull_threshold_for_all
+        {
+            if self
+                .consume_request_flow_control_times
+                .fetch_add(1, Ordering::AcqRel)
+                % 1000
+                == 0
+            {
+                warn!(
+                    "The consume request count exceeds threshold {}, so do flow control, consume \
+                     request count={}",
+                    self.consumer_config.pull_threshold_for_all, cached_requests
+                );
+            }
+            return true;
+        }
+        let cached_message_count = process_queue.msg_count();
+        let cached_message_size_in_mib = process_queue.msg_size() / _1MB;
+        if cached_message_count > self.consumer_config.pull_threshold_for_queue
+            || cached_message_size_in_mib > self.consumer_config.pull_threshold_size_for_queue
+        {
+            if self.queue_flow_control_times.fetch_add(1, Ordering::AcqRel) % 1000 == 0 {
+                warn!(
+                    "The cached message count or size exceeds the threshold, so do flow control, \
+                     count={}, size={} MiB, mq={}",
+                    cached_message_count, cached_message_size_in_mib, message_queue
+                );
+            }
+            return true;
+        }
+        let max_span = process_queue.get_max_span().await;
+        if max_span > self.consumer_config.consume_max_span {
+            if self
+                .queue_max_span_flow_control_times
+                .fetch_add(1, Ordering::AcqRel)
+                % 1000
+                == 0
+            {
+                warn!(
+                    "The queue's messages span too long, so do flow control, maxSpan={}, mq={}",
+                    max_span, message_queue
+                );
+            }
+            return true;
+        }
+        false
+    }
+
+    async fn next_pull_offset(
+        &mut self,
+        message_queue: &MessageQueue,
+    ) -> rocketmq_error::RocketMQResult<i64> {
+        if let Some(offset) = self.assigned_message_queue.take_seek_offset(message_queue) {
+            return Ok(offset);
+        }
+        let offset = self.assigned_message_queue.get_pull_offset(message_queue);
+        if offset != -1 {
+            return Ok(offset);
+        }
+        self.rebalance_impl
+            .compute_pull_from_where_with_exception(message_queue)
+            .await
+    }
+
+    async fn subscription_data_of(&self, topic: &CheetahString) -> Option<SubscriptionData> {
+        let subscription_type = *self.subscription_type.lock();
+        if subscription_type == SubscriptionType::Subscribe {
+            return self
+                .rebalance_impl
+                .get_subscription_inner()
+                .read()
+                .await
+                .get(topic)
+                .cloned();
+        }
+        self.build_assign_subscription_data(topic)
+    }
+
+    fn build_assign_subscription_data(&self, topic: &CheetahString) -> Option<SubscriptionData> {
+        let sub_expression = self
+            .topic_to_sub_expression
+            .read()
+            .get(topic)
+            .cloned()
+            .unwrap_or_else(|| CheetahString::from_static_str(SubscriptionData::SUB_ALL));
+        match FilterAPI::build_subscription_data(topic, &sub_expression) {
+            Ok(subscription_data) => Some(subscription_data),
+            Err(e) => {
+                error!(
+                    "build subscription data of topic {} with {} failed: {}",
+                    topic, sub_expression, e
+                );
+                None
+            }
+        }
+    }
+
+    async fn pull_sync_impl(
+        &mut self,
+        message_queue: &MessageQueue,
+        subscription_data: &SubscriptionData,
+        offset: i64,
+        max_nums: u32,
+        block: bool,
+    ) -> rocketmq_error::RocketMQResult<PullResultExt> {
+        let sys_flag = PullSysFlag::build_sys_flag_with_lite_pull(false, block, true, false, true);
+        let timeout_millis = if block {
+            self.consumer_config.consumer_timeout_millis_when_suspend
+        } else {
+            self.consumer_config.consumer_pull_timeout_millis
+        };
+        let pull_api_wrapper = self.pull_api_wrapper.as_mut().unwrap();
+        let pull_result = pull_api_wrapper
+            .pull_kernel_impl(
+                message_queue,
+                subscription_data.sub_string.clone(),
+                subscription_data.expression_type.clone(),
+                subscription_data.sub_version,
+                offset,
+                max_nums as i32,
+                i32::MAX,
+                sys_flag as i32,
+                0,
+                self.consumer_config.broker_suspend_max_time_millis,
+                timeout_millis,
+                CommunicationMode::Sync,
+                SyncPullCallback,
+            )
+            .await?;
+        let Some(mut pull_result) = pull_result else {
+            return mq_client_err!(format!(
+                "pull message from {} returned nothing",
+                message_queue
+            ));
+        };
+        pull_api_wrapper.process_pull_result(message_queue, &mut pull_result, subscription_data);
+        Ok(pull_result)
+    }
+
+    /// Queues `request` for `poll`, unless a seek invalidated it; the request is handed back in
+    /// that case so its messages can be released.
+    fn submit_consume_request(
+        &self,
+        request: ConsumeRequest,
+        generation: u64,
+    ) -> Result<(), ConsumeRequest> {
+        let mut cache = self.consume_request_cache.lock();
+        if self
+            .assigned_message_queue
+            .get_generation(&request.message_queue)
+            != Some(generation)
+        {
+            return Err(request);
+        }
+        cache.push_back(request);
+        drop(cache);
+        self.consume_request_notify.notify_one();
+        Ok(())
+    }
+
+    pub async fn poll(&mut self, timeout: u64) -> Vec<MessageExt> {
+        if let Err(e) = self.make_sure_state_ok() {
+            error!("poll failed: {}", e);
+            return vec![];
+        }
+        if self.consumer_config.auto_commit {
+            self.maybe_auto_commit().await;
+        }
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(timeout);
+        loop {
+            let request = self.consume_request_cache.lock().pop_front();
+            let Some(request) = request else {
+                if tokio::time::timeout_at(deadline, self.consume_request_notify.notified())
+                    .await
+                    .is_err()
+                {
+                    return vec![];
+                }
+                continue;
+            };
+            if request.process_queue.is_dropped() {
+                continue;
+            }
+            let offset = request
+                .process_queue
+                .remove_message(&request.messages)
+                .await;
+            if offset >= 0 {
+                self.assigned_message_queue
+                    .update_consume_offset(&request.message_queue, offset);
+            }
+            let namespace = self.client_config.get_namespace().unwrap_or_default();
+            return request
+                .messages
+                .into_iter()
+                .map(|message| {
+                    let mut message = message.as_ref().clone();
+                    if !namespace.is_empty() {
+                        let topic = NamespaceUtil::without_namespace_with_namespace(
+                            message.get_topic(),
+                            namespace.as_str(),
+                        );
+                        message.set_topic(CheetahString::from_string(topic));
+                    }
+                    message
+                })
+                .collect();
+        }
+    }
+
+    async fn maybe_auto_commit(&mut self) {
+        let now = get_current_millis();
+        if now >= self.next_auto_commit_deadline.load(Ordering::Acquire) {
+            self.next_auto_commit_deadline.store(
+                now + self.consumer_config.auto_commit_interval_millis,
+                Ordering::Release,
+            );
+            self.commit_all().await;
+        }
+    }
+
+    pub async fn seek(
+        &mut self,
+        message_queue: &MessageQueue,
+        offset: i64,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        self.make_sure_state_ok()?;
+        if !self.assigned_message_queue.contains(message_queue) {
+            if *self.subscription_type.lock() == SubscriptionType::Subscribe {
+                return mq_client_err!(format!(
+                    "The message queue is not in assigned list, may be rebalancing, message \
+                     queue: {}",
+                    message_queue
+                ));
+            }
+            return mq_client_err!(format!(
+                "The message queue is not in assigned list, message queue: {}",
+                message_queue
+            ));
+        }
+        let min_offset = self.min_offset(message_queue).await?;
+        let max_offset = self.max_offset(message_queue).await?;
+        if offset < min_offset || offset > max_offset {
+            return mq_client_err!(format!(
+                "Seek offset illegal, seek offset = {}, min offset = {}, max offset = {}",
+                offset, min_offset, max_offset
+            ));
+        }
+        self.assigned_message_queue
+            .set_seek_offset(message_queue, offset);
+        self.consume_request_cache
+            .lock()
+            .retain(|request| request.message_queue != *message_queue);
+        if let Some(process_queue) = self.assigned_message_queue.get_process_queue(message_queue) {
+            process_queue.clear().await;
+        }
+        Ok(())
+    }
+
+    pub async fn seek_to_begin(
+        &mut self,
+        message_queue: &MessageQueue,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        let begin = self.min_offset(message_queue).await?;
+        self.seek(message_queue, begin).await
+    }
+
+    pub async fn seek_to_end(
+        &mut self,
+        message_queue: &MessageQueue,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        let end = self.max_offset(message_queue).await?;
+        self.seek(message_queue, end).await
+    }
+
+    async fn min_offset(
+        &mut self,
+        message_queue: &MessageQueue,
+    ) -> rocketmq_error::RocketMQResult<i64> {
+        self.make_sure_state_ok()?;
+        self.client_instance
+            .as_mut()
+            .unwrap()
+            .mq_admin_impl
+            .min_offset(message_queue)
+            .await
+    }
+
+    async fn max_offset(
+        &mut self,
+        message_queue: &MessageQueue,
+    ) -> rocketmq_error::RocketMQResult<i64> {
+        self.make_sure_state_ok()?;
+        self.client_instance
+            .as_mut()
+            .unwrap()
+            .mq_admin_impl
+            .max_offset(message_queue)
+            .await
+    }
+
+    pub async fn search_offset(
+        &mut self,
+        message_queue: &MessageQueue,
+        timestamp: u64,
+    ) -> rocketmq_error::RocketMQResult<i64> {
+        self.make_sure_state_ok()?;
+        self.client_instance
+            .as_mut()
+            .unwrap()
+            .mq_admin_impl
+            .search_offset(message_queue, timestamp)
+            .await
+    }
+
+    pub async fn fetch_message_queues(
+        &mut self,
+        topic: &str,
+    ) -> rocketmq_error::RocketMQResult<Vec<MessageQueue>> {
+        self.make_sure_state_ok()?;
+        self.client_instance
+            .as_mut()
+            .unwrap()
+            .mq_admin_impl
+            .fetch_subscribe_message_queues(topic)
+            .await
+    }
+
+    pub async fn commit_all(&mut self) {
+        let message_queues = self.assigned_message_queue.message_queues();
+        for message_queue in &message_queues {
+            let consume_offset = self
+                .assigned_message_queue
+                .get_consume_offset(message_queue);
+            if consume_offset != -1 {
+                self.update_consume_offset(message_queue, consume_offset)
+                    .await;
+            }
+        }
+        if self.consumer_config.message_model == MessageModel::Broadcasting {
+            if let Some(offset_store) = self.offset_store.as_mut() {
+                offset_store.persist_all(&message_queues).await;
+            }
+        }
+    }
+
+    pub async fn commit_with_map(&mut self, offset_map: HashMap<MessageQueue, i64>, persist: bool) {
+        for (message_queue, offset) in &offset_map {
+            if *offset != -1 {
+                self.update_consume_offset(message_queue, *offset).await;
+            } else {
+                error!("consumer offset is -1, message queue: {}", message_queue);
+            }
+        }
+        if persist {
+            let message_queues = offset_map.into_keys().collect::<HashSet<_>>();
+            if let Some(offset_store) = self.offset_store.as_mut() {
+                offset_store.persist_all(&message_queues).await;
+            }
+        }
+    }
+
+    pub async fn commit_with_set(&mut self, message_queues: HashSet<MessageQueue>, persist: bool) {
+        for message_queue in &message_queues {
+            let consume_offset = self
+                .assigned_message_queue
+                .get_consume_offset(message_queue);
+            if consume_offset != -1 {
+                self.update_consume_offset(message_queue, consume_offset)
+                    .await;
+            }
+        }
+        if persist {
+            if let Some(offset_store) = self.offset_store.as_mut() {
+                offset_store.persist_all(&message_queues).await;
+            }
+        }
+    }
+
+    async fn update_consume_offset(&self, message_queue: &MessageQueue, offset: i64) {
+        let Some(process_queue) = self.assigned_message_queue.get_process_queue(message_queue)
+        else {
+            return;
+        };
+        if process_queue.is_dropped() {
+            return;
+        }
+        if let Some(offset_store) = self.offset_store.as_ref() {
+            offset_store
+                .update_offset(message_queue, offset, false)
+                .await;
+        }
+    }
+
+    pub async fn committed(
+        &self,
+        message_queue: &MessageQueue,
+    ) -> rocketmq_error::RocketMQResult<i64> {
+        self.make_sure_state_ok()?;
+        let offset = self
+            .offset_store
+            .as_ref()
+            .unwrap()
+            .read_offset(message_queue, ReadOffsetType::MemoryFirstThenStore)
+            .await;
+        if offset == -2 {
+            return mq_client_err!("Fetch consume offset from broker exception");
+        }
+        Ok(offset)
+    }
+
+    pub async fn register_topic_message_queue_change_listener(
+        &mut self,
+        topic: CheetahString,
+        listener: Arc<dyn TopicMessageQueueChangeListener>,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        if topic.is_empty() {
+            return mq_client_err!("Topic can not be null or empty.");
+        }
+        let previous = self
+            .topic_message_queue_change_listener_map
+            .write()
+            .insert(topic.clone(), listener);
+        if previous.is_some() {
+            warn!(
+                "Topic {} had been registered, new listener will overwrite the old one",
+                topic
+            );
+        }
+        if self.is_running() {
+            let message_queues = self.fetch_message_queues(&topic).await?;
+            self.message_queues_for_topic
+                .write()
+                .insert(topic, message_queues.into_iter().collect());
+        }
+        Ok(())
+    }
+
+    pub async fn update_name_server_address(&self, name_server_address: &str) {
+        if let Some(client_instance) = self.client_instance.as_ref() {
+            client_instance
+                .get_mq_client_api_impl()
+                .update_name_server_address_list(name_server_address)
+                .await;
+        }
+    }
+}
+
+impl MQConsumerInner for DefaultLitePullConsumerImpl {
+    fn group_name(&self) -> CheetahString {
+        self.consumer_config.consumer_group.clone()
+    }
+
+    fn message_model(&self) -> MessageModel {
+        self.consumer_config.message_model
+    }
+
+    fn consume_type(&self) -> ConsumeType {
+        ConsumeType::ConsumeActively
+    }
+
+    fn consume_from_where(&self) -> ConsumeFromWhere {
+        self.consumer_config.consume_from_where
+    }
+
+    fn subscriptions(&self) -> HashSet<SubscriptionData> {
+        if *self.subscription_type.lock() == SubscriptionType::Assign {
+            return self
+                .assigned_message_queue
+                .message_queues()
+                .iter()
+                .map(|mq| mq.get_topic_cs().clone())
+                .collect::<HashSet<_>>()
+                .iter()
+                .filter_map(|topic| self.build_assign_subscription_data(topic))
+                .collect();
+        }
+        let inner = self.rebalance_impl.get_subscription_inner();
+        let handle = Handle::current();
+        thread::spawn(move || {
+            handle.block_on(async move {
+                let inner = inner.read().await;
+                inner.values().cloned().collect()
+            })
+        })
+        .join()
+        .unwrap()
+    }
+
+    fn do_rebalance(&self) {
+        if *self.subscription_type.lock() == SubscriptionType::Subscribe {
+            let mut rebalance_impl = self.rebalance_impl.clone();
+            tokio::spawn(async move {
+                rebalance_impl.do_rebalance(false).await;
+            });
+        }
+    }
+
+    async fn try_rebalance(&self) -> rocketmq_error::RocketMQResult<bool> {
+        if *self.subscription_type.lock() == SubscriptionType::Subscribe {
+            return Ok(self.rebalance_impl.mut_from_ref().do_rebalance(false).await);
+        }
+        Ok(true)
+    }
+
+    async fn persist_consumer_offset(&self) {
+        if let Err(err) = self.make_sure_state_ok() {
+            error!(
+                "group: {} persistConsumerOffset exception:{}",
+                self.consumer_config.consumer_group, err
+            );
+            return;
+        }
+        let message_queues = self.assigned_message_queue.message_queues();
+        self.offset_store
+            .as_ref()
+            .unwrap()
+            .mut_from_ref()
+            .persist_all(&message_queues)
+            .await;
+    }
+
+    async fn update_topic_subscribe_info(
+        &self,
+        topic: CheetahString,
+        info: &HashSet<MessageQueue>,
+    ) {
+        let sub_table = self.rebalance_impl.get_subscription_inner();
+        let sub_table_inner = sub_table.read().await;
+        if sub_table_inner.contains_key(&topic) {
+            let mut guard = self
+                .rebalance_impl
+                .rebalance_impl_inner
+                .topic_subscribe_info_table
+                .write()
+                .await;
+            guard.insert(topic, info.clone());
+        }
+    }
+
+    async fn is_subscribe_topic_need_update(&self, topic: &str) -> bool {
+        let sub_table = self.rebalance_impl.get_subscription_inner();
+        let sub_table_inner = sub_table.read().await;
+        if sub_table_inner.contains_key(topic) {
+            drop(sub_table_inner);
+            let guard = self
+                .rebalance_impl
+                .rebalance_impl_inner
+                .topic_subscribe_info_table
+                .read()
+                .await;
+            return !guard.contains_key(topic);
+        }
+        false
+    }
+
+    fn is_unit_mode(&self) -> bool {
+        self.consumer_config.unit_mode
+    }
+
+    async fn consumer_running_info(&self) -> ConsumerRunningInfo {
+        ConsumerRunningInfo::default()
+    }
+}
@@ -15,12 +15,14 @@
  * limitations under the License.
  */
 
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::error::Error;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::thread;
+use std::time::Duration;
 use std::time::Instant;
 
 use cheetah_string::CheetahString;
@@ -42,6 +44,8 @@ use rocketmq_error::mq_client_err;
 use rocketmq_error::ClientErr;
 use rocketmq_remoting::protocol::body::consume_message_directly_result::ConsumeMessageDirectlyResult;
 use rocketmq_remoting::protocol::body::consumer_running_info::ConsumerRunningInfo;
+use rocketmq_remoting::protocol::body::pop_process_queue_info::PopProcessQueueInfo;
+use rocketmq_remoting::protocol::body::process_queue_info::ProcessQueueInfo;
 use rocketmq_remoting::protocol::filter::filter_api::FilterAPI;
 use rocketmq_remoting::protocol::header::ack_message_request_header::AckMessageRequestHeader;
 use rocketmq_remoting::protocol::header::change_invisible_time_request_header::ChangeInvisibleTimeRequestHeader;
@@ -95,6 +99,7 @@ use crate::hook::filter_message_hook::FilterMessageHook;
 use crate::implementation::communication_mode::CommunicationMode;
 use crate::implementation::mq_client_manager::MQClientManager;
 use crate::producer::mq_producer::MQProducer;
+use crate::stat::consumer_stats_manager::ConsumerStatsManager;
 
 const PULL_TIME_DELAY_MILLS_WHEN_CACHE_FLOW_CONTROL: u64 = 50;
 pub(crate) const PULL_TIME_DELAY_MILLS_WHEN_BROKER_FLOW_CONTROL: u64 = 20;
@@ -143,6 +148,7 @@ pub struct DefaultMQPushConsumerImpl {
     queue_max_span_flow_control_times: u64,
     pub(crate) pop_delay_level: Arc<[i32; 16]>,
     default_mqpush_consumer_impl: Option<ArcMut<DefaultMQPushConsumerImpl>>,
+    consumer_start_timestamp: u64,
 }
 
 impl DefaultMQPushConsumerImpl {
@@ -175,6 +181,7 @@ impl DefaultMQPushConsumerImpl {
                 10, 30, 60, 120, 180, 240, 300, 360, 420, 480, 540, 600, 1200, 1800, 3600, 7200,
             ]),
             default_mqpush_consumer_impl: None,
+            consumer_start_timestamp: get_current_millis(),
         };
         let wrapper = ArcMut::downgrade(&this.rebalance_impl);
         this.rebalance_impl.set_rebalance_impl(wrapper);
@@ -199,6 +206,48 @@ impl DefaultMQPushConsumerImpl {
     pub fn is_consume_orderly(&self) -> bool {
         self.consume_orderly
     }
+
+    /// Sets how many consume tasks may run at once; takes effect once the consumer started.
+    pub fn update_core_pool_size(&self, core_pool_size: usize) {
+        if let Some(consume_message_service) = self.consume_message_service.as_ref() {
+            consume_message_service.update_core_pool_size(core_pool_size);
+        }
+        if let Some(consume_message_pop_service) = self.consume_message_pop_service.as_ref() {
+            consume_message_pop_service.update_core_pool_size(core_pool_size);
+        }
+    }
+
+    pub fn inc_core_pool_size(&self) {
+        if let Some(consume_message_service) = self.consume_message_service.as_ref() {
+            consume_message_service.inc_core_pool_size();
+        }
+        if let Some(consume_message_pop_service) = self.consume_message_pop_service.as_ref() {
+            consume_message_pop_service.inc_core_pool_size();
+        }
+    }
+
+    pub fn dec_core_pool_size(&self) {
+        if let Some(consume_message_service) = self.consume_message_service.as_ref() {
+            consume_message_service.dec_core_pool_size();
+        }
+        if let Some(consume_message_pop_service) = self.consume_message_pop_service.as_ref() {
+            consume_message_pop_service.dec_core_pool_size();
+        }
+    }
+
+    pub fn get_core_pool_size(&self) -> usize {
+        self.consume_message_service
+            .as_ref()
+            .map(|consume_message_service| consume_message_service.get_core_pool_size())
+            .unwrap_or(self.consumer_config.consume_thread_min as usize)
+    }
+
+    #[inline]
+    pub(crate) fn consumer_stats_manager(&self) -> Option<&Arc<ConsumerStatsManager>> {
+        self.client_instance
+            .as_ref()
+            .map(|client_instance| client_instance.get_consumer_stats_manager())
+    }
 }
 
 impl DefaultMQPushConsumerImpl {
@@ -354,12 +403,11 @@ impl DefaultMQPushConsumerImpl {
                     .unwrap()
                     .register_consumer(
                         self.consumer_config.consumer_group.as_ref(),
-                        MQConsumerInnerImpl {
-                            default_mqpush_consumer_impl: self
-                                .default_mqpush_consumer_impl
+                        MQConsumerInnerImpl::new_push(
+                            self.default_mqpush_consumer_impl
                                 .clone()
                                 .expect("default_mqpush_consumer_impl is None"),
-                        },
+                        ),
                     )
                     .await;
                 let cloned = self.client_instance.as_mut().cloned().unwrap();
@@ -416,6 +464,12 @@ impl DefaultMQPushConsumerImpl {
                         .shutdown(await_terminate_millis)
                         .await;
                 }
+                if let Some(consume_message_pop_service) = self.consume_message_pop_service.as_mut()
+                {
+                    consume_message_pop_service
+                        .shutdown(await_terminate_millis)
+                        .await;
+                }
                 self.persist_consumer_offset().await;
                 let client = self.client_instance.as_mut().unwrap();
                 client
@@ -732,6 +786,75 @@ impl DefaultMQPushConsumerImpl {
             .execute_pop_pull_request_later(pop_request, time_delay);
     }
 
+    pub fn suspend(&self) {
+        self.pause.store(true, Ordering::Release);
+        info!(
+            "suspend this consumer, {}",
+            self.consumer_config.consumer_group
+        );
+    }
+
+    pub async fn resume(&self) {
+        self.pause.store(false, Ordering::Release);
+        self.rebalance_impl
+            .mut_from_ref()
+            .do_rebalance(self.consume_orderly)
+            .await;
+        info!(
+            "resume this consumer, {}",
+            self.consumer_config.consumer_group
+        );
+    }
+
+    pub async fn update_consume_offset(&self, mq: &MessageQueue, offset: i64) {
+        if let Some(offset_store) = self.offset_store.as_ref() {
+            offset_store.update_offset(mq, offset, false).await;
+        }
+    }
+
+    /// Drops the process queues of `topic` that are covered by `offset_table`, waits for their
+    /// in-flight messages to drain, then restarts them from the reset offsets.
+    pub(crate) async fn reset_offset(
+        &self,
+        topic: &CheetahString,
+        offset_table: &HashMap<MessageQueue, i64>,
+    ) {
+        self.suspend();
+        let process_queue_table = self
+            .rebalance_impl
+            .rebalance_impl_inner
+            .process_queue_table
+            .clone();
+        for (mq, pq) in process_queue_table.read().await.iter() {
+            if mq.get_topic() == topic.as_str() && offset_table.contains_key(mq) {
+                pq.set_dropped(true);
+                pq.clear().await;
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(10)).await;
+
+        let reset_queues = process_queue_table
+            .read()
+            .await
+            .iter()
+            .filter(|(mq, _)| mq.get_topic() == topic.as_str())
+            .filter_map(|(mq, pq)| {
+                offset_table
+                    .get(mq)
+                    .map(|offset| (mq.clone(), pq.clone(), *offset))
+            })
+            .collect::<Vec<_>>();
+        for (mq, pq, offset) in reset_queues {
+            self.update_consume_offset(&mq, offset).await;
+            self.rebalance_impl
+                .mut_from_ref()
+                .remove_unnecessary_message_queue(&mq, &pq)
+                .await;
+            process_queue_table.write().await.remove(&mq);
+        }
+        self.resume().await;
+    }
+
     pub(crate) async fn consume_message_directly(
         &self,
         msg: MessageExt,
@@ -1090,6 +1213,7 @@ impl DefaultMQPushConsumerImpl {
                     message_queue_inner: Some(message_queue_inner),
                     subscription_data: Some(subscription_data),
                     pull_request: Some(pull_request.clone()),
+                    begin_timestamp,
                 },
             )
             .await;
@@ -1660,7 +1784,118 @@ impl MQConsumerInner for DefaultMQPushConsumerImpl {
         self.consumer_config.unit_mode
     }
 
-    fn consumer_running_info(&self) -> ConsumerRunningInfo {
-        todo!()
+    async fn consumer_running_info(&self) -> ConsumerRunningInfo {
+        let mut info = ConsumerRunningInfo::default();
+        let properties = [
+            (
+                ConsumerRunningInfo::PROP_CONSUME_ORDERLY,
+                self.consume_orderly.to_string(),
+            ),
+            (
+                ConsumerRunningInfo::PROP_THREADPOOL_CORE_SIZE,
+                self.get_core_pool_size().to_string(),
+            ),
+            (
+                ConsumerRunningInfo::PROP_CONSUMER_START_TIMESTAMP,
+                self.consumer_start_timestamp.to_string(),
+            ),
+            (
+                "consumerGroup",
+                self.consumer_config.consumer_group.to_string(),
+            ),
+            (
+                "messageModel",
+                self.consumer_config.message_model.to_string(),
+            ),
+            (
+                "consumeThreadMax",
+                self.consumer_config.consume_thread_max.to_string(),
+            ),
+            (
+                "consumeMessageBatchMaxSize",
+                self.consumer_config
+                    .consume_message_batch_max_size
+                    .to_string(),
+            ),
+            (
+                "pullBatchSize",
+                self.consumer_config.pull_batch_size.to_string(),
+            ),
+            (
+                "pullInterval",
+                self.consumer_config.pull_interval.to_string(),
+            ),
+            (
+                "maxReconsumeTimes",
+                self.consumer_config.max_reconsume_times.to_string(),
+            ),
+        ];
+        info.properties = properties
+            .into_iter()
+            .map(|(key, value)| {
+                (
+                    CheetahString::from_static_str(key),
+                    CheetahString::from_string(value),
+                )
+            })
+            .collect();
+
+        let subscriptions = self
+            .rebalance_impl
+            .get_subscription_inner()
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let process_queue_table = self
+            .rebalance_impl
+            .rebalance_impl_inner
+            .process_queue_table
+            .read()
+            .await
+            .iter()
+            .map(|(mq, pq)| (mq.clone(), pq.clone()))
+            .collect::<Vec<_>>();
+        for (mq, pq) in process_queue_table {
+            let mut process_queue_info = ProcessQueueInfo::default();
+            if let Some(offset_store) = self.offset_store.as_ref() {
+                process_queue_info.commit_offset = offset_store
+                    .read_offset(&mq, ReadOffsetType::MemoryFirstThenStore)
+                    .await
+                    .max(0) as u64;
+            }
+            pq.fill_process_queue_info(&mut process_queue_info).await;
+            info.mq_table.insert(mq, process_queue_info);
+        }
+
+        for (mq, pq) in self
+            .rebalance_impl
+            .rebalance_impl_inner
+            .pop_process_queue_table
+            .read()
+            .await
+            .iter()
+        {
+            let mut pop_process_queue_info = PopProcessQueueInfo::default();
+            pq.fill_pop_process_queue_info(&mut pop_process_queue_info);
+            info.mq_pop_table.insert(mq.clone(), pop_process_queue_info);
+        }
+
+        if let Some(client_instance) = self.client_instance.as_ref() {
+            let consumer_stats_manager = client_instance.get_consumer_stats_manager();
+            for subscription in subscriptions.iter() {
+                info.status_table.insert(
+                    subscription.topic.clone(),
+                    consumer_stats_manager.consume_status(
+                        self.consumer_config.consumer_group.as_str(),
+                        subscription.topic.as_str(),
+                    ),
+                );
+            }
+        }
+        info.subscription_set = subscriptions;
+        info
     }
 }
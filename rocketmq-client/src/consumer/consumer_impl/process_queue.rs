@@ -260,13 +260,13 @@ impl ProcessQueue {
                     Ordering::AcqRel,
                 );
             }
-            self.msg_count.fetch_sub(removed_cnt, Ordering::AcqRel);
-            if self.msg_count.load(Ordering::Acquire) == 0 {
-                self.msg_size.store(0, Ordering::Release);
-            }
-            if !msg_tree_map.is_empty() {
-                result = *msg_tree_map.first_key_value().unwrap().0;
-            }
+        }
+        self.msg_count.fetch_sub(removed_cnt, Ordering::AcqRel);
+        if self.msg_count.load(Ordering::Acquire) == 0 {
+            self.msg_size.store(0, Ordering::Release);
+        }
+        if !msg_tree_map.is_empty() {
+            result = *msg_tree_map.first_key_value().unwrap().0;
         }
         result
     }
@@ -346,8 +346,38 @@ impl ProcessQueue {
         drop(lock);
     }
 
-    pub(crate) fn fill_process_queue_info(&self, info: ProcessQueueInfo) {
-        unimplemented!("fill_process_queue_info")
+    pub(crate) async fn fill_process_queue_info(&self, info: &mut ProcessQueueInfo) {
+        let lock = self.tree_map_lock.read().await;
+        {
+            let msg_tree_map = self.msg_tree_map.read().await;
+            if let (Some((first, _)), Some((last, _))) = (
+                msg_tree_map.first_key_value(),
+                msg_tree_map.last_key_value(),
+            ) {
+                info.cached_msg_min_offset = *first as u64;
+                info.cached_msg_max_offset = *last as u64;
+                info.cached_msg_count = msg_tree_map.len() as u32;
+            }
+        }
+        info.cached_msg_size_in_mib = (self.msg_size() / (1024 * 1024)) as u32;
+        {
+            let consuming_msg_orderly_tree_map = self.consuming_msg_orderly_tree_map.read().await;
+            if let (Some((first, _)), Some((last, _))) = (
+                consuming_msg_orderly_tree_map.first_key_value(),
+                consuming_msg_orderly_tree_map.last_key_value(),
+            ) {
+                info.transaction_msg_min_offset = *first as u64;
+                info.transaction_msg_max_offset = *last as u64;
+                info.transaction_msg_count = consuming_msg_orderly_tree_map.len() as u32;
+            }
+        }
+        drop(lock);
+        info.locked = self.is_locked();
+        info.try_unlock_times = self.try_unlock_times.load(Ordering::Acquire) as u64;
+        info.last_lock_timestamp = self.get_last_lock_timestamp();
+        info.droped = self.is_dropped();
+        info.last_pull_timestamp = self.last_pull_timestamp.load(Ordering::Acquire);
+        info.last_consume_timestamp = self.last_consume_timestamp.load(Ordering::Acquire);
     }
 
     pub(crate) fn set_last_pull_timestamp(&self, last_pull_timestamp: u64) {
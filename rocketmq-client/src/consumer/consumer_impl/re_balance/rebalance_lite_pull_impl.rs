@@ -0,0 +1,300 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use cheetah_string::CheetahString;
+use rocketmq_common::common::constant::consume_init_mode::ConsumeInitMode;
+use rocketmq_common::common::consumer::consume_from_where::ConsumeFromWhere;
+use rocketmq_common::common::message::message_queue::MessageQueue;
+use rocketmq_common::common::mix_all;
+use rocketmq_common::utils::util_all;
+use rocketmq_error::mq_client_err;
+use rocketmq_remoting::code::response_code::ResponseCode;
+use rocketmq_remoting::protocol::heartbeat::consume_type::ConsumeType;
+use rocketmq_remoting::protocol::heartbeat::message_model::MessageModel;
+use rocketmq_remoting::protocol::heartbeat::subscription_data::SubscriptionData;
+use rocketmq_rust::ArcMut;
+use rocketmq_rust::WeakArcMut;
+use tokio::sync::RwLock;
+use tracing::info;
+use tracing::warn;
+
+use crate::consumer::allocate_message_queue_strategy::AllocateMessageQueueStrategy;
+use crate::consumer::consumer_impl::default_lite_pull_consumer_impl::DefaultLitePullConsumerImpl;
+use crate::consumer::consumer_impl::pop_process_queue::PopProcessQueue;
+use crate::consumer::consumer_impl::pop_request::PopRequest;
+use crate::consumer::consumer_impl::process_queue::ProcessQueue;
+use crate::consumer::consumer_impl::pull_request::PullRequest;
+use crate::consumer::consumer_impl::re_balance::rebalance_impl::RebalanceImpl;
+use crate::consumer::consumer_impl::re_balance::Rebalance;
+use crate::consumer::default_lite_pull_consumer::LitePullConsumerConfig;
+use crate::consumer::store::read_offset_type::ReadOffsetType;
+use crate::factory::mq_client_instance::MQClientInstance;
+
+pub struct RebalanceLitePullImpl {
+    pub(crate) consumer_config: ArcMut<LitePullConsumerConfig>,
+    pub(crate) rebalance_impl_inner: RebalanceImpl<RebalanceLitePullImpl>,
+    pub(crate) default_lite_pull_consumer_impl: Option<ArcMut<DefaultLitePullConsumerImpl>>,
+}
+
+impl RebalanceLitePullImpl {
+    pub fn new(consumer_config: ArcMut<LitePullConsumerConfig>) -> Self {
+        RebalanceLitePullImpl {
+            consumer_config,
+            rebalance_impl_inner: RebalanceImpl::new(None, None, None, None),
+            default_lite_pull_consumer_impl: None,
+        }
+    }
+}
+
+impl RebalanceLitePullImpl {
+    pub fn get_subscription_inner(&self) -> Arc<RwLock<HashMap<CheetahString, SubscriptionData>>> {
+        self.rebalance_impl_inner.subscription_inner.clone()
+    }
+
+    pub fn set_default_lite_pull_consumer_impl(
+        &mut self,
+        default_lite_pull_consumer_impl: ArcMut<DefaultLitePullConsumerImpl>,
+    ) {
+        self.default_lite_pull_consumer_impl = Some(default_lite_pull_consumer_impl);
+    }
+
+    pub fn set_consumer_group(&mut self, consumer_group: CheetahString) {
+        self.rebalance_impl_inner.consumer_group = Some(consumer_group);
+    }
+
+    pub fn set_message_model(&mut self, message_model: MessageModel) {
+        self.rebalance_impl_inner.message_model = Some(message_model);
+    }
+
+    pub fn set_allocate_message_queue_strategy(
+        &mut self,
+        allocate_message_queue_strategy: Arc<dyn AllocateMessageQueueStrategy>,
+    ) {
+        self.rebalance_impl_inner.allocate_message_queue_strategy =
+            Some(allocate_message_queue_strategy);
+    }
+
+    pub fn set_mq_client_factory(&mut self, client_instance: ArcMut<MQClientInstance>) {
+        self.rebalance_impl_inner.client_instance = Some(client_instance);
+    }
+
+    pub fn set_rebalance_impl(&mut self, rebalance_impl: WeakArcMut<RebalanceLitePullImpl>) {
+        self.rebalance_impl_inner.sub_rebalance_impl = Some(rebalance_impl);
+    }
+}
+
+impl Rebalance for RebalanceLitePullImpl {
+    async fn message_queue_changed(
+        &mut self,
+        topic: &str,
+        mq_all: &HashSet<MessageQueue>,
+        mq_divided: &HashSet<MessageQueue>,
+    ) {
+        if let Some(lite_pull_consumer_impl) = self.default_lite_pull_consumer_impl.as_ref() {
+            lite_pull_consumer_impl
+                .mut_from_ref()
+                .update_assign_queue_and_start_pull_task(topic, mq_all, mq_divided)
+                .await;
+        }
+        if let Some(ref message_queue_listener) = self.consumer_config.message_queue_listener {
+            message_queue_listener.message_queue_changed(topic, mq_all, mq_divided);
+        }
+    }
+
+    async fn remove_unnecessary_message_queue(
+        &mut self,
+        mq: &MessageQueue,
+        _pq: &ProcessQueue,
+    ) -> bool {
+        let mut lite_pull_consumer_impl = self
+            .default_lite_pull_consumer_impl
+            .as_ref()
+            .unwrap()
+            .clone();
+        let offset_store = lite_pull_consumer_impl.offset_store.as_mut().unwrap();
+        offset_store.persist(mq).await;
+        offset_store.remove_offset(mq).await;
+        true
+    }
+
+    fn consume_type(&self) -> ConsumeType {
+        ConsumeType::ConsumeActively
+    }
+
+    async fn remove_dirty_offset(&mut self, mq: &MessageQueue) {
+        let offset_store = self
+            .default_lite_pull_consumer_impl
+            .as_mut()
+            .unwrap()
+            .offset_store
+            .as_mut()
+            .unwrap();
+        offset_store.remove_offset(mq).await;
+    }
+
+    #[allow(deprecated)]
+    async fn compute_pull_from_where_with_exception(
+        &mut self,
+        mq: &MessageQueue,
+    ) -> rocketmq_error::RocketMQResult<i64> {
+        let consume_from_where = self.consumer_config.consume_from_where;
+        let mut lite_pull_consumer_impl = self
+            .default_lite_pull_consumer_impl
+            .as_ref()
+            .unwrap()
+            .clone();
+        let offset_store = lite_pull_consumer_impl.offset_store.as_mut().unwrap();
+        let last_offset = offset_store
+            .read_offset(mq, ReadOffsetType::MemoryFirstThenStore)
+            .await;
+        if last_offset >= 0 {
+            return Ok(last_offset);
+        }
+        if last_offset != -1 {
+            return mq_client_err!(
+                ResponseCode::QueryNotFound as i32,
+                "Failed to query consume offset from offset store"
+            );
+        }
+        let is_retry_topic = mq
+            .get_topic()
+            .starts_with(mix_all::RETRY_GROUP_TOPIC_PREFIX);
+        let mq_admin_impl = &mut self
+            .rebalance_impl_inner
+            .client_instance
+            .as_mut()
+            .unwrap()
+            .mq_admin_impl;
+        let result = match consume_from_where {
+            ConsumeFromWhere::ConsumeFromLastOffset
+            | ConsumeFromWhere::ConsumeFromLastOffsetAndFromMinWhenBootFirst
+            | ConsumeFromWhere::ConsumeFromMinOffset
+            | ConsumeFromWhere::ConsumeFromMaxOffset => {
+                if is_retry_topic {
+                    0
+                } else {
+                    mq_admin_impl.max_offset(mq).await?
+                }
+            }
+            ConsumeFromWhere::ConsumeFromFirstOffset => 0,
+            ConsumeFromWhere::ConsumeFromTimestamp => {
+                if is_retry_topic {
+                    mq_admin_impl.max_offset(mq).await?
+                } else {
+                    let timestamp = util_all::parse_date(
+                        self.consumer_config.consume_timestamp.as_ref().unwrap(),
+                        util_all::YYYYMMDDHHMMSS,
+                    )
+                    .unwrap()
+                    .and_utc()
+                    .timestamp_millis();
+                    mq_admin_impl.search_offset(mq, timestamp as u64).await?
+                }
+            }
+        };
+        if result < 0 {
+            return mq_client_err!(
+                ResponseCode::SystemError as i32,
+                "Failed to query consume offset from offset store"
+            );
+        }
+        Ok(result)
+    }
+
+    async fn compute_pull_from_where(&mut self, mq: &MessageQueue) -> i64 {
+        self.compute_pull_from_where_with_exception(mq)
+            .await
+            .unwrap_or_else(|e| {
+                warn!("Compute consume offset exception, mq={:?}", e);
+                -1
+            })
+    }
+
+    fn get_consume_init_mode(&self) -> i32 {
+        let consume_from_where = self.consumer_config.consume_from_where;
+        if consume_from_where == ConsumeFromWhere::ConsumeFromFirstOffset {
+            ConsumeInitMode::MIN
+        } else {
+            ConsumeInitMode::MAX
+        }
+    }
+
+    async fn dispatch_pull_request(&self, _pull_request_list: Vec<PullRequest>, _delay: u64) {
+        // pull tasks are started from message_queue_changed instead
+    }
+
+    async fn dispatch_pop_pull_request(&self, _pop_request_list: Vec<PopRequest>, _delay: u64) {
+        // lite pull consumers never pop
+    }
+
+    #[inline]
+    fn create_process_queue(&self) -> ProcessQueue {
+        ProcessQueue::new()
+    }
+
+    #[inline]
+    fn create_pop_process_queue(&self) -> PopProcessQueue {
+        PopProcessQueue::new()
+    }
+
+    async fn remove_process_queue(&mut self, mq: &MessageQueue) {
+        let mut process_queue_table = self.rebalance_impl_inner.process_queue_table.write().await;
+        let prev = process_queue_table.remove(mq);
+        drop(process_queue_table);
+        if let Some(pq) = prev {
+            let droped = pq.is_dropped();
+            pq.set_dropped(true);
+            self.remove_unnecessary_message_queue(mq, &pq).await;
+            info!(
+                "Fix Offset, {}, remove unnecessary mq, {} Droped: {}",
+                self.rebalance_impl_inner.consumer_group.as_ref().unwrap(),
+                mq,
+                droped
+            );
+        }
+    }
+
+    async fn unlock(&mut self, _mq: &MessageQueue, _oneway: bool) {
+        // lite pull consumers never lock message queues on the broker
+    }
+
+    fn lock_all(&self) {}
+
+    fn unlock_all(&self, _oneway: bool) {}
+
+    async fn do_rebalance(&mut self, is_order: bool) -> bool {
+        self.rebalance_impl_inner.do_rebalance(is_order).await
+    }
+
+    fn client_rebalance(&mut self, _topic: &str) -> bool {
+        true
+    }
+
+    fn destroy(&mut self) {
+        if let Ok(mut process_queue_table) =
+            self.rebalance_impl_inner.process_queue_table.try_write()
+        {
+            for pq in process_queue_table.values() {
+                pq.set_dropped(true);
+            }
+            process_queue_table.clear();
+        }
+    }
+}
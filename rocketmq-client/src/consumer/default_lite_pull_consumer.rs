@@ -0,0 +1,467 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use cheetah_string::CheetahString;
+use rocketmq_common::common::consumer::consume_from_where::ConsumeFromWhere;
+use rocketmq_common::common::message::message_ext::MessageExt;
+use rocketmq_common::common::message::message_queue::MessageQueue;
+use rocketmq_common::utils::util_all;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_remoting::protocol::heartbeat::message_model::MessageModel;
+use rocketmq_remoting::protocol::namespace_util::NamespaceUtil;
+use rocketmq_remoting::runtime::RPCHook;
+use rocketmq_rust::ArcMut;
+
+use crate::base::client_config::ClientConfig;
+use crate::consumer::allocate_message_queue_strategy::AllocateMessageQueueStrategy;
+use crate::consumer::consumer_impl::default_lite_pull_consumer_impl::DefaultLitePullConsumerImpl;
+use crate::consumer::default_lite_pull_consumer_builder::DefaultLitePullConsumerBuilder;
+use crate::consumer::lite_pull_consumer::LitePullConsumer;
+use crate::consumer::message_queue_listener::MessageQueueListener;
+use crate::consumer::message_selector::MessageSelector;
+use crate::consumer::rebalance_strategy::allocate_message_queue_averagely::AllocateMessageQueueAveragely;
+use crate::consumer::topic_message_queue_change_listener::TopicMessageQueueChangeListener;
+
+#[derive(Clone)]
+pub struct LitePullConsumerConfig {
+    pub(crate) consumer_group: CheetahString,
+    pub(crate) message_model: MessageModel,
+    pub(crate) consume_from_where: ConsumeFromWhere,
+    pub(crate) consume_timestamp: Option<CheetahString>,
+    pub(crate) allocate_message_queue_strategy: Option<Arc<dyn AllocateMessageQueueStrategy>>,
+    pub(crate) message_queue_listener: Option<Arc<Box<dyn MessageQueueListener>>>,
+    pub(crate) unit_mode: bool,
+    pub(crate) auto_commit: bool,
+    pub(crate) auto_commit_interval_millis: u64,
+    pub(crate) pull_batch_size: u32,
+    pub(crate) pull_threshold_for_all: u64,
+    pub(crate) consume_max_span: u64,
+    pub(crate) pull_threshold_for_queue: u64,
+    pub(crate) pull_threshold_size_for_queue: u64,
+    pub(crate) poll_timeout_millis: u64,
+    pub(crate) topic_metadata_check_interval_millis: u64,
+    pub(crate) broker_suspend_max_time_millis: u64,
+    pub(crate) consumer_timeout_millis_when_suspend: u64,
+    pub(crate) consumer_pull_timeout_millis: u64,
+    pub(crate) pull_time_delay_millis_when_exception: u64,
+    pub(crate) rpc_hook: Option<Arc<Box<dyn RPCHook>>>,
+}
+
+impl LitePullConsumerConfig {
+    pub fn consumer_group(&self) -> &CheetahString {
+        &self.consumer_group
+    }
+
+    pub fn message_model(&self) -> MessageModel {
+        self.message_model
+    }
+
+    pub fn consume_from_where(&self) -> ConsumeFromWhere {
+        self.consume_from_where
+    }
+
+    pub fn consume_timestamp(&self) -> &Option<CheetahString> {
+        &self.consume_timestamp
+    }
+
+    pub fn unit_mode(&self) -> bool {
+        self.unit_mode
+    }
+
+    pub fn auto_commit(&self) -> bool {
+        self.auto_commit
+    }
+
+    pub fn auto_commit_interval_millis(&self) -> u64 {
+        self.auto_commit_interval_millis
+    }
+
+    pub fn pull_batch_size(&self) -> u32 {
+        self.pull_batch_size
+    }
+
+    pub fn pull_threshold_for_all(&self) -> u64 {
+        self.pull_threshold_for_all
+    }
+
+    pub fn consume_max_span(&self) -> u64 {
+        self.consume_max_span
+    }
+
+    pub fn pull_threshold_for_queue(&self) -> u64 {
+        self.pull_threshold_for_queue
+    }
+
+    pub fn pull_threshold_size_for_queue(&self) -> u64 {
+        self.pull_threshold_size_for_queue
+    }
+
+    pub fn poll_timeout_millis(&self) -> u64 {
+        self.poll_timeout_millis
+    }
+
+    pub fn topic_metadata_check_interval_millis(&self) -> u64 {
+        self.topic_metadata_check_interval_millis
+    }
+
+    pub fn broker_suspend_max_time_millis(&self) -> u64 {
+        self.broker_suspend_max_time_millis
+    }
+
+    pub fn consumer_timeout_millis_when_suspend(&self) -> u64 {
+        self.consumer_timeout_millis_when_suspend
+    }
+
+    pub fn consumer_pull_timeout_millis(&self) -> u64 {
+        self.consumer_pull_timeout_millis
+    }
+
+    pub fn pull_time_delay_millis_when_exception(&self) -> u64 {
+        self.pull_time_delay_millis_when_exception
+    }
+}
+
+impl Default for LitePullConsumerConfig {
+    fn default() -> Self {
+        LitePullConsumerConfig {
+            consumer_group: CheetahString::new(),
+            message_model: MessageModel::Clustering,
+            consume_from_where: ConsumeFromWhere::ConsumeFromLastOffset,
+            consume_timestamp: Some(CheetahString::from_string(
+                util_all::time_millis_to_human_string3(
+                    (get_current_millis() - (1000 * 60 * 30)) as i64,
+                ),
+            )),
+            allocate_message_queue_strategy: Some(Arc::new(AllocateMessageQueueAveragely)),
+            message_queue_listener: None,
+            unit_mode: false,
+            auto_commit: true,
+            auto_commit_interval_millis: 5 * 1000,
+            pull_batch_size: 10,
+            pull_threshold_for_all: 10000,
+            consume_max_span: 2000,
+            pull_threshold_for_queue: 1000,
+            pull_threshold_size_for_queue: 100,
+            poll_timeout_millis: 1000 * 5,
+            topic_metadata_check_interval_millis: 30 * 1000,
+            broker_suspend_max_time_millis: 1000 * 20,
+            consumer_timeout_millis_when_suspend: 1000 * 30,
+            consumer_pull_timeout_millis: 1000 * 10,
+            pull_time_delay_millis_when_exception: 1000,
+            rpc_hook: None,
+        }
+    }
+}
+
+/// A pull consumer with Kafka-style `poll` semantics.
+///
+/// Messages are prefetched into a buffer per message queue by background pull tasks and handed
+/// out by [`LitePullConsumer::poll`]. Queues are either allocated by rebalance (`subscribe`) or
+/// chosen by the caller (`assign`); the two modes are mutually exclusive.
+pub struct DefaultLitePullConsumer {
+    client_config: ArcMut<ClientConfig>,
+    consumer_config: ArcMut<LitePullConsumerConfig>,
+    default_lite_pull_consumer_impl: ArcMut<DefaultLitePullConsumerImpl>,
+}
+
+impl DefaultLitePullConsumer {
+    pub fn builder() -> DefaultLitePullConsumerBuilder {
+        DefaultLitePullConsumerBuilder::default()
+    }
+
+    pub fn new(
+        client_config: ClientConfig,
+        consumer_config: LitePullConsumerConfig,
+    ) -> DefaultLitePullConsumer {
+        let client_config = ArcMut::new(client_config);
+        let consumer_config = ArcMut::new(consumer_config);
+        let mut default_lite_pull_consumer_impl = ArcMut::new(DefaultLitePullConsumerImpl::new(
+            client_config.clone(),
+            consumer_config.clone(),
+        ));
+        let wrapper = default_lite_pull_consumer_impl.clone();
+        default_lite_pull_consumer_impl.set_default_lite_pull_consumer_impl(wrapper);
+        DefaultLitePullConsumer {
+            client_config,
+            consumer_config,
+            default_lite_pull_consumer_impl,
+        }
+    }
+
+    #[inline]
+    pub fn set_consumer_group(&mut self, consumer_group: impl Into<CheetahString>) {
+        self.consumer_config.consumer_group = consumer_group.into();
+    }
+
+    pub fn set_name_server_addr(&mut self, name_server_addr: CheetahString) {
+        self.client_config.namesrv_addr = Some(name_server_addr);
+        self.client_config
+            .namespace_initialized
+            .store(false, std::sync::atomic::Ordering::Release);
+    }
+
+    pub fn set_consume_from_where(&mut self, consume_from_where: ConsumeFromWhere) {
+        self.consumer_config.consume_from_where = consume_from_where;
+    }
+
+    pub fn set_message_model(&mut self, message_model: MessageModel) {
+        self.consumer_config.message_model = message_model;
+    }
+
+    pub fn set_pull_batch_size(&mut self, pull_batch_size: u32) {
+        self.consumer_config.pull_batch_size = pull_batch_size;
+    }
+
+    pub fn set_poll_timeout_millis(&mut self, poll_timeout_millis: u64) {
+        self.consumer_config.poll_timeout_millis = poll_timeout_millis;
+    }
+
+    pub fn consumer_config(&self) -> &LitePullConsumerConfig {
+        self.consumer_config.as_ref()
+    }
+}
+
+impl LitePullConsumer for DefaultLitePullConsumer {
+    async fn start(&self) -> rocketmq_error::RocketMQResult<()> {
+        let consumer_group = NamespaceUtil::wrap_namespace(
+            self.client_config
+                .mut_from_ref()
+                .get_namespace()
+                .unwrap_or_default()
+                .as_str(),
+            self.consumer_config.consumer_group.as_str(),
+        );
+        self.consumer_config.mut_from_ref().consumer_group = consumer_group.into();
+        self.default_lite_pull_consumer_impl
+            .mut_from_ref()
+            .start()
+            .await
+    }
+
+    async fn shutdown(&self) {
+        self.default_lite_pull_consumer_impl
+            .mut_from_ref()
+            .shutdown()
+            .await
+    }
+
+    async fn is_running(&self) -> bool {
+        self.default_lite_pull_consumer_impl.is_running()
+    }
+
+    async fn subscribe(&self, topic: &str) -> rocketmq_error::RocketMQResult<()> {
+        self.subscribe_with_expression(topic, "*").await
+    }
+
+    async fn subscribe_with_expression(
+        &self,
+        topic: &str,
+        sub_expression: &str,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        self.default_lite_pull_consumer_impl
+            .mut_from_ref()
+            .subscribe(topic.into(), sub_expression.into())
+            .await
+    }
+
+    async fn subscribe_with_listener<MQL>(
+        &self,
+        topic: &str,
+        sub_expression: &str,
+        listener: MQL,
+    ) -> rocketmq_error::RocketMQResult<()>
+    where
+        MQL: MessageQueueListener + 'static,
+    {
+        self.consumer_config.mut_from_ref().message_queue_listener =
+            Some(Arc::new(Box::new(listener)));
+        self.subscribe_with_expression(topic, sub_expression).await
+    }
+
+    async fn subscribe_with_selector(
+        &self,
+        topic: &str,
+        selector: Option<MessageSelector>,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        self.default_lite_pull_consumer_impl
+            .mut_from_ref()
+            .subscribe_with_selector(topic.into(), selector)
+            .await
+    }
+
+    async fn unsubscribe(&self, topic: &str) {
+        self.default_lite_pull_consumer_impl
+            .mut_from_ref()
+            .unsubscribe(topic.into())
+            .await
+    }
+
+    async fn assignment(&self) -> rocketmq_error::RocketMQResult<HashSet<MessageQueue>> {
+        self.default_lite_pull_consumer_impl.assignment()
+    }
+
+    async fn assign(&self, message_queues: Vec<MessageQueue>) {
+        self.default_lite_pull_consumer_impl
+            .mut_from_ref()
+            .assign(message_queues)
+            .await
+    }
+
+    async fn set_sub_expression_for_assign(&self, topic: &str, sub_expression: &str) {
+        self.default_lite_pull_consumer_impl
+            .set_sub_expression_for_assign(topic.into(), sub_expression.into())
+    }
+
+    async fn poll(&self) -> Vec<MessageExt> {
+        self.poll_with_timeout(self.consumer_config.poll_timeout_millis)
+            .await
+    }
+
+    async fn poll_with_timeout(&self, timeout: u64) -> Vec<MessageExt> {
+        self.default_lite_pull_consumer_impl
+            .mut_from_ref()
+            .poll(timeout)
+            .await
+    }
+
+    async fn seek(
+        &self,
+        message_queue: &MessageQueue,
+        offset: i64,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        self.default_lite_pull_consumer_impl
+            .mut_from_ref()
+            .seek(message_queue, offset)
+            .await
+    }
+
+    async fn pause(&self, message_queues: Vec<MessageQueue>) {
+        self.default_lite_pull_consumer_impl.pause(&message_queues)
+    }
+
+    async fn resume(&self, message_queues: Vec<MessageQueue>) {
+        self.default_lite_pull_consumer_impl.resume(&message_queues)
+    }
+
+    async fn is_auto_commit(&self) -> bool {
+        self.consumer_config.auto_commit
+    }
+
+    async fn set_auto_commit(&self, auto_commit: bool) {
+        self.consumer_config.mut_from_ref().auto_commit = auto_commit;
+    }
+
+    async fn fetch_message_queues(
+        &self,
+        topic: &str,
+    ) -> rocketmq_error::RocketMQResult<Vec<MessageQueue>> {
+        self.default_lite_pull_consumer_impl
+            .mut_from_ref()
+            .fetch_message_queues(topic)
+            .await
+    }
+
+    async fn offset_for_timestamp(
+        &self,
+        message_queue: &MessageQueue,
+        timestamp: u64,
+    ) -> rocketmq_error::RocketMQResult<i64> {
+        self.default_lite_pull_consumer_impl
+            .mut_from_ref()
+            .search_offset(message_queue, timestamp)
+            .await
+    }
+
+    async fn commit_sync(&self) {
+        self.default_lite_pull_consumer_impl
+            .mut_from_ref()
+            .commit_all()
+            .await
+    }
+
+    async fn commit_sync_with_map(&self, offset_map: HashMap<MessageQueue, i64>, persist: bool) {
+        self.default_lite_pull_consumer_impl
+            .mut_from_ref()
+            .commit_with_map(offset_map, persist)
+            .await
+    }
+
+    async fn commit(&self) {
+        self.commit_sync().await
+    }
+
+    async fn commit_with_map(&self, offset_map: HashMap<MessageQueue, i64>, persist: bool) {
+        self.commit_sync_with_map(offset_map, persist).await
+    }
+
+    async fn commit_with_set(&self, message_queues: HashSet<MessageQueue>, persist: bool) {
+        self.default_lite_pull_consumer_impl
+            .mut_from_ref()
+            .commit_with_set(message_queues, persist)
+            .await
+    }
+
+    async fn committed(&self, message_queue: &MessageQueue) -> rocketmq_error::RocketMQResult<i64> {
+        self.default_lite_pull_consumer_impl
+            .committed(message_queue)
+            .await
+    }
+
+    async fn register_topic_message_queue_change_listener<TL>(
+        &self,
+        topic: &str,
+        listener: TL,
+    ) -> rocketmq_error::RocketMQResult<()>
+    where
+        TL: TopicMessageQueueChangeListener + 'static,
+    {
+        self.default_lite_pull_consumer_impl
+            .mut_from_ref()
+            .register_topic_message_queue_change_listener(topic.into(), Arc::new(listener))
+            .await
+    }
+
+    async fn update_name_server_address(&self, name_server_address: &str) {
+        self.client_config.mut_from_ref().namesrv_addr = Some(name_server_address.into());
+        self.default_lite_pull_consumer_impl
+            .update_name_server_address(name_server_address)
+            .await
+    }
+
+    async fn seek_to_begin(
+        &self,
+        message_queue: &MessageQueue,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        self.default_lite_pull_consumer_impl
+            .mut_from_ref()
+            .seek_to_begin(message_queue)
+            .await
+    }
+
+    async fn seek_to_end(
+        &self,
+        message_queue: &MessageQueue,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        self.default_lite_pull_consumer_impl
+            .mut_from_ref()
+            .seek_to_end(message_queue)
+            .await
+    }
+}
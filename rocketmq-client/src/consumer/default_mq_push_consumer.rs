@@ -23,6 +23,7 @@ use cheetah_string::CheetahString;
 use rocketmq_common::common::consumer::consume_from_where::ConsumeFromWhere;
 use rocketmq_common::common::message::message_ext::MessageExt;
 use rocketmq_common::common::message::message_queue::MessageQueue;
+use rocketmq_common::common::topic::TopicValidator;
 use rocketmq_common::utils::util_all;
 use rocketmq_common::TimeUtils::get_current_millis;
 use rocketmq_remoting::protocol::heartbeat::message_model::MessageModel;
@@ -30,6 +31,7 @@ use rocketmq_remoting::protocol::namespace_util::NamespaceUtil;
 use rocketmq_remoting::runtime::RPCHook;
 use rocketmq_rust::ArcMut;
 use tokio::runtime::Handle;
+use tracing::warn;
 
 use crate::base::client_config::ClientConfig;
 use crate::base::mq_admin::MQAdmin;
@@ -531,7 +533,10 @@ impl MQPushConsumer for DefaultMQPushConsumer {
             let mut dispatcher = AsyncTraceDispatcher::new(
                 self.consumer_config.consumer_group.as_str(),
                 Type::Consume,
-                self.client_config.trace_topic.clone().unwrap().as_str(),
+                self.client_config
+                    .trace_topic
+                    .as_deref()
+                    .unwrap_or(TopicValidator::RMQ_SYS_TRACE_TOPIC),
                 self.consumer_config.rpc_hook.clone(),
             );
             dispatcher
@@ -546,15 +551,31 @@ impl MQPushConsumer for DefaultMQPushConsumer {
             );
         }
 
-        if let Some(ref rpc_hook) = self.consumer_config.trace_dispatcher {
-            unimplemented!("trace hook");
+        if let Some(ref trace_dispatcher) = self.consumer_config.trace_dispatcher {
+            if let Err(e) = trace_dispatcher.start(
+                self.client_config
+                    .get_namesrv_addr()
+                    .unwrap_or_default()
+                    .as_str(),
+                self.client_config.access_channel,
+            ) {
+                warn!("trace dispatcher start failed: {}", e);
+            }
         }
 
         Ok(())
     }
 
     async fn shutdown(&mut self) {
-        todo!()
+        let await_terminate_millis = self.consumer_config.await_termination_millis_when_shutdown;
+        if let Some(default_mqpush_consumer_impl) = self.default_mqpush_consumer_impl.as_mut() {
+            default_mqpush_consumer_impl
+                .shutdown(await_terminate_millis)
+                .await;
+        }
+        if let Some(trace_dispatcher) = self.consumer_config.trace_dispatcher.as_ref() {
+            trace_dispatcher.shutdown();
+        }
     }
 
     fn register_message_listener_concurrently_fn<MLCFN>(&mut self, message_listener: MLCFN)
@@ -651,11 +672,15 @@ impl MQPushConsumer for DefaultMQPushConsumer {
     }
 
     async fn suspend(&mut self) {
-        todo!()
+        if let Some(default_mqpush_consumer_impl) = self.default_mqpush_consumer_impl.as_ref() {
+            default_mqpush_consumer_impl.suspend();
+        }
     }
 
     async fn resume(&mut self) {
-        todo!()
+        if let Some(default_mqpush_consumer_impl) = self.default_mqpush_consumer_impl.as_ref() {
+            default_mqpush_consumer_impl.resume().await;
+        }
     }
 }
 
@@ -698,4 +723,19 @@ impl DefaultMQPushConsumer {
     pub fn set_consume_from_where(&mut self, consume_from_where: ConsumeFromWhere) {
         self.consumer_config.consume_from_where = consume_from_where;
     }
+
+    /// Sets how many consume requests may run at once, within `[1, consume_thread_max]`.
+    /// Takes effect once the consumer started.
+    pub fn update_core_pool_size(&self, core_pool_size: usize) {
+        if let Some(default_mqpush_consumer_impl) = self.default_mqpush_consumer_impl.as_ref() {
+            default_mqpush_consumer_impl.update_core_pool_size(core_pool_size);
+        }
+    }
+
+    pub fn core_pool_size(&self) -> usize {
+        self.default_mqpush_consumer_impl
+            .as_ref()
+            .map(|default_mqpush_consumer_impl| default_mqpush_consumer_impl.get_core_pool_size())
+            .unwrap_or(self.consumer_config.consume_thread_min as usize)
+    }
 }
@@ -125,6 +125,20 @@ impl DefaultMQPushConsumerBuilder {
         self
     }
 
+    pub fn enable_trace(mut self, enable_trace: bool) -> Self {
+        if let Some(client_config) = self.client_config.as_mut() {
+            client_config.enable_trace = enable_trace;
+        }
+        self
+    }
+
+    pub fn trace_topic(mut self, trace_topic: impl Into<CheetahString>) -> Self {
+        if let Some(client_config) = self.client_config.as_mut() {
+            client_config.trace_topic = Some(trace_topic.into());
+        }
+        self
+    }
+
     // Methods to set each field
     pub fn consumer_group(mut self, consumer_group: impl Into<CheetahString>) -> Self {
         self.consumer_group = Some(consumer_group.into());
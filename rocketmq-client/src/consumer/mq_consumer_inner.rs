@@ -28,6 +28,7 @@ use rocketmq_remoting::protocol::heartbeat::message_model::MessageModel;
 use rocketmq_remoting::protocol::heartbeat::subscription_data::SubscriptionData;
 use rocketmq_rust::ArcMut;
 
+use crate::consumer::consumer_impl::default_lite_pull_consumer_impl::DefaultLitePullConsumerImpl;
 use crate::consumer::consumer_impl::default_mq_push_consumer_impl::DefaultMQPushConsumerImpl;
 use crate::consumer::consumer_impl::pop_request::PopRequest;
 use crate::consumer::consumer_impl::pull_request::PullRequest;
@@ -86,7 +87,7 @@ pub trait MQConsumerInnerLocal: MQConsumerInnerAny + Sync + 'static {
     fn is_unit_mode(&self) -> bool;
 
     /// Returns the running information of the consumer.
-    fn consumer_running_info(&self) -> ConsumerRunningInfo;
+    async fn consumer_running_info(&self) -> ConsumerRunningInfo;
 }
 
 pub trait MQConsumerInnerAny: std::any::Any {
@@ -105,22 +106,58 @@ impl<T: MQConsumerInner> MQConsumerInnerAny for T {
     }
 }
 
+/// Dispatches the inner consumer calls to either a push or a lite pull consumer; exactly one
+/// of the two is set.
 #[derive(Clone)]
 pub struct MQConsumerInnerImpl {
-    pub(crate) default_mqpush_consumer_impl: ArcMut<DefaultMQPushConsumerImpl>,
+    pub(crate) default_mqpush_consumer_impl: Option<ArcMut<DefaultMQPushConsumerImpl>>,
+    pub(crate) default_lite_pull_consumer_impl: Option<ArcMut<DefaultLitePullConsumerImpl>>,
+}
+
+macro_rules! dispatch_inner {
+    ($self:ident, $consumer:ident => $body:expr) => {
+        match (
+            $self.default_mqpush_consumer_impl.as_ref(),
+            $self.default_lite_pull_consumer_impl.as_ref(),
+        ) {
+            (Some($consumer), _) => $body,
+            (None, Some($consumer)) => $body,
+            (None, None) => unreachable!("MQConsumerInnerImpl without consumer"),
+        }
+    };
 }
 
 impl MQConsumerInnerImpl {
+    pub(crate) fn new_push(
+        default_mqpush_consumer_impl: ArcMut<DefaultMQPushConsumerImpl>,
+    ) -> Self {
+        Self {
+            default_mqpush_consumer_impl: Some(default_mqpush_consumer_impl),
+            default_lite_pull_consumer_impl: None,
+        }
+    }
+
+    pub(crate) fn new_lite_pull(
+        default_lite_pull_consumer_impl: ArcMut<DefaultLitePullConsumerImpl>,
+    ) -> Self {
+        Self {
+            default_mqpush_consumer_impl: None,
+            default_lite_pull_consumer_impl: Some(default_lite_pull_consumer_impl),
+        }
+    }
+
     pub(crate) async fn pop_message(&mut self, pop_request: PopRequest) {
-        self.default_mqpush_consumer_impl
-            .pop_message(pop_request)
-            .await;
+        if let Some(default_mqpush_consumer_impl) = self.default_mqpush_consumer_impl.as_mut() {
+            default_mqpush_consumer_impl.pop_message(pop_request).await;
+        }
     }
 
     pub(crate) async fn pull_message(&mut self, pull_request: PullRequest) {
-        self.default_mqpush_consumer_impl
-            .pull_message(pull_request)
-            .await;
+        if let Some(default_mqpush_consumer_impl) = self.default_mqpush_consumer_impl.as_mut() {
+            default_mqpush_consumer_impl
+                .pull_message(pull_request)
+                .await;
+        }
     }
 
     pub(crate) async fn consume_message_directly(
@@ -128,51 +165,56 @@ impl MQConsumerInnerImpl {
         msg: MessageExt,
         broker_name: Option<CheetahString>,
     ) -> Option<ConsumeMessageDirectlyResult> {
-        self.default_mqpush_consumer_impl
-            .consume_message_directly(msg, broker_name)
-            .await
+        match self.default_mqpush_consumer_impl.as_ref() {
+            Some(default_mqpush_consumer_impl) => {
+                default_mqpush_consumer_impl
+                    .consume_message_directly(msg, broker_name)
+                    .await
+            }
+            None => None,
+        }
     }
 }
 
 impl MQConsumerInner for MQConsumerInnerImpl {
     #[inline]
     fn group_name(&self) -> CheetahString {
-        MQConsumerInner::group_name(self.default_mqpush_consumer_impl.as_ref())
+        dispatch_inner!(self, consumer => MQConsumerInner::group_name(consumer.as_ref()))
     }
 
     #[inline]
     fn message_model(&self) -> MessageModel {
-        MQConsumerInner::message_model(self.default_mqpush_consumer_impl.as_ref())
+        dispatch_inner!(self, consumer => MQConsumerInner::message_model(consumer.as_ref()))
     }
 
     #[inline]
     fn consume_type(&self) -> ConsumeType {
-        MQConsumerInner::consume_type(self.default_mqpush_consumer_impl.as_ref())
+        dispatch_inner!(self, consumer => MQConsumerInner::consume_type(consumer.as_ref()))
     }
 
     #[inline]
     fn consume_from_where(&self) -> ConsumeFromWhere {
-        MQConsumerInner::consume_from_where(self.default_mqpush_consumer_impl.as_ref())
+        dispatch_inner!(self, consumer => MQConsumerInner::consume_from_where(consumer.as_ref()))
     }
 
     #[inline]
     fn subscriptions(&self) -> HashSet<SubscriptionData> {
-        MQConsumerInner::subscriptions(self.default_mqpush_consumer_impl.as_ref())
+        dispatch_inner!(self, consumer => MQConsumerInner::subscriptions(consumer.as_ref()))
     }
 
     #[inline]
     fn do_rebalance(&self) {
-        MQConsumerInner::do_rebalance(self.default_mqpush_consumer_impl.as_ref())
+        dispatch_inner!(self, consumer => MQConsumerInner::do_rebalance(consumer.as_ref()))
     }
 
     #[inline]
     async fn try_rebalance(&self) -> rocketmq_error::RocketMQResult<bool> {
-        MQConsumerInner::try_rebalance(self.default_mqpush_consumer_impl.as_ref()).await
+        dispatch_inner!(self, consumer => MQConsumerInner::try_rebalance(consumer.as_ref()).await)
     }
 
     #[inline]
     async fn persist_consumer_offset(&self) {
-        MQConsumerInner::persist_consumer_offset(self.default_mqpush_consumer_impl.as_ref()).await
+        dispatch_inner!(self, consumer => MQConsumerInner::persist_consumer_offset(consumer.as_ref()).await)
     }
 
     #[inline]
@@ -181,30 +223,25 @@ impl MQConsumerInner for MQConsumerInnerImpl {
         topic: CheetahString,
         info: &HashSet<MessageQueue>,
     ) {
-        MQConsumerInner::update_topic_subscribe_info(
-            self.default_mqpush_consumer_impl.mut_from_ref(),
-            topic,
-            info,
-        )
-        .await
+        dispatch_inner!(self, consumer => {
+            MQConsumerInner::update_topic_subscribe_info(consumer.mut_from_ref(), topic, info).await
+        })
     }
 
     #[inline]
     async fn is_subscribe_topic_need_update(&self, topic: &str) -> bool {
-        MQConsumerInner::is_subscribe_topic_need_update(
-            self.default_mqpush_consumer_impl.as_ref(),
-            topic,
-        )
-        .await
+        dispatch_inner!(self, consumer => {
+            MQConsumerInner::is_subscribe_topic_need_update(consumer.as_ref(), topic).await
+        })
     }
 
     #[inline]
     fn is_unit_mode(&self) -> bool {
-        MQConsumerInner::is_unit_mode(self.default_mqpush_consumer_impl.as_ref())
+        dispatch_inner!(self, consumer => MQConsumerInner::is_unit_mode(consumer.as_ref()))
     }
 
     #[inline]
-    fn consumer_running_info(&self) -> ConsumerRunningInfo {
-        MQConsumerInner::consumer_running_info(self.default_mqpush_consumer_impl.as_ref())
+    async fn consumer_running_info(&self) -> ConsumerRunningInfo {
+        dispatch_inner!(self, consumer => MQConsumerInner::consumer_running_info(consumer.as_ref()).await)
     }
 }
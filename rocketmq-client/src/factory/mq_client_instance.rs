@@ -31,10 +31,14 @@ use rocketmq_common::common::message::message_ext::MessageExt;
 use rocketmq_common::common::message::message_queue::MessageQueue;
 use rocketmq_common::common::message::message_queue_assignment::MessageQueueAssignment;
 use rocketmq_common::common::mix_all;
+use rocketmq_common::common::mq_version::RocketMqVersion;
+use rocketmq_common::common::stats::stats_item::SAMPLING_IN_MINUTES_INTERVAL_MILLIS;
+use rocketmq_common::common::stats::stats_item::SAMPLING_IN_SECONDS_INTERVAL_MILLIS;
 use rocketmq_common::TimeUtils::get_current_millis;
 use rocketmq_error::mq_client_err;
 use rocketmq_remoting::base::connection_net_event::ConnectionNetEvent;
 use rocketmq_remoting::protocol::body::consume_message_directly_result::ConsumeMessageDirectlyResult;
+use rocketmq_remoting::protocol::body::consumer_running_info::ConsumerRunningInfo;
 use rocketmq_remoting::protocol::heartbeat::consumer_data::ConsumerData;
 use rocketmq_remoting::protocol::heartbeat::heartbeat_data::HeartbeatData;
 use rocketmq_remoting::protocol::heartbeat::message_model::MessageModel;
@@ -66,6 +70,7 @@ use crate::producer::default_mq_producer::DefaultMQProducer;
 use crate::producer::default_mq_producer::ProducerConfig;
 use crate::producer::producer_impl::mq_producer_inner::MQProducerInnerImpl;
 use crate::producer::producer_impl::topic_publish_info::TopicPublishInfo;
+use crate::stat::consumer_stats_manager::ConsumerStatsManager;
 
 const LOCK_TIMEOUT_MILLIS: u64 = 3000;
 
@@ -117,6 +122,7 @@ pub struct MQClientInstance {
         >,
     >,
     send_heartbeat_times_total: Arc<AtomicI64>,
+    consumer_stats_manager: Arc<ConsumerStatsManager>,
 }
 
 impl MQClientInstance {
@@ -221,14 +227,17 @@ impl MQClientInstance {
             broker_addr_table,
             broker_version_table: Arc::new(Default::default()),
             send_heartbeat_times_total: Arc::new(AtomicI64::new(0)),
+            consumer_stats_manager: Arc::new(ConsumerStatsManager::new()),
         });
         let instance_clone = instance.clone();
         instance.mq_admin_impl.set_client(instance_clone);
         let weak_instance = ArcMut::downgrade(&instance);
         let (tx, mut rx) = tokio::sync::broadcast::channel::<ConnectionNetEvent>(16);
 
+        let mut tokio_client_config = TokioClientConfig::default();
+        tokio_client_config.use_tls |= client_config.use_tls;
         let mq_client_api_impl = ArcMut::new(MQClientAPIImpl::new(
-            Arc::new(TokioClientConfig::default()),
+            Arc::new(tokio_client_config),
             ClientRemotingProcessor::new(instance.clone()),
             rpc_hook,
             client_config.clone(),
@@ -441,6 +450,26 @@ impl MQClientInstance {
                 tokio::time::sleep(delay).await;
             }
         });
+
+        // Sample the consumer statistics
+        let consumer_stats_manager = self.consumer_stats_manager.clone();
+        self.instance_runtime.get_handle().spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_millis(SAMPLING_IN_SECONDS_INTERVAL_MILLIS));
+            loop {
+                interval.tick().await;
+                consumer_stats_manager.sampling_in_seconds();
+            }
+        });
+        let consumer_stats_manager = self.consumer_stats_manager.clone();
+        self.instance_runtime.get_handle().spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_millis(SAMPLING_IN_MINUTES_INTERVAL_MILLIS));
+            loop {
+                interval.tick().await;
+                consumer_stats_manager.sampling_in_minutes();
+            }
+        });
     }
 
     pub async fn update_topic_route_info_from_name_server(&mut self) {
@@ -1225,6 +1254,88 @@ impl MQClientInstance {
         }
     }
 
+    pub async fn reset_offset(
+        &self,
+        topic: &CheetahString,
+        group: &CheetahString,
+        offset_table: HashMap<MessageQueue, i64>,
+    ) {
+        let consumer = self
+            .consumer_table
+            .read()
+            .await
+            .get(group)
+            .and_then(|consumer| consumer.default_mqpush_consumer_impl.clone());
+        let Some(consumer) = consumer else {
+            info!("[reset-offset] consumer dose not exist. group={}", group);
+            return;
+        };
+        consumer.reset_offset(topic, &offset_table).await;
+    }
+
+    pub async fn consumer_running_info(
+        &self,
+        consumer_group: &CheetahString,
+    ) -> Option<ConsumerRunningInfo> {
+        let consumer = self
+            .consumer_table
+            .read()
+            .await
+            .get(consumer_group)
+            .cloned()?;
+        let mut consumer_running_info = consumer.consumer_running_info().await;
+        let namesrv_addr = self
+            .mq_client_api_impl
+            .as_ref()
+            .map(|api| {
+                api.get_name_server_address_list()
+                    .iter()
+                    .map(CheetahString::as_str)
+                    .collect::<Vec<_>>()
+                    .join(";")
+            })
+            .unwrap_or_default();
+        let properties = &mut consumer_running_info.properties;
+        properties.insert(
+            CheetahString::from_static_str(ConsumerRunningInfo::PROP_NAMESERVER_ADDR),
+            CheetahString::from_string(namesrv_addr),
+        );
+        properties.insert(
+            CheetahString::from_static_str(ConsumerRunningInfo::PROP_CONSUME_TYPE),
+            CheetahString::from_static_str(consumer.consume_type().name()),
+        );
+        properties.insert(
+            CheetahString::from_static_str(ConsumerRunningInfo::PROP_CLIENT_VERSION),
+            CheetahString::from_string(RocketMqVersion::CURRENT_VERSION.to_string()),
+        );
+        Some(consumer_running_info)
+    }
+
+    pub async fn get_consumer_status(
+        &self,
+        topic: &CheetahString,
+        group: &CheetahString,
+    ) -> HashMap<MessageQueue, i64> {
+        let consumer = self
+            .consumer_table
+            .read()
+            .await
+            .get(group)
+            .and_then(|consumer| consumer.default_mqpush_consumer_impl.clone());
+        match consumer
+            .as_ref()
+            .and_then(|consumer| consumer.offset_store.as_ref())
+        {
+            Some(offset_store) => offset_store.clone_offset_table(topic).await,
+            None => HashMap::new(),
+        }
+    }
+
+    #[inline]
+    pub fn get_consumer_stats_manager(&self) -> &Arc<ConsumerStatsManager> {
+        &self.consumer_stats_manager
+    }
+
     pub async fn consume_message_directly(
         &self,
         message: MessageExt,
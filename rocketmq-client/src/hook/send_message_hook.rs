@@ -19,7 +19,7 @@ use crate::hook::send_message_context::SendMessageContext;
 pub trait SendMessageHook: Send + Sync {
     fn hook_name(&self) -> &str;
 
-    fn send_message_before(&self, context: &Option<SendMessageContext<'_>>);
+    fn send_message_before(&self, context: &mut Option<SendMessageContext<'_>>);
 
     fn send_message_after(&self, context: &Option<SendMessageContext<'_>>);
 }
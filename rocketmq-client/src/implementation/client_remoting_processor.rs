@@ -14,7 +14,10 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::collections::HashMap;
+use std::fmt::Write;
 use std::net::SocketAddr;
+use std::thread;
 
 use cheetah_string::CheetahString;
 use rocketmq_common::common::compression::compressor_factory::CompressorFactory;
@@ -29,16 +32,23 @@ use rocketmq_common::TimeUtils::get_current_millis;
 use rocketmq_remoting::code::request_code::RequestCode;
 use rocketmq_remoting::code::response_code::ResponseCode;
 use rocketmq_remoting::net::channel::Channel;
+use rocketmq_remoting::protocol::body::get_consumer_status_body::GetConsumerStatusBody;
+use rocketmq_remoting::protocol::body::reset_offset_body::ResetOffsetBody;
 use rocketmq_remoting::protocol::header::check_transaction_state_request_header::CheckTransactionStateRequestHeader;
 use rocketmq_remoting::protocol::header::consume_message_directly_result_request_header::ConsumeMessageDirectlyResultRequestHeader;
+use rocketmq_remoting::protocol::header::get_consumer_running_info_request_header::GetConsumerRunningInfoRequestHeader;
+use rocketmq_remoting::protocol::header::get_consumer_status_request_header::GetConsumerStatusRequestHeader;
 use rocketmq_remoting::protocol::header::notify_consumer_ids_changed_request_header::NotifyConsumerIdsChangedRequestHeader;
 use rocketmq_remoting::protocol::header::reply_message_request_header::ReplyMessageRequestHeader;
+use rocketmq_remoting::protocol::header::reset_offset_request_header::ResetOffsetRequestHeader;
 use rocketmq_remoting::protocol::namespace_util::NamespaceUtil;
 use rocketmq_remoting::protocol::remoting_command::RemotingCommand;
+use rocketmq_remoting::protocol::RemotingDeserializable;
 use rocketmq_remoting::protocol::RemotingSerializable;
 use rocketmq_remoting::runtime::connection_handler_context::ConnectionHandlerContext;
 use rocketmq_remoting::runtime::processor::RequestProcessor;
 use rocketmq_rust::ArcMut;
+use tokio::runtime::Handle;
 use tracing::debug;
 use tracing::info;
 use tracing::warn;
@@ -71,13 +81,13 @@ impl RequestProcessor for ClientRemotingProcessor {
                 self.check_transaction_state(channel, ctx, request).await
             }
             RequestCode::ResetConsumerClientOffset => {
-                unimplemented!("ResetConsumerClientOffset")
+                self.reset_offset(channel, ctx, request).await
             }
             RequestCode::GetConsumerStatusFromClient => {
-                unimplemented!("GetConsumerStatusFromClient")
+                self.get_consumer_status(channel, ctx, request).await
             }
             RequestCode::GetConsumerRunningInfo => {
-                unimplemented!("GetConsumerRunningInfo")
+                self.get_consumer_running_info(channel, ctx, request).await
             }
             RequestCode::ConsumeMessageDirectly => {
                 self.consume_message_directly(channel, ctx, request).await
@@ -193,6 +203,94 @@ impl ClientRemotingProcessor {
         }
     }
 
+    async fn reset_offset(
+        &mut self,
+        channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        request: RemotingCommand,
+    ) -> rocketmq_error::RocketMQResult<Option<RemotingCommand>> {
+        let request_header = request.decode_command_custom_header::<ResetOffsetRequestHeader>()?;
+        info!(
+            "invoke reset offset operation from broker. brokerAddr={}, topic={}, group={}, \
+             timestamp={}",
+            channel.remote_address(),
+            request_header.topic,
+            request_header.group,
+            request_header.timestamp
+        );
+        let offset_table = match request.body() {
+            Some(body) => ResetOffsetBody::decode(body)?.offset_table,
+            None => HashMap::new(),
+        };
+        // Draining the process queues takes a while, so the reset must not hold up the
+        // connection.
+        let client_instance = self.client_instance.clone();
+        tokio::spawn(async move {
+            client_instance
+                .reset_offset(&request_header.topic, &request_header.group, offset_table)
+                .await;
+        });
+        Ok(None)
+    }
+
+    async fn get_consumer_status(
+        &mut self,
+        channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        request: RemotingCommand,
+    ) -> rocketmq_error::RocketMQResult<Option<RemotingCommand>> {
+        let request_header =
+            request.decode_command_custom_header::<GetConsumerStatusRequestHeader>()?;
+        info!(
+            "invoke get consumer status operation from broker. brokerAddr={}, topic={}, group={}",
+            channel.remote_address(),
+            request_header.topic,
+            request_header.group
+        );
+        let body = GetConsumerStatusBody {
+            message_queue_table: self
+                .client_instance
+                .get_consumer_status(&request_header.topic, &request_header.group)
+                .await,
+            ..Default::default()
+        };
+        Ok(Some(
+            RemotingCommand::create_response_command().set_body(body.encode()?),
+        ))
+    }
+
+    async fn get_consumer_running_info(
+        &mut self,
+        _channel: Channel,
+        _ctx: ConnectionHandlerContext,
+        request: RemotingCommand,
+    ) -> rocketmq_error::RocketMQResult<Option<RemotingCommand>> {
+        let request_header =
+            request.decode_command_custom_header::<GetConsumerRunningInfoRequestHeader>()?;
+        match self
+            .client_instance
+            .consumer_running_info(&request_header.consumer_group)
+            .await
+        {
+            Some(mut consumer_running_info) => {
+                if request_header.jstack_enable {
+                    consumer_running_info.jstack = Some(CheetahString::from_string(task_dump()));
+                }
+                Ok(Some(
+                    RemotingCommand::create_response_command()
+                        .set_body(consumer_running_info.encode()?),
+                ))
+            }
+            None => Ok(Some(
+                RemotingCommand::create_response_command_with_code(ResponseCode::SystemError)
+                    .set_remark(format!(
+                        "The Consumer Group <{}> not exist in this consumer",
+                        request_header.consumer_group
+                    )),
+            )),
+        }
+    }
+
     fn notify_consumer_ids_changed(
         &mut self,
         channel: Channel,
@@ -321,3 +419,28 @@ impl ClientRemotingProcessor {
         }
     }
 }
+
+/// Rust counterpart of the Java client's `jstack`: a snapshot of the async runtime serving the
+/// request, since tasks have no stack traces to dump.
+fn task_dump() -> String {
+    let mut dump = String::new();
+    let current = thread::current();
+    let _ = writeln!(
+        dump,
+        "\"{}\" {:?}",
+        current.name().unwrap_or("unnamed"),
+        current.id()
+    );
+    if let Ok(handle) = Handle::try_current() {
+        let metrics = handle.metrics();
+        let _ = writeln!(
+            dump,
+            "tokio runtime flavor={:?}, workers={}, alive tasks={}, global queue depth={}",
+            handle.runtime_flavor(),
+            metrics.num_workers(),
+            metrics.num_alive_tasks(),
+            metrics.global_queue_depth()
+        );
+    }
+    dump
+}
@@ -14,12 +14,22 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use cheetah_string::CheetahString;
+use rocketmq_common::common::message::message_client_id_setter::MessageClientIDSetter;
+use rocketmq_common::common::message::message_ext::MessageExt;
 use rocketmq_common::common::message::message_queue::MessageQueue;
+use rocketmq_common::common::message::MessageConst;
+use rocketmq_common::common::message::MessageTrait;
+use rocketmq_common::MessageDecoder;
 use rocketmq_error::mq_client_err;
+use rocketmq_remoting::code::response_code::ResponseCode;
+use rocketmq_remoting::protocol::header::query_message_request_header::QueryMessageRequestHeader;
 use rocketmq_remoting::protocol::namespace_util::NamespaceUtil;
 use rocketmq_rust::ArcMut;
+use tracing::warn;
 
 use crate::base::client_config::ClientConfig;
+use crate::base::query_result::QueryResult;
 use crate::factory::mq_client_instance;
 use crate::factory::mq_client_instance::MQClientInstance;
 use crate::implementation::mq_client_api_impl::MQClientAPIImpl;
@@ -92,7 +102,231 @@ impl MQAdminImpl {
         ))
     }
 
+    pub async fn fetch_subscribe_message_queues(
+        &mut self,
+        topic: &str,
+    ) -> rocketmq_error::RocketMQResult<Vec<MessageQueue>> {
+        let client = self.client.as_mut().expect("client is None");
+        let topic_route_data = client
+            .get_mq_client_api_impl()
+            .get_topic_route_info_from_name_server_detail(topic, self.timeout_millis, true)
+            .await?;
+        if let Some(topic_route_data) = topic_route_data {
+            let mq_set =
+                mq_client_instance::topic_route_data2topic_subscribe_info(topic, &topic_route_data);
+            if !mq_set.is_empty() {
+                let namespace = client.client_config.get_namespace().unwrap_or_default();
+                return Ok(mq_set
+                    .into_iter()
+                    .map(|mq| {
+                        MessageQueue::from_parts(
+                            NamespaceUtil::without_namespace_with_namespace(
+                                mq.get_topic(),
+                                namespace.as_str(),
+                            ),
+                            mq.get_broker_name(),
+                            mq.get_queue_id(),
+                        )
+                    })
+                    .collect());
+            }
+        }
+        mq_client_err!(format!(
+            "Namesrv return empty, Can not find Message Queue for this topic, {}",
+            topic
+        ))
+    }
+
     pub async fn max_offset(&mut self, mq: &MessageQueue) -> rocketmq_error::RocketMQResult<i64> {
+        let broker_addr = self.find_broker_addr(mq).await?;
+        self.client
+            .as_mut()
+            .expect("client is None")
+            .mq_client_api_impl
+            .as_mut()
+            .expect("mq_client_api_impl is None")
+            .get_max_offset(&broker_addr, mq, self.timeout_millis)
+            .await
+    }
+
+    pub async fn min_offset(&mut self, mq: &MessageQueue) -> rocketmq_error::RocketMQResult<i64> {
+        let broker_addr = self.find_broker_addr(mq).await?;
+        self.client
+            .as_mut()
+            .expect("client is None")
+            .mq_client_api_impl
+            .as_mut()
+            .expect("mq_client_api_impl is None")
+            .get_min_offset(&broker_addr, mq, self.timeout_millis)
+            .await
+    }
+
+    pub async fn search_offset(
+        &mut self,
+        mq: &MessageQueue,
+        timestamp: u64,
+    ) -> rocketmq_error::RocketMQResult<i64> {
+        let broker_addr = self.find_broker_addr(mq).await?;
+        self.client
+            .as_mut()
+            .expect("client is None")
+            .mq_client_api_impl
+            .as_mut()
+            .expect("mq_client_api_impl is None")
+            .search_offset(&broker_addr, mq, timestamp, self.timeout_millis)
+            .await
+    }
+
+    pub async fn query_message(
+        &mut self,
+        cluster_name: Option<&CheetahString>,
+        topic: &CheetahString,
+        key: &CheetahString,
+        max_num: i32,
+        begin: i64,
+        end: i64,
+        is_unique_key: bool,
+    ) -> rocketmq_error::RocketMQResult<QueryResult> {
+        let broker_addrs = self.query_broker_addrs(cluster_name, topic).await;
+        if broker_addrs.is_empty() {
+            return mq_client_err!(
+                ResponseCode::TopicNotExist as i32,
+                format!("The topic[{}] not matched route info", topic)
+            );
+        }
+        let mut mq_client_api_impl = self
+            .client
+            .as_ref()
+            .expect("client is None")
+            .get_mq_client_api_impl();
+        let mut index_last_update_timestamp = 0;
+        let mut message_list = Vec::new();
+        for addr in broker_addrs.iter() {
+            let request_header = QueryMessageRequestHeader {
+                topic: topic.clone(),
+                key: key.clone(),
+                max_num,
+                begin_timestamp: begin,
+                end_timestamp: end,
+                topic_request_header: None,
+            };
+            let query_result = match mq_client_api_impl
+                .query_message(addr, request_header, is_unique_key, self.timeout_millis)
+                .await
+            {
+                Ok(query_result) => query_result,
+                Err(e) => {
+                    warn!("queryMessage from broker {} exception, {}", addr, e);
+                    continue;
+                }
+            };
+            index_last_update_timestamp =
+                index_last_update_timestamp.max(query_result.index_last_update_timestamp());
+            for msg in query_result.message_list() {
+                let matched = if is_unique_key {
+                    MessageClientIDSetter::get_uniq_id(msg).as_ref() == Some(key)
+                } else {
+                    msg.get_keys().is_some_and(|keys| {
+                        keys.split(MessageConst::KEY_SEPARATOR)
+                            .any(|k| k == key.as_str())
+                    })
+                };
+                if matched {
+                    message_list.push(msg.clone());
+                } else {
+                    warn!(
+                        "queryMessage, find message key not matched, maybe hash duplicate {}",
+                        msg.msg_id()
+                    );
+                }
+            }
+        }
+        if message_list.is_empty() {
+            return mq_client_err!(
+                ResponseCode::NoMessage as i32,
+                "query message by key finished, but no message."
+            );
+        }
+        Ok(QueryResult::new(index_last_update_timestamp, message_list))
+    }
+
+    pub async fn query_message_by_uniq_key(
+        &mut self,
+        cluster_name: Option<&CheetahString>,
+        topic: &CheetahString,
+        uniq_key: &CheetahString,
+        begin: i64,
+        end: i64,
+    ) -> rocketmq_error::RocketMQResult<MessageExt> {
+        let query_result = self
+            .query_message(cluster_name, topic, uniq_key, 32, begin, end, true)
+            .await?;
+        // a uniq key can be stored more than once when the producer retries, take the earliest
+        let message = query_result
+            .message_list()
+            .iter()
+            .min_by_key(|msg| msg.store_timestamp())
+            .cloned();
+        match message {
+            Some(message) => Ok(message),
+            None => mq_client_err!(
+                ResponseCode::NoMessage as i32,
+                format!(
+                    "query message by uniq key {} finished, but no message.",
+                    uniq_key
+                )
+            ),
+        }
+    }
+
+    pub async fn view_message(
+        &mut self,
+        topic: &CheetahString,
+        msg_id: &CheetahString,
+    ) -> rocketmq_error::RocketMQResult<MessageExt> {
+        let message_id = MessageDecoder::decode_message_id(msg_id.as_str());
+        self.client
+            .as_ref()
+            .expect("client is None")
+            .get_mq_client_api_impl()
+            .view_message(
+                &CheetahString::from_string(message_id.address.to_string()),
+                topic,
+                message_id.offset,
+                self.timeout_millis,
+            )
+            .await
+    }
+
+    async fn query_broker_addrs(
+        &mut self,
+        cluster_name: Option<&CheetahString>,
+        topic: &CheetahString,
+    ) -> Vec<CheetahString> {
+        let client = self.client.as_mut().expect("client is None");
+        if !client.topic_route_table.read().await.contains_key(topic) {
+            client
+                .update_topic_route_info_from_name_server_topic(topic)
+                .await;
+        }
+        let topic_route_table = client.topic_route_table.read().await;
+        let Some(topic_route_data) = topic_route_table.get(topic) else {
+            return vec![];
+        };
+        topic_route_data
+            .broker_datas
+            .iter()
+            .filter(|broker_data| {
+                cluster_name.is_none_or(|cluster| broker_data.cluster() == cluster.as_str())
+            })
+            .filter_map(|broker_data| broker_data.select_broker_addr())
+            .collect()
+    }
+
+    async fn find_broker_addr(
+        &mut self,
+        mq: &MessageQueue,
+    ) -> rocketmq_error::RocketMQResult<CheetahString> {
         let client = self.client.as_mut().expect("client is None");
         let broker_name = client.get_broker_name_from_message_queue(mq).await;
         let mut broker_addr = client
@@ -107,23 +341,9 @@ impl MQAdminImpl {
                 .find_broker_address_in_publish(broker_name.as_ref())
                 .await;
         }
-        if let Some(ref broker_addr) = broker_addr {
-            let offset = client
-                .mq_client_api_impl
-                .as_mut()
-                .expect("mq_client_api_impl is None")
-                .get_max_offset(broker_addr, mq, self.timeout_millis)
-                .await?;
-            return Ok(offset);
+        match broker_addr {
+            Some(broker_addr) => Ok(broker_addr),
+            None => mq_client_err!(format!("The broker[{}] not exist", mq.get_broker_name())),
         }
-
-        unimplemented!("max_offset")
-    }
-    pub async fn search_offset(
-        &mut self,
-        mq: &MessageQueue,
-        timestamp: u64,
-    ) -> rocketmq_error::RocketMQResult<i64> {
-        unimplemented!("max_offset")
     }
 }
@@ -23,6 +23,9 @@ use std::time::Instant;
 
 use cheetah_string::CheetahString;
 use lazy_static::lazy_static;
+use rocketmq_common::common::attribute::attribute_parser::AttributeParser;
+use rocketmq_common::common::base::plain_access_config::PlainAccessConfig;
+use rocketmq_common::common::config::TopicConfig;
 use rocketmq_common::common::message::message_batch::MessageBatch;
 use rocketmq_common::common::message::message_client_id_setter::MessageClientIDSetter;
 use rocketmq_common::common::message::message_enum::MessageRequestMode;
@@ -51,52 +54,120 @@ use rocketmq_remoting::clients::rocketmq_default_impl::RocketmqDefaultClient;
 use rocketmq_remoting::clients::RemotingClient;
 use rocketmq_remoting::code::request_code::RequestCode;
 use rocketmq_remoting::code::response_code::ResponseCode;
+use rocketmq_remoting::protocol::admin::consume_stats::ConsumeStats;
+use rocketmq_remoting::protocol::admin::topic_stats_table::TopicStatsTable;
+use rocketmq_remoting::protocol::body::acl_info::AclInfo;
 use rocketmq_remoting::protocol::body::batch_ack_message_request_body::BatchAckMessageRequestBody;
+use rocketmq_remoting::protocol::body::broker_body::cluster_info::ClusterInfo;
 use rocketmq_remoting::protocol::body::check_client_request_body::CheckClientRequestBody;
+use rocketmq_remoting::protocol::body::check_rocksdb_cqwrite_progress_response_body::CheckRocksdbCqWriteProgressResponseBody;
+use rocketmq_remoting::protocol::body::consume_message_directly_result::ConsumeMessageDirectlyResult;
+use rocketmq_remoting::protocol::body::consumer_connection::ConsumerConnection;
+use rocketmq_remoting::protocol::body::consumer_running_info::ConsumerRunningInfo;
+use rocketmq_remoting::protocol::body::create_topic_list_request_body::CreateTopicListRequestBody;
 use rocketmq_remoting::protocol::body::get_consumer_listby_group_response_body::GetConsumerListByGroupResponseBody;
+use rocketmq_remoting::protocol::body::get_consumer_status_body::GetConsumerStatusBody;
+use rocketmq_remoting::protocol::body::group_list::GroupList;
+use rocketmq_remoting::protocol::body::kv_table::KVTable;
+use rocketmq_remoting::protocol::body::producer_connection::ProducerConnection;
 use rocketmq_remoting::protocol::body::query_assignment_request_body::QueryAssignmentRequestBody;
 use rocketmq_remoting::protocol::body::query_assignment_response_body::QueryAssignmentResponseBody;
+use rocketmq_remoting::protocol::body::query_subscription_response_body::QuerySubscriptionResponseBody;
 use rocketmq_remoting::protocol::body::request::lock_batch_request_body::LockBatchRequestBody;
+use rocketmq_remoting::protocol::body::reset_offset_body::ResetOffsetBody;
 use rocketmq_remoting::protocol::body::response::lock_batch_response_body::LockBatchResponseBody;
 use rocketmq_remoting::protocol::body::set_message_request_mode_request_body::SetMessageRequestModeRequestBody;
+use rocketmq_remoting::protocol::body::topic::topic_list::TopicList;
+use rocketmq_remoting::protocol::body::topic_info_wrapper::TopicConfigSerializeWrapper;
 use rocketmq_remoting::protocol::body::unlock_batch_request_body::UnlockBatchRequestBody;
+use rocketmq_remoting::protocol::body::user_info::UserInfo;
 use rocketmq_remoting::protocol::header::ack_message_request_header::AckMessageRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::CreateAccessConfigRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::CreateAclRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::CreateUserRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::DeleteAccessConfigRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::DeleteAclRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::DeleteUserRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::UpdateAclRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::UpdateGlobalWhiteAddrsConfigRequestHeader;
+use rocketmq_remoting::protocol::header::acl_request_header::UpdateUserRequestHeader;
+use rocketmq_remoting::protocol::header::broker_container_request_header::AddBrokerRequestHeader;
+use rocketmq_remoting::protocol::header::broker_container_request_header::RemoveBrokerRequestHeader;
 use rocketmq_remoting::protocol::header::change_invisible_time_request_header::ChangeInvisibleTimeRequestHeader;
 use rocketmq_remoting::protocol::header::change_invisible_time_response_header::ChangeInvisibleTimeResponseHeader;
+use rocketmq_remoting::protocol::header::check_rocksdb_cq_write_progress_request_header::CheckRocksdbCqWriteProgressRequestHeader;
 use rocketmq_remoting::protocol::header::client_request_header::GetRouteInfoRequestHeader;
+use rocketmq_remoting::protocol::header::clone_group_offset_request_header::CloneGroupOffsetRequestHeader;
+use rocketmq_remoting::protocol::header::consume_message_directly_result_request_header::ConsumeMessageDirectlyResultRequestHeader;
 use rocketmq_remoting::protocol::header::consumer_send_msg_back_request_header::ConsumerSendMsgBackRequestHeader;
+use rocketmq_remoting::protocol::header::controller::clean_controller_broker_data_request_header::CleanControllerBrokerDataRequestHeader;
+use rocketmq_remoting::protocol::header::create_topic_request_header::CreateTopicRequestHeader;
+use rocketmq_remoting::protocol::header::delete_subscription_group_request_header::DeleteSubscriptionGroupRequestHeader;
+use rocketmq_remoting::protocol::header::delete_topic_request_header::DeleteTopicRequestHeader;
 use rocketmq_remoting::protocol::header::empty_header::EmptyHeader;
 use rocketmq_remoting::protocol::header::end_transaction_request_header::EndTransactionRequestHeader;
 use rocketmq_remoting::protocol::header::extra_info_util::ExtraInfoUtil;
+use rocketmq_remoting::protocol::header::get_consume_stats_request_header::GetConsumeStatsRequestHeader;
+use rocketmq_remoting::protocol::header::get_consumer_connection_list_request_header::GetConsumerConnectionListRequestHeader;
 use rocketmq_remoting::protocol::header::get_consumer_listby_group_request_header::GetConsumerListByGroupRequestHeader;
+use rocketmq_remoting::protocol::header::get_consumer_running_info_request_header::GetConsumerRunningInfoRequestHeader;
+use rocketmq_remoting::protocol::header::get_consumer_status_request_header::GetConsumerStatusRequestHeader;
 use rocketmq_remoting::protocol::header::get_max_offset_request_header::GetMaxOffsetRequestHeader;
 use rocketmq_remoting::protocol::header::get_max_offset_response_header::GetMaxOffsetResponseHeader;
+use rocketmq_remoting::protocol::header::get_min_offset_request_header::GetMinOffsetRequestHeader;
+use rocketmq_remoting::protocol::header::get_min_offset_response_header::GetMinOffsetResponseHeader;
+use rocketmq_remoting::protocol::header::get_producer_connection_list_request_header::GetProducerConnectionListRequestHeader;
+use rocketmq_remoting::protocol::header::get_subscription_group_config_request_header::GetSubscriptionGroupConfigRequestHeader;
+use rocketmq_remoting::protocol::header::get_topic_config_request_header::GetTopicConfigRequestHeader;
+use rocketmq_remoting::protocol::header::get_topic_stats_info_request_header::GetTopicStatsInfoRequestHeader;
 use rocketmq_remoting::protocol::header::heartbeat_request_header::HeartbeatRequestHeader;
 use rocketmq_remoting::protocol::header::lock_batch_mq_request_header::LockBatchMqRequestHeader;
 use rocketmq_remoting::protocol::header::message_operation_header::send_message_request_header::SendMessageRequestHeader;
 use rocketmq_remoting::protocol::header::message_operation_header::send_message_request_header_v2::SendMessageRequestHeaderV2;
 use rocketmq_remoting::protocol::header::message_operation_header::send_message_response_header::SendMessageResponseHeader;
 use rocketmq_remoting::protocol::header::namesrv::kv_config_header::DeleteKVConfigRequestHeader;
+use rocketmq_remoting::protocol::header::namesrv::kv_config_header::GetKVConfigRequestHeader;
+use rocketmq_remoting::protocol::header::namesrv::kv_config_header::GetKVConfigResponseHeader;
+use rocketmq_remoting::protocol::header::namesrv::kv_config_header::GetKVListByNamespaceRequestHeader;
 use rocketmq_remoting::protocol::header::namesrv::kv_config_header::PutKVConfigRequestHeader;
 use rocketmq_remoting::protocol::header::namesrv::perm_broker_header::AddWritePermOfBrokerRequestHeader;
 use rocketmq_remoting::protocol::header::namesrv::perm_broker_header::AddWritePermOfBrokerResponseHeader;
 use rocketmq_remoting::protocol::header::namesrv::perm_broker_header::WipeWritePermOfBrokerRequestHeader;
 use rocketmq_remoting::protocol::header::namesrv::perm_broker_header::WipeWritePermOfBrokerResponseHeader;
+use rocketmq_remoting::protocol::header::namesrv::topic_operation_header::DeleteTopicFromNamesrvRequestHeader;
+use rocketmq_remoting::protocol::header::namesrv::topic_operation_header::GetTopicsByClusterRequestHeader;
+use rocketmq_remoting::protocol::header::peek_message_request_header::PeekMessageRequestHeader;
+use rocketmq_remoting::protocol::header::polling_info_request_header::PollingInfoRequestHeader;
+use rocketmq_remoting::protocol::header::polling_info_response_header::PollingInfoResponseHeader;
 use rocketmq_remoting::protocol::header::pop_message_request_header::PopMessageRequestHeader;
 use rocketmq_remoting::protocol::header::pop_message_response_header::PopMessageResponseHeader;
 use rocketmq_remoting::protocol::header::pull_message_request_header::PullMessageRequestHeader;
 use rocketmq_remoting::protocol::header::pull_message_response_header::PullMessageResponseHeader;
 use rocketmq_remoting::protocol::header::query_consumer_offset_request_header::QueryConsumerOffsetRequestHeader;
 use rocketmq_remoting::protocol::header::query_consumer_offset_response_header::QueryConsumerOffsetResponseHeader;
+use rocketmq_remoting::protocol::header::query_message_request_header::QueryMessageRequestHeader;
+use rocketmq_remoting::protocol::header::query_message_response_header::QueryMessageResponseHeader;
+use rocketmq_remoting::protocol::header::query_subscription_by_consumer_request_header::QuerySubscriptionByConsumerRequestHeader;
+use rocketmq_remoting::protocol::header::query_topic_consume_by_who_request_header::QueryTopicConsumeByWhoRequestHeader;
+use rocketmq_remoting::protocol::header::query_topics_by_consumer_request_header::QueryTopicsByConsumerRequestHeader;
+use rocketmq_remoting::protocol::header::reset_master_flush_offset_header::ResetMasterFlushOffsetHeader;
+use rocketmq_remoting::protocol::header::reset_offset_request_header::ResetOffsetRequestHeader;
+use rocketmq_remoting::protocol::header::resume_check_half_message_request_header::ResumeCheckHalfMessageRequestHeader;
+use rocketmq_remoting::protocol::header::search_offset_request_header::SearchOffsetRequestHeader;
+use rocketmq_remoting::protocol::header::search_offset_response_header::SearchOffsetResponseHeader;
 use rocketmq_remoting::protocol::header::unlock_batch_mq_request_header::UnlockBatchMqRequestHeader;
 use rocketmq_remoting::protocol::header::unregister_client_request_header::UnregisterClientRequestHeader;
 use rocketmq_remoting::protocol::header::update_consumer_offset_header::UpdateConsumerOffsetRequestHeader;
+use rocketmq_remoting::protocol::header::view_message_request_header::ViewMessageRequestHeader;
 use rocketmq_remoting::protocol::heartbeat::heartbeat_data::HeartbeatData;
 use rocketmq_remoting::protocol::heartbeat::message_model::MessageModel;
 use rocketmq_remoting::protocol::heartbeat::subscription_data::SubscriptionData;
 use rocketmq_remoting::protocol::namespace_util::NamespaceUtil;
 use rocketmq_remoting::protocol::remoting_command::RemotingCommand;
 use rocketmq_remoting::protocol::route::topic_route_data::TopicRouteData;
+use rocketmq_remoting::protocol::static_topic::topic_config_and_queue_mapping::TopicConfigAndQueueMapping;
+use rocketmq_remoting::protocol::static_topic::topic_queue_mapping_detail::TopicQueueMappingDetail;
+use rocketmq_remoting::protocol::subscription::subscription_group_config::SubscriptionGroupConfig;
 use rocketmq_remoting::protocol::RemotingDeserializable;
 use rocketmq_remoting::protocol::RemotingSerializable;
 use rocketmq_remoting::remoting::RemotingService;
@@ -109,6 +180,7 @@ use tracing::error;
 use tracing::warn;
 
 use crate::base::client_config::ClientConfig;
+use crate::base::query_result::QueryResult;
 use crate::consumer::ack_callback::AckCallback;
 use crate::consumer::ack_result::AckResult;
 use crate::consumer::ack_status::AckStatus;
@@ -136,6 +208,8 @@ lazy_static! {
         .unwrap_or(false);
 }
 
+const READ_AHEAD_MODE: &str = "READ_AHEAD_MODE";
+
 pub struct MQClientAPIImpl {
     remoting_client: ArcMut<RocketmqDefaultClient<ClientRemotingProcessor>>,
     top_addressing: Arc<Box<dyn TopAddressing>>,
@@ -224,14 +298,10 @@ impl MQClientAPIImpl {
         if body.is_empty() {
             return Ok(());
         }
-        let invoke_name_servers;
-        if let Some(name_servers) = special_name_servers
-            && !name_servers.is_empty()
-        {
-            invoke_name_servers = name_servers;
-        } else {
-            invoke_name_servers = Vec::from(self.get_name_server_address_list());
-        }
+        let invoke_name_servers = match special_name_servers {
+            Some(name_servers) if !name_servers.is_empty() => name_servers,
+            _ => Vec::from(self.get_name_server_address_list()),
+        };
         if invoke_name_servers.is_empty() {
             return Ok(());
         }
@@ -1442,6 +1512,197 @@ impl MQClientAPIImpl {
         )
     }
 
+    pub async fn get_min_offset(
+        &mut self,
+        addr: &str,
+        message_queue: &MessageQueue,
+        timeout_millis: u64,
+    ) -> rocketmq_error::RocketMQResult<i64> {
+        let request_header = GetMinOffsetRequestHeader {
+            topic: CheetahString::from_slice(message_queue.get_topic()),
+            queue_id: message_queue.get_queue_id(),
+            topic_request_header: Some(TopicRequestHeader {
+                rpc_request_header: Some(RpcRequestHeader {
+                    broker_name: Some(CheetahString::from_slice(message_queue.get_broker_name())),
+                    ..Default::default()
+                }),
+                lo: None,
+            }),
+        };
+
+        let request =
+            RemotingCommand::create_request_command(RequestCode::GetMinOffset, request_header);
+
+        let response = self
+            .remoting_client
+            .invoke_async(
+                Some(&mix_all::broker_vip_channel(
+                    self.client_config.vip_channel_enabled,
+                    addr,
+                )),
+                request,
+                timeout_millis,
+            )
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            let response_header = response
+                .decode_command_custom_header::<GetMinOffsetResponseHeader>()
+                .expect("decode error");
+            return Ok(response_header.offset);
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn search_offset(
+        &mut self,
+        addr: &str,
+        message_queue: &MessageQueue,
+        timestamp: u64,
+        timeout_millis: u64,
+    ) -> rocketmq_error::RocketMQResult<i64> {
+        let request_header = SearchOffsetRequestHeader {
+            topic: CheetahString::from_slice(message_queue.get_topic()),
+            queue_id: message_queue.get_queue_id(),
+            timestamp: timestamp as i64,
+            boundary_type: None,
+            topic_request_header: Some(
+                rocketmq_remoting::protocol::header::namesrv::topic_operation_header::TopicRequestHeader {
+                    lo: None,
+                    rpc: Some(RpcRequestHeader {
+                        broker_name: Some(CheetahString::from_slice(
+                            message_queue.get_broker_name(),
+                        )),
+                        ..Default::default()
+                    }),
+                },
+            ),
+        };
+
+        let request = RemotingCommand::create_request_command(
+            RequestCode::SearchOffsetByTimestamp,
+            request_header,
+        );
+
+        let response = self
+            .remoting_client
+            .invoke_async(
+                Some(&mix_all::broker_vip_channel(
+                    self.client_config.vip_channel_enabled,
+                    addr,
+                )),
+                request,
+                timeout_millis,
+            )
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            let response_header = response
+                .decode_command_custom_header::<SearchOffsetResponseHeader>()
+                .expect("decode error");
+            return Ok(response_header.offset);
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn query_message(
+        &mut self,
+        addr: &CheetahString,
+        request_header: QueryMessageRequestHeader,
+        is_unique_key: bool,
+        timeout_millis: u64,
+    ) -> rocketmq_error::RocketMQResult<QueryResult> {
+        let mut request =
+            RemotingCommand::create_request_command(RequestCode::QueryMessage, request_header);
+        if is_unique_key {
+            request = request.set_ext_fields(HashMap::from([(
+                CheetahString::from_static_str(mix_all::UNIQUE_MSG_QUERY_FLAG),
+                CheetahString::from_static_str("true"),
+            )]));
+        }
+        let mut response = self
+            .remoting_client
+            .invoke_async(
+                Some(&mix_all::broker_vip_channel(
+                    self.client_config.vip_channel_enabled,
+                    addr,
+                )),
+                request,
+                timeout_millis,
+            )
+            .await?;
+        match ResponseCode::from(response.code()) {
+            ResponseCode::Success => {
+                let response_header =
+                    response.decode_command_custom_header::<QueryMessageResponseHeader>()?;
+                let message_list = match response.take_body() {
+                    Some(mut body) => MessageDecoder::decodes_batch(&mut body, true, true),
+                    None => vec![],
+                };
+                Ok(QueryResult::new(
+                    response_header.index_last_update_timestamp as u64,
+                    message_list,
+                ))
+            }
+            ResponseCode::QueryNotFound => Ok(QueryResult::default()),
+            _ => client_broker_err!(
+                response.code(),
+                response.remark().map_or("".to_string(), |s| s.to_string()),
+                addr.to_string()
+            ),
+        }
+    }
+
+    pub async fn view_message(
+        &mut self,
+        addr: &CheetahString,
+        topic: &CheetahString,
+        phy_offset: i64,
+        timeout_millis: u64,
+    ) -> rocketmq_error::RocketMQResult<MessageExt> {
+        let request_header = ViewMessageRequestHeader {
+            topic: topic.clone(),
+            offset: phy_offset,
+        };
+        let request =
+            RemotingCommand::create_request_command(RequestCode::ViewMessageById, request_header);
+        let mut response = self
+            .remoting_client
+            .invoke_async(
+                Some(&mix_all::broker_vip_channel(
+                    self.client_config.vip_channel_enabled,
+                    addr,
+                )),
+                request,
+                timeout_millis,
+            )
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            if let Some(mut body) = response.take_body() {
+                if let Some(message) =
+                    MessageDecoder::decode(&mut body, true, true, false, false, false)
+                {
+                    return Ok(message);
+                }
+            }
+            return mq_client_err!(format!(
+                "view message by offset {} failed, response body is invalid",
+                phy_offset
+            ));
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
     pub async fn set_message_request_mode(
         &mut self,
         broker_addr: &CheetahString,
@@ -1874,92 +2135,1328 @@ impl MQClientAPIImpl {
         Ok(pop_result)
     }
 
-    pub async fn ack_message_async(
+    /// Reads messages from the head of a queue without creating pop checkpoints or moving the
+    /// consume offset of the group.
+    pub async fn peek_message(
         &self,
         addr: &CheetahString,
-        request_header: AckMessageRequestHeader,
+        request_header: PeekMessageRequestHeader,
         timeout_millis: u64,
-        ack_callback: impl AckCallback,
-    ) -> rocketmq_error::RocketMQResult<()> {
-        self.ack_message_async_inner(
-            addr,
-            Some(request_header),
-            None,
-            timeout_millis,
-            ack_callback,
-        )
-        .await
+    ) -> RocketMQResult<PopResult> {
+        let request =
+            RemotingCommand::create_request_command(RequestCode::PeekMessage, request_header);
+        let mut response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        let (pop_status, msg_found_list) = match ResponseCode::from(response.code()) {
+            ResponseCode::Success => {
+                let messages = match response.get_body_mut() {
+                    Some(body) => MessageDecoder::decodes_batch(
+                        body,
+                        self.client_config.decode_read_body,
+                        self.client_config.decode_decompress_body,
+                    ),
+                    None => vec![],
+                };
+                (PopStatus::Found, messages)
+            }
+            ResponseCode::PullNotFound => (PopStatus::NoNewMsg, vec![]),
+            _ => {
+                return client_broker_err!(
+                    response.code(),
+                    response.remark().cloned().unwrap_or_default(),
+                    addr.to_string()
+                )
+            }
+        };
+        let response_header =
+            response.decode_command_custom_header::<PopMessageResponseHeader>()?;
+        Ok(PopResult {
+            msg_found_list: Some(msg_found_list),
+            pop_status,
+            rest_num: response_header.rest_num,
+            ..Default::default()
+        })
     }
 
-    pub(self) async fn ack_message_async_inner(
+    /// Returns the number of POP long-polling requests parked on the broker for the
+    /// topic/group/queue in `request_header`.
+    pub async fn get_polling_info(
         &self,
         addr: &CheetahString,
-        request_header: Option<AckMessageRequestHeader>,
-        request_body: Option<BatchAckMessageRequestBody>,
+        request_header: PollingInfoRequestHeader,
         timeout_millis: u64,
-        ack_callback: impl AckCallback,
-    ) -> rocketmq_error::RocketMQResult<()> {
-        let request = if let Some(header) = request_header {
-            RemotingCommand::create_request_command(RequestCode::AckMessage, header)
-        } else {
-            let body = request_body.unwrap();
-            RemotingCommand::new_request(RequestCode::BatchAckMessage, body.encode()?)
-        };
-        match self
+    ) -> RocketMQResult<i32> {
+        let request =
+            RemotingCommand::create_request_command(RequestCode::PollingInfo, request_header);
+        let response = self
             .remoting_client
             .invoke_async(Some(addr), request, timeout_millis)
-            .await
-        {
-            Ok(response) => {
-                let response_code = ResponseCode::from(response.code());
-                let ack_result = if response_code == ResponseCode::Success {
-                    AckResult {
-                        status: AckStatus::Ok,
-                        ..Default::default()
-                    }
-                } else {
-                    AckResult {
-                        status: AckStatus::NotExist,
-                        ..Default::default()
-                    }
-                };
-                ack_callback.on_success(ack_result);
-            }
-            Err(e) => {
-                ack_callback.on_exception(Box::new(e));
-            }
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            let response_header =
+                response.decode_command_custom_header::<PollingInfoResponseHeader>()?;
+            return Ok(response_header.polling_num);
         }
-        Ok(())
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
     }
 
-    pub async fn get_name_server_config(
+    pub async fn update_cold_data_flow_ctr_group_config(
         &self,
-        name_servers: Option<Vec<CheetahString>>,
-        timeout_millis: Duration,
-    ) -> RocketMQResult<Option<HashMap<CheetahString, HashMap<CheetahString, CheetahString>>>> {
-        // Determine which name servers to invoke
-        let invoke_name_servers = match name_servers {
-            Some(servers) if !servers.is_empty() => servers,
-            _ => self.remoting_client.get_name_server_address_list().to_vec(),
-        };
+        addr: &CheetahString,
+        properties: &HashMap<CheetahString, CheetahString>,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let body = mix_all::properties_to_string(properties);
+        let request = RemotingCommand::create_request_command(
+            RequestCode::UpdateColdDataFlowCtrConfig,
+            EmptyHeader {},
+        )
+        .set_body(body.to_string());
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
 
-        if invoke_name_servers.is_empty() {
-            return Ok(None);
-        }
+    pub async fn remove_cold_data_flow_ctr_group_config(
+        &self,
+        addr: &CheetahString,
+        consumer_group: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let request = RemotingCommand::create_request_command(
+            RequestCode::RemoveColdDataFlowCtrConfig,
+            EmptyHeader {},
+        )
+        .set_body(consumer_group.to_string());
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
 
-        // Create request command
-        let request = RemotingCommand::create_remoting_command(RequestCode::GetNamesrvConfig);
-        let mut config_map = HashMap::with_capacity(4);
-        // Iterate through each name server
-        for name_server in invoke_name_servers {
-            // Make synchronous call with timeout
-            let response = self
-                .remoting_client
-                .invoke_async(
-                    Some(&name_server),
-                    request.clone(),
-                    timeout_millis.as_millis() as u64,
-                )
+    pub async fn get_cold_data_flow_ctr_info(
+        &self,
+        addr: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<CheetahString> {
+        let request = RemotingCommand::create_remoting_command(RequestCode::GetColdDataFlowCtrInfo);
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            return Ok(response
+                .get_body()
+                .map(|body| CheetahString::from(String::from_utf8_lossy(body.as_ref()).to_string()))
+                .unwrap_or_default());
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn get_broker_cluster_info(
+        &self,
+        timeout_millis: u64,
+    ) -> RocketMQResult<ClusterInfo> {
+        let request = RemotingCommand::create_remoting_command(RequestCode::GetBrokerClusterInfo);
+        let response = self
+            .remoting_client
+            .invoke_async(None, request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            if let Some(body) = response.get_body() {
+                return ClusterInfo::decode(body.as_ref());
+            }
+        }
+        mq_client_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string())
+        )
+    }
+
+    pub async fn delete_expired_commit_log(
+        &self,
+        addr: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<bool> {
+        let request = RemotingCommand::create_remoting_command(RequestCode::DeleteExpiredCommitlog);
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            return Ok(true);
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn check_rocksdb_cq_write_progress(
+        &self,
+        addr: &CheetahString,
+        topic: CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<CheckRocksdbCqWriteProgressResponseBody> {
+        let request_header = CheckRocksdbCqWriteProgressRequestHeader { topic };
+        let request = RemotingCommand::create_request_command(
+            RequestCode::CheckRocksdbCqWriteProgress,
+            request_header,
+        );
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            if let Some(body) = response.body() {
+                return CheckRocksdbCqWriteProgressResponseBody::decode(body.as_ref());
+            }
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn create_topic(
+        &self,
+        addr: &CheetahString,
+        default_topic: &str,
+        topic_config: &TopicConfig,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let attributes = topic_config
+            .attributes
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect::<HashMap<String, String>>();
+        let request_header = CreateTopicRequestHeader {
+            topic: topic_config.topic_name.clone().unwrap_or_default(),
+            default_topic: CheetahString::from(default_topic),
+            read_queue_nums: topic_config.read_queue_nums as i32,
+            write_queue_nums: topic_config.write_queue_nums as i32,
+            perm: topic_config.perm as i32,
+            topic_filter_type: topic_config.topic_filter_type.to_string().into(),
+            topic_sys_flag: Some(topic_config.topic_sys_flag as i32),
+            order: topic_config.order,
+            attributes: Some(AttributeParser::parse_to_string(&attributes).into()),
+            force: None,
+            topic_request_header: None,
+        };
+        let request = RemotingCommand::create_request_command(
+            RequestCode::UpdateAndCreateTopic,
+            request_header,
+        );
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn create_subscription_group(
+        &self,
+        addr: &CheetahString,
+        config: &SubscriptionGroupConfig,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let request =
+            RemotingCommand::create_remoting_command(RequestCode::UpdateAndCreateSubscriptionGroup)
+                .set_body(config.encode()?);
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn get_topic_list_from_name_server(
+        &self,
+        timeout_millis: u64,
+    ) -> RocketMQResult<TopicList> {
+        let request =
+            RemotingCommand::create_remoting_command(RequestCode::GetAllTopicListFromNameserver);
+        let response = self
+            .remoting_client
+            .invoke_async(None, request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            if let Some(body) = response.body() {
+                return TopicList::decode(body.as_ref());
+            }
+        }
+        mq_client_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string())
+        )
+    }
+
+    pub async fn get_topic_stats_info(
+        &self,
+        addr: &CheetahString,
+        topic: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<TopicStatsTable> {
+        let request_header = GetTopicStatsInfoRequestHeader {
+            topic: topic.clone(),
+            topic_request_header: None,
+        };
+        let request =
+            RemotingCommand::create_request_command(RequestCode::GetTopicStatsInfo, request_header);
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            if let Some(body) = response.body() {
+                return TopicStatsTable::decode(body.as_ref());
+            }
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn delete_topic_in_broker(
+        &self,
+        addr: &CheetahString,
+        topic: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let request_header = DeleteTopicRequestHeader {
+            topic: topic.clone(),
+            topic_request_header: None,
+        };
+        let request = RemotingCommand::create_request_command(
+            RequestCode::DeleteTopicInBroker,
+            request_header,
+        );
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn delete_topic_in_name_server(
+        &self,
+        addr: &CheetahString,
+        cluster_name: Option<&CheetahString>,
+        topic: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let request_header =
+            DeleteTopicFromNamesrvRequestHeader::new(topic.clone(), cluster_name.cloned());
+        let request = RemotingCommand::create_request_command(
+            RequestCode::DeleteTopicInNamesrv,
+            request_header,
+        );
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn delete_subscription_group(
+        &self,
+        addr: &CheetahString,
+        group_name: &CheetahString,
+        remove_offset: bool,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let request_header = DeleteSubscriptionGroupRequestHeader {
+            group_name: group_name.clone(),
+            clean_offset: remove_offset,
+            rpc_request_header: None,
+        };
+        let request = RemotingCommand::create_request_command(
+            RequestCode::DeleteSubscriptionGroup,
+            request_header,
+        );
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn get_consume_stats(
+        &self,
+        addr: &CheetahString,
+        consumer_group: &CheetahString,
+        topic: Option<&CheetahString>,
+        timeout_millis: u64,
+    ) -> RocketMQResult<ConsumeStats> {
+        let request_header = GetConsumeStatsRequestHeader {
+            consumer_group: consumer_group.clone(),
+            topic: topic.cloned().unwrap_or_default(),
+            topic_request_header: None,
+        };
+        let request =
+            RemotingCommand::create_request_command(RequestCode::GetConsumeStats, request_header);
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            if let Some(body) = response.body() {
+                return ConsumeStats::decode(body.as_ref());
+            }
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn get_consumer_connection_list(
+        &self,
+        addr: &CheetahString,
+        consumer_group: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<ConsumerConnection> {
+        let request_header = GetConsumerConnectionListRequestHeader {
+            consumer_group: consumer_group.clone(),
+            rpc_request_header: None,
+        };
+        let request = RemotingCommand::create_request_command(
+            RequestCode::GetConsumerConnectionList,
+            request_header,
+        );
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            if let Some(body) = response.body() {
+                return ConsumerConnection::decode(body.as_ref());
+            }
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn query_topic_consume_by_who(
+        &self,
+        addr: &CheetahString,
+        topic: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<GroupList> {
+        let request_header = QueryTopicConsumeByWhoRequestHeader {
+            topic: topic.clone(),
+            topic_request_header: None,
+        };
+        let request = RemotingCommand::create_request_command(
+            RequestCode::QueryTopicConsumeByWho,
+            request_header,
+        );
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            if let Some(body) = response.body() {
+                return GroupList::decode(body.as_ref());
+            }
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn get_kv_config_value(
+        &self,
+        namespace: &CheetahString,
+        key: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<Option<CheetahString>> {
+        let request_header = GetKVConfigRequestHeader::new(namespace.clone(), key.clone());
+        let request =
+            RemotingCommand::create_request_command(RequestCode::GetKvConfig, request_header);
+        let response = self
+            .remoting_client
+            .invoke_async(None, request, timeout_millis)
+            .await?;
+        match ResponseCode::from(response.code()) {
+            ResponseCode::Success => {
+                let response_header =
+                    response.decode_command_custom_header::<GetKVConfigResponseHeader>()?;
+                Ok(response_header.value)
+            }
+            ResponseCode::QueryNotFound => Ok(None),
+            _ => mq_client_err!(
+                response.code(),
+                response.remark().map_or("".to_string(), |s| s.to_string())
+            ),
+        }
+    }
+
+    pub async fn add_broker(
+        &self,
+        broker_container_addr: &CheetahString,
+        broker_config_path: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let request_header = AddBrokerRequestHeader {
+            config_path: Some(broker_config_path.clone()),
+        };
+        let request =
+            RemotingCommand::create_request_command(RequestCode::AddBroker, request_header);
+        self.invoke_admin_request(broker_container_addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn remove_broker(
+        &self,
+        broker_container_addr: &CheetahString,
+        cluster_name: &CheetahString,
+        broker_name: &CheetahString,
+        broker_id: u64,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let request_header = RemoveBrokerRequestHeader {
+            broker_name: broker_name.clone(),
+            broker_cluster_name: cluster_name.clone(),
+            broker_id,
+        };
+        let request =
+            RemotingCommand::create_request_command(RequestCode::RemoveBroker, request_header);
+        self.invoke_admin_request(broker_container_addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn update_broker_config(
+        &self,
+        addr: &CheetahString,
+        properties: &HashMap<CheetahString, CheetahString>,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let body = mix_all::properties_to_string(properties);
+        if body.is_empty() {
+            return Ok(());
+        }
+        let request = RemotingCommand::create_remoting_command(RequestCode::UpdateBrokerConfig)
+            .set_body(body.to_string());
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn get_broker_config(
+        &self,
+        addr: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<HashMap<CheetahString, CheetahString>> {
+        let request = RemotingCommand::create_remoting_command(RequestCode::GetBrokerConfig);
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            return Ok(response
+                .body()
+                .as_ref()
+                .and_then(|body| {
+                    mix_all::string_to_properties(&String::from_utf8_lossy(body.as_ref()))
+                })
+                .unwrap_or_default());
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn create_topic_list(
+        &self,
+        addr: &CheetahString,
+        topic_config_list: Vec<TopicConfig>,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let request_body = CreateTopicListRequestBody { topic_config_list };
+        let request =
+            RemotingCommand::create_remoting_command(RequestCode::UpdateAndCreateTopicList)
+                .set_body(request_body.encode()?);
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn get_broker_cluster_acl_version(
+        &self,
+        addr: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<CheetahString> {
+        let request =
+            RemotingCommand::create_remoting_command(RequestCode::GetBrokerClusterAclInfo);
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            return Ok(response
+                .get_ext_fields()
+                .and_then(|ext_fields| ext_fields.get("version").cloned())
+                .unwrap_or_default());
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn get_subscription_group_config(
+        &self,
+        addr: &CheetahString,
+        group: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<SubscriptionGroupConfig> {
+        let request_header = GetSubscriptionGroupConfigRequestHeader {
+            group: group.clone(),
+            rpc_request_header: None,
+        };
+        let request = RemotingCommand::create_request_command(
+            RequestCode::GetSubscriptionGroupConfig,
+            request_header,
+        );
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            if let Some(body) = response.body() {
+                return SubscriptionGroupConfig::decode(body.as_ref());
+            }
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn get_topics_by_cluster(
+        &self,
+        cluster: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<TopicList> {
+        let request_header = GetTopicsByClusterRequestHeader::new(cluster.clone());
+        let request = RemotingCommand::create_request_command(
+            RequestCode::GetTopicsByCluster,
+            request_header,
+        );
+        let response = self
+            .remoting_client
+            .invoke_async(None, request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            if let Some(body) = response.body() {
+                return TopicList::decode(body.as_ref());
+            }
+        }
+        mq_client_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string())
+        )
+    }
+
+    pub async fn get_broker_runtime_info(
+        &self,
+        addr: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<KVTable> {
+        let request = RemotingCommand::create_remoting_command(RequestCode::GetBrokerRuntimeInfo);
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            if let Some(body) = response.body() {
+                return KVTable::decode(body.as_ref());
+            }
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn get_producer_connection_list(
+        &self,
+        addr: &CheetahString,
+        producer_group: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<ProducerConnection> {
+        let request_header = GetProducerConnectionListRequestHeader {
+            producer_group: producer_group.clone(),
+            rpc_request_header: None,
+        };
+        let request = RemotingCommand::create_request_command(
+            RequestCode::GetProducerConnectionList,
+            request_header,
+        );
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            if let Some(body) = response.body() {
+                return ProducerConnection::decode(body.as_ref());
+            }
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn get_kv_list_by_namespace(
+        &self,
+        namespace: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<KVTable> {
+        let request_header = GetKVListByNamespaceRequestHeader::new(namespace.clone());
+        let request = RemotingCommand::create_request_command(
+            RequestCode::GetKvlistByNamespace,
+            request_header,
+        );
+        let response = self
+            .remoting_client
+            .invoke_async(None, request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            if let Some(body) = response.body() {
+                return KVTable::decode(body.as_ref());
+            }
+        }
+        mq_client_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string())
+        )
+    }
+
+    pub async fn invoke_broker_to_reset_offset(
+        &self,
+        addr: &CheetahString,
+        topic: &CheetahString,
+        group: &CheetahString,
+        timestamp: u64,
+        is_force: bool,
+        timeout_millis: u64,
+    ) -> RocketMQResult<HashMap<MessageQueue, i64>> {
+        let request_header = ResetOffsetRequestHeader {
+            topic: topic.clone(),
+            group: group.clone(),
+            timestamp: timestamp as i64,
+            is_force,
+            ..Default::default()
+        };
+        let request = RemotingCommand::create_request_command(
+            RequestCode::InvokeBrokerToResetOffset,
+            request_header,
+        );
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            return match response.body() {
+                Some(body) => Ok(ResetOffsetBody::decode(body.as_ref())?.offset_table),
+                None => Ok(HashMap::new()),
+            };
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn invoke_broker_to_get_consumer_status(
+        &self,
+        addr: &CheetahString,
+        topic: &CheetahString,
+        group: &CheetahString,
+        client_addr: Option<&CheetahString>,
+        timeout_millis: u64,
+    ) -> RocketMQResult<HashMap<CheetahString, HashMap<MessageQueue, i64>>> {
+        let request_header = GetConsumerStatusRequestHeader {
+            topic: topic.clone(),
+            group: group.clone(),
+            client_addr: client_addr.cloned(),
+            topic_request_header: None,
+        };
+        let request = RemotingCommand::create_request_command(
+            RequestCode::InvokeBrokerToGetConsumerStatus,
+            request_header,
+        );
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            return match response.body() {
+                Some(body) => Ok(GetConsumerStatusBody::decode(body.as_ref())?.consumer_table),
+                None => Ok(HashMap::new()),
+            };
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn query_topics_by_consumer(
+        &self,
+        addr: &CheetahString,
+        group: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<TopicList> {
+        let request_header = QueryTopicsByConsumerRequestHeader {
+            group: group.clone(),
+            rpc_request_header: None,
+        };
+        let request = RemotingCommand::create_request_command(
+            RequestCode::QueryTopicsByConsumer,
+            request_header,
+        );
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            if let Some(body) = response.body() {
+                return TopicList::decode(body.as_ref());
+            }
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn query_subscription_by_consumer(
+        &self,
+        addr: &CheetahString,
+        group: &CheetahString,
+        topic: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<SubscriptionData> {
+        let request_header = QuerySubscriptionByConsumerRequestHeader {
+            group: group.clone(),
+            topic: topic.clone(),
+            topic_request_header: None,
+        };
+        let request = RemotingCommand::create_request_command(
+            RequestCode::QuerySubscriptionByConsumer,
+            request_header,
+        );
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            if let Some(body) = response.body() {
+                return Ok(QuerySubscriptionResponseBody::decode(body.as_ref())?.subscription_data);
+            }
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn clean_expired_consume_queue(
+        &self,
+        addr: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<bool> {
+        let request =
+            RemotingCommand::create_remoting_command(RequestCode::CleanExpiredConsumequeue);
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            return Ok(true);
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn clean_unused_topic(
+        &self,
+        addr: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<bool> {
+        let request = RemotingCommand::create_remoting_command(RequestCode::CleanUnusedTopic);
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            return Ok(true);
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn get_consumer_running_info(
+        &self,
+        addr: &CheetahString,
+        consumer_group: &CheetahString,
+        client_id: &CheetahString,
+        jstack: bool,
+        timeout_millis: u64,
+    ) -> RocketMQResult<ConsumerRunningInfo> {
+        let request_header = GetConsumerRunningInfoRequestHeader {
+            consumer_group: consumer_group.clone(),
+            client_id: client_id.clone(),
+            jstack_enable: jstack,
+            rpc_request_header: None,
+        };
+        let request = RemotingCommand::create_request_command(
+            RequestCode::GetConsumerRunningInfo,
+            request_header,
+        );
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            if let Some(body) = response.body() {
+                return ConsumerRunningInfo::decode(body.as_ref());
+            }
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn consume_message_directly(
+        &self,
+        addr: &CheetahString,
+        consumer_group: &CheetahString,
+        client_id: &CheetahString,
+        topic: &CheetahString,
+        msg_id: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<ConsumeMessageDirectlyResult> {
+        let request_header = ConsumeMessageDirectlyResultRequestHeader {
+            consumer_group: consumer_group.clone(),
+            client_id: Some(client_id.clone()),
+            msg_id: Some(msg_id.clone()),
+            topic: Some(topic.clone()),
+            ..Default::default()
+        };
+        let request = RemotingCommand::create_request_command(
+            RequestCode::ConsumeMessageDirectly,
+            request_header,
+        );
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            if let Some(body) = response.body() {
+                return ConsumeMessageDirectlyResult::decode(body.as_ref());
+            }
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn clone_group_offset(
+        &self,
+        addr: &CheetahString,
+        src_group: &CheetahString,
+        dest_group: &CheetahString,
+        topic: &CheetahString,
+        is_offline: bool,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let request_header = CloneGroupOffsetRequestHeader {
+            src_group: src_group.clone(),
+            dest_group: dest_group.clone(),
+            topic: Some(topic.clone()),
+            offline: is_offline,
+            rpc_request_header: None,
+        };
+        let request =
+            RemotingCommand::create_request_command(RequestCode::CloneGroupOffset, request_header);
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn get_all_topic_config(
+        &self,
+        addr: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<TopicConfigSerializeWrapper> {
+        let request = RemotingCommand::create_remoting_command(RequestCode::GetAllTopicConfig);
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            if let Some(body) = response.body() {
+                return TopicConfigSerializeWrapper::decode(body.as_ref());
+            }
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn get_system_topic_list_from_broker(
+        &self,
+        addr: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<TopicList> {
+        let request =
+            RemotingCommand::create_remoting_command(RequestCode::GetSystemTopicListFromBroker);
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            if let Some(body) = response.body() {
+                return TopicList::decode(body.as_ref());
+            }
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn resume_check_half_message(
+        &self,
+        addr: &CheetahString,
+        topic: &CheetahString,
+        msg_id: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<bool> {
+        let request_header = ResumeCheckHalfMessageRequestHeader {
+            topic: Some(topic.clone()),
+            msg_id: Some(msg_id.clone()),
+            rpc_request_header: None,
+        };
+        let request = RemotingCommand::create_request_command(
+            RequestCode::ResumeCheckHalfMessage,
+            request_header,
+        );
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            return Ok(true);
+        }
+        error!(
+            "Failed to resume half message check logic. Remark={:?}",
+            response.remark()
+        );
+        Ok(false)
+    }
+
+    pub async fn get_topic_config(
+        &self,
+        addr: &CheetahString,
+        topic: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<TopicConfig> {
+        let request_header = GetTopicConfigRequestHeader {
+            topic: topic.clone(),
+            topic_request_header: None,
+        };
+        let request =
+            RemotingCommand::create_request_command(RequestCode::GetTopicConfig, request_header);
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            if let Some(body) = response.body() {
+                // The Java broker flattens the topic config into the body.
+                return match TopicConfigAndQueueMapping::decode(body.as_ref()) {
+                    Ok(topic_config_and_queue_mapping) => {
+                        Ok(topic_config_and_queue_mapping.topic_config)
+                    }
+                    Err(_) => SerdeJsonUtils::from_json_slice::<TopicConfig>(body.as_ref()),
+                };
+            }
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn create_static_topic(
+        &self,
+        addr: &CheetahString,
+        default_topic: &CheetahString,
+        topic_config: &TopicConfig,
+        mapping_detail: &TopicQueueMappingDetail,
+        force: bool,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let request_header = CreateTopicRequestHeader {
+            topic: topic_config.topic_name.clone().unwrap_or_default(),
+            default_topic: default_topic.clone(),
+            read_queue_nums: topic_config.read_queue_nums as i32,
+            write_queue_nums: topic_config.write_queue_nums as i32,
+            perm: topic_config.perm as i32,
+            topic_filter_type: topic_config.topic_filter_type.to_string().into(),
+            topic_sys_flag: Some(topic_config.topic_sys_flag as i32),
+            order: topic_config.order,
+            attributes: None,
+            force: Some(force),
+            topic_request_header: None,
+        };
+        let request = RemotingCommand::create_request_command(
+            RequestCode::UpdateAndCreateStaticTopic,
+            request_header,
+        )
+        .set_body(mapping_detail.encode()?);
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn reset_master_flush_offset(
+        &self,
+        addr: &CheetahString,
+        master_flush_offset: u64,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let request_header = ResetMasterFlushOffsetHeader {
+            master_flush_offset: master_flush_offset as i64,
+        };
+        let request = RemotingCommand::create_request_command(
+            RequestCode::ResetMasterFlushOffset,
+            request_header,
+        );
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn get_controller_config(
+        &self,
+        controller_servers: &[CheetahString],
+        timeout_millis: u64,
+    ) -> RocketMQResult<HashMap<CheetahString, HashMap<CheetahString, CheetahString>>> {
+        let request = RemotingCommand::create_remoting_command(RequestCode::GetControllerConfig);
+        let mut config_map = HashMap::with_capacity(controller_servers.len());
+        for controller in controller_servers {
+            let response = self
+                .remoting_client
+                .invoke_async(Some(controller), request.clone(), timeout_millis)
+                .await?;
+            if ResponseCode::from(response.code()) != ResponseCode::Success {
+                return mq_client_err!(
+                    response.code(),
+                    response.remark().map_or("".to_string(), |s| s.to_string())
+                );
+            }
+            let properties = response
+                .body()
+                .as_ref()
+                .and_then(|body| {
+                    mix_all::string_to_properties(&String::from_utf8_lossy(body.as_ref()))
+                })
+                .unwrap_or_default();
+            config_map.insert(controller.clone(), properties);
+        }
+        Ok(config_map)
+    }
+
+    pub async fn update_controller_config(
+        &self,
+        properties: &HashMap<CheetahString, CheetahString>,
+        controllers: &[CheetahString],
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let body = mix_all::properties_to_string(properties);
+        if body.is_empty() || controllers.is_empty() {
+            return Ok(());
+        }
+        let request = RemotingCommand::create_remoting_command(RequestCode::UpdateControllerConfig)
+            .set_body(body.to_string());
+        let mut err_response = None;
+        for controller in controllers {
+            let response = self
+                .remoting_client
+                .invoke_async(Some(controller), request.clone(), timeout_millis)
+                .await?;
+            if ResponseCode::from(response.code()) != ResponseCode::Success {
+                err_response = Some(response);
+            }
+        }
+        if let Some(err_response) = err_response {
+            return mq_client_err!(
+                err_response.code(),
+                err_response
+                    .remark()
+                    .map_or("".to_string(), |s| s.to_string())
+            );
+        }
+        Ok(())
+    }
+
+    pub async fn clean_controller_broker_data(
+        &self,
+        controller_addr: &CheetahString,
+        cluster_name: &CheetahString,
+        broker_name: &CheetahString,
+        broker_controller_ids_to_clean: Option<CheetahString>,
+        is_clean_living_broker: bool,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let request_header = CleanControllerBrokerDataRequestHeader {
+            cluster_name: Some(cluster_name.clone()),
+            broker_name: broker_name.clone(),
+            broker_controller_ids_to_clean,
+            is_clean_living_broker,
+        };
+        let request =
+            RemotingCommand::create_request_command(RequestCode::CleanBrokerData, request_header);
+        self.invoke_admin_request(controller_addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn set_commit_log_read_ahead_mode(
+        &self,
+        addr: &CheetahString,
+        mode: &CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<CheetahString> {
+        let mut request =
+            RemotingCommand::create_remoting_command(RequestCode::SetCommitlogReadMode);
+        request.add_ext_field(READ_AHEAD_MODE, mode.clone());
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            return Ok(response.remark().cloned().unwrap_or_default());
+        }
+        client_broker_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string()),
+            addr.to_string()
+        )
+    }
+
+    pub async fn ack_message_async(
+        &self,
+        addr: &CheetahString,
+        request_header: AckMessageRequestHeader,
+        timeout_millis: u64,
+        ack_callback: impl AckCallback,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        self.ack_message_async_inner(
+            addr,
+            Some(request_header),
+            None,
+            timeout_millis,
+            ack_callback,
+        )
+        .await
+    }
+
+    pub(self) async fn ack_message_async_inner(
+        &self,
+        addr: &CheetahString,
+        request_header: Option<AckMessageRequestHeader>,
+        request_body: Option<BatchAckMessageRequestBody>,
+        timeout_millis: u64,
+        ack_callback: impl AckCallback,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        let request = if let Some(header) = request_header {
+            RemotingCommand::create_request_command(RequestCode::AckMessage, header)
+        } else {
+            let body = request_body.unwrap();
+            RemotingCommand::new_request(RequestCode::BatchAckMessage, body.encode()?)
+        };
+        match self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await
+        {
+            Ok(response) => {
+                let response_code = ResponseCode::from(response.code());
+                let ack_result = if response_code == ResponseCode::Success {
+                    AckResult {
+                        status: AckStatus::Ok,
+                        ..Default::default()
+                    }
+                } else {
+                    AckResult {
+                        status: AckStatus::NotExist,
+                        ..Default::default()
+                    }
+                };
+                ack_callback.on_success(ack_result);
+            }
+            Err(e) => {
+                ack_callback.on_exception(Box::new(e));
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn get_name_server_config(
+        &self,
+        name_servers: Option<Vec<CheetahString>>,
+        timeout_millis: Duration,
+    ) -> RocketMQResult<Option<HashMap<CheetahString, HashMap<CheetahString, CheetahString>>>> {
+        // Determine which name servers to invoke
+        let invoke_name_servers = match name_servers {
+            Some(servers) if !servers.is_empty() => servers,
+            _ => self.remoting_client.get_name_server_address_list().to_vec(),
+        };
+
+        if invoke_name_servers.is_empty() {
+            return Ok(None);
+        }
+
+        // Create request command
+        let request = RemotingCommand::create_remoting_command(RequestCode::GetNamesrvConfig);
+        let mut config_map = HashMap::with_capacity(4);
+        // Iterate through each name server
+        for name_server in invoke_name_servers {
+            // Make synchronous call with timeout
+            let response = self
+                .remoting_client
+                .invoke_async(
+                    Some(&name_server),
+                    request.clone(),
+                    timeout_millis.as_millis() as u64,
+                )
                 .await?;
             // Check response code
             match ResponseCode::from(response.code()) {
@@ -1996,6 +3493,189 @@ impl MQClientAPIImpl {
         }
         Ok(Some(config_map))
     }
+
+    pub async fn create_user(
+        &self,
+        addr: &CheetahString,
+        user_info: &UserInfo,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let request_header = CreateUserRequestHeader {
+            username: user_info.username.clone().unwrap_or_default(),
+        };
+        let request =
+            RemotingCommand::create_request_command(RequestCode::AuthCreateUser, request_header)
+                .set_body(SerdeJsonUtils::to_json(user_info)?);
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn update_user(
+        &self,
+        addr: &CheetahString,
+        user_info: &UserInfo,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let request_header = UpdateUserRequestHeader {
+            username: user_info.username.clone().unwrap_or_default(),
+        };
+        let request =
+            RemotingCommand::create_request_command(RequestCode::AuthUpdateUser, request_header)
+                .set_body(SerdeJsonUtils::to_json(user_info)?);
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn delete_user(
+        &self,
+        addr: &CheetahString,
+        username: CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let request = RemotingCommand::create_request_command(
+            RequestCode::AuthDeleteUser,
+            DeleteUserRequestHeader { username },
+        );
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn create_acl(
+        &self,
+        addr: &CheetahString,
+        acl_info: &AclInfo,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let request_header = CreateAclRequestHeader {
+            subject: acl_info.subject.clone().unwrap_or_default(),
+        };
+        let request =
+            RemotingCommand::create_request_command(RequestCode::AuthCreateAcl, request_header)
+                .set_body(SerdeJsonUtils::to_json(acl_info)?);
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn update_acl(
+        &self,
+        addr: &CheetahString,
+        acl_info: &AclInfo,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let request_header = UpdateAclRequestHeader {
+            subject: acl_info.subject.clone().unwrap_or_default(),
+        };
+        let request =
+            RemotingCommand::create_request_command(RequestCode::AuthUpdateAcl, request_header)
+                .set_body(SerdeJsonUtils::to_json(acl_info)?);
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn delete_acl(
+        &self,
+        addr: &CheetahString,
+        subject: CheetahString,
+        resource: Option<CheetahString>,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let request_header = DeleteAclRequestHeader {
+            subject,
+            policy_type: None,
+            resource,
+        };
+        let request =
+            RemotingCommand::create_request_command(RequestCode::AuthDeleteAcl, request_header);
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn create_plain_access_config(
+        &self,
+        addr: &CheetahString,
+        config: PlainAccessConfig,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let join = |perms: Vec<CheetahString>| {
+            (!perms.is_empty()).then(|| {
+                CheetahString::from(
+                    perms
+                        .iter()
+                        .map(|perm| perm.as_str())
+                        .collect::<Vec<_>>()
+                        .join(","),
+                )
+            })
+        };
+        let request_header = CreateAccessConfigRequestHeader {
+            access_key: config.access_key.unwrap_or_default(),
+            secret_key: config.secret_key,
+            white_remote_address: config.white_remote_address,
+            admin: Some(config.admin),
+            default_topic_perm: config.default_topic_perm,
+            default_group_perm: config.default_group_perm,
+            topic_perms: join(config.topic_perms),
+            group_perms: join(config.group_perms),
+        };
+        let request = RemotingCommand::create_request_command(
+            RequestCode::UpdateAndCreateAclConfig,
+            request_header,
+        );
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn delete_plain_access_config(
+        &self,
+        addr: &CheetahString,
+        access_key: CheetahString,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let request = RemotingCommand::create_request_command(
+            RequestCode::DeleteAclConfig,
+            DeleteAccessConfigRequestHeader { access_key },
+        );
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
+
+    pub async fn update_global_white_addrs_config(
+        &self,
+        addr: &CheetahString,
+        global_white_addrs: CheetahString,
+        acl_file_full_path: Option<CheetahString>,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let request = RemotingCommand::create_request_command(
+            RequestCode::UpdateGlobalWhiteAddrsConfig,
+            UpdateGlobalWhiteAddrsConfigRequestHeader {
+                global_white_addrs,
+                acl_file_full_path,
+            },
+        );
+        self.invoke_admin_request(addr, request, timeout_millis)
+            .await
+    }
+
+    /// Sends an admin request whose successful response carries nothing.
+    async fn invoke_admin_request(
+        &self,
+        addr: &CheetahString,
+        request: RemotingCommand,
+        timeout_millis: u64,
+    ) -> RocketMQResult<()> {
+        let response = self
+            .remoting_client
+            .invoke_async(Some(addr), request, timeout_millis)
+            .await?;
+        if ResponseCode::from(response.code()) == ResponseCode::Success {
+            return Ok(());
+        }
+        mq_client_err!(
+            response.code(),
+            response.remark().map_or("".to_string(), |s| s.to_string())
+        )
+    }
 }
 
 fn build_queue_offset_sorted_map(
@@ -1,4 +1,3 @@
-#![feature(let_chains)]
 /*
  * Licensed to the Apache Software Foundation (ASF) under one or more
  * contributor license agreements.  See the NOTICE file distributed with
@@ -32,7 +31,8 @@ mod hook;
 pub mod implementation;
 mod latency;
 pub mod producer;
-mod trace;
+pub mod stat;
+pub mod trace;
 pub mod utils;
 
 pub use crate::consumer::consumer_impl::pull_request_ext::PullResultExt;
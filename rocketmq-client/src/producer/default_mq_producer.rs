@@ -37,9 +37,11 @@ use rocketmq_remoting::code::response_code::ResponseCode;
 use rocketmq_remoting::runtime::RPCHook;
 use rocketmq_rust::ArcMut;
 use tracing::error;
+use tracing::warn;
 
 use crate::base::client_config::ClientConfig;
 use crate::base::validators::Validators;
+use crate::implementation::mq_client_manager::MQClientManager;
 use crate::producer::default_mq_produce_builder::DefaultMQProducerBuilder;
 use crate::producer::mq_producer::MQProducer;
 use crate::producer::produce_accumulator::ProduceAccumulator;
@@ -418,8 +420,60 @@ impl DefaultMQProducer {
         self.producer_config.trace_dispatcher = trace_dispatcher;
     }
 
+    /// Enabling auto batch without a produce accumulator shares the accumulator of the client
+    /// instance.
     pub fn set_auto_batch(&mut self, auto_batch: bool) {
         self.producer_config.auto_batch = auto_batch;
+        if auto_batch && self.producer_config.produce_accumulator.is_none() {
+            self.producer_config.produce_accumulator = Some(
+                MQClientManager::get_instance()
+                    .get_or_create_produce_accumulator(self.client_config.clone()),
+            );
+        }
+    }
+
+    /// The time a batch is held before being sent, in milliseconds.
+    pub fn batch_max_delay_ms(&self) -> u32 {
+        self.producer_config
+            .produce_accumulator
+            .as_ref()
+            .map_or(0, |produce_accumulator| produce_accumulator.hold_ms())
+    }
+
+    /// The size a batch is sent at, in bytes.
+    pub fn batch_max_bytes(&self) -> usize {
+        self.producer_config
+            .produce_accumulator
+            .as_ref()
+            .map_or(0, |produce_accumulator| produce_accumulator.hold_size())
+    }
+
+    /// The size of all messages held for batching, in bytes.
+    pub fn total_batch_max_bytes(&self) -> usize {
+        self.producer_config
+            .produce_accumulator
+            .as_ref()
+            .map_or(0, |produce_accumulator| {
+                produce_accumulator.total_hold_size()
+            })
+    }
+
+    pub fn set_batch_max_delay_ms(&mut self, hold_ms: u32) {
+        if let Some(ref mut produce_accumulator) = self.producer_config.produce_accumulator {
+            produce_accumulator.set_hold_ms(hold_ms);
+        }
+    }
+
+    pub fn set_batch_max_bytes(&mut self, hold_size: usize) {
+        if let Some(ref mut produce_accumulator) = self.producer_config.produce_accumulator {
+            produce_accumulator.set_hold_size(hold_size);
+        }
+    }
+
+    pub fn set_total_batch_max_bytes(&mut self, total_hold_size: usize) {
+        if let Some(ref mut produce_accumulator) = self.producer_config.produce_accumulator {
+            produce_accumulator.set_total_hold_size(total_hold_size);
+        }
     }
 
     pub fn set_produce_accumulator(&mut self, produce_accumulator: Option<ProduceAccumulator>) {
@@ -548,10 +602,11 @@ impl DefaultMQProducer {
     where
         M: MessageTrait + Send + std::clone::Clone + std::marker::Sync + 'static,
     {
+        // checked before can_batch, which holds the message size in the accumulator
+        Validators::check_message(Some(&msg), self.producer_config())?;
         if !self.can_batch(&msg) {
             self.send_direct(msg, mq, send_callback).await
         } else {
-            Validators::check_message(Some(&msg), self.producer_config())?;
             MessageClientIDSetter::set_uniq_id(&mut msg);
             if send_callback.is_none() {
                 let mq_producer = self.clone();
@@ -578,16 +633,6 @@ impl DefaultMQProducer {
     where
         M: MessageTrait,
     {
-        // produceAccumulator is full
-        if !self
-            .producer_config
-            .produce_accumulator
-            .as_ref()
-            .unwrap()
-            .try_add_message(msg)
-        {
-            return false;
-        }
         // delay message do not support batch processing
         if msg.get_delay_time_level() > 0
             || msg.get_delay_time_ms() > 0
@@ -610,7 +655,12 @@ impl DefaultMQProducer {
         {
             return false;
         }
-        true
+        // produceAccumulator is full, checked last as it takes hold of the message size
+        self.producer_config
+            .produce_accumulator
+            .as_ref()
+            .unwrap()
+            .try_add_message(msg)
     }
 }
 
@@ -638,7 +688,10 @@ impl MQProducer for DefaultMQProducer {
             let mut dispatcher = AsyncTraceDispatcher::new(
                 self.producer_config.producer_group.as_str(),
                 Type::Produce,
-                self.client_config.trace_topic.clone().unwrap().as_str(),
+                self.client_config
+                    .trace_topic
+                    .as_deref()
+                    .unwrap_or(TopicValidator::RMQ_SYS_TRACE_TOPIC),
                 self.producer_config.rpc_hook.clone(),
             );
             dispatcher.set_host_producer(self.default_mqproducer_impl.as_ref().unwrap().clone());
@@ -653,8 +706,16 @@ impl MQProducer for DefaultMQProducer {
                 .register_end_transaction_hook(EndTransactionTraceHookImpl::new(dispatcher))
         }
 
-        if let Some(ref mut trace_dispatcher) = self.producer_config.trace_dispatcher {
-            //TODO: trace
+        if let Some(ref trace_dispatcher) = self.producer_config.trace_dispatcher {
+            if let Err(e) = trace_dispatcher.start(
+                self.client_config
+                    .get_namesrv_addr()
+                    .unwrap_or_default()
+                    .as_str(),
+                self.client_config.access_channel,
+            ) {
+                warn!("trace dispatcher start failed: {}", e);
+            }
         }
         Ok(())
     }
@@ -1286,3 +1347,32 @@ impl MQProducer for DefaultMQProducer {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn send_by_accumulator_rejects_invalid_message_without_holding_it() {
+        let mut producer = DefaultMQProducer::default();
+        producer.set_produce_accumulator(Some(ProduceAccumulator::new("invalid_message_test")));
+        producer.set_auto_batch(true);
+        producer.set_max_message_size(4);
+
+        let oversized = Message::with_keys("TopicTest", "TagA", "k1", b"0123456789");
+        for _ in 0..3 {
+            assert!(producer
+                .send_by_accumulator(oversized.clone(), None, None)
+                .await
+                .is_err());
+        }
+        assert_eq!(
+            producer
+                .produce_accumulator()
+                .as_ref()
+                .unwrap()
+                .currently_hold_size(),
+            0
+        );
+    }
+}
@@ -18,36 +18,50 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::hash::Hash;
 use std::hash::Hasher;
-use std::sync::atomic::AtomicBool;
-use std::sync::atomic::AtomicI32;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
-use std::thread::Builder;
+use std::time::Duration;
 
 use cheetah_string::CheetahString;
+use rocketmq_common::common::message::message_batch::MessageBatch;
+use rocketmq_common::common::message::message_client_id_setter::MessageClientIDSetter;
 use rocketmq_common::common::message::message_queue::MessageQueue;
+use rocketmq_common::common::message::message_single::Message;
+use rocketmq_common::common::message::MessageConst;
 use rocketmq_common::common::message::MessageTrait;
 use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_error::mq_client_err;
 use rocketmq_rust::ArcMut;
-use tokio::sync::Mutex;
+use tokio::sync::oneshot;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tracing::info;
+use tracing::warn;
 
 use crate::producer::default_mq_producer::DefaultMQProducer;
 use crate::producer::send_callback::SendMessageCallback;
 use crate::producer::send_result::SendResult;
 
+type SendBatches = Arc<parking_lot::Mutex<HashMap<AggregateKey, Arc<MessageAccumulation>>>>;
+
+/// Aggregates small messages sent with auto batch enabled into [`MessageBatch`]es.
+///
+/// Messages are held per [`AggregateKey`] until the batch reaches `hold_size` bytes or is older
+/// than `hold_ms`, then the batch is sent once and its result is split back to every caller.
+/// At most `total_hold_size` bytes are held at a time, messages beyond that are sent directly.
 #[derive(Default)]
 pub struct ProduceAccumulator {
     total_hold_size: usize,
     hold_size: usize,
     hold_ms: u32,
-    guard_thread_for_sync_send: GuardForSyncSendService,
-    guard_thread_for_async_send: GuardForAsyncSendService,
-    currently_hold_size: AtomicU64,
+    guard_thread_for_sync_send: GuardForSendService,
+    guard_thread_for_async_send: GuardForSendService,
+    currently_hold_size: Arc<AtomicU64>,
     instance_name: String,
     currently_hold_size_lock: Arc<parking_lot::Mutex<()>>,
-    sync_send_batchs: Arc<Mutex<HashMap<AggregateKey, ArcMut<MessageAccumulation>>>>,
-    async_send_batchs: Arc<Mutex<HashMap<AggregateKey, ArcMut<MessageAccumulation>>>>,
+    sync_send_batchs: SendBatches,
+    async_send_batchs: SendBatches,
 }
 
 impl ProduceAccumulator {
@@ -57,22 +71,62 @@ impl ProduceAccumulator {
             hold_size: 1024 * 32,
             hold_ms: 10,
             instance_name: instance_name.to_string(),
-            guard_thread_for_async_send: GuardForAsyncSendService {
-                service_name: instance_name.to_string(),
-            },
-            guard_thread_for_sync_send: GuardForSyncSendService {
-                service_name: instance_name.to_string(),
-            },
+            guard_thread_for_async_send: GuardForSendService::new(&format!(
+                "Client_{instance_name}_GuardForAsyncSend"
+            )),
+            guard_thread_for_sync_send: GuardForSendService::new(&format!(
+                "Client_{instance_name}_GuardForSyncSend"
+            )),
             ..Default::default()
         }
     }
+
+    #[inline]
+    pub fn total_hold_size(&self) -> usize {
+        self.total_hold_size
+    }
+
+    #[inline]
+    pub fn hold_size(&self) -> usize {
+        self.hold_size
+    }
+
+    #[inline]
+    pub fn hold_ms(&self) -> u32 {
+        self.hold_ms
+    }
+
+    #[inline]
+    pub fn currently_hold_size(&self) -> u64 {
+        self.currently_hold_size.load(Ordering::Acquire)
+    }
+
+    #[inline]
+    pub fn set_total_hold_size(&mut self, total_hold_size: usize) {
+        self.total_hold_size = total_hold_size;
+    }
+
+    #[inline]
+    pub fn set_hold_size(&mut self, hold_size: usize) {
+        self.hold_size = hold_size;
+    }
+
+    #[inline]
+    pub fn set_hold_ms(&mut self, hold_ms: u32) {
+        self.hold_ms = hold_ms;
+    }
 }
 
 impl ProduceAccumulator {
     pub fn start(&mut self) {
-        self.guard_thread_for_sync_send.start();
-        self.guard_thread_for_async_send.start();
+        self.guard_thread_for_sync_send
+            .start(self.sync_send_batchs.clone(), self.hold_ms);
+        self.guard_thread_for_async_send
+            .start(self.async_send_batchs.clone(), self.hold_ms);
     }
+
+    /// Stops the guard services. Batches still held are sent before the services exit, so no
+    /// caller is left waiting.
     pub fn shutdown(&mut self) {
         self.guard_thread_for_sync_send.shutdown();
         self.guard_thread_for_async_send.shutdown();
@@ -85,45 +139,124 @@ impl ProduceAccumulator {
             return false;
         }
         self.currently_hold_size
-            .fetch_add(message.get_body().unwrap().len() as u64, Ordering::AcqRel);
+            .fetch_add(body_size(message) as u64, Ordering::AcqRel);
         drop(lock);
         true
     }
 
     pub(crate) async fn send<M: MessageTrait + Send + Sync + 'static>(
-        &mut self,
+        &self,
         message: M,
         mq: Option<MessageQueue>,
         default_mq_producer: DefaultMQProducer,
     ) -> rocketmq_error::RocketMQResult<Option<SendResult>> {
-        unimplemented!("send")
+        let message = into_message(message);
+        let partition_key = AggregateKey::new_from_message_queue(&message, mq);
+        let (tx, rx) = oneshot::channel();
+        let default_mq_producer = ArcMut::new(default_mq_producer);
+        let mut pending = (message, PendingSend::Sync(tx));
+        loop {
+            let batch = get_or_create_send_batch(
+                &self.sync_send_batchs,
+                &partition_key,
+                &default_mq_producer,
+                &self.currently_hold_size,
+            );
+            match batch.add(pending.0, pending.1, self.hold_size, self.hold_ms) {
+                Ok(ready) => {
+                    if ready {
+                        batch.send().await;
+                    }
+                    break;
+                }
+                // the batch has been sent meanwhile, add the message to a new one
+                Err(rejected) => pending = rejected,
+            }
+        }
+        match rx.await {
+            Ok(result) => result.map(Some),
+            Err(_) => mq_client_err!("the batch holding the message was dropped before sending"),
+        }
     }
 
     pub(crate) async fn send_callback<M: MessageTrait + Send + Sync + 'static + Clone>(
-        &mut self,
+        &self,
         message: M,
         mq: Option<MessageQueue>,
         send_callback: Option<SendMessageCallback>,
         default_mq_producer: DefaultMQProducer,
     ) -> rocketmq_error::RocketMQResult<()> {
+        let message = into_message(message);
         let partition_key = AggregateKey::new_from_message_queue(&message, mq);
+        let default_mq_producer = ArcMut::new(default_mq_producer);
+        let mut pending = (message, PendingSend::Async(send_callback));
         loop {
-            let batch =
-                self.get_or_create_async_send_batch(partition_key.clone(), &default_mq_producer);
-            /*if batch.add(message.clone(), send_callback.clone()) {
-                self.async_send_batchs.lock().await.remove(&partition_key);
-            } else {
-                return Ok(());
-            }*/
+            let batch = self.get_or_create_async_send_batch(&partition_key, &default_mq_producer);
+            match batch.add(pending.0, pending.1, self.hold_size, self.hold_ms) {
+                Ok(ready) => {
+                    if ready {
+                        tokio::spawn(batch.send());
+                    }
+                    return Ok(());
+                }
+                Err(rejected) => pending = rejected,
+            }
         }
     }
 
     fn get_or_create_async_send_batch(
-        &mut self,
-        aggregate_key: AggregateKey,
-        default_mq_producer: &DefaultMQProducer,
-    ) -> ArcMut<MessageAccumulation> {
-        unimplemented!("getOrCreateAsyncSendBatch")
+        &self,
+        aggregate_key: &AggregateKey,
+        default_mq_producer: &ArcMut<DefaultMQProducer>,
+    ) -> Arc<MessageAccumulation> {
+        get_or_create_send_batch(
+            &self.async_send_batchs,
+            aggregate_key,
+            default_mq_producer,
+            &self.currently_hold_size,
+        )
+    }
+}
+
+fn body_size<T: MessageTrait>(message: &T) -> usize {
+    message.get_body().map_or(0, |body| body.len())
+}
+
+fn into_message<M: MessageTrait + 'static>(message: M) -> Message {
+    if let Some(message) = message.as_any().downcast_ref::<Message>() {
+        return message.clone();
+    }
+    let mut converted = Message::default();
+    converted.set_topic(message.get_topic().clone());
+    converted.set_flag(message.get_flag());
+    converted.set_properties(message.get_properties().clone());
+    if let Some(body) = message.get_body() {
+        converted.set_body(body.clone());
+    }
+    if let Some(transaction_id) = message.get_transaction_id() {
+        converted.set_transaction_id(transaction_id.clone());
+    }
+    converted
+}
+
+fn get_or_create_send_batch(
+    batches: &SendBatches,
+    aggregate_key: &AggregateKey,
+    default_mq_producer: &ArcMut<DefaultMQProducer>,
+    currently_hold_size: &Arc<AtomicU64>,
+) -> Arc<MessageAccumulation> {
+    let mut batches = batches.lock();
+    match batches.get(aggregate_key) {
+        Some(batch) if !batch.is_closed() => batch.clone(),
+        _ => {
+            let batch = Arc::new(MessageAccumulation::new(
+                aggregate_key.clone(),
+                default_mq_producer.clone(),
+                currently_hold_size.clone(),
+            ));
+            batches.insert(aggregate_key.clone(), batch.clone());
+            batch
+        }
     }
 }
 
@@ -189,16 +322,43 @@ impl Hash for AggregateKey {
     }
 }
 
+enum PendingSend {
+    Sync(oneshot::Sender<rocketmq_error::RocketMQResult<SendResult>>),
+    Async(Option<SendMessageCallback>),
+}
+
+impl PendingSend {
+    fn complete(self, result: Result<&SendResult, &rocketmq_error::RocketmqError>) {
+        match (self, result) {
+            (PendingSend::Sync(tx), Ok(send_result)) => {
+                let _ = tx.send(Ok(send_result.clone()));
+            }
+            (PendingSend::Sync(tx), Err(e)) => {
+                let _ = tx.send(mq_client_err!(e.to_string()));
+            }
+            (PendingSend::Async(Some(callback)), Ok(send_result)) => {
+                callback(Some(send_result), None);
+            }
+            (PendingSend::Async(Some(callback)), Err(e)) => callback(None, Some(e)),
+            (PendingSend::Async(None), _) => {}
+        }
+    }
+}
+
+#[derive(Default)]
+struct AccumulationState {
+    messages: Vec<Message>,
+    pending_sends: Vec<PendingSend>,
+    keys: HashSet<String>,
+    messages_size: usize,
+    closed: bool,
+}
+
 struct MessageAccumulation {
     default_mq_producer: ArcMut<DefaultMQProducer>,
-    messages: Vec<Box<dyn MessageTrait + Send + Sync + 'static>>,
-    send_callbacks: Vec<SendMessageCallback>,
-    keys: HashSet<String>,
-    closed: Arc<AtomicBool>,
-    send_results: Option<Vec<SendResult>>,
     aggregate_key: AggregateKey,
-    messages_size: Arc<AtomicI32>,
-    count: usize,
+    currently_hold_size: Arc<AtomicU64>,
+    state: parking_lot::Mutex<AccumulationState>,
     create_time: u64,
 }
 
@@ -206,142 +366,311 @@ impl MessageAccumulation {
     pub fn new(
         aggregate_key: AggregateKey,
         default_mq_producer: ArcMut<DefaultMQProducer>,
+        currently_hold_size: Arc<AtomicU64>,
     ) -> Self {
         Self {
             default_mq_producer,
-            messages: vec![],
-            send_callbacks: vec![],
-            keys: HashSet::new(),
-            closed: Arc::new(AtomicBool::new(false)),
-            send_results: None,
             aggregate_key,
-            messages_size: Arc::new(AtomicI32::new(0)),
-            count: 0,
+            currently_hold_size,
+            state: parking_lot::Mutex::new(AccumulationState::default()),
             create_time: get_current_millis(),
         }
     }
 
-    pub fn add<M: MessageTrait + Send + Sync + 'static>(
-        &mut self,
-        msg: M,
-        send_callback: Option<SendMessageCallback>,
-    ) -> rocketmq_error::RocketMQResult<bool> {
-        unimplemented!()
-    }
-
-    /*    fn ready_to_send(&self, hold_size: i32, hold_ms: u128) -> bool {
-        self.messages_size.load(Ordering::SeqCst) > hold_size
-            || SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("Time went backwards")
-                .as_millis()
-                >= self.create_time + hold_ms
+    fn is_closed(&self) -> bool {
+        self.state.lock().closed
     }
 
-
-
-    pub fn add_with_callback(
-        &mut self,
+    /// Adds the message to the batch and returns whether the batch is ready to be sent. A closed
+    /// batch hands the message back.
+    fn add(
+        &self,
         msg: Message,
-        send_callback: SendCallback,
-    ) -> rocketmq_error::RocketMQResult<bool, ()> {
-        if self.closed.load(Ordering::SeqCst) {
-            return Err(());
+        pending_send: PendingSend,
+        hold_size: usize,
+        hold_ms: u32,
+    ) -> Result<bool, (Message, PendingSend)> {
+        let mut state = self.state.lock();
+        if state.closed {
+            return Err((msg, pending_send));
         }
-        self.count += 1;
-        self.messages.push_back(msg);
-        self.send_callbacks.push_back(send_callback);
-        self.messages_size
-            .fetch_add(msg.get_body().len() as i32, Ordering::SeqCst);
-
-        Ok(true)
+        if let Some(keys) = msg.get_keys() {
+            state.keys.extend(
+                keys.split(MessageConst::KEY_SEPARATOR)
+                    .filter(|key| !key.is_empty())
+                    .map(str::to_string),
+            );
+        }
+        state.messages_size += body_size(&msg);
+        state.messages.push(msg);
+        state.pending_sends.push(pending_send);
+        Ok(state.messages_size >= hold_size || self.expired(hold_ms))
     }
 
-    fn batch(&self) -> MessageBatch {
-        // Implementation for creating a message batch from the accumulated messages
-        MessageBatch {}
+    fn expired(&self, hold_ms: u32) -> bool {
+        get_current_millis() >= self.create_time + hold_ms as u64
     }
 
-    fn split_send_results(&mut self, send_result: SendResult) {
-        // Implementation for splitting send results
+    /// Closes the batch and takes what it holds, only the first caller gets the messages.
+    fn close(&self) -> Option<AccumulationState> {
+        let mut state = self.state.lock();
+        if state.closed {
+            return None;
+        }
+        let taken = std::mem::take(&mut *state);
+        state.closed = true;
+        Some(taken)
     }
 
-    pub fn send(&mut self) -> rocketmq_error::RocketMQResult<(), ()> {
-        if self.closed.swap(true, Ordering::SeqCst) {
-            return Ok(());
+    /// Sends the held messages as one [`MessageBatch`] and completes every pending send.
+    async fn send(self: Arc<Self>) {
+        let Some(state) = self.close() else {
+            return;
+        };
+        let count = state.messages.len();
+        let result = self.send_batch(state.messages, state.keys).await;
+        self.currently_hold_size
+            .fetch_sub(state.messages_size as u64, Ordering::AcqRel);
+        match result.and_then(|send_result| split_send_results(&send_result, count)) {
+            Ok(send_results) => {
+                for (pending_send, send_result) in
+                    state.pending_sends.into_iter().zip(&send_results)
+                {
+                    pending_send.complete(Ok(send_result));
+                }
+            }
+            Err(e) => {
+                warn!(
+                    "send batch of {} messages to {} failed: {}",
+                    count, self.aggregate_key.topic, e
+                );
+                for pending_send in state.pending_sends {
+                    pending_send.complete(Err(&e));
+                }
+            }
         }
-        let message_batch = self.batch();
-        let send_result = self
-            .default_mq_producer
-            .send_direct(message_batch, None, None)?;
-        self.split_send_results(send_result);
-        Ok(())
     }
 
-    pub fn send_with_callback(&mut self, send_callback: SendCallback) -> rocketmq_error::RocketMQResult<(), ()> {
-        if self.closed.swap(true, Ordering::SeqCst) {
-            return Ok(());
+    async fn send_batch(
+        &self,
+        messages: Vec<Message>,
+        keys: HashSet<String>,
+    ) -> rocketmq_error::RocketMQResult<SendResult> {
+        let mut message_batch = MessageBatch::generate_from_vec(messages)?;
+        MessageClientIDSetter::set_uniq_id(&mut message_batch.final_message);
+        if !keys.is_empty() {
+            message_batch
+                .final_message
+                .set_keys_from_collection(keys.into_iter().collect());
         }
-        let message_batch = self.batch();
-        let size = self.messages_size.load(Ordering::SeqCst);
-
-        self.default_mq_producer
-            .send_direct(message_batch, None, Some(send_callback))?;
+        message_batch.set_body(message_batch.encode());
+        match self
+            .default_mq_producer
+            .mut_from_ref()
+            .send_direct(message_batch, self.aggregate_key.mq.clone(), None)
+            .await?
+        {
+            Some(send_result) => Ok(send_result),
+            None => mq_client_err!("send batch returned no result"),
+        }
+    }
+}
 
-        Ok(())
-    }*/
+/// Splits the result of a batch into the results of its messages, the message ids of a batch
+/// are joined by commas and its messages take consecutive queue offsets.
+fn split_send_results(
+    send_result: &SendResult,
+    count: usize,
+) -> rocketmq_error::RocketMQResult<Vec<SendResult>> {
+    let msg_ids: Vec<&str> = send_result
+        .msg_id
+        .as_ref()
+        .map_or(vec![], |msg_id| msg_id.split(',').collect());
+    let offset_msg_ids: Vec<&str> = send_result
+        .offset_msg_id
+        .as_ref()
+        .map_or(vec![], |offset_msg_id| offset_msg_id.split(',').collect());
+    if msg_ids.len() != count || offset_msg_ids.len() != count {
+        return mq_client_err!(format!(
+            "illegal batch send result, {} message ids and {} offset message ids for {} messages",
+            msg_ids.len(),
+            offset_msg_ids.len(),
+            count
+        ));
+    }
+    Ok(msg_ids
+        .into_iter()
+        .zip(offset_msg_ids)
+        .enumerate()
+        .map(|(index, (msg_id, offset_msg_id))| {
+            let mut result = send_result.clone();
+            result.msg_id = Some(CheetahString::from_slice(msg_id));
+            result.offset_msg_id = Some(offset_msg_id.to_string());
+            result.queue_offset = send_result.queue_offset + index as u64;
+            result
+        })
+        .collect())
 }
 
+/// Sends the batches which have been held for `hold_ms` without reaching the hold size.
 #[derive(Default)]
-struct GuardForSyncSendService {
+struct GuardForSendService {
     service_name: String,
+    shutdown: Arc<Notify>,
+    handle: Option<JoinHandle<()>>,
 }
 
-impl GuardForSyncSendService {
+impl GuardForSendService {
     pub fn new(service_name: &str) -> Self {
         Self {
             service_name: service_name.to_string(),
+            ..Default::default()
         }
     }
 
-    pub fn start(&mut self) {
-        let service_name = self.service_name.clone();
-        Builder::new()
-            .name(service_name)
-            .spawn(|| {
-                // Implementation for starting the guard thread
-            })
-            .expect("Failed to start guard thread");
+    pub fn start(&mut self, batches: SendBatches, hold_ms: u32) {
+        if self.handle.is_some() {
+            return;
+        }
+        let shutdown = self.shutdown.clone();
+        let interval = Duration::from_millis((hold_ms / 2).max(1) as u64);
+        info!("{} started", self.service_name);
+        self.handle = Some(tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown.notified() => break,
+                    _ = tokio::time::sleep(interval) => {}
+                }
+                for batch in take_batches(&batches, |batch| batch.expired(hold_ms)) {
+                    tokio::spawn(batch.send());
+                }
+            }
+            for batch in take_batches(&batches, |_| true) {
+                batch.send().await;
+            }
+        }));
     }
 
     pub fn shutdown(&mut self) {
-        // Implementation for shutting down the guard thread
+        if self.handle.take().is_some() {
+            self.shutdown.notify_one();
+        }
     }
 }
 
-#[derive(Default)]
-struct GuardForAsyncSendService {
-    service_name: String,
+/// Removes the batches matching `filter` together with the closed ones.
+fn take_batches(
+    batches: &SendBatches,
+    filter: impl Fn(&MessageAccumulation) -> bool,
+) -> Vec<Arc<MessageAccumulation>> {
+    let mut taken = Vec::new();
+    batches.lock().retain(|_, batch| {
+        if batch.is_closed() {
+            return false;
+        }
+        if filter(batch) {
+            taken.push(batch.clone());
+            return false;
+        }
+        true
+    });
+    taken
 }
 
-impl GuardForAsyncSendService {
-    pub fn new(service_name: &str) -> Self {
-        Self {
-            service_name: service_name.to_string(),
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accumulation() -> MessageAccumulation {
+        MessageAccumulation::new(
+            AggregateKey::new(
+                CheetahString::from_static_str("TopicTest"),
+                None,
+                true,
+                None,
+            ),
+            ArcMut::new(DefaultMQProducer::default()),
+            Arc::new(AtomicU64::new(0)),
+        )
     }
 
-    pub fn start(&mut self) {
-        let service_name = self.service_name.clone();
-        Builder::new()
-            .name(service_name)
-            .spawn(|| {
-                // Implementation for starting the guard thread
-            })
-            .expect("Failed to start guard thread");
+    #[test]
+    fn split_send_results_assigns_ids_and_offsets() {
+        let send_result = SendResult {
+            msg_id: Some(CheetahString::from_static_str("A,B,C")),
+            offset_msg_id: Some("OA,OB,OC".to_string()),
+            queue_offset: 100,
+            ..Default::default()
+        };
+        let results = split_send_results(&send_result, 3).unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[1].msg_id.as_ref().unwrap().as_str(), "B");
+        assert_eq!(results[1].offset_msg_id.as_deref(), Some("OB"));
+        assert_eq!(results[2].queue_offset, 102);
+
+        assert!(split_send_results(&send_result, 2).is_err());
     }
 
-    pub fn shutdown(&mut self) {
-        // Implementation for shutting down the guard thread
+    #[test]
+    fn accumulation_is_ready_at_hold_size_and_rejects_after_close() {
+        let batch = accumulation();
+        let (tx, _rx) = oneshot::channel();
+        let ready = batch.add(
+            Message::with_keys("TopicTest", "TagA", "k1 k2", b"0123"),
+            PendingSend::Sync(tx),
+            8,
+            60_000,
+        );
+        assert_eq!(ready.ok(), Some(false));
+        let ready = batch.add(
+            Message::with_keys("TopicTest", "TagA", "k2 k3", b"4567"),
+            PendingSend::Async(None),
+            8,
+            60_000,
+        );
+        assert_eq!(ready.ok(), Some(true));
+
+        let state = batch.close().unwrap();
+        assert_eq!(state.messages.len(), 2);
+        assert_eq!(state.pending_sends.len(), 2);
+        assert_eq!(state.messages_size, 8);
+        assert_eq!(state.keys.len(), 3);
+        assert!(batch.close().is_none());
+
+        let rejected = batch.add(
+            Message::new("TopicTest", b"89"),
+            PendingSend::Async(None),
+            8,
+            60_000,
+        );
+        assert!(rejected.is_err());
+    }
+
+    #[test]
+    fn take_batches_skips_closed_and_young_batches() {
+        let batches: SendBatches = Default::default();
+        let closed = Arc::new(accumulation());
+        closed.close();
+        let young = Arc::new(accumulation());
+        let key = |topic: &'static str| {
+            AggregateKey::new(CheetahString::from_static_str(topic), None, true, None)
+        };
+        batches.lock().insert(key("closed"), closed);
+        batches.lock().insert(key("young"), young);
+
+        assert!(take_batches(&batches, |batch| batch.expired(60_000)).is_empty());
+        assert_eq!(batches.lock().len(), 1);
+        assert_eq!(take_batches(&batches, |batch| batch.expired(0)).len(), 1);
+        assert!(batches.lock().is_empty());
+    }
+
+    #[test]
+    fn try_add_message_is_bounded_by_total_hold_size() {
+        let mut accumulator = ProduceAccumulator::new("test");
+        accumulator.set_total_hold_size(4);
+        let message = Message::new("TopicTest", b"01234");
+        assert!(accumulator.try_add_message(&message));
+        assert_eq!(accumulator.currently_hold_size(), 5);
+        assert!(!accumulator.try_add_message(&message));
     }
 }
@@ -1051,8 +1051,8 @@ impl DefaultMQProducerImpl {
             if msg_type_flag {
                 send_message_context.msg_type = Some(MessageType::DelayMsg);
             }
-            let send_message_context = Some(send_message_context);
-            self.execute_send_message_hook_before(&send_message_context);
+            let mut send_message_context = Some(send_message_context);
+            self.execute_send_message_hook_before(&mut send_message_context);
             send_message_context
         } else {
             None
@@ -1184,7 +1184,10 @@ impl DefaultMQProducerImpl {
         }
     }
 
-    pub fn execute_send_message_hook_before(&mut self, context: &Option<SendMessageContext<'_>>) {
+    pub fn execute_send_message_hook_before(
+        &mut self,
+        context: &mut Option<SendMessageContext<'_>>,
+    ) {
         if self.has_send_message_hook() {
             for hook in self.send_message_hook_list.iter() {
                 hook.send_message_before(context);
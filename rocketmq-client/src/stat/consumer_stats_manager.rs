@@ -0,0 +1,185 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use rocketmq_common::common::stats::stats_item_set::StatsItemSet;
+use rocketmq_remoting::protocol::body::consume_status::ConsumeStatus;
+
+const TOPIC_AND_GROUP_CONSUME_OK_TPS: &str = "CONSUME_OK_TPS";
+const TOPIC_AND_GROUP_CONSUME_FAILED_TPS: &str = "CONSUME_FAILED_TPS";
+const TOPIC_AND_GROUP_CONSUME_RT: &str = "CONSUME_RT";
+const TOPIC_AND_GROUP_PULL_TPS: &str = "PULL_TPS";
+const TOPIC_AND_GROUP_PULL_RT: &str = "PULL_RT";
+
+/// Pull and consume statistics of every `topic@group` served by a client instance, reported to
+/// the broker as part of the consumer running info.
+pub struct ConsumerStatsManager {
+    topic_and_group_consume_ok_tps: StatsItemSet,
+    topic_and_group_consume_failed_tps: StatsItemSet,
+    topic_and_group_consume_rt: StatsItemSet,
+    topic_and_group_pull_tps: StatsItemSet,
+    topic_and_group_pull_rt: StatsItemSet,
+}
+
+impl Default for ConsumerStatsManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConsumerStatsManager {
+    pub fn new() -> Self {
+        ConsumerStatsManager {
+            topic_and_group_consume_ok_tps: StatsItemSet::new(
+                TOPIC_AND_GROUP_CONSUME_OK_TPS.to_string(),
+            ),
+            topic_and_group_consume_failed_tps: StatsItemSet::new(
+                TOPIC_AND_GROUP_CONSUME_FAILED_TPS.to_string(),
+            ),
+            topic_and_group_consume_rt: StatsItemSet::new(TOPIC_AND_GROUP_CONSUME_RT.to_string()),
+            topic_and_group_pull_tps: StatsItemSet::new(TOPIC_AND_GROUP_PULL_TPS.to_string()),
+            topic_and_group_pull_rt: StatsItemSet::new(TOPIC_AND_GROUP_PULL_RT.to_string()),
+        }
+    }
+
+    pub fn inc_pull_rt(&self, group: &str, topic: &str, rt: u64) {
+        self.topic_and_group_pull_rt
+            .add_value(&stats_key(topic, group), rt, 1);
+    }
+
+    pub fn inc_pull_tps(&self, group: &str, topic: &str, msgs: u64) {
+        self.topic_and_group_pull_tps
+            .add_value(&stats_key(topic, group), msgs, 1);
+    }
+
+    pub fn inc_consume_rt(&self, group: &str, topic: &str, rt: u64) {
+        self.topic_and_group_consume_rt
+            .add_value(&stats_key(topic, group), rt, 1);
+    }
+
+    pub fn inc_consume_ok_tps(&self, group: &str, topic: &str, msgs: u64) {
+        self.topic_and_group_consume_ok_tps
+            .add_value(&stats_key(topic, group), msgs, 1);
+    }
+
+    pub fn inc_consume_failed_tps(&self, group: &str, topic: &str, msgs: u64) {
+        self.topic_and_group_consume_failed_tps
+            .add_value(&stats_key(topic, group), msgs, 1);
+    }
+
+    /// Builds the status of `topic@group` from the last minute, falling back to the last hour
+    /// for the consume RT when no message was consumed within the minute.
+    pub fn consume_status(&self, group: &str, topic: &str) -> ConsumeStatus {
+        let key = stats_key(topic, group);
+        let mut consume_rt = self
+            .topic_and_group_consume_rt
+            .get_stats_data_in_minute(&key)
+            .get_avgpt();
+        if consume_rt == 0.0 {
+            consume_rt = self
+                .topic_and_group_consume_rt
+                .get_stats_data_in_hour(&key)
+                .get_avgpt();
+        }
+        ConsumeStatus {
+            pull_rt: self
+                .topic_and_group_pull_rt
+                .get_stats_data_in_minute(&key)
+                .get_avgpt(),
+            pull_tps: self
+                .topic_and_group_pull_tps
+                .get_stats_data_in_minute(&key)
+                .get_tps(),
+            consume_rt,
+            consume_ok_tps: self
+                .topic_and_group_consume_ok_tps
+                .get_stats_data_in_minute(&key)
+                .get_tps(),
+            consume_failed_tps: self
+                .topic_and_group_consume_failed_tps
+                .get_stats_data_in_minute(&key)
+                .get_tps(),
+            consume_failed_msgs: self
+                .topic_and_group_consume_failed_tps
+                .get_stats_data_in_hour(&key)
+                .get_sum() as i64,
+        }
+    }
+
+    /// Takes a sample for the per-minute statistics of every set.
+    pub fn sampling_in_seconds(&self) {
+        self.for_each_set(StatsItemSet::sampling_in_seconds);
+    }
+
+    /// Takes a sample for the per-hour statistics of every set.
+    pub fn sampling_in_minutes(&self) {
+        self.for_each_set(StatsItemSet::sampling_in_minutes);
+    }
+
+    fn for_each_set(&self, f: impl Fn(&StatsItemSet)) {
+        [
+            &self.topic_and_group_consume_ok_tps,
+            &self.topic_and_group_consume_failed_tps,
+            &self.topic_and_group_consume_rt,
+            &self.topic_and_group_pull_tps,
+            &self.topic_and_group_pull_rt,
+        ]
+        .into_iter()
+        .for_each(f);
+    }
+}
+
+#[inline]
+fn stats_key(topic: &str, group: &str) -> String {
+    format!("{topic}@{group}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consume_status_is_computed_from_samples() {
+        let manager = ConsumerStatsManager::new();
+        manager.inc_consume_ok_tps("group", "topic", 30);
+        manager.inc_consume_failed_tps("group", "topic", 10);
+        manager.inc_consume_rt("group", "topic", 20);
+        manager.inc_consume_rt("group", "topic", 40);
+        manager.inc_pull_tps("group", "topic", 50);
+        manager.inc_pull_rt("group", "topic", 5);
+        manager.sampling_in_seconds();
+        manager.sampling_in_minutes();
+
+        let status = manager.consume_status("group", "topic");
+        assert_eq!(status.consume_ok_tps, 3.0);
+        assert_eq!(status.consume_failed_tps, 1.0);
+        assert_eq!(status.consume_rt, 30.0);
+        assert_eq!(status.pull_tps, 5.0);
+        assert_eq!(status.pull_rt, 5.0);
+        assert_eq!(status.consume_failed_msgs, 10);
+
+        // Nothing consumed within the last minute window: the RT falls back to the hour.
+        for _ in 0..7 {
+            manager.sampling_in_seconds();
+        }
+        let status = manager.consume_status("group", "topic");
+        assert_eq!(status.consume_ok_tps, 0.0);
+        assert_eq!(status.consume_rt, 30.0);
+
+        let other = manager.consume_status("other_group", "topic");
+        assert_eq!(other.consume_ok_tps, 0.0);
+        assert_eq!(other.consume_rt, 0.0);
+    }
+}
@@ -15,19 +15,94 @@
  * limitations under the License.
  */
 use std::any::Any;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
 
 use cheetah_string::CheetahString;
+use rocketmq_common::common::message::message_single::Message;
+use rocketmq_common::common::message::MessageTrait;
+use rocketmq_common::TimeUtils::get_current_millis;
 use rocketmq_remoting::runtime::RPCHook;
 use rocketmq_rust::ArcMut;
+use tokio::sync::mpsc;
+use tokio::sync::Notify;
+use tracing::error;
+use tracing::info;
+use tracing::warn;
 
 use crate::base::access_channel::AccessChannel;
+use crate::base::client_config::ClientConfig;
 use crate::consumer::consumer_impl::default_mq_push_consumer_impl::DefaultMQPushConsumerImpl;
+use crate::producer::default_mq_producer::DefaultMQProducer;
+use crate::producer::mq_producer::MQProducer;
 use crate::producer::producer_impl::default_mq_producer_impl::DefaultMQProducerImpl;
+use crate::trace::trace_constants::TraceConstants;
+use crate::trace::trace_context::TraceContext;
+use crate::trace::trace_data_encoder::TraceDataEncoder;
 use crate::trace::trace_dispatcher::TraceDispatcher;
 use crate::trace::trace_dispatcher::Type;
+use crate::trace::trace_transfer_bean::TraceTransferBean;
 
-pub struct AsyncTraceDispatcher;
+const DEFAULT_QUEUE_SIZE: usize = 2048;
+const DEFAULT_BATCH_SIZE: usize = 100;
+const DEFAULT_MAX_MSG_SIZE: usize = 128000;
+const DEFAULT_POLLING_TIME_MILLIS: u64 = 100;
+const DEFAULT_WAIT_TIME_THRESHOLD_MILLIS: u64 = 500;
+const TRACE_SEND_TIMEOUT_MILLIS: u32 = 5000;
+
+/// Counters of the trace records handled by an [`AsyncTraceDispatcher`].
+#[derive(Debug, Default)]
+pub struct TraceDispatcherStats {
+    discard_count: AtomicU64,
+    send_failed_count: AtomicU64,
+    sent_count: AtomicU64,
+}
+
+impl TraceDispatcherStats {
+    /// Records dropped because the queue of the dispatcher was full.
+    pub fn discard_count(&self) -> u64 {
+        self.discard_count.load(Ordering::Relaxed)
+    }
+
+    /// Records dropped because the trace message holding them could not be sent.
+    pub fn send_failed_count(&self) -> u64 {
+        self.send_failed_count.load(Ordering::Relaxed)
+    }
+
+    pub fn sent_count(&self) -> u64 {
+        self.sent_count.load(Ordering::Relaxed)
+    }
+}
+
+/// Collects trace contexts from the trace hooks and sends them to the trace topic in batches.
+///
+/// Contexts are appended to a bounded queue without blocking the traced client, a full queue
+/// drops the context. A background task encodes the contexts with [`TraceDataEncoder`], packs
+/// them per topic and region, and sends each batch through an internal producer once it holds
+/// `batch_size` records or `max_msg_size` bytes, or has waited for `wait_time_threshold_millis`.
+pub struct AsyncTraceDispatcher {
+    group: CheetahString,
+    type_: Type,
+    trace_topic_name: CheetahString,
+    rpc_hook: Option<Arc<Box<dyn RPCHook>>>,
+    batch_size: usize,
+    max_msg_size: usize,
+    polling_time_millis: u64,
+    wait_time_threshold_millis: u64,
+    host_producer: Option<ArcMut<DefaultMQProducerImpl>>,
+    host_consumer: Option<ArcMut<DefaultMQPushConsumerImpl>>,
+    namespace_v2: Option<CheetahString>,
+    started: AtomicBool,
+    trace_context_sender: parking_lot::Mutex<Option<mpsc::Sender<TraceContext>>>,
+    trace_context_receiver: parking_lot::Mutex<Option<mpsc::Receiver<TraceContext>>>,
+    flush_notify: Arc<Notify>,
+    stats: Arc<TraceDispatcherStats>,
+}
 
 impl AsyncTraceDispatcher {
     pub fn new(
@@ -36,7 +111,73 @@ impl AsyncTraceDispatcher {
         trace_topic_name: &str,
         rpc_hook: Option<Arc<Box<dyn RPCHook>>>,
     ) -> Self {
-        AsyncTraceDispatcher
+        Self::with_queue_size(group, type_, trace_topic_name, rpc_hook, DEFAULT_QUEUE_SIZE)
+    }
+
+    pub fn with_queue_size(
+        group: &str,
+        type_: Type,
+        trace_topic_name: &str,
+        rpc_hook: Option<Arc<Box<dyn RPCHook>>>,
+        queue_size: usize,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel(queue_size.max(1));
+        AsyncTraceDispatcher {
+            group: CheetahString::from_slice(group),
+            type_,
+            trace_topic_name: CheetahString::from_slice(trace_topic_name),
+            rpc_hook,
+            batch_size: DEFAULT_BATCH_SIZE,
+            max_msg_size: DEFAULT_MAX_MSG_SIZE,
+            polling_time_millis: DEFAULT_POLLING_TIME_MILLIS,
+            wait_time_threshold_millis: DEFAULT_WAIT_TIME_THRESHOLD_MILLIS,
+            host_producer: None,
+            host_consumer: None,
+            namespace_v2: None,
+            started: AtomicBool::new(false),
+            trace_context_sender: parking_lot::Mutex::new(Some(sender)),
+            trace_context_receiver: parking_lot::Mutex::new(Some(receiver)),
+            flush_notify: Arc::new(Notify::new()),
+            stats: Arc::new(TraceDispatcherStats::default()),
+        }
+    }
+
+    fn trace_producer_group(&self) -> String {
+        let role = match self.type_ {
+            Type::Produce => "PRODUCER",
+            Type::Consume => "CONSUMER",
+        };
+        format!(
+            "{}-{}-{}",
+            TraceConstants::GROUP_NAME_PREFIX,
+            role,
+            self.group
+        )
+    }
+
+    fn create_trace_producer(
+        &self,
+        name_srv_addr: &str,
+        access_channel: AccessChannel,
+    ) -> DefaultMQProducer {
+        let mut client_config = ClientConfig::new();
+        client_config.set_namesrv_addr(CheetahString::from_slice(name_srv_addr));
+        client_config.set_instance_name(CheetahString::from_string(format!(
+            "{}_{}",
+            TraceConstants::TRACE_INSTANCE_NAME,
+            name_srv_addr
+        )));
+        client_config.namespace_v2 = self.namespace_v2.clone();
+        client_config.access_channel = access_channel;
+        client_config.vip_channel_enabled = false;
+        let mut producer = DefaultMQProducer::builder()
+            .client_config(client_config)
+            .producer_group(self.trace_producer_group())
+            .send_msg_timeout(TRACE_SEND_TIMEOUT_MILLIS)
+            .max_message_size(self.max_msg_size as u32)
+            .build();
+        producer.set_rpc_hook(self.rpc_hook.clone());
+        producer
     }
 }
 
@@ -46,19 +187,65 @@ impl TraceDispatcher for AsyncTraceDispatcher {
         name_srv_addr: &str,
         access_channel: AccessChannel,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        if self.started.swap(true, Ordering::AcqRel) {
+            return Ok(());
+        }
+        let Some(receiver) = self.trace_context_receiver.lock().take() else {
+            return Ok(());
+        };
+        let worker = TraceWorker {
+            receiver,
+            trace_producer: self.create_trace_producer(name_srv_addr, access_channel),
+            trace_topic_name: self.trace_topic_name.clone(),
+            access_channel,
+            batch_size: self.batch_size,
+            max_msg_size: self.max_msg_size,
+            polling_time_millis: self.polling_time_millis,
+            wait_time_threshold_millis: self.wait_time_threshold_millis,
+            flush_notify: self.flush_notify.clone(),
+            stats: self.stats.clone(),
+            segments: HashMap::new(),
+        };
+        tokio::spawn(worker.run());
+        Ok(())
     }
 
     fn append(&self, ctx: &dyn Any) -> bool {
-        todo!()
+        let Some(context) = ctx.downcast_ref::<TraceContext>() else {
+            return false;
+        };
+        let sender = self.trace_context_sender.lock();
+        let Some(sender) = sender.as_ref() else {
+            return false;
+        };
+        match sender.try_send(context.clone()) {
+            Ok(()) => true,
+            Err(_) => {
+                let discarded = self.stats.discard_count.fetch_add(1, Ordering::Relaxed) + 1;
+                if discarded % 1000 == 1 {
+                    warn!(
+                        "trace context queue of group {} is full, {} trace contexts discarded so \
+                         far",
+                        self.group, discarded
+                    );
+                }
+                false
+            }
+        }
     }
 
+    /// Asks the background task to send the batches it holds without waiting for them to fill.
     fn flush(&self) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.flush_notify.notify_one();
+        Ok(())
     }
 
+    /// Stops accepting trace contexts. The background task sends what is left and then shuts
+    /// the internal producer down.
     fn shutdown(&self) {
-        todo!()
+        if self.trace_context_sender.lock().take().is_some() {
+            self.flush_notify.notify_one();
+        }
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -71,8 +258,276 @@ impl TraceDispatcher for AsyncTraceDispatcher {
 }
 
 impl AsyncTraceDispatcher {
-    pub fn set_host_producer(&mut self, host_producer: ArcMut<DefaultMQProducerImpl>) {}
-    pub fn set_host_consumer(&mut self, host_producer: ArcMut<DefaultMQPushConsumerImpl>) {}
+    pub(crate) fn set_host_producer(&mut self, host_producer: ArcMut<DefaultMQProducerImpl>) {
+        self.host_producer = Some(host_producer);
+    }
+
+    pub(crate) fn set_host_consumer(&mut self, host_consumer: ArcMut<DefaultMQPushConsumerImpl>) {
+        self.host_consumer = Some(host_consumer);
+    }
+
+    pub fn set_namespace_v2(&mut self, namespace_v2: Option<CheetahString>) {
+        self.namespace_v2 = namespace_v2;
+    }
+
+    pub fn set_batch_size(&mut self, batch_size: usize) {
+        self.batch_size = batch_size;
+    }
+
+    pub fn set_max_msg_size(&mut self, max_msg_size: usize) {
+        self.max_msg_size = max_msg_size;
+    }
+
+    pub fn set_wait_time_threshold_millis(&mut self, wait_time_threshold_millis: u64) {
+        self.wait_time_threshold_millis = wait_time_threshold_millis;
+    }
 
-    pub fn set_namespace_v2(&mut self, namespace_v2: Option<CheetahString>) {}
+    pub fn trace_topic_name(&self) -> &CheetahString {
+        &self.trace_topic_name
+    }
+
+    pub fn stats(&self) -> &TraceDispatcherStats {
+        &self.stats
+    }
+}
+
+/// Trace records waiting to be sent in one trace message.
+#[derive(Default)]
+struct TraceDataSegment {
+    region_id: CheetahString,
+    trace_transfer_beans: Vec<TraceTransferBean>,
+    current_msg_size: usize,
+    first_bean_add_time: u64,
+}
+
+impl TraceDataSegment {
+    fn add(&mut self, trace_transfer_bean: TraceTransferBean) {
+        if self.trace_transfer_beans.is_empty() {
+            self.first_bean_add_time = get_current_millis();
+        }
+        self.current_msg_size += trace_transfer_bean.trans_data.len();
+        self.trace_transfer_beans.push(trace_transfer_bean);
+    }
+
+    fn is_full(&self, batch_size: usize, max_msg_size: usize) -> bool {
+        self.trace_transfer_beans.len() >= batch_size || self.current_msg_size >= max_msg_size
+    }
+
+    fn is_expired(&self, wait_time_threshold_millis: u64) -> bool {
+        !self.trace_transfer_beans.is_empty()
+            && get_current_millis() >= self.first_bean_add_time + wait_time_threshold_millis
+    }
+
+    /// Takes the held records as the body and keys of one trace message.
+    fn take(&mut self) -> (String, HashSet<CheetahString>, usize) {
+        let beans = std::mem::take(&mut self.trace_transfer_beans);
+        self.current_msg_size = 0;
+        let count = beans.len();
+        let mut data = String::new();
+        let mut keys = HashSet::new();
+        for bean in beans {
+            data.push_str(&bean.trans_data);
+            keys.extend(bean.trans_key);
+        }
+        (data, keys, count)
+    }
+}
+
+struct TraceWorker {
+    receiver: mpsc::Receiver<TraceContext>,
+    trace_producer: DefaultMQProducer,
+    trace_topic_name: CheetahString,
+    access_channel: AccessChannel,
+    batch_size: usize,
+    max_msg_size: usize,
+    polling_time_millis: u64,
+    wait_time_threshold_millis: u64,
+    flush_notify: Arc<Notify>,
+    stats: Arc<TraceDispatcherStats>,
+    segments: HashMap<CheetahString, TraceDataSegment>,
+}
+
+impl TraceWorker {
+    async fn run(mut self) {
+        if let Err(e) = self.trace_producer.start().await {
+            error!(
+                "start trace producer failed, trace data will be dropped: {}",
+                e
+            );
+        }
+        let polling_time = Duration::from_millis(self.polling_time_millis);
+        loop {
+            tokio::select! {
+                context = self.receiver.recv() => match context {
+                    Some(context) => self.add(context).await,
+                    None => break,
+                },
+                _ = self.flush_notify.notified() => self.send_segments(true).await,
+                _ = tokio::time::sleep(polling_time) => {}
+            }
+            self.send_segments(false).await;
+        }
+        self.send_segments(true).await;
+        self.trace_producer.shutdown().await;
+        info!("trace dispatcher of {} stopped", self.trace_topic_name);
+    }
+
+    async fn add(&mut self, context: TraceContext) {
+        let Some(trace_transfer_bean) = TraceDataEncoder::encoder_from_context_bean(&context)
+        else {
+            return;
+        };
+        let topic = context
+            .trace_beans
+            .as_ref()
+            .and_then(|beans| beans.first())
+            .map(|bean| bean.topic.as_str())
+            .unwrap_or_default();
+        let key = CheetahString::from_string(format!(
+            "{}{}{}",
+            topic,
+            TraceConstants::CONTENT_SPLITOR,
+            context.region_id
+        ));
+        let segment = self
+            .segments
+            .entry(key)
+            .or_insert_with(|| TraceDataSegment {
+                region_id: context.region_id.clone(),
+                ..Default::default()
+            });
+        segment.add(trace_transfer_bean);
+        if segment.is_full(self.batch_size, self.max_msg_size) {
+            let (data, keys, count) = segment.take();
+            let region_id = segment.region_id.clone();
+            self.send_trace_data(&region_id, data, keys, count).await;
+        }
+    }
+
+    async fn send_segments(&mut self, force: bool) {
+        let mut batches = Vec::new();
+        for segment in self.segments.values_mut() {
+            if segment.trace_transfer_beans.is_empty()
+                || !(force || segment.is_expired(self.wait_time_threshold_millis))
+            {
+                continue;
+            }
+            let (data, keys, count) = segment.take();
+            batches.push((segment.region_id.clone(), data, keys, count));
+        }
+        for (region_id, data, keys, count) in batches {
+            self.send_trace_data(&region_id, data, keys, count).await;
+        }
+    }
+
+    async fn send_trace_data(
+        &mut self,
+        region_id: &CheetahString,
+        data: String,
+        keys: HashSet<CheetahString>,
+        count: usize,
+    ) {
+        let trace_topic = match self.access_channel {
+            AccessChannel::Cloud => CheetahString::from_string(format!(
+                "{}{}",
+                TraceConstants::TRACE_TOPIC_PREFIX,
+                region_id
+            )),
+            AccessChannel::Local => self.trace_topic_name.clone(),
+        };
+        let mut message = Message::new(trace_topic, data.as_bytes());
+        message.set_keys_from_collection(keys.into_iter().map(|key| key.to_string()).collect());
+        let stats = self.stats.clone();
+        let result = self
+            .trace_producer
+            .send_with_callback(message, move |send_result, e| {
+                if let (Some(_), None) = (send_result, e) {
+                    stats.sent_count.fetch_add(count as u64, Ordering::Relaxed);
+                } else {
+                    stats
+                        .send_failed_count
+                        .fetch_add(count as u64, Ordering::Relaxed);
+                    warn!(
+                        "send {} trace records failed: {}",
+                        count,
+                        e.map_or(String::new(), |e| e.to_string())
+                    );
+                }
+            })
+            .await;
+        if let Err(e) = result {
+            self.stats
+                .send_failed_count
+                .fetch_add(count as u64, Ordering::Relaxed);
+            warn!("send {} trace records failed: {}", count, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::trace_bean::TraceBean;
+    use crate::trace::trace_type::TraceType;
+
+    fn trace_context(msg_id: &str) -> TraceContext {
+        TraceContext {
+            trace_type: Some(TraceType::Pub),
+            region_id: CheetahString::from("DefaultRegion"),
+            trace_beans: Some(vec![TraceBean {
+                topic: CheetahString::from("TopicTest"),
+                msg_id: CheetahString::from(msg_id),
+                ..Default::default()
+            }]),
+            ..TraceContext::new()
+        }
+    }
+
+    #[test]
+    fn append_drops_contexts_when_queue_is_full() {
+        let dispatcher = AsyncTraceDispatcher::with_queue_size(
+            "group",
+            Type::Produce,
+            "RMQ_SYS_TRACE_TOPIC",
+            None,
+            2,
+        );
+        assert!(dispatcher.append(&trace_context("1")));
+        assert!(dispatcher.append(&trace_context("2")));
+        assert!(!dispatcher.append(&trace_context("3")));
+        assert!(!dispatcher.append(&"not a trace context"));
+        assert_eq!(dispatcher.stats().discard_count(), 1);
+
+        dispatcher.shutdown();
+        assert!(!dispatcher.append(&trace_context("4")));
+        assert_eq!(dispatcher.stats().discard_count(), 1);
+        assert_eq!(
+            dispatcher.trace_producer_group(),
+            "_INNER_TRACE_PRODUCER-PRODUCER-group"
+        );
+    }
+
+    #[test]
+    fn segment_packs_records_until_full() {
+        let mut segment = TraceDataSegment::default();
+        for msg_id in ["1", "2"] {
+            segment
+                .add(TraceDataEncoder::encoder_from_context_bean(&trace_context(msg_id)).unwrap());
+        }
+        assert!(!segment.is_full(3, DEFAULT_MAX_MSG_SIZE));
+        assert!(segment.is_full(2, DEFAULT_MAX_MSG_SIZE));
+        assert!(segment.is_full(3, 10));
+        assert!(!segment.is_expired(60_000));
+        assert!(segment.is_expired(0));
+
+        let (data, keys, count) = segment.take();
+        assert_eq!(count, 2);
+        assert_eq!(keys.len(), 2);
+        assert_eq!(
+            TraceDataEncoder::decoder_from_trace_data_string(&data).len(),
+            2
+        );
+        assert!(segment.trace_transfer_beans.is_empty());
+        assert!(!segment.is_expired(0));
+    }
 }
@@ -17,3 +17,17 @@
 pub mod consume_message_trace_hook_impl;
 pub mod end_transaction_trace_hook_impl;
 pub mod send_message_trace_hook_impl;
+
+use crate::trace::async_trace_dispatcher::AsyncTraceDispatcher;
+use crate::trace::trace_dispatcher::TraceDispatcher;
+
+/// Messages sent to the trace topic are never traced themselves.
+pub(crate) fn is_trace_topic(
+    trace_dispatcher: &(dyn TraceDispatcher + Send + Sync),
+    topic: &str,
+) -> bool {
+    trace_dispatcher
+        .as_any()
+        .downcast_ref::<AsyncTraceDispatcher>()
+        .is_some_and(|dispatcher| topic.starts_with(dispatcher.trace_topic_name().as_str()))
+}
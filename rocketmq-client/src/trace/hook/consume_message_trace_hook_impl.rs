@@ -16,9 +16,21 @@
  */
 use std::sync::Arc;
 
+use cheetah_string::CheetahString;
+use rocketmq_common::common::message::MessageConst;
+use rocketmq_common::common::message::MessageTrait;
+use rocketmq_common::common::mix_all;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_remoting::protocol::namespace_util::NamespaceUtil;
+
+use crate::consumer::listener::consume_return_type::ConsumeReturnType;
 use crate::hook::consume_message_context::ConsumeMessageContext;
 use crate::hook::consume_message_hook::ConsumeMessageHook;
+use crate::trace::hook::is_trace_topic;
+use crate::trace::trace_bean::TraceBean;
+use crate::trace::trace_context::TraceContext;
 use crate::trace::trace_dispatcher::TraceDispatcher;
+use crate::trace::trace_type::TraceType;
 
 pub struct ConsumeMessageTraceHookImpl {
     trace_dispatcher: Arc<Box<dyn TraceDispatcher + Send + Sync>>,
@@ -32,14 +44,103 @@ impl ConsumeMessageTraceHookImpl {
 
 impl ConsumeMessageHook for ConsumeMessageTraceHookImpl {
     fn hook_name(&self) -> &str {
-        todo!()
+        "ConsumeMessageTraceHook"
     }
 
     fn consume_message_before(&self, context: Option<&mut ConsumeMessageContext>) {
-        todo!()
+        let Some(context) = context else {
+            return;
+        };
+        let mut region_id = CheetahString::new();
+        let mut trace_beans = Vec::with_capacity(context.msg_list.len());
+        for message in context.msg_list {
+            if is_trace_topic(&**self.trace_dispatcher, message.get_topic()) {
+                continue;
+            }
+            if let Some(region) = message.get_property(&CheetahString::from_static_str(
+                MessageConst::PROPERTY_MSG_REGION,
+            )) {
+                region_id = region;
+            }
+            // the broker which stored the message switched tracing off
+            if message
+                .get_property(&CheetahString::from_static_str(
+                    MessageConst::PROPERTY_TRACE_SWITCH,
+                ))
+                .is_some_and(|trace_on| trace_on == "false")
+            {
+                continue;
+            }
+            trace_beans.push(TraceBean {
+                topic: NamespaceUtil::without_namespace(message.get_topic()).into(),
+                msg_id: message.msg_id().clone(),
+                tags: message.get_tags().unwrap_or_default(),
+                keys: message.get_keys().unwrap_or_default(),
+                store_time: message.store_timestamp(),
+                body_length: message.store_size(),
+                store_host: message.store_host().to_string().into(),
+                retry_times: message.reconsume_times(),
+                ..Default::default()
+            });
+        }
+        if trace_beans.is_empty() {
+            return;
+        }
+        let trace_context = TraceContext {
+            trace_type: Some(TraceType::SubBefore),
+            group_name: NamespaceUtil::without_namespace(&context.consumer_group).into(),
+            region_id,
+            trace_beans: Some(trace_beans),
+            ..TraceContext::new()
+        };
+        self.trace_dispatcher.append(&trace_context);
+        context.mq_trace_context = Some(Arc::new(Box::new(trace_context)));
     }
 
     fn consume_message_after(&self, context: Option<&mut ConsumeMessageContext>) {
-        todo!()
+        let Some(context) = context else {
+            return;
+        };
+        if context.msg_list.is_empty() {
+            return;
+        }
+        let Some(sub_before_context) = context
+            .mq_trace_context
+            .as_ref()
+            .and_then(|trace_context| trace_context.downcast_ref::<TraceContext>())
+        else {
+            return;
+        };
+        let cost_time = get_current_millis().saturating_sub(sub_before_context.time_stamp)
+            / context.msg_list.len() as u64;
+        let context_code = context
+            .props
+            .get(mix_all::CONSUME_CONTEXT_TYPE)
+            .and_then(|return_type| consume_return_type_of_name(return_type))
+            .map_or(0, i32::from);
+        let trace_context = TraceContext {
+            trace_type: Some(TraceType::SubAfter),
+            region_id: sub_before_context.region_id.clone(),
+            group_name: sub_before_context.group_name.clone(),
+            request_id: sub_before_context.request_id.clone(),
+            is_success: context.success,
+            cost_time: cost_time as i32,
+            context_code,
+            access_channel: context.access_channel,
+            trace_beans: sub_before_context.trace_beans.clone(),
+            ..TraceContext::new()
+        };
+        self.trace_dispatcher.append(&trace_context);
+    }
+}
+
+fn consume_return_type_of_name(name: &str) -> Option<ConsumeReturnType> {
+    match name {
+        "SUCCESS" => Some(ConsumeReturnType::Success),
+        "TIME_OUT" => Some(ConsumeReturnType::TimeOut),
+        "EXCEPTION" => Some(ConsumeReturnType::Exception),
+        "RETURN_NULL" => Some(ConsumeReturnType::ReturnNull),
+        "FAILED" => Some(ConsumeReturnType::Failed),
+        _ => None,
     }
 }
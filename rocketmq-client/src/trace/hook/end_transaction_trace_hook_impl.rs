@@ -16,9 +16,17 @@
  */
 use std::sync::Arc;
 
+use rocketmq_common::common::message::message_enum::MessageType;
+use rocketmq_common::common::message::MessageTrait;
+use rocketmq_remoting::protocol::namespace_util::NamespaceUtil;
+
 use crate::hook::end_transaction_context::EndTransactionContext;
 use crate::hook::end_transaction_hook::EndTransactionHook;
+use crate::trace::hook::is_trace_topic;
+use crate::trace::trace_bean::TraceBean;
+use crate::trace::trace_context::TraceContext;
 use crate::trace::trace_dispatcher::TraceDispatcher;
+use crate::trace::trace_type::TraceType;
 
 pub struct EndTransactionTraceHookImpl {
     trace_dispatcher: Arc<Box<dyn TraceDispatcher + Send + Sync>>,
@@ -36,6 +44,29 @@ impl EndTransactionHook for EndTransactionTraceHookImpl {
     }
 
     fn end_transaction(&self, context: &EndTransactionContext) {
-        todo!()
+        let message = context.message;
+        if is_trace_topic(&**self.trace_dispatcher, message.get_topic()) {
+            return;
+        }
+        let trace_bean = TraceBean {
+            topic: NamespaceUtil::without_namespace(message.get_topic()).into(),
+            msg_id: context.msg_id.clone(),
+            tags: message.get_tags().unwrap_or_default(),
+            keys: message.get_keys().unwrap_or_default(),
+            store_host: context.broker_addr.clone(),
+            body_length: message.get_body().map_or(0, |body| body.len() as i32),
+            msg_type: Some(MessageType::TransMsgCommit),
+            transaction_id: Some(context.transaction_id.clone()),
+            transaction_state: Some(context.transaction_state),
+            from_transaction_check: context.from_transaction_check,
+            ..Default::default()
+        };
+        let trace_context = TraceContext {
+            trace_type: Some(TraceType::EndTransaction),
+            group_name: NamespaceUtil::without_namespace(&context.producer_group).into(),
+            trace_beans: Some(vec![trace_bean]),
+            ..TraceContext::new()
+        };
+        self.trace_dispatcher.append(&trace_context);
     }
 }
@@ -16,9 +16,18 @@
  */
 use std::sync::Arc;
 
+use cheetah_string::CheetahString;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_remoting::protocol::namespace_util::NamespaceUtil;
+
 use crate::hook::send_message_context::SendMessageContext;
 use crate::hook::send_message_hook::SendMessageHook;
+use crate::producer::send_status::SendStatus;
+use crate::trace::hook::is_trace_topic;
+use crate::trace::trace_bean::TraceBean;
+use crate::trace::trace_context::TraceContext;
 use crate::trace::trace_dispatcher::TraceDispatcher;
+use crate::trace::trace_type::TraceType;
 
 pub struct SendMessageTraceHookImpl {
     trace_dispatcher: Arc<Box<dyn TraceDispatcher + Send + Sync>>,
@@ -31,14 +40,79 @@ impl SendMessageTraceHookImpl {
 }
 impl SendMessageHook for SendMessageTraceHookImpl {
     fn hook_name(&self) -> &str {
-        todo!()
+        "SendMessageTraceHook"
     }
 
-    fn send_message_before(&self, context: &Option<SendMessageContext<'_>>) {
-        todo!()
+    fn send_message_before(&self, context: &mut Option<SendMessageContext<'_>>) {
+        let Some(context) = context.as_mut() else {
+            return;
+        };
+        let Some(message) = context.message.as_ref() else {
+            return;
+        };
+        if is_trace_topic(&**self.trace_dispatcher, message.get_topic()) {
+            return;
+        }
+        let trace_bean = TraceBean {
+            topic: NamespaceUtil::without_namespace(message.get_topic()).into(),
+            tags: message.get_tags().unwrap_or_default(),
+            keys: message.get_keys().unwrap_or_default(),
+            store_host: context.broker_addr.clone().unwrap_or_default(),
+            body_length: message.get_body().map_or(0, |body| body.len() as i32),
+            msg_type: context.msg_type,
+            ..Default::default()
+        };
+        let trace_context = TraceContext {
+            trace_type: Some(TraceType::Pub),
+            group_name: NamespaceUtil::without_namespace(
+                context.producer_group.as_deref().unwrap_or_default(),
+            )
+            .into(),
+            trace_beans: Some(vec![trace_bean]),
+            ..TraceContext::new()
+        };
+        context.mq_trace_context = Some(Arc::new(Box::new(trace_context)));
     }
 
     fn send_message_after(&self, context: &Option<SendMessageContext<'_>>) {
-        todo!()
+        let Some(context) = context.as_ref() else {
+            return;
+        };
+        // failed and asynchronous sends have no result here, they are not traced
+        let (Some(trace_context), Some(send_result)) = (
+            context
+                .mq_trace_context
+                .as_ref()
+                .and_then(|trace_context| trace_context.downcast_ref::<TraceContext>()),
+            context.send_result.as_ref(),
+        ) else {
+            return;
+        };
+        let Some(region_id) = send_result.region_id.as_ref() else {
+            return;
+        };
+        if !send_result.trace_on {
+            return;
+        }
+
+        let mut trace_context = trace_context.clone();
+        let cost_time = get_current_millis().saturating_sub(trace_context.time_stamp);
+        trace_context.cost_time = cost_time as i32;
+        trace_context.region_id = CheetahString::from_slice(region_id);
+        trace_context.is_success = send_result.send_status == SendStatus::SendOk;
+        if let Some(trace_bean) = trace_context
+            .trace_beans
+            .as_mut()
+            .and_then(|beans| beans.first_mut())
+        {
+            trace_bean.msg_id = send_result.msg_id.clone().unwrap_or_default();
+            trace_bean.offset_msg_id = send_result
+                .offset_msg_id
+                .as_deref()
+                .map(CheetahString::from_slice)
+                .unwrap_or_default();
+            trace_bean.store_time = (trace_context.time_stamp + cost_time / 2) as i64;
+        }
+        self.trace_dispatcher.append(&trace_context);
     }
 }
@@ -0,0 +1,466 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use cheetah_string::CheetahString;
+use rocketmq_common::common::message::message_enum::MessageType;
+use rocketmq_common::common::message::MessageConst;
+use tracing::warn;
+
+use crate::producer::local_transaction_state::LocalTransactionState;
+use crate::trace::trace_bean::TraceBean;
+use crate::trace::trace_constants::TraceConstants;
+use crate::trace::trace_context::TraceContext;
+use crate::trace::trace_transfer_bean::TraceTransferBean;
+use crate::trace::trace_type::TraceType;
+
+/// Codec between trace contexts and the text stored in the trace topic.
+///
+/// A trace message body holds one or more records terminated by
+/// [`TraceConstants::FIELD_SPLITOR`], each record being a list of fields separated by
+/// [`TraceConstants::CONTENT_SPLITOR`] and starting with the trace type name.
+pub struct TraceDataEncoder;
+
+impl TraceDataEncoder {
+    /// Decodes all trace records of a trace message body, skipping malformed records.
+    pub fn decoder_from_trace_data_string(trace_data: &str) -> Vec<TraceContext> {
+        trace_data
+            .split(TraceConstants::FIELD_SPLITOR)
+            .filter(|record| !record.is_empty())
+            .filter_map(|record| {
+                let context = Self::decode_record(record);
+                if context.is_none() {
+                    warn!("skip malformed trace record: {:?}", record);
+                }
+                context
+            })
+            .collect()
+    }
+
+    /// Encodes a trace context into the records stored in the trace topic. Contexts without a
+    /// trace type or trace beans have nothing to encode.
+    pub fn encoder_from_context_bean(context: &TraceContext) -> Option<TraceTransferBean> {
+        let trace_type = context.trace_type?;
+        let beans = context
+            .trace_beans
+            .as_ref()
+            .filter(|beans| !beans.is_empty())?;
+        let mut record = Record::default();
+        match trace_type {
+            TraceType::Pub => {
+                let bean = &beans[0];
+                record
+                    .field(trace_type)
+                    .field(context.time_stamp)
+                    .field(&context.region_id)
+                    .field(&context.group_name)
+                    .field(&bean.topic)
+                    .field(&bean.msg_id)
+                    .field(&bean.tags)
+                    .field(&bean.keys)
+                    .field(&bean.store_host)
+                    .field(bean.body_length)
+                    .field(context.cost_time)
+                    .field(message_type_ordinal(bean.msg_type))
+                    .field(&bean.offset_msg_id)
+                    .field(context.is_success)
+                    .last_field(&bean.client_host);
+            }
+            TraceType::SubBefore => {
+                for bean in beans {
+                    record
+                        .field(trace_type)
+                        .field(context.time_stamp)
+                        .field(&context.region_id)
+                        .field(&context.group_name)
+                        .field(&context.request_id)
+                        .field(&bean.msg_id)
+                        .field(bean.retry_times)
+                        .field(&bean.keys)
+                        .last_field(&bean.client_host);
+                }
+            }
+            TraceType::SubAfter => {
+                for bean in beans {
+                    record
+                        .field(trace_type)
+                        .field(&context.request_id)
+                        .field(&bean.msg_id)
+                        .field(context.cost_time)
+                        .field(context.is_success)
+                        .field(&bean.keys)
+                        .field(context.context_code)
+                        .field(context.time_stamp)
+                        .last_field(&context.group_name);
+                }
+            }
+            TraceType::EndTransaction => {
+                let bean = &beans[0];
+                record
+                    .field(trace_type)
+                    .field(context.time_stamp)
+                    .field(&context.region_id)
+                    .field(&context.group_name)
+                    .field(&bean.topic)
+                    .field(&bean.msg_id)
+                    .field(&bean.tags)
+                    .field(&bean.keys)
+                    .field(&bean.store_host)
+                    .field(message_type_ordinal(bean.msg_type))
+                    .field(bean.transaction_id.as_deref().unwrap_or_default())
+                    .field(bean.transaction_state.unwrap_or_default())
+                    .last_field(bean.from_transaction_check);
+            }
+        }
+
+        let mut trans_key = HashSet::new();
+        for bean in beans {
+            trans_key.insert(bean.msg_id.clone());
+            trans_key.extend(
+                bean.keys
+                    .split(MessageConst::KEY_SEPARATOR)
+                    .filter(|key| !key.is_empty())
+                    .map(CheetahString::from_slice),
+            );
+        }
+        Some(TraceTransferBean {
+            trans_data: record.data,
+            trans_key,
+        })
+    }
+
+    fn decode_record(record: &str) -> Option<TraceContext> {
+        let mut line: Vec<&str> = record.split(TraceConstants::CONTENT_SPLITOR).collect();
+        // trailing empty fields are dropped, matching the Java client which produced the data
+        while line.last().is_some_and(|field| field.is_empty()) {
+            line.pop();
+        }
+        let field =
+            |index: usize| CheetahString::from_slice(line.get(index).copied().unwrap_or(""));
+        let mut context = TraceContext::default();
+        let mut bean = TraceBean::default();
+        match *line.first()? {
+            "Pub" => {
+                context.trace_type = Some(TraceType::Pub);
+                context.time_stamp = parse(&line, 1)?;
+                context.region_id = field(2);
+                context.group_name = field(3);
+                bean.topic = field(4);
+                bean.msg_id = field(5);
+                bean.tags = field(6);
+                bean.keys = field(7);
+                bean.store_host = field(8);
+                bean.body_length = parse(&line, 9)?;
+                context.cost_time = parse(&line, 10)?;
+                bean.msg_type = Some(message_type_of_ordinal(parse(&line, 11)?)?);
+                if line.len() == 13 {
+                    context.is_success = parse(&line, 12)?;
+                } else if line.len() >= 14 {
+                    bean.offset_msg_id = field(12);
+                    context.is_success = parse(&line, 13)?;
+                    if line.len() >= 15 {
+                        bean.client_host = field(14);
+                    }
+                }
+            }
+            "SubBefore" => {
+                context.trace_type = Some(TraceType::SubBefore);
+                context.time_stamp = parse(&line, 1)?;
+                context.region_id = field(2);
+                context.group_name = field(3);
+                context.request_id = field(4);
+                bean.msg_id = field(5);
+                bean.retry_times = parse(&line, 6)?;
+                bean.keys = field(7);
+                if line.len() >= 9 {
+                    bean.client_host = field(8);
+                }
+            }
+            "SubAfter" => {
+                context.trace_type = Some(TraceType::SubAfter);
+                context.request_id = field(1);
+                bean.msg_id = field(2);
+                context.cost_time = parse(&line, 3)?;
+                context.is_success = parse(&line, 4)?;
+                bean.keys = field(5);
+                if line.len() >= 7 {
+                    context.context_code = parse(&line, 6)?;
+                }
+                if line.len() >= 9 {
+                    context.time_stamp = parse(&line, 7)?;
+                    context.group_name = field(8);
+                }
+            }
+            "EndTransaction" => {
+                context.trace_type = Some(TraceType::EndTransaction);
+                context.time_stamp = parse(&line, 1)?;
+                context.region_id = field(2);
+                context.group_name = field(3);
+                bean.topic = field(4);
+                bean.msg_id = field(5);
+                bean.tags = field(6);
+                bean.keys = field(7);
+                bean.store_host = field(8);
+                bean.msg_type = Some(message_type_of_ordinal(parse(&line, 9)?)?);
+                bean.transaction_id = Some(field(10));
+                bean.transaction_state = Some(transaction_state_of_name(line.get(11)?)?);
+                bean.from_transaction_check = parse(&line, 12)?;
+            }
+            _ => return None,
+        }
+        context.trace_beans = Some(vec![bean]);
+        Some(context)
+    }
+}
+
+#[derive(Default)]
+struct Record {
+    data: String,
+}
+
+impl Record {
+    fn field(&mut self, value: impl Display) -> &mut Self {
+        let _ = write!(self.data, "{}{}", value, TraceConstants::CONTENT_SPLITOR);
+        self
+    }
+
+    fn last_field(&mut self, value: impl Display) -> &mut Self {
+        let _ = write!(self.data, "{}{}", value, TraceConstants::FIELD_SPLITOR);
+        self
+    }
+}
+
+fn message_type_ordinal(message_type: Option<MessageType>) -> usize {
+    match message_type.unwrap_or_default() {
+        MessageType::NormalMsg => 0,
+        MessageType::TransMsgHalf => 1,
+        MessageType::TransMsgCommit => 2,
+        MessageType::DelayMsg => 3,
+        MessageType::OrderMsg => 4,
+    }
+}
+
+fn parse<T: FromStr>(line: &[&str], index: usize) -> Option<T> {
+    line.get(index)?.parse().ok()
+}
+
+fn message_type_of_ordinal(ordinal: usize) -> Option<MessageType> {
+    match ordinal {
+        0 => Some(MessageType::NormalMsg),
+        1 => Some(MessageType::TransMsgHalf),
+        2 => Some(MessageType::TransMsgCommit),
+        3 => Some(MessageType::DelayMsg),
+        4 => Some(MessageType::OrderMsg),
+        _ => None,
+    }
+}
+
+fn transaction_state_of_name(name: &str) -> Option<LocalTransactionState> {
+    match name {
+        "COMMIT_MESSAGE" => Some(LocalTransactionState::CommitMessage),
+        "ROLLBACK_MESSAGE" => Some(LocalTransactionState::RollbackMessage),
+        "UNKNOW" | "UNKNOWN" => Some(LocalTransactionState::Unknown),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(fields: &[&str]) -> String {
+        let mut data = fields.join(&TraceConstants::CONTENT_SPLITOR.to_string());
+        data.push(TraceConstants::FIELD_SPLITOR);
+        data
+    }
+
+    #[test]
+    fn decode_pub_and_sub_records() {
+        let mut data = record(&[
+            "Pub",
+            "1700000000000",
+            "DefaultRegion",
+            "producer_group",
+            "TopicTest",
+            "7F00000100002A9F0000000000000000",
+            "TagA",
+            "key1",
+            "127.0.0.1:10911",
+            "11",
+            "5",
+            "0",
+            "7F00000100002A9F00000000000000FF",
+            "true",
+            "127.0.0.1",
+        ]);
+        data.push_str(&record(&[
+            "SubBefore",
+            "1700000000100",
+            "DefaultRegion",
+            "consumer_group",
+            "request_id",
+            "7F00000100002A9F0000000000000000",
+            "0",
+            "key1",
+            "127.0.0.2",
+        ]));
+        data.push_str(&record(&[
+            "SubAfter",
+            "request_id",
+            "7F00000100002A9F0000000000000000",
+            "3",
+            "false",
+            "key1",
+            "1",
+            "1700000000103",
+            "consumer_group",
+        ]));
+
+        let contexts = TraceDataEncoder::decoder_from_trace_data_string(&data);
+        assert_eq!(contexts.len(), 3);
+
+        let pub_context = &contexts[0];
+        assert_eq!(pub_context.trace_type, Some(TraceType::Pub));
+        assert_eq!(pub_context.time_stamp, 1700000000000);
+        assert_eq!(pub_context.group_name, "producer_group");
+        assert_eq!(pub_context.cost_time, 5);
+        assert!(pub_context.is_success);
+        let pub_bean = &pub_context.trace_beans.as_ref().unwrap()[0];
+        assert_eq!(pub_bean.topic, "TopicTest");
+        assert_eq!(pub_bean.body_length, 11);
+        assert_eq!(pub_bean.msg_type, Some(MessageType::NormalMsg));
+        assert_eq!(pub_bean.offset_msg_id, "7F00000100002A9F00000000000000FF");
+        assert_eq!(pub_bean.client_host, "127.0.0.1");
+
+        let sub_before = &contexts[1];
+        assert_eq!(sub_before.trace_type, Some(TraceType::SubBefore));
+        assert_eq!(sub_before.request_id, "request_id");
+        assert_eq!(
+            sub_before.trace_beans.as_ref().unwrap()[0].client_host,
+            "127.0.0.2"
+        );
+
+        let sub_after = &contexts[2];
+        assert_eq!(sub_after.trace_type, Some(TraceType::SubAfter));
+        assert_eq!(sub_after.cost_time, 3);
+        assert!(!sub_after.is_success);
+        assert_eq!(sub_after.context_code, 1);
+        assert_eq!(sub_after.time_stamp, 1700000000103);
+        assert_eq!(sub_after.group_name, "consumer_group");
+    }
+
+    #[test]
+    fn encode_then_decode_round_trips() {
+        let publish = TraceContext {
+            trace_type: Some(TraceType::Pub),
+            time_stamp: 1700000000000,
+            region_id: CheetahString::from("DefaultRegion"),
+            group_name: CheetahString::from("producer_group"),
+            cost_time: 7,
+            is_success: true,
+            trace_beans: Some(vec![TraceBean {
+                topic: CheetahString::from("TopicTest"),
+                msg_id: CheetahString::from("msg_id"),
+                offset_msg_id: CheetahString::from("offset_msg_id"),
+                tags: CheetahString::from("TagA"),
+                keys: CheetahString::from("key1 key2"),
+                body_length: 11,
+                msg_type: Some(MessageType::DelayMsg),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        };
+        let sub_after = TraceContext {
+            trace_type: Some(TraceType::SubAfter),
+            time_stamp: 1700000000100,
+            group_name: CheetahString::from("consumer_group"),
+            request_id: CheetahString::from("request_id"),
+            cost_time: 3,
+            context_code: 2,
+            trace_beans: Some(vec![
+                TraceBean {
+                    msg_id: CheetahString::from("msg_id"),
+                    ..Default::default()
+                },
+                TraceBean {
+                    msg_id: CheetahString::from("msg_id_2"),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        };
+
+        let publish_bean = TraceDataEncoder::encoder_from_context_bean(&publish).unwrap();
+        assert_eq!(publish_bean.trans_key.len(), 3);
+        assert!(publish_bean.trans_key.contains("key2"));
+        let sub_after_bean = TraceDataEncoder::encoder_from_context_bean(&sub_after).unwrap();
+        assert_eq!(sub_after_bean.trans_key.len(), 2);
+
+        let data = publish_bean.trans_data + &sub_after_bean.trans_data;
+        let contexts = TraceDataEncoder::decoder_from_trace_data_string(&data);
+        assert_eq!(contexts.len(), 3);
+        assert_eq!(contexts[0].cost_time, 7);
+        assert!(contexts[0].is_success);
+        let bean = &contexts[0].trace_beans.as_ref().unwrap()[0];
+        assert_eq!(bean.keys, "key1 key2");
+        assert_eq!(bean.offset_msg_id, "offset_msg_id");
+        assert_eq!(bean.msg_type, Some(MessageType::DelayMsg));
+        assert_eq!(contexts[2].trace_type, Some(TraceType::SubAfter));
+        assert_eq!(contexts[2].context_code, 2);
+        assert_eq!(contexts[2].group_name, "consumer_group");
+        assert_eq!(
+            contexts[2].trace_beans.as_ref().unwrap()[0].msg_id,
+            "msg_id_2"
+        );
+
+        assert!(TraceDataEncoder::encoder_from_context_bean(&TraceContext::default()).is_none());
+    }
+
+    #[test]
+    fn decode_skips_malformed_records() {
+        let mut data = record(&["Pub", "not_a_number"]);
+        data.push_str(&record(&["Unknown", "1"]));
+        data.push_str(&record(&[
+            "EndTransaction",
+            "1700000000000",
+            "DefaultRegion",
+            "producer_group",
+            "TopicTest",
+            "msg_id",
+            "TagA",
+            "key1",
+            "127.0.0.1:10911",
+            "1",
+            "transaction_id",
+            "COMMIT_MESSAGE",
+            "false",
+        ]));
+
+        let contexts = TraceDataEncoder::decoder_from_trace_data_string(&data);
+        assert_eq!(contexts.len(), 1);
+        let bean = &contexts[0].trace_beans.as_ref().unwrap()[0];
+        assert_eq!(bean.msg_type, Some(MessageType::TransMsgHalf));
+        assert_eq!(
+            bean.transaction_state,
+            Some(LocalTransactionState::CommitMessage)
+        );
+        assert!(!bean.from_transaction_check);
+    }
+}
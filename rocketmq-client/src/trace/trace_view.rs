@@ -18,8 +18,13 @@
 use cheetah_string::CheetahString;
 use lazy_static::lazy_static;
 use rocketmq_common::common::message::message_enum::MessageType;
+use rocketmq_common::common::message::message_ext::MessageExt;
+use rocketmq_common::common::message::MessageTrait;
 use rocketmq_common::utils::util_all;
 
+use crate::trace::trace_data_encoder::TraceDataEncoder;
+use crate::trace::trace_type::TraceType;
+
 lazy_static! {
     static ref LOCAL_ADDRESS: CheetahString = util_all::get_ip_str();
 }
@@ -39,6 +44,7 @@ pub struct TraceView {
     pub topic: CheetahString,
     pub group_name: CheetahString,
     pub status: CheetahString,
+    pub trace_type: Option<TraceType>,
 }
 
 impl Default for TraceView {
@@ -57,15 +63,57 @@ impl Default for TraceView {
             topic: CheetahString::default(),
             group_name: CheetahString::default(),
             status: CheetahString::default(),
+            trace_type: None,
         }
     }
 }
 
+impl TraceView {
+    /// Builds the views of the message `key` from one message of the trace topic.
+    pub fn decode_from_trace_trans_data(key: &str, message: &MessageExt) -> Vec<TraceView> {
+        let Some(body) = message.get_body().filter(|body| !body.is_empty()) else {
+            return vec![];
+        };
+        let client_host = CheetahString::from_string(message.born_host().ip().to_string());
+        TraceDataEncoder::decoder_from_trace_data_string(&String::from_utf8_lossy(body))
+            .into_iter()
+            .filter_map(|context| {
+                let bean = context.trace_beans.as_ref()?.first()?;
+                if bean.msg_id != key {
+                    return None;
+                }
+                Some(TraceView {
+                    msg_id: bean.msg_id.clone(),
+                    tags: bean.tags.clone(),
+                    keys: bean.keys.clone(),
+                    store_host: bean.store_host.clone(),
+                    client_host: client_host.clone(),
+                    cost_time: context.cost_time as i64,
+                    msg_type: bean.msg_type,
+                    offset_msg_id: bean.offset_msg_id.clone(),
+                    time_stamp: context.time_stamp as i64,
+                    born_time: 0,
+                    topic: bean.topic.clone(),
+                    group_name: context.group_name.clone(),
+                    status: CheetahString::from_static_str(if context.is_success {
+                        "success"
+                    } else {
+                        "failed"
+                    }),
+                    trace_type: context.trace_type,
+                })
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use bytes::Bytes;
     use cheetah_string::CheetahString;
 
     use super::*;
+    use crate::trace::trace_constants::TraceConstants;
 
     #[test]
     fn trace_view_default_values() {
@@ -83,6 +131,7 @@ mod tests {
         assert_eq!(trace_view.topic, CheetahString::default());
         assert_eq!(trace_view.group_name, CheetahString::default());
         assert_eq!(trace_view.status, CheetahString::default());
+        assert_eq!(trace_view.trace_type, None);
     }
 
     #[test]
@@ -101,6 +150,7 @@ mod tests {
             topic: CheetahString::from("topic"),
             group_name: CheetahString::from("group"),
             status: CheetahString::from("status"),
+            trace_type: Some(TraceType::Pub),
         };
         assert_eq!(trace_view.msg_id, CheetahString::from("msg_id"));
         assert_eq!(trace_view.tags, CheetahString::from("tags"));
@@ -118,5 +168,36 @@ mod tests {
         assert_eq!(trace_view.topic, CheetahString::from("topic"));
         assert_eq!(trace_view.group_name, CheetahString::from("group"));
         assert_eq!(trace_view.status, CheetahString::from("status"));
+        assert_eq!(trace_view.trace_type, Some(TraceType::Pub));
+    }
+
+    #[test]
+    fn decode_from_trace_trans_data_filters_by_msg_id() {
+        let content = TraceConstants::CONTENT_SPLITOR.to_string();
+        let mut data = [
+            "SubAfter",
+            "request_id",
+            "msg_a",
+            "3",
+            "true",
+            "key1",
+            "0",
+            "1700000000103",
+            "group",
+        ]
+        .join(&content);
+        data.push(TraceConstants::FIELD_SPLITOR);
+        data.push_str(&["SubAfter", "request_id", "msg_b", "4", "false", "key2"].join(&content));
+        data.push(TraceConstants::FIELD_SPLITOR);
+        let mut message = MessageExt::default();
+        message.set_body(Bytes::from(data));
+
+        let views = TraceView::decode_from_trace_trans_data("msg_a", &message);
+        assert_eq!(views.len(), 1);
+        assert_eq!(views[0].trace_type, Some(TraceType::SubAfter));
+        assert_eq!(views[0].group_name, CheetahString::from("group"));
+        assert_eq!(views[0].status, CheetahString::from("success"));
+        assert_eq!(views[0].cost_time, 3);
+        assert_eq!(views[0].time_stamp, 1700000000103);
     }
 }
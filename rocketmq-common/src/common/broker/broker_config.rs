@@ -111,6 +111,30 @@ mod defaults {
         true
     }
 
+    pub fn controller_heartbeat_timeout_mills() -> u64 {
+        10 * 1000
+    }
+
+    pub fn broker_heartbeat_interval() -> u64 {
+        1000
+    }
+
+    pub fn sync_broker_metadata_period() -> u64 {
+        5 * 1000
+    }
+
+    pub fn check_sync_state_set_period() -> u64 {
+        5 * 1000
+    }
+
+    pub fn sync_controller_metadata_period() -> u64 {
+        10 * 1000
+    }
+
+    pub fn broker_election_priority() -> i32 {
+        i32::MAX
+    }
+
     pub fn broker_permission() -> u32 {
         PermName::PERM_WRITE | PermName::PERM_READ
     }
@@ -304,6 +328,14 @@ mod defaults {
         1
     }
 
+    pub fn cg_cold_read_threshold() -> i64 {
+        3 * 1024 * 1024
+    }
+
+    pub fn global_cold_read_threshold() -> i64 {
+        100 * 1024 * 1024
+    }
+
     pub fn broker_not_active_timeout_millis() -> i64 {
         10_000
     }
@@ -475,9 +507,46 @@ pub struct BrokerConfig {
     #[serde(default = "defaults::msg_trace_topic_name")]
     pub msg_trace_topic_name: CheetahString,
 
+    /// Verifies request signatures and topic/group permissions against `plain_acl.json`.
+    #[serde(default)]
+    pub acl_enable: bool,
+
+    /// Cold-read bytes per consumer group, per accounting period, above which the group is
+    /// flow controlled. Can be overridden per group through the admin API.
+    #[serde(default = "defaults::cg_cold_read_threshold")]
+    pub cg_cold_read_threshold: i64,
+
+    /// Cold-read bytes across all consumer groups, per accounting period, above which flow
+    /// control kicks in.
+    #[serde(default = "defaults::global_cold_read_threshold")]
+    pub global_cold_read_threshold: i64,
+
     #[serde(default)]
     pub enable_controller_mode: bool,
 
+    /// Controller addresses separated by `;`, used when `enable_controller_mode` is on.
+    #[serde(default)]
+    pub controller_addr: CheetahString,
+
+    #[serde(default = "defaults::controller_heartbeat_timeout_mills")]
+    pub controller_heartbeat_timeout_mills: u64,
+
+    #[serde(default = "defaults::broker_heartbeat_interval")]
+    pub broker_heartbeat_interval: u64,
+
+    #[serde(default = "defaults::sync_broker_metadata_period")]
+    pub sync_broker_metadata_period: u64,
+
+    #[serde(default = "defaults::check_sync_state_set_period")]
+    pub check_sync_state_set_period: u64,
+
+    #[serde(default = "defaults::sync_controller_metadata_period")]
+    pub sync_controller_metadata_period: u64,
+
+    /// Lower value means higher priority when the controller elects a new master.
+    #[serde(default = "defaults::broker_election_priority")]
+    pub broker_election_priority: i32,
+
     #[serde(default = "defaults::broker_name")]
     pub broker_name: CheetahString,
 
@@ -769,7 +838,17 @@ impl Default for BrokerConfig {
             msg_trace_topic_name: CheetahString::from_static_str(
                 TopicValidator::RMQ_SYS_TRACE_TOPIC,
             ),
+            acl_enable: false,
+            cg_cold_read_threshold: 3 * 1024 * 1024,
+            global_cold_read_threshold: 100 * 1024 * 1024,
             enable_controller_mode: false,
+            controller_addr: CheetahString::empty(),
+            controller_heartbeat_timeout_mills: 10 * 1000,
+            broker_heartbeat_interval: 1000,
+            sync_broker_metadata_period: 5 * 1000,
+            check_sync_state_set_period: 5 * 1000,
+            sync_controller_metadata_period: 10 * 1000,
+            broker_election_priority: i32::MAX,
             broker_name: default_broker_name().into(),
             region_id: CheetahString::from_static_str(mix_all::DEFAULT_TRACE_REGION_ID),
             trace_on: true,
@@ -965,10 +1044,44 @@ impl BrokerConfig {
             "msgTraceTopicName".into(),
             self.msg_trace_topic_name.clone(),
         );
+        properties.insert("aclEnable".into(), self.acl_enable.to_string().into());
+        properties.insert(
+            "cgColdReadThreshold".into(),
+            self.cg_cold_read_threshold.to_string().into(),
+        );
+        properties.insert(
+            "globalColdReadThreshold".into(),
+            self.global_cold_read_threshold.to_string().into(),
+        );
         properties.insert(
             "enableControllerMode".into(),
             self.enable_controller_mode.to_string().into(),
         );
+        properties.insert("controllerAddr".into(), self.controller_addr.clone());
+        properties.insert(
+            "controllerHeartBeatTimeoutMills".into(),
+            self.controller_heartbeat_timeout_mills.to_string().into(),
+        );
+        properties.insert(
+            "brokerHeartbeatInterval".into(),
+            self.broker_heartbeat_interval.to_string().into(),
+        );
+        properties.insert(
+            "syncBrokerMetadataPeriod".into(),
+            self.sync_broker_metadata_period.to_string().into(),
+        );
+        properties.insert(
+            "checkSyncStateSetPeriod".into(),
+            self.check_sync_state_set_period.to_string().into(),
+        );
+        properties.insert(
+            "syncControllerMetadataPeriod".into(),
+            self.sync_controller_metadata_period.to_string().into(),
+        );
+        properties.insert(
+            "brokerElectionPriority".into(),
+            self.broker_election_priority.to_string().into(),
+        );
         properties.insert("regionId".into(), self.region_id.clone());
         properties.insert("brokerName".into(), self.broker_name.clone());
         properties.insert("traceOn".into(), self.trace_on.to_string().into());
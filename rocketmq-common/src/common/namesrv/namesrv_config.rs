@@ -47,6 +47,17 @@ mod defaults {
         )
     }
 
+    pub fn acl_config_path() -> String {
+        format!(
+            "{}{}{}{}{}",
+            dirs::home_dir().unwrap().to_str().unwrap(),
+            MAIN_SEPARATOR,
+            "rocketmq-namesrv",
+            MAIN_SEPARATOR,
+            "plain_acl.json"
+        )
+    }
+
     pub fn config_store_path() -> String {
         format!(
             "{}{}{}{}{}",
@@ -201,6 +212,13 @@ pub struct NamesrvConfig {
 
     #[serde(alias = "configBlackList", default = "defaults::config_black_list")]
     pub config_black_list: String,
+
+    /// Requires admin requests to be signed by an admin account of `acl_config_path`.
+    #[serde(alias = "aclEnable", default)]
+    pub acl_enable: bool,
+
+    #[serde(alias = "aclConfigPath", default = "defaults::acl_config_path")]
+    pub acl_config_path: String,
 }
 
 impl Default for NamesrvConfig {
@@ -248,6 +266,8 @@ impl Default for NamesrvConfig {
             wait_seconds_for_service: 45,
             delete_topic_with_broker_registration: false,
             config_black_list: "configBlackList;configStorePath;kvConfigPath".to_string(),
+            acl_enable: false,
+            acl_config_path: defaults::acl_config_path(),
         }
     }
 }
@@ -349,6 +369,14 @@ impl NamesrvConfig {
             "configBlackList".to_string(),
             Value::String(self.config_black_list.clone()),
         );
+        json_map.insert(
+            "aclEnable".to_string(),
+            Value::String(self.acl_enable.to_string()),
+        );
+        json_map.insert(
+            "aclConfigPath".to_string(),
+            Value::String(self.acl_config_path.clone()),
+        );
 
         // Convert the HashMap to a JSON value
         match SerdeJsonUtils::to_json(&json_map) {
@@ -460,6 +488,11 @@ impl NamesrvConfig {
                         .parse()
                         .map_err(|_| format!("Invalid string value for key '{key}'"))?
                 }
+                "aclEnable" => {
+                    self.acl_enable = value
+                        .parse()
+                        .map_err(|_| format!("Invalid boolean value for key '{key}'"))?
+                }
                 _ => {
                     return Err(format!("Unknown configuration key: '{key}'"));
                 }
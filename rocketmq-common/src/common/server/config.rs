@@ -17,8 +17,15 @@
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::common::server::tls::TlsClientAuth;
+use crate::common::server::tls::TlsMode;
+
 /// Default value functions for Serde deserialization
 mod defaults {
+    use crate::common::server::tls;
+    use crate::common::server::tls::TlsClientAuth;
+    use crate::common::server::tls::TlsMode;
+
     pub fn listen_port() -> u32 {
         10911
     }
@@ -26,6 +33,26 @@ mod defaults {
     pub fn bind_address() -> String {
         "0.0.0.0".to_string()
     }
+
+    pub fn tls_mode() -> TlsMode {
+        tls::env_or(tls::TLS_SERVER_MODE, TlsMode::Disabled)
+    }
+
+    pub fn tls_server_need_client_auth() -> TlsClientAuth {
+        tls::env_or(tls::TLS_SERVER_NEED_CLIENT_AUTH, TlsClientAuth::None)
+    }
+
+    pub fn tls_server_cert_path() -> Option<String> {
+        tls::env_path(tls::TLS_SERVER_CERT_PATH)
+    }
+
+    pub fn tls_server_key_path() -> Option<String> {
+        tls::env_path(tls::TLS_SERVER_KEY_PATH)
+    }
+
+    pub fn tls_server_trust_cert_path() -> Option<String> {
+        tls::env_path(tls::TLS_SERVER_TRUST_CERT_PATH)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +63,24 @@ pub struct ServerConfig {
 
     #[serde(default = "defaults::bind_address")]
     pub bind_address: String,
+
+    #[serde(default = "defaults::tls_mode")]
+    pub tls_mode: TlsMode,
+
+    /// Client certificates are verified against `tls_server_trust_cert_path`.
+    #[serde(default = "defaults::tls_server_need_client_auth")]
+    pub tls_server_need_client_auth: TlsClientAuth,
+
+    /// PEM certificate chain presented to clients.
+    #[serde(default = "defaults::tls_server_cert_path")]
+    pub tls_server_cert_path: Option<String>,
+
+    /// PEM private key of `tls_server_cert_path`.
+    #[serde(default = "defaults::tls_server_key_path")]
+    pub tls_server_key_path: Option<String>,
+
+    #[serde(default = "defaults::tls_server_trust_cert_path")]
+    pub tls_server_trust_cert_path: Option<String>,
 }
 
 impl Default for ServerConfig {
@@ -43,6 +88,11 @@ impl Default for ServerConfig {
         ServerConfig {
             listen_port: defaults::listen_port(),
             bind_address: defaults::bind_address(),
+            tls_mode: defaults::tls_mode(),
+            tls_server_need_client_auth: defaults::tls_server_need_client_auth(),
+            tls_server_cert_path: defaults::tls_server_cert_path(),
+            tls_server_key_path: defaults::tls_server_key_path(),
+            tls_server_trust_cert_path: defaults::tls_server_trust_cert_path(),
         }
     }
 }
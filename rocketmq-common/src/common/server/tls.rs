@@ -0,0 +1,141 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! TLS settings shared by the remoting server and client. As in the Java implementation the
+//! defaults can be supplied through `tls.*` environment variables.
+
+use std::env;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+pub const TLS_SERVER_MODE: &str = "tls.server.mode";
+pub const TLS_SERVER_NEED_CLIENT_AUTH: &str = "tls.server.need.client.auth";
+pub const TLS_SERVER_CERT_PATH: &str = "tls.server.certPath";
+pub const TLS_SERVER_KEY_PATH: &str = "tls.server.keyPath";
+pub const TLS_SERVER_TRUST_CERT_PATH: &str = "tls.server.trustCertPath";
+pub const TLS_ENABLE: &str = "tls.enable";
+pub const TLS_CLIENT_CERT_PATH: &str = "tls.client.certPath";
+pub const TLS_CLIENT_KEY_PATH: &str = "tls.client.keyPath";
+pub const TLS_CLIENT_TRUST_CERT_PATH: &str = "tls.client.trustCertPath";
+pub const TLS_CLIENT_AUTH_SERVER: &str = "tls.client.authServer";
+
+/// How the server treats TLS connections.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TlsMode {
+    /// Plain TCP only, TLS handshakes are rejected.
+    #[default]
+    Disabled,
+    /// Both TLS and plain TCP connections are accepted.
+    Permissive,
+    /// Only TLS connections are accepted.
+    Enforcing,
+}
+
+/// Whether the server asks clients for a certificate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TlsClientAuth {
+    #[default]
+    None,
+    Optional,
+    Require,
+}
+
+impl FromStr for TlsMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "disabled" => Ok(TlsMode::Disabled),
+            "permissive" => Ok(TlsMode::Permissive),
+            "enforcing" => Ok(TlsMode::Enforcing),
+            _ => Err(format!("Unknown tls mode: {s}")),
+        }
+    }
+}
+
+impl FromStr for TlsClientAuth {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "none" => Ok(TlsClientAuth::None),
+            "optional" => Ok(TlsClientAuth::Optional),
+            "require" => Ok(TlsClientAuth::Require),
+            _ => Err(format!("Unknown tls client auth: {s}")),
+        }
+    }
+}
+
+impl Display for TlsMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TlsMode::Disabled => write!(f, "disabled"),
+            TlsMode::Permissive => write!(f, "permissive"),
+            TlsMode::Enforcing => write!(f, "enforcing"),
+        }
+    }
+}
+
+/// Reads `key` from the environment, falling back to `default` when it is absent or invalid.
+pub fn env_or<T: FromStr>(key: &str, default: T) -> T {
+    env::var(key)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Reads an optional path from the environment, blank values count as absent.
+pub fn env_path(key: &str) -> Option<String> {
+    env::var(key)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tls_mode_parses_case_insensitively() {
+        assert_eq!("Enforcing".parse::<TlsMode>().unwrap(), TlsMode::Enforcing);
+        assert_eq!(
+            "permissive".parse::<TlsMode>().unwrap(),
+            TlsMode::Permissive
+        );
+        assert!("strict".parse::<TlsMode>().is_err());
+        assert_eq!(
+            "optional".parse::<TlsClientAuth>().unwrap(),
+            TlsClientAuth::Optional
+        );
+    }
+
+    #[test]
+    fn tls_mode_serde_uses_lowercase_names() {
+        assert_eq!(
+            serde_json::to_string(&TlsMode::Enforcing).unwrap(),
+            "\"enforcing\""
+        );
+        let mode: TlsMode = serde_json::from_str("\"disabled\"").unwrap();
+        assert_eq!(mode, TlsMode::Disabled);
+    }
+}
@@ -29,10 +29,18 @@ use tracing::info;
 
 use crate::common::stats::call_snapshot::CallSnapshot;
 use crate::common::stats::stats_snapshot::StatsSnapshot;
+use crate::TimeUtils::get_current_millis;
+
+/// Interval of the samples backing the per-minute statistics.
+pub const SAMPLING_IN_SECONDS_INTERVAL_MILLIS: u64 = 10 * 1000;
+/// Interval of the samples backing the per-hour statistics.
+pub const SAMPLING_IN_MINUTES_INTERVAL_MILLIS: u64 = 10 * 60 * 1000;
+/// Interval of the samples backing the per-day statistics.
+pub const SAMPLING_IN_HOUR_INTERVAL_MILLIS: u64 = 60 * 60 * 1000;
 
 pub struct StatsItem {
-    value: AtomicU64,
-    times: AtomicU64,
+    value: Arc<AtomicU64>,
+    times: Arc<AtomicU64>,
     cs_list_minute: Arc<Mutex<LinkedList<CallSnapshot>>>,
     cs_list_hour: Arc<Mutex<LinkedList<CallSnapshot>>>,
     cs_list_day: Arc<Mutex<LinkedList<CallSnapshot>>>,
@@ -43,8 +51,8 @@ pub struct StatsItem {
 impl StatsItem {
     pub fn new(stats_name: &str, stats_key: &str) -> Self {
         StatsItem {
-            value: AtomicU64::new(0),
-            times: AtomicU64::new(0),
+            value: Arc::new(AtomicU64::new(0)),
+            times: Arc::new(AtomicU64::new(0)),
             cs_list_minute: Arc::new(Mutex::new(LinkedList::new())),
             cs_list_hour: Arc::new(Mutex::new(LinkedList::new())),
             cs_list_day: Arc::new(Mutex::new(LinkedList::new())),
@@ -53,6 +61,27 @@ impl StatsItem {
         }
     }
 
+    pub fn add_value(&self, inc_value: u64, inc_times: u64) {
+        self.value.fetch_add(inc_value, Ordering::Relaxed);
+        self.times.fetch_add(inc_times, Ordering::Relaxed);
+    }
+
+    pub fn get_value(&self) -> u64 {
+        self.value.load(Ordering::Relaxed)
+    }
+
+    pub fn get_times(&self) -> u64 {
+        self.times.load(Ordering::Relaxed)
+    }
+
+    pub fn get_stats_name(&self) -> &str {
+        &self.stats_name
+    }
+
+    pub fn get_stats_key(&self) -> &str {
+        &self.stats_key
+    }
+
     pub fn compute_stats_data(cs_list: Arc<Mutex<LinkedList<CallSnapshot>>>) -> StatsSnapshot {
         let mut stats_snapshot = StatsSnapshot::new();
         let cs_list = cs_list.lock();
@@ -60,7 +89,12 @@ impl StatsItem {
             let first = cs_list.front().unwrap();
             let last = cs_list.back().unwrap();
             let sum = last.get_value() - first.get_value();
-            let tps = (sum as f64 * 1000.0) / (last.get_timestamp() - first.get_timestamp()) as f64;
+            let elapsed = last.get_timestamp() - first.get_timestamp();
+            let tps = if elapsed > 0 {
+                (sum as f64 * 1000.0) / elapsed as f64
+            } else {
+                0.0
+            };
             let times_diff = last.get_times() - first.get_times();
             let avgpt = if times_diff > 0 {
                 sum as f64 / times_diff as f64
@@ -97,19 +131,40 @@ impl StatsItem {
         let stats_name = self.stats_name.clone();
         let stats_key = self.stats_key.clone();
 
+        let (value, times) = (self.value.clone(), self.times.clone());
         thread::spawn(move || loop {
-            thread::sleep(Duration::from_secs(10));
-            Self::sampling_in_seconds(cs_list_minute.clone());
+            thread::sleep(Duration::from_millis(SAMPLING_IN_SECONDS_INTERVAL_MILLIS));
+            Self::sampling(
+                &cs_list_minute,
+                &value,
+                &times,
+                SAMPLING_IN_SECONDS_INTERVAL_MILLIS,
+                7,
+            );
         });
 
+        let (value, times) = (self.value.clone(), self.times.clone());
         thread::spawn(move || loop {
-            thread::sleep(Duration::from_secs(600));
-            Self::sampling_in_minutes(cs_list_hour.clone());
+            thread::sleep(Duration::from_millis(SAMPLING_IN_MINUTES_INTERVAL_MILLIS));
+            Self::sampling(
+                &cs_list_hour,
+                &value,
+                &times,
+                SAMPLING_IN_MINUTES_INTERVAL_MILLIS,
+                7,
+            );
         });
 
+        let (value, times) = (self.value.clone(), self.times.clone());
         thread::spawn(move || loop {
-            thread::sleep(Duration::from_secs(3600));
-            Self::sampling_in_hour(cs_list_day.clone());
+            thread::sleep(Duration::from_millis(SAMPLING_IN_HOUR_INTERVAL_MILLIS));
+            Self::sampling(
+                &cs_list_day,
+                &value,
+                &times,
+                SAMPLING_IN_HOUR_INTERVAL_MILLIS,
+                25,
+            );
         });
 
         let stats_name_clone = stats_name.clone();
@@ -156,80 +211,61 @@ impl StatsItem {
         });
     }
 
-    pub fn sampling_in_seconds(cs_list: Arc<Mutex<LinkedList<CallSnapshot>>>) {
-        let mut cs_list = cs_list.lock();
-        if cs_list.is_empty() {
-            cs_list.push_back(CallSnapshot::new(
-                SystemTime::now()
-                    .duration_since(SystemTime::UNIX_EPOCH)
-                    .unwrap()
-                    .as_millis() as u64
-                    - 10 * 1000,
-                0,
-                0,
-            ));
-        }
-        cs_list.push_back(CallSnapshot::new(
-            SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_millis() as u64,
-            0,
-            0,
-        ));
-        if cs_list.len() > 7 {
-            cs_list.pop_front();
-        }
+    /// Takes a sample for the per-minute statistics; expected every
+    /// [`SAMPLING_IN_SECONDS_INTERVAL_MILLIS`].
+    pub fn sampling_in_seconds(&self) {
+        Self::sampling(
+            &self.cs_list_minute,
+            &self.value,
+            &self.times,
+            SAMPLING_IN_SECONDS_INTERVAL_MILLIS,
+            7,
+        );
     }
 
-    pub fn sampling_in_minutes(cs_list: Arc<Mutex<LinkedList<CallSnapshot>>>) {
-        let mut cs_list = cs_list.lock();
-        if cs_list.is_empty() {
-            cs_list.push_back(CallSnapshot::new(
-                SystemTime::now()
-                    .duration_since(SystemTime::UNIX_EPOCH)
-                    .unwrap()
-                    .as_millis() as u64
-                    - 10 * 60 * 1000,
-                0,
-                0,
-            ));
-        }
-        cs_list.push_back(CallSnapshot::new(
-            SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_millis() as u64,
-            0,
-            0,
-        ));
-        if cs_list.len() > 7 {
-            cs_list.pop_front();
-        }
+    /// Takes a sample for the per-hour statistics; expected every
+    /// [`SAMPLING_IN_MINUTES_INTERVAL_MILLIS`].
+    pub fn sampling_in_minutes(&self) {
+        Self::sampling(
+            &self.cs_list_hour,
+            &self.value,
+            &self.times,
+            SAMPLING_IN_MINUTES_INTERVAL_MILLIS,
+            7,
+        );
     }
 
-    pub fn sampling_in_hour(cs_list: Arc<Mutex<LinkedList<CallSnapshot>>>) {
+    /// Takes a sample for the per-day statistics; expected every
+    /// [`SAMPLING_IN_HOUR_INTERVAL_MILLIS`].
+    pub fn sampling_in_hour(&self) {
+        Self::sampling(
+            &self.cs_list_day,
+            &self.value,
+            &self.times,
+            SAMPLING_IN_HOUR_INTERVAL_MILLIS,
+            25,
+        );
+    }
+
+    fn sampling(
+        cs_list: &Mutex<LinkedList<CallSnapshot>>,
+        value: &AtomicU64,
+        times: &AtomicU64,
+        interval_millis: u64,
+        max_snapshots: usize,
+    ) {
+        let now = get_current_millis();
         let mut cs_list = cs_list.lock();
         if cs_list.is_empty() {
-            cs_list.push_back(CallSnapshot::new(
-                SystemTime::now()
-                    .duration_since(SystemTime::UNIX_EPOCH)
-                    .unwrap()
-                    .as_millis() as u64
-                    - 60 * 60 * 1000,
-                0,
-                0,
-            ));
+            // whatever was counted before the first sample belongs to the first interval
+            cs_list.push_back(CallSnapshot::new(now.saturating_sub(interval_millis), 0, 0));
         }
         cs_list.push_back(CallSnapshot::new(
-            SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap()
-                .as_millis() as u64,
-            0,
-            0,
+            now,
+            times.load(Ordering::Relaxed),
+            value.load(Ordering::Relaxed),
         ));
-        if cs_list.len() > 25 {
+        if cs_list.len() > max_snapshots {
             cs_list.pop_front();
         }
     }
@@ -375,6 +411,18 @@ mod tests {
         assert_eq!(snapshot.get_avgpt(), 0.0);
     }
 
+    #[test]
+    fn sampling_records_added_values() {
+        let stats_item = StatsItem::new("TestName", "TestKey");
+        stats_item.add_value(30, 2);
+        stats_item.sampling_in_seconds();
+        let snapshot = stats_item.get_stats_data_in_minute();
+        assert_eq!(snapshot.get_sum(), 30);
+        assert_eq!(snapshot.get_times(), 2);
+        assert_eq!(snapshot.get_tps(), 3.0);
+        assert_eq!(snapshot.get_avgpt(), 15.0);
+    }
+
     #[test]
     fn get_stats_data_in_day_returns_correct_snapshot() {
         let stats_item = StatsItem::new("TestName", "TestKey");
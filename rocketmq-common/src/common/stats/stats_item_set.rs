@@ -14,17 +14,109 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::sync::Arc;
+
+use dashmap::DashMap;
+
+use crate::common::stats::stats_item::StatsItem;
 use crate::common::stats::stats_snapshot::StatsSnapshot;
 
+/// A named group of [`StatsItem`]s, one per stats key. Sampling is driven by the owner, which
+/// calls the `sampling_in_*` methods at the intervals documented on [`StatsItem`].
 #[derive(Debug)]
-pub struct StatsItemSet {}
+pub struct StatsItemSet {
+    stats_item_table: Arc<DashMap<String, Arc<StatsItem>>>,
+    stats_name: String,
+}
 
 impl StatsItemSet {
-    pub fn new(_stats_name: String) -> Self {
-        StatsItemSet {}
+    pub fn new(stats_name: String) -> Self {
+        StatsItemSet {
+            stats_item_table: Arc::new(DashMap::new()),
+            stats_name,
+        }
+    }
+
+    pub fn get_stats_name(&self) -> &str {
+        &self.stats_name
+    }
+
+    pub fn add_value(&self, stats_key: &str, inc_value: u64, inc_times: u64) {
+        self.get_and_create_stats_item(stats_key)
+            .add_value(inc_value, inc_times);
+    }
+
+    pub fn get_and_create_stats_item(&self, stats_key: &str) -> Arc<StatsItem> {
+        if let Some(stats_item) = self.stats_item_table.get(stats_key) {
+            return stats_item.clone();
+        }
+        self.stats_item_table
+            .entry(stats_key.to_string())
+            .or_insert_with(|| Arc::new(StatsItem::new(&self.stats_name, stats_key)))
+            .clone()
+    }
+
+    pub fn get_stats_item(&self, stats_key: &str) -> Option<Arc<StatsItem>> {
+        self.stats_item_table
+            .get(stats_key)
+            .map(|stats_item| stats_item.clone())
     }
 
     pub fn get_stats_data_in_minute(&self, stats_key: &str) -> StatsSnapshot {
-        StatsSnapshot::new()
+        self.get_stats_item(stats_key)
+            .map(|stats_item| stats_item.get_stats_data_in_minute())
+            .unwrap_or_default()
+    }
+
+    pub fn get_stats_data_in_hour(&self, stats_key: &str) -> StatsSnapshot {
+        self.get_stats_item(stats_key)
+            .map(|stats_item| stats_item.get_stats_data_in_hour())
+            .unwrap_or_default()
+    }
+
+    pub fn get_stats_data_in_day(&self, stats_key: &str) -> StatsSnapshot {
+        self.get_stats_item(stats_key)
+            .map(|stats_item| stats_item.get_stats_data_in_day())
+            .unwrap_or_default()
+    }
+
+    pub fn sampling_in_seconds(&self) {
+        for entry in self.stats_item_table.iter() {
+            entry.value().sampling_in_seconds();
+        }
+    }
+
+    pub fn sampling_in_minutes(&self) {
+        for entry in self.stats_item_table.iter() {
+            entry.value().sampling_in_minutes();
+        }
+    }
+
+    pub fn sampling_in_hour(&self) {
+        for entry in self.stats_item_table.iter() {
+            entry.value().sampling_in_hour();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_item_set_aggregates_values_per_key() {
+        let stats_item_set = StatsItemSet::new("TestName".to_string());
+        stats_item_set.add_value("key", 10, 1);
+        stats_item_set.add_value("key", 20, 1);
+        stats_item_set.sampling_in_seconds();
+
+        let snapshot = stats_item_set.get_stats_data_in_minute("key");
+        assert_eq!(snapshot.get_sum(), 30);
+        assert_eq!(snapshot.get_times(), 2);
+        assert_eq!(snapshot.get_avgpt(), 15.0);
+        assert_eq!(
+            stats_item_set.get_stats_data_in_minute("missing").get_sum(),
+            0
+        );
     }
 }
@@ -38,6 +38,7 @@ use chrono::Timelike;
 use chrono::Utc;
 use local_ip_address::Error;
 use once_cell::sync::Lazy;
+use sysinfo::Disks;
 use tracing::error;
 use tracing::info;
 
@@ -151,6 +152,8 @@ pub fn is_path_exists(path: &str) -> bool {
     Path::new(path).exists()
 }
 
+/// Returns the used ratio (`0.0..=1.0`) of the disk partition holding `path`, or `-1.0` if it
+/// cannot be measured.
 pub fn get_disk_partition_space_used_percent(path: &str) -> f64 {
     if path.is_empty() {
         error!(
@@ -160,53 +163,43 @@ pub fn get_disk_partition_space_used_percent(path: &str) -> f64 {
         return -1.0;
     }
 
-    let path = Path::new(path);
-    if !path.exists() {
-        error!(
-            "Error when measuring disk space usage, file doesn't exist on this path: {}",
-            path.to_string_lossy()
-        );
-        return -1.0;
-    }
-
-    match fs::metadata(path) {
-        Ok(metadata) => {
-            let total_space = metadata.len();
-            if total_space > 0 {
-                match (fs::metadata(path), fs::metadata(path)) {
-                    (Ok(metadata1), Ok(metadata2)) => {
-                        let free_space = metadata1.len();
-                        let usable_space = metadata2.len();
-                        let used_space = total_space.saturating_sub(free_space);
-                        let entire_space = used_space + usable_space;
-                        let round_num = if used_space * 100 % entire_space != 0 {
-                            1
-                        } else {
-                            0
-                        };
-                        let result = used_space * 100 / entire_space + round_num;
-                        return result as f64 / 100.0;
-                    }
-                    (Err(e), _) | (_, Err(e)) => {
-                        error!(
-                            "Error when measuring disk space usage, got exception: {:?}",
-                            e
-                        );
-                        return -1.0;
-                    }
-                }
-            }
-        }
+    let path = match fs::canonicalize(path) {
+        Ok(path) => path,
         Err(e) => {
             error!(
-                "Error when measuring disk space usage, got exception: {:?}",
-                e
+                "Error when measuring disk space usage, file doesn't exist on this path: {}, {:?}",
+                path, e
             );
             return -1.0;
         }
+    };
+
+    let disks = Disks::new_with_refreshed_list();
+    let disk = disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len());
+    match disk {
+        Some(disk) if disk.total_space() > 0 => {
+            let total_space = disk.total_space();
+            let used_space = total_space.saturating_sub(disk.available_space());
+            let round_num = if used_space * 100 % total_space != 0 {
+                1
+            } else {
+                0
+            };
+            let result = used_space * 100 / total_space + round_num;
+            result as f64 / 100.0
+        }
+        _ => {
+            error!(
+                "Error when measuring disk space usage, no disk partition found for path: {}",
+                path.to_string_lossy()
+            );
+            -1.0
+        }
     }
-
-    -1.0
 }
 
 pub fn bytes_to_string(src: &[u8]) -> String {
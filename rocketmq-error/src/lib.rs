@@ -96,6 +96,12 @@ pub enum RocketmqError {
     #[error("{0}")]
     IllegalArgument(String),
 
+    #[error("AclException: {0}")]
+    AclError(String),
+
+    #[error("TlsException: {0}")]
+    TlsError(String),
+
     //client error
     #[error("{0}")]
     MQClientErr(#[from] ClientErr),
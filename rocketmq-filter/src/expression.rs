@@ -15,6 +15,8 @@
  * limitations under the License.
  */
 pub mod evaluation_context;
+pub mod sql_expression;
+pub mod value;
 
 use std::error::Error;
 
@@ -0,0 +1,505 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::any::Any;
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use cheetah_string::CheetahString;
+
+use crate::expression::evaluation_context::EvaluationContext;
+use crate::expression::value::Value;
+use crate::expression::Expression;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComparisonOperator {
+    Equal,
+    NotEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    LessThan,
+    LessThanOrEqual,
+}
+
+impl ComparisonOperator {
+    fn symbol(&self) -> &'static str {
+        match self {
+            ComparisonOperator::Equal => "=",
+            ComparisonOperator::NotEqual => "<>",
+            ComparisonOperator::GreaterThan => ">",
+            ComparisonOperator::GreaterThanOrEqual => ">=",
+            ComparisonOperator::LessThan => "<",
+            ComparisonOperator::LessThanOrEqual => "<=",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogicOperator {
+    And,
+    Or,
+}
+
+/// One element of a compiled `LIKE` pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LikeToken {
+    /// `%`, any sequence of characters.
+    AnySequence,
+    /// `_`, exactly one character.
+    AnyChar,
+    Literal(char),
+}
+
+/// A compiled SQL92 filter expression.
+///
+/// Evaluation follows SQL three-valued logic: a missing property evaluates to `NULL`, any
+/// comparison involving `NULL` is unknown, and an unknown result never matches.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlExpression {
+    Constant(Value),
+    Property(CheetahString),
+    Negate(Box<SqlExpression>),
+    Not(Box<SqlExpression>),
+    IsNull {
+        expr: Box<SqlExpression>,
+        negated: bool,
+    },
+    Comparison {
+        operator: ComparisonOperator,
+        left: Box<SqlExpression>,
+        right: Box<SqlExpression>,
+    },
+    Logic {
+        operator: LogicOperator,
+        left: Box<SqlExpression>,
+        right: Box<SqlExpression>,
+    },
+    Between {
+        expr: Box<SqlExpression>,
+        low: Box<SqlExpression>,
+        high: Box<SqlExpression>,
+        negated: bool,
+    },
+    In {
+        expr: Box<SqlExpression>,
+        list: Vec<Value>,
+        negated: bool,
+    },
+    Like {
+        expr: Box<SqlExpression>,
+        pattern: CheetahString,
+        escape: Option<char>,
+        tokens: Vec<LikeToken>,
+        negated: bool,
+    },
+}
+
+impl SqlExpression {
+    /// Whether this expression produces a boolean (or unknown) result.
+    pub fn is_boolean(&self) -> bool {
+        match self {
+            SqlExpression::Constant(value) => matches!(value, Value::Bool(_) | Value::Null),
+            SqlExpression::Negate(_) => false,
+            _ => true,
+        }
+    }
+
+    /// Evaluate the expression and check whether the result is `TRUE`.
+    pub fn matches(&self, context: &dyn EvaluationContext) -> bool {
+        self.eval(context).as_bool().unwrap_or(false)
+    }
+
+    pub fn eval(&self, context: &dyn EvaluationContext) -> Value {
+        match self {
+            SqlExpression::Constant(value) => value.clone(),
+            SqlExpression::Property(name) => match context.get(name.as_str()) {
+                None => Value::Null,
+                Some(value) => Value::String(value.clone()),
+            },
+            SqlExpression::Negate(expr) => match expr.eval(context) {
+                Value::Long(value) => Value::Long(value.wrapping_neg()),
+                Value::Double(value) => Value::Double(-value),
+                Value::String(value) => match value.trim().parse::<i64>() {
+                    Ok(value) => Value::Long(value.wrapping_neg()),
+                    Err(_) => value
+                        .trim()
+                        .parse::<f64>()
+                        .map_or(Value::Null, |value| Value::Double(-value)),
+                },
+                _ => Value::Null,
+            },
+            SqlExpression::Not(expr) => match expr.eval(context).as_bool() {
+                None => Value::Null,
+                Some(value) => Value::Bool(!value),
+            },
+            SqlExpression::IsNull { expr, negated } => {
+                Value::Bool(expr.eval(context).is_null() != *negated)
+            }
+            SqlExpression::Comparison {
+                operator,
+                left,
+                right,
+            } => compare(*operator, &left.eval(context), &right.eval(context)),
+            SqlExpression::Logic {
+                operator,
+                left,
+                right,
+            } => {
+                let left = left.eval(context).as_bool();
+                match (operator, left) {
+                    (LogicOperator::And, Some(false)) => return Value::Bool(false),
+                    (LogicOperator::Or, Some(true)) => return Value::Bool(true),
+                    _ => {}
+                }
+                let right = right.eval(context).as_bool();
+                match (operator, left, right) {
+                    (LogicOperator::And, _, Some(false)) => Value::Bool(false),
+                    (LogicOperator::And, Some(true), Some(true)) => Value::Bool(true),
+                    (LogicOperator::Or, _, Some(true)) => Value::Bool(true),
+                    (LogicOperator::Or, Some(false), Some(false)) => Value::Bool(false),
+                    _ => Value::Null,
+                }
+            }
+            SqlExpression::Between {
+                expr,
+                low,
+                high,
+                negated,
+            } => {
+                let value = expr.eval(context);
+                let low = low.eval(context);
+                let high = high.eval(context);
+                if value.is_null() || low.is_null() || high.is_null() {
+                    return Value::Null;
+                }
+                let within =
+                    matches!(
+                        value.compare(&low),
+                        Some(Ordering::Greater | Ordering::Equal)
+                    ) && matches!(value.compare(&high), Some(Ordering::Less | Ordering::Equal));
+                Value::Bool(within != *negated)
+            }
+            SqlExpression::In {
+                expr,
+                list,
+                negated,
+            } => {
+                let value = expr.eval(context);
+                if value.is_null() {
+                    return Value::Null;
+                }
+                let contains = list
+                    .iter()
+                    .any(|candidate| value.equals(candidate).unwrap_or(false));
+                Value::Bool(contains != *negated)
+            }
+            SqlExpression::Like {
+                expr,
+                tokens,
+                negated,
+                ..
+            } => match expr.eval(context) {
+                Value::Null => Value::Null,
+                Value::String(value) => {
+                    let chars = value.chars().collect::<Vec<_>>();
+                    Value::Bool(like_matches(tokens, &chars) != *negated)
+                }
+                _ => Value::Bool(false),
+            },
+        }
+    }
+}
+
+fn compare(operator: ComparisonOperator, left: &Value, right: &Value) -> Value {
+    if left.is_null() || right.is_null() {
+        return Value::Null;
+    }
+    let ordering = left.compare(right);
+    let result = match operator {
+        ComparisonOperator::Equal => ordering == Some(Ordering::Equal),
+        // values that can't be compared are unknown, not different
+        ComparisonOperator::NotEqual => {
+            ordering.is_some_and(|ordering| ordering != Ordering::Equal)
+        }
+        ComparisonOperator::GreaterThan => ordering == Some(Ordering::Greater),
+        ComparisonOperator::GreaterThanOrEqual => {
+            matches!(ordering, Some(Ordering::Greater | Ordering::Equal))
+        }
+        ComparisonOperator::LessThan => ordering == Some(Ordering::Less),
+        ComparisonOperator::LessThanOrEqual => {
+            matches!(ordering, Some(Ordering::Less | Ordering::Equal))
+        }
+    };
+    Value::Bool(result)
+}
+
+/// Compile a `LIKE` pattern, honouring an optional escape character.
+pub fn compile_like_pattern(pattern: &str, escape: Option<char>) -> Vec<LikeToken> {
+    let mut tokens = Vec::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if Some(c) == escape {
+            if let Some(escaped) = chars.next() {
+                tokens.push(LikeToken::Literal(escaped));
+                continue;
+            }
+        }
+        tokens.push(match c {
+            '%' => LikeToken::AnySequence,
+            '_' => LikeToken::AnyChar,
+            c => LikeToken::Literal(c),
+        });
+    }
+    tokens
+}
+
+fn like_matches(tokens: &[LikeToken], chars: &[char]) -> bool {
+    // Greedy wildcard matching with backtracking to the last `%`.
+    let (mut t, mut c) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while c < chars.len() {
+        match tokens.get(t) {
+            Some(LikeToken::AnySequence) => {
+                backtrack = Some((t, c));
+                t += 1;
+                continue;
+            }
+            Some(LikeToken::AnyChar) => {
+                t += 1;
+                c += 1;
+                continue;
+            }
+            Some(LikeToken::Literal(expected)) if *expected == chars[c] => {
+                t += 1;
+                c += 1;
+                continue;
+            }
+            _ => {}
+        }
+        match backtrack {
+            Some((star_t, star_c)) => {
+                t = star_t + 1;
+                c = star_c + 1;
+                backtrack = Some((star_t, star_c + 1));
+            }
+            None => return false,
+        }
+    }
+    tokens[t..]
+        .iter()
+        .all(|token| *token == LikeToken::AnySequence)
+}
+
+impl Expression for SqlExpression {
+    fn evaluate(
+        &self,
+        context: &dyn EvaluationContext,
+    ) -> Result<Box<dyn Any + Send + Sync + 'static>, Box<dyn Error + Send + Sync + 'static>> {
+        Ok(self.eval(context).into_any())
+    }
+}
+
+impl Display for SqlExpression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let not = |negated: &bool| if *negated { "NOT " } else { "" };
+        match self {
+            SqlExpression::Constant(value) => write!(f, "{value}"),
+            SqlExpression::Property(name) => write!(f, "{name}"),
+            SqlExpression::Negate(expr) => write!(f, "(-{expr})"),
+            SqlExpression::Not(expr) => write!(f, "NOT ({expr})"),
+            SqlExpression::IsNull { expr, negated } => {
+                write!(f, "{expr} IS {}NULL", not(negated))
+            }
+            SqlExpression::Comparison {
+                operator,
+                left,
+                right,
+            } => write!(f, "({left} {} {right})", operator.symbol()),
+            SqlExpression::Logic {
+                operator,
+                left,
+                right,
+            } => {
+                let operator = match operator {
+                    LogicOperator::And => "AND",
+                    LogicOperator::Or => "OR",
+                };
+                write!(f, "({left} {operator} {right})")
+            }
+            SqlExpression::Between {
+                expr,
+                low,
+                high,
+                negated,
+            } => write!(f, "{expr} {}BETWEEN {low} AND {high}", not(negated)),
+            SqlExpression::In {
+                expr,
+                list,
+                negated,
+            } => {
+                let list = list
+                    .iter()
+                    .map(|value| value.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "{expr} {}IN ({list})", not(negated))
+            }
+            SqlExpression::Like {
+                expr,
+                pattern,
+                escape,
+                negated,
+                ..
+            } => {
+                let pattern = Value::String(pattern.clone());
+                write!(f, "{expr} {}LIKE {pattern}", not(negated))?;
+                if let Some(escape) = escape {
+                    write!(f, " ESCAPE '{escape}'")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    struct MapContext(HashMap<CheetahString, CheetahString>);
+
+    impl EvaluationContext for MapContext {
+        fn get(&self, name: &str) -> Option<&CheetahString> {
+            self.0.get(name)
+        }
+
+        fn key_values(&self) -> Option<HashMap<CheetahString, CheetahString>> {
+            Some(self.0.clone())
+        }
+    }
+
+    fn context(pairs: &[(&str, &str)]) -> MapContext {
+        MapContext(
+            pairs
+                .iter()
+                .map(|(k, v)| (CheetahString::from(*k), CheetahString::from(*v)))
+                .collect(),
+        )
+    }
+
+    fn property(name: &str) -> Box<SqlExpression> {
+        Box::new(SqlExpression::Property(CheetahString::from(name)))
+    }
+
+    fn constant(value: Value) -> Box<SqlExpression> {
+        Box::new(SqlExpression::Constant(value))
+    }
+
+    #[test]
+    fn comparison_with_missing_property_is_unknown() {
+        let expr = SqlExpression::Comparison {
+            operator: ComparisonOperator::NotEqual,
+            left: property("a"),
+            right: constant(Value::Long(1)),
+        };
+        assert_eq!(expr.eval(&context(&[])), Value::Null);
+        assert!(!expr.matches(&context(&[])));
+    }
+
+    #[test]
+    fn not_equal_with_incomparable_values_is_false() {
+        let expr = SqlExpression::Comparison {
+            operator: ComparisonOperator::NotEqual,
+            left: constant(Value::String(CheetahString::from("a"))),
+            right: constant(Value::Long(1)),
+        };
+        assert_eq!(expr.eval(&context(&[])), Value::Bool(false));
+        assert!(!expr.matches(&context(&[])));
+    }
+
+    #[test]
+    fn logic_uses_three_valued_semantics() {
+        let unknown = SqlExpression::Comparison {
+            operator: ComparisonOperator::Equal,
+            left: property("missing"),
+            right: constant(Value::Long(1)),
+        };
+        let or = SqlExpression::Logic {
+            operator: LogicOperator::Or,
+            left: Box::new(unknown.clone()),
+            right: constant(Value::Bool(true)),
+        };
+        let and = SqlExpression::Logic {
+            operator: LogicOperator::And,
+            left: Box::new(unknown),
+            right: constant(Value::Bool(true)),
+        };
+        assert_eq!(or.eval(&context(&[])), Value::Bool(true));
+        assert_eq!(and.eval(&context(&[])), Value::Null);
+    }
+
+    #[test]
+    fn between_and_in() {
+        let between = SqlExpression::Between {
+            expr: property("a"),
+            low: constant(Value::Long(1)),
+            high: constant(Value::Long(3)),
+            negated: false,
+        };
+        assert!(between.matches(&context(&[("a", "3")])));
+        assert!(!between.matches(&context(&[("a", "4")])));
+
+        let in_list = SqlExpression::In {
+            expr: property("b"),
+            list: vec![
+                Value::String(CheetahString::from_static_str("x")),
+                Value::String(CheetahString::from_static_str("y")),
+            ],
+            negated: true,
+        };
+        assert!(in_list.matches(&context(&[("b", "z")])));
+        assert!(!in_list.matches(&context(&[("b", "x")])));
+        assert!(!in_list.matches(&context(&[])));
+    }
+
+    #[test]
+    fn like_patterns() {
+        let tokens = compile_like_pattern("a%b_c", None);
+        let matches = |s: &str| like_matches(&tokens, &s.chars().collect::<Vec<_>>());
+        assert!(matches("abxc"));
+        assert!(matches("a123b4c"));
+        assert!(!matches("abc"));
+        assert!(!matches("abxcd"));
+
+        let tokens = compile_like_pattern("100!%", Some('!'));
+        assert!(like_matches(&tokens, &"100%".chars().collect::<Vec<_>>()));
+        assert!(!like_matches(&tokens, &"1000".chars().collect::<Vec<_>>()));
+    }
+
+    #[test]
+    fn evaluate_returns_boxed_bool() {
+        let expr = SqlExpression::IsNull {
+            expr: property("a"),
+            negated: false,
+        };
+        let result = expr.evaluate(&context(&[])).unwrap();
+        assert_eq!(result.downcast_ref::<bool>(), Some(&true));
+    }
+}
@@ -0,0 +1,184 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::any::Any;
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use cheetah_string::CheetahString;
+
+/// The result of evaluating a SQL92 expression.
+///
+/// Message properties are always carried as strings, so comparisons between a string and a
+/// numeric or boolean value try to convert the string first.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Long(i64),
+    Double(f64),
+    String(CheetahString),
+}
+
+impl Value {
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    pub fn is_number(&self) -> bool {
+        matches!(self, Value::Long(_) | Value::Double(_))
+    }
+
+    /// Interpret this value as a boolean, accepting `"true"`/`"false"` strings.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(value) => Some(*value),
+            Value::String(value) => parse_bool(value.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Compare two values, returning `None` if either side is null or the values are not
+    /// comparable.
+    pub fn compare(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::Null, _) | (_, Value::Null) => None,
+            (Value::Long(l), Value::Long(r)) => Some(l.cmp(r)),
+            (Value::Long(l), Value::Double(r)) => (*l as f64).partial_cmp(r),
+            (Value::Double(l), Value::Long(r)) => l.partial_cmp(&(*r as f64)),
+            (Value::Double(l), Value::Double(r)) => l.partial_cmp(r),
+            (Value::String(l), Value::String(r)) => Some(l.as_str().cmp(r.as_str())),
+            (Value::Bool(l), Value::Bool(r)) => Some(l.cmp(r)),
+            (Value::String(l), r) => Value::convert_string(l.as_str(), r)?.compare(r),
+            (l, Value::String(r)) => l.compare(&Value::convert_string(r.as_str(), l)?),
+            _ => None,
+        }
+    }
+
+    /// SQL equality, `None` if either side is null.
+    pub fn equals(&self, other: &Value) -> Option<bool> {
+        if self.is_null() || other.is_null() {
+            return None;
+        }
+        Some(self.compare(other) == Some(Ordering::Equal))
+    }
+
+    pub fn into_any(self) -> Box<dyn Any + Send + Sync + 'static> {
+        match self {
+            Value::Null => Box::new(()),
+            Value::Bool(value) => Box::new(value),
+            Value::Long(value) => Box::new(value),
+            Value::Double(value) => Box::new(value),
+            Value::String(value) => Box::new(value),
+        }
+    }
+
+    /// Convert a string to the type of `target`, so that it can be compared with it.
+    fn convert_string(value: &str, target: &Value) -> Option<Value> {
+        let value = value.trim();
+        match target {
+            Value::Long(_) => value
+                .parse::<i64>()
+                .map(Value::Long)
+                .ok()
+                .or_else(|| value.parse::<f64>().ok().map(Value::Double)),
+            Value::Double(_) => value.parse::<f64>().ok().map(Value::Double),
+            Value::Bool(_) => parse_bool(value).map(Value::Bool),
+            _ => None,
+        }
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    if value.eq_ignore_ascii_case("true") {
+        Some(true)
+    } else if value.eq_ignore_ascii_case("false") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Null => write!(f, "NULL"),
+            Value::Bool(value) => write!(f, "{}", if *value { "TRUE" } else { "FALSE" }),
+            Value::Long(value) => write!(f, "{value}"),
+            Value::Double(value) => write!(f, "{value:?}"),
+            Value::String(value) => write!(f, "'{}'", value.replace('\'', "''")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_numbers_of_different_types() {
+        assert_eq!(
+            Value::Long(3).compare(&Value::Double(3.5)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            Value::Double(3.0).compare(&Value::Long(3)),
+            Some(Ordering::Equal)
+        );
+    }
+
+    #[test]
+    fn compare_string_converts_to_number() {
+        let value = Value::String(CheetahString::from_static_str("10"));
+        assert_eq!(value.compare(&Value::Long(9)), Some(Ordering::Greater));
+        assert_eq!(Value::Long(11).compare(&value), Some(Ordering::Greater));
+        assert_eq!(value.compare(&Value::Double(10.5)), Some(Ordering::Less));
+    }
+
+    #[test]
+    fn compare_unconvertible_string_is_none() {
+        let value = Value::String(CheetahString::from_static_str("abc"));
+        assert_eq!(value.compare(&Value::Long(1)), None);
+    }
+
+    #[test]
+    fn compare_with_null_is_none() {
+        assert_eq!(Value::Null.compare(&Value::Long(1)), None);
+        assert_eq!(Value::Long(1).equals(&Value::Null), None);
+    }
+
+    #[test]
+    fn as_bool_accepts_strings() {
+        assert_eq!(
+            Value::String(CheetahString::from_static_str("TRUE")).as_bool(),
+            Some(true)
+        );
+        assert_eq!(Value::Long(1).as_bool(), None);
+    }
+
+    #[test]
+    fn into_any_unwraps_bool() {
+        let any = Value::Bool(true).into_any();
+        assert_eq!(any.downcast_ref::<bool>(), Some(&true));
+    }
+
+    #[test]
+    fn display_escapes_strings() {
+        let value = Value::String(CheetahString::from_static_str("it's"));
+        assert_eq!(value.to_string(), "'it''s'");
+    }
+}
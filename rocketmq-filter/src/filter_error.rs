@@ -0,0 +1,63 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum FilterError {
+    #[error("Invalid expression '{expression}': {message}")]
+    InvalidExpression { expression: String, message: String },
+
+    #[error("Filter spi type({0}) already exist!")]
+    DuplicateType(String),
+
+    #[error("Unsupported filter type: {0}")]
+    UnsupportedType(String),
+}
+
+pub type FilterResult<T> = std::result::Result<T, FilterError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_expression_error() {
+        let error = FilterError::InvalidExpression {
+            expression: "a >".to_string(),
+            message: "unexpected end of expression".to_string(),
+        };
+        assert_eq!(
+            format!("{}", error),
+            "Invalid expression 'a >': unexpected end of expression"
+        );
+    }
+
+    #[test]
+    fn duplicate_type_error() {
+        let error = FilterError::DuplicateType("SQL92".to_string());
+        assert_eq!(
+            format!("{}", error),
+            "Filter spi type(SQL92) already exist!"
+        );
+    }
+
+    #[test]
+    fn unsupported_type_error() {
+        let error = FilterError::UnsupportedType("XPATH".to_string());
+        assert_eq!(format!("{}", error), "Unsupported filter type: XPATH");
+    }
+}
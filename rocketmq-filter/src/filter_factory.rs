@@ -0,0 +1,124 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+
+use crate::expression::Expression;
+use crate::filter_error::FilterError;
+use crate::filter_error::FilterResult;
+use crate::filter_spi::FilterSpi;
+use crate::sql_filter::SqlFilter;
+
+static INSTANCE: Lazy<FilterFactory> = Lazy::new(FilterFactory::new);
+
+/// Filter factory: to get a FilterSpi.
+pub struct FilterFactory {
+    filter_spi_table: RwLock<HashMap<&'static str, Arc<dyn FilterSpi>>>,
+}
+
+impl FilterFactory {
+    /// Create a factory with the default SQL92 filter registered.
+    pub fn new() -> Self {
+        let factory = FilterFactory {
+            filter_spi_table: RwLock::new(HashMap::new()),
+        };
+        factory
+            .register(Arc::new(SqlFilter))
+            .expect("register SQL92 filter");
+        factory
+    }
+
+    /// The process wide factory.
+    pub fn instance() -> &'static FilterFactory {
+        &INSTANCE
+    }
+
+    /// Register a filter. An error is returned if the type was already registered.
+    pub fn register(&self, filter_spi: Arc<dyn FilterSpi>) -> FilterResult<()> {
+        let mut table = self.filter_spi_table.write();
+        let type_ = filter_spi.of_type();
+        if table.contains_key(type_) {
+            return Err(FilterError::DuplicateType(type_.to_string()));
+        }
+        table.insert(type_, filter_spi);
+        Ok(())
+    }
+
+    /// Un register a filter.
+    pub fn unregister(&self, type_: &str) -> Option<Arc<dyn FilterSpi>> {
+        self.filter_spi_table.write().remove(type_)
+    }
+
+    /// Get a filter registered, `None` if none exist.
+    pub fn get(&self, type_: &str) -> Option<Arc<dyn FilterSpi>> {
+        self.filter_spi_table.read().get(type_).cloned()
+    }
+
+    /// Compile `expr` with the filter registered for `type_`.
+    pub fn compile(
+        &self,
+        type_: &str,
+        expr: &str,
+    ) -> FilterResult<Box<dyn Expression + Send + Sync + 'static>> {
+        match self.get(type_) {
+            Some(filter_spi) => filter_spi.compile(expr),
+            None => Err(FilterError::UnsupportedType(type_.to_string())),
+        }
+    }
+}
+
+impl Default for FilterFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql_filter::SQL92;
+
+    #[test]
+    fn sql92_is_registered_by_default() {
+        let factory = FilterFactory::new();
+        assert_eq!(factory.get(SQL92).unwrap().of_type(), SQL92);
+        assert!(factory.compile(SQL92, "a > 1").is_ok());
+    }
+
+    #[test]
+    fn register_duplicate_type_fails() {
+        let factory = FilterFactory::new();
+        assert!(matches!(
+            factory.register(Arc::new(SqlFilter)),
+            Err(FilterError::DuplicateType(_))
+        ));
+    }
+
+    #[test]
+    fn unregister_removes_filter() {
+        let factory = FilterFactory::new();
+        assert!(factory.unregister(SQL92).is_some());
+        assert!(factory.get(SQL92).is_none());
+        assert!(matches!(
+            factory.compile(SQL92, "a > 1"),
+            Err(FilterError::UnsupportedType(_))
+        ));
+    }
+}
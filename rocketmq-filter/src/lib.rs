@@ -16,4 +16,9 @@
  */
 
 pub mod expression;
+pub mod filter_error;
+pub mod filter_factory;
+pub mod filter_spi;
+pub mod parser;
+pub mod sql_filter;
 pub mod utils;
@@ -0,0 +1,312 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Identifier(String),
+    StringLiteral(String),
+    LongLiteral(i64),
+    DoubleLiteral(f64),
+    True,
+    False,
+    Null,
+    And,
+    Or,
+    Not,
+    Between,
+    In,
+    Like,
+    Escape,
+    Is,
+    Equal,
+    NotEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    LessThan,
+    LessThanOrEqual,
+    Plus,
+    Minus,
+    LeftParen,
+    RightParen,
+    Comma,
+    Eof,
+}
+
+/// A token together with the character offset it starts at.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned {
+    pub token: Token,
+    pub position: usize,
+}
+
+/// Splits a SQL92 selector into tokens. Keywords are case-insensitive.
+pub struct Lexer<'a> {
+    input: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Lexer {
+            input,
+            chars: input.char_indices().peekable(),
+        }
+    }
+
+    pub fn tokenize(mut self) -> Result<Vec<Spanned>, String> {
+        let mut tokens = Vec::new();
+        loop {
+            let spanned = self.next_token()?;
+            let eof = spanned.token == Token::Eof;
+            tokens.push(spanned);
+            if eof {
+                return Ok(tokens);
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Spanned, String> {
+        while let Some((_, c)) = self.chars.peek() {
+            if c.is_whitespace() {
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+        let Some((position, c)) = self.chars.next() else {
+            return Ok(Spanned {
+                token: Token::Eof,
+                position: self.input.len(),
+            });
+        };
+        let token = match c {
+            '=' => Token::Equal,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            ',' => Token::Comma,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '<' => match self.chars.peek() {
+                Some((_, '>')) => {
+                    self.chars.next();
+                    Token::NotEqual
+                }
+                Some((_, '=')) => {
+                    self.chars.next();
+                    Token::LessThanOrEqual
+                }
+                _ => Token::LessThan,
+            },
+            '!' => match self.chars.peek() {
+                Some((_, '=')) => {
+                    self.chars.next();
+                    Token::NotEqual
+                }
+                _ => return Err(format!("unexpected character '!' at position {position}")),
+            },
+            '>' => match self.chars.peek() {
+                Some((_, '=')) => {
+                    self.chars.next();
+                    Token::GreaterThanOrEqual
+                }
+                _ => Token::GreaterThan,
+            },
+            '\'' => Token::StringLiteral(self.string_literal(position)?),
+            c if c.is_ascii_digit() || c == '.' => self.number(position)?,
+            c if is_identifier_start(c) => self.identifier_or_keyword(position),
+            c => return Err(format!("unexpected character '{c}' at position {position}")),
+        };
+        Ok(Spanned { token, position })
+    }
+
+    fn string_literal(&mut self, start: usize) -> Result<String, String> {
+        let mut value = String::new();
+        loop {
+            match self.chars.next() {
+                Some((_, '\'')) => {
+                    // '' is an escaped single quote
+                    if let Some((_, '\'')) = self.chars.peek() {
+                        self.chars.next();
+                        value.push('\'');
+                    } else {
+                        return Ok(value);
+                    }
+                }
+                Some((_, c)) => value.push(c),
+                None => return Err(format!("unterminated string literal at position {start}")),
+            }
+        }
+    }
+
+    fn number(&mut self, start: usize) -> Result<Token, String> {
+        let mut end = start + 1;
+        let mut previous = None;
+        while let Some((index, c)) = self.chars.peek() {
+            let accept = c.is_ascii_alphanumeric()
+                || *c == '.'
+                || ((*c == '+' || *c == '-') && matches!(previous, Some('e' | 'E')));
+            if !accept {
+                break;
+            }
+            previous = Some(*c);
+            end = index + c.len_utf8();
+            self.chars.next();
+        }
+        let text = &self.input[start..end];
+        let invalid = || format!("invalid number '{text}' at position {start}");
+        if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+            return i64::from_str_radix(hex, 16)
+                .map(Token::LongLiteral)
+                .map_err(|_| invalid());
+        }
+        if !text.contains(['.', 'e', 'E']) {
+            let digits = text.trim_end_matches(['l', 'L']);
+            return digits
+                .parse::<i64>()
+                .map(Token::LongLiteral)
+                .map_err(|_| invalid());
+        }
+        text.trim_end_matches(['d', 'D', 'f', 'F'])
+            .parse::<f64>()
+            .map(Token::DoubleLiteral)
+            .map_err(|_| invalid())
+    }
+
+    fn identifier_or_keyword(&mut self, start: usize) -> Token {
+        let mut end = start + 1;
+        while let Some((index, c)) = self.chars.peek() {
+            if !is_identifier_part(*c) {
+                break;
+            }
+            end = index + c.len_utf8();
+            self.chars.next();
+        }
+        let text = &self.input[start..end];
+        match text.to_ascii_uppercase().as_str() {
+            "TRUE" => Token::True,
+            "FALSE" => Token::False,
+            "NULL" => Token::Null,
+            "AND" => Token::And,
+            "OR" => Token::Or,
+            "NOT" => Token::Not,
+            "BETWEEN" => Token::Between,
+            "IN" => Token::In,
+            "LIKE" => Token::Like,
+            "ESCAPE" => Token::Escape,
+            "IS" => Token::Is,
+            _ => Token::Identifier(text.to_string()),
+        }
+    }
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '$'
+}
+
+fn is_identifier_part(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$' || c == '.'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(input: &str) -> Vec<Token> {
+        Lexer::new(input)
+            .tokenize()
+            .unwrap()
+            .into_iter()
+            .map(|spanned| spanned.token)
+            .collect()
+    }
+
+    #[test]
+    fn tokenize_comparison() {
+        assert_eq!(
+            tokens("a >= 10 and b <> 'x''y'"),
+            vec![
+                Token::Identifier("a".to_string()),
+                Token::GreaterThanOrEqual,
+                Token::LongLiteral(10),
+                Token::And,
+                Token::Identifier("b".to_string()),
+                Token::NotEqual,
+                Token::StringLiteral("x'y".to_string()),
+                Token::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_not_equal_operators() {
+        assert_eq!(
+            tokens("a <> 1 or b != 2"),
+            vec![
+                Token::Identifier("a".to_string()),
+                Token::NotEqual,
+                Token::LongLiteral(1),
+                Token::Or,
+                Token::Identifier("b".to_string()),
+                Token::NotEqual,
+                Token::LongLiteral(2),
+                Token::Eof,
+            ]
+        );
+        assert!(Lexer::new("a ! 1").tokenize().is_err());
+    }
+
+    #[test]
+    fn tokenize_numbers() {
+        assert_eq!(
+            tokens("1.5 2e3 0x1F 7L .5"),
+            vec![
+                Token::DoubleLiteral(1.5),
+                Token::DoubleLiteral(2000.0),
+                Token::LongLiteral(31),
+                Token::LongLiteral(7),
+                Token::DoubleLiteral(0.5),
+                Token::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_keywords_case_insensitive() {
+        assert_eq!(
+            tokens("NoT BeTwEeN iS nUlL"),
+            vec![
+                Token::Not,
+                Token::Between,
+                Token::Is,
+                Token::Null,
+                Token::Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn unterminated_string_is_error() {
+        assert!(Lexer::new("a = 'abc").tokenize().is_err());
+    }
+
+    #[test]
+    fn invalid_number_is_error() {
+        assert!(Lexer::new("a = 12ab").tokenize().is_err());
+    }
+}
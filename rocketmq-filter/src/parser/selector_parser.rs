@@ -0,0 +1,474 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use cheetah_string::CheetahString;
+
+use crate::expression::sql_expression::compile_like_pattern;
+use crate::expression::sql_expression::ComparisonOperator;
+use crate::expression::sql_expression::LogicOperator;
+use crate::expression::sql_expression::SqlExpression;
+use crate::expression::value::Value;
+use crate::filter_error::FilterError;
+use crate::filter_error::FilterResult;
+use crate::parser::lexer::Lexer;
+use crate::parser::lexer::Spanned;
+use crate::parser::lexer::Token;
+
+/// Recursive-descent parser for the SQL92 subset supported by message selectors.
+///
+/// ```text
+/// selector    := or_expr
+/// or_expr     := and_expr ( OR and_expr )*
+/// and_expr    := equality ( AND equality )*
+/// equality    := comparison ( ( '=' | '<>' ) comparison | IS [NOT] NULL )*
+/// comparison  := unary ( ( '>' | '>=' | '<' | '<=' ) unary
+///                      | [NOT] BETWEEN unary AND unary
+///                      | [NOT] IN '(' literal ( ',' literal )* ')'
+///                      | [NOT] LIKE string [ ESCAPE string ] )*
+/// unary       := ( '+' | '-' | NOT ) unary | primary
+/// primary     := literal | identifier | '(' or_expr ')'
+/// ```
+pub struct SelectorParser {
+    tokens: Vec<Spanned>,
+    cursor: usize,
+}
+
+impl SelectorParser {
+    /// Parse `sql` into an executable expression.
+    pub fn parse(sql: &str) -> FilterResult<SqlExpression> {
+        let invalid = |message: String| FilterError::InvalidExpression {
+            expression: sql.to_string(),
+            message,
+        };
+        let tokens = Lexer::new(sql).tokenize().map_err(invalid)?;
+        let mut parser = SelectorParser { tokens, cursor: 0 };
+        parser.selector().map_err(invalid)
+    }
+
+    fn selector(&mut self) -> Result<SqlExpression, String> {
+        let expr = self.or_expression()?;
+        if self.peek() != &Token::Eof {
+            return Err(self.unexpected());
+        }
+        Self::check_boolean(&expr)?;
+        Ok(expr)
+    }
+
+    fn or_expression(&mut self) -> Result<SqlExpression, String> {
+        let mut left = self.and_expression()?;
+        while self.consume(&Token::Or) {
+            let right = self.and_expression()?;
+            left = Self::logic(LogicOperator::Or, left, right)?;
+        }
+        Ok(left)
+    }
+
+    fn and_expression(&mut self) -> Result<SqlExpression, String> {
+        let mut left = self.equality_expression()?;
+        while self.consume(&Token::And) {
+            let right = self.equality_expression()?;
+            left = Self::logic(LogicOperator::And, left, right)?;
+        }
+        Ok(left)
+    }
+
+    fn equality_expression(&mut self) -> Result<SqlExpression, String> {
+        let mut left = self.comparison_expression()?;
+        loop {
+            let operator = match self.peek() {
+                Token::Equal => ComparisonOperator::Equal,
+                Token::NotEqual => ComparisonOperator::NotEqual,
+                Token::Is => {
+                    self.advance();
+                    let negated = self.consume(&Token::Not);
+                    self.expect(&Token::Null, "NULL")?;
+                    left = SqlExpression::IsNull {
+                        expr: Box::new(left),
+                        negated,
+                    };
+                    continue;
+                }
+                _ => return Ok(left),
+            };
+            self.advance();
+            let right = self.comparison_expression()?;
+            Self::check_equal_operand(&left)?;
+            Self::check_equal_operand(&right)?;
+            left = SqlExpression::Comparison {
+                operator,
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+    }
+
+    fn comparison_expression(&mut self) -> Result<SqlExpression, String> {
+        let mut left = self.unary_expression()?;
+        loop {
+            let operator = match self.peek() {
+                Token::GreaterThan => ComparisonOperator::GreaterThan,
+                Token::GreaterThanOrEqual => ComparisonOperator::GreaterThanOrEqual,
+                Token::LessThan => ComparisonOperator::LessThan,
+                Token::LessThanOrEqual => ComparisonOperator::LessThanOrEqual,
+                Token::Not
+                    if matches!(self.peek_nth(1), Token::Between | Token::In | Token::Like) =>
+                {
+                    self.advance();
+                    left = self.negatable_predicate(left, true)?;
+                    continue;
+                }
+                Token::Between | Token::In | Token::Like => {
+                    left = self.negatable_predicate(left, false)?;
+                    continue;
+                }
+                _ => return Ok(left),
+            };
+            self.advance();
+            let right = self.unary_expression()?;
+            Self::check_less_than_operand(&left)?;
+            Self::check_less_than_operand(&right)?;
+            left = SqlExpression::Comparison {
+                operator,
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+    }
+
+    /// `BETWEEN`, `IN` or `LIKE`, with the optional `NOT` already consumed.
+    fn negatable_predicate(
+        &mut self,
+        expr: SqlExpression,
+        negated: bool,
+    ) -> Result<SqlExpression, String> {
+        match self.advance() {
+            Token::Between => {
+                let low = self.unary_expression()?;
+                self.expect(&Token::And, "AND")?;
+                let high = self.unary_expression()?;
+                for operand in [&expr, &low, &high] {
+                    Self::check_less_than_operand(operand)?;
+                }
+                Ok(SqlExpression::Between {
+                    expr: Box::new(expr),
+                    low: Box::new(low),
+                    high: Box::new(high),
+                    negated,
+                })
+            }
+            Token::In => {
+                self.expect(&Token::LeftParen, "(")?;
+                let mut list = vec![self.literal()?];
+                while self.consume(&Token::Comma) {
+                    list.push(self.literal()?);
+                }
+                self.expect(&Token::RightParen, ")")?;
+                Ok(SqlExpression::In {
+                    expr: Box::new(expr),
+                    list,
+                    negated,
+                })
+            }
+            Token::Like => {
+                let pattern = self.string_literal()?;
+                let escape = if self.consume(&Token::Escape) {
+                    let escape = self.string_literal()?;
+                    let mut chars = escape.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => Some(c),
+                        _ => {
+                            return Err(format!(
+                                "ESCAPE string '{escape}' must be exactly one character"
+                            ))
+                        }
+                    }
+                } else {
+                    None
+                };
+                Ok(SqlExpression::Like {
+                    expr: Box::new(expr),
+                    tokens: compile_like_pattern(&pattern, escape),
+                    pattern: CheetahString::from_string(pattern),
+                    escape,
+                    negated,
+                })
+            }
+            _ => unreachable!("caller checked BETWEEN, IN or LIKE"),
+        }
+    }
+
+    fn unary_expression(&mut self) -> Result<SqlExpression, String> {
+        match self.peek() {
+            Token::Plus => {
+                self.advance();
+                self.unary_expression()
+            }
+            Token::Minus => {
+                self.advance();
+                Ok(match self.unary_expression()? {
+                    SqlExpression::Constant(Value::Long(value)) => {
+                        SqlExpression::Constant(Value::Long(value.wrapping_neg()))
+                    }
+                    SqlExpression::Constant(Value::Double(value)) => {
+                        SqlExpression::Constant(Value::Double(-value))
+                    }
+                    expr => SqlExpression::Negate(Box::new(expr)),
+                })
+            }
+            Token::Not => {
+                self.advance();
+                let expr = self.unary_expression()?;
+                Self::check_boolean(&expr)?;
+                Ok(SqlExpression::Not(Box::new(expr)))
+            }
+            _ => self.primary_expression(),
+        }
+    }
+
+    fn primary_expression(&mut self) -> Result<SqlExpression, String> {
+        match self.peek().clone() {
+            Token::Identifier(name) => {
+                self.advance();
+                Ok(SqlExpression::Property(CheetahString::from_string(name)))
+            }
+            Token::LeftParen => {
+                self.advance();
+                let expr = self.or_expression()?;
+                self.expect(&Token::RightParen, ")")?;
+                Ok(expr)
+            }
+            _ => self.literal().map(SqlExpression::Constant),
+        }
+    }
+
+    fn literal(&mut self) -> Result<Value, String> {
+        let value = match self.peek() {
+            Token::StringLiteral(value) => Value::String(CheetahString::from_string(value.clone())),
+            Token::LongLiteral(value) => Value::Long(*value),
+            Token::DoubleLiteral(value) => Value::Double(*value),
+            Token::True => Value::Bool(true),
+            Token::False => Value::Bool(false),
+            Token::Null => Value::Null,
+            _ => return Err(self.unexpected()),
+        };
+        self.advance();
+        Ok(value)
+    }
+
+    fn string_literal(&mut self) -> Result<String, String> {
+        match self.peek().clone() {
+            Token::StringLiteral(value) => {
+                self.advance();
+                Ok(value)
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn logic(
+        operator: LogicOperator,
+        left: SqlExpression,
+        right: SqlExpression,
+    ) -> Result<SqlExpression, String> {
+        Self::check_boolean(&left)?;
+        Self::check_boolean(&right)?;
+        Ok(SqlExpression::Logic {
+            operator,
+            left: Box::new(left),
+            right: Box::new(right),
+        })
+    }
+
+    fn check_boolean(expr: &SqlExpression) -> Result<(), String> {
+        if expr.is_boolean() {
+            Ok(())
+        } else {
+            Err(format!("Value '{expr}' is not a boolean expression"))
+        }
+    }
+
+    fn check_equal_operand(expr: &SqlExpression) -> Result<(), String> {
+        match expr {
+            SqlExpression::Constant(Value::Null) => {
+                Err("'=' and '<>' cannot be used with NULL, use IS [NOT] NULL".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_less_than_operand(expr: &SqlExpression) -> Result<(), String> {
+        match expr {
+            SqlExpression::Constant(value) if !value.is_number() => {
+                Err(format!("Value '{value}' cannot be compared"))
+            }
+            SqlExpression::Constant(_) | SqlExpression::Property(_) | SqlExpression::Negate(_) => {
+                Ok(())
+            }
+            expr => Err(format!("Value '{expr}' cannot be compared")),
+        }
+    }
+
+    fn peek(&self) -> &Token {
+        self.peek_nth(0)
+    }
+
+    fn peek_nth(&self, n: usize) -> &Token {
+        // the token list always ends with Eof
+        let index = (self.cursor + n).min(self.tokens.len() - 1);
+        &self.tokens[index].token
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.peek().clone();
+        if self.cursor < self.tokens.len() - 1 {
+            self.cursor += 1;
+        }
+        token
+    }
+
+    fn consume(&mut self, token: &Token) -> bool {
+        if self.peek() == token {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token, display: &str) -> Result<(), String> {
+        if self.consume(token) {
+            Ok(())
+        } else {
+            Err(format!("expected {display}, {}", self.unexpected()))
+        }
+    }
+
+    fn unexpected(&self) -> String {
+        let spanned = &self.tokens[self.cursor];
+        match &spanned.token {
+            Token::Eof => "unexpected end of expression".to_string(),
+            token => format!(
+                "unexpected token {token:?} at position {}",
+                spanned.position
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::expression::evaluation_context::EvaluationContext;
+
+    struct MapContext(HashMap<CheetahString, CheetahString>);
+
+    impl EvaluationContext for MapContext {
+        fn get(&self, name: &str) -> Option<&CheetahString> {
+            self.0.get(name)
+        }
+
+        fn key_values(&self) -> Option<HashMap<CheetahString, CheetahString>> {
+            Some(self.0.clone())
+        }
+    }
+
+    fn matches(sql: &str, pairs: &[(&str, &str)]) -> bool {
+        let context = MapContext(
+            pairs
+                .iter()
+                .map(|(k, v)| (CheetahString::from(*k), CheetahString::from(*v)))
+                .collect(),
+        );
+        SelectorParser::parse(sql).unwrap().matches(&context)
+    }
+
+    #[test]
+    fn parse_numeric_comparisons() {
+        assert!(matches("a > 3", &[("a", "4")]));
+        assert!(matches("a >= 3.5", &[("a", "3.5")]));
+        assert!(matches("a < -1", &[("a", "-2")]));
+        assert!(!matches("a <= 3", &[("a", "4")]));
+        assert!(!matches("a > 3", &[]));
+    }
+
+    #[test]
+    fn parse_string_and_bool_equality() {
+        assert!(matches("region = 'hangzhou'", &[("region", "hangzhou")]));
+        assert!(matches("region <> 'hangzhou'", &[("region", "beijing")]));
+        assert!(matches("flag = TRUE", &[("flag", "true")]));
+        assert!(!matches("region = 'hangzhou'", &[]));
+        assert!(!matches("region <> 'hangzhou'", &[]));
+    }
+
+    #[test]
+    fn parse_not_equal_operators() {
+        assert!(matches("a <> 3", &[("a", "4")]));
+        assert!(matches("a != 3", &[("a", "4")]));
+        assert!(!matches("a != 3", &[("a", "3")]));
+        // a string and a number can't be compared, which is unknown rather than different
+        assert!(!matches("region <> 3", &[("region", "hangzhou")]));
+        assert!(!matches("region != 3", &[("region", "hangzhou")]));
+    }
+
+    #[test]
+    fn parse_between_in_like_and_null() {
+        assert!(matches("a BETWEEN 1 AND 3", &[("a", "2")]));
+        assert!(matches("a NOT BETWEEN 1 AND 3", &[("a", "5")]));
+        assert!(matches("b IN ('x', 'y')", &[("b", "y")]));
+        assert!(matches("b NOT IN ('x', 'y')", &[("b", "z")]));
+        assert!(matches("c LIKE 'ab%'", &[("c", "abc")]));
+        assert!(matches("c NOT LIKE 'ab_'", &[("c", "abcd")]));
+        assert!(matches("c LIKE 'a!_%' ESCAPE '!'", &[("c", "a_b")]));
+        assert!(matches("d IS NULL", &[]));
+        assert!(matches("d IS NOT NULL", &[("d", "")]));
+    }
+
+    #[test]
+    fn parse_logic_precedence() {
+        let pairs = [("a", "1"), ("b", "2")];
+        assert!(matches("a = 1 OR b = 3 AND a = 2", &pairs));
+        assert!(!matches("(a = 1 OR b = 3) AND a = 2", &pairs));
+        assert!(matches("NOT (a = 2) and b = 2", &pairs));
+    }
+
+    #[test]
+    fn parse_rejects_invalid_expressions() {
+        for sql in [
+            "",
+            "a >",
+            "a = NULL",
+            "a > 'abc'",
+            "'abc'",
+            "a = 1 AND 3",
+            "a IN ()",
+            "a LIKE 'x' ESCAPE 'ab'",
+            "a = 1)",
+        ] {
+            assert!(SelectorParser::parse(sql).is_err(), "{sql} should fail");
+        }
+    }
+
+    #[test]
+    fn parse_error_mentions_expression() {
+        let error = SelectorParser::parse("a >").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid expression 'a >': unexpected end of expression"
+        );
+    }
+}
@@ -39,8 +39,6 @@ impl Default for BloomFilter {
     }
 }
 
-#[allow(unused_mut)]
-#[allow(unused_variables)]
 impl BloomFilter {
     pub fn new(f: i32, n: i32) -> Result<Self, &'static str> {
         if !(1..100).contains(&f) {
@@ -83,6 +81,27 @@ impl BloomFilter {
         self.m
     }
 
+    /// Calculate the `k` bit positions of `s`, using double hashing on murmur3 x64 128.
+    pub fn calc_bit_positions(&self, s: &str) -> Vec<i32> {
+        let hash64 = murmur3_x64_128(s.as_bytes()).0;
+        let hash1 = hash64 as i32;
+        let hash2 = (hash64 >> 32) as i32;
+        (1..=self.k)
+            .map(|i| {
+                let mut combined_hash = hash1.wrapping_add(i.wrapping_mul(hash2));
+                if combined_hash < 0 {
+                    combined_hash = !combined_hash;
+                }
+                combined_hash % self.m
+            })
+            .collect()
+    }
+
+    /// Calculate bit positions of `s` to construct a `BloomFilterData`.
+    pub fn generate(&self, s: &str) -> BloomFilterData {
+        BloomFilterData::new(self.calc_bit_positions(s), self.m as u32)
+    }
+
     pub fn is_valid(&self, filter_data: Option<&BloomFilterData>) -> bool {
         match filter_data {
             Some(data) => {
@@ -92,23 +111,159 @@ impl BloomFilter {
         }
     }
 
+    /// Set the bits of `filter_data` to `bits`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `filter_data` does not belong to this filter or `bits` has a wrong length.
     pub fn hash_to(&self, filter_data: &BloomFilterData, bits: &mut BitsArray) {
-        /* if !self.is_valid(filter_data) {
-            panic!(
-                "Bloom filter data may not belong to this filter! {:?}, {:?}",
-                filter_data, self
-            );
+        if !self.is_valid(Some(filter_data)) {
+            panic!("Bloom filter data may not belong to this filter! {filter_data:?}");
         }
-        self.hash_to_positions(filter_data.bit_pos(), bits);*/
-        unimplemented!("hash_to");
+        self.hash_to_positions(filter_data.bit_pos(), bits);
     }
 
     // Helper method for setting bits at given positions
-    pub fn hash_to_positions(&self, bit_positions: &[usize], bits: &mut BitsArray) {
-        /*self.check(bits);
+    pub fn hash_to_positions(&self, bit_positions: &[i32], bits: &mut BitsArray) {
+        self.check(bits);
         for &i in bit_positions {
-            bits.set_bit(i, true);
-        }*/
-        unimplemented!("hash_to_positions");
+            bits.set_bit(i as usize, true);
+        }
+    }
+
+    /// Check whether all bits of `filter_data` are set in `bits`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `filter_data` does not belong to this filter or `bits` has a wrong length.
+    pub fn is_hit(&self, filter_data: &BloomFilterData, bits: &BitsArray) -> bool {
+        if !self.is_valid(Some(filter_data)) {
+            panic!("Bloom filter data may not belong to this filter! {filter_data:?}");
+        }
+        self.is_hit_positions(filter_data.bit_pos(), bits)
+    }
+
+    pub fn is_hit_positions(&self, bit_positions: &[i32], bits: &BitsArray) -> bool {
+        self.check(bits);
+        bit_positions.iter().all(|&i| bits.get_bit(i as usize))
+    }
+
+    fn check(&self, bits: &BitsArray) {
+        if bits.bit_length() != self.m as usize {
+            panic!(
+                "Length({}) of bits in BitsArray is not equal to {}!",
+                bits.bit_length(),
+                self.m
+            );
+        }
+    }
+}
+
+/// MurmurHash3 x64 128 with seed 0, returning `(h1, h2)`.
+fn murmur3_x64_128(data: &[u8]) -> (u64, u64) {
+    const C1: u64 = 0x87c3_7b91_1142_53d5;
+    const C2: u64 = 0x4cf5_ad43_2745_937f;
+
+    fn fmix64(mut k: u64) -> u64 {
+        k ^= k >> 33;
+        k = k.wrapping_mul(0xff51_afd7_ed55_8ccd);
+        k ^= k >> 33;
+        k = k.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+        k ^ (k >> 33)
+    }
+
+    fn mix_k1(k1: u64) -> u64 {
+        k1.wrapping_mul(C1).rotate_left(31).wrapping_mul(C2)
+    }
+
+    fn mix_k2(k2: u64) -> u64 {
+        k2.wrapping_mul(C2).rotate_left(33).wrapping_mul(C1)
+    }
+
+    let (mut h1, mut h2) = (0u64, 0u64);
+    let mut blocks = data.chunks_exact(16);
+    for block in &mut blocks {
+        let k1 = u64::from_le_bytes(block[..8].try_into().unwrap());
+        let k2 = u64::from_le_bytes(block[8..].try_into().unwrap());
+
+        h1 ^= mix_k1(k1);
+        h1 = h1
+            .rotate_left(27)
+            .wrapping_add(h2)
+            .wrapping_mul(5)
+            .wrapping_add(0x52dc_e729);
+
+        h2 ^= mix_k2(k2);
+        h2 = h2
+            .rotate_left(31)
+            .wrapping_add(h1)
+            .wrapping_mul(5)
+            .wrapping_add(0x3849_5ab5);
+    }
+
+    let tail = blocks.remainder();
+    if !tail.is_empty() {
+        let (mut k1, mut k2) = (0u64, 0u64);
+        for (i, &byte) in tail.iter().enumerate() {
+            if i < 8 {
+                k1 |= (byte as u64) << (8 * i);
+            } else {
+                k2 |= (byte as u64) << (8 * (i - 8));
+            }
+        }
+        h1 ^= mix_k1(k1);
+        if tail.len() > 8 {
+            h2 ^= mix_k2(k2);
+        }
+    }
+
+    h1 ^= data.len() as u64;
+    h2 ^= data.len() as u64;
+    h1 = h1.wrapping_add(h2);
+    h2 = h2.wrapping_add(h1);
+    h1 = fmix64(h1);
+    h2 = fmix64(h2);
+    h1 = h1.wrapping_add(h2);
+    h2 = h2.wrapping_add(h1);
+    (h1, h2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn murmur3_known_vectors() {
+        assert_eq!(murmur3_x64_128(b""), (0, 0));
+        // guava: Hashing.murmur3_128().hashString("hello", UTF_8)
+        assert_eq!(
+            murmur3_x64_128(b"hello").0,
+            u64::from_le_bytes([0x02, 0x9b, 0xbd, 0x41, 0xb3, 0xa7, 0xd8, 0xcb])
+        );
+    }
+
+    #[test]
+    fn generate_is_valid_and_hits() {
+        let bloom_filter = BloomFilter::new(10, 64).unwrap();
+        let data = bloom_filter.generate("group#topic");
+        assert!(bloom_filter.is_valid(Some(&data)));
+        assert!(data
+            .bit_pos()
+            .iter()
+            .all(|&pos| pos >= 0 && pos < bloom_filter.m()));
+
+        let mut bits = BitsArray::create(bloom_filter.m() as usize);
+        assert!(!bloom_filter.is_hit(&data, &bits));
+        bloom_filter.hash_to(&data, &mut bits);
+        assert!(bloom_filter.is_hit(&data, &bits));
+    }
+
+    #[test]
+    #[should_panic]
+    fn hash_to_rejects_wrong_length() {
+        let bloom_filter = BloomFilter::new(10, 64).unwrap();
+        let data = bloom_filter.generate("group#topic");
+        let mut bits = BitsArray::create(8);
+        bloom_filter.hash_to(&data, &mut bits);
     }
 }
@@ -75,12 +75,11 @@ async fn main() -> RocketMQResult<()> {
         .set_server_config(ServerConfig {
             listen_port: args.port,
             bind_address: args.ip,
+            ..ServerConfig::default()
         })
         .build()
         .boot()
-        .await;
-
-    Ok(())
+        .await
 }
 
 #[derive(Parser, Debug)]
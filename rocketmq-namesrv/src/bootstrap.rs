@@ -22,6 +22,9 @@ use cheetah_string::CheetahString;
 use rocketmq_common::common::namesrv::namesrv_config::NamesrvConfig;
 use rocketmq_common::common::server::config::ServerConfig;
 use rocketmq_common::utils::network_util::NetworkUtil;
+use rocketmq_error::RocketMQResult;
+use rocketmq_remoting::acl::plain_access_validator::PlainAccessValidator;
+use rocketmq_remoting::acl::plain_permission_manager::PlainPermissionManager;
 use rocketmq_remoting::base::channel_event_listener::ChannelEventListener;
 use rocketmq_remoting::clients::rocketmq_default_impl::RocketmqDefaultClient;
 use rocketmq_remoting::clients::RemotingClient;
@@ -33,6 +36,7 @@ use rocketmq_runtime::RocketMQRuntime;
 use rocketmq_rust::wait_for_signal;
 use rocketmq_rust::ArcMut;
 use tokio::sync::broadcast;
+use tracing::error;
 use tracing::info;
 
 use crate::processor::ClientRequestProcessor;
@@ -55,16 +59,19 @@ struct NameServerRuntime {
     inner: ArcMut<NameServerRuntimeInner>,
     // receiver for shutdown signal
     shutdown_rx: Option<tokio::sync::broadcast::Receiver<()>>,
+    permission_manager: Option<Arc<PlainPermissionManager>>,
 }
 
 impl NameServerBootstrap {
-    pub async fn boot(mut self) {
+    pub async fn boot(mut self) -> RocketMQResult<()> {
+        self.name_server_runtime.initial_acl()?;
         let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
         self.name_server_runtime.shutdown_rx = Some(shutdown_rx);
         tokio::join!(
             self.name_server_runtime.start(),
             wait_for_signal_inner(shutdown_tx)
         );
+        Ok(())
     }
 }
 
@@ -84,14 +91,21 @@ impl NameServerRuntime {
         let (notify_conn_disconnect, _) = broadcast::channel::<SocketAddr>(100);
         let receiver = notify_conn_disconnect.subscribe();
         let request_processor = self.init_processors(receiver);
-        let server = RocketMQServer::new(Arc::new(self.inner.server_config.clone()));
+        let mut server = RocketMQServer::new(Arc::new(self.inner.server_config.clone()));
+        if let Some(permission_manager) = self.permission_manager.clone() {
+            server.register_rpc_hook(Arc::new(PlainAccessValidator::new_admin_only(
+                permission_manager,
+            )));
+        }
         let channel_event_listener = self
             .inner
             .broker_housekeeping_service
             .take()
             .map(|item| item as Arc<dyn ChannelEventListener>);
         tokio::spawn(async move {
-            server.run(request_processor, channel_event_listener).await;
+            if let Err(e) = server.run(request_processor, channel_event_listener).await {
+                error!("Failed to start name server remoting server: {}", e);
+            }
         });
         let namesrv = CheetahString::from_string(format!(
             "{}:{}",
@@ -114,6 +128,24 @@ impl NameServerRuntime {
         }
     }
 
+    /// Loads the acl config when acl is enabled. A name server that cannot load it must not
+    /// start, as it would otherwise serve requests without checking them.
+    fn initial_acl(&mut self) -> RocketMQResult<()> {
+        if !self.inner.name_server_config.acl_enable {
+            return Ok(());
+        }
+        let permission_manager = Arc::new(PlainPermissionManager::new(
+            self.inner.name_server_config.acl_config_path.as_str(),
+        ));
+        if let Err(e) = permission_manager.load() {
+            error!("Failed to load acl config: {}", e);
+            return Err(e);
+        }
+        self.permission_manager = Some(permission_manager);
+        info!("The name server enables acl");
+        Ok(())
+    }
+
     #[inline]
     fn shutdown(&mut self) {
         if let Some(runtime) = self.name_server_runtime.take() {
@@ -229,6 +261,7 @@ impl Builder {
                 name_server_runtime: Some(runtime),
                 inner,
                 shutdown_rx: None,
+                permission_manager: None,
             },
         }
     }
@@ -0,0 +1,84 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+use cheetah_string::CheetahString;
+
+use crate::acl::acl_utils;
+use crate::acl::session_credentials::SessionCredentials;
+use crate::protocol::remoting_command::RemotingCommand;
+use crate::runtime::RPCHook;
+
+/// Signs every outgoing request with the configured AccessKey/SecretKey.
+///
+/// The custom header is flattened into `ext_fields` first so the signature covers exactly what
+/// the broker receives.
+pub struct AclClientRPCHook {
+    session_credentials: SessionCredentials,
+}
+
+impl AclClientRPCHook {
+    pub fn new(session_credentials: SessionCredentials) -> Self {
+        Self {
+            session_credentials,
+        }
+    }
+
+    pub fn session_credentials(&self) -> &SessionCredentials {
+        &self.session_credentials
+    }
+}
+
+impl RPCHook for AclClientRPCHook {
+    fn do_before_request(
+        &self,
+        _remote_addr: SocketAddr,
+        request: &mut RemotingCommand,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        request.make_custom_header_to_net();
+        if request.ext_fields().is_none() {
+            *request = std::mem::take(request).set_ext_fields(HashMap::new());
+        }
+        request.add_ext_field(
+            SessionCredentials::ACCESS_KEY,
+            self.session_credentials.access_key.clone(),
+        );
+        if let Some(security_token) = self.session_credentials.security_token.as_ref() {
+            request.add_ext_field(SessionCredentials::SECURITY_TOKEN, security_token.clone());
+        }
+        let content = acl_utils::combine_request_content(
+            request.ext_fields().unwrap(),
+            request.get_body().map(|body| body.as_ref()),
+        );
+        let signature =
+            acl_utils::cal_signature(&content, self.session_credentials.secret_key.as_str());
+        request.add_ext_field(
+            SessionCredentials::SIGNATURE,
+            CheetahString::from_string(signature),
+        );
+        Ok(())
+    }
+
+    fn do_after_response(
+        &self,
+        _remote_addr: SocketAddr,
+        _response: &mut RemotingCommand,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        Ok(())
+    }
+}
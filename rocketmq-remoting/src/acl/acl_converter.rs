@@ -0,0 +1,346 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Maps the user and ACL entry models of the auth admin API onto plain access accounts.
+//!
+//! A user is an account (`username` is the AccessKey, `password` the SecretKey, a `Super` user
+//! is an admin). An ACL entry grants an account `Pub`/`Sub` on `Topic:<name>` or
+//! `Group:<name>`; `*` as the name sets the account's default permission, and a `Deny` decision
+//! maps to DENY.
+
+use cheetah_string::CheetahString;
+use rocketmq_common::common::base::plain_access_config::PlainAccessConfig;
+use rocketmq_error::RocketmqError;
+
+use crate::acl::permission::Permission;
+use crate::protocol::body::acl_info::AclInfo;
+use crate::protocol::body::acl_info::PolicyEntryInfo;
+use crate::protocol::body::acl_info::PolicyInfo;
+use crate::protocol::body::user_info::UserInfo;
+
+pub const USER_TYPE_SUPER: &str = "Super";
+pub const USER_TYPE_NORMAL: &str = "Normal";
+pub const USER_STATUS_ENABLE: &str = "enable";
+pub const USER_STATUS_DISABLE: &str = "disable";
+pub const SUBJECT_USER_PREFIX: &str = "User:";
+pub const RESOURCE_TOPIC_PREFIX: &str = "Topic:";
+pub const RESOURCE_GROUP_PREFIX: &str = "Group:";
+pub const POLICY_TYPE_CUSTOM: &str = "Custom";
+pub const DECISION_ALLOW: &str = "Allow";
+pub const DECISION_DENY: &str = "Deny";
+
+/// A resource addressed by an ACL entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AclResource {
+    Topic(CheetahString),
+    Group(CheetahString),
+}
+
+impl AclResource {
+    pub fn parse(resource: &str) -> rocketmq_error::RocketMQResult<Self> {
+        let resource = resource.trim();
+        if let Some(topic) = resource.strip_prefix(RESOURCE_TOPIC_PREFIX) {
+            return Ok(AclResource::Topic(topic.into()));
+        }
+        if let Some(group) = resource.strip_prefix(RESOURCE_GROUP_PREFIX) {
+            return Ok(AclResource::Group(group.into()));
+        }
+        Err(RocketmqError::AclError(format!(
+            "The resource {resource} is illegal, it should start with {RESOURCE_TOPIC_PREFIX} or \
+             {RESOURCE_GROUP_PREFIX}"
+        )))
+    }
+}
+
+/// Strips the `User:` prefix of an ACL subject.
+pub fn subject_to_access_key(subject: &str) -> CheetahString {
+    subject
+        .strip_prefix(SUBJECT_USER_PREFIX)
+        .unwrap_or(subject)
+        .into()
+}
+
+pub fn user_to_access_config(user: &UserInfo) -> rocketmq_error::RocketMQResult<PlainAccessConfig> {
+    let username = user
+        .username
+        .clone()
+        .filter(|username| !username.is_empty())
+        .ok_or_else(|| RocketmqError::AclError("The username is blank".to_string()))?;
+    Ok(PlainAccessConfig {
+        access_key: Some(username),
+        secret_key: user.password.clone(),
+        admin: is_super(user.user_type.as_deref()),
+        default_topic_perm: Some("DENY".into()),
+        default_group_perm: Some("DENY".into()),
+        ..Default::default()
+    })
+}
+
+pub fn access_config_to_user(config: &PlainAccessConfig, disabled: bool) -> UserInfo {
+    UserInfo {
+        username: config.access_key.clone(),
+        password: config.secret_key.clone(),
+        user_type: Some(
+            if config.admin {
+                USER_TYPE_SUPER
+            } else {
+                USER_TYPE_NORMAL
+            }
+            .into(),
+        ),
+        user_status: Some(
+            if disabled {
+                USER_STATUS_DISABLE
+            } else {
+                USER_STATUS_ENABLE
+            }
+            .into(),
+        ),
+    }
+}
+
+pub fn is_super(user_type: Option<&str>) -> bool {
+    user_type.is_some_and(|user_type| user_type.eq_ignore_ascii_case(USER_TYPE_SUPER))
+}
+
+/// Applies every policy entry of `acl` to `config`, replacing previous grants of the same
+/// resources.
+pub fn apply_acl(
+    config: &mut PlainAccessConfig,
+    acl: &AclInfo,
+) -> rocketmq_error::RocketMQResult<()> {
+    let entries = acl
+        .policies
+        .iter()
+        .flatten()
+        .flat_map(|policy| policy.entries.iter().flatten());
+    for entry in entries {
+        let perm = entry_perm(entry);
+        let resource = entry
+            .resource
+            .as_deref()
+            .ok_or_else(|| RocketmqError::AclError("The resource is blank".to_string()))?;
+        for resource in resource
+            .split(',')
+            .filter(|resource| !resource.trim().is_empty())
+        {
+            match AclResource::parse(resource)? {
+                AclResource::Topic(topic) if topic == "*" => {
+                    config.default_topic_perm = Some(perm.clone())
+                }
+                AclResource::Group(group) if group == "*" => {
+                    config.default_group_perm = Some(perm.clone())
+                }
+                AclResource::Topic(topic) => upsert_perm(&mut config.topic_perms, &topic, &perm),
+                AclResource::Group(group) => upsert_perm(&mut config.group_perms, &group, &perm),
+            }
+        }
+        if let Some(source_ips) = entry.source_ips.as_ref().filter(|ips| !ips.is_empty()) {
+            config.white_remote_address = Some(
+                source_ips
+                    .iter()
+                    .map(|ip| ip.as_str())
+                    .collect::<Vec<_>>()
+                    .join(",")
+                    .into(),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Removes the grant on `resource`, or every grant when `resource` is `None`.
+pub fn remove_acl(
+    config: &mut PlainAccessConfig,
+    resource: Option<&str>,
+) -> rocketmq_error::RocketMQResult<()> {
+    let Some(resource) = resource.filter(|resource| !resource.is_empty()) else {
+        config.topic_perms.clear();
+        config.group_perms.clear();
+        config.default_topic_perm = Some("DENY".into());
+        config.default_group_perm = Some("DENY".into());
+        return Ok(());
+    };
+    match AclResource::parse(resource)? {
+        AclResource::Topic(topic) if topic == "*" => {
+            config.default_topic_perm = Some("DENY".into())
+        }
+        AclResource::Group(group) if group == "*" => {
+            config.default_group_perm = Some("DENY".into())
+        }
+        AclResource::Topic(topic) => remove_perm(&mut config.topic_perms, &topic),
+        AclResource::Group(group) => remove_perm(&mut config.group_perms, &group),
+    }
+    Ok(())
+}
+
+pub fn access_config_to_acl(config: &PlainAccessConfig) -> AclInfo {
+    let source_ips = config
+        .white_remote_address
+        .as_ref()
+        .filter(|address| !address.is_empty())
+        .map(|address| {
+            address
+                .split(',')
+                .map(|ip| CheetahString::from(ip.trim()))
+                .collect::<Vec<_>>()
+        });
+    let mut entries = Vec::new();
+    let mut push = |resource: String, perm: &str| {
+        let (actions, decision) = perm_to_actions(perm);
+        entries.push(PolicyEntryInfo {
+            resource: Some(resource.into()),
+            actions: Some(actions.into()),
+            source_ips: source_ips.clone(),
+            decision: Some(decision.into()),
+        });
+    };
+    push(
+        format!("{RESOURCE_TOPIC_PREFIX}*"),
+        config.default_topic_perm.as_deref().unwrap_or("DENY"),
+    );
+    push(
+        format!("{RESOURCE_GROUP_PREFIX}*"),
+        config.default_group_perm.as_deref().unwrap_or("DENY"),
+    );
+    for (prefix, perms) in [
+        (RESOURCE_TOPIC_PREFIX, &config.topic_perms),
+        (RESOURCE_GROUP_PREFIX, &config.group_perms),
+    ] {
+        for resource_perm in perms {
+            if let Some((resource, perm)) = resource_perm.split_once('=') {
+                push(format!("{prefix}{}", resource.trim()), perm.trim());
+            }
+        }
+    }
+    AclInfo {
+        subject: config
+            .access_key
+            .as_ref()
+            .map(|access_key| format!("{SUBJECT_USER_PREFIX}{access_key}").into()),
+        policies: Some(vec![PolicyInfo {
+            policy_type: Some(POLICY_TYPE_CUSTOM.into()),
+            entries: Some(entries),
+        }]),
+    }
+}
+
+fn entry_perm(entry: &PolicyEntryInfo) -> CheetahString {
+    if entry
+        .decision
+        .as_deref()
+        .is_some_and(|decision| decision.eq_ignore_ascii_case(DECISION_DENY))
+    {
+        return "DENY".into();
+    }
+    let mut perm = 0;
+    for action in entry.actions.as_deref().unwrap_or_default().split(',') {
+        match action.trim().to_ascii_lowercase().as_str() {
+            "pub" => perm |= Permission::PUB,
+            "sub" => perm |= Permission::SUB,
+            "all" => perm |= Permission::PUB | Permission::SUB,
+            _ => {}
+        }
+    }
+    match perm {
+        p if p == Permission::PUB | Permission::SUB => "PUB|SUB",
+        Permission::PUB => "PUB",
+        Permission::SUB => "SUB",
+        _ => "DENY",
+    }
+    .into()
+}
+
+fn perm_to_actions(perm: &str) -> (&'static str, &'static str) {
+    match Permission::parse_perm_from_string(Some(perm)) {
+        Permission::PUB => ("Pub", DECISION_ALLOW),
+        Permission::SUB => ("Sub", DECISION_ALLOW),
+        p if p == Permission::PUB | Permission::SUB => ("Pub,Sub", DECISION_ALLOW),
+        _ => ("All", DECISION_DENY),
+    }
+}
+
+fn upsert_perm(perms: &mut Vec<CheetahString>, resource: &str, perm: &str) {
+    remove_perm(perms, resource);
+    perms.push(format!("{resource}={perm}").into());
+}
+
+fn remove_perm(perms: &mut Vec<CheetahString>, resource: &str) {
+    perms.retain(|resource_perm| {
+        resource_perm
+            .split_once('=')
+            .is_none_or(|(name, _)| name.trim() != resource)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_and_convert_back_acl() {
+        let mut config = user_to_access_config(&UserInfo {
+            username: Some("rocketmq2".into()),
+            password: Some("12345678".into()),
+            user_type: Some("Normal".into()),
+            user_status: None,
+        })
+        .unwrap();
+        let acl = AclInfo {
+            subject: Some("User:rocketmq2".into()),
+            policies: Some(vec![PolicyInfo {
+                policy_type: Some(POLICY_TYPE_CUSTOM.into()),
+                entries: Some(vec![
+                    PolicyEntryInfo {
+                        resource: Some("Topic:TopicA,Group:GroupA".into()),
+                        actions: Some("Pub,Sub".into()),
+                        source_ips: Some(vec!["10.0.0.*".into()]),
+                        decision: Some("Allow".into()),
+                    },
+                    PolicyEntryInfo {
+                        resource: Some("Topic:*".into()),
+                        actions: Some("Sub".into()),
+                        source_ips: None,
+                        decision: Some("Allow".into()),
+                    },
+                ]),
+            }]),
+        };
+        apply_acl(&mut config, &acl).unwrap();
+        assert_eq!(
+            config.topic_perms,
+            vec![CheetahString::from("TopicA=PUB|SUB")]
+        );
+        assert_eq!(
+            config.group_perms,
+            vec![CheetahString::from("GroupA=PUB|SUB")]
+        );
+        assert_eq!(config.default_topic_perm.as_deref(), Some("SUB"));
+        assert_eq!(config.white_remote_address.as_deref(), Some("10.0.0.*"));
+
+        let converted = access_config_to_acl(&config);
+        assert_eq!(converted.subject.as_deref(), Some("User:rocketmq2"));
+        let entries = converted.policies.unwrap().remove(0).entries.unwrap();
+        assert!(entries
+            .iter()
+            .any(|entry| entry.resource.as_deref() == Some("Topic:TopicA")
+                && entry.actions.as_deref() == Some("Pub,Sub")));
+
+        remove_acl(&mut config, Some("Topic:TopicA")).unwrap();
+        assert!(config.topic_perms.is_empty());
+        assert!(remove_acl(&mut config, Some("Cluster:c")).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use crate::code::request_code::RequestCode;
+
+/// Permission bits granted to, or required from, an account on a resource.
+pub struct Permission;
+
+impl Permission {
+    pub const DENY: u8 = 1;
+    pub const ANY: u8 = 1 << 1;
+    pub const PUB: u8 = 1 << 2;
+    pub const SUB: u8 = 1 << 3;
+
+    /// Returns whether `owned_perm` satisfies `needed_perm`. A DENY always loses.
+    pub fn check_permission(needed_perm: u8, owned_perm: u8) -> bool {
+        if owned_perm & Self::DENY > 0 {
+            return false;
+        }
+        if needed_perm & Self::ANY > 0 {
+            return owned_perm & (Self::PUB | Self::SUB) > 0;
+        }
+        needed_perm & owned_perm > 0
+    }
+
+    /// Parses `PUB`, `SUB`, `PUB|SUB` or `DENY`; anything else is treated as DENY.
+    pub fn parse_perm_from_string(perm: Option<&str>) -> u8 {
+        let Some(perm) = perm else {
+            return Self::DENY;
+        };
+        match perm.trim().to_uppercase().as_str() {
+            "PUB" => Self::PUB,
+            "SUB" => Self::SUB,
+            "PUB|SUB" | "SUB|PUB" => Self::PUB | Self::SUB,
+            _ => Self::DENY,
+        }
+    }
+
+    /// Request codes that change cluster metadata and require an admin account.
+    pub fn need_admin_perm(request_code: i32) -> bool {
+        matches!(
+            RequestCode::from(request_code),
+            RequestCode::UpdateAndCreateTopic
+                | RequestCode::UpdateAndCreateTopicList
+                | RequestCode::UpdateAndCreateStaticTopic
+                | RequestCode::UpdateBrokerConfig
+                | RequestCode::DeleteTopicInBroker
+                | RequestCode::UpdateAndCreateSubscriptionGroup
+                | RequestCode::DeleteSubscriptionGroup
+                | RequestCode::UpdateAndCreateAclConfig
+                | RequestCode::DeleteAclConfig
+                | RequestCode::UpdateGlobalWhiteAddrsConfig
+                | RequestCode::AuthCreateUser
+                | RequestCode::AuthUpdateUser
+                | RequestCode::AuthDeleteUser
+                | RequestCode::AuthGetUser
+                | RequestCode::AuthListUser
+                | RequestCode::AuthCreateAcl
+                | RequestCode::AuthUpdateAcl
+                | RequestCode::AuthDeleteAcl
+                | RequestCode::AuthGetAcl
+                | RequestCode::AuthListAcl
+                | RequestCode::DeleteTopicInNamesrv
+                | RequestCode::PutKvConfig
+                | RequestCode::DeleteKvConfig
+                | RequestCode::WipeWritePermOfBroker
+                | RequestCode::AddWritePermOfBroker
+                | RequestCode::UpdateNamesrvConfig
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deny_overrides_everything() {
+        let owned = Permission::DENY | Permission::PUB;
+        assert!(!Permission::check_permission(Permission::PUB, owned));
+        assert!(!Permission::check_permission(Permission::ANY, owned));
+    }
+
+    #[test]
+    fn check_permission_matches_needed_bits() {
+        let owned = Permission::parse_perm_from_string(Some("pub|sub"));
+        assert!(Permission::check_permission(Permission::PUB, owned));
+        assert!(Permission::check_permission(Permission::SUB, owned));
+        assert!(Permission::check_permission(Permission::ANY, owned));
+        assert!(!Permission::check_permission(
+            Permission::SUB,
+            Permission::parse_perm_from_string(Some("PUB"))
+        ));
+        assert_eq!(Permission::parse_perm_from_string(None), Permission::DENY);
+        assert_eq!(
+            Permission::parse_perm_from_string(Some("unknown")),
+            Permission::DENY
+        );
+    }
+}
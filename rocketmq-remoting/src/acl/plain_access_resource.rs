@@ -0,0 +1,327 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::net::SocketAddr;
+
+use cheetah_string::CheetahString;
+use rocketmq_common::common::base::plain_access_config::PlainAccessConfig;
+use rocketmq_common::common::mix_all;
+use rocketmq_common::utils::serde_json_utils::SerdeJsonUtils;
+use rocketmq_error::RocketmqError;
+
+use crate::acl::acl_utils;
+use crate::acl::permission::Permission;
+use crate::acl::remote_address_strategy::RemoteAddressStrategy;
+use crate::acl::session_credentials::SessionCredentials;
+use crate::code::request_code::RequestCode;
+use crate::protocol::heartbeat::heartbeat_data::HeartbeatData;
+use crate::protocol::remoting_command::RemotingCommand;
+
+/// Either the access a request needs (built by [`PlainAccessResource::parse`]) or the access an
+/// account owns (built by [`PlainAccessResource::from_config`]).
+///
+/// Groups are keyed by their retry topic, so topics and groups share one permission map.
+#[derive(Debug, Clone, Default)]
+pub struct PlainAccessResource {
+    pub access_key: Option<CheetahString>,
+    pub secret_key: Option<CheetahString>,
+    pub white_remote_address: Option<CheetahString>,
+    pub admin: bool,
+    pub default_topic_perm: u8,
+    pub default_group_perm: u8,
+    pub resource_perm_map: HashMap<CheetahString, u8>,
+    pub remote_address_strategy: RemoteAddressStrategy,
+    pub request_code: i32,
+    pub content: Vec<u8>,
+    pub signature: Option<CheetahString>,
+    pub security_token: Option<CheetahString>,
+    pub remote_addr: Option<IpAddr>,
+}
+
+impl PlainAccessResource {
+    /// Extracts the credentials, the signed content and the needed permissions of `request`.
+    pub fn parse(
+        request: &RemotingCommand,
+        remote_addr: SocketAddr,
+    ) -> rocketmq_error::RocketMQResult<Self> {
+        let empty = HashMap::new();
+        let fields = request.ext_fields().unwrap_or(&empty);
+        let mut resource = PlainAccessResource {
+            access_key: fields.get(SessionCredentials::ACCESS_KEY).cloned(),
+            signature: fields.get(SessionCredentials::SIGNATURE).cloned(),
+            security_token: fields.get(SessionCredentials::SECURITY_TOKEN).cloned(),
+            request_code: request.code(),
+            remote_addr: Some(remote_addr.ip()),
+            ..Default::default()
+        };
+        let field = |key: &str| fields.get(key).filter(|value| !value.is_empty());
+
+        match RequestCode::from(request.code()) {
+            RequestCode::SendMessage | RequestCode::SendReplyMessage => {
+                if let Some(topic) = field("topic") {
+                    resource.add_topic_for_send(topic);
+                }
+            }
+            RequestCode::SendMessageV2
+            | RequestCode::SendBatchMessage
+            | RequestCode::SendReplyMessageV2 => {
+                if let Some(topic) = field("b") {
+                    resource.add_topic_for_send(topic);
+                }
+            }
+            RequestCode::ConsumerSendMsgBack => {
+                if let Some(group) = field("group") {
+                    resource.add_group(group, Permission::SUB);
+                }
+            }
+            RequestCode::PullMessage
+            | RequestCode::LitePullMessage
+            | RequestCode::PopMessage
+            | RequestCode::AckMessage
+            | RequestCode::ChangeMessageInvisibleTime
+            | RequestCode::QueryConsumerOffset
+            | RequestCode::UpdateConsumerOffset => {
+                if let Some(topic) = field("topic") {
+                    resource.add_resource_and_perm(topic.clone(), Permission::SUB);
+                }
+                if let Some(group) = field("consumerGroup") {
+                    resource.add_group(group, Permission::SUB);
+                }
+            }
+            RequestCode::QueryMessage => {
+                if let Some(topic) = field("topic") {
+                    resource.add_resource_and_perm(topic.clone(), Permission::SUB);
+                }
+            }
+            RequestCode::UnregisterClient | RequestCode::GetConsumerListByGroup => {
+                if let Some(group) = field("consumerGroup") {
+                    resource.add_group(group, Permission::SUB);
+                }
+            }
+            RequestCode::HeartBeat => {
+                if let Some(body) = request.get_body() {
+                    let heartbeat_data = SerdeJsonUtils::decode::<HeartbeatData>(body.as_ref())
+                        .map_err(|e| {
+                            RocketmqError::AclError(format!("Decode heartbeat data failed, {e}"))
+                        })?;
+                    for consumer_data in &heartbeat_data.consumer_data_set {
+                        resource.add_group(&consumer_data.group_name, Permission::SUB);
+                        for subscription in &consumer_data.subscription_data_set {
+                            if !subscription
+                                .topic
+                                .starts_with(mix_all::RETRY_GROUP_TOPIC_PREFIX)
+                            {
+                                resource.add_resource_and_perm(
+                                    subscription.topic.clone(),
+                                    Permission::SUB,
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        let mut signed_fields = fields.clone();
+        signed_fields.remove(SessionCredentials::SIGNATURE);
+        resource.content = acl_utils::combine_request_content(
+            &signed_fields,
+            request.get_body().map(|body| body.as_ref()),
+        );
+        Ok(resource)
+    }
+
+    /// Builds the access owned by the account described by `config`.
+    pub fn from_config(config: &PlainAccessConfig) -> rocketmq_error::RocketMQResult<Self> {
+        let access_key = config
+            .access_key
+            .clone()
+            .filter(|access_key| !access_key.is_empty())
+            .ok_or_else(|| RocketmqError::AclError("The accessKey is null".to_string()))?;
+        if access_key.len() <= 6 {
+            return Err(RocketmqError::AclError(format!(
+                "The accessKey={access_key} cannot be less than 6 characters"
+            )));
+        }
+        let secret_key = config
+            .secret_key
+            .clone()
+            .filter(|secret_key| !secret_key.is_empty())
+            .ok_or_else(|| RocketmqError::AclError("The secretKey is null".to_string()))?;
+        if secret_key.len() <= 6 {
+            return Err(RocketmqError::AclError(format!(
+                "The secretKey={secret_key} value cannot be less than 6 characters"
+            )));
+        }
+        let white_remote_address = config.white_remote_address.clone();
+        let remote_address_strategy = RemoteAddressStrategy::parse(
+            white_remote_address
+                .as_ref()
+                .map(|address| address.as_str())
+                .unwrap_or_default(),
+        )
+        .map_err(RocketmqError::AclError)?;
+        let mut resource = PlainAccessResource {
+            access_key: Some(access_key),
+            secret_key: Some(secret_key),
+            white_remote_address,
+            admin: config.admin,
+            default_topic_perm: Permission::parse_perm_from_string(
+                config.default_topic_perm.as_deref(),
+            ),
+            default_group_perm: Permission::parse_perm_from_string(
+                config.default_group_perm.as_deref(),
+            ),
+            remote_address_strategy,
+            ..Default::default()
+        };
+        for topic_perm in &config.topic_perms {
+            let (topic, perm) = Self::split_resource_perm(topic_perm)?;
+            resource
+                .resource_perm_map
+                .insert(topic.into(), Permission::parse_perm_from_string(Some(perm)));
+        }
+        for group_perm in &config.group_perms {
+            let (group, perm) = Self::split_resource_perm(group_perm)?;
+            resource.resource_perm_map.insert(
+                mix_all::get_retry_topic(group).into(),
+                Permission::parse_perm_from_string(Some(perm)),
+            );
+        }
+        Ok(resource)
+    }
+
+    fn split_resource_perm(resource_perm: &str) -> rocketmq_error::RocketMQResult<(&str, &str)> {
+        resource_perm
+            .split_once('=')
+            .map(|(resource, perm)| (resource.trim(), perm.trim()))
+            .filter(|(resource, _)| !resource.is_empty())
+            .ok_or_else(|| {
+                RocketmqError::AclError(format!("Parse resource permission error: {resource_perm}"))
+            })
+    }
+
+    fn add_topic_for_send(&mut self, topic: &CheetahString) {
+        // a retry topic is consumed back by its group
+        let perm = if topic.starts_with(mix_all::RETRY_GROUP_TOPIC_PREFIX) {
+            Permission::SUB
+        } else {
+            Permission::PUB
+        };
+        self.add_resource_and_perm(topic.clone(), perm);
+    }
+
+    fn add_group(&mut self, group: &str, perm: u8) {
+        self.add_resource_and_perm(mix_all::get_retry_topic(group).into(), perm);
+    }
+
+    pub fn add_resource_and_perm(&mut self, resource: CheetahString, perm: u8) {
+        *self.resource_perm_map.entry(resource).or_default() |= perm;
+    }
+
+    /// Human readable form of a resource key, e.g. `group=foo` for `%RETRY%foo`.
+    pub fn print_resource(resource: &str) -> String {
+        match resource.strip_prefix(mix_all::RETRY_GROUP_TOPIC_PREFIX) {
+            Some(group) => format!("group={group}"),
+            None => format!("topic={resource}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::protocol::header::empty_header::EmptyHeader;
+
+    fn request_with_fields(code: RequestCode, fields: &[(&str, &str)]) -> RemotingCommand {
+        RemotingCommand::create_request_command(code, EmptyHeader {}).set_ext_fields(
+            fields
+                .iter()
+                .map(|(key, value)| (CheetahString::from(*key), CheetahString::from(*value)))
+                .collect::<HashMap<_, _>>(),
+        )
+    }
+
+    #[test]
+    fn parse_pull_request_needs_topic_and_group_sub() {
+        let request = request_with_fields(
+            RequestCode::PullMessage,
+            &[
+                ("topic", "TopicA"),
+                ("consumerGroup", "GroupA"),
+                (SessionCredentials::ACCESS_KEY, "rocketmq2"),
+                (SessionCredentials::SIGNATURE, "sig"),
+            ],
+        );
+        let resource =
+            PlainAccessResource::parse(&request, "127.0.0.1:1".parse().unwrap()).unwrap();
+        assert_eq!(resource.access_key.as_deref(), Some("rocketmq2"));
+        assert_eq!(
+            resource.resource_perm_map.get("TopicA"),
+            Some(&Permission::SUB)
+        );
+        assert_eq!(
+            resource.resource_perm_map.get("%RETRY%GroupA"),
+            Some(&Permission::SUB)
+        );
+    }
+
+    #[test]
+    fn parse_send_v2_uses_short_topic_field() {
+        let request = request_with_fields(RequestCode::SendMessageV2, &[("b", "TopicB")]);
+        let resource =
+            PlainAccessResource::parse(&request, "127.0.0.1:1".parse().unwrap()).unwrap();
+        assert_eq!(
+            resource.resource_perm_map.get("TopicB"),
+            Some(&Permission::PUB)
+        );
+    }
+
+    #[test]
+    fn from_config_parses_perms() {
+        let config = PlainAccessConfig {
+            access_key: Some("rocketmq2".into()),
+            secret_key: Some("12345678".into()),
+            white_remote_address: Some("192.168.0.*".into()),
+            admin: false,
+            default_topic_perm: Some("DENY".into()),
+            default_group_perm: Some("SUB".into()),
+            topic_perms: vec!["topicA=DENY".into(), "topicB=PUB|SUB".into()],
+            group_perms: vec!["groupA=DENY".into()],
+        };
+        let resource = PlainAccessResource::from_config(&config).unwrap();
+        assert_eq!(resource.default_topic_perm, Permission::DENY);
+        assert_eq!(resource.default_group_perm, Permission::SUB);
+        assert_eq!(
+            resource.resource_perm_map.get("topicB"),
+            Some(&(Permission::PUB | Permission::SUB))
+        );
+        assert_eq!(
+            resource.resource_perm_map.get("%RETRY%groupA"),
+            Some(&Permission::DENY)
+        );
+        assert!(PlainAccessResource::from_config(&PlainAccessConfig {
+            access_key: Some("short".into()),
+            ..config
+        })
+        .is_err());
+    }
+}
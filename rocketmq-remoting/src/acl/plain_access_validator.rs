@@ -0,0 +1,203 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use rocketmq_error::RocketmqError;
+
+use crate::acl::permission::Permission;
+use crate::acl::plain_access_resource::PlainAccessResource;
+use crate::acl::plain_permission_manager::PlainPermissionManager;
+use crate::code::response_code::ResponseCode;
+use crate::protocol::remoting_command::RemotingCommand;
+use crate::runtime::RPCHook;
+
+/// Server side hook that rejects requests failing the ACL check with `NoPermission`.
+pub struct PlainAccessValidator {
+    permission_manager: Arc<PlainPermissionManager>,
+    admin_only: bool,
+}
+
+impl PlainAccessValidator {
+    /// Validates every request, as the broker does.
+    pub fn new(permission_manager: Arc<PlainPermissionManager>) -> Self {
+        Self {
+            permission_manager,
+            admin_only: false,
+        }
+    }
+
+    /// Only validates requests that need admin permission, which is what the name server
+    /// enforces: brokers and clients keep reading routes without credentials.
+    pub fn new_admin_only(permission_manager: Arc<PlainPermissionManager>) -> Self {
+        Self {
+            permission_manager,
+            admin_only: true,
+        }
+    }
+
+    pub fn permission_manager(&self) -> &Arc<PlainPermissionManager> {
+        &self.permission_manager
+    }
+
+    pub fn validate(
+        &self,
+        remote_addr: SocketAddr,
+        request: &RemotingCommand,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        if self.admin_only && !Permission::need_admin_perm(request.code()) {
+            return Ok(());
+        }
+        let resource = PlainAccessResource::parse(request, remote_addr)?;
+        self.permission_manager.validate(&resource)
+    }
+}
+
+impl RPCHook for PlainAccessValidator {
+    fn do_before_request(
+        &self,
+        remote_addr: SocketAddr,
+        request: &mut RemotingCommand,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        self.validate(remote_addr, request).map_err(|e| match e {
+            RocketmqError::AclError(message) => {
+                RocketmqError::AbortProcessError(ResponseCode::NoPermission as i32, message)
+            }
+            other => RocketmqError::AbortProcessError(
+                ResponseCode::NoPermission as i32,
+                other.to_string(),
+            ),
+        })
+    }
+
+    fn do_after_response(
+        &self,
+        _remote_addr: SocketAddr,
+        _response: &mut RemotingCommand,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cheetah_string::CheetahString;
+    use rocketmq_common::common::base::plain_access_config::PlainAccessConfig;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::acl::acl_client_rpc_hook::AclClientRPCHook;
+    use crate::acl::session_credentials::SessionCredentials;
+    use crate::code::request_code::RequestCode;
+    use crate::protocol::header::pull_message_request_header::PullMessageRequestHeader;
+
+    fn validator(temp_dir: &TempDir, admin_only: bool) -> PlainAccessValidator {
+        let path = temp_dir.path().join("plain_acl.json");
+        let manager = Arc::new(PlainPermissionManager::new(path.to_string_lossy()));
+        manager
+            .update_access_config(PlainAccessConfig {
+                access_key: Some("rocketmq2".into()),
+                secret_key: Some("12345678".into()),
+                default_topic_perm: Some("DENY".into()),
+                default_group_perm: Some("SUB".into()),
+                topic_perms: vec!["TopicA=SUB".into()],
+                ..Default::default()
+            })
+            .unwrap();
+        if admin_only {
+            PlainAccessValidator::new_admin_only(manager)
+        } else {
+            PlainAccessValidator::new(manager)
+        }
+    }
+
+    fn signed_pull(topic: &str, secret_key: &str) -> RemotingCommand {
+        let header = PullMessageRequestHeader {
+            consumer_group: CheetahString::from("GroupA"),
+            topic: CheetahString::from(topic),
+            ..Default::default()
+        };
+        let mut request = RemotingCommand::create_request_command(RequestCode::PullMessage, header);
+        AclClientRPCHook::new(SessionCredentials::new("rocketmq2", secret_key))
+            .do_before_request("127.0.0.1:10911".parse().unwrap(), &mut request)
+            .unwrap();
+        request
+    }
+
+    #[test]
+    fn signed_request_with_permission_passes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let validator = validator(&temp_dir, false);
+        let mut request = signed_pull("TopicA", "12345678");
+        assert!(validator
+            .do_before_request("127.0.0.1:1".parse().unwrap(), &mut request)
+            .is_ok());
+    }
+
+    #[test]
+    fn wrong_secret_or_denied_topic_is_rejected() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let validator = validator(&temp_dir, false);
+        let addr = "127.0.0.1:1".parse().unwrap();
+        let mut wrong_secret = signed_pull("TopicA", "87654321");
+        match validator.do_before_request(addr, &mut wrong_secret) {
+            Err(RocketmqError::AbortProcessError(code, message)) => {
+                assert_eq!(code, ResponseCode::NoPermission as i32);
+                assert!(message.contains("Check signature failed"));
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+        let mut denied_topic = signed_pull("TopicB", "12345678");
+        assert!(validator
+            .do_before_request(addr, &mut denied_topic)
+            .is_err());
+    }
+
+    #[test]
+    fn global_white_address_skips_validation() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let validator = validator(&temp_dir, false);
+        validator
+            .permission_manager()
+            .update_global_white_addrs(vec!["10.10.*.*".into()])
+            .unwrap();
+        let mut request = signed_pull("TopicB", "87654321");
+        assert!(validator
+            .do_before_request("10.10.1.1:1".parse().unwrap(), &mut request)
+            .is_ok());
+    }
+
+    #[test]
+    fn admin_only_validator_ignores_normal_requests() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let validator = validator(&temp_dir, true);
+        let addr = "127.0.0.1:1".parse().unwrap();
+        let mut request = signed_pull("TopicB", "87654321");
+        assert!(validator.do_before_request(addr, &mut request).is_ok());
+
+        let mut wipe = RemotingCommand::create_remoting_command(RequestCode::WipeWritePermOfBroker);
+        AclClientRPCHook::new(SessionCredentials::new("rocketmq2", "12345678"))
+            .do_before_request(addr, &mut wipe)
+            .unwrap();
+        match validator.do_before_request(addr, &mut wipe) {
+            Err(RocketmqError::AbortProcessError(_, message)) => {
+                assert!(message.contains("Need admin permission"))
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+}
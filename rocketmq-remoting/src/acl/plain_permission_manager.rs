@@ -0,0 +1,541 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use cheetah_string::CheetahString;
+use parking_lot::RwLock;
+use rocketmq_common::common::base::plain_access_config::PlainAccessConfig;
+use rocketmq_common::common::mix_all;
+use rocketmq_common::utils::file_utils;
+use rocketmq_common::utils::serde_json_utils::SerdeJsonUtils;
+use rocketmq_error::RocketmqError;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::info;
+use tracing::warn;
+
+use crate::acl::acl_converter;
+use crate::acl::acl_utils;
+use crate::acl::permission::Permission;
+use crate::acl::plain_access_resource::PlainAccessResource;
+use crate::acl::remote_address_strategy::RemoteAddressStrategy;
+use crate::protocol::body::acl_info::AclInfo;
+use crate::protocol::body::user_info::UserInfo;
+
+/// The persisted ACL configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PlainAclConfig {
+    pub global_white_remote_addresses: Vec<CheetahString>,
+    pub accounts: Vec<PlainAccessConfig>,
+    pub disabled_accounts: Vec<CheetahString>,
+    pub data_version: u64,
+}
+
+#[derive(Default)]
+struct PermissionState {
+    config: PlainAclConfig,
+    global_white_strategies: Vec<RemoteAddressStrategy>,
+    access_resources: HashMap<CheetahString, PlainAccessResource>,
+    disabled_accounts: HashSet<CheetahString>,
+}
+
+impl PermissionState {
+    fn build(config: PlainAclConfig) -> rocketmq_error::RocketMQResult<Self> {
+        let mut global_white_strategies = Vec::new();
+        for address in &config.global_white_remote_addresses {
+            global_white_strategies
+                .push(RemoteAddressStrategy::parse(address).map_err(RocketmqError::AclError)?);
+        }
+        let mut access_resources = HashMap::new();
+        for account in &config.accounts {
+            let resource = PlainAccessResource::from_config(account)?;
+            let access_key = resource.access_key.clone().unwrap_or_default();
+            if access_resources
+                .insert(access_key.clone(), resource)
+                .is_some()
+            {
+                return Err(RocketmqError::AclError(format!(
+                    "Duplicate acl config for accessKey={access_key}"
+                )));
+            }
+        }
+        let disabled_accounts = config.disabled_accounts.iter().cloned().collect();
+        Ok(Self {
+            config,
+            global_white_strategies,
+            access_resources,
+            disabled_accounts,
+        })
+    }
+}
+
+/// Owns the plain ACL accounts, validates requests against them and persists every change to
+/// `config_path` as JSON.
+pub struct PlainPermissionManager {
+    config_path: String,
+    state: RwLock<PermissionState>,
+}
+
+impl PlainPermissionManager {
+    pub fn new(config_path: impl Into<String>) -> Self {
+        Self {
+            config_path: config_path.into(),
+            state: RwLock::new(PermissionState::default()),
+        }
+    }
+
+    pub fn config_path(&self) -> &str {
+        self.config_path.as_str()
+    }
+
+    /// Loads the configuration file; a missing file means no accounts.
+    pub fn load(&self) -> rocketmq_error::RocketMQResult<()> {
+        let content = file_utils::file_to_string(self.config_path.as_str())?;
+        let config = if content.trim().is_empty() {
+            warn!(
+                "acl config file {} is empty or not exist, no account is configured",
+                self.config_path
+            );
+            PlainAclConfig::default()
+        } else {
+            SerdeJsonUtils::from_json_str::<PlainAclConfig>(content.as_str())?
+        };
+        let state = PermissionState::build(config)?;
+        info!(
+            "load acl config from {}, accounts: {}",
+            self.config_path,
+            state.access_resources.len()
+        );
+        *self.state.write() = state;
+        Ok(())
+    }
+
+    /// Checks the white lists, the signature and the permissions of a parsed request.
+    pub fn validate(&self, resource: &PlainAccessResource) -> rocketmq_error::RocketMQResult<()> {
+        let state = self.state.read();
+        if let Some(remote_addr) = resource.remote_addr {
+            if state
+                .global_white_strategies
+                .iter()
+                .any(|strategy| strategy.matches(remote_addr))
+            {
+                return Ok(());
+            }
+        }
+        let Some(access_key) = resource.access_key.as_ref() else {
+            return Err(RocketmqError::AclError(
+                "No accessKey is configured".to_string(),
+            ));
+        };
+        let Some(owned) = state.access_resources.get(access_key) else {
+            return Err(RocketmqError::AclError(format!(
+                "No acl config for {access_key}"
+            )));
+        };
+        if state.disabled_accounts.contains(access_key) {
+            return Err(RocketmqError::AclError(format!(
+                "The accessKey={access_key} is disabled"
+            )));
+        }
+        if let Some(remote_addr) = resource.remote_addr {
+            if owned.remote_address_strategy.matches(remote_addr) {
+                return Ok(());
+            }
+        }
+        let signature_matches = resource.signature.as_deref().is_some_and(|signature| {
+            acl_utils::verify_signature(
+                &resource.content,
+                owned.secret_key.as_deref().unwrap_or_default(),
+                signature,
+            )
+        });
+        if !signature_matches {
+            return Err(RocketmqError::AclError(format!(
+                "Check signature failed for accessKey={access_key}"
+            )));
+        }
+        Self::check_perm(resource, owned)
+    }
+
+    fn check_perm(
+        needed: &PlainAccessResource,
+        owned: &PlainAccessResource,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        if Permission::need_admin_perm(needed.request_code) && !owned.admin {
+            return Err(RocketmqError::AclError(format!(
+                "Need admin permission for request code={}, but accessKey={} is not admin",
+                needed.request_code,
+                owned.access_key.as_deref().unwrap_or_default()
+            )));
+        }
+        if owned.admin && owned.resource_perm_map.is_empty() {
+            return Ok(());
+        }
+        for (resource, needed_perm) in &needed.resource_perm_map {
+            let owned_perm = match owned.resource_perm_map.get(resource) {
+                Some(perm) => *perm,
+                None if resource.starts_with(mix_all::RETRY_GROUP_TOPIC_PREFIX) => {
+                    owned.default_group_perm
+                }
+                None => owned.default_topic_perm,
+            };
+            if !Permission::check_permission(*needed_perm, owned_perm) {
+                return Err(RocketmqError::AclError(format!(
+                    "No permission for {}",
+                    PlainAccessResource::print_resource(resource)
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates or replaces the account of `config.access_key`.
+    pub fn update_access_config(
+        &self,
+        config: PlainAccessConfig,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        PlainAccessResource::from_config(&config)?;
+        self.update_config(|acl_config| {
+            acl_config
+                .accounts
+                .retain(|account| account.access_key != config.access_key);
+            acl_config.accounts.push(config);
+            Ok(())
+        })
+    }
+
+    /// Deletes the account of `access_key`, returning whether it existed.
+    pub fn delete_access_config(&self, access_key: &str) -> rocketmq_error::RocketMQResult<bool> {
+        let mut existed = false;
+        self.update_config(|acl_config| {
+            let before = acl_config.accounts.len();
+            acl_config
+                .accounts
+                .retain(|account| account.access_key.as_deref() != Some(access_key));
+            acl_config
+                .disabled_accounts
+                .retain(|disabled| disabled != access_key);
+            existed = before != acl_config.accounts.len();
+            Ok(())
+        })?;
+        Ok(existed)
+    }
+
+    pub fn update_global_white_addrs(
+        &self,
+        global_white_addrs: Vec<CheetahString>,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        self.update_config(|acl_config| {
+            acl_config.global_white_remote_addresses = global_white_addrs;
+            Ok(())
+        })
+    }
+
+    pub fn get_access_config(&self, access_key: &str) -> Option<PlainAccessConfig> {
+        Self::find_account(&self.state.read().config, access_key).cloned()
+    }
+
+    pub fn acl_config(&self) -> PlainAclConfig {
+        self.state.read().config.clone()
+    }
+
+    pub fn create_user(&self, user: &UserInfo) -> rocketmq_error::RocketMQResult<()> {
+        let config = acl_converter::user_to_access_config(user)?;
+        let username = config.access_key.clone().unwrap_or_default();
+        PlainAccessResource::from_config(&config)?;
+        self.update_config(|acl_config| {
+            if Self::find_account(acl_config, &username).is_some() {
+                return Err(RocketmqError::AclError(format!(
+                    "The user of {username} is existed"
+                )));
+            }
+            acl_config.accounts.push(config);
+            Self::set_disabled(acl_config, &username, user.user_status.as_deref());
+            Ok(())
+        })
+    }
+
+    /// Updates the password, type and status of an existing user; absent fields are kept.
+    pub fn update_user(&self, user: &UserInfo) -> rocketmq_error::RocketMQResult<()> {
+        let username = user
+            .username
+            .clone()
+            .filter(|username| !username.is_empty())
+            .ok_or_else(|| RocketmqError::AclError("The username is blank".to_string()))?;
+        self.update_config(|acl_config| {
+            let account = Self::find_account_mut(acl_config, &username).ok_or_else(|| {
+                RocketmqError::AclError(format!("The user of {username} is not exist"))
+            })?;
+            if let Some(password) = user.password.clone().filter(|p| !p.is_empty()) {
+                account.secret_key = Some(password);
+            }
+            if let Some(user_type) = user.user_type.as_deref().filter(|t| !t.is_empty()) {
+                account.admin = acl_converter::is_super(Some(user_type));
+            }
+            PlainAccessResource::from_config(account)?;
+            Self::set_disabled(acl_config, &username, user.user_status.as_deref());
+            Ok(())
+        })
+    }
+
+    pub fn delete_user(&self, username: &str) -> rocketmq_error::RocketMQResult<bool> {
+        self.delete_access_config(username)
+    }
+
+    pub fn get_user(&self, username: &str) -> Option<UserInfo> {
+        let state = self.state.read();
+        Self::find_account(&state.config, username).map(|account| {
+            acl_converter::access_config_to_user(
+                account,
+                state.disabled_accounts.contains(username),
+            )
+        })
+    }
+
+    /// Lists the users whose name contains `filter`.
+    pub fn list_users(&self, filter: Option<&str>) -> Vec<UserInfo> {
+        let state = self.state.read();
+        state
+            .config
+            .accounts
+            .iter()
+            .filter(|account| {
+                filter.is_none_or(|filter| {
+                    account
+                        .access_key
+                        .as_deref()
+                        .is_some_and(|access_key| access_key.contains(filter))
+                })
+            })
+            .map(|account| {
+                let disabled = account
+                    .access_key
+                    .as_ref()
+                    .is_some_and(|access_key| state.disabled_accounts.contains(access_key));
+                acl_converter::access_config_to_user(account, disabled)
+            })
+            .collect()
+    }
+
+    /// Grants the entries of `acl` to the user named by its subject.
+    pub fn update_acl(&self, acl: &AclInfo) -> rocketmq_error::RocketMQResult<()> {
+        let subject = acl
+            .subject
+            .as_deref()
+            .filter(|subject| !subject.is_empty())
+            .ok_or_else(|| RocketmqError::AclError("The subject is blank".to_string()))?;
+        let access_key = acl_converter::subject_to_access_key(subject);
+        self.update_config(|acl_config| {
+            let account = Self::find_account_mut(acl_config, &access_key).ok_or_else(|| {
+                RocketmqError::AclError(format!("The subject of {subject} is not exist"))
+            })?;
+            acl_converter::apply_acl(account, acl)?;
+            PlainAccessResource::from_config(account)?;
+            Ok(())
+        })
+    }
+
+    pub fn delete_acl(
+        &self,
+        subject: &str,
+        resource: Option<&str>,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        let access_key = acl_converter::subject_to_access_key(subject);
+        self.update_config(|acl_config| {
+            let account = Self::find_account_mut(acl_config, &access_key).ok_or_else(|| {
+                RocketmqError::AclError(format!("The subject of {subject} is not exist"))
+            })?;
+            acl_converter::remove_acl(account, resource)
+        })
+    }
+
+    pub fn get_acl(&self, subject: &str) -> Option<AclInfo> {
+        let access_key = acl_converter::subject_to_access_key(subject);
+        Self::find_account(&self.state.read().config, &access_key)
+            .map(acl_converter::access_config_to_acl)
+    }
+
+    /// Lists the ACLs whose subject contains `subject_filter` and which grant a resource
+    /// containing `resource_filter`.
+    pub fn list_acls(
+        &self,
+        subject_filter: Option<&str>,
+        resource_filter: Option<&str>,
+    ) -> Vec<AclInfo> {
+        let state = self.state.read();
+        state
+            .config
+            .accounts
+            .iter()
+            .map(acl_converter::access_config_to_acl)
+            .filter(|acl| {
+                subject_filter.is_none_or(|filter| {
+                    acl.subject
+                        .as_deref()
+                        .is_some_and(|subject| subject.contains(filter))
+                })
+            })
+            .filter(|acl| {
+                resource_filter.is_none_or(|filter| {
+                    acl.policies
+                        .iter()
+                        .flatten()
+                        .flat_map(|policy| policy.entries.iter().flatten())
+                        .any(|entry| {
+                            entry
+                                .resource
+                                .as_deref()
+                                .is_some_and(|resource| resource.contains(filter))
+                        })
+                })
+            })
+            .collect()
+    }
+
+    fn find_account<'a>(
+        config: &'a PlainAclConfig,
+        access_key: &str,
+    ) -> Option<&'a PlainAccessConfig> {
+        config
+            .accounts
+            .iter()
+            .find(|account| account.access_key.as_deref() == Some(access_key))
+    }
+
+    fn find_account_mut<'a>(
+        config: &'a mut PlainAclConfig,
+        access_key: &str,
+    ) -> Option<&'a mut PlainAccessConfig> {
+        config
+            .accounts
+            .iter_mut()
+            .find(|account| account.access_key.as_deref() == Some(access_key))
+    }
+
+    fn set_disabled(config: &mut PlainAclConfig, access_key: &str, user_status: Option<&str>) {
+        let Some(user_status) = user_status.filter(|status| !status.is_empty()) else {
+            return;
+        };
+        config
+            .disabled_accounts
+            .retain(|disabled| disabled != access_key);
+        if user_status.eq_ignore_ascii_case(acl_converter::USER_STATUS_DISABLE) {
+            config.disabled_accounts.push(access_key.into());
+        }
+    }
+
+    /// Applies `update` to a copy of the configuration and, once it validates, swaps it in and
+    /// persists it.
+    fn update_config(
+        &self,
+        update: impl FnOnce(&mut PlainAclConfig) -> rocketmq_error::RocketMQResult<()>,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        let mut state = self.state.write();
+        let mut config = state.config.clone();
+        update(&mut config)?;
+        config.data_version += 1;
+        let new_state = PermissionState::build(config)?;
+        let content = SerdeJsonUtils::to_json_pretty(&new_state.config)?;
+        file_utils::string_to_file(content.as_str(), self.config_path.as_str())?;
+        *state = new_state;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn temp_config_path(temp_dir: &TempDir) -> String {
+        temp_dir
+            .path()
+            .join("plain_acl.json")
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn users_and_acls_are_persisted() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_config_path(&temp_dir);
+        let manager = PlainPermissionManager::new(path.as_str());
+        manager.load().unwrap();
+        manager
+            .create_user(&UserInfo {
+                username: Some("rocketmq2".into()),
+                password: Some("12345678".into()),
+                user_type: Some("Super".into()),
+                user_status: Some("disable".into()),
+            })
+            .unwrap();
+        assert!(manager
+            .create_user(&UserInfo {
+                username: Some("rocketmq2".into()),
+                password: Some("12345678".into()),
+                ..Default::default()
+            })
+            .is_err());
+        manager
+            .update_acl(
+                &serde_json::from_str::<AclInfo>(
+                    r#"{"subject":"User:rocketmq2","policies":[{"entries":[
+                    {"resource":"Topic:TopicA","actions":"Pub","decision":"Allow"}]}]}"#,
+                )
+                .unwrap(),
+            )
+            .unwrap();
+
+        let reloaded = PlainPermissionManager::new(path.as_str());
+        reloaded.load().unwrap();
+        let user = reloaded.get_user("rocketmq2").unwrap();
+        assert_eq!(user.user_type.as_deref(), Some("Super"));
+        assert_eq!(user.user_status.as_deref(), Some("disable"));
+        assert_eq!(
+            reloaded.get_access_config("rocketmq2").unwrap().topic_perms,
+            vec![CheetahString::from("TopicA=PUB")]
+        );
+        assert_eq!(reloaded.list_acls(None, Some("TopicA")).len(), 1);
+        assert_eq!(reloaded.acl_config().data_version, 2);
+
+        assert!(reloaded.delete_user("rocketmq2").unwrap());
+        assert!(reloaded.list_users(None).is_empty());
+    }
+
+    #[test]
+    fn disabled_account_is_rejected() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manager = PlainPermissionManager::new(temp_config_path(&temp_dir));
+        manager
+            .create_user(&UserInfo {
+                username: Some("rocketmq2".into()),
+                password: Some("12345678".into()),
+                user_status: Some("disable".into()),
+                ..Default::default()
+            })
+            .unwrap();
+        let resource = PlainAccessResource {
+            access_key: Some("rocketmq2".into()),
+            ..Default::default()
+        };
+        let err = manager.validate(&resource).unwrap_err();
+        assert!(err.to_string().contains("disabled"));
+    }
+}
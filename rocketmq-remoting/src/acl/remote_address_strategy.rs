@@ -0,0 +1,145 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::net::IpAddr;
+
+/// Matches a client address against a white list expression.
+///
+/// Supported expressions are `*` (everyone), a single address, IPv4 patterns where each segment
+/// is a number, `*` or an inclusive `a-b` range (e.g. `192.168.1.*`, `10.0.1-10.*`), and any
+/// comma separated combination of those. A blank expression matches nobody.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RemoteAddressStrategy {
+    patterns: Vec<AddressPattern>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AddressPattern {
+    Any,
+    Exact(IpAddr),
+    Segments([SegmentPattern; 4]),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SegmentPattern {
+    Any,
+    Range(u8, u8),
+}
+
+impl RemoteAddressStrategy {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let mut patterns = Vec::new();
+        for item in expression
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            patterns.push(Self::parse_pattern(item)?);
+        }
+        Ok(Self { patterns })
+    }
+
+    fn parse_pattern(item: &str) -> Result<AddressPattern, String> {
+        if item == "*" {
+            return Ok(AddressPattern::Any);
+        }
+        if let Ok(addr) = item.parse::<IpAddr>() {
+            return Ok(AddressPattern::Exact(addr));
+        }
+        let segments = item.split('.').collect::<Vec<_>>();
+        if segments.len() != 4 {
+            return Err(format!(
+                "Netaddress examine scope Exception netaddress: {item}"
+            ));
+        }
+        let mut parsed = [SegmentPattern::Any; 4];
+        for (index, segment) in segments.iter().enumerate() {
+            parsed[index] = Self::parse_segment(segment)
+                .ok_or_else(|| format!("Netaddress examine scope Exception netaddress: {item}"))?;
+        }
+        Ok(AddressPattern::Segments(parsed))
+    }
+
+    fn parse_segment(segment: &str) -> Option<SegmentPattern> {
+        if segment == "*" {
+            return Some(SegmentPattern::Any);
+        }
+        if let Some((start, end)) = segment.split_once('-') {
+            let start = start.parse::<u8>().ok()?;
+            let end = end.parse::<u8>().ok()?;
+            return (start <= end).then_some(SegmentPattern::Range(start, end));
+        }
+        let value = segment.parse::<u8>().ok()?;
+        Some(SegmentPattern::Range(value, value))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    pub fn matches(&self, addr: IpAddr) -> bool {
+        let addr = match addr {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(addr, IpAddr::V4),
+            IpAddr::V4(_) => addr,
+        };
+        self.patterns.iter().any(|pattern| match pattern {
+            AddressPattern::Any => true,
+            AddressPattern::Exact(exact) => *exact == addr,
+            AddressPattern::Segments(segments) => match addr {
+                IpAddr::V4(v4) => {
+                    segments
+                        .iter()
+                        .zip(v4.octets())
+                        .all(|(segment, octet)| match segment {
+                            SegmentPattern::Any => true,
+                            SegmentPattern::Range(start, end) => (*start..=*end).contains(&octet),
+                        })
+                }
+                IpAddr::V6(_) => false,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(addr: &str) -> IpAddr {
+        addr.parse().unwrap()
+    }
+
+    #[test]
+    fn blank_matches_nobody_and_star_matches_everyone() {
+        assert!(!RemoteAddressStrategy::parse("")
+            .unwrap()
+            .matches(ip("10.0.0.1")));
+        assert!(RemoteAddressStrategy::parse("*")
+            .unwrap()
+            .matches(ip("10.0.0.1")));
+    }
+
+    #[test]
+    fn segment_patterns_and_lists() {
+        let strategy = RemoteAddressStrategy::parse("192.168.1.*, 10.0.1-10.5,::1").unwrap();
+        assert!(strategy.matches(ip("192.168.1.200")));
+        assert!(strategy.matches(ip("10.0.7.5")));
+        assert!(!strategy.matches(ip("10.0.11.5")));
+        assert!(strategy.matches(ip("::1")));
+        assert!(strategy.matches(ip("::ffff:192.168.1.3")));
+        assert!(RemoteAddressStrategy::parse("10.0.300.1").is_err());
+    }
+}
@@ -0,0 +1,45 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use cheetah_string::CheetahString;
+
+/// The credentials a client signs its requests with.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionCredentials {
+    pub access_key: CheetahString,
+    pub secret_key: CheetahString,
+    pub security_token: Option<CheetahString>,
+}
+
+impl SessionCredentials {
+    pub const ACCESS_KEY: &'static str = "AccessKey";
+    pub const SECRET_KEY: &'static str = "SecretKey";
+    pub const SIGNATURE: &'static str = "Signature";
+    pub const SECURITY_TOKEN: &'static str = "SecurityToken";
+
+    pub fn new(access_key: impl Into<CheetahString>, secret_key: impl Into<CheetahString>) -> Self {
+        Self {
+            access_key: access_key.into(),
+            secret_key: secret_key.into(),
+            security_token: None,
+        }
+    }
+
+    pub fn with_security_token(mut self, security_token: impl Into<CheetahString>) -> Self {
+        self.security_token = Some(security_token.into());
+        self
+    }
+}
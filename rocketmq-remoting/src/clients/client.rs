@@ -36,6 +36,7 @@ use crate::protocol::RemotingCommandType;
 use crate::runtime::connection_handler_context::ConnectionHandlerContext;
 use crate::runtime::connection_handler_context::ConnectionHandlerContextWrapper;
 use crate::runtime::processor::RequestProcessor;
+use crate::tls::TlsClientConnector;
 
 #[derive(Clone)]
 pub struct Client {
@@ -132,6 +133,7 @@ impl ClientInner {
         addr: T,
         processor: PR,
         tx: Option<&tokio::sync::broadcast::Sender<ConnectionNetEvent>>,
+        tls_connector: &TlsClientConnector,
     ) -> RocketMQResult<(tokio::sync::mpsc::Sender<SendMessage>, ArcMut<ClientInner>)>
     where
         T: tokio::net::ToSocketAddrs,
@@ -144,6 +146,7 @@ impl ClientInner {
         let stream = tcp_stream?;
         let local_addr = stream.local_addr()?;
         let remote_address = stream.peer_addr()?;
+        let stream = tls_connector.connect(stream, remote_address).await?;
         let connection = Connection::new(stream);
         let response_table = ArcMut::new(HashMap::with_capacity(128));
         let channel_inner = ArcMut::new(ChannelInner::new(connection, response_table.clone()));
@@ -208,6 +211,7 @@ impl Client {
     /// # Arguments
     ///
     /// * `addr` - The address to connect to.
+    /// * `tls_connector` - Wraps the socket in TLS when the client enables it.
     ///
     /// # Returns
     ///
@@ -216,12 +220,13 @@ impl Client {
         addr: T,
         processor: PR,
         tx: Option<&tokio::sync::broadcast::Sender<ConnectionNetEvent>>,
+        tls_connector: &TlsClientConnector,
     ) -> RocketMQResult<Client>
     where
         T: tokio::net::ToSocketAddrs,
         PR: RequestProcessor + 'static,
     {
-        let (tx, inner) = ClientInner::connect(addr, processor, tx).await?;
+        let (tx, inner) = ClientInner::connect(addr, processor, tx, tls_connector).await?;
         Ok(Client {
             //connection: inner.connection.clone(),
             inner,
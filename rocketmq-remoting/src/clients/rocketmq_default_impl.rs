@@ -16,6 +16,7 @@
  */
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::net::SocketAddr;
 use std::sync::atomic::AtomicI32;
 use std::sync::Arc;
 use std::time::Duration;
@@ -42,6 +43,7 @@ use crate::request_processor::default_request_processor::DefaultRemotingRequestP
 use crate::runtime::config::client_config::TokioClientConfig;
 use crate::runtime::processor::RequestProcessor;
 use crate::runtime::RPCHook;
+use crate::tls::TlsClientConnector;
 
 const LOCK_TIMEOUT_MILLIS: u64 = 3000;
 
@@ -58,6 +60,9 @@ pub struct RocketmqDefaultClient<PR = DefaultRemotingRequestProcessor> {
     client_runtime: Option<RocketMQRuntime>,
     processor: PR,
     tx: Option<tokio::sync::broadcast::Sender<ConnectionNetEvent>>,
+    rpc_hooks: Vec<Arc<Box<dyn RPCHook>>>,
+    /// An invalid tls config fails every connection attempt instead of the constructor.
+    tls_connector: Result<TlsClientConnector, String>,
 }
 impl<PR: RequestProcessor + Sync + Clone + 'static> RocketmqDefaultClient<PR> {
     pub fn new(tokio_client_config: Arc<TokioClientConfig>, processor: PR) -> Self {
@@ -69,6 +74,10 @@ impl<PR: RequestProcessor + Sync + Clone + 'static> RocketmqDefaultClient<PR> {
         processor: PR,
         tx: Option<tokio::sync::broadcast::Sender<ConnectionNetEvent>>,
     ) -> Self {
+        let tls_connector = TlsClientConnector::new(&tokio_client_config).map_err(|e| {
+            error!("Failed to initialize remoting client tls: {}", e);
+            e.to_string()
+        });
         Self {
             tokio_client_config,
             connection_tables: Arc::new(Mutex::new(Default::default())),
@@ -79,6 +88,8 @@ impl<PR: RequestProcessor + Sync + Clone + 'static> RocketmqDefaultClient<PR> {
             client_runtime: Some(RocketMQRuntime::new_multi(10, "client-thread")),
             processor,
             tx,
+            rpc_hooks: Vec::new(),
+            tls_connector,
         }
     }
 }
@@ -178,10 +189,23 @@ impl<PR: RequestProcessor + Sync + Clone + 'static> RocketmqDefaultClient<PR> {
             let _ = connection_tables.remove(addr.as_str());
         }
 
+        let tls_connector = match self.tls_connector.as_ref() {
+            Ok(tls_connector) => tls_connector,
+            Err(e) => {
+                error!("getAndCreateClient connect to {} failed: {}", addr, e);
+                return None;
+            }
+        };
         let addr_inner = addr.to_string();
 
         match time::timeout(duration, async {
-            Client::connect(addr_inner, self.processor.clone(), self.tx.as_ref()).await
+            Client::connect(
+                addr_inner,
+                self.processor.clone(),
+                self.tx.as_ref(),
+                tls_connector,
+            )
+            .await
         })
         .await
         {
@@ -204,6 +228,43 @@ impl<PR: RequestProcessor + Sync + Clone + 'static> RocketmqDefaultClient<PR> {
         }
     }
 
+    /// The address handed to the hooks; requests to the name server may not know it yet.
+    fn hook_remote_address(&self, addr: Option<&CheetahString>) -> SocketAddr {
+        addr.or(self.namesrv_addr_choosed.as_ref().as_ref())
+            .and_then(|addr| addr.parse::<SocketAddr>().ok())
+            .unwrap_or_else(|| SocketAddr::from(([0, 0, 0, 0], 0)))
+    }
+
+    fn do_before_rpc_hooks(
+        &self,
+        addr: Option<&CheetahString>,
+        request: &mut RemotingCommand,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        if self.rpc_hooks.is_empty() {
+            return Ok(());
+        }
+        let remote_addr = self.hook_remote_address(addr);
+        for hook in &self.rpc_hooks {
+            hook.do_before_request(remote_addr, request)?;
+        }
+        Ok(())
+    }
+
+    fn do_after_rpc_hooks(
+        &self,
+        addr: Option<&CheetahString>,
+        response: &mut RemotingCommand,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        if self.rpc_hooks.is_empty() {
+            return Ok(());
+        }
+        let remote_addr = self.hook_remote_address(addr);
+        for hook in &self.rpc_hooks {
+            hook.do_after_response(remote_addr, response)?;
+        }
+        Ok(())
+    }
+
     async fn scan_available_name_srv(&self) {
         if self.namesrv_addr_list.as_ref().is_empty() {
             debug!("scanAvailableNameSrv addresses of name remoting_server is null!");
@@ -270,11 +331,11 @@ impl<PR: RequestProcessor + Sync + Clone + 'static> RemotingService for Rocketmq
     }
 
     fn register_rpc_hook(&mut self, hook: Arc<Box<dyn RPCHook>>) {
-        todo!()
+        self.rpc_hooks.push(hook);
     }
 
     fn clear_rpc_hook(&mut self) {
-        todo!()
+        self.rpc_hooks.clear();
     }
 }
 
@@ -346,6 +407,8 @@ impl<PR: RequestProcessor + Sync + Clone + 'static> RemotingClient for RocketmqD
         request: RemotingCommand,
         timeout_millis: u64,
     ) -> rocketmq_error::RocketMQResult<RemotingCommand> {
+        let mut request = request;
+        self.do_before_rpc_hooks(addr, &mut request)?;
         let client = self.get_and_create_client(addr).await;
         match client {
             None => Err(rocketmq_error::RocketmqError::RemoteError(
@@ -367,7 +430,10 @@ impl<PR: RequestProcessor + Sync + Clone + 'static> RemotingClient for RocketmqD
                 {
                     Ok(result) => match result {
                         Ok(response) => match response {
-                            Ok(value) => Ok(value),
+                            Ok(mut value) => {
+                                self.do_after_rpc_hooks(addr, &mut value)?;
+                                Ok(value)
+                            }
                             Err(e) => {
                                 Err(rocketmq_error::RocketmqError::RemoteError(e.to_string()))
                             }
@@ -388,6 +454,11 @@ impl<PR: RequestProcessor + Sync + Clone + 'static> RemotingClient for RocketmqD
         request: RemotingCommand,
         timeout_millis: u64,
     ) {
+        let mut request = request;
+        if let Err(e) = self.do_before_rpc_hooks(Some(addr), &mut request) {
+            error!("rpc hook rejected oneway request to {}: {}", addr, e);
+            return;
+        }
         let client = self.get_and_create_client(Some(addr)).await;
         match client {
             None => {
@@ -400,7 +471,6 @@ impl<PR: RequestProcessor + Sync + Clone + 'static> RemotingClient for RocketmqD
                     .get_handle()
                     .spawn(async move {
                         match time::timeout(Duration::from_millis(timeout_millis), async move {
-                            let mut request = request;
                             request.mark_oneway_rpc_ref();
                             client.send(request).await
                         })
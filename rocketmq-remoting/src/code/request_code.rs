@@ -151,6 +151,7 @@ pub enum RequestCode {
     ResetMasterFlushOffset = 908,
     GetAllProducerInfo = 328,
     DeleteExpiredCommitlog = 329,
+    CheckRocksdbCqWriteProgress = 354,
 
     UpdateColdDataFlowCtrConfig = 2001,
     RemoveColdDataFlowCtrConfig = 2002,
@@ -328,6 +329,7 @@ impl From<i32> for RequestCode {
             908 => RequestCode::ResetMasterFlushOffset,
             328 => RequestCode::GetAllProducerInfo,
             329 => RequestCode::DeleteExpiredCommitlog,
+            354 => RequestCode::CheckRocksdbCqWriteProgress,
             2001 => RequestCode::UpdateColdDataFlowCtrConfig,
             2002 => RequestCode::RemoveColdDataFlowCtrConfig,
             2003 => RequestCode::GetColdDataFlowCtrInfo,
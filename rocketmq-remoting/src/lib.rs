@@ -20,6 +20,7 @@
 #![feature(duration_constructors_lite)]
 extern crate core;
 
+pub mod acl;
 pub mod clients;
 pub mod code;
 pub mod codec;
@@ -35,3 +36,4 @@ pub mod remoting_server;
 pub mod request_processor;
 pub mod rpc;
 pub mod runtime;
+pub mod tls;
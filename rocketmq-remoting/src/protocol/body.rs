@@ -35,6 +35,8 @@ pub mod connection;
 pub mod consume_message_directly_result;
 pub mod consume_queue_data;
 pub mod consume_status;
+pub mod elect_master_response_body;
+pub mod get_consumer_status_body;
 pub mod group_list;
 pub mod ha_client_runtime_info;
 pub mod ha_connection_runtime_info;
@@ -48,10 +50,13 @@ pub mod producer_table_info;
 pub mod query_assignment_request_body;
 pub mod query_assignment_response_body;
 pub mod query_consume_queue_response_body;
+pub mod query_subscription_response_body;
 pub mod queue_time_span;
 pub mod request;
+pub mod reset_offset_body;
 pub mod response;
 pub mod set_message_request_mode_request_body;
+pub mod sync_state_set;
 pub mod topic;
 pub mod topic_info_wrapper;
 pub mod unlock_batch_request_body;
@@ -19,9 +19,10 @@ use cheetah_string::CheetahString;
 use serde::Deserialize;
 use serde::Serialize;
 
+/// Differences found between the RocksDB and the file consume queues of a broker.
 #[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
-pub struct ClusterAclVersionInfo {
+pub struct CheckRocksdbCqWriteProgressResponseBody {
     pub diff_result: Option<CheetahString>,
 }
 
@@ -32,22 +33,22 @@ mod tests {
     use super::*;
 
     #[test]
-    fn cluster_acl_version_info_default_values() {
-        let info = ClusterAclVersionInfo::default();
+    fn check_rocksdb_cq_write_progress_response_body_default_values() {
+        let info = CheckRocksdbCqWriteProgressResponseBody::default();
         assert!(info.diff_result.is_none());
     }
 
     #[test]
-    fn cluster_acl_version_info_with_diff_result() {
-        let info = ClusterAclVersionInfo {
+    fn check_rocksdb_cq_write_progress_response_body_with_diff_result() {
+        let info = CheckRocksdbCqWriteProgressResponseBody {
             diff_result: Some(CheetahString::from("diff")),
         };
         assert_eq!(info.diff_result, Some(CheetahString::from("diff")));
     }
 
     #[test]
-    fn serialize_cluster_acl_version_info() {
-        let info = ClusterAclVersionInfo {
+    fn serialize_check_rocksdb_cq_write_progress_response_body() {
+        let info = CheckRocksdbCqWriteProgressResponseBody {
             diff_result: Some(CheetahString::from("diff")),
         };
         let serialized = serde_json::to_string(&info).unwrap();
@@ -55,16 +56,18 @@ mod tests {
     }
 
     #[test]
-    fn deserialize_cluster_acl_version_info() {
+    fn deserialize_check_rocksdb_cq_write_progress_response_body() {
         let json = r#"{"diffResult":"diff"}"#;
-        let deserialized: ClusterAclVersionInfo = serde_json::from_str(json).unwrap();
+        let deserialized: CheckRocksdbCqWriteProgressResponseBody =
+            serde_json::from_str(json).unwrap();
         assert_eq!(deserialized.diff_result, Some(CheetahString::from("diff")));
     }
 
     #[test]
-    fn deserialize_cluster_acl_version_info_missing_diff_result() {
+    fn deserialize_check_rocksdb_cq_write_progress_response_body_missing_diff_result() {
         let json = r#"{}"#;
-        let deserialized: ClusterAclVersionInfo = serde_json::from_str(json).unwrap();
+        let deserialized: CheckRocksdbCqWriteProgressResponseBody =
+            serde_json::from_str(json).unwrap();
         assert!(deserialized.diff_result.is_none());
     }
 }
@@ -17,7 +17,7 @@
 use serde::Deserialize;
 use serde::Serialize;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct ConsumeStatus {
     #[serde(rename = "pullRT")]
     pub pull_rt: f64,
@@ -67,7 +67,7 @@ mod tests {
     #[test]
     fn consume_status_deserialization() {
         let serialized = r#"{"pullRT":1.1,"pullTPS":1.2,"consumeRT":1.3,"consumeOKTPS":1.4,"consumeFailedTPS":1.5,"consumeFailedMsgs":6}"#;
-        let deserialized: ConsumeStatus = serde_json::from_str(&serialized).unwrap();
+        let deserialized: ConsumeStatus = serde_json::from_str(serialized).unwrap();
         assert_eq!(deserialized.pull_rt, 1.1);
         assert_eq!(deserialized.pull_tps, 1.2);
         assert_eq!(deserialized.consume_rt, 1.3);
@@ -14,5 +14,83 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-#[derive(Clone)]
-pub struct ConsumerRunningInfo {}
+
+use std::collections::HashMap;
+
+use cheetah_string::CheetahString;
+use rocketmq_common::common::message::message_queue::MessageQueue;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json_any_key::*;
+
+use crate::protocol::body::consume_status::ConsumeStatus;
+use crate::protocol::body::pop_process_queue_info::PopProcessQueueInfo;
+use crate::protocol::body::process_queue_info::ProcessQueueInfo;
+use crate::protocol::heartbeat::subscription_data::SubscriptionData;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ConsumerRunningInfo {
+    pub properties: HashMap<CheetahString, CheetahString>,
+    pub subscription_set: Vec<SubscriptionData>,
+    #[serde(with = "any_key_map")]
+    pub mq_table: HashMap<MessageQueue, ProcessQueueInfo>,
+    #[serde(with = "any_key_map")]
+    pub mq_pop_table: HashMap<MessageQueue, PopProcessQueueInfo>,
+    pub status_table: HashMap<CheetahString, ConsumeStatus>,
+    pub user_consumer_info: HashMap<CheetahString, CheetahString>,
+    pub jstack: Option<CheetahString>,
+}
+
+impl ConsumerRunningInfo {
+    pub const PROP_NAMESERVER_ADDR: &'static str = "PROP_NAMESERVER_ADDR";
+    pub const PROP_THREADPOOL_CORE_SIZE: &'static str = "PROP_THREADPOOL_CORE_SIZE";
+    pub const PROP_CONSUME_ORDERLY: &'static str = "PROP_CONSUMEORDERLY";
+    pub const PROP_CONSUME_TYPE: &'static str = "PROP_CONSUME_TYPE";
+    pub const PROP_CLIENT_VERSION: &'static str = "PROP_CLIENT_VERSION";
+    pub const PROP_CONSUMER_START_TIMESTAMP: &'static str = "PROP_CONSUMER_START_TIMESTAMP";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::RemotingDeserializable;
+    use crate::protocol::RemotingSerializable;
+
+    #[test]
+    fn consumer_running_info_round_trips_through_json() {
+        let mut info = ConsumerRunningInfo::default();
+        info.properties.insert(
+            CheetahString::from_static_str(ConsumerRunningInfo::PROP_CONSUME_TYPE),
+            CheetahString::from_static_str("CONSUME_PASSIVELY"),
+        );
+        info.mq_table.insert(
+            MessageQueue::from_parts("test_topic", "broker-a", 0),
+            ProcessQueueInfo {
+                commit_offset: 10,
+                cached_msg_count: 2,
+                ..Default::default()
+            },
+        );
+        info.jstack = Some(CheetahString::from_static_str("main"));
+
+        let json = info.to_json().unwrap();
+        assert!(json.contains("\"mqTable\""));
+        assert!(json.contains("\"commitOffset\":10"));
+        let decoded = ConsumerRunningInfo::decode(json.as_bytes()).unwrap();
+        assert_eq!(
+            decoded
+                .properties
+                .get(ConsumerRunningInfo::PROP_CONSUME_TYPE)
+                .unwrap(),
+            "CONSUME_PASSIVELY"
+        );
+        let process_queue_info = decoded
+            .mq_table
+            .get(&MessageQueue::from_parts("test_topic", "broker-a", 0))
+            .unwrap();
+        assert_eq!(process_queue_info.commit_offset, 10);
+        assert_eq!(process_queue_info.cached_msg_count, 2);
+        assert_eq!(decoded.jstack.unwrap(), "main");
+    }
+}
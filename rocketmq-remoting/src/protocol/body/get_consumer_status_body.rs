@@ -0,0 +1,99 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+
+use cheetah_string::CheetahString;
+use rocketmq_common::common::message::message_queue::MessageQueue;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+use serde_json_any_key::*;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetConsumerStatusBody {
+    #[serde(with = "any_key_map")]
+    pub message_queue_table: HashMap<MessageQueue, i64>,
+    #[serde(
+        serialize_with = "serialize_consumer_table",
+        deserialize_with = "deserialize_consumer_table"
+    )]
+    pub consumer_table: HashMap<CheetahString, HashMap<MessageQueue, i64>>,
+}
+
+/// The offset table of a single client, keyed by message queue.
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+struct OffsetTable(#[serde(with = "any_key_map")] HashMap<MessageQueue, i64>);
+
+fn serialize_consumer_table<S>(
+    consumer_table: &HashMap<CheetahString, HashMap<MessageQueue, i64>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    consumer_table
+        .iter()
+        .map(|(client_id, offset_table)| (client_id, OffsetTable(offset_table.clone())))
+        .collect::<HashMap<_, _>>()
+        .serialize(serializer)
+}
+
+fn deserialize_consumer_table<'de, D>(
+    deserializer: D,
+) -> Result<HashMap<CheetahString, HashMap<MessageQueue, i64>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(
+        HashMap::<CheetahString, OffsetTable>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(client_id, offset_table)| (client_id, offset_table.0))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::RemotingDeserializable;
+    use crate::protocol::RemotingSerializable;
+
+    #[test]
+    fn get_consumer_status_body_round_trips_through_json() {
+        let mq = MessageQueue::from_parts("test_topic", "broker-a", 0);
+        let mut body = GetConsumerStatusBody::default();
+        body.message_queue_table.insert(mq.clone(), 7);
+        body.consumer_table.insert(
+            CheetahString::from_static_str("client-1"),
+            HashMap::from([(mq.clone(), 9)]),
+        );
+        let json = body.to_json().unwrap();
+        let decoded = GetConsumerStatusBody::decode(json.as_bytes()).unwrap();
+        assert_eq!(decoded.message_queue_table.get(&mq), Some(&7));
+        assert_eq!(
+            decoded
+                .consumer_table
+                .get("client-1")
+                .and_then(|offset_table| offset_table.get(&mq)),
+            Some(&9)
+        );
+    }
+}
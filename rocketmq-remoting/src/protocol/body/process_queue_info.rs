@@ -15,12 +15,17 @@
  * limitations under the License.
  */
 
-#[derive(Debug, Clone, Copy)]
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ProcessQueueInfo {
     pub commit_offset: u64,
     pub cached_msg_min_offset: u64,
     pub cached_msg_max_offset: u64,
     pub cached_msg_count: u32,
+    #[serde(rename = "cachedMsgSizeInMiB")]
     pub cached_msg_size_in_mib: u32,
 
     pub transaction_msg_min_offset: u64,
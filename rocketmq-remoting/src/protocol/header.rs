@@ -15,13 +15,18 @@
  * limitations under the License.
  */
 pub mod ack_message_request_header;
+pub mod acl_request_header;
 pub mod broker;
+pub mod broker_container_request_header;
 pub mod change_invisible_time_request_header;
 pub mod change_invisible_time_response_header;
+pub mod check_rocksdb_cq_write_progress_request_header;
 pub mod check_transaction_state_request_header;
 pub mod client_request_header;
+pub mod clone_group_offset_request_header;
 pub mod consume_message_directly_result_request_header;
 pub mod consumer_send_msg_back_request_header;
+pub mod controller;
 pub mod create_topic_request_header;
 pub mod delete_subscription_group_request_header;
 pub mod delete_topic_request_header;
@@ -35,12 +40,15 @@ pub mod get_consumer_connection_list_request_header;
 pub mod get_consumer_listby_group_request_header;
 pub mod get_consumer_listby_group_response_header;
 pub mod get_consumer_running_info_request_header;
+pub mod get_consumer_status_request_header;
 pub mod get_earliest_msg_storetime_response_header;
 pub mod get_max_offset_request_header;
 pub mod get_max_offset_response_header;
 pub mod get_meta_data_response_header;
 pub mod get_min_offset_request_header;
 pub mod get_min_offset_response_header;
+pub mod get_producer_connection_list_request_header;
+pub mod get_subscription_group_config_request_header;
 pub mod get_topic_config_request_header;
 pub mod get_topic_stats_info_request_header;
 pub mod get_topic_stats_request_header;
@@ -51,6 +59,9 @@ pub mod namesrv;
 pub mod notification_request_header;
 pub mod notification_response_header;
 pub mod notify_consumer_ids_changed_request_header;
+pub mod peek_message_request_header;
+pub mod polling_info_request_header;
+pub mod polling_info_response_header;
 pub mod pop_message_request_header;
 pub mod pop_message_response_header;
 pub mod pull_message_request_header;
@@ -64,7 +75,10 @@ pub mod query_subscription_by_consumer_request_header;
 pub mod query_topic_consume_by_who_request_header;
 pub mod query_topics_by_consumer_request_header;
 pub mod reply_message_request_header;
+pub mod reset_master_flush_offset_header;
 pub mod reset_offset_request_header;
+pub mod resume_check_half_message_request_header;
+pub mod search_offset_request_header;
 pub mod search_offset_response_header;
 pub mod unlock_batch_mq_request_header;
 pub mod unregister_client_request_header;
@@ -0,0 +1,191 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use cheetah_string::CheetahString;
+use rocketmq_macros::RequestHeaderCodec;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateUserRequestHeader {
+    #[required]
+    pub username: CheetahString,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateUserRequestHeader {
+    #[required]
+    pub username: CheetahString,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteUserRequestHeader {
+    #[required]
+    pub username: CheetahString,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct GetUserRequestHeader {
+    #[required]
+    pub username: CheetahString,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct ListUsersRequestHeader {
+    pub filter: Option<CheetahString>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAclRequestHeader {
+    #[required]
+    pub subject: CheetahString,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateAclRequestHeader {
+    #[required]
+    pub subject: CheetahString,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteAclRequestHeader {
+    #[required]
+    pub subject: CheetahString,
+
+    pub policy_type: Option<CheetahString>,
+
+    pub resource: Option<CheetahString>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAclRequestHeader {
+    #[required]
+    pub subject: CheetahString,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct ListAclsRequestHeader {
+    pub subject_filter: Option<CheetahString>,
+
+    pub resource_filter: Option<CheetahString>,
+}
+
+/// Header of `UPDATE_AND_CREATE_ACL_CONFIG`, topic and group permissions are comma separated
+/// `resource=PERM` pairs.
+#[derive(Debug, Clone, Deserialize, Serialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAccessConfigRequestHeader {
+    #[required]
+    pub access_key: CheetahString,
+
+    pub secret_key: Option<CheetahString>,
+
+    pub white_remote_address: Option<CheetahString>,
+
+    pub admin: Option<bool>,
+
+    pub default_topic_perm: Option<CheetahString>,
+
+    pub default_group_perm: Option<CheetahString>,
+
+    pub topic_perms: Option<CheetahString>,
+
+    pub group_perms: Option<CheetahString>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteAccessConfigRequestHeader {
+    #[required]
+    pub access_key: CheetahString,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateGlobalWhiteAddrsConfigRequestHeader {
+    #[required]
+    pub global_white_addrs: CheetahString,
+
+    pub acl_file_full_path: Option<CheetahString>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::protocol::command_custom_header::CommandCustomHeader;
+    use crate::protocol::command_custom_header::FromMap;
+
+    #[test]
+    fn delete_acl_request_header_round_trip() {
+        let header = DeleteAclRequestHeader {
+            subject: CheetahString::from("User:rocketmq"),
+            policy_type: None,
+            resource: Some(CheetahString::from("Topic:TopicA")),
+        };
+        let map = header.to_map().unwrap();
+        assert_eq!(
+            map.get(&CheetahString::from_static_str("subject")).unwrap(),
+            "User:rocketmq"
+        );
+        assert!(!map.contains_key(&CheetahString::from_static_str("policyType")));
+
+        let decoded = <DeleteAclRequestHeader as FromMap>::from(&map).unwrap();
+        assert_eq!(decoded.subject, "User:rocketmq");
+        assert_eq!(decoded.resource.unwrap(), "Topic:TopicA");
+    }
+
+    #[test]
+    fn create_access_config_request_header_from_map() {
+        let mut map = HashMap::new();
+        map.insert(
+            CheetahString::from_static_str("accessKey"),
+            CheetahString::from("rocketmq2"),
+        );
+        map.insert(
+            CheetahString::from_static_str("admin"),
+            CheetahString::from("true"),
+        );
+        map.insert(
+            CheetahString::from_static_str("topicPerms"),
+            CheetahString::from("topicA=PUB,topicB=SUB"),
+        );
+
+        let header = <CreateAccessConfigRequestHeader as FromMap>::from(&map).unwrap();
+        assert_eq!(header.access_key, "rocketmq2");
+        assert_eq!(header.admin, Some(true));
+        assert_eq!(header.topic_perms.unwrap(), "topicA=PUB,topicB=SUB");
+        assert!(header.secret_key.is_none());
+    }
+
+    #[test]
+    fn missing_required_field_is_rejected() {
+        let map = HashMap::new();
+        assert!(<GetUserRequestHeader as FromMap>::from(&map).is_err());
+    }
+}
@@ -0,0 +1,62 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use cheetah_string::CheetahString;
+use rocketmq_macros::RequestHeaderCodec;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct AddBrokerRequestHeader {
+    pub config_path: Option<CheetahString>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveBrokerRequestHeader {
+    #[required]
+    pub broker_name: CheetahString,
+
+    #[required]
+    pub broker_cluster_name: CheetahString,
+
+    #[required]
+    pub broker_id: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::command_custom_header::CommandCustomHeader;
+    use crate::protocol::command_custom_header::FromMap;
+
+    #[test]
+    fn remove_broker_request_header_round_trips_through_map() {
+        let header = RemoveBrokerRequestHeader {
+            broker_name: CheetahString::from_static_str("broker-a"),
+            broker_cluster_name: CheetahString::from_static_str("DefaultCluster"),
+            broker_id: 1,
+        };
+        let map = header.to_map().unwrap();
+        assert_eq!(map.get("brokerClusterName").unwrap(), "DefaultCluster");
+        let decoded = <RemoveBrokerRequestHeader as FromMap>::from(&map).unwrap();
+        assert_eq!(decoded.broker_name, "broker-a");
+        assert_eq!(decoded.broker_cluster_name, "DefaultCluster");
+        assert_eq!(decoded.broker_id, 1);
+    }
+}
@@ -0,0 +1,63 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::fmt::Display;
+
+use cheetah_string::CheetahString;
+use rocketmq_macros::RequestHeaderCodec;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Asks a broker to compare its RocksDB consume queues with the file consume queues.
+///
+/// An empty `topic` checks every topic on the broker.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckRocksdbCqWriteProgressRequestHeader {
+    #[required]
+    pub topic: CheetahString,
+}
+
+impl Display for CheckRocksdbCqWriteProgressRequestHeader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "CheckRocksdbCqWriteProgressRequestHeader [topic={}]",
+            self.topic
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::protocol::command_custom_header::CommandCustomHeader;
+    use crate::protocol::command_custom_header::FromMap;
+
+    #[test]
+    fn check_rocksdb_cq_write_progress_request_header_round_trip() {
+        let header = CheckRocksdbCqWriteProgressRequestHeader {
+            topic: CheetahString::from("topic1"),
+        };
+        let map: HashMap<CheetahString, CheetahString> = header.to_map().unwrap();
+        assert_eq!(map.get("topic").unwrap(), "topic1");
+
+        let decoded = <CheckRocksdbCqWriteProgressRequestHeader as FromMap>::from(&map).unwrap();
+        assert_eq!(decoded.topic, "topic1");
+    }
+}
@@ -0,0 +1,66 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use cheetah_string::CheetahString;
+use rocketmq_macros::RequestHeaderCodec;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::rpc::rpc_request_header::RpcRequestHeader;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct CloneGroupOffsetRequestHeader {
+    #[required]
+    pub src_group: CheetahString,
+
+    #[required]
+    pub dest_group: CheetahString,
+
+    pub topic: Option<CheetahString>,
+
+    pub offline: bool,
+
+    #[serde(flatten)]
+    pub rpc_request_header: Option<RpcRequestHeader>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::command_custom_header::CommandCustomHeader;
+    use crate::protocol::command_custom_header::FromMap;
+
+    #[test]
+    fn clone_group_offset_request_header_round_trips_through_map() {
+        let header = CloneGroupOffsetRequestHeader {
+            src_group: CheetahString::from_static_str("src_group"),
+            dest_group: CheetahString::from_static_str("dest_group"),
+            topic: Some(CheetahString::from_static_str("test_topic")),
+            offline: true,
+            rpc_request_header: None,
+        };
+        let map = header.to_map().unwrap();
+        assert_eq!(map.get("srcGroup").unwrap(), "src_group");
+        assert_eq!(map.get("destGroup").unwrap(), "dest_group");
+        let decoded = <CloneGroupOffsetRequestHeader as FromMap>::from(&map).unwrap();
+        assert_eq!(decoded.src_group, "src_group");
+        assert_eq!(decoded.dest_group, "dest_group");
+        assert_eq!(decoded.topic.unwrap(), "test_topic");
+        assert!(decoded.offline);
+    }
+}
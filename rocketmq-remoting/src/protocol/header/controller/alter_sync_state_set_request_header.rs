@@ -0,0 +1,77 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use cheetah_string::CheetahString;
+use rocketmq_macros::RequestHeaderCodec;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct AlterSyncStateSetRequestHeader {
+    #[required]
+    pub broker_name: CheetahString,
+
+    #[required]
+    pub master_broker_id: i64,
+
+    #[required]
+    pub master_epoch: i32,
+
+    pub invoke_time: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct AlterSyncStateSetResponseHeader {
+    pub new_sync_state_set_epoch: Option<i32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::protocol::command_custom_header::CommandCustomHeader;
+    use crate::protocol::command_custom_header::FromMap;
+
+    #[test]
+    fn alter_sync_state_set_request_header_round_trips_through_map() {
+        let header = AlterSyncStateSetRequestHeader {
+            broker_name: CheetahString::from_static_str("broker-a"),
+            master_broker_id: 1,
+            master_epoch: 3,
+            invoke_time: Some(100),
+        };
+        let map = header.to_map().unwrap();
+        assert_eq!(
+            map.get(&CheetahString::from_static_str("masterEpoch"))
+                .unwrap(),
+            "3"
+        );
+        let decoded = <AlterSyncStateSetRequestHeader as FromMap>::from(&map).unwrap();
+        assert_eq!(decoded.broker_name, "broker-a");
+        assert_eq!(decoded.master_broker_id, 1);
+        assert_eq!(decoded.master_epoch, 3);
+        assert_eq!(decoded.invoke_time, Some(100));
+    }
+
+    #[test]
+    fn alter_sync_state_set_response_header_handles_missing_epoch() {
+        let decoded = <AlterSyncStateSetResponseHeader as FromMap>::from(&HashMap::new()).unwrap();
+        assert!(decoded.new_sync_state_set_epoch.is_none());
+    }
+}
@@ -0,0 +1,67 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use cheetah_string::CheetahString;
+use rocketmq_macros::RequestHeaderCodec;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyBrokerIdRequestHeader {
+    #[required]
+    pub cluster_name: CheetahString,
+
+    #[required]
+    pub broker_name: CheetahString,
+
+    #[required]
+    pub applied_broker_id: i64,
+
+    #[required]
+    pub register_check_code: CheetahString,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyBrokerIdResponseHeader {
+    pub cluster_name: Option<CheetahString>,
+
+    pub broker_name: Option<CheetahString>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::command_custom_header::CommandCustomHeader;
+    use crate::protocol::command_custom_header::FromMap;
+
+    #[test]
+    fn apply_broker_id_request_header_round_trips_through_map() {
+        let header = ApplyBrokerIdRequestHeader {
+            cluster_name: CheetahString::from_static_str("cluster"),
+            broker_name: CheetahString::from_static_str("broker-a"),
+            applied_broker_id: 2,
+            register_check_code: CheetahString::from_static_str("127.0.0.1:10911;1"),
+        };
+        let map = header.to_map().unwrap();
+        let decoded = <ApplyBrokerIdRequestHeader as FromMap>::from(&map).unwrap();
+        assert_eq!(decoded.cluster_name, "cluster");
+        assert_eq!(decoded.broker_name, "broker-a");
+        assert_eq!(decoded.applied_broker_id, 2);
+        assert_eq!(decoded.register_check_code, "127.0.0.1:10911;1");
+    }
+}
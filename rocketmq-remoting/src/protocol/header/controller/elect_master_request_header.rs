@@ -0,0 +1,80 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use cheetah_string::CheetahString;
+use rocketmq_macros::RequestHeaderCodec;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct ElectMasterRequestHeader {
+    #[required]
+    pub cluster_name: CheetahString,
+
+    #[required]
+    pub broker_name: CheetahString,
+
+    /// The broker asking for the election, or the designated master when `designate_elect` is
+    /// set.
+    #[required]
+    pub broker_id: i64,
+
+    #[required]
+    pub designate_elect: bool,
+
+    pub invoke_time: Option<u64>,
+}
+
+impl ElectMasterRequestHeader {
+    pub fn new(
+        cluster_name: impl Into<CheetahString>,
+        broker_name: impl Into<CheetahString>,
+        broker_id: i64,
+    ) -> Self {
+        Self {
+            cluster_name: cluster_name.into(),
+            broker_name: broker_name.into(),
+            broker_id,
+            designate_elect: false,
+            invoke_time: Some(rocketmq_common::TimeUtils::get_current_millis()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::command_custom_header::CommandCustomHeader;
+    use crate::protocol::command_custom_header::FromMap;
+
+    #[test]
+    fn elect_master_request_header_round_trips_through_map() {
+        let header = ElectMasterRequestHeader::new("cluster", "broker-a", 2);
+        let map = header.to_map().unwrap();
+        assert_eq!(
+            map.get(&CheetahString::from_static_str("designateElect"))
+                .unwrap(),
+            "false"
+        );
+        let decoded = <ElectMasterRequestHeader as FromMap>::from(&map).unwrap();
+        assert_eq!(decoded.cluster_name, "cluster");
+        assert_eq!(decoded.broker_name, "broker-a");
+        assert_eq!(decoded.broker_id, 2);
+        assert!(!decoded.designate_elect);
+        assert_eq!(decoded.invoke_time, header.invoke_time);
+    }
+}
@@ -0,0 +1,61 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use cheetah_string::CheetahString;
+use rocketmq_macros::RequestHeaderCodec;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct GetNextBrokerIdRequestHeader {
+    #[required]
+    pub cluster_name: CheetahString,
+
+    #[required]
+    pub broker_name: CheetahString,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct GetNextBrokerIdResponseHeader {
+    pub cluster_name: Option<CheetahString>,
+
+    pub broker_name: Option<CheetahString>,
+
+    pub next_broker_id: Option<i64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::command_custom_header::CommandCustomHeader;
+    use crate::protocol::command_custom_header::FromMap;
+
+    #[test]
+    fn get_next_broker_id_response_header_round_trips_through_map() {
+        let header = GetNextBrokerIdResponseHeader {
+            cluster_name: Some(CheetahString::from_static_str("cluster")),
+            broker_name: Some(CheetahString::from_static_str("broker-a")),
+            next_broker_id: Some(3),
+        };
+        let map = header.to_map().unwrap();
+        let decoded = <GetNextBrokerIdResponseHeader as FromMap>::from(&map).unwrap();
+        assert_eq!(decoded.cluster_name.unwrap(), "cluster");
+        assert_eq!(decoded.broker_name.unwrap(), "broker-a");
+        assert_eq!(decoded.next_broker_id, Some(3));
+    }
+}
@@ -0,0 +1,67 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use cheetah_string::CheetahString;
+use rocketmq_macros::RequestHeaderCodec;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct GetReplicaInfoRequestHeader {
+    #[required]
+    pub broker_name: CheetahString,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct GetReplicaInfoResponseHeader {
+    pub master_broker_id: Option<i64>,
+
+    pub master_address: Option<CheetahString>,
+
+    pub master_epoch: Option<i32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::protocol::command_custom_header::CommandCustomHeader;
+    use crate::protocol::command_custom_header::FromMap;
+
+    #[test]
+    fn get_replica_info_response_header_round_trips_through_map() {
+        let header = GetReplicaInfoResponseHeader {
+            master_broker_id: Some(1),
+            master_address: Some(CheetahString::from_static_str("127.0.0.1:10911")),
+            master_epoch: Some(2),
+        };
+        let map = header.to_map().unwrap();
+        let decoded = <GetReplicaInfoResponseHeader as FromMap>::from(&map).unwrap();
+        assert_eq!(decoded.master_broker_id, Some(1));
+        assert_eq!(decoded.master_address.unwrap(), "127.0.0.1:10911");
+        assert_eq!(decoded.master_epoch, Some(2));
+    }
+
+    #[test]
+    fn get_replica_info_response_header_without_master() {
+        let decoded = <GetReplicaInfoResponseHeader as FromMap>::from(&HashMap::new()).unwrap();
+        assert!(decoded.master_broker_id.is_none());
+        assert!(decoded.master_address.is_none());
+    }
+}
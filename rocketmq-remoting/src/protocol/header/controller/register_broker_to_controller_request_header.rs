@@ -0,0 +1,94 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use cheetah_string::CheetahString;
+use rocketmq_macros::RequestHeaderCodec;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterBrokerToControllerRequestHeader {
+    #[required]
+    pub cluster_name: CheetahString,
+
+    #[required]
+    pub broker_name: CheetahString,
+
+    #[required]
+    pub broker_id: i64,
+
+    #[required]
+    pub broker_address: CheetahString,
+
+    pub invoke_time: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct RegisterBrokerToControllerResponseHeader {
+    pub cluster_name: Option<CheetahString>,
+
+    pub broker_name: Option<CheetahString>,
+
+    pub master_broker_id: Option<i64>,
+
+    pub master_address: Option<CheetahString>,
+
+    pub master_epoch: Option<i32>,
+
+    pub sync_state_set_epoch: Option<i32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::command_custom_header::CommandCustomHeader;
+    use crate::protocol::command_custom_header::FromMap;
+
+    #[test]
+    fn register_broker_to_controller_request_header_round_trips_through_map() {
+        let header = RegisterBrokerToControllerRequestHeader {
+            cluster_name: CheetahString::from_static_str("cluster"),
+            broker_name: CheetahString::from_static_str("broker-a"),
+            broker_id: 1,
+            broker_address: CheetahString::from_static_str("127.0.0.1:10911"),
+            invoke_time: None,
+        };
+        let map = header.to_map().unwrap();
+        let decoded = <RegisterBrokerToControllerRequestHeader as FromMap>::from(&map).unwrap();
+        assert_eq!(decoded.broker_id, 1);
+        assert_eq!(decoded.broker_address, "127.0.0.1:10911");
+        assert!(decoded.invoke_time.is_none());
+    }
+
+    #[test]
+    fn register_broker_to_controller_response_header_round_trips_through_map() {
+        let header = RegisterBrokerToControllerResponseHeader {
+            cluster_name: Some(CheetahString::from_static_str("cluster")),
+            broker_name: Some(CheetahString::from_static_str("broker-a")),
+            master_broker_id: Some(1),
+            master_address: Some(CheetahString::from_static_str("127.0.0.1:10911")),
+            master_epoch: Some(4),
+            sync_state_set_epoch: Some(5),
+        };
+        let map = header.to_map().unwrap();
+        let decoded = <RegisterBrokerToControllerResponseHeader as FromMap>::from(&map).unwrap();
+        assert_eq!(decoded.master_broker_id, Some(1));
+        assert_eq!(decoded.master_epoch, Some(4));
+        assert_eq!(decoded.sync_state_set_epoch, Some(5));
+    }
+}
@@ -18,6 +18,7 @@ use cheetah_string::CheetahString;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::protocol::command_custom_header::CommandCustomHeader;
 use crate::protocol::command_custom_header::FromMap;
 use crate::rpc::rpc_request_header::RpcRequestHeader;
 
@@ -41,6 +42,22 @@ impl GetConsumerConnectionListRequestHeader {
     }
 }
 
+impl CommandCustomHeader for GetConsumerConnectionListRequestHeader {
+    fn to_map(&self) -> Option<std::collections::HashMap<CheetahString, CheetahString>> {
+        let mut map = std::collections::HashMap::new();
+        map.insert(
+            CheetahString::from_static_str(Self::CONSUMER_GROUP),
+            self.consumer_group.clone(),
+        );
+        if let Some(value) = self.rpc_request_header.as_ref() {
+            if let Some(value) = value.to_map() {
+                map.extend(value);
+            }
+        }
+        Some(map)
+    }
+}
+
 impl FromMap for GetConsumerConnectionListRequestHeader {
     type Error = rocketmq_error::RocketmqError;
 
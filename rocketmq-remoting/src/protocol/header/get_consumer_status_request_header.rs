@@ -0,0 +1,61 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use cheetah_string::CheetahString;
+use rocketmq_macros::RequestHeaderCodec;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::rpc::topic_request_header::TopicRequestHeader;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct GetConsumerStatusRequestHeader {
+    #[required]
+    pub topic: CheetahString,
+
+    #[required]
+    pub group: CheetahString,
+
+    pub client_addr: Option<CheetahString>,
+
+    #[serde(flatten)]
+    pub topic_request_header: Option<TopicRequestHeader>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::command_custom_header::CommandCustomHeader;
+    use crate::protocol::command_custom_header::FromMap;
+
+    #[test]
+    fn get_consumer_status_request_header_round_trips_through_map() {
+        let header = GetConsumerStatusRequestHeader {
+            topic: CheetahString::from_static_str("test_topic"),
+            group: CheetahString::from_static_str("test_group"),
+            client_addr: Some(CheetahString::from_static_str("127.0.0.1")),
+            topic_request_header: None,
+        };
+        let map = header.to_map().unwrap();
+        assert_eq!(map.get("clientAddr").unwrap(), "127.0.0.1");
+        let decoded = <GetConsumerStatusRequestHeader as FromMap>::from(&map).unwrap();
+        assert_eq!(decoded.topic, "test_topic");
+        assert_eq!(decoded.group, "test_group");
+        assert_eq!(decoded.client_addr.unwrap(), "127.0.0.1");
+    }
+}
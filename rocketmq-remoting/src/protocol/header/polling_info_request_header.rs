@@ -0,0 +1,86 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::fmt::Display;
+
+use cheetah_string::CheetahString;
+use rocketmq_macros::RequestHeaderCodec;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::protocol::header::namesrv::topic_operation_header::TopicRequestHeader;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct PollingInfoRequestHeader {
+    #[required]
+    pub consumer_group: CheetahString,
+    #[required]
+    pub topic: CheetahString,
+    #[required]
+    pub queue_id: i32,
+
+    #[serde(flatten)]
+    pub topic_request_header: Option<TopicRequestHeader>,
+}
+
+impl Display for PollingInfoRequestHeader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PollingInfoRequestHeader [consumer_group={}, topic={}, queue_id={}]",
+            self.consumer_group, self.topic, self.queue_id
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::protocol::command_custom_header::CommandCustomHeader;
+    use crate::protocol::command_custom_header::FromMap;
+
+    #[test]
+    fn polling_info_request_header_round_trip() {
+        let header = PollingInfoRequestHeader {
+            consumer_group: CheetahString::from("group1"),
+            topic: CheetahString::from("topic1"),
+            queue_id: 3,
+            topic_request_header: None,
+        };
+        let map: HashMap<CheetahString, CheetahString> = header.to_map().unwrap();
+        assert_eq!(map.get("consumerGroup").unwrap(), "group1");
+        assert_eq!(map.get("topic").unwrap(), "topic1");
+        assert_eq!(map.get("queueId").unwrap(), "3");
+
+        let decoded = <PollingInfoRequestHeader as FromMap>::from(&map).unwrap();
+        assert_eq!(decoded.consumer_group, "group1");
+        assert_eq!(decoded.queue_id, 3);
+    }
+
+    #[test]
+    fn polling_info_request_header_requires_queue_id() {
+        let mut map = HashMap::new();
+        map.insert(
+            CheetahString::from("consumerGroup"),
+            CheetahString::from("g"),
+        );
+        map.insert(CheetahString::from("topic"), CheetahString::from("t"));
+        assert!(<PollingInfoRequestHeader as FromMap>::from(&map).is_err());
+    }
+}
@@ -0,0 +1,55 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use cheetah_string::CheetahString;
+use rocketmq_macros::RequestHeaderCodec;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::rpc::rpc_request_header::RpcRequestHeader;
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default, RequestHeaderCodec)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeCheckHalfMessageRequestHeader {
+    pub topic: Option<CheetahString>,
+
+    pub msg_id: Option<CheetahString>,
+
+    #[serde(flatten)]
+    pub rpc_request_header: Option<RpcRequestHeader>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::command_custom_header::CommandCustomHeader;
+    use crate::protocol::command_custom_header::FromMap;
+
+    #[test]
+    fn resume_check_half_message_request_header_round_trips_through_map() {
+        let header = ResumeCheckHalfMessageRequestHeader {
+            topic: Some(CheetahString::from_static_str("test_topic")),
+            msg_id: Some(CheetahString::from_static_str("msg_id")),
+            rpc_request_header: None,
+        };
+        let map = header.to_map().unwrap();
+        assert_eq!(map.get("msgId").unwrap(), "msg_id");
+        let decoded = <ResumeCheckHalfMessageRequestHeader as FromMap>::from(&map).unwrap();
+        assert_eq!(decoded.topic.unwrap(), "test_topic");
+        assert_eq!(decoded.msg_id.unwrap(), "msg_id");
+    }
+}
@@ -39,6 +39,14 @@ impl ConsumeType {
             ConsumeType::ConsumePop => "POP",
         }
     }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConsumeType::ConsumeActively => "CONSUME_ACTIVELY",
+            ConsumeType::ConsumePassively => "CONSUME_PASSIVELY",
+            ConsumeType::ConsumePop => "CONSUME_POP",
+        }
+    }
 }
 
 impl Serialize for ConsumeType {
@@ -46,12 +54,7 @@ impl Serialize for ConsumeType {
     where
         S: Serializer,
     {
-        let value = match self {
-            ConsumeType::ConsumeActively => "CONSUME_ACTIVELY",
-            ConsumeType::ConsumePassively => "CONSUME_PASSIVELY",
-            ConsumeType::ConsumePop => "CONSUME_POP",
-        };
-        serializer.serialize_str(value)
+        serializer.serialize_str(self.name())
     }
 }
 
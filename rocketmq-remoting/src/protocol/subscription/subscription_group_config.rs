@@ -16,6 +16,7 @@
  */
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::fmt::Display;
 
 use cheetah_string::CheetahString;
 use rocketmq_common::common::mix_all::MASTER_ID;
@@ -90,6 +91,40 @@ impl Default for SubscriptionGroupConfig {
     }
 }
 
+impl Display for SubscriptionGroupConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let attributes = self
+            .attributes
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(
+            f,
+            "SubscriptionGroupConfig{{groupName={}, consumeEnable={}, consumeFromMinEnable={}, \
+             consumeBroadcastEnable={}, consumeMessageOrderly={}, retryQueueNums={}, \
+             retryMaxTimes={}, groupRetryPolicy={:?}, brokerId={}, \
+             whichBrokerWhenConsumeSlowly={}, notifyConsumerIdsChangedEnable={}, groupSysFlag={}, \
+             consumeTimeoutMinute={}, subscriptionDataSet={:?}, attributes={{{}}}}}",
+            self.group_name,
+            self.consume_enable,
+            self.consume_from_min_enable,
+            self.consume_broadcast_enable,
+            self.consume_message_orderly,
+            self.retry_queue_nums,
+            self.retry_max_times,
+            self.group_retry_policy,
+            self.broker_id,
+            self.which_broker_when_consume_slowly,
+            self.notify_consumer_ids_changed_enable,
+            self.group_sys_flag,
+            self.consume_timeout_minute,
+            self.subscription_data_set,
+            attributes
+        )
+    }
+}
+
 impl SubscriptionGroupConfig {
     #[inline]
     pub fn group_name(&self) -> &str {
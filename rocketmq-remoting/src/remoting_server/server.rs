@@ -48,10 +48,14 @@ use crate::runtime::connection_handler_context::ConnectionHandlerContext;
 use crate::runtime::connection_handler_context::ConnectionHandlerContextWrapper;
 use crate::runtime::processor::RequestProcessor;
 use crate::runtime::RPCHook;
+use crate::tls::TlsServerAcceptor;
 
 /// Default limit the max number of connections.
 const DEFAULT_MAX_CONNECTIONS: usize = 1000;
 
+/// How long a client may take to complete the TLS handshake before it is dropped.
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Shorthand for the transmit half of the message channel.
 type Tx = mpsc::UnboundedSender<RemotingCommand>;
 
@@ -301,6 +305,8 @@ struct ConnectionListener<RP> {
     rpc_hooks: Arc<Vec<Box<dyn RPCHook>>>,
 
     channel_event_listener: Option<Arc<dyn ChannelEventListener>>,
+
+    tls_acceptor: TlsServerAcceptor,
 }
 
 impl<RP: RequestProcessor + Sync + 'static + Clone> ConnectionListener<RP> {
@@ -345,33 +351,60 @@ impl<RP: RequestProcessor + Sync + 'static + Clone> ConnectionListener<RP> {
             info!("Accepted connection, client ip:{}", remote_addr);
             socket.set_nodelay(true).expect("set nodelay failed");
             let local_addr = socket.local_addr()?;
-            let response_table = ArcMut::new(HashMap::with_capacity(128));
-            let channel_inner = ArcMut::new(ChannelInner::new(
-                Connection::new(socket),
-                response_table.clone(),
-            ));
-            //create per connection handler state
-            let weak_channel = ArcMut::downgrade(&channel_inner);
-            let channel = Channel::new(weak_channel, local_addr, remote_addr);
-            let _ = tx.send(TokioEvent::new(
-                ConnectionNetEvent::CONNECTED(remote_addr),
-                remote_addr,
-                channel.clone(),
-            ));
-            let mut handler = ConnectionHandler {
-                request_processor: self.request_processor.clone(),
-                connection_handler_context: ArcMut::new(ConnectionHandlerContextWrapper {
-                    channel: channel.clone(),
-                }),
-                channel_inner: (channel_inner, channel),
-                shutdown: Shutdown::new(self.notify_shutdown.subscribe()),
-                _shutdown_complete: self.shutdown_complete_tx.clone(),
-                conn_disconnect_notify: self.conn_disconnect_notify.clone(),
-                rpc_hooks: self.rpc_hooks.clone(),
-                response_table,
-            };
+            let tls_acceptor = self.tls_acceptor.clone();
+            let request_processor = self.request_processor.clone();
+            let shutdown = Shutdown::new(self.notify_shutdown.subscribe());
+            let shutdown_complete = self.shutdown_complete_tx.clone();
+            let conn_disconnect_notify = self.conn_disconnect_notify.clone();
+            let rpc_hooks = self.rpc_hooks.clone();
             let sender = tx.clone();
+            // The handshake runs on the connection task so a slow client cannot stall accepting.
             tokio::spawn(async move {
+                let stream =
+                    match time::timeout(TLS_HANDSHAKE_TIMEOUT, tls_acceptor.accept(socket)).await {
+                        Ok(Ok(stream)) => stream,
+                        Ok(Err(err)) => {
+                            warn!(
+                                "TLS handshake with client[IP={}] failed: {}",
+                                remote_addr, err
+                            );
+                            drop(permit);
+                            return;
+                        }
+                        Err(_) => {
+                            warn!(
+                                "TLS handshake with client[IP={}] timed out after {:?}",
+                                remote_addr, TLS_HANDSHAKE_TIMEOUT
+                            );
+                            drop(permit);
+                            return;
+                        }
+                    };
+                let response_table = ArcMut::new(HashMap::with_capacity(128));
+                let channel_inner = ArcMut::new(ChannelInner::new(
+                    Connection::new(stream),
+                    response_table.clone(),
+                ));
+                //create per connection handler state
+                let weak_channel = ArcMut::downgrade(&channel_inner);
+                let channel = Channel::new(weak_channel, local_addr, remote_addr);
+                let _ = sender.send(TokioEvent::new(
+                    ConnectionNetEvent::CONNECTED(remote_addr),
+                    remote_addr,
+                    channel.clone(),
+                ));
+                let mut handler = ConnectionHandler {
+                    request_processor,
+                    connection_handler_context: ArcMut::new(ConnectionHandlerContextWrapper {
+                        channel: channel.clone(),
+                    }),
+                    channel_inner: (channel_inner, channel),
+                    shutdown,
+                    _shutdown_complete: shutdown_complete,
+                    conn_disconnect_notify,
+                    rpc_hooks,
+                    response_table,
+                };
                 if let Err(err) = handler.handle().await {
                     error!(cause = ?err, "connection error");
                 }
@@ -421,6 +454,7 @@ impl<RP: RequestProcessor + Sync + 'static + Clone> ConnectionListener<RP> {
 
 pub struct RocketMQServer<RP> {
     config: Arc<ServerConfig>,
+    rpc_hooks: Vec<Arc<dyn RPCHook>>,
     _phantom_data: std::marker::PhantomData<RP>,
 }
 
@@ -428,9 +462,16 @@ impl<RP> RocketMQServer<RP> {
     pub fn new(config: Arc<ServerConfig>) -> Self {
         Self {
             config,
+            rpc_hooks: Vec::new(),
             _phantom_data: std::marker::PhantomData,
         }
     }
+
+    /// Registers a hook run around every request this server handles, must be called before
+    /// `run`.
+    pub fn register_rpc_hook(&mut self, hook: Arc<dyn RPCHook>) {
+        self.rpc_hooks.push(hook);
+    }
 }
 
 impl<RP: RequestProcessor + Sync + 'static + Clone> RocketMQServer<RP> {
@@ -438,7 +479,8 @@ impl<RP: RequestProcessor + Sync + 'static + Clone> RocketMQServer<RP> {
         &self,
         request_processor: RP,
         channel_event_listener: Option<Arc<dyn ChannelEventListener>>,
-    ) {
+    ) -> rocketmq_error::RocketMQResult<()> {
+        let tls_acceptor = TlsServerAcceptor::new(&self.config)?;
         let listener = TcpListener::bind(&format!(
             "{}:{}",
             self.config.bind_address, self.config.listen_port
@@ -455,10 +497,15 @@ impl<RP: RequestProcessor + Sync + 'static + Clone> RocketMQServer<RP> {
             wait_for_signal(),
             request_processor,
             Some(notify_conn_disconnect),
-            vec![],
+            self.rpc_hooks
+                .iter()
+                .map(|hook| Box::new(hook.clone()) as Box<dyn RPCHook>)
+                .collect(),
             channel_event_listener,
+            tls_acceptor,
         )
         .await;
+        Ok(())
     }
 }
 
@@ -469,6 +516,7 @@ pub async fn run<RP: RequestProcessor + Sync + 'static + Clone>(
     conn_disconnect_notify: Option<broadcast::Sender<SocketAddr>>,
     rpc_hooks: Vec<Box<dyn RPCHook>>,
     channel_event_listener: Option<Arc<dyn ChannelEventListener>>,
+    tls_acceptor: TlsServerAcceptor,
 ) {
     let (notify_shutdown, _) = broadcast::channel(1);
     let (shutdown_complete_tx, mut shutdown_complete_rx) = mpsc::channel(1);
@@ -482,6 +530,7 @@ pub async fn run<RP: RequestProcessor + Sync + 'static + Clone>(
         request_processor,
         rpc_hooks: Arc::new(rpc_hooks),
         channel_event_listener,
+        tls_acceptor,
     };
 
     tokio::select! {
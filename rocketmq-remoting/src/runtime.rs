@@ -15,6 +15,7 @@
  * limitations under the License.
  */
 use std::net::SocketAddr;
+use std::sync::Arc;
 
 use crate::protocol::remoting_command::RemotingCommand;
 
@@ -73,3 +74,21 @@ pub trait RPCHook: Send + Sync + 'static {
         response: &mut RemotingCommand,
     ) -> rocketmq_error::RocketMQResult<()>;
 }
+
+impl RPCHook for Arc<dyn RPCHook> {
+    fn do_before_request(
+        &self,
+        remote_addr: SocketAddr,
+        request: &mut RemotingCommand,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        self.as_ref().do_before_request(remote_addr, request)
+    }
+
+    fn do_after_response(
+        &self,
+        remote_addr: SocketAddr,
+        response: &mut RemotingCommand,
+    ) -> rocketmq_error::RocketMQResult<()> {
+        self.as_ref().do_after_response(remote_addr, response)
+    }
+}
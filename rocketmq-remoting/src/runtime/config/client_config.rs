@@ -16,6 +16,7 @@
  */
 
 use lazy_static::lazy_static;
+use rocketmq_common::common::server::tls;
 
 use crate::runtime::config::net_system_config::NetSystemConfig;
 
@@ -37,7 +38,14 @@ pub struct TokioClientConfig {
     pub client_socket_rcv_buf_size: i32,
     pub client_pooled_byte_buf_allocator_enable: bool,
     pub client_close_socket_if_timeout: bool,
-    //pub use_tls: bool,
+    pub use_tls: bool,
+    /// Verifies the server certificate against `tls_client_trust_cert_path`, otherwise any
+    /// certificate is accepted.
+    pub tls_client_auth_server: bool,
+    pub tls_client_trust_cert_path: Option<String>,
+    /// Certificate and key presented when the server asks for mutual TLS.
+    pub tls_client_cert_path: Option<String>,
+    pub tls_client_key_path: Option<String>,
     pub socks_proxy_config: String,
     pub write_buffer_high_water_mark: i32,
     pub write_buffer_low_water_mark: i32,
@@ -62,7 +70,11 @@ impl Default for TokioClientConfig {
             client_socket_rcv_buf_size: NET_SYSTEM_CONFIG.socket_rcvbuf_size,
             client_pooled_byte_buf_allocator_enable: false,
             client_close_socket_if_timeout: NET_SYSTEM_CONFIG.client_close_socket_if_timeout,
-            //use_tls: TlsSystemConfig::is_tls_enabled(),
+            use_tls: tls::env_or(tls::TLS_ENABLE, false),
+            tls_client_auth_server: tls::env_or(tls::TLS_CLIENT_AUTH_SERVER, false),
+            tls_client_trust_cert_path: tls::env_path(tls::TLS_CLIENT_TRUST_CERT_PATH),
+            tls_client_cert_path: tls::env_path(tls::TLS_CLIENT_CERT_PATH),
+            tls_client_key_path: tls::env_path(tls::TLS_CLIENT_KEY_PATH),
             socks_proxy_config: "{}".to_string(),
             write_buffer_high_water_mark: NET_SYSTEM_CONFIG.write_buffer_high_water_mark_value,
             write_buffer_low_water_mark: NET_SYSTEM_CONFIG.write_buffer_low_water_mark,
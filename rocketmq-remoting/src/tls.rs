@@ -0,0 +1,492 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Optional TLS transport for the remoting server and client, built on rustls.
+
+use std::fmt::Debug;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use rocketmq_common::common::server::config::ServerConfig;
+use rocketmq_common::common::server::tls::TlsClientAuth;
+use rocketmq_common::common::server::tls::TlsMode;
+use rocketmq_error::RocketMQResult;
+use rocketmq_error::RocketmqError;
+use rustls::client::danger::HandshakeSignatureValid;
+use rustls::client::danger::ServerCertVerified;
+use rustls::client::danger::ServerCertVerifier;
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::CertificateDer;
+use rustls::pki_types::PrivateKeyDer;
+use rustls::pki_types::ServerName;
+use rustls::pki_types::UnixTime;
+use rustls::server::WebPkiClientVerifier;
+use rustls::DigitallySignedStruct;
+use rustls::RootCertStore;
+use rustls::SignatureScheme;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
+use tokio::io::ReadBuf;
+use tokio::net::TcpStream;
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::TlsConnector;
+use tracing::info;
+
+use crate::runtime::config::client_config::TokioClientConfig;
+
+/// First byte of a TLS record carrying a handshake message.
+const TLS_HANDSHAKE_RECORD: u8 = 0x16;
+
+/// The byte stream under a [`Connection`](crate::connection::Connection), either plain TCP or
+/// TLS on top of it.
+pub enum TransportStream {
+    Plain(TcpStream),
+    ServerTls(Box<tokio_rustls::server::TlsStream<TcpStream>>),
+    ClientTls(Box<tokio_rustls::client::TlsStream<TcpStream>>),
+}
+
+impl TransportStream {
+    pub fn is_tls(&self) -> bool {
+        !matches!(self, TransportStream::Plain(_))
+    }
+}
+
+impl From<TcpStream> for TransportStream {
+    fn from(stream: TcpStream) -> Self {
+        TransportStream::Plain(stream)
+    }
+}
+
+impl AsyncRead for TransportStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            TransportStream::Plain(stream) => Pin::new(stream).poll_read(cx, buf),
+            TransportStream::ServerTls(stream) => Pin::new(stream.as_mut()).poll_read(cx, buf),
+            TransportStream::ClientTls(stream) => Pin::new(stream.as_mut()).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for TransportStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            TransportStream::Plain(stream) => Pin::new(stream).poll_write(cx, buf),
+            TransportStream::ServerTls(stream) => Pin::new(stream.as_mut()).poll_write(cx, buf),
+            TransportStream::ClientTls(stream) => Pin::new(stream.as_mut()).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            TransportStream::Plain(stream) => Pin::new(stream).poll_flush(cx),
+            TransportStream::ServerTls(stream) => Pin::new(stream.as_mut()).poll_flush(cx),
+            TransportStream::ClientTls(stream) => Pin::new(stream.as_mut()).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            TransportStream::Plain(stream) => Pin::new(stream).poll_shutdown(cx),
+            TransportStream::ServerTls(stream) => Pin::new(stream.as_mut()).poll_shutdown(cx),
+            TransportStream::ClientTls(stream) => Pin::new(stream.as_mut()).poll_shutdown(cx),
+        }
+    }
+}
+
+/// Wraps accepted sockets according to the server's [`TlsMode`].
+#[derive(Clone)]
+pub struct TlsServerAcceptor {
+    mode: TlsMode,
+    acceptor: Option<TlsAcceptor>,
+}
+
+impl TlsServerAcceptor {
+    pub fn new(config: &ServerConfig) -> RocketMQResult<Self> {
+        if config.tls_mode == TlsMode::Disabled {
+            return Ok(Self {
+                mode: TlsMode::Disabled,
+                acceptor: None,
+            });
+        }
+        let provider = crypto_provider();
+        let cert_path = config
+            .tls_server_cert_path
+            .as_deref()
+            .ok_or_else(|| tls_error("tls.server.certPath is required when tls is not disabled"))?;
+        let key_path = config
+            .tls_server_key_path
+            .as_deref()
+            .ok_or_else(|| tls_error("tls.server.keyPath is required when tls is not disabled"))?;
+        let builder = rustls::ServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .map_err(tls_error)?;
+        let builder = match config.tls_server_need_client_auth {
+            TlsClientAuth::None => builder.with_no_client_auth(),
+            client_auth => {
+                let trust_path = config
+                    .tls_server_trust_cert_path
+                    .as_deref()
+                    .ok_or_else(|| {
+                        tls_error(
+                            "tls.server.trustCertPath is required to verify client certificates",
+                        )
+                    })?;
+                let verifier = WebPkiClientVerifier::builder_with_provider(
+                    Arc::new(load_root_store(trust_path)?),
+                    provider,
+                );
+                let verifier = if client_auth == TlsClientAuth::Optional {
+                    verifier.allow_unauthenticated()
+                } else {
+                    verifier
+                };
+                builder.with_client_cert_verifier(verifier.build().map_err(tls_error)?)
+            }
+        };
+        let server_config = builder
+            .with_single_cert(load_certs(cert_path)?, load_private_key(key_path)?)
+            .map_err(tls_error)?;
+        info!(
+            "Remoting server tls mode: {}, client auth: {:?}",
+            config.tls_mode, config.tls_server_need_client_auth
+        );
+        Ok(Self {
+            mode: config.tls_mode,
+            acceptor: Some(TlsAcceptor::from(Arc::new(server_config))),
+        })
+    }
+
+    pub fn mode(&self) -> TlsMode {
+        self.mode
+    }
+
+    /// Performs the TLS handshake when the mode asks for it. In permissive mode the first byte
+    /// tells a TLS client hello from a plain remoting frame, whose length prefix never starts
+    /// with `0x16` for sane frame sizes.
+    pub async fn accept(&self, stream: TcpStream) -> RocketMQResult<TransportStream> {
+        let Some(acceptor) = self.acceptor.as_ref() else {
+            return Ok(TransportStream::Plain(stream));
+        };
+        if self.mode == TlsMode::Permissive {
+            let mut first = [0u8; 1];
+            if stream.peek(&mut first).await? == 0 || first[0] != TLS_HANDSHAKE_RECORD {
+                return Ok(TransportStream::Plain(stream));
+            }
+        }
+        let stream = acceptor.accept(stream).await?;
+        Ok(TransportStream::ServerTls(Box::new(stream)))
+    }
+}
+
+/// Wraps connected sockets in TLS when the client enables it.
+#[derive(Clone)]
+pub struct TlsClientConnector {
+    connector: Option<TlsConnector>,
+}
+
+impl TlsClientConnector {
+    pub fn new(config: &TokioClientConfig) -> RocketMQResult<Self> {
+        if !config.use_tls {
+            return Ok(Self { connector: None });
+        }
+        let provider = crypto_provider();
+        let builder = rustls::ClientConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .map_err(tls_error)?;
+        let builder = if config.tls_client_auth_server {
+            let trust_path = config
+                .tls_client_trust_cert_path
+                .as_deref()
+                .ok_or_else(|| {
+                    tls_error("tls.client.trustCertPath is required to verify the server")
+                })?;
+            builder.with_root_certificates(load_root_store(trust_path)?)
+        } else {
+            builder
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(NoServerVerification(provider)))
+        };
+        let client_config = match (
+            config.tls_client_cert_path.as_deref(),
+            config.tls_client_key_path.as_deref(),
+        ) {
+            (Some(cert_path), Some(key_path)) => builder
+                .with_client_auth_cert(load_certs(cert_path)?, load_private_key(key_path)?)
+                .map_err(tls_error)?,
+            _ => builder.with_no_client_auth(),
+        };
+        Ok(Self {
+            connector: Some(TlsConnector::from(Arc::new(client_config))),
+        })
+    }
+
+    pub async fn connect(
+        &self,
+        stream: TcpStream,
+        remote_addr: SocketAddr,
+    ) -> RocketMQResult<TransportStream> {
+        let Some(connector) = self.connector.as_ref() else {
+            return Ok(TransportStream::Plain(stream));
+        };
+        let server_name = ServerName::IpAddress(remote_addr.ip().into());
+        let stream = connector.connect(server_name, stream).await?;
+        Ok(TransportStream::ClientTls(Box::new(stream)))
+    }
+}
+
+/// Accepts any server certificate, used unless `tls.client.authServer` is set, matching the
+/// Java client's default.
+#[derive(Debug)]
+struct NoServerVerification(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for NoServerVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+fn crypto_provider() -> Arc<CryptoProvider> {
+    Arc::new(rustls::crypto::ring::default_provider())
+}
+
+fn load_certs(path: &str) -> RocketMQResult<Vec<CertificateDer<'static>>> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| tls_error(format!("failed to load certificates from {path}: {e}")))?;
+    if certs.is_empty() {
+        return Err(tls_error(format!("no certificate found in {path}")));
+    }
+    Ok(certs)
+}
+
+fn load_private_key(path: &str) -> RocketMQResult<PrivateKeyDer<'static>> {
+    PrivateKeyDer::from_pem_file(path)
+        .map_err(|e| tls_error(format!("failed to load private key from {path}: {e}")))
+}
+
+fn load_root_store(path: &str) -> RocketMQResult<RootCertStore> {
+    let mut roots = RootCertStore::empty();
+    for cert in load_certs(path)? {
+        roots.add(cert).map_err(tls_error)?;
+    }
+    Ok(roots)
+}
+
+fn tls_error(error: impl std::fmt::Display) -> RocketmqError {
+    RocketmqError::TlsError(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::AsyncReadExt;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::clients::rocketmq_default_impl::RocketmqDefaultClient;
+    use crate::remoting_server::server::RocketMQServer;
+    use crate::request_processor::default_request_processor::DefaultRemotingRequestProcessor;
+
+    fn testdata(name: &str) -> Option<String> {
+        Some(format!(
+            "{}/testdata/tls/{name}",
+            env!("CARGO_MANIFEST_DIR")
+        ))
+    }
+
+    fn server_config(mode: TlsMode, client_auth: TlsClientAuth) -> ServerConfig {
+        ServerConfig {
+            tls_mode: mode,
+            tls_server_need_client_auth: client_auth,
+            tls_server_cert_path: testdata("server.pem"),
+            tls_server_key_path: testdata("server.key"),
+            tls_server_trust_cert_path: testdata("ca.pem"),
+            ..ServerConfig::default()
+        }
+    }
+
+    fn client_config(use_tls: bool, with_cert: bool) -> TokioClientConfig {
+        TokioClientConfig {
+            use_tls,
+            tls_client_auth_server: true,
+            tls_client_trust_cert_path: testdata("ca.pem"),
+            tls_client_cert_path: with_cert.then(|| testdata("client.pem")).flatten(),
+            tls_client_key_path: with_cert.then(|| testdata("client.key")).flatten(),
+            ..TokioClientConfig::default()
+        }
+    }
+
+    /// Connects the client to an echo server and returns whether the stream was TLS on both
+    /// sides, or `None` when the exchange failed.
+    async fn echo(server: &ServerConfig, client: &TokioClientConfig) -> Option<(bool, bool)> {
+        let acceptor = TlsServerAcceptor::new(server).unwrap();
+        let connector = TlsClientConnector::new(client).unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server_task = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut stream = acceptor.accept(socket).await.ok()?;
+            let mut buf = [0u8; 4];
+            stream.read_exact(&mut buf).await.ok()?;
+            stream.write_all(&buf).await.ok()?;
+            stream.flush().await.ok()?;
+            Some(stream.is_tls())
+        });
+
+        let socket = TcpStream::connect(addr).await.unwrap();
+        let client_result = async {
+            let mut stream = connector.connect(socket, addr).await.ok()?;
+            stream.write_all(b"ping").await.ok()?;
+            stream.flush().await.ok()?;
+            let mut buf = [0u8; 4];
+            stream.read_exact(&mut buf).await.ok()?;
+            (&buf == b"ping").then_some(stream.is_tls())
+        }
+        .await;
+        let server_result = server_task.await.unwrap();
+        Some((client_result?, server_result?))
+    }
+
+    #[tokio::test]
+    async fn enforcing_mode_speaks_tls() {
+        let server = server_config(TlsMode::Enforcing, TlsClientAuth::None);
+        assert_eq!(
+            echo(&server, &client_config(true, false)).await,
+            Some((true, true))
+        );
+        assert_eq!(echo(&server, &client_config(false, false)).await, None);
+    }
+
+    #[tokio::test]
+    async fn permissive_mode_accepts_plain_and_tls() {
+        let server = server_config(TlsMode::Permissive, TlsClientAuth::None);
+        assert_eq!(
+            echo(&server, &client_config(true, false)).await,
+            Some((true, true))
+        );
+        assert_eq!(
+            echo(&server, &client_config(false, false)).await,
+            Some((false, false))
+        );
+    }
+
+    #[tokio::test]
+    async fn required_client_auth_rejects_anonymous_clients() {
+        let server = server_config(TlsMode::Enforcing, TlsClientAuth::Require);
+        assert_eq!(
+            echo(&server, &client_config(true, true)).await,
+            Some((true, true))
+        );
+        assert_eq!(echo(&server, &client_config(true, false)).await, None);
+
+        let server = server_config(TlsMode::Enforcing, TlsClientAuth::Optional);
+        assert_eq!(
+            echo(&server, &client_config(true, false)).await,
+            Some((true, true))
+        );
+    }
+
+    #[test]
+    fn missing_certificate_is_reported() {
+        let server = ServerConfig {
+            tls_mode: TlsMode::Enforcing,
+            tls_server_cert_path: None,
+            ..ServerConfig::default()
+        };
+        assert!(matches!(
+            TlsServerAcceptor::new(&server),
+            Err(RocketmqError::TlsError(_))
+        ));
+        assert!(TlsServerAcceptor::new(&ServerConfig::default()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn server_returns_invalid_tls_config() {
+        let server = RocketMQServer::new(Arc::new(ServerConfig {
+            tls_mode: TlsMode::Enforcing,
+            tls_server_cert_path: None,
+            listen_port: 0,
+            ..ServerConfig::default()
+        }));
+        assert!(matches!(
+            server.run(DefaultRemotingRequestProcessor, None).await,
+            Err(RocketmqError::TlsError(_))
+        ));
+    }
+
+    #[test]
+    fn client_with_invalid_tls_config_is_still_created() {
+        let client_config = TokioClientConfig {
+            tls_client_trust_cert_path: None,
+            ..client_config(true, false)
+        };
+        assert!(TlsClientConnector::new(&client_config).is_err());
+        RocketmqDefaultClient::new(Arc::new(client_config), DefaultRemotingRequestProcessor);
+    }
+}
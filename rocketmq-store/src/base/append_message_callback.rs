@@ -147,7 +147,14 @@ impl AppendMessageCallback for DefaultAppendMessageCallback {
             bytes.put_i32(max_blank);
             bytes.put_i32(BLANK_MAGIC_CODE);
             let instant = Instant::now();
-            mapped_file.write_bytes_segment(bytes.as_ref(), wrote_offset as usize, 0, bytes.len());
+            mapped_file.write_bytes_segment(
+                bytes.as_ref(),
+                mapped_file.get_wrote_position() as usize,
+                0,
+                bytes.len(),
+            );
+            // the message is appended again to the next file, keep the encoded buffer for it
+            msg_inner.encoded_buff = Some(pre_encode_buffer);
             return AppendMessageResult {
                 status: AppendMessageStatus::EndOfFile,
                 wrote_offset,
@@ -30,6 +30,7 @@ use rocketmq_common::common::message::message_ext::MessageExt;
 use rocketmq_common::common::message::message_ext_broker_inner::MessageExtBrokerInner;
 use rocketmq_common::common::system_clock::SystemClock;
 use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_rust::ArcMut;
 
 use crate::base::allocate_mapped_file_service::AllocateMappedFileService;
 use crate::base::commit_log_dispatcher::CommitLogDispatcher;
@@ -44,6 +45,7 @@ use crate::base::store_stats_service::StoreStatsService;
 use crate::base::transient_store_pool::TransientStorePool;
 use crate::config::message_store_config::MessageStoreConfig;
 use crate::filter::MessageFilter;
+use crate::ha::general_ha_service::GeneralHAService;
 use crate::hook::put_message_hook::BoxedPutMessageHook;
 use crate::hook::put_message_hook::PutMessageHook;
 use crate::hook::send_message_back_hook::SendMessageBackHook;
@@ -53,6 +55,7 @@ use crate::queue::ArcConsumeQueue;
 use crate::stats::broker_stats_manager::BrokerStatsManager;
 use crate::store::running_flags::RunningFlags;
 use crate::store_error::StoreError;
+use crate::tiered::tiered_metadata::TieredMetadataStore;
 use crate::timer::timer_message_store::TimerMessageStore;
 
 type AsyncResult<T> = Pin<Box<dyn Future<Output = Result<T, StoreError>> + Send>>;
@@ -364,6 +367,16 @@ pub trait MessageStoreInner: Sync + 'static {
         batch_size: i32,
     ) -> bool;
 
+    /// Check if the message at the given consume offset lies in the cold area of the commit log,
+    /// i.e. is unlikely to still be in the page cache.
+    fn is_msg_in_cold_area(
+        &self,
+        group: &CheetahString,
+        topic: &CheetahString,
+        queue_id: i32,
+        offset: i64,
+    ) -> bool;
+
     /// Check if the given message is in store.
     fn check_in_store_by_consume_offset(
         &self,
@@ -464,7 +477,8 @@ pub trait MessageStoreInner: Sync + 'static {
     /// Get the transient store pool
     fn get_transient_store_pool(&self) -> Arc<TransientStorePool>;
 
-    // fn get_ha_service(&self) -> Arc<dyn HAService>;
+    /// Get the HA service
+    fn get_ha_service(&self) -> Option<&ArcMut<GeneralHAService>>;
 
     /// Get the allocate-mappedFile service
     fn get_allocate_mapped_file_service(&self) -> Arc<AllocateMappedFileService>;
@@ -579,6 +593,12 @@ pub trait MessageStoreInner: Sync + 'static {
     /// Get whether message store is shutdown
     fn is_shutdown(&self) -> bool;
 
+    /// Whether old data may live in a tiered backend rather than on local disk, in which case
+    /// a missing message can show up on a later read.
+    fn is_tiered_message_store(&self) -> bool {
+        false
+    }
+
     /// Estimate number of messages, within [from, to], which match given filter
     fn estimate_message_count(
         &self,
@@ -601,6 +621,11 @@ pub trait MessageStoreInner: Sync + 'static {
     /// the offsets and states of various topic queues.
     fn recover_topic_queue_table(&mut self);
 
+    /// Compare the RocksDB consume queue written in double-write mode with the file consume
+    /// queue of `topic`, or of every topic when `topic` is blank, and describe the first
+    /// difference found for each queue.
+    fn check_rocksdb_cq_write_progress(&self, topic: &CheetahString) -> CheetahString;
+
     /// Notify message arrive if necessary
     fn notify_message_arrive_if_necessary(&self, dispatch_request: &mut DispatchRequest);
 
@@ -610,4 +635,12 @@ pub trait MessageStoreInner: Sync + 'static {
     ///
     /// * `put_message_hook` - The hook to set.
     fn set_put_message_hook(&mut self, put_message_hook: BoxedPutMessageHook);
+
+    /// Set the metadata of the tiered store wrapping this store, local files are then only
+    /// deleted once they have been uploaded to the tiered backend.
+    ///
+    /// # Arguments
+    ///
+    /// * `tiered_metadata` - The segments in the tiered backend.
+    fn set_tiered_metadata(&mut self, tiered_metadata: Arc<TieredMetadataStore>);
 }
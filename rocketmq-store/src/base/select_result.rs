@@ -48,11 +48,14 @@ impl Default for SelectMappedBufferResult {
 }
 
 impl SelectMappedBufferResult {
-    /// Returns the buffer.
+    /// Returns the buffer, read from the mapped file or, without one, from `bytes`.
     pub fn get_buffer(&self) -> &[u8] {
-        self.mapped_file.as_ref().unwrap().get_mapped_file()
-            [self.start_offset as usize..(self.start_offset + self.size as u64) as usize]
-            .as_ref()
+        match self.mapped_file.as_ref() {
+            Some(mapped_file) => mapped_file.get_mapped_file()
+                [self.start_offset as usize..(self.start_offset + self.size as u64) as usize]
+                .as_ref(),
+            None => self.bytes.as_deref().unwrap_or_default(),
+        }
     }
 
     pub fn get_buffer_slice_mut(&self) -> &mut [u8] {
@@ -26,6 +26,7 @@ use serde::Deserialize;
 use crate::base::store_enum::StoreType;
 use crate::config::flush_disk_type::FlushDiskType;
 use crate::queue::single_consume_queue::CQ_STORE_UNIT_SIZE;
+use crate::tiered::tiered_store_config::TieredBackendType;
 
 lazy_static! {
     static ref USER_HOME: PathBuf = dirs::home_dir().unwrap();
@@ -68,6 +69,10 @@ mod defaults {
         6
     }
 
+    pub fn access_message_in_memory_hot_ratio() -> usize {
+        26
+    }
+
     pub fn mapped_file_size_timer_log() -> usize {
         100 * 1024 * 1024 // 100MB
     }
@@ -96,6 +101,14 @@ mod defaults {
         true
     }
 
+    pub fn timer_max_delay_sec() -> u64 {
+        3600 * 24 * 3 // 3 days
+    }
+
+    pub fn timer_congest_num_each_slot() -> usize {
+        i32::MAX as usize
+    }
+
     pub fn disappear_time_after_start() -> i64 {
         -1
     }
@@ -168,6 +181,30 @@ mod defaults {
         75
     }
 
+    pub fn disk_space_warning_level_ratio() -> usize {
+        90
+    }
+
+    pub fn disk_space_clean_forcibly_ratio() -> usize {
+        85
+    }
+
+    pub fn file_reserved_time() -> usize {
+        72 // 72 hours
+    }
+
+    pub fn delete_file_batch_max() -> usize {
+        10
+    }
+
+    pub fn clean_file_forcibly_enable() -> bool {
+        true
+    }
+
+    pub fn correct_logic_min_offset_force_interval() -> usize {
+        5 * 60 * 1000 // 5 minutes
+    }
+
     pub fn max_message_size() -> i32 {
         1024 * 1024 * 4 // 4MB
     }
@@ -252,6 +289,30 @@ mod defaults {
         1
     }
 
+    pub fn ha_listen_port() -> usize {
+        10912
+    }
+
+    pub fn ha_send_heartbeat_interval() -> usize {
+        1000 * 5 // 5 seconds
+    }
+
+    pub fn ha_housekeeping_interval() -> usize {
+        1000 * 20 // 20 seconds
+    }
+
+    pub fn ha_transfer_batch_size() -> usize {
+        1024 * 32 // 32KB
+    }
+
+    pub fn ha_max_gap_not_in_sync() -> usize {
+        1024 * 1024 * 256 // 256MB
+    }
+
+    pub fn slave_timeout() -> usize {
+        3000 // 3 seconds
+    }
+
     pub fn total_replicas() -> usize {
         1
     }
@@ -260,6 +321,14 @@ mod defaults {
         1
     }
 
+    pub fn min_in_sync_replicas() -> usize {
+        1
+    }
+
+    pub fn ha_max_time_slave_not_catchup() -> usize {
+        1000 * 15
+    }
+
     pub fn topic_queue_lock_num() -> usize {
         32
     }
@@ -267,6 +336,22 @@ mod defaults {
     pub fn max_filter_message_size() -> i32 {
         16000
     }
+
+    pub fn tiered_upload_interval_ms() -> u64 {
+        10_000
+    }
+
+    pub fn tiered_file_reserved_time_hours() -> u64 {
+        72
+    }
+
+    pub fn tiered_object_store_request_timeout_ms() -> u64 {
+        300_000
+    }
+
+    pub fn tiered_object_store_connect_timeout_ms() -> u64 {
+        10_000
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq)]
@@ -338,7 +423,7 @@ pub struct MessageStoreConfig {
     #[serde(default)]
     pub timer_intercept_delay_level: bool,
 
-    #[serde(default)]
+    #[serde(default = "defaults::timer_max_delay_sec")]
     pub timer_max_delay_sec: u64,
 
     #[serde(default = "defaults::timer_wheel_enable")]
@@ -362,7 +447,7 @@ pub struct MessageStoreConfig {
     #[serde(default)]
     pub timer_stop_dequeue: bool,
 
-    #[serde(default)]
+    #[serde(default = "defaults::timer_congest_num_each_slot")]
     pub timer_congest_num_each_slot: usize,
 
     #[serde(default)]
@@ -398,10 +483,10 @@ pub struct MessageStoreConfig {
     #[serde(default)]
     pub max_recovery_commit_log_files: usize,
 
-    #[serde(default)]
+    #[serde(default = "defaults::disk_space_warning_level_ratio")]
     pub disk_space_warning_level_ratio: usize,
 
-    #[serde(default)]
+    #[serde(default = "defaults::disk_space_clean_forcibly_ratio")]
     pub disk_space_clean_forcibly_ratio: usize,
 
     #[serde(default)]
@@ -434,10 +519,10 @@ pub struct MessageStoreConfig {
     #[serde(default = "defaults::disk_max_used_space_ratio")]
     pub disk_max_used_space_ratio: usize,
 
-    #[serde(default)]
+    #[serde(default = "defaults::file_reserved_time")]
     pub file_reserved_time: usize,
 
-    #[serde(default)]
+    #[serde(default = "defaults::delete_file_batch_max")]
     pub delete_file_batch_max: usize,
 
     #[serde(default)]
@@ -500,22 +585,22 @@ pub struct MessageStoreConfig {
     #[serde(default)]
     pub message_index_safe: bool,
 
-    #[serde(default)]
+    #[serde(default = "defaults::ha_listen_port")]
     pub ha_listen_port: usize,
 
-    #[serde(default)]
+    #[serde(default = "defaults::ha_send_heartbeat_interval")]
     pub ha_send_heartbeat_interval: usize,
 
-    #[serde(default)]
+    #[serde(default = "defaults::ha_housekeeping_interval")]
     pub ha_housekeeping_interval: usize,
 
-    #[serde(default)]
+    #[serde(default = "defaults::ha_transfer_batch_size")]
     pub ha_transfer_batch_size: usize,
 
     #[serde(default)]
     pub ha_master_address: Option<String>,
 
-    #[serde(default)]
+    #[serde(default = "defaults::ha_max_gap_not_in_sync")]
     pub ha_max_gap_not_in_sync: usize,
 
     #[serde(default = "defaults::broker_role")]
@@ -530,7 +615,7 @@ pub struct MessageStoreConfig {
     #[serde(default)]
     pub put_message_timeout: usize,
 
-    #[serde(default)]
+    #[serde(default = "defaults::slave_timeout")]
     pub slave_timeout: usize,
 
     #[serde(default = "defaults::message_delay_level")]
@@ -539,7 +624,7 @@ pub struct MessageStoreConfig {
     #[serde(default = "defaults::flush_delay_offset_interval")]
     pub flush_delay_offset_interval: u64,
 
-    #[serde(default)]
+    #[serde(default = "defaults::clean_file_forcibly_enable")]
     pub clean_file_forcibly_enable: bool,
 
     #[serde(default)]
@@ -644,7 +729,7 @@ pub struct MessageStoreConfig {
     #[serde(default)]
     pub correct_logic_min_offset_sleep_interval: usize,
 
-    #[serde(default)]
+    #[serde(default = "defaults::correct_logic_min_offset_force_interval")]
     pub correct_logic_min_offset_force_interval: usize,
 
     #[serde(default)]
@@ -698,7 +783,7 @@ pub struct MessageStoreConfig {
     #[serde(default = "defaults::in_sync_replicas")]
     pub in_sync_replicas: u32,
 
-    #[serde(default)]
+    #[serde(default = "defaults::min_in_sync_replicas")]
     pub min_in_sync_replicas: usize,
 
     #[serde(default)]
@@ -713,7 +798,7 @@ pub struct MessageStoreConfig {
     #[serde(default)]
     pub max_ha_transfer_byte_in_second: usize,
 
-    #[serde(default)]
+    #[serde(default = "defaults::ha_max_time_slave_not_catchup")]
     pub ha_max_time_slave_not_catchup: usize,
 
     #[serde(default)]
@@ -758,7 +843,8 @@ pub struct MessageStoreConfig {
     #[serde(default)]
     pub sample_steps: usize,
 
-    #[serde(default)]
+    /// Percentage of physical memory, counted back from the commit log tail, treated as hot data.
+    #[serde(default = "defaults::access_message_in_memory_hot_ratio")]
     pub access_message_in_memory_hot_ratio: usize,
 
     #[serde(default)]
@@ -796,6 +882,51 @@ pub struct MessageStoreConfig {
 
     #[serde(default)]
     pub read_uncommitted: bool,
+
+    /// Wraps the store in a `TieredMessageStore` that offloads sealed files to
+    /// `tiered_backend_type`.
+    #[serde(default)]
+    pub tiered_store_enable: bool,
+
+    #[serde(default)]
+    pub tiered_backend_type: TieredBackendType,
+
+    /// Root directory of the `LocalFile` backend, `{store_path_root_dir}/tiered-objects` when
+    /// unset.
+    #[serde(default)]
+    pub tiered_store_file_path: Option<CheetahString>,
+
+    #[serde(default)]
+    pub tiered_object_store_endpoint: CheetahString,
+
+    #[serde(default)]
+    pub tiered_object_store_region: CheetahString,
+
+    #[serde(default)]
+    pub tiered_object_store_bucket: CheetahString,
+
+    #[serde(default)]
+    pub tiered_object_store_access_key: CheetahString,
+
+    #[serde(default)]
+    pub tiered_object_store_secret_key: CheetahString,
+
+    #[serde(default)]
+    pub tiered_object_store_path_style: bool,
+
+    /// Bounds a whole object store request, including the upload of a segment, so it has to
+    /// leave room for the largest commit log file.
+    #[serde(default = "defaults::tiered_object_store_request_timeout_ms")]
+    pub tiered_object_store_request_timeout_ms: u64,
+
+    #[serde(default = "defaults::tiered_object_store_connect_timeout_ms")]
+    pub tiered_object_store_connect_timeout_ms: u64,
+
+    #[serde(default = "defaults::tiered_upload_interval_ms")]
+    pub tiered_upload_interval_ms: u64,
+
+    #[serde(default = "defaults::tiered_file_reserved_time_hours")]
+    pub tiered_file_reserved_time_hours: u64,
 }
 
 impl Default for MessageStoreConfig {
@@ -832,7 +963,7 @@ impl Default for MessageStoreConfig {
             timer_enable_disruptor: false,
             timer_enable_check_metrics: false,
             timer_intercept_delay_level: false,
-            timer_max_delay_sec: 0,
+            timer_max_delay_sec: 3600 * 24 * 3,
             timer_wheel_enable: true,
             disappear_time_after_start: -1,
             timer_stop_enqueue: false,
@@ -840,7 +971,7 @@ impl Default for MessageStoreConfig {
             timer_skip_unknown_error: false,
             timer_warm_enable: false,
             timer_stop_dequeue: false,
-            timer_congest_num_each_slot: 0,
+            timer_congest_num_each_slot: i32::MAX as usize,
             timer_metric_small_threshold: 0,
             timer_progress_log_interval_ms: 0,
             store_type: Default::default(),
@@ -852,8 +983,8 @@ impl Default for MessageStoreConfig {
             flush_interval_commit_log: 500,
             commit_interval_commit_log: 200,
             max_recovery_commit_log_files: 0,
-            disk_space_warning_level_ratio: 0,
-            disk_space_clean_forcibly_ratio: 0,
+            disk_space_warning_level_ratio: 90,
+            disk_space_clean_forcibly_ratio: 85,
             use_reentrant_lock_when_put_message: false,
             flush_commit_log_timed: true,
             flush_interval_consume_queue: 1000,
@@ -886,21 +1017,21 @@ impl Default for MessageStoreConfig {
             max_index_num: 5000000 * 4,
             max_msgs_num_batch: 64,
             message_index_safe: false,
-            ha_listen_port: 0,
-            ha_send_heartbeat_interval: 0,
-            ha_housekeeping_interval: 0,
-            ha_transfer_batch_size: 0,
+            ha_listen_port: 10912,
+            ha_send_heartbeat_interval: 1000 * 5,
+            ha_housekeeping_interval: 1000 * 20,
+            ha_transfer_batch_size: 1024 * 32,
             ha_master_address: None,
-            ha_max_gap_not_in_sync: 0,
+            ha_max_gap_not_in_sync: 1024 * 1024 * 256,
             broker_role: Default::default(),
             flush_disk_type: FlushDiskType::SyncFlush,
             sync_flush_timeout: 1000 * 5,
             put_message_timeout: 0,
-            slave_timeout: 0,
+            slave_timeout: 3000,
             message_delay_level: "1s 5s 10s 30s 1m 2m 3m 4m 5m 6m 7m 8m 9m 10m 20m 30m 1h 2h"
                 .to_string(),
             flush_delay_offset_interval: 10_000,
-            clean_file_forcibly_enable: false,
+            clean_file_forcibly_enable: true,
             warm_mapped_file_enable: false,
             offset_check_in_slave: false,
             debug_lock_enable: false,
@@ -935,7 +1066,7 @@ impl Default for MessageStoreConfig {
             force_verify_prop_crc: false,
             travel_cq_file_num_when_get_message: 1,
             correct_logic_min_offset_sleep_interval: 0,
-            correct_logic_min_offset_force_interval: 0,
+            correct_logic_min_offset_force_interval: 5 * 60 * 1000,
             mapped_file_swap_enable: false,
             commit_log_force_swap_map_interval: 0,
             commit_log_swap_map_interval: 0,
@@ -953,12 +1084,12 @@ impl Default for MessageStoreConfig {
             pull_batch_max_message_count: 0,
             total_replicas: 1,
             in_sync_replicas: 1,
-            min_in_sync_replicas: 0,
+            min_in_sync_replicas: 1,
             all_ack_in_sync_state_set: false,
             enable_auto_in_sync_replicas: false,
             ha_flow_control_enable: false,
             max_ha_transfer_byte_in_second: 0,
-            ha_max_time_slave_not_catchup: 0,
+            ha_max_time_slave_not_catchup: 1000 * 15,
             sync_master_flush_offset_when_startup: false,
             max_checksum_range: 0,
             replicas_per_disk_partition: 0,
@@ -973,7 +1104,7 @@ impl Default for MessageStoreConfig {
             data_read_ahead_enable: false,
             timer_cold_data_check_interval_ms: 0,
             sample_steps: 0,
-            access_message_in_memory_hot_ratio: 0,
+            access_message_in_memory_hot_ratio: 26,
             enable_build_consume_queue_concurrently: false,
             batch_dispatch_request_thread_pool_nums: 0,
             clean_rocksdb_dirty_cq_interval_min: 0,
@@ -986,6 +1117,19 @@ impl Default for MessageStoreConfig {
             enable_dleger_commit_log: false,
             rocksdb_cq_double_write_enable: false,
             read_uncommitted: false,
+            tiered_store_enable: false,
+            tiered_backend_type: TieredBackendType::default(),
+            tiered_store_file_path: None,
+            tiered_object_store_endpoint: CheetahString::empty(),
+            tiered_object_store_region: CheetahString::empty(),
+            tiered_object_store_bucket: CheetahString::empty(),
+            tiered_object_store_access_key: CheetahString::empty(),
+            tiered_object_store_secret_key: CheetahString::empty(),
+            tiered_object_store_path_style: false,
+            tiered_object_store_request_timeout_ms: 300_000,
+            tiered_object_store_connect_timeout_ms: 10_000,
+            tiered_upload_interval_ms: 10_000,
+            tiered_file_reserved_time_hours: 72,
         }
     }
 }
@@ -1001,6 +1145,26 @@ impl MessageStoreConfig {
         self.store_path_commit_log.clone().unwrap().to_string()
     }
 
+    pub fn get_store_path_epoch_file(&self) -> String {
+        match self.store_path_epoch_file.as_ref() {
+            Some(path) => path.to_string(),
+            None => PathBuf::from(self.store_path_root_dir.to_string())
+                .join("epochFileCheckpoint")
+                .to_string_lossy()
+                .to_string(),
+        }
+    }
+
+    pub fn get_tiered_store_file_path(&self) -> String {
+        match self.tiered_store_file_path.as_ref() {
+            Some(path) => path.to_string(),
+            None => PathBuf::from(self.store_path_root_dir.to_string())
+                .join("tiered-objects")
+                .to_string_lossy()
+                .to_string(),
+        }
+    }
+
     pub fn is_enable_rocksdb_store(&self) -> bool {
         self.store_type == StoreType::RocksDB
     }
@@ -1696,6 +1860,50 @@ impl MessageStoreConfig {
             "maxFilterMessageSize".into(),
             self.max_filter_message_size.to_string(),
         );
+        properties.insert(
+            "tieredStoreEnable".into(),
+            self.tiered_store_enable.to_string(),
+        );
+        properties.insert(
+            "tieredBackendType".into(),
+            self.tiered_backend_type.get_backend_type().to_string(),
+        );
+        properties.insert(
+            "tieredStoreFilePath".into(),
+            self.get_tiered_store_file_path(),
+        );
+        properties.insert(
+            "tieredObjectStoreEndpoint".into(),
+            self.tiered_object_store_endpoint.to_string(),
+        );
+        properties.insert(
+            "tieredObjectStoreRegion".into(),
+            self.tiered_object_store_region.to_string(),
+        );
+        properties.insert(
+            "tieredObjectStoreBucket".into(),
+            self.tiered_object_store_bucket.to_string(),
+        );
+        properties.insert(
+            "tieredObjectStorePathStyle".into(),
+            self.tiered_object_store_path_style.to_string(),
+        );
+        properties.insert(
+            "tieredObjectStoreRequestTimeoutMs".into(),
+            self.tiered_object_store_request_timeout_ms.to_string(),
+        );
+        properties.insert(
+            "tieredObjectStoreConnectTimeoutMs".into(),
+            self.tiered_object_store_connect_timeout_ms.to_string(),
+        );
+        properties.insert(
+            "tieredUploadIntervalMs".into(),
+            self.tiered_upload_interval_ms.to_string(),
+        );
+        properties.insert(
+            "tieredFileReservedTimeHours".into(),
+            self.tiered_file_reserved_time_hours.to_string(),
+        );
         properties
             .into_iter()
             .map(|(k, v)| (k.into(), v.into()))
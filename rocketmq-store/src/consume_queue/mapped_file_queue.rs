@@ -21,9 +21,12 @@ use std::path::PathBuf;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
 
+use bytes::Buf;
 use cheetah_string::CheetahString;
 use parking_lot::RwLock;
+use rocketmq_common::TimeUtils::get_current_millis;
 use rocketmq_common::UtilAll::offset_to_file_name;
 use tracing::error;
 use tracing::info;
@@ -288,6 +291,11 @@ impl MappedFileQueue {
                 }
             }
         }
+        if !will_remove_files.is_empty() {
+            self.mapped_files
+                .write()
+                .retain(|mf| !will_remove_files.contains(mf));
+        }
     }
 
     #[inline]
@@ -313,15 +321,144 @@ impl MappedFileQueue {
     }
 
     #[inline]
-    pub(crate) fn delete_expired_file(&mut self, files: Vec<Arc<DefaultMappedFile>>) {
-        let mut files = files;
-        let read_guard = self.mapped_files.read();
+    pub(crate) fn delete_expired_file(&self, files: Vec<Arc<DefaultMappedFile>>) {
         if !files.is_empty() {
-            files.retain(|mf| read_guard.contains(mf));
             self.mapped_files.write().retain(|mf| !files.contains(mf));
         }
     }
 
+    /// Deletes files, oldest first, whose last modification is older than `expired_time` millis.
+    ///
+    /// The newest file is always kept. With `clean_immediately` files are deleted regardless of
+    /// their age. Returns the number of deleted files.
+    pub fn delete_expired_file_by_time(
+        &self,
+        expired_time: i64,
+        delete_files_interval: u64,
+        interval_forcibly: u64,
+        clean_immediately: bool,
+        delete_file_batch_max: usize,
+    ) -> i32 {
+        let mfs = self.mapped_files.read().clone();
+        if mfs.is_empty() {
+            return 0;
+        }
+        let mfs_length = mfs.len() - 1;
+        let mut files = Vec::new();
+        for (index, mapped_file) in mfs.iter().take(mfs_length).enumerate() {
+            let live_max_timestamp =
+                mapped_file.get_last_modified_timestamp() as i64 + expired_time;
+            if get_current_millis() as i64 >= live_max_timestamp || clean_immediately {
+                if !mapped_file.destroy(interval_forcibly) {
+                    break;
+                }
+                files.push(mapped_file.clone());
+                if files.len() >= delete_file_batch_max {
+                    break;
+                }
+                if delete_files_interval > 0 && index + 1 < mfs_length {
+                    std::thread::sleep(Duration::from_millis(delete_files_interval));
+                }
+            } else {
+                // avoid deleting files in the middle
+                break;
+            }
+        }
+        let delete_count = files.len() as i32;
+        self.delete_expired_file(files);
+        delete_count
+    }
+
+    /// Deletes logic queue files whose last unit points below the commit log `offset`.
+    ///
+    /// The newest file is always kept. Returns the number of deleted files.
+    pub fn delete_expired_file_by_offset(&self, offset: i64, unit_size: i32) -> i32 {
+        let mfs = self.mapped_files.read().clone();
+        if mfs.is_empty() {
+            return 0;
+        }
+        let mfs_length = mfs.len() - 1;
+        let mut files = Vec::new();
+        for mapped_file in mfs.iter().take(mfs_length) {
+            let destroy = match mapped_file
+                .select_mapped_buffer(self.mapped_file_size as i32 - unit_size, unit_size)
+            {
+                Some(result) => {
+                    let max_offset_in_logic_queue = result
+                        .get_bytes_ref()
+                        .filter(|bytes| bytes.len() >= 8)
+                        .map_or(-1, |bytes| bytes.as_ref().get_i64());
+                    mapped_file.release();
+                    let destroy = max_offset_in_logic_queue < offset;
+                    if destroy {
+                        info!(
+                            "physic min offset {}, logics in current mappedFile max offset {}, \
+                             delete it",
+                            offset, max_offset_in_logic_queue
+                        );
+                    }
+                    destroy
+                }
+                None if !mapped_file.is_available() => {
+                    warn!("Found a hanged consume queue file, attempting to delete it.");
+                    true
+                }
+                None => {
+                    warn!("this being not executed forever.");
+                    break;
+                }
+            };
+            if destroy && mapped_file.destroy(1000 * 60) {
+                files.push(mapped_file.clone());
+            } else {
+                break;
+            }
+        }
+        let delete_count = files.len() as i32;
+        self.delete_expired_file(files);
+        delete_count
+    }
+
+    /// Retries destroying the first file if a previous deletion left it shut down but alive.
+    pub fn retry_delete_first_file(&self, interval_forcibly: u64) -> bool {
+        let Some(mapped_file) = self.get_first_mapped_file() else {
+            return false;
+        };
+        if mapped_file.is_available() {
+            return false;
+        }
+        warn!(
+            "the mappedFile was destroyed once, but still alive, {}",
+            mapped_file.get_file_name()
+        );
+        let result = mapped_file.destroy(interval_forcibly);
+        if result {
+            info!(
+                "the mappedFile re delete OK, {}",
+                mapped_file.get_file_name()
+            );
+            self.delete_expired_file(vec![mapped_file]);
+        } else {
+            warn!(
+                "the mappedFile re delete failed, {}",
+                mapped_file.get_file_name()
+            );
+        }
+        result
+    }
+
+    #[inline]
+    pub fn is_first_file_available(&self) -> bool {
+        self.get_first_mapped_file()
+            .is_some_and(|mapped_file| mapped_file.is_available())
+    }
+
+    #[inline]
+    pub fn is_first_file_exist(&self) -> bool {
+        self.get_first_mapped_file()
+            .is_some_and(|mapped_file| Path::new(mapped_file.get_file_name().as_str()).exists())
+    }
+
     #[inline]
     pub fn destroy(&mut self) {
         for mapped_file in self.mapped_files.read().iter() {
@@ -513,4 +650,92 @@ mod tests {
         assert!(queue.load());
         assert_eq!(queue.mapped_files.read().len(), 1);
     }
+
+    #[test]
+    fn test_delete_expired_file_by_time_keeps_last_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for offset in [0u64, 1024, 2048] {
+            let file_path = temp_dir.path().join(format!("{:020}", offset));
+            fs::write(&file_path, vec![0u8; 1024]).unwrap();
+        }
+
+        let mut queue = MappedFileQueue {
+            store_path: temp_dir.path().to_string_lossy().into_owned(),
+            mapped_file_size: 1024,
+            ..MappedFileQueue::default()
+        };
+        assert!(queue.load());
+        assert_eq!(queue.get_mapped_files_size(), 3);
+
+        let reserved_time = 72 * 60 * 60 * 1000;
+        assert_eq!(
+            queue.delete_expired_file_by_time(reserved_time, 0, 1000, false, 10),
+            0
+        );
+        assert_eq!(
+            queue.delete_expired_file_by_time(reserved_time, 0, 1000, true, 10),
+            2
+        );
+        assert_eq!(queue.get_mapped_files_size(), 1);
+        assert!(queue.is_first_file_exist());
+        assert!(queue.is_first_file_available());
+    }
+
+    /// Writes consume queue files of `units_per_file` units where unit `i` points to commit log
+    /// offset `i * 100`.
+    fn write_consume_queue_files(dir: &Path, files: usize, units_per_file: usize) {
+        let unit_size = 20;
+        for file in 0..files {
+            let mut data = Vec::with_capacity(units_per_file * unit_size);
+            for unit in 0..units_per_file {
+                let index = (file * units_per_file + unit) as i64;
+                data.extend_from_slice(&(index * 100).to_be_bytes());
+                data.extend_from_slice(&100i32.to_be_bytes());
+                data.extend_from_slice(&0i64.to_be_bytes());
+            }
+            let file_from_offset = (file * units_per_file * unit_size) as u64;
+            fs::write(dir.join(format!("{:020}", file_from_offset)), data).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_delete_expired_file_by_offset_keeps_files_with_live_units() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        // units 0..=3 in the first file, 4..=7 in the second and 8..=11 in the third
+        write_consume_queue_files(temp_dir.path(), 3, 4);
+        let mut queue = MappedFileQueue {
+            store_path: temp_dir.path().to_string_lossy().into_owned(),
+            mapped_file_size: 80,
+            ..MappedFileQueue::default()
+        };
+        assert!(queue.load());
+        assert_eq!(queue.get_mapped_files_size(), 3);
+
+        // the last unit of the first file is still at the min offset, nothing can go
+        assert_eq!(queue.delete_expired_file_by_offset(300, 20), 0);
+        assert_eq!(queue.get_mapped_files_size(), 3);
+
+        // one byte further the whole first file is behind the commit log
+        assert_eq!(queue.delete_expired_file_by_offset(301, 20), 1);
+        assert_eq!(queue.get_mapped_files_size(), 2);
+        assert_eq!(
+            queue
+                .get_first_mapped_file()
+                .unwrap()
+                .get_file_from_offset(),
+            80
+        );
+        assert!(!temp_dir.path().join(format!("{:020}", 0)).exists());
+
+        // the newest file is kept even when all of its units are expired
+        assert_eq!(queue.delete_expired_file_by_offset(10_000, 20), 1);
+        assert_eq!(queue.get_mapped_files_size(), 1);
+        assert_eq!(
+            queue
+                .get_first_mapped_file()
+                .unwrap()
+                .get_file_from_offset(),
+            160
+        );
+    }
 }
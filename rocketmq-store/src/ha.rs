@@ -15,11 +15,18 @@
  * limitations under the License.
  */
 
+pub mod auto_switch;
+pub(crate) mod default_ha_client;
+pub(crate) mod default_ha_connection;
 pub(crate) mod default_ha_service;
-pub(crate) mod general_ha_service;
+pub(crate) mod flow_monitor;
+pub(crate) mod general_ha_client;
+pub(crate) mod general_ha_connection;
+pub mod general_ha_service;
+pub(crate) mod group_transfer_service;
 pub(crate) mod ha_client;
 pub(crate) mod ha_connection;
 pub(crate) mod ha_connection_state;
 pub(crate) mod ha_connection_state_notification_request;
-pub(crate) mod ha_service;
+pub mod ha_service;
 pub(crate) mod wait_notify_object;
@@ -0,0 +1,722 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicI32;
+use std::sync::atomic::AtomicI64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use parking_lot::RwLock;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_remoting::protocol::body::ha_runtime_info::HARuntimeInfo;
+use rocketmq_rust::ArcMut;
+use tracing::info;
+use tracing::warn;
+
+use crate::base::message_store::MessageStore;
+use crate::config::message_store_config::MessageStoreConfig;
+use crate::ha::auto_switch::epoch_file_cache::EpochEntry;
+use crate::ha::auto_switch::epoch_file_cache::EpochFileCache;
+use crate::ha::default_ha_client::DefaultHAClient;
+use crate::ha::default_ha_service::DefaultHAService;
+use crate::ha::default_ha_service::DefaultHAServiceInner;
+use crate::ha::general_ha_client::GeneralHAClient;
+use crate::ha::general_ha_connection::GeneralHAConnection;
+use crate::ha::ha_connection::HAConnection;
+use crate::ha::ha_connection_state_notification_request::HAConnectionStateNotificationRequest;
+use crate::ha::ha_service::HAService;
+use crate::ha::wait_notify_object::WaitNotifyObject;
+use crate::log_file::commit_log;
+use crate::log_file::flush_manager_impl::group_commit_request::GroupCommitRequest;
+use crate::message_store::local_file_message_store::LocalFileMessageStore;
+use crate::store_error::HAError;
+use crate::store_error::HAResult;
+
+/// Rounds to wait for the consume queues to catch up with the commit log when a slave is
+/// promoted.
+const WAIT_DISPATCH_ROUNDS: usize = 100;
+
+/// Callback invoked with the new sync state set when the master expands it locally and the
+/// change has to be reported to the controller.
+pub type SyncStateSetChangedListener = Arc<dyn Fn(HashSet<i64>) + Send + Sync>;
+
+#[derive(Default)]
+struct SyncStateSet {
+    local: HashSet<i64>,
+    remote: HashSet<i64>,
+    is_synchronizing: bool,
+}
+
+/// Replica state shared by the auto switch HA service, its connections and its client.
+pub(crate) struct AutoSwitchHAState {
+    epoch_cache: EpochFileCache,
+    local_broker_id: AtomicI64,
+    is_master: AtomicBool,
+    sync_state_set: RwLock<SyncStateSet>,
+    connection_caught_up_time_table: Mutex<HashMap<i64, i64>>,
+    sync_state_set_changed_listeners: Mutex<Vec<SyncStateSetChangedListener>>,
+}
+
+impl AutoSwitchHAState {
+    pub(crate) fn new(epoch_cache: EpochFileCache) -> Self {
+        Self {
+            epoch_cache,
+            local_broker_id: AtomicI64::new(-1),
+            is_master: AtomicBool::new(false),
+            sync_state_set: RwLock::new(SyncStateSet::default()),
+            connection_caught_up_time_table: Mutex::new(HashMap::new()),
+            sync_state_set_changed_listeners: Mutex::new(Vec::new()),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn epoch_cache(&self) -> &EpochFileCache {
+        &self.epoch_cache
+    }
+
+    #[inline]
+    pub(crate) fn is_master(&self) -> bool {
+        self.is_master.load(Ordering::Acquire)
+    }
+
+    #[inline]
+    pub(crate) fn local_broker_id(&self) -> i64 {
+        self.local_broker_id.load(Ordering::Acquire)
+    }
+
+    /// The sync state set used to count acks. While a change is being reported to the
+    /// controller, this is the union of the confirmed set and the reported one.
+    pub(crate) fn get_sync_state_set(&self) -> HashSet<i64> {
+        let sync_state_set = self.sync_state_set.read();
+        if sync_state_set.is_synchronizing {
+            sync_state_set
+                .local
+                .union(&sync_state_set.remote)
+                .copied()
+                .collect()
+        } else {
+            sync_state_set.local.clone()
+        }
+    }
+
+    /// The sync state set confirmed by the controller.
+    pub(crate) fn get_local_sync_state_set(&self) -> HashSet<i64> {
+        self.sync_state_set.read().local.clone()
+    }
+
+    pub(crate) fn set_sync_state_set(&self, new_sync_state_set: HashSet<i64>) {
+        let mut sync_state_set = self.sync_state_set.write();
+        sync_state_set.local = new_sync_state_set;
+        sync_state_set.is_synchronizing = false;
+    }
+
+    fn mark_synchronizing_sync_state_set(&self, new_sync_state_set: HashSet<i64>) {
+        let mut sync_state_set = self.sync_state_set.write();
+        sync_state_set.remote = new_sync_state_set;
+        sync_state_set.is_synchronizing = true;
+    }
+
+    fn notify_sync_state_set_changed(&self, new_sync_state_set: HashSet<i64>) {
+        let listeners = self.sync_state_set_changed_listeners.lock().clone();
+        for listener in listeners {
+            listener(new_sync_state_set.clone());
+        }
+    }
+
+    pub(crate) fn update_connection_last_caught_up_time(
+        &self,
+        slave_broker_id: i64,
+        last_caught_up_time_ms: i64,
+    ) {
+        self.connection_caught_up_time_table
+            .lock()
+            .entry(slave_broker_id)
+            .and_modify(|time| *time = (*time).max(last_caught_up_time_ms))
+            .or_insert(last_caught_up_time_ms);
+    }
+
+    /// Adds a slave that caught up with the current epoch to the sync state set and
+    /// notifies the listeners, who report the change to the controller.
+    pub(crate) fn maybe_expand_in_sync_state_set(
+        &self,
+        slave_broker_id: i64,
+        slave_max_offset: i64,
+        confirm_offset: i64,
+    ) {
+        let mut sync_state_set = self.get_local_sync_state_set();
+        if sync_state_set.contains(&slave_broker_id) || slave_max_offset < confirm_offset {
+            return;
+        }
+        let Some(current_epoch) = self.epoch_cache.last_entry() else {
+            return;
+        };
+        if slave_max_offset >= current_epoch.start_offset {
+            info!(
+                "The slave {} has caught up with the master, add it to the sync state set",
+                slave_broker_id
+            );
+            sync_state_set.insert(slave_broker_id);
+            self.mark_synchronizing_sync_state_set(sync_state_set.clone());
+            self.notify_sync_state_set_changed(sync_state_set);
+        }
+    }
+
+    /// Removes the slaves that have not caught up with the master for
+    /// `ha_max_time_slave_not_catchup` or have lost their connection, and returns the new
+    /// sync state set.
+    pub(crate) fn maybe_shrink_sync_state_set(
+        &self,
+        ha_max_time_slave_not_catchup: i64,
+    ) -> HashSet<i64> {
+        let mut new_sync_state_set = self.get_local_sync_state_set();
+        let local_broker_id = self.local_broker_id();
+        let now = get_current_millis() as i64;
+        let caught_up_time_table = self.connection_caught_up_time_table.lock();
+        let before = new_sync_state_set.len();
+        new_sync_state_set.retain(|broker_id| {
+            if *broker_id == local_broker_id {
+                return true;
+            }
+            match caught_up_time_table.get(broker_id) {
+                Some(last_caught_up_time) => {
+                    now - last_caught_up_time <= ha_max_time_slave_not_catchup
+                }
+                None => false,
+            }
+        });
+        drop(caught_up_time_table);
+        if new_sync_state_set.len() != before {
+            self.mark_synchronizing_sync_state_set(new_sync_state_set.clone());
+        }
+        new_sync_state_set
+    }
+
+    /// Computes the offset acknowledged by every member of the sync state set. Returns
+    /// `current` when a member has lost its connection.
+    pub(crate) fn compute_confirm_offset(
+        &self,
+        ha_service: &DefaultHAServiceInner,
+        max_phy_offset: i64,
+        current: i64,
+    ) -> i64 {
+        let sync_state_set = self.get_sync_state_set();
+        let local_broker_id = self.local_broker_id();
+        let connections = ha_service.connection_list().clone();
+        let connected = connections
+            .iter()
+            .map(|connection| connection.get_slave_broker_id())
+            .collect::<HashSet<_>>();
+        if let Some(lost) = sync_state_set
+            .iter()
+            .find(|broker_id| **broker_id != local_broker_id && !connected.contains(broker_id))
+        {
+            warn!(
+                "Slave {} is still in syncStateSet, but has lost its connection. So new offset \
+                 can't be compute.",
+                lost
+            );
+            return current;
+        }
+        connections
+            .iter()
+            .filter(|connection| {
+                sync_state_set.contains(&connection.get_slave_broker_id())
+                    && connection.get_slave_ack_offset() > 0
+            })
+            .map(|connection| connection.get_slave_ack_offset())
+            .fold(max_phy_offset, i64::min)
+    }
+
+    /// Moves the confirm offset of the master after an ack from a member of the sync state
+    /// set.
+    pub(crate) fn update_confirm_offset_when_slave_ack(
+        &self,
+        ha_service: &DefaultHAServiceInner,
+        message_store: &LocalFileMessageStore,
+        slave_broker_id: i64,
+    ) {
+        if !self.get_sync_state_set().contains(&slave_broker_id) {
+            return;
+        }
+        let confirm_offset = self.compute_confirm_offset(
+            ha_service,
+            message_store.get_max_phy_offset(),
+            message_store.get_confirm_offset(),
+        );
+        message_store.update_confirm_offset(confirm_offset);
+    }
+
+    /// The epoch history of the master sent to a slave in the handshake, the last epoch ends
+    /// at the max physical offset.
+    pub(crate) fn handshake_entries(&self, max_phy_offset: i64) -> Vec<EpochEntry> {
+        let mut entries = self.epoch_cache.get_all_entries();
+        if let Some(last_entry) = entries.last_mut() {
+            last_entry.end_offset = max_phy_offset;
+        }
+        entries
+    }
+}
+
+/// HA service of a broker running in controller mode.
+///
+/// The role is not taken from the configuration but assigned by the controller through
+/// [`HAService::change_to_master`] and [`HAService::change_to_slave`]. Replication reuses the
+/// connections of [`DefaultHAService`], extended with a handshake in which the slave
+/// truncates the commit log to the last point consistent with the epoch history of the
+/// master, and with the sync state set: the replicas that have caught up with the master.
+pub struct AutoSwitchHAService {
+    message_store_config: Arc<MessageStoreConfig>,
+    default_ha_service: DefaultHAService,
+    state: Arc<AutoSwitchHAState>,
+    message_store: Option<ArcMut<LocalFileMessageStore>>,
+    ha_client: RwLock<Option<Arc<DefaultHAClient>>>,
+}
+
+impl AutoSwitchHAService {
+    pub fn new(message_store_config: Arc<MessageStoreConfig>) -> Self {
+        let epoch_cache =
+            EpochFileCache::new_with_file(message_store_config.get_store_path_epoch_file());
+        let state = Arc::new(AutoSwitchHAState::new(epoch_cache));
+        Self {
+            default_ha_service: DefaultHAService::new_with_auto_switch(
+                message_store_config.clone(),
+                state.clone(),
+            ),
+            message_store_config,
+            state,
+            message_store: None,
+            ha_client: RwLock::new(None),
+        }
+    }
+
+    pub fn is_master(&self) -> bool {
+        self.state.is_master()
+    }
+
+    pub fn set_local_broker_id(&self, local_broker_id: i64) {
+        self.state
+            .local_broker_id
+            .store(local_broker_id, Ordering::Release);
+    }
+
+    pub fn get_local_broker_id(&self) -> i64 {
+        self.state.local_broker_id()
+    }
+
+    pub fn get_sync_state_set(&self) -> HashSet<i64> {
+        self.state.get_sync_state_set()
+    }
+
+    pub fn get_local_sync_state_set(&self) -> HashSet<i64> {
+        self.state.get_local_sync_state_set()
+    }
+
+    /// Replaces the sync state set with the one confirmed by the controller.
+    pub fn set_sync_state_set(&self, sync_state_set: HashSet<i64>) {
+        self.state.set_sync_state_set(sync_state_set);
+    }
+
+    pub fn register_sync_state_set_changed_listener(&self, listener: SyncStateSetChangedListener) {
+        self.state
+            .sync_state_set_changed_listeners
+            .lock()
+            .push(listener);
+    }
+
+    pub fn maybe_shrink_sync_state_set(&self) -> HashSet<i64> {
+        self.state.maybe_shrink_sync_state_set(
+            self.message_store_config.ha_max_time_slave_not_catchup as i64,
+        )
+    }
+
+    pub fn get_last_epoch(&self) -> i32 {
+        self.state.epoch_cache.last_epoch()
+    }
+
+    pub fn get_epoch_entries(&self) -> Vec<EpochEntry> {
+        self.state.epoch_cache.get_all_entries()
+    }
+
+    /// Computes the offset acknowledged by the whole sync state set.
+    pub fn compute_confirm_offset(&self) -> i64 {
+        let Some(message_store) = self.message_store.as_ref() else {
+            return -1;
+        };
+        self.state.compute_confirm_offset(
+            self.default_ha_service.inner(),
+            message_store.get_max_phy_offset(),
+            message_store.get_confirm_offset(),
+        )
+    }
+
+    fn message_store(&self) -> HAResult<&ArcMut<LocalFileMessageStore>> {
+        self.message_store
+            .as_ref()
+            .ok_or_else(|| HAError::Service("AutoSwitchHAService is not initialized".to_string()))
+    }
+
+    fn shutdown_ha_client(&self) {
+        if let Some(ha_client) = self.ha_client.write().take() {
+            ha_client.shutdown_client();
+        }
+    }
+
+    /// Truncates the tail of the commit log that the consume queues have not seen and that
+    /// does not hold complete messages, e.g. a message half received from the old master.
+    /// Returns the truncate offset, `-1` if nothing was truncated.
+    fn truncate_invalid_msg(message_store: &LocalFileMessageStore) -> HAResult<i64> {
+        let max_phy_offset = message_store.get_max_phy_offset();
+        let mut offset = message_store.get_reput_from_offset();
+        if offset >= max_phy_offset {
+            return Ok(-1);
+        }
+        let message_store_config = message_store.message_store_config();
+        let mut valid = true;
+        while valid && offset < max_phy_offset {
+            let Some(mut result) = message_store.get_commit_log_data(offset) else {
+                break;
+            };
+            let mut bytes = result.get_bytes().unwrap_or_default();
+            while offset < max_phy_offset {
+                if !commit_log::starts_with_complete_record(&bytes) {
+                    valid = false;
+                    break;
+                }
+                let dispatch_request = commit_log::check_message_and_return_size(
+                    &mut bytes,
+                    false,
+                    false,
+                    false,
+                    &message_store_config,
+                    message_store.max_delay_level(),
+                    message_store.delay_level_table_ref(),
+                );
+                if !dispatch_request.success {
+                    valid = false;
+                    break;
+                }
+                if dispatch_request.msg_size == 0 {
+                    offset = message_store.roll_next_file(offset);
+                    break;
+                }
+                offset += dispatch_request.msg_size as i64;
+            }
+            result.release();
+        }
+        if offset >= max_phy_offset {
+            return Ok(-1);
+        }
+        info!(
+            "Truncate invalid messages in commit log from {} to {}",
+            offset, max_phy_offset
+        );
+        match message_store.truncate_files(offset) {
+            Ok(true) => Ok(offset),
+            Ok(false) => Err(HAError::Service(format!(
+                "failed to truncate commit log to {offset}"
+            ))),
+            Err(e) => Err(HAError::Service(e.to_string())),
+        }
+    }
+
+    async fn wait_dispatch(message_store: &LocalFileMessageStore) {
+        for _ in 0..WAIT_DISPATCH_ROUNDS {
+            if message_store.dispatch_behind_bytes() <= 0 {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        warn!(
+            "Consume queues are still {} bytes behind the commit log",
+            message_store.dispatch_behind_bytes()
+        );
+    }
+
+    fn append_epoch_entry(&self, master_epoch: i32, start_offset: i64) -> bool {
+        let epoch_cache = &self.state.epoch_cache;
+        if epoch_cache.last_epoch() >= master_epoch {
+            epoch_cache.truncate_suffix_by_epoch(master_epoch);
+        }
+        epoch_cache.append_entry(EpochEntry::new(master_epoch, start_offset))
+    }
+}
+
+impl HAService for AutoSwitchHAService {
+    fn init(&mut self, message_store: ArcMut<LocalFileMessageStore>) -> HAResult<()> {
+        if !self.state.epoch_cache.init_cache_from_file() {
+            return Err(HAError::Service(format!(
+                "failed to load epoch file {}",
+                self.message_store_config.get_store_path_epoch_file()
+            )));
+        }
+        self.default_ha_service.init(message_store.clone())?;
+        self.message_store = Some(message_store);
+        Ok(())
+    }
+
+    fn start(&mut self) -> HAResult<()> {
+        self.default_ha_service.start()
+    }
+
+    fn shutdown(&self) {
+        self.shutdown_ha_client();
+        self.default_ha_service.shutdown();
+    }
+
+    async fn change_to_master(&self, master_epoch: i32) -> HAResult<bool> {
+        let last_epoch = self.state.epoch_cache.last_epoch();
+        if master_epoch < last_epoch {
+            warn!(
+                "newMasterEpoch {} < lastEpoch {}, fail to change to master",
+                master_epoch, last_epoch
+            );
+            return Ok(false);
+        }
+        let message_store = self.message_store()?;
+        self.default_ha_service.inner().destroy_connections();
+        self.shutdown_ha_client();
+
+        let truncate_offset = Self::truncate_invalid_msg(message_store)?;
+        if truncate_offset >= 0 {
+            self.state
+                .epoch_cache
+                .truncate_suffix_by_offset(truncate_offset);
+        }
+        let start_offset = message_store.get_max_phy_offset();
+        if !self.append_epoch_entry(master_epoch, start_offset) {
+            return Ok(false);
+        }
+        self.state.is_master.store(true, Ordering::Release);
+        message_store.update_confirm_offset(self.compute_confirm_offset());
+
+        Self::wait_dispatch(message_store).await;
+        message_store.mut_from_ref().recover_topic_queue_table();
+        message_store.set_state_machine_version(master_epoch as i64);
+        info!(
+            "Change ha to master success, newMasterEpoch:{}, startOffset:{}",
+            master_epoch, start_offset
+        );
+        Ok(true)
+    }
+
+    async fn change_to_master_when_last_role_is_master(&self, master_epoch: i32) -> HAResult<bool> {
+        let last_epoch = self.state.epoch_cache.last_epoch();
+        if master_epoch < last_epoch {
+            warn!(
+                "newMasterEpoch {} < lastEpoch {}, fail to change to master",
+                master_epoch, last_epoch
+            );
+            return Ok(false);
+        }
+        let start_offset = self.message_store()?.get_max_phy_offset();
+        Ok(self.append_epoch_entry(master_epoch, start_offset))
+    }
+
+    async fn change_to_slave(
+        &self,
+        new_master_addr: &str,
+        new_master_epoch: i32,
+        slave_id: Option<i64>,
+    ) -> HAResult<bool> {
+        let last_epoch = self.state.epoch_cache.last_epoch();
+        if new_master_epoch < last_epoch {
+            warn!(
+                "newMasterEpoch {} < lastEpoch {}, fail to change to slave",
+                new_master_epoch, last_epoch
+            );
+            return Ok(false);
+        }
+        let message_store = self.message_store()?;
+        if let Some(slave_id) = slave_id {
+            self.set_local_broker_id(slave_id);
+        }
+        self.state.is_master.store(false, Ordering::Release);
+        self.default_ha_service.inner().destroy_connections();
+        self.shutdown_ha_client();
+
+        // The HA address of the new master is set once it has been exchanged with the
+        // master, until then the client keeps waiting.
+        let ha_client =
+            DefaultHAClient::new_with_auto_switch(message_store.clone(), self.state.clone());
+        ha_client.set_master_address(new_master_addr);
+        ha_client.start_client();
+        *self.ha_client.write() = Some(ha_client);
+        message_store.set_state_machine_version(new_master_epoch as i64);
+        info!(
+            "Change ha to slave success, newMasterAddress:{}, newMasterEpoch:{}",
+            new_master_addr, new_master_epoch
+        );
+        Ok(true)
+    }
+
+    async fn change_to_slave_when_master_not_change(
+        &self,
+        _new_master_addr: &str,
+        new_master_epoch: i32,
+    ) -> HAResult<bool> {
+        let last_epoch = self.state.epoch_cache.last_epoch();
+        if new_master_epoch < last_epoch {
+            warn!(
+                "newMasterEpoch {} < lastEpoch {}, fail to change to slave",
+                new_master_epoch, last_epoch
+            );
+            return Ok(false);
+        }
+        self.message_store()?
+            .set_state_machine_version(new_master_epoch as i64);
+        Ok(true)
+    }
+
+    fn update_master_address(&self, new_addr: &str) {
+        if let Some(ha_client) = self.ha_client.read().as_ref() {
+            ha_client.set_master_address(new_addr);
+        }
+    }
+
+    fn update_ha_master_address(&self, new_addr: &str) {
+        if let Some(ha_client) = self.ha_client.read().as_ref() {
+            ha_client.set_ha_master_address(new_addr);
+        }
+    }
+
+    fn in_sync_replicas_nums(&self, _master_put_where: i64) -> i32 {
+        self.state.get_sync_state_set().len() as i32
+    }
+
+    fn get_connection_count(&self) -> &AtomicI32 {
+        self.default_ha_service.get_connection_count()
+    }
+
+    fn put_request(&self, request: GroupCommitRequest) {
+        self.default_ha_service.put_request(request)
+    }
+
+    fn put_group_connection_state_request(&self, request: HAConnectionStateNotificationRequest) {
+        self.default_ha_service
+            .put_group_connection_state_request(request)
+    }
+
+    fn get_connection_list(&self) -> Vec<Arc<GeneralHAConnection>> {
+        self.default_ha_service.get_connection_list()
+    }
+
+    fn get_ha_client(&self) -> Option<Arc<GeneralHAClient>> {
+        self.ha_client
+            .read()
+            .as_ref()
+            .map(|client| Arc::new(GeneralHAClient::new_with_default_ha_client(client.clone())))
+    }
+
+    fn get_push_to_slave_max_offset(&self) -> &AtomicI64 {
+        self.default_ha_service.get_push_to_slave_max_offset()
+    }
+
+    fn get_runtime_info(&self, master_put_where: i64) -> HARuntimeInfo {
+        if self.is_master() {
+            let mut info = self
+                .default_ha_service
+                .master_runtime_info(master_put_where);
+            info.in_sync_slave_nums = self.state.get_sync_state_set().len() as i32 - 1;
+            info
+        } else {
+            self.default_ha_service
+                .slave_runtime_info(self.ha_client.read().as_deref())
+        }
+    }
+
+    fn get_wait_notify_object(&self) -> Arc<WaitNotifyObject> {
+        self.default_ha_service.get_wait_notify_object()
+    }
+
+    fn is_slave_ok(&self, master_put_where: i64) -> bool {
+        self.default_ha_service.is_slave_ok(master_put_where)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with_set(local_broker_id: i64, sync_state_set: &[i64]) -> AutoSwitchHAState {
+        let state = AutoSwitchHAState::new(EpochFileCache::new());
+        state
+            .local_broker_id
+            .store(local_broker_id, Ordering::Release);
+        state.set_sync_state_set(sync_state_set.iter().copied().collect());
+        state
+    }
+
+    #[test]
+    fn expand_marks_and_notifies_new_sync_state_set() {
+        let state = state_with_set(1, &[1]);
+        state.epoch_cache.append_entry(EpochEntry::new(1, 1000));
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let reported_cloned = reported.clone();
+        state
+            .sync_state_set_changed_listeners
+            .lock()
+            .push(Arc::new(move |set| reported_cloned.lock().push(set)));
+
+        // Behind the confirm offset, not caught up yet.
+        state.maybe_expand_in_sync_state_set(2, 1500, 2000);
+        assert!(reported.lock().is_empty());
+
+        state.maybe_expand_in_sync_state_set(2, 2000, 2000);
+        let expected: HashSet<i64> = [1, 2].into_iter().collect();
+        assert_eq!(reported.lock().as_slice(), std::slice::from_ref(&expected));
+        assert_eq!(state.get_sync_state_set(), expected);
+        // Not confirmed by the controller yet.
+        assert_eq!(state.get_local_sync_state_set(), [1].into_iter().collect());
+
+        state.set_sync_state_set(expected.clone());
+        assert_eq!(state.get_sync_state_set(), expected);
+    }
+
+    #[test]
+    fn shrink_removes_lagging_and_disconnected_slaves() {
+        let state = state_with_set(1, &[1, 2, 3, 4]);
+        let now = get_current_millis() as i64;
+        state.update_connection_last_caught_up_time(2, now);
+        state.update_connection_last_caught_up_time(3, now - 20_000);
+
+        let new_sync_state_set = state.maybe_shrink_sync_state_set(15_000);
+        assert_eq!(new_sync_state_set, [1, 2].into_iter().collect());
+        // Until the controller confirms it, acks are still counted on the old set.
+        assert_eq!(
+            state.get_sync_state_set(),
+            [1, 2, 3, 4].into_iter().collect()
+        );
+    }
+
+    #[test]
+    fn handshake_entries_end_at_max_offset() {
+        let state = state_with_set(1, &[1]);
+        state.epoch_cache.append_entry(EpochEntry::new(1, 0));
+        state.epoch_cache.append_entry(EpochEntry::new(2, 100));
+        assert_eq!(
+            state.handshake_entries(300),
+            vec![
+                EpochEntry::with_end_offset(1, 0, 100),
+                EpochEntry::with_end_offset(2, 100, 300)
+            ]
+        );
+    }
+}
@@ -0,0 +1,358 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use parking_lot::RwLock;
+use rocketmq_common::utils::file_utils;
+use tracing::error;
+use tracing::info;
+
+/// The commit log range written under one master epoch. The range starts at `start_offset`
+/// and ends right before `end_offset`, the last entry is open ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EpochEntry {
+    pub epoch: i32,
+    pub start_offset: i64,
+    pub end_offset: i64,
+}
+
+impl EpochEntry {
+    pub fn new(epoch: i32, start_offset: i64) -> Self {
+        Self {
+            epoch,
+            start_offset,
+            end_offset: i64::MAX,
+        }
+    }
+
+    pub fn with_end_offset(epoch: i32, start_offset: i64, end_offset: i64) -> Self {
+        Self {
+            epoch,
+            start_offset,
+            end_offset,
+        }
+    }
+}
+
+impl Display for EpochEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "EpochEntry{{epoch={}, startOffset={}, endOffset={}}}",
+            self.epoch, self.start_offset, self.end_offset
+        )
+    }
+}
+
+/// Cache of the epoch history of the local commit log.
+///
+/// Every time a broker becomes master it starts a new epoch at its current max physical
+/// offset. Comparing the history of two replicas tells up to which offset their commit logs
+/// are identical, see [`EpochFileCache::find_consistent_point`]. The history is checkpointed
+/// to `file_path` on every change when the cache is backed by a file.
+pub struct EpochFileCache {
+    epoch_map: RwLock<BTreeMap<i32, EpochEntry>>,
+    file_path: Option<String>,
+}
+
+impl Default for EpochFileCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EpochFileCache {
+    /// Creates a cache that only lives in memory.
+    pub fn new() -> Self {
+        Self {
+            epoch_map: RwLock::new(BTreeMap::new()),
+            file_path: None,
+        }
+    }
+
+    /// Creates a cache checkpointed to `file_path`.
+    pub fn new_with_file(file_path: impl Into<String>) -> Self {
+        Self {
+            epoch_map: RwLock::new(BTreeMap::new()),
+            file_path: Some(file_path.into()),
+        }
+    }
+
+    /// Loads the checkpoint file. A missing file is an empty history.
+    pub fn init_cache_from_file(&self) -> bool {
+        let Some(file_path) = self.file_path.as_ref() else {
+            return true;
+        };
+        let content = match file_utils::file_to_string(file_path) {
+            Ok(content) => content,
+            Err(e) => {
+                error!("read epoch file {} failed: {}", file_path, e);
+                return false;
+            }
+        };
+        let Some(entries) = Self::decode(&content) else {
+            error!("epoch file {} is corrupted", file_path);
+            return false;
+        };
+        self.init_cache_from_entries(entries);
+        true
+    }
+
+    pub fn init_cache_from_entries(&self, entries: Vec<EpochEntry>) {
+        let mut epoch_map = self.epoch_map.write();
+        epoch_map.clear();
+        let mut previous: Option<i32> = None;
+        for entry in entries {
+            if let Some(previous) = previous {
+                if let Some(previous) = epoch_map.get_mut(&previous) {
+                    previous.end_offset = entry.start_offset;
+                }
+            }
+            previous = Some(entry.epoch);
+            epoch_map.insert(
+                entry.epoch,
+                EpochEntry::new(entry.epoch, entry.start_offset),
+            );
+        }
+    }
+
+    /// Appends a new epoch. The epoch and the start offset must not go back.
+    pub fn append_entry(&self, entry: EpochEntry) -> bool {
+        let mut epoch_map = self.epoch_map.write();
+        if let Some((_, last_entry)) = epoch_map.iter_mut().next_back() {
+            if last_entry.epoch >= entry.epoch || last_entry.start_offset > entry.start_offset {
+                error!(
+                    "The appending entry's lastEpoch or endOffset {} is not bigger than lastEntry \
+                     {}, append failed",
+                    entry, last_entry
+                );
+                return false;
+            }
+            last_entry.end_offset = entry.start_offset;
+        }
+        epoch_map.insert(
+            entry.epoch,
+            EpochEntry::new(entry.epoch, entry.start_offset),
+        );
+        self.flush(&epoch_map);
+        true
+    }
+
+    /// Sets the end offset of the last epoch, usually the max physical offset of the
+    /// commit log, so that the history can be compared with another replica.
+    pub fn set_last_epoch_entry_end_offset(&self, end_offset: i64) {
+        let mut epoch_map = self.epoch_map.write();
+        if let Some((_, last_entry)) = epoch_map.iter_mut().next_back() {
+            if last_entry.start_offset <= end_offset {
+                last_entry.end_offset = end_offset;
+            }
+        }
+    }
+
+    pub fn get_entry(&self, epoch: i32) -> Option<EpochEntry> {
+        self.epoch_map.read().get(&epoch).copied()
+    }
+
+    /// Finds the epoch whose range contains `offset`.
+    pub fn find_epoch_entry_by_offset(&self, offset: i64) -> Option<EpochEntry> {
+        self.epoch_map
+            .read()
+            .values()
+            .find(|entry| entry.start_offset <= offset && offset < entry.end_offset)
+            .copied()
+    }
+
+    pub fn first_entry(&self) -> Option<EpochEntry> {
+        self.epoch_map.read().values().next().copied()
+    }
+
+    pub fn last_entry(&self) -> Option<EpochEntry> {
+        self.epoch_map.read().values().next_back().copied()
+    }
+
+    /// The latest epoch, `-1` if the history is empty.
+    pub fn last_epoch(&self) -> i32 {
+        self.last_entry().map_or(-1, |entry| entry.epoch)
+    }
+
+    pub fn get_entry_size(&self) -> usize {
+        self.epoch_map.read().len()
+    }
+
+    pub fn get_all_entries(&self) -> Vec<EpochEntry> {
+        self.epoch_map.read().values().copied().collect()
+    }
+
+    /// Finds the offset up to which this history and `compare_cache` are identical, `-1` if
+    /// they never share an epoch.
+    ///
+    /// The newest epoch known by both sides with the same start offset is the last one both
+    /// replicas wrote, and the shorter of the two ranges is common to both of them.
+    pub fn find_consistent_point(&self, compare_cache: &EpochFileCache) -> i64 {
+        let epoch_map = self.epoch_map.read();
+        for local_entry in epoch_map.values().rev() {
+            if let Some(compare_entry) = compare_cache.get_entry(local_entry.epoch) {
+                if compare_entry.start_offset == local_entry.start_offset {
+                    return local_entry.end_offset.min(compare_entry.end_offset);
+                }
+            }
+        }
+        -1
+    }
+
+    /// Removes the epochs newer than or equal to `truncate_epoch`.
+    pub fn truncate_suffix_by_epoch(&self, truncate_epoch: i32) {
+        let mut epoch_map = self.epoch_map.write();
+        epoch_map.retain(|epoch, _| *epoch < truncate_epoch);
+        self.flush(&epoch_map);
+    }
+
+    /// Removes the epochs starting at or after `truncate_offset`, used when the tail of the
+    /// commit log is truncated.
+    pub fn truncate_suffix_by_offset(&self, truncate_offset: i64) {
+        let mut epoch_map = self.epoch_map.write();
+        epoch_map.retain(|_, entry| entry.start_offset < truncate_offset);
+        if let Some((_, last_entry)) = epoch_map.iter_mut().next_back() {
+            last_entry.end_offset = i64::MAX;
+        }
+        self.flush(&epoch_map);
+    }
+
+    /// Removes the epochs ending at or before `truncate_offset` and moves the start of the
+    /// first remaining epoch, used when the head of the commit log is deleted.
+    pub fn truncate_prefix_by_offset(&self, truncate_offset: i64) {
+        let mut epoch_map = self.epoch_map.write();
+        epoch_map.retain(|_, entry| entry.end_offset > truncate_offset);
+        if let Some((_, first_entry)) = epoch_map.iter_mut().next() {
+            if first_entry.start_offset < truncate_offset {
+                first_entry.start_offset = truncate_offset;
+            }
+        }
+        self.flush(&epoch_map);
+    }
+
+    fn flush(&self, epoch_map: &BTreeMap<i32, EpochEntry>) {
+        let Some(file_path) = self.file_path.as_ref() else {
+            return;
+        };
+        let mut content = format!("{}\n", epoch_map.len());
+        for entry in epoch_map.values() {
+            content.push_str(&format!("{}-{}\n", entry.epoch, entry.start_offset));
+        }
+        if let Err(e) = file_utils::string_to_file(&content, file_path) {
+            error!("flush epoch file {} failed: {}", file_path, e);
+        } else {
+            info!(
+                "flush epoch file {}, entries: {}",
+                file_path,
+                epoch_map.len()
+            );
+        }
+    }
+
+    /// Parses the checkpoint format: the number of entries on the first line, then one
+    /// `epoch-startOffset` line per entry.
+    fn decode(content: &str) -> Option<Vec<EpochEntry>> {
+        let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+        let Some(size) = lines.next() else {
+            return Some(Vec::new());
+        };
+        let size = size.trim().parse::<usize>().ok()?;
+        let entries = lines
+            .map(|line| {
+                let (epoch, start_offset) = line.trim().split_once('-')?;
+                Some(EpochEntry::new(
+                    epoch.parse().ok()?,
+                    start_offset.parse().ok()?,
+                ))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        (entries.len() == size).then_some(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache_of(entries: &[(i32, i64)], end_offset: i64) -> EpochFileCache {
+        let cache = EpochFileCache::new();
+        cache.init_cache_from_entries(
+            entries
+                .iter()
+                .map(|(epoch, start_offset)| EpochEntry::new(*epoch, *start_offset))
+                .collect(),
+        );
+        cache.set_last_epoch_entry_end_offset(end_offset);
+        cache
+    }
+
+    #[test]
+    fn append_entry_rejects_stale_epoch() {
+        let cache = EpochFileCache::new();
+        assert!(cache.append_entry(EpochEntry::new(1, 0)));
+        assert!(cache.append_entry(EpochEntry::new(2, 100)));
+        assert!(!cache.append_entry(EpochEntry::new(2, 200)));
+        assert!(!cache.append_entry(EpochEntry::new(3, 50)));
+        assert_eq!(cache.last_epoch(), 2);
+        assert_eq!(cache.get_entry(1).unwrap().end_offset, 100);
+        assert_eq!(cache.get_entry(2).unwrap().end_offset, i64::MAX);
+    }
+
+    #[test]
+    fn checkpoint_survives_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("epochFileCheckpoint");
+        let cache = EpochFileCache::new_with_file(path.to_string_lossy());
+        cache.append_entry(EpochEntry::new(1, 0));
+        cache.append_entry(EpochEntry::new(3, 4096));
+
+        let reloaded = EpochFileCache::new_with_file(path.to_string_lossy());
+        assert!(reloaded.init_cache_from_file());
+        assert_eq!(reloaded.get_all_entries(), cache.get_all_entries());
+        assert_eq!(
+            reloaded.find_epoch_entry_by_offset(100),
+            Some(EpochEntry::with_end_offset(1, 0, 4096))
+        );
+    }
+
+    #[test]
+    fn find_consistent_point_uses_last_shared_epoch() {
+        // The old master wrote up to 300 in epoch 2, the new master only got 250 of it.
+        let old_master = cache_of(&[(1, 0), (2, 100)], 300);
+        let new_master = cache_of(&[(1, 0), (2, 100), (3, 250)], 400);
+        assert_eq!(old_master.find_consistent_point(&new_master), 250);
+
+        let diverged = cache_of(&[(1, 0), (2, 120)], 300);
+        assert_eq!(diverged.find_consistent_point(&new_master), 100);
+
+        let unrelated = cache_of(&[(5, 0)], 300);
+        assert_eq!(unrelated.find_consistent_point(&new_master), -1);
+    }
+
+    #[test]
+    fn truncate_by_offset() {
+        let cache = cache_of(&[(1, 0), (2, 100), (3, 250)], 400);
+        cache.truncate_suffix_by_offset(250);
+        assert_eq!(cache.last_epoch(), 2);
+        assert_eq!(cache.last_entry().unwrap().end_offset, i64::MAX);
+
+        cache.truncate_prefix_by_offset(150);
+        assert_eq!(cache.get_all_entries(), vec![EpochEntry::new(2, 150)]);
+    }
+}
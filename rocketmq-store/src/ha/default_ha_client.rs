@@ -0,0 +1,547 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::sync::atomic::AtomicI64;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Weak;
+use std::time::Duration;
+
+use bytes::Buf;
+use bytes::BytesMut;
+use parking_lot::RwLock;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_rust::ArcMut;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::net::TcpStream;
+use tokio::sync::watch;
+use tokio::sync::Notify;
+use tracing::error;
+use tracing::info;
+use tracing::warn;
+
+use crate::base::message_store::MessageStore;
+use crate::config::message_store_config::MessageStoreConfig;
+use crate::ha::auto_switch::auto_switch_ha_service::AutoSwitchHAState;
+use crate::ha::auto_switch::epoch_file_cache::EpochEntry;
+use crate::ha::auto_switch::epoch_file_cache::EpochFileCache;
+use crate::ha::default_ha_connection::AUTO_SWITCH_TRANSFER_HEADER_SIZE;
+use crate::ha::default_ha_connection::EPOCH_ENTRY_SIZE;
+use crate::ha::default_ha_connection::TRANSFER_HEADER_SIZE;
+use crate::ha::flow_monitor::FlowMonitor;
+use crate::ha::ha_client::HAClient;
+use crate::ha::ha_connection_state::HAConnectionState;
+use crate::message_store::local_file_message_store::LocalFileMessageStore;
+
+const READ_MAX_BUFFER_SIZE: usize = 1024 * 1024 * 4;
+const CONNECT_TIMEOUT_MILLIS: u64 = 3000;
+const RECONNECT_INTERVAL_MILLIS: u64 = 5000;
+
+/// Slave side of a replication link.
+///
+/// Connects to the HA port of the master, reports the local max physical offset and
+/// appends the commit log data pushed by the master. In controller mode the client first
+/// truncates the local commit log to the last offset consistent with the epoch history of
+/// the master.
+pub struct DefaultHAClient {
+    this: Weak<DefaultHAClient>,
+    message_store: ArcMut<LocalFileMessageStore>,
+    message_store_config: Arc<MessageStoreConfig>,
+    master_ha_address: RwLock<Option<String>>,
+    master_address: RwLock<Option<String>>,
+    current_reported_offset: AtomicI64,
+    last_read_timestamp: AtomicI64,
+    last_write_timestamp: AtomicI64,
+    current_state: AtomicU8,
+    flow_monitor: FlowMonitor,
+    wakeup_notify: Notify,
+    close_master_notify: Notify,
+    auto_switch: Option<Arc<AutoSwitchHAState>>,
+    shutdown_tx: watch::Sender<bool>,
+}
+
+impl DefaultHAClient {
+    pub(crate) fn new(message_store: ArcMut<LocalFileMessageStore>) -> Arc<Self> {
+        let master_ha_address = message_store
+            .message_store_config()
+            .ha_master_address
+            .clone()
+            .filter(|address| !address.is_empty());
+        Self::new_inner(message_store, master_ha_address, None)
+    }
+
+    /// Creates the client of a slave in controller mode. The HA address of the master is
+    /// set once the broker has learnt it from the master.
+    pub(crate) fn new_with_auto_switch(
+        message_store: ArcMut<LocalFileMessageStore>,
+        auto_switch: Arc<AutoSwitchHAState>,
+    ) -> Arc<Self> {
+        Self::new_inner(message_store, None, Some(auto_switch))
+    }
+
+    fn new_inner(
+        message_store: ArcMut<LocalFileMessageStore>,
+        master_ha_address: Option<String>,
+        auto_switch: Option<Arc<AutoSwitchHAState>>,
+    ) -> Arc<Self> {
+        let message_store_config = message_store.message_store_config();
+        let (shutdown_tx, _) = watch::channel(false);
+        Arc::new_cyclic(|this| Self {
+            this: this.clone(),
+            message_store,
+            message_store_config,
+            master_ha_address: RwLock::new(master_ha_address),
+            master_address: RwLock::new(None),
+            current_reported_offset: AtomicI64::new(0),
+            last_read_timestamp: AtomicI64::new(get_current_millis() as i64),
+            last_write_timestamp: AtomicI64::new(0),
+            current_state: AtomicU8::new(HAConnectionState::Ready.into()),
+            flow_monitor: FlowMonitor::new(),
+            wakeup_notify: Notify::new(),
+            close_master_notify: Notify::new(),
+            auto_switch,
+            shutdown_tx,
+        })
+    }
+
+    fn is_stopped(&self) -> bool {
+        *self.shutdown_tx.borrow()
+    }
+
+    async fn run(self: Arc<Self>) {
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+        while !self.is_stopped() {
+            if self.get_current_state() == HAConnectionState::Shutdown {
+                break;
+            }
+            if let Some(socket) = self.connect_master().await {
+                self.change_current_state(HAConnectionState::Transfer);
+                self.transfer_from_master(socket).await;
+                self.close_master_inner();
+                if self.is_stopped() {
+                    break;
+                }
+            }
+            tokio::select! {
+                _ = shutdown_rx.changed() => break,
+                _ = self.wakeup_notify.notified() => {},
+                _ = tokio::time::sleep(Duration::from_millis(RECONNECT_INTERVAL_MILLIS)) => {},
+            }
+        }
+        self.change_current_state(HAConnectionState::Shutdown);
+        info!("DefaultHAClient service end");
+    }
+
+    async fn connect_master(&self) -> Option<TcpStream> {
+        let address = self.master_ha_address.read().clone()?;
+        match tokio::time::timeout(
+            Duration::from_millis(CONNECT_TIMEOUT_MILLIS),
+            TcpStream::connect(address.as_str()),
+        )
+        .await
+        {
+            Ok(Ok(socket)) => {
+                let _ = socket.set_nodelay(true);
+                info!("HAClient connect to master {}", address);
+                self.current_reported_offset
+                    .store(self.message_store.get_max_phy_offset(), Ordering::Release);
+                self.last_read_timestamp
+                    .store(get_current_millis() as i64, Ordering::Release);
+                self.last_write_timestamp.store(0, Ordering::Release);
+                Some(socket)
+            }
+            Ok(Err(e)) => {
+                warn!("HAClient connect to master {} failed: {}", address, e);
+                None
+            }
+            Err(_) => {
+                warn!("HAClient connect to master {} timeout", address);
+                None
+            }
+        }
+    }
+
+    async fn transfer_from_master(&self, socket: TcpStream) {
+        let heartbeat_interval = self.message_store_config.ha_send_heartbeat_interval as i64;
+        let housekeeping_interval = self.message_store_config.ha_housekeeping_interval as i64;
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+        let mut socket = socket;
+        if let Some(auto_switch) = self.auto_switch.as_ref() {
+            self.change_current_state(HAConnectionState::Handshake);
+            if !self.handshake(&mut socket, auto_switch).await {
+                return;
+            }
+            self.change_current_state(HAConnectionState::Transfer);
+        }
+        let (mut reader, mut writer) = socket.into_split();
+        let mut buf = BytesMut::with_capacity(READ_MAX_BUFFER_SIZE);
+        loop {
+            let now = get_current_millis() as i64;
+            if now - self.last_write_timestamp.load(Ordering::Acquire) >= heartbeat_interval {
+                let offset = self.current_reported_offset.load(Ordering::Acquire);
+                if !self.report_slave_max_offset(&mut writer, offset).await {
+                    return;
+                }
+            }
+
+            tokio::select! {
+                _ = shutdown_rx.changed() => return,
+                _ = self.close_master_notify.notified() => return,
+                read = tokio::time::timeout(Duration::from_secs(1), reader.read_buf(&mut buf)) => {
+                    match read {
+                        Ok(Ok(0)) => {
+                            warn!("HAClient, master closed the connection");
+                            return;
+                        }
+                        Ok(Ok(read_size)) => {
+                            self.last_read_timestamp
+                                .store(get_current_millis() as i64, Ordering::Release);
+                            self.flow_monitor
+                                .add_byte_count_transferred(read_size as i64);
+                            if !self.dispatch_read_request(&mut buf) {
+                                error!("HAClient, dispatchReadRequest error");
+                                return;
+                            }
+                            if !self.report_slave_max_offset_plus(&mut writer).await {
+                                return;
+                            }
+                        }
+                        Ok(Err(e)) => {
+                            warn!("HAClient, read from master failed: {}", e);
+                            return;
+                        }
+                        Err(_) => {}
+                    }
+                }
+            }
+
+            let interval =
+                get_current_millis() as i64 - self.last_read_timestamp.load(Ordering::Acquire);
+            if interval > housekeeping_interval {
+                warn!(
+                    "AutoRecoverHAClient, housekeeping, found this connection[{:?}] expired, {}",
+                    self.master_ha_address.read(),
+                    interval
+                );
+                return;
+            }
+        }
+    }
+
+    /// Sends the local broker id and truncates the local commit log to the last offset
+    /// consistent with the epoch history sent back by the master.
+    async fn handshake(&self, socket: &mut TcpStream, auto_switch: &AutoSwitchHAState) -> bool {
+        let housekeeping_interval =
+            Duration::from_millis(self.message_store_config.ha_housekeeping_interval as u64);
+        if let Err(e) = socket
+            .write_all(&auto_switch.local_broker_id().to_be_bytes())
+            .await
+        {
+            warn!("HAClient, send handshake to master failed: {}", e);
+            return false;
+        }
+        let master_entries =
+            match tokio::time::timeout(housekeeping_interval, Self::read_epoch_entries(socket))
+                .await
+            {
+                Ok(Ok(entries)) => entries,
+                Ok(Err(e)) => {
+                    warn!("HAClient, read handshake from master failed: {}", e);
+                    return false;
+                }
+                Err(_) => {
+                    warn!("HAClient, read handshake from master timeout");
+                    return false;
+                }
+            };
+        let truncated = self.do_truncate(auto_switch, master_entries);
+        self.current_reported_offset
+            .store(self.message_store.get_max_phy_offset(), Ordering::Release);
+        truncated
+    }
+
+    async fn read_epoch_entries(socket: &mut TcpStream) -> std::io::Result<Vec<EpochEntry>> {
+        let size = socket.read_i32().await?;
+        let mut entries = Vec::with_capacity(size.max(0) as usize);
+        let mut buf = [0u8; EPOCH_ENTRY_SIZE];
+        for _ in 0..size {
+            socket.read_exact(&mut buf).await?;
+            let mut entry = &buf[..];
+            entries.push(EpochEntry::with_end_offset(
+                entry.get_i32(),
+                entry.get_i64(),
+                entry.get_i64(),
+            ));
+        }
+        Ok(entries)
+    }
+
+    fn do_truncate(
+        &self,
+        auto_switch: &AutoSwitchHAState,
+        master_entries: Vec<EpochEntry>,
+    ) -> bool {
+        let epoch_cache = auto_switch.epoch_cache();
+        if epoch_cache.get_entry_size() == 0 {
+            info!("Slave local epochCache is empty, skip truncate log");
+            return true;
+        }
+        let master_end_offset = master_entries.last().map_or(0, |entry| entry.end_offset);
+        let master_epoch_cache = EpochFileCache::new();
+        master_epoch_cache.init_cache_from_entries(master_entries);
+        master_epoch_cache.set_last_epoch_entry_end_offset(master_end_offset);
+        let local_epoch_cache = EpochFileCache::new();
+        local_epoch_cache.init_cache_from_entries(epoch_cache.get_all_entries());
+        local_epoch_cache.set_last_epoch_entry_end_offset(self.message_store.get_max_phy_offset());
+
+        let truncate_offset = local_epoch_cache.find_consistent_point(&master_epoch_cache);
+        if truncate_offset < 0 {
+            error!(
+                "Failed to find a consistent point between masterEpoch:{:?} and slaveEpoch:{:?}",
+                master_epoch_cache.get_all_entries(),
+                local_epoch_cache.get_all_entries()
+            );
+            return false;
+        }
+        match self.message_store.truncate_files(truncate_offset) {
+            Ok(true) => {}
+            Ok(false) => {
+                error!("Failed to truncate slave log to {}", truncate_offset);
+                return false;
+            }
+            Err(e) => {
+                error!("Failed to truncate slave log to {}: {}", truncate_offset, e);
+                return false;
+            }
+        }
+        epoch_cache.truncate_suffix_by_offset(truncate_offset);
+        info!(
+            "Truncate slave log to {} success, change to transfer state",
+            truncate_offset
+        );
+        true
+    }
+
+    /// Appends every complete frame in `buf` to the local commit log. Returns `false` if the
+    /// master pushed data that does not line up with the local commit log.
+    fn dispatch_read_request(&self, buf: &mut BytesMut) -> bool {
+        let header_size = if self.auto_switch.is_some() {
+            AUTO_SWITCH_TRANSFER_HEADER_SIZE
+        } else {
+            TRANSFER_HEADER_SIZE
+        };
+        while buf.len() >= header_size {
+            let mut header = &buf[..header_size];
+            let master_phy_offset = header.get_i64();
+            let body_size = header.get_i32() as usize;
+            // Epoch, epoch start offset and confirm offset of the master in controller mode.
+            let epoch_header = self
+                .auto_switch
+                .as_ref()
+                .map(|_| (header.get_i32(), header.get_i64(), header.get_i64()));
+            let slave_phy_offset = self.message_store.get_max_phy_offset();
+            if slave_phy_offset != 0 && slave_phy_offset != master_phy_offset {
+                error!(
+                    "master pushed offset not equal the max phy offset in slave, SLAVE: {} \
+                     MASTER: {}",
+                    slave_phy_offset, master_phy_offset
+                );
+                return false;
+            }
+            if buf.len() < header_size + body_size {
+                if buf.capacity() < header_size + body_size {
+                    buf.reserve(header_size + body_size - buf.len());
+                }
+                break;
+            }
+            let frame = buf.split_to(header_size + body_size);
+            if let (
+                Some(auto_switch),
+                Some((master_epoch, master_epoch_start_offset, confirm_offset)),
+            ) = (self.auto_switch.as_ref(), epoch_header)
+            {
+                let epoch_cache = auto_switch.epoch_cache();
+                if body_size > 0
+                    && master_epoch > epoch_cache.last_epoch()
+                    && !epoch_cache
+                        .append_entry(EpochEntry::new(master_epoch, master_epoch_start_offset))
+                {
+                    return false;
+                }
+                if body_size > 0
+                    && !self.append_to_commit_log(master_phy_offset, &frame[header_size..])
+                {
+                    return false;
+                }
+                self.message_store.update_confirm_offset(
+                    confirm_offset.min(self.message_store.get_max_phy_offset()),
+                );
+            } else if body_size > 0
+                && !self.append_to_commit_log(master_phy_offset, &frame[header_size..])
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn append_to_commit_log(&self, master_phy_offset: i64, body: &[u8]) -> bool {
+        match self
+            .message_store
+            .append_to_commit_log(master_phy_offset, body, 0, body.len() as i32)
+        {
+            Ok(result) => result,
+            Err(e) => {
+                error!("HAClient, append data to commit log failed: {}", e);
+                false
+            }
+        }
+    }
+
+    async fn report_slave_max_offset_plus(&self, writer: &mut OwnedWriteHalf) -> bool {
+        let current_phy_offset = self.message_store.get_max_phy_offset();
+        if current_phy_offset > self.current_reported_offset.load(Ordering::Acquire) {
+            self.current_reported_offset
+                .store(current_phy_offset, Ordering::Release);
+            return self
+                .report_slave_max_offset(writer, current_phy_offset)
+                .await;
+        }
+        true
+    }
+
+    async fn report_slave_max_offset(&self, writer: &mut OwnedWriteHalf, max_offset: i64) -> bool {
+        match writer.write_all(&max_offset.to_be_bytes()).await {
+            Ok(_) => {
+                self.last_write_timestamp
+                    .store(get_current_millis() as i64, Ordering::Release);
+                true
+            }
+            Err(e) => {
+                error!(
+                    "HAClient, report slave max offset to {:?} failed: {}",
+                    self.master_ha_address.read(),
+                    e
+                );
+                false
+            }
+        }
+    }
+
+    pub(crate) fn start_client(&self) {
+        if let Some(this) = self.this.upgrade() {
+            tokio::spawn(this.run());
+        }
+    }
+
+    pub(crate) fn shutdown_client(&self) {
+        self.change_current_state(HAConnectionState::Shutdown);
+        self.shutdown_tx.send_replace(true);
+    }
+
+    pub(crate) fn set_master_address(&self, new_address: &str) {
+        let mut master_address = self.master_address.write();
+        info!(
+            "update master address, OLD: {:?} NEW: {}",
+            master_address, new_address
+        );
+        *master_address = Some(new_address.to_string());
+    }
+
+    pub(crate) fn set_ha_master_address(&self, new_address: &str) {
+        let mut master_ha_address = self.master_ha_address.write();
+        info!(
+            "update master ha address, OLD: {:?} NEW: {}",
+            master_ha_address, new_address
+        );
+        *master_ha_address = Some(new_address.to_string());
+        drop(master_ha_address);
+        self.wakeup_notify.notify_one();
+    }
+
+    fn close_master_inner(&self) {
+        if self.get_current_state() != HAConnectionState::Shutdown {
+            self.change_current_state(HAConnectionState::Ready);
+        }
+        self.last_read_timestamp.store(0, Ordering::Release);
+        self.last_write_timestamp.store(0, Ordering::Release);
+        info!(
+            "HAClient close connection with master {:?}",
+            self.master_ha_address.read()
+        );
+    }
+}
+
+impl HAClient for DefaultHAClient {
+    async fn start(&self) {
+        self.start_client();
+    }
+
+    async fn shutdown(&self) {
+        self.shutdown_client();
+    }
+
+    async fn wakeup(&self) {
+        self.wakeup_notify.notify_one();
+    }
+
+    async fn update_master_address(&self, new_address: &str) {
+        self.set_master_address(new_address);
+    }
+
+    async fn update_ha_master_address(&self, new_address: &str) {
+        self.set_ha_master_address(new_address);
+    }
+
+    fn get_master_address(&self) -> String {
+        self.master_address.read().clone().unwrap_or_default()
+    }
+
+    fn get_ha_master_address(&self) -> String {
+        self.master_ha_address.read().clone().unwrap_or_default()
+    }
+
+    fn get_last_read_timestamp(&self) -> i64 {
+        self.last_read_timestamp.load(Ordering::Acquire)
+    }
+
+    fn get_last_write_timestamp(&self) -> i64 {
+        self.last_write_timestamp.load(Ordering::Acquire)
+    }
+
+    fn get_current_state(&self) -> HAConnectionState {
+        HAConnectionState::from(self.current_state.load(Ordering::Acquire))
+    }
+
+    fn change_current_state(&self, ha_connection_state: HAConnectionState) {
+        info!("change state to {}", ha_connection_state);
+        self.current_state
+            .store(ha_connection_state.into(), Ordering::Release);
+    }
+
+    async fn close_master(&self) {
+        if self.get_current_state() == HAConnectionState::Transfer {
+            self.close_master_notify.notify_one();
+        }
+    }
+
+    fn get_transferred_byte_in_second(&self) -> i64 {
+        self.flow_monitor.get_transferred_byte_in_second()
+    }
+}
@@ -0,0 +1,451 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::net::SocketAddr;
+use std::sync::atomic::AtomicI64;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Weak;
+use std::time::Duration;
+
+use bytes::BufMut;
+use bytes::BytesMut;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_rust::ArcMut;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::tcp::OwnedReadHalf;
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::net::TcpStream;
+use tokio::sync::watch;
+use tracing::error;
+use tracing::info;
+use tracing::warn;
+
+use crate::base::message_store::MessageStore;
+use crate::config::message_store_config::MessageStoreConfig;
+use crate::ha::auto_switch::auto_switch_ha_service::AutoSwitchHAState;
+use crate::ha::auto_switch::epoch_file_cache::EpochEntry;
+use crate::ha::default_ha_service::DefaultHAServiceInner;
+use crate::ha::flow_monitor::FlowMonitor;
+use crate::ha::ha_connection::HAConnection;
+use crate::ha::ha_connection_state::HAConnectionState;
+use crate::message_store::local_file_message_store::LocalFileMessageStore;
+
+/// Header of a transfer frame: physical offset (8 bytes) + body size (4 bytes).
+pub(crate) const TRANSFER_HEADER_SIZE: usize = 8 + 4;
+
+/// Header of a transfer frame in controller mode: physical offset (8 bytes) + body size
+/// (4 bytes) + epoch (4 bytes) + epoch start offset (8 bytes) + confirm offset (8 bytes).
+pub(crate) const AUTO_SWITCH_TRANSFER_HEADER_SIZE: usize = 8 + 4 + 4 + 8 + 8;
+
+/// Size of the offset reported by a slave.
+pub(crate) const REPORT_HEADER_SIZE: usize = 8;
+
+/// Size of an epoch entry in the handshake: epoch (4 bytes) + start offset (8 bytes) + end
+/// offset (8 bytes).
+pub(crate) const EPOCH_ENTRY_SIZE: usize = 4 + 8 + 8;
+
+/// Master side of a replication link.
+///
+/// The read task receives the max physical offset reported by the slave, the write task
+/// streams commit log data to the slave starting from the offset the slave asked for.
+///
+/// In controller mode the link starts with a handshake: the slave sends its broker id and the
+/// master answers with its epoch history, so that the slave can truncate the entries the
+/// master does not have. Every transfer frame then carries the epoch of its data.
+pub struct DefaultHAConnection {
+    this: Weak<DefaultHAConnection>,
+    ha_service: Arc<DefaultHAServiceInner>,
+    message_store: ArcMut<LocalFileMessageStore>,
+    message_store_config: Arc<MessageStoreConfig>,
+    socket: parking_lot::Mutex<Option<TcpStream>>,
+    client_address: SocketAddr,
+    current_state: AtomicU8,
+    slave_request_offset: AtomicI64,
+    slave_ack_offset: AtomicI64,
+    next_transfer_from_where: AtomicI64,
+    slave_broker_id: AtomicI64,
+    last_master_max_offset: AtomicI64,
+    last_transfer_time_ms: AtomicI64,
+    flow_monitor: FlowMonitor,
+    shutdown_tx: watch::Sender<bool>,
+}
+
+impl DefaultHAConnection {
+    pub(crate) fn new(
+        ha_service: Arc<DefaultHAServiceInner>,
+        message_store: ArcMut<LocalFileMessageStore>,
+        socket: TcpStream,
+        client_address: SocketAddr,
+    ) -> Arc<Self> {
+        let message_store_config = message_store.message_store_config();
+        let (shutdown_tx, _) = watch::channel(false);
+        let _ = socket.set_nodelay(true);
+        ha_service.connection_count().fetch_add(1, Ordering::AcqRel);
+        Arc::new_cyclic(|this| Self {
+            this: this.clone(),
+            ha_service,
+            message_store,
+            message_store_config,
+            socket: parking_lot::Mutex::new(Some(socket)),
+            client_address,
+            current_state: AtomicU8::new(HAConnectionState::Transfer.into()),
+            slave_request_offset: AtomicI64::new(-1),
+            slave_ack_offset: AtomicI64::new(-1),
+            next_transfer_from_where: AtomicI64::new(-1),
+            slave_broker_id: AtomicI64::new(-1),
+            last_master_max_offset: AtomicI64::new(-1),
+            last_transfer_time_ms: AtomicI64::new(0),
+            flow_monitor: FlowMonitor::new(),
+            shutdown_tx,
+        })
+    }
+
+    /// The broker id sent by the slave in the handshake, `-1` outside controller mode.
+    #[inline]
+    pub(crate) fn get_slave_broker_id(&self) -> i64 {
+        self.slave_broker_id.load(Ordering::Acquire)
+    }
+
+    fn is_stopped(&self) -> bool {
+        *self.shutdown_tx.borrow()
+    }
+
+    fn change_current_state(&self, state: HAConnectionState) {
+        self.current_state.store(state.into(), Ordering::Release);
+    }
+
+    /// Stops both tasks and unregisters the connection from the HA service. Only the first
+    /// call has an effect.
+    fn stop(&self) {
+        if self.shutdown_tx.send_replace(true) {
+            return;
+        }
+        self.change_current_state(HAConnectionState::Shutdown);
+        self.ha_service.remove_connection(&self.client_address);
+        self.ha_service
+            .connection_count()
+            .fetch_sub(1, Ordering::AcqRel);
+        info!("HA connection {} closed", self.client_address);
+    }
+
+    async fn read_socket(self: Arc<Self>, mut reader: OwnedReadHalf) {
+        let housekeeping_interval = self.message_store_config.ha_housekeeping_interval as u64;
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+        let mut buf = [0u8; REPORT_HEADER_SIZE];
+        loop {
+            let read = tokio::select! {
+                _ = shutdown_rx.changed() => break,
+                read = tokio::time::timeout(
+                    Duration::from_millis(housekeeping_interval),
+                    reader.read_exact(&mut buf),
+                ) => read,
+            };
+            match read {
+                Ok(Ok(_)) => {
+                    let read_offset = i64::from_be_bytes(buf);
+                    self.slave_ack_offset.store(read_offset, Ordering::Release);
+                    if self.slave_request_offset.load(Ordering::Acquire) < 0 {
+                        self.slave_request_offset
+                            .store(read_offset, Ordering::Release);
+                        info!(
+                            "slave[{}] request offset {}",
+                            self.client_address, read_offset
+                        );
+                    }
+                    if let Some(auto_switch) = self.ha_service.auto_switch() {
+                        self.on_slave_ack(auto_switch, read_offset);
+                    }
+                    self.ha_service.notify_transfer_some(read_offset);
+                }
+                Ok(Err(e)) => {
+                    warn!(
+                        "read from slave[{}] failed, close the connection: {}",
+                        self.client_address, e
+                    );
+                    break;
+                }
+                Err(_) => {
+                    warn!(
+                        "slave[{}] has not reported for {}ms, close the connection",
+                        self.client_address, housekeeping_interval
+                    );
+                    break;
+                }
+            }
+        }
+        self.stop();
+    }
+
+    async fn write_socket(self: Arc<Self>, mut writer: OwnedWriteHalf) {
+        let heartbeat_interval = self.message_store_config.ha_send_heartbeat_interval as i64;
+        let batch_size = self.message_store_config.ha_transfer_batch_size as i32;
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+        let mut last_write_timestamp = get_current_millis() as i64;
+        let mut frame = BytesMut::with_capacity(TRANSFER_HEADER_SIZE + batch_size as usize);
+        while !self.is_stopped() {
+            let slave_request_offset = self.slave_request_offset.load(Ordering::Acquire);
+            if slave_request_offset == -1 {
+                tokio::select! {
+                    _ = shutdown_rx.changed() => break,
+                    _ = tokio::time::sleep(Duration::from_millis(10)) => {},
+                }
+                continue;
+            }
+
+            if self.next_transfer_from_where.load(Ordering::Acquire) == -1 {
+                let next_transfer_from_where =
+                    if slave_request_offset == 0 && self.ha_service.auto_switch().is_some() {
+                        // A new replica copies the whole commit log, so that its epoch history
+                        // matches the one of the master.
+                        self.message_store.get_min_phy_offset()
+                    } else if slave_request_offset == 0 {
+                        // An empty slave starts from the beginning of the last commit log file.
+                        let master_offset = self.message_store.get_max_phy_offset();
+                        let master_offset = master_offset
+                            - master_offset
+                                % self.message_store_config.mapped_file_size_commit_log as i64;
+                        master_offset.max(0)
+                    } else {
+                        slave_request_offset
+                    };
+                self.next_transfer_from_where
+                    .store(next_transfer_from_where, Ordering::Release);
+                info!(
+                    "master transfer data from {} to slave[{}], and slave request {}",
+                    next_transfer_from_where, self.client_address, slave_request_offset
+                );
+            }
+
+            let this_offset = self.next_transfer_from_where.load(Ordering::Acquire);
+            frame.clear();
+            self.last_master_max_offset
+                .store(self.message_store.get_max_phy_offset(), Ordering::Release);
+            self.last_transfer_time_ms
+                .store(get_current_millis() as i64, Ordering::Release);
+            match self.message_store.get_commit_log_data(this_offset) {
+                Some(mut result) => {
+                    let mut size = result.size.min(batch_size);
+                    let epoch_entry = self.transfer_epoch_entry(this_offset);
+                    if let Some(epoch_entry) = epoch_entry.as_ref() {
+                        // A frame never spans two epochs.
+                        size = (size as i64).min(epoch_entry.end_offset - this_offset) as i32;
+                    }
+                    if size > 0 {
+                        if let Some(bytes) = result.get_bytes_ref() {
+                            self.put_header(&mut frame, this_offset, size, epoch_entry);
+                            frame.put_slice(&bytes[..size as usize]);
+                        }
+                    }
+                    result.release();
+                    if frame.is_empty() {
+                        // The data is not readable yet or the epoch clamp left nothing to
+                        // send, wait for the next round instead of sending an empty frame.
+                        tokio::select! {
+                            _ = shutdown_rx.changed() => break,
+                            _ = self.ha_service.wait_notify_object().wait_for_running(100) => {},
+                        }
+                        continue;
+                    }
+                    self.next_transfer_from_where
+                        .store(this_offset + size as i64, Ordering::Release);
+                }
+                None => {
+                    let now = get_current_millis() as i64;
+                    if now - last_write_timestamp > heartbeat_interval {
+                        // Heartbeat keeps the slave from closing an idle link.
+                        let epoch_entry = self.transfer_epoch_entry(this_offset);
+                        self.put_header(&mut frame, this_offset, 0, epoch_entry);
+                    } else {
+                        tokio::select! {
+                            _ = shutdown_rx.changed() => break,
+                            _ = self.ha_service.wait_notify_object().wait_for_running(100) => {},
+                        }
+                        continue;
+                    }
+                }
+            }
+
+            if let Err(e) = writer.write_all(&frame).await {
+                error!(
+                    "write to slave[{}] failed, close the connection: {}",
+                    self.client_address, e
+                );
+                break;
+            }
+            self.flow_monitor
+                .add_byte_count_transferred(frame.len() as i64);
+            last_write_timestamp = get_current_millis() as i64;
+        }
+        let _ = writer.shutdown().await;
+        self.stop();
+    }
+
+    /// Receives the broker id of the slave and answers with the epoch history of the master.
+    async fn handshake(&self, socket: &mut TcpStream, auto_switch: &AutoSwitchHAState) -> bool {
+        self.change_current_state(HAConnectionState::Handshake);
+        let housekeeping_interval = self.message_store_config.ha_housekeeping_interval as u64;
+        let mut buf = [0u8; 8];
+        match tokio::time::timeout(
+            Duration::from_millis(housekeeping_interval),
+            socket.read_exact(&mut buf),
+        )
+        .await
+        {
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => {
+                warn!(
+                    "handshake with slave[{}] failed: {}",
+                    self.client_address, e
+                );
+                return false;
+            }
+            Err(_) => {
+                warn!("handshake with slave[{}] timeout", self.client_address);
+                return false;
+            }
+        }
+        let slave_broker_id = i64::from_be_bytes(buf);
+        self.slave_broker_id
+            .store(slave_broker_id, Ordering::Release);
+
+        let entries = auto_switch.handshake_entries(self.message_store.get_max_phy_offset());
+        let mut frame = BytesMut::with_capacity(4 + entries.len() * EPOCH_ENTRY_SIZE);
+        frame.put_i32(entries.len() as i32);
+        for entry in entries.iter() {
+            frame.put_i32(entry.epoch);
+            frame.put_i64(entry.start_offset);
+            frame.put_i64(entry.end_offset);
+        }
+        if let Err(e) = socket.write_all(&frame).await {
+            warn!(
+                "handshake with slave[{}] failed: {}",
+                self.client_address, e
+            );
+            return false;
+        }
+        info!(
+            "handshake with slave[{}] brokerId {} finished, epochs: {:?}",
+            self.client_address, slave_broker_id, entries
+        );
+        self.change_current_state(HAConnectionState::Transfer);
+        true
+    }
+
+    fn transfer_epoch_entry(&self, offset: i64) -> Option<EpochEntry> {
+        let epoch_cache = self.ha_service.auto_switch()?.epoch_cache();
+        epoch_cache
+            .find_epoch_entry_by_offset(offset)
+            .or_else(|| epoch_cache.first_entry())
+    }
+
+    fn put_header(
+        &self,
+        frame: &mut BytesMut,
+        offset: i64,
+        size: i32,
+        epoch_entry: Option<EpochEntry>,
+    ) {
+        frame.put_i64(offset);
+        frame.put_i32(size);
+        if self.ha_service.auto_switch().is_some() {
+            let epoch_entry = epoch_entry.unwrap_or(EpochEntry::new(-1, 0));
+            frame.put_i32(epoch_entry.epoch);
+            frame.put_i64(epoch_entry.start_offset);
+            frame.put_i64(self.message_store.get_confirm_offset());
+        }
+    }
+
+    /// Tracks when the slave caught up with the master, expands the sync state set with it
+    /// and moves the confirm offset.
+    fn on_slave_ack(&self, auto_switch: &AutoSwitchHAState, slave_max_offset: i64) {
+        let slave_broker_id = self.get_slave_broker_id();
+        if slave_max_offset >= self.last_master_max_offset.load(Ordering::Acquire) {
+            let caught_up_time_ms = if self.message_store.get_max_phy_offset() == slave_max_offset {
+                get_current_millis() as i64
+            } else {
+                self.last_transfer_time_ms.load(Ordering::Acquire)
+            };
+            auto_switch.update_connection_last_caught_up_time(slave_broker_id, caught_up_time_ms);
+            auto_switch.maybe_expand_in_sync_state_set(
+                slave_broker_id,
+                slave_max_offset,
+                self.message_store.get_confirm_offset(),
+            );
+        }
+        auto_switch.update_confirm_offset_when_slave_ack(
+            &self.ha_service,
+            &self.message_store,
+            slave_broker_id,
+        );
+    }
+}
+
+impl HAConnection for DefaultHAConnection {
+    async fn start(&self) {
+        let Some(this) = self.this.upgrade() else {
+            return;
+        };
+        let Some(mut socket) = self.socket.lock().take() else {
+            return;
+        };
+        let Some(auto_switch) = self.ha_service.auto_switch().cloned() else {
+            let (reader, writer) = socket.into_split();
+            tokio::spawn(this.clone().read_socket(reader));
+            tokio::spawn(this.write_socket(writer));
+            return;
+        };
+        tokio::spawn(async move {
+            if !this.handshake(&mut socket, &auto_switch).await {
+                this.stop();
+                return;
+            }
+            let (reader, writer) = socket.into_split();
+            tokio::spawn(this.clone().read_socket(reader));
+            tokio::spawn(this.write_socket(writer));
+        });
+    }
+
+    async fn shutdown(&self) {
+        self.stop();
+    }
+
+    fn close(&self) {
+        self.stop();
+    }
+
+    fn get_current_state(&self) -> HAConnectionState {
+        HAConnectionState::from(self.current_state.load(Ordering::Acquire))
+    }
+
+    fn get_client_address(&self) -> SocketAddr {
+        self.client_address
+    }
+
+    fn get_transferred_byte_in_second(&self) -> i64 {
+        self.flow_monitor.get_transferred_byte_in_second()
+    }
+
+    fn get_transfer_from_where(&self) -> i64 {
+        self.next_transfer_from_where.load(Ordering::Acquire)
+    }
+
+    fn get_slave_ack_offset(&self) -> i64 {
+        self.slave_ack_offset.load(Ordering::Acquire)
+    }
+}
@@ -14,127 +14,520 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-/*
- * Licensed to the Apache Software Foundation (ASF) under one or more
- * contributor license agreements.  See the NOTICE file distributed with
- * this work for additional information regarding copyright ownership.
- * The ASF licenses this file to You under the Apache License, Version 2.0
- * (the "License"); you may not use this file except in compliance with
- * the License.  You may obtain a copy of the License at
- *
- *     http://www.apache.org/licenses/LICENSE-2.0
- *
- * Unless required by applicable law or agreed to in writing, software
- * distributed under the License is distributed on an "AS IS" BASIS,
- * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
- * See the License for the specific language governing permissions and
- * limitations under the License.
- */
 
+use std::net::SocketAddr;
 use std::sync::atomic::AtomicI32;
 use std::sync::atomic::AtomicI64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
 
+use parking_lot::Mutex;
+use parking_lot::MutexGuard;
+use rocketmq_common::common::broker::broker_role::BrokerRole;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_remoting::protocol::body::ha_connection_runtime_info::HAConnectionRuntimeInfo;
 use rocketmq_remoting::protocol::body::ha_runtime_info::HARuntimeInfo;
 use rocketmq_rust::ArcMut;
+use tokio::net::TcpListener;
+use tokio::sync::watch;
 use tracing::error;
+use tracing::info;
 
 use crate::base::message_store::MessageStore;
+use crate::config::message_store_config::MessageStoreConfig;
+use crate::ha::auto_switch::auto_switch_ha_service::AutoSwitchHAState;
+use crate::ha::default_ha_client::DefaultHAClient;
+use crate::ha::default_ha_connection::DefaultHAConnection;
+use crate::ha::general_ha_client::GeneralHAClient;
+use crate::ha::general_ha_connection::GeneralHAConnection;
+use crate::ha::group_transfer_service::GroupTransferService;
 use crate::ha::ha_client::HAClient;
 use crate::ha::ha_connection::HAConnection;
+use crate::ha::ha_connection_state::HAConnectionState;
 use crate::ha::ha_connection_state_notification_request::HAConnectionStateNotificationRequest;
 use crate::ha::ha_service::HAService;
 use crate::ha::wait_notify_object::WaitNotifyObject;
 use crate::log_file::flush_manager_impl::group_commit_request::GroupCommitRequest;
+use crate::message_store::local_file_message_store::LocalFileMessageStore;
+use crate::store_error::HAError;
 use crate::store_error::HAResult;
 
-pub struct DefaultHAService;
+const CONNECTION_ESTABLISH_TIMEOUT_MILLIS: i64 = 10 * 1000;
+
+/// Master/slave replication over the HA port.
+///
+/// On a master, slaves connect to `ha_listen_port` and every connection streams commit log
+/// data to its slave. On a slave, a [`DefaultHAClient`] pulls data from `ha_master_address`.
+pub struct DefaultHAService {
+    message_store_config: Arc<MessageStoreConfig>,
+    default_message_store: Option<ArcMut<LocalFileMessageStore>>,
+    inner: Arc<DefaultHAServiceInner>,
+    accept_socket_service: Option<AcceptSocketService>,
+    ha_client: Option<Arc<DefaultHAClient>>,
+}
+
+/// State shared between the HA service and its background tasks.
+pub(crate) struct DefaultHAServiceInner {
+    message_store_config: Arc<MessageStoreConfig>,
+    connection_count: AtomicI32,
+    connection_list: Mutex<Vec<Arc<DefaultHAConnection>>>,
+    push_to_slave_max_offset: AtomicI64,
+    wait_notify_object: Arc<WaitNotifyObject>,
+    group_transfer_service: GroupTransferService,
+    auto_switch: Option<Arc<AutoSwitchHAState>>,
+    shutdown_tx: watch::Sender<bool>,
+}
+
+impl DefaultHAServiceInner {
+    pub(crate) fn new(
+        message_store_config: Arc<MessageStoreConfig>,
+        auto_switch: Option<Arc<AutoSwitchHAState>>,
+    ) -> Self {
+        let (shutdown_tx, _) = watch::channel(false);
+        Self {
+            message_store_config,
+            connection_count: AtomicI32::new(0),
+            connection_list: Mutex::new(Vec::new()),
+            push_to_slave_max_offset: AtomicI64::new(0),
+            wait_notify_object: Arc::new(WaitNotifyObject::new()),
+            group_transfer_service: GroupTransferService::new(),
+            auto_switch,
+            shutdown_tx,
+        }
+    }
+
+    /// The replica state when running in controller mode.
+    #[inline]
+    pub(crate) fn auto_switch(&self) -> Option<&Arc<AutoSwitchHAState>> {
+        self.auto_switch.as_ref()
+    }
+
+    #[inline]
+    pub(crate) fn connection_count(&self) -> &AtomicI32 {
+        &self.connection_count
+    }
+
+    #[inline]
+    pub(crate) fn connection_list(&self) -> MutexGuard<'_, Vec<Arc<DefaultHAConnection>>> {
+        self.connection_list.lock()
+    }
+
+    #[inline]
+    pub(crate) fn push_to_slave_max_offset(&self) -> i64 {
+        self.push_to_slave_max_offset.load(Ordering::Acquire)
+    }
+
+    #[inline]
+    pub(crate) fn wait_notify_object(&self) -> &WaitNotifyObject {
+        &self.wait_notify_object
+    }
+
+    #[inline]
+    pub(crate) fn group_transfer_service(&self) -> &GroupTransferService {
+        &self.group_transfer_service
+    }
+
+    pub(crate) fn add_connection(&self, connection: Arc<DefaultHAConnection>) {
+        self.connection_list.lock().push(connection);
+    }
+
+    pub(crate) fn remove_connection(&self, client_address: &SocketAddr) {
+        self.connection_list
+            .lock()
+            .retain(|connection| connection.get_client_address() != *client_address);
+    }
+
+    /// Records the offset acknowledged by a slave and wakes up the waiting write requests.
+    pub(crate) fn notify_transfer_some(&self, offset: i64) {
+        self.push_to_slave_max_offset
+            .fetch_max(offset, Ordering::AcqRel);
+        self.group_transfer_service.notify_transfer_some();
+    }
+
+    fn is_in_sync_slave(&self, master_put_where: i64, connection: &DefaultHAConnection) -> bool {
+        master_put_where - connection.get_slave_ack_offset()
+            < self.message_store_config.ha_max_gap_not_in_sync as i64
+    }
+
+    pub(crate) fn destroy_connections(&self) {
+        let connections = std::mem::take(&mut *self.connection_list.lock());
+        for connection in connections {
+            connection.close();
+        }
+    }
+}
+
+impl DefaultHAService {
+    pub fn new(message_store_config: Arc<MessageStoreConfig>) -> Self {
+        Self {
+            inner: Arc::new(DefaultHAServiceInner::new(
+                message_store_config.clone(),
+                None,
+            )),
+            message_store_config,
+            default_message_store: None,
+            accept_socket_service: None,
+            ha_client: None,
+        }
+    }
+
+    /// Creates the replication part of an
+    /// [`AutoSwitchHAService`](crate::ha::auto_switch::auto_switch_ha_service::AutoSwitchHAService),
+    /// whose slaves are managed by the auto switch service itself.
+    pub(crate) fn new_with_auto_switch(
+        message_store_config: Arc<MessageStoreConfig>,
+        auto_switch: Arc<AutoSwitchHAState>,
+    ) -> Self {
+        Self {
+            inner: Arc::new(DefaultHAServiceInner::new(
+                message_store_config.clone(),
+                Some(auto_switch),
+            )),
+            message_store_config,
+            default_message_store: None,
+            accept_socket_service: None,
+            ha_client: None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn inner(&self) -> &DefaultHAServiceInner {
+        &self.inner
+    }
+
+    pub(crate) fn master_runtime_info(&self, master_put_where: i64) -> HARuntimeInfo {
+        let mut info = HARuntimeInfo {
+            master: true,
+            master_commit_log_max_offset: master_put_where as u64,
+            ..Default::default()
+        };
+        let mut in_sync_nums = 0;
+        for connection in self.inner.connection_list().iter() {
+            let slave_ack_offset = connection.get_slave_ack_offset();
+            let in_sync = self.inner.is_in_sync_slave(master_put_where, connection);
+            if in_sync {
+                in_sync_nums += 1;
+            }
+            info.ha_connection_info.push(HAConnectionRuntimeInfo {
+                addr: connection.get_client_address().to_string(),
+                slave_ack_offset: slave_ack_offset.max(0) as u64,
+                diff: master_put_where - slave_ack_offset,
+                in_sync,
+                transferred_byte_in_second: connection.get_transferred_byte_in_second() as u64,
+                transfer_from_where: connection.get_transfer_from_where().max(0) as u64,
+            });
+        }
+        info.in_sync_slave_nums = in_sync_nums;
+        info
+    }
+
+    pub(crate) fn slave_runtime_info(&self, ha_client: Option<&DefaultHAClient>) -> HARuntimeInfo {
+        let mut info = HARuntimeInfo::default();
+        if let Some(ha_client) = ha_client {
+            let client_info = &mut info.ha_client_runtime_info;
+            client_info.master_addr = ha_client.get_ha_master_address();
+            client_info.last_read_timestamp = ha_client.get_last_read_timestamp() as u64;
+            client_info.last_write_timestamp = ha_client.get_last_write_timestamp() as u64;
+            client_info.transferred_byte_in_second =
+                ha_client.get_transferred_byte_in_second() as u64;
+            client_info.is_activated = true;
+        }
+        if let Some(message_store) = self.default_message_store.as_ref() {
+            info.ha_client_runtime_info.max_offset = message_store.get_max_phy_offset() as u64;
+            info.ha_client_runtime_info.master_flush_offset =
+                message_store.get_master_flushed_offset() as u64;
+        }
+        info
+    }
+}
 
 impl HAService for DefaultHAService {
-    fn init<MS: MessageStore>(&mut self, message_store: ArcMut<MS>) -> HAResult<()> {
-        error!("DefaultHAService init not implemented");
+    fn init(&mut self, message_store: ArcMut<LocalFileMessageStore>) -> HAResult<()> {
+        self.accept_socket_service = Some(AcceptSocketService::new(
+            self.message_store_config.ha_listen_port,
+        ));
+        if self.message_store_config.broker_role == BrokerRole::Slave
+            && self.inner.auto_switch.is_none()
+        {
+            self.ha_client = Some(DefaultHAClient::new(message_store.clone()));
+        }
+        self.default_message_store = Some(message_store);
         Ok(())
     }
 
     fn start(&mut self) -> HAResult<()> {
-        error!("DefaultHAService start not implemented");
+        let message_store = self
+            .default_message_store
+            .clone()
+            .ok_or_else(|| HAError::Service("DefaultHAService is not initialized".to_string()))?;
+        if let Some(accept_socket_service) = self.accept_socket_service.as_mut() {
+            accept_socket_service.begin_accept()?;
+            accept_socket_service.start(self.inner.clone(), message_store)?;
+        }
+        let inner = self.inner.clone();
+        let shutdown_rx = inner.shutdown_tx.subscribe();
+        tokio::spawn(async move {
+            inner
+                .group_transfer_service
+                .run(inner.as_ref(), shutdown_rx)
+                .await;
+        });
+        if let Some(ha_client) = self.ha_client.as_ref() {
+            ha_client.start_client();
+        }
         Ok(())
     }
 
     fn shutdown(&self) {
-        todo!()
+        if let Some(ha_client) = self.ha_client.as_ref() {
+            ha_client.shutdown_client();
+        }
+        self.inner.shutdown_tx.send_replace(true);
+        self.inner.destroy_connections();
     }
 
-    async fn change_to_master(&self, master_epoch: i32) -> HAResult<bool> {
-        todo!()
+    async fn change_to_master(&self, _master_epoch: i32) -> HAResult<bool> {
+        Ok(false)
     }
 
-    async fn change_to_master_when_last_role_is_master(&self, master_epoch: i32) -> HAResult<bool> {
-        todo!()
+    async fn change_to_master_when_last_role_is_master(
+        &self,
+        _master_epoch: i32,
+    ) -> HAResult<bool> {
+        Ok(false)
     }
 
     async fn change_to_slave(
         &self,
-        new_master_addr: &str,
-        new_master_epoch: i32,
-        slave_id: Option<i64>,
+        _new_master_addr: &str,
+        _new_master_epoch: i32,
+        _slave_id: Option<i64>,
     ) -> HAResult<bool> {
-        todo!()
+        Ok(false)
     }
 
     async fn change_to_slave_when_master_not_change(
         &self,
-        new_master_addr: &str,
-        new_master_epoch: i32,
+        _new_master_addr: &str,
+        _new_master_epoch: i32,
     ) -> HAResult<bool> {
-        todo!()
+        Ok(false)
     }
 
     fn update_master_address(&self, new_addr: &str) {
-        todo!()
+        if let Some(ha_client) = self.ha_client.as_ref() {
+            ha_client.set_master_address(new_addr);
+        }
     }
 
     fn update_ha_master_address(&self, new_addr: &str) {
-        todo!()
+        if let Some(ha_client) = self.ha_client.as_ref() {
+            ha_client.set_ha_master_address(new_addr);
+        }
     }
 
     fn in_sync_replicas_nums(&self, master_put_where: i64) -> i32 {
-        todo!()
+        let in_sync_slaves = self
+            .inner
+            .connection_list()
+            .iter()
+            .filter(|connection| self.inner.is_in_sync_slave(master_put_where, connection))
+            .count();
+        in_sync_slaves as i32 + 1
     }
 
     fn get_connection_count(&self) -> &AtomicI32 {
-        todo!()
+        self.inner.connection_count()
     }
 
     fn put_request(&self, request: GroupCommitRequest) {
-        todo!()
+        self.inner.group_transfer_service.put_request(request);
     }
 
     fn put_group_connection_state_request(&self, request: HAConnectionStateNotificationRequest) {
-        todo!()
+        let inner = self.inner.clone();
+        tokio::spawn(async move {
+            let begin = get_current_millis() as i64;
+            loop {
+                let state = inner
+                    .connection_list()
+                    .iter()
+                    .find(|connection| {
+                        connection.get_client_address().to_string() == request.remote_addr()
+                    })
+                    .map(|connection| connection.get_current_state());
+                match state {
+                    Some(state) if state == request.expect_state() => {
+                        request.complete(true);
+                        return;
+                    }
+                    Some(HAConnectionState::Shutdown) if request.notify_when_shutdown() => {
+                        request.complete(false);
+                        return;
+                    }
+                    None if get_current_millis() as i64 - begin
+                        > CONNECTION_ESTABLISH_TIMEOUT_MILLIS =>
+                    {
+                        request.complete(false);
+                        return;
+                    }
+                    _ => {}
+                }
+                if *inner.shutdown_tx.borrow() {
+                    request.complete(false);
+                    return;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        });
     }
 
-    fn get_connection_list<CN: HAConnection>(&self) -> Vec<Arc<CN>> {
-        todo!()
+    fn get_connection_list(&self) -> Vec<Arc<GeneralHAConnection>> {
+        self.inner
+            .connection_list()
+            .iter()
+            .map(|connection| {
+                Arc::new(GeneralHAConnection::new_with_default_ha_connection(
+                    connection.clone(),
+                ))
+            })
+            .collect()
     }
 
-    fn get_ha_client<CL: HAClient>(&self) -> Arc<CL> {
-        todo!()
+    fn get_ha_client(&self) -> Option<Arc<GeneralHAClient>> {
+        self.ha_client
+            .as_ref()
+            .map(|client| Arc::new(GeneralHAClient::new_with_default_ha_client(client.clone())))
     }
 
     fn get_push_to_slave_max_offset(&self) -> &AtomicI64 {
-        todo!()
+        &self.inner.push_to_slave_max_offset
     }
 
     fn get_runtime_info(&self, master_put_where: i64) -> HARuntimeInfo {
-        todo!()
+        if self.message_store_config.broker_role == BrokerRole::Slave {
+            self.slave_runtime_info(self.ha_client.as_deref())
+        } else {
+            self.master_runtime_info(master_put_where)
+        }
     }
 
     fn get_wait_notify_object(&self) -> Arc<WaitNotifyObject> {
-        todo!()
+        self.inner.wait_notify_object.clone()
     }
 
     fn is_slave_ok(&self, master_put_where: i64) -> bool {
-        todo!()
+        self.inner.connection_count.load(Ordering::Acquire) > 0
+            && master_put_where - self.inner.push_to_slave_max_offset()
+                < self.message_store_config.ha_max_gap_not_in_sync as i64
+    }
+}
+
+/// Accepts replication connections from slaves on the HA listen port.
+struct AcceptSocketService {
+    listen_port: usize,
+    listener: Option<std::net::TcpListener>,
+}
+
+impl AcceptSocketService {
+    fn new(listen_port: usize) -> Self {
+        Self {
+            listen_port,
+            listener: None,
+        }
+    }
+
+    /// Binds the listen port, so that a port conflict fails the store start-up.
+    fn begin_accept(&mut self) -> HAResult<()> {
+        let listener = std::net::TcpListener::bind(("0.0.0.0", self.listen_port as u16))?;
+        listener.set_nonblocking(true)?;
+        info!(
+            "HAService listening on {}",
+            listener.local_addr().map_err(HAError::Io)?
+        );
+        self.listener = Some(listener);
+        Ok(())
+    }
+
+    fn start(
+        &mut self,
+        ha_service: Arc<DefaultHAServiceInner>,
+        message_store: ArcMut<LocalFileMessageStore>,
+    ) -> HAResult<()> {
+        let Some(listener) = self.listener.take() else {
+            return Ok(());
+        };
+        let listener = TcpListener::from_std(listener)?;
+        let mut shutdown_rx = ha_service.shutdown_tx.subscribe();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown_rx.changed() => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok((socket, client_address)) => {
+                            info!("HAService receive new connection, {}", client_address);
+                            let connection = DefaultHAConnection::new(
+                                ha_service.clone(),
+                                message_store.clone(),
+                                socket,
+                                client_address,
+                            );
+                            ha_service.add_connection(connection.clone());
+                            connection.start().await;
+                        }
+                        Err(e) => {
+                            error!("HAService accept connection failed: {}", e);
+                        }
+                    }
+                }
+            }
+            info!("AcceptSocketService service end");
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_slave_ok_requires_connection_and_small_gap() {
+        let message_store_config = Arc::new(MessageStoreConfig::default());
+        let ha_service = DefaultHAService::new(message_store_config.clone());
+        assert!(!ha_service.is_slave_ok(0));
+
+        ha_service
+            .get_connection_count()
+            .fetch_add(1, Ordering::AcqRel);
+        ha_service.inner.notify_transfer_some(1024);
+        assert!(ha_service.is_slave_ok(2048));
+        let far_ahead = 1024 + message_store_config.ha_max_gap_not_in_sync as i64;
+        assert!(!ha_service.is_slave_ok(far_ahead));
+    }
+
+    #[test]
+    fn push_to_slave_max_offset_never_goes_back() {
+        let ha_service = DefaultHAService::new(Arc::new(MessageStoreConfig::default()));
+        ha_service.inner.notify_transfer_some(4096);
+        ha_service.inner.notify_transfer_some(1024);
+        assert_eq!(
+            ha_service
+                .get_push_to_slave_max_offset()
+                .load(Ordering::Acquire),
+            4096
+        );
+    }
+
+    #[test]
+    fn runtime_info_of_master_without_slaves() {
+        let ha_service = DefaultHAService::new(Arc::new(MessageStoreConfig::default()));
+        let info = ha_service.get_runtime_info(1024);
+        assert!(info.master);
+        assert_eq!(info.master_commit_log_max_offset, 1024);
+        assert_eq!(info.in_sync_slave_nums, 0);
+        assert!(info.ha_connection_info.is_empty());
+        assert_eq!(ha_service.in_sync_replicas_nums(1024), 1);
     }
 }
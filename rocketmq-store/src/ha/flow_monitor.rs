@@ -0,0 +1,93 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::sync::atomic::AtomicI64;
+use std::sync::atomic::Ordering;
+
+use rocketmq_common::TimeUtils::get_current_millis;
+
+/// Counts the bytes moved by an HA connection and exposes the throughput of the last
+/// complete second.
+#[derive(Default)]
+pub(crate) struct FlowMonitor {
+    transferred_byte: AtomicI64,
+    transferred_byte_in_second: AtomicI64,
+    window_begin_timestamp: AtomicI64,
+}
+
+impl FlowMonitor {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn add_byte_count_transferred(&self, count: i64) {
+        self.roll_window(get_current_millis() as i64);
+        self.transferred_byte.fetch_add(count, Ordering::AcqRel);
+    }
+
+    pub(crate) fn get_transferred_byte_in_second(&self) -> i64 {
+        self.roll_window(get_current_millis() as i64);
+        self.transferred_byte_in_second.load(Ordering::Acquire)
+    }
+
+    fn roll_window(&self, now: i64) {
+        let begin = self.window_begin_timestamp.load(Ordering::Acquire);
+        if now - begin < 1000 {
+            return;
+        }
+        if self
+            .window_begin_timestamp
+            .compare_exchange(begin, now, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+        {
+            let transferred = self.transferred_byte.swap(0, Ordering::AcqRel);
+            // The window is stale if nothing was recorded during the last second.
+            let in_second = if now - begin < 2000 { transferred } else { 0 };
+            self.transferred_byte_in_second
+                .store(in_second, Ordering::Release);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transferred_bytes_are_reported_after_window_rolls() {
+        let monitor = FlowMonitor::new();
+        let now = get_current_millis() as i64;
+        monitor.window_begin_timestamp.store(now, Ordering::Release);
+        monitor.add_byte_count_transferred(100);
+        monitor.add_byte_count_transferred(28);
+        assert_eq!(
+            monitor.transferred_byte_in_second.load(Ordering::Acquire),
+            0
+        );
+
+        monitor.roll_window(now + 1000);
+        assert_eq!(
+            monitor.transferred_byte_in_second.load(Ordering::Acquire),
+            128
+        );
+
+        monitor.roll_window(now + 5000);
+        assert_eq!(
+            monitor.transferred_byte_in_second.load(Ordering::Acquire),
+            0
+        );
+    }
+}
@@ -0,0 +1,95 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::sync::Arc;
+
+use crate::ha::default_ha_client::DefaultHAClient;
+use crate::ha::ha_client::HAClient;
+use crate::ha::ha_connection_state::HAConnectionState;
+
+/// Client handle returned by [`GeneralHAService`](crate::ha::general_ha_service::GeneralHAService),
+/// dispatching to the concrete client of the running HA service.
+pub struct GeneralHAClient {
+    default_ha_client: Option<Arc<DefaultHAClient>>,
+}
+
+impl GeneralHAClient {
+    pub fn new_with_default_ha_client(default_ha_client: Arc<DefaultHAClient>) -> Self {
+        Self {
+            default_ha_client: Some(default_ha_client),
+        }
+    }
+
+    fn client(&self) -> &DefaultHAClient {
+        self.default_ha_client
+            .as_deref()
+            .expect("GeneralHAClient without underlying client")
+    }
+}
+
+impl HAClient for GeneralHAClient {
+    async fn start(&self) {
+        self.client().start().await
+    }
+
+    async fn shutdown(&self) {
+        self.client().shutdown().await
+    }
+
+    async fn wakeup(&self) {
+        self.client().wakeup().await
+    }
+
+    async fn update_master_address(&self, new_address: &str) {
+        self.client().update_master_address(new_address).await
+    }
+
+    async fn update_ha_master_address(&self, new_address: &str) {
+        self.client().update_ha_master_address(new_address).await
+    }
+
+    fn get_master_address(&self) -> String {
+        self.client().get_master_address()
+    }
+
+    fn get_ha_master_address(&self) -> String {
+        self.client().get_ha_master_address()
+    }
+
+    fn get_last_read_timestamp(&self) -> i64 {
+        self.client().get_last_read_timestamp()
+    }
+
+    fn get_last_write_timestamp(&self) -> i64 {
+        self.client().get_last_write_timestamp()
+    }
+
+    fn get_current_state(&self) -> HAConnectionState {
+        self.client().get_current_state()
+    }
+
+    fn change_current_state(&self, ha_connection_state: HAConnectionState) {
+        self.client().change_current_state(ha_connection_state)
+    }
+
+    async fn close_master(&self) {
+        self.client().close_master().await
+    }
+
+    fn get_transferred_byte_in_second(&self) -> i64 {
+        self.client().get_transferred_byte_in_second()
+    }
+}
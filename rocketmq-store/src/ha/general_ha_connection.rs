@@ -0,0 +1,77 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use crate::ha::default_ha_connection::DefaultHAConnection;
+use crate::ha::ha_connection::HAConnection;
+use crate::ha::ha_connection_state::HAConnectionState;
+
+/// Connection handle returned by
+/// [`GeneralHAService`](crate::ha::general_ha_service::GeneralHAService), dispatching to the
+/// concrete connection of the running HA service.
+pub struct GeneralHAConnection {
+    default_ha_connection: Option<Arc<DefaultHAConnection>>,
+}
+
+impl GeneralHAConnection {
+    pub fn new_with_default_ha_connection(default_ha_connection: Arc<DefaultHAConnection>) -> Self {
+        Self {
+            default_ha_connection: Some(default_ha_connection),
+        }
+    }
+
+    fn connection(&self) -> &DefaultHAConnection {
+        self.default_ha_connection
+            .as_deref()
+            .expect("GeneralHAConnection without underlying connection")
+    }
+}
+
+impl HAConnection for GeneralHAConnection {
+    async fn start(&self) {
+        self.connection().start().await
+    }
+
+    async fn shutdown(&self) {
+        self.connection().shutdown().await
+    }
+
+    fn close(&self) {
+        self.connection().close()
+    }
+
+    fn get_current_state(&self) -> HAConnectionState {
+        self.connection().get_current_state()
+    }
+
+    fn get_client_address(&self) -> SocketAddr {
+        self.connection().get_client_address()
+    }
+
+    fn get_transferred_byte_in_second(&self) -> i64 {
+        self.connection().get_transferred_byte_in_second()
+    }
+
+    fn get_transfer_from_where(&self) -> i64 {
+        self.connection().get_transfer_from_where()
+    }
+
+    fn get_slave_ack_offset(&self) -> i64 {
+        self.connection().get_slave_ack_offset()
+    }
+}
@@ -21,40 +21,89 @@ use std::sync::Arc;
 
 use rocketmq_remoting::protocol::body::ha_runtime_info::HARuntimeInfo;
 use rocketmq_rust::ArcMut;
-use tracing::error;
 
-use crate::base::message_store::MessageStore;
-use crate::ha::ha_client::HAClient;
-use crate::ha::ha_connection::HAConnection;
+use crate::ha::auto_switch::auto_switch_ha_service::AutoSwitchHAService;
+use crate::ha::default_ha_service::DefaultHAService;
+use crate::ha::general_ha_client::GeneralHAClient;
+use crate::ha::general_ha_connection::GeneralHAConnection;
 use crate::ha::ha_connection_state_notification_request::HAConnectionStateNotificationRequest;
 use crate::ha::ha_service::HAService;
 use crate::ha::wait_notify_object::WaitNotifyObject;
 use crate::log_file::flush_manager_impl::group_commit_request::GroupCommitRequest;
+use crate::message_store::local_file_message_store::LocalFileMessageStore;
 use crate::store_error::HAResult;
 
-pub struct GeneralHAService;
+/// Calls `$call` on the configured implementation, bound to `$service`.
+macro_rules! dispatch {
+    ($self:ident, $service:ident => $call:expr) => {
+        match (&$self.default_ha_service, &$self.auto_switch_ha_service) {
+            (Some($service), _) => $call,
+            (None, Some($service)) => $call,
+            (None, None) => panic!("GeneralHAService without underlying HA service"),
+        }
+    };
+    (mut $self:ident, $service:ident => $call:expr) => {
+        match (
+            &mut $self.default_ha_service,
+            &mut $self.auto_switch_ha_service,
+        ) {
+            (Some($service), _) => $call,
+            (None, Some($service)) => $call,
+            (None, None) => panic!("GeneralHAService without underlying HA service"),
+        }
+    };
+}
+
+/// HA service used by the message store, dispatching to the configured implementation.
+pub struct GeneralHAService {
+    default_ha_service: Option<DefaultHAService>,
+    auto_switch_ha_service: Option<AutoSwitchHAService>,
+}
+
+impl GeneralHAService {
+    pub fn new_with_default_ha_service(default_ha_service: DefaultHAService) -> Self {
+        Self {
+            default_ha_service: Some(default_ha_service),
+            auto_switch_ha_service: None,
+        }
+    }
+
+    pub fn new_with_auto_switch_ha_service(auto_switch_ha_service: AutoSwitchHAService) -> Self {
+        Self {
+            default_ha_service: None,
+            auto_switch_ha_service: Some(auto_switch_ha_service),
+        }
+    }
+
+    /// The HA service of a broker in controller mode.
+    pub fn get_auto_switch_ha_service(&self) -> Option<&AutoSwitchHAService> {
+        self.auto_switch_ha_service.as_ref()
+    }
+}
 
 impl HAService for GeneralHAService {
-    fn init<MS: MessageStore>(&mut self, message_store: ArcMut<MS>) -> HAResult<()> {
-        error!("Initializing general HAService is not implemented");
-        Ok(())
+    fn init(&mut self, message_store: ArcMut<LocalFileMessageStore>) -> HAResult<()> {
+        dispatch!(mut self, service => service.init(message_store))
     }
 
     fn start(&mut self) -> HAResult<()> {
-        error!("Starting general HAService is not implemented");
-        Ok(())
+        dispatch!(mut self, service => service.start())
     }
 
     fn shutdown(&self) {
-        todo!()
+        dispatch!(self, service => service.shutdown())
     }
 
     async fn change_to_master(&self, master_epoch: i32) -> HAResult<bool> {
-        todo!()
+        dispatch!(self, service => service.change_to_master(master_epoch).await)
     }
 
     async fn change_to_master_when_last_role_is_master(&self, master_epoch: i32) -> HAResult<bool> {
-        todo!()
+        dispatch!(self, service => {
+            service
+                .change_to_master_when_last_role_is_master(master_epoch)
+                .await
+        })
     }
 
     async fn change_to_slave(
@@ -63,7 +112,11 @@ impl HAService for GeneralHAService {
         new_master_epoch: i32,
         slave_id: Option<i64>,
     ) -> HAResult<bool> {
-        todo!()
+        dispatch!(self, service => {
+            service
+                .change_to_slave(new_master_addr, new_master_epoch, slave_id)
+                .await
+        })
     }
 
     async fn change_to_slave_when_master_not_change(
@@ -71,54 +124,58 @@ impl HAService for GeneralHAService {
         new_master_addr: &str,
         new_master_epoch: i32,
     ) -> HAResult<bool> {
-        todo!()
+        dispatch!(self, service => {
+            service
+                .change_to_slave_when_master_not_change(new_master_addr, new_master_epoch)
+                .await
+        })
     }
 
     fn update_master_address(&self, new_addr: &str) {
-        todo!()
+        dispatch!(self, service => service.update_master_address(new_addr))
     }
 
     fn update_ha_master_address(&self, new_addr: &str) {
-        todo!()
+        dispatch!(self, service => service.update_ha_master_address(new_addr))
     }
 
     fn in_sync_replicas_nums(&self, master_put_where: i64) -> i32 {
-        todo!()
+        dispatch!(self, service => service.in_sync_replicas_nums(master_put_where))
     }
 
     fn get_connection_count(&self) -> &AtomicI32 {
-        todo!()
+        dispatch!(self, service => service.get_connection_count())
     }
 
     fn put_request(&self, request: GroupCommitRequest) {
-        todo!()
+        dispatch!(self, service => service.put_request(request))
     }
 
     fn put_group_connection_state_request(&self, request: HAConnectionStateNotificationRequest) {
-        todo!()
+        dispatch!(self, service => service.put_group_connection_state_request(request))
     }
 
-    fn get_connection_list<CN: HAConnection>(&self) -> Vec<Arc<CN>> {
-        todo!()
+    fn get_connection_list(&self) -> Vec<Arc<GeneralHAConnection>> {
+        dispatch!(self, service => service.get_connection_list())
     }
 
-    fn get_ha_client<CL: HAClient>(&self) -> Arc<CL> {
-        todo!()
+    fn get_ha_client(&self) -> Option<Arc<GeneralHAClient>> {
+        dispatch!(self, service => service.get_ha_client())
     }
 
     fn get_push_to_slave_max_offset(&self) -> &AtomicI64 {
-        todo!()
+        dispatch!(self, service => service.get_push_to_slave_max_offset())
     }
 
     fn get_runtime_info(&self, master_put_where: i64) -> HARuntimeInfo {
-        todo!()
+        dispatch!(self, service => service.get_runtime_info(master_put_where))
     }
 
     fn get_wait_notify_object(&self) -> Arc<WaitNotifyObject> {
-        todo!()
+        dispatch!(self, service => service.get_wait_notify_object())
     }
 
     fn is_slave_ok(&self, master_put_where: i64) -> bool {
-        todo!()
+        dispatch!(self, service => service.is_slave_ok(master_put_where))
     }
 }
@@ -0,0 +1,178 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use rocketmq_common::common::mix_all;
+use rocketmq_common::TimeUtils::get_current_nano;
+use tokio::sync::watch;
+use tokio::sync::Notify;
+use tracing::info;
+use tracing::warn;
+
+use crate::base::message_status_enum::PutMessageStatus;
+use crate::ha::default_ha_service::DefaultHAServiceInner;
+use crate::ha::ha_connection::HAConnection;
+use crate::log_file::flush_manager_impl::group_commit_request::GroupCommitRequest;
+
+/// Completes `SYNC_MASTER` write requests once enough slaves have acknowledged the
+/// offset of the request, or fails them with `FLUSH_SLAVE_TIMEOUT` after their deadline.
+#[derive(Default)]
+pub(crate) struct GroupTransferService {
+    requests: Mutex<Vec<GroupCommitRequest>>,
+    notify_transfer_object: Notify,
+}
+
+impl GroupTransferService {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn put_request(&self, request: GroupCommitRequest) {
+        self.requests.lock().push(request);
+        self.notify_transfer_object.notify_one();
+    }
+
+    pub(crate) fn notify_transfer_some(&self) {
+        self.notify_transfer_object.notify_one();
+    }
+
+    pub(crate) async fn run(
+        &self,
+        ha_service: &DefaultHAServiceInner,
+        mut shutdown_rx: watch::Receiver<bool>,
+    ) {
+        info!("GroupTransferService service started");
+        loop {
+            tokio::select! {
+                _ = shutdown_rx.changed() => break,
+                _ = tokio::time::timeout(
+                    Duration::from_millis(10),
+                    self.notify_transfer_object.notified(),
+                ) => {}
+            }
+            self.do_wait_transfer(ha_service);
+        }
+        info!("GroupTransferService service end");
+    }
+
+    pub(crate) fn do_wait_transfer(&self, ha_service: &DefaultHAServiceInner) {
+        let mut requests = self.requests.lock();
+        if requests.is_empty() {
+            return;
+        }
+        let now = get_current_nano();
+        let mut pending = Vec::with_capacity(requests.len());
+        for mut request in requests.drain(..) {
+            if Self::is_transfer_ok(ha_service, &request) {
+                request.wakeup_customer(PutMessageStatus::PutOk);
+            } else if now >= request.dead_line {
+                warn!(
+                    "Transfer message to slave timeout, offset : {}, request acks: {}",
+                    request.next_offset,
+                    request.get_ack_nums()
+                );
+                request.wakeup_customer(PutMessageStatus::FlushSlaveTimeout);
+            } else {
+                pending.push(request);
+            }
+        }
+        *requests = pending;
+    }
+
+    fn is_transfer_ok(ha_service: &DefaultHAServiceInner, request: &GroupCommitRequest) -> bool {
+        let ack_nums = request.get_ack_nums();
+        if ack_nums == mix_all::ALL_ACK_IN_SYNC_STATE_SET {
+            if let Some(auto_switch) = ha_service.auto_switch() {
+                // Every replica in the sync state set must acknowledge the write.
+                let sync_state_set = auto_switch.get_sync_state_set();
+                if sync_state_set.len() <= 1 {
+                    return true;
+                }
+                let acked = 1 + ha_service
+                    .connection_list()
+                    .iter()
+                    .filter(|connection| {
+                        sync_state_set.contains(&connection.get_slave_broker_id())
+                            && connection.get_slave_ack_offset() >= request.next_offset
+                    })
+                    .count();
+                return acked >= sync_state_set.len();
+            }
+        }
+        if ack_nums <= 1 {
+            return ha_service.push_to_slave_max_offset() >= request.next_offset;
+        }
+        // The master itself counts as one replica.
+        let mut acked = 1;
+        for connection in ha_service.connection_list().iter() {
+            if connection.get_slave_ack_offset() >= request.next_offset {
+                acked += 1;
+            }
+            if acked >= ack_nums {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::config::message_store_config::MessageStoreConfig;
+
+    #[tokio::test]
+    async fn request_completes_when_slave_catches_up() {
+        let ha_service = DefaultHAServiceInner::new(Arc::new(MessageStoreConfig::default()), None);
+        let mut request = GroupCommitRequest::new_with_ack_nums(1024, 3000, 1);
+        let future = request.future();
+        ha_service.group_transfer_service().put_request(request);
+
+        ha_service
+            .group_transfer_service()
+            .do_wait_transfer(&ha_service);
+        assert_eq!(ha_service.group_transfer_service().requests.lock().len(), 1);
+
+        ha_service.notify_transfer_some(2048);
+        ha_service
+            .group_transfer_service()
+            .do_wait_transfer(&ha_service);
+        assert!(ha_service
+            .group_transfer_service()
+            .requests
+            .lock()
+            .is_empty());
+        assert_eq!(future.await.unwrap(), PutMessageStatus::PutOk);
+    }
+
+    #[tokio::test]
+    async fn request_times_out_without_enough_acks() {
+        let ha_service = DefaultHAServiceInner::new(Arc::new(MessageStoreConfig::default()), None);
+        ha_service.notify_transfer_some(2048);
+        let mut request = GroupCommitRequest::new_with_ack_nums(1024, 0, 2);
+        let future = request.future();
+        ha_service.group_transfer_service().put_request(request);
+
+        ha_service
+            .group_transfer_service()
+            .do_wait_transfer(&ha_service);
+        assert_eq!(future.await.unwrap(), PutMessageStatus::FlushSlaveTimeout);
+    }
+}
@@ -21,12 +21,12 @@ use std::sync::Arc;
 use rocketmq_remoting::protocol::body::ha_runtime_info::HARuntimeInfo;
 use rocketmq_rust::ArcMut;
 
-use crate::base::message_store::MessageStore;
-use crate::ha::ha_client::HAClient;
-use crate::ha::ha_connection::HAConnection;
+use crate::ha::general_ha_client::GeneralHAClient;
+use crate::ha::general_ha_connection::GeneralHAConnection;
 use crate::ha::ha_connection_state_notification_request::HAConnectionStateNotificationRequest;
 use crate::ha::wait_notify_object::WaitNotifyObject;
 use crate::log_file::flush_manager_impl::group_commit_request::GroupCommitRequest;
+use crate::message_store::local_file_message_store::LocalFileMessageStore;
 use crate::store_error::HAResult;
 
 #[trait_variant::make(HAService: Send)]
@@ -40,7 +40,7 @@ pub trait RocketHAService: Sync {
     ///
     /// # Returns
     /// IO Result indicating success or failure
-    fn init<MS: MessageStore>(&mut self, message_store: ArcMut<MS>) -> HAResult<()>;
+    fn init(&mut self, message_store: ArcMut<LocalFileMessageStore>) -> HAResult<()>;
 
     /// Start the HA service
     ///
@@ -145,13 +145,13 @@ pub trait RocketHAService: Sync {
     ///
     /// # Returns
     /// List of HA connections
-    fn get_connection_list<CN: HAConnection>(&self) -> Vec<Arc<CN>>;
+    fn get_connection_list(&self) -> Vec<Arc<GeneralHAConnection>>;
 
     /// Get the HA client
     ///
     /// # Returns
-    /// Reference to the HA client
-    fn get_ha_client<CL: HAClient>(&self) -> Arc<CL>;
+    /// The HA client, `None` if this node is not a slave
+    fn get_ha_client(&self) -> Option<Arc<GeneralHAClient>>;
 
     /// Get the maximum offset across all slaves
     ///
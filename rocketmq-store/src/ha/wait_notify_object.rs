@@ -14,5 +14,94 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
 
-pub(crate) struct WaitNotifyObject;
+use tokio::sync::Notify;
+
+/// Wakes up the HA transfer tasks when new data is appended to the commit log.
+///
+/// A wakeup issued while nobody is waiting is remembered, so the next call to
+/// [`WaitNotifyObject::wait_for_running`] returns immediately instead of sleeping
+/// for the whole interval.
+#[derive(Default)]
+pub struct WaitNotifyObject {
+    has_notified: AtomicBool,
+    notify: Notify,
+}
+
+impl WaitNotifyObject {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wakes up one waiting task.
+    pub(crate) fn wakeup(&self) {
+        self.has_notified.store(true, Ordering::Release);
+        self.notify.notify_one();
+    }
+
+    /// Wakes up all waiting tasks.
+    pub(crate) fn wakeup_all(&self) {
+        self.has_notified.store(true, Ordering::Release);
+        self.notify.notify_waiters();
+    }
+
+    /// Waits until woken up or until `interval_millis` has elapsed.
+    pub(crate) async fn wait_for_running(&self, interval_millis: u64) {
+        if self
+            .has_notified
+            .compare_exchange(true, false, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+        {
+            return;
+        }
+        let _ = tokio::time::timeout(
+            Duration::from_millis(interval_millis),
+            self.notify.notified(),
+        )
+        .await;
+        self.has_notified.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn wait_for_running_returns_immediately_after_wakeup() {
+        let wait_notify_object = WaitNotifyObject::new();
+        wait_notify_object.wakeup();
+        let begin = Instant::now();
+        wait_notify_object.wait_for_running(5_000).await;
+        assert!(begin.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn wait_for_running_times_out_without_wakeup() {
+        let wait_notify_object = WaitNotifyObject::new();
+        let begin = Instant::now();
+        wait_notify_object.wait_for_running(20).await;
+        assert!(begin.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[tokio::test]
+    async fn wakeup_all_releases_waiting_task() {
+        let wait_notify_object = Arc::new(WaitNotifyObject::new());
+        let waiter = wait_notify_object.clone();
+        let handle = tokio::spawn(async move {
+            let begin = Instant::now();
+            waiter.wait_for_running(5_000).await;
+            begin.elapsed()
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        wait_notify_object.wakeup_all();
+        let elapsed = handle.await.unwrap();
+        assert!(elapsed < Duration::from_secs(1));
+    }
+}
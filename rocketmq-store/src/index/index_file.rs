@@ -28,8 +28,8 @@ use crate::index::index_header::INDEX_HEADER_SIZE;
 use crate::log_file::mapped_file::default_mapped_file_impl::DefaultMappedFile;
 use crate::log_file::mapped_file::MappedFile;
 
-const HASH_SLOT_SIZE: usize = 4;
-const INDEX_SIZE: usize = 20;
+pub const HASH_SLOT_SIZE: usize = 4;
+pub const INDEX_SIZE: usize = 20;
 const INVALID_INDEX: i32 = 0;
 
 /// Each index's store unit. Format:
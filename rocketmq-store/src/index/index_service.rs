@@ -131,7 +131,9 @@ impl IndexService {
             return;
         }
         let mut files = Vec::new();
-        for index_file in index_file_list_lock.iter() {
+        // the last file is still being written, it is always kept
+        let candidates = index_file_list_lock.len() - 1;
+        for index_file in index_file_list_lock.iter().take(candidates) {
             if (index_file.get_end_phy_offset() as u64) < offset {
                 files.push(index_file.clone());
             } else {
@@ -381,3 +383,60 @@ impl IndexService {
 fn build_key(topic: &str, key: &str) -> String {
     format!("{topic}#{key}")
 }
+
+#[cfg(test)]
+mod tests {
+    use cheetah_string::CheetahString;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn delete_expired_file_keeps_files_with_live_offsets_and_the_last_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let message_store_config = Arc::new(MessageStoreConfig {
+            store_path_root_dir: temp_dir.path().to_string_lossy().into_owned().into(),
+            max_hash_slot_num: 16,
+            // the count starts at one, so every index file holds three keys
+            max_index_num: 4,
+            ..MessageStoreConfig::default()
+        });
+        let store_checkpoint =
+            Arc::new(StoreCheckpoint::new(temp_dir.path().join("checkpoint")).unwrap());
+        let index_service = IndexService::new(
+            message_store_config,
+            store_checkpoint,
+            Arc::new(RunningFlags::new()),
+        );
+        for i in 0..9 {
+            index_service.build_index(&DispatchRequest {
+                topic: CheetahString::from_static_str("IndexTopic"),
+                keys: CheetahString::from_string(format!("k{i}")),
+                commit_log_offset: i * 100,
+                store_timestamp: 1000 + i,
+                ..DispatchRequest::default()
+            });
+            // index files are named after their creation time
+            thread::sleep(Duration::from_millis(2));
+        }
+        let end_phy_offsets = |index_service: &IndexService| {
+            index_service
+                .index_file_list
+                .read()
+                .iter()
+                .map(|index_file| index_file.get_end_phy_offset())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(end_phy_offsets(&index_service), vec![200, 500, 800]);
+
+        index_service.delete_expired_file(500);
+        assert_eq!(end_phy_offsets(&index_service), vec![500, 800]);
+        index_service.delete_expired_file(501);
+        assert_eq!(end_phy_offsets(&index_service), vec![800]);
+        index_service.delete_expired_file(10_000);
+        assert_eq!(end_phy_offsets(&index_service), vec![800]);
+        assert_eq!(
+            fs::read_dir(temp_dir.path().join("index")).unwrap().count(),
+            1
+        );
+    }
+}
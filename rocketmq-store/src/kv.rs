@@ -15,5 +15,7 @@
  * limitations under the License.
  */
 
+pub(crate) mod compaction_log;
 pub(crate) mod compaction_service;
 pub(crate) mod compaction_store;
+pub(crate) mod sparse_consume_queue;
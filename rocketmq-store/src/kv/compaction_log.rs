@@ -0,0 +1,890 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use bytes::Buf;
+use bytes::BufMut;
+use bytes::Bytes;
+use bytes::BytesMut;
+use cheetah_string::CheetahString;
+use parking_lot::Mutex;
+use rocketmq_common::common::message::message_decoder;
+use rocketmq_common::common::message::MessageTrait;
+use rocketmq_common::common::message::MESSAGE_MAGIC_CODE_V2;
+use tracing::error;
+use tracing::info;
+use tracing::warn;
+
+use crate::base::get_message_result::GetMessageResult;
+use crate::base::message_status_enum::GetMessageStatus;
+use crate::base::select_result::SelectMappedBufferResult;
+use crate::config::message_store_config::MessageStoreConfig;
+use crate::consume_queue::mapped_file_queue::MappedFileQueue;
+use crate::kv::sparse_consume_queue::flush_all;
+use crate::kv::sparse_consume_queue::SparseConsumeQueue;
+use crate::kv::sparse_consume_queue::SparseCqUnit;
+use crate::log_file::commit_log::BLANK_MAGIC_CODE;
+use crate::log_file::commit_log::MESSAGE_MAGIC_CODE;
+use crate::log_file::mapped_file::default_mapped_file_impl::DefaultMappedFile;
+use crate::log_file::mapped_file::MappedFile;
+use crate::store_path_config_helper::get_store_path_compaction_consume_queue;
+use crate::store_path_config_helper::get_store_path_compaction_log;
+
+/// Every file keeps room for a blank record marking its end.
+const END_FILE_MIN_BLANK_LENGTH: usize = 4 + 4;
+/// Position of the queue offset in a stored message: TOTALSIZE, MAGICCODE, BODYCRC, QUEUEID and
+/// FLAG come before it.
+const QUEUE_OFFSET_POSITION: usize = 4 + 4 + 4 + 4 + 4;
+pub(crate) const COMPACTING_SUFFIX: &str = ".compacting";
+/// Marks the compacting directories of a log as complete.
+pub(crate) const COMPACTED_SUFFIX: &str = ".compacted";
+
+/// The messages of one compacted queue.
+///
+/// Messages of the queue are appended here as they are dispatched, compaction rewrites the log
+/// keeping only the latest message of every key, messages without keys are always kept.
+pub(crate) struct CompactionLog {
+    topic: CheetahString,
+    queue_id: i32,
+    log_path: String,
+    cq_path: String,
+    mapped_file_size: u64,
+    cq_mapped_file_size: u64,
+    files: Mutex<CompactionFiles>,
+    /// Keeps two compactions of the log from running at once.
+    compacting: Mutex<()>,
+}
+
+struct CompactionFiles {
+    log: MappedFileQueue,
+    cq: SparseConsumeQueue,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LogRecord {
+    pos: i64,
+    size: i32,
+    queue_offset: i64,
+}
+
+/// The messages of the log from some offset on, as they were when the snapshot was taken.
+/// Messages are only appended past `end_offset`, so the snapshot can be read without the files
+/// lock.
+struct LogSnapshot {
+    mapped_files: Vec<Arc<DefaultMappedFile>>,
+    file_size: usize,
+    records: Vec<LogRecord>,
+    end_offset: i64,
+}
+
+impl LogSnapshot {
+    fn take(log: &MappedFileQueue, from: i64) -> Self {
+        let mapped_files = log.get_mapped_files().read().clone();
+        let file_size = log.mapped_file_size as usize;
+        let (records, end_offset) = scan_log(&mapped_files, file_size, from);
+        Self {
+            mapped_files,
+            file_size,
+            records,
+            end_offset,
+        }
+    }
+
+    fn read_bytes(&self, pos: i64, size: i32) -> Option<Bytes> {
+        let first_offset = self.mapped_files.first()?.get_file_from_offset() as i64;
+        let index = (pos - first_offset) / self.file_size as i64;
+        let mapped_file = self.mapped_files.get(usize::try_from(index).ok()?)?;
+        let position = pos - mapped_file.get_file_from_offset() as i64;
+        mapped_file.get_bytes(position as usize, size as usize)
+    }
+}
+
+impl CompactionLog {
+    pub fn new(
+        message_store_config: &MessageStoreConfig,
+        topic: CheetahString,
+        queue_id: i32,
+    ) -> Self {
+        let root_dir = message_store_config.store_path_root_dir.as_str();
+        let log_path = PathBuf::from(get_store_path_compaction_log(root_dir))
+            .join(topic.as_str())
+            .join(queue_id.to_string())
+            .to_string_lossy()
+            .into_owned();
+        let cq_path = PathBuf::from(get_store_path_compaction_consume_queue(root_dir))
+            .join(topic.as_str())
+            .join(queue_id.to_string())
+            .to_string_lossy()
+            .into_owned();
+        let mapped_file_size = message_store_config.compaction_mapped_file_size as u64;
+        let cq_mapped_file_size = message_store_config.compaction_cq_mapped_file_size as u64;
+        Self {
+            files: Mutex::new(CompactionFiles {
+                log: MappedFileQueue::new(log_path.clone(), mapped_file_size, None),
+                cq: SparseConsumeQueue::new(cq_path.clone(), cq_mapped_file_size),
+            }),
+            compacting: Mutex::new(()),
+            topic,
+            queue_id,
+            log_path,
+            cq_path,
+            mapped_file_size,
+            cq_mapped_file_size,
+        }
+    }
+
+    /// Loads the log and its consume queue, the consume queue is rebuilt from the log when the
+    /// two disagree.
+    pub fn load(&self) -> bool {
+        self.recover_compaction();
+        let mut files = self.files.lock();
+        *files = match self.open_files() {
+            Some(files) => files,
+            None => return false,
+        };
+        true
+    }
+
+    fn open_files(&self) -> Option<CompactionFiles> {
+        let mut log = MappedFileQueue::new(self.log_path.clone(), self.mapped_file_size, None);
+        let mut cq = SparseConsumeQueue::new(self.cq_path.clone(), self.cq_mapped_file_size);
+        if !log.load() || !cq.load() {
+            error!(
+                "load compaction log of {}-{} failed",
+                self.topic, self.queue_id
+            );
+            return None;
+        }
+        let LogSnapshot {
+            records,
+            end_offset,
+            ..
+        } = LogSnapshot::take(&log, 0);
+        log.set_flushed_where(end_offset);
+        log.set_committed_where(end_offset);
+        log.truncate_dirty_files(end_offset);
+        cq.recover();
+
+        let in_sync = cq.len() == records.len() as i64
+            && records.last().map(|record| record.queue_offset)
+                == cq.last().map(|unit| unit.queue_offset);
+        if !in_sync {
+            warn!(
+                "compaction consume queue of {}-{} does not match the log, rebuild it",
+                self.topic, self.queue_id
+            );
+            cq.destroy();
+            cq = SparseConsumeQueue::new(self.cq_path.clone(), self.cq_mapped_file_size);
+            for record in &records {
+                if !cq.put(record.into()) {
+                    return None;
+                }
+            }
+            cq.flush();
+        }
+        info!(
+            "load compaction log of {}-{}, {} messages, log offset {}",
+            self.topic,
+            self.queue_id,
+            records.len(),
+            end_offset
+        );
+        Some(CompactionFiles { log, cq })
+    }
+
+    /// Appends a message dispatched from the commit log. Messages at or below the last queue
+    /// offset are already here, they come back when the commit log is dispatched again after a
+    /// restart.
+    pub fn put_message(&self, message: &[u8], queue_offset: i64) -> bool {
+        let mut files = self.files.lock();
+        if files
+            .cq
+            .last()
+            .is_some_and(|unit| unit.queue_offset >= queue_offset)
+        {
+            return true;
+        }
+        let CompactionFiles { log, cq } = &mut *files;
+        append_record(log, cq, message, queue_offset)
+    }
+
+    pub fn get_min_offset(&self) -> i64 {
+        self.files
+            .lock()
+            .cq
+            .first()
+            .map_or(0, |unit| unit.queue_offset)
+    }
+
+    pub fn get_max_offset(&self) -> i64 {
+        self.files
+            .lock()
+            .cq
+            .last()
+            .map_or(0, |unit| unit.queue_offset + 1)
+    }
+
+    pub fn get_message(
+        &self,
+        offset: i64,
+        max_msg_nums: i32,
+        max_total_msg_size: i32,
+    ) -> GetMessageResult {
+        let files = self.files.lock();
+        let min_offset = files.cq.first().map_or(0, |unit| unit.queue_offset);
+        let max_offset = files.cq.last().map_or(0, |unit| unit.queue_offset + 1);
+        let mut result = GetMessageResult::new();
+        result.set_min_offset(min_offset);
+        result.set_max_offset(max_offset);
+
+        let (status, next_begin_offset) = if max_offset == 0 {
+            (GetMessageStatus::NoMessageInQueue, 0)
+        } else if offset < min_offset {
+            (GetMessageStatus::OffsetTooSmall, min_offset)
+        } else if offset == max_offset {
+            (GetMessageStatus::OffsetOverflowOne, offset)
+        } else if offset > max_offset {
+            (GetMessageStatus::OffsetOverflowBadly, max_offset)
+        } else {
+            let mut next_begin_offset = offset;
+            let mut index = files.cq.lower_bound(offset);
+            while let Some(unit) = files.cq.get(index) {
+                if result.message_count() >= max_msg_nums
+                    || (result.buffer_total_size() > 0
+                        && result.buffer_total_size() + unit.size > max_total_msg_size)
+                {
+                    break;
+                }
+                let Some(bytes) = read_bytes(&files.log, unit.pos, unit.size) else {
+                    warn!(
+                        "read compaction log of {}-{} at {} failed",
+                        self.topic, self.queue_id, unit.pos
+                    );
+                    break;
+                };
+                result.add_message(
+                    SelectMappedBufferResult {
+                        start_offset: unit.pos as u64,
+                        size: unit.size,
+                        bytes: Some(bytes),
+                        ..Default::default()
+                    },
+                    unit.queue_offset as u64,
+                    1,
+                );
+                next_begin_offset = unit.queue_offset + 1;
+                index += 1;
+            }
+            if result.message_count() > 0 {
+                (GetMessageStatus::Found, next_begin_offset)
+            } else {
+                (GetMessageStatus::OffsetFoundNull, next_begin_offset)
+            }
+        };
+        result.set_status(Some(status));
+        result.set_next_begin_offset(next_begin_offset);
+        result
+    }
+
+    /// Rewrites the log keeping the latest message of every key, returns how many messages were
+    /// removed. The files lock is only held to take the snapshot and to switch the rewritten
+    /// files in, puts go on while the retained messages are copied.
+    pub fn compact(&self) -> usize {
+        let _compacting = self.compacting.lock();
+        let snapshot = LogSnapshot::take(&self.files.lock().log, 0);
+        let (retained, removed) = self.retained_records(&snapshot);
+        if removed == 0 {
+            return 0;
+        }
+        let Some(compacting) = self.write_compacting(&snapshot, &retained) else {
+            return 0;
+        };
+        let snapshot_end = snapshot.end_offset;
+        drop(snapshot);
+        if !self.switch_to_compacting(compacting, snapshot_end) {
+            return 0;
+        }
+        info!(
+            "compact {}-{} done, {} messages removed",
+            self.topic, self.queue_id, removed
+        );
+        removed
+    }
+
+    /// Returns the records kept by a compaction and how many are removed.
+    fn retained_records(&self, snapshot: &LogSnapshot) -> (Vec<LogRecord>, usize) {
+        let records = &snapshot.records;
+        let mut keys = Vec::with_capacity(records.len());
+        let mut latest = HashMap::new();
+        for record in records {
+            let key = snapshot
+                .read_bytes(record.pos, record.size)
+                .and_then(message_key);
+            if let Some(key) = key.as_ref() {
+                latest.insert(key.clone(), record.queue_offset);
+            }
+            keys.push(key);
+        }
+        let retained: Vec<LogRecord> = records
+            .iter()
+            .zip(keys.iter())
+            .filter(|(record, key)| {
+                key.as_ref()
+                    .is_none_or(|key| latest.get(key) == Some(&record.queue_offset))
+            })
+            .map(|(record, _)| *record)
+            .collect();
+        let removed = records.len() - retained.len();
+        (retained, removed)
+    }
+
+    /// Writes the retained messages to the compacting directories next to the live ones.
+    fn write_compacting(
+        &self,
+        snapshot: &LogSnapshot,
+        retained: &[LogRecord],
+    ) -> Option<CompactionFiles> {
+        self.drop_compacting();
+        let mut compacting = CompactionFiles {
+            log: MappedFileQueue::new(compacting_path(&self.log_path), self.mapped_file_size, None),
+            cq: SparseConsumeQueue::new(compacting_path(&self.cq_path), self.cq_mapped_file_size),
+        };
+        if !self.append_records(&mut compacting, snapshot, retained) {
+            compacting.log.destroy();
+            compacting.cq.destroy();
+            return None;
+        }
+        flush_all(&compacting.log);
+        compacting.cq.flush();
+        Some(compacting)
+    }
+
+    /// Carries the messages put since the snapshot ending at `snapshot_end` over to the
+    /// compacting files and switches them in place of the live ones.
+    fn switch_to_compacting(&self, mut compacting: CompactionFiles, snapshot_end: i64) -> bool {
+        let mut files = self.files.lock();
+        let tail = LogSnapshot::take(&files.log, snapshot_end);
+        if !self.append_records(&mut compacting, &tail, &tail.records) {
+            drop(compacting);
+            self.drop_compacting();
+            return false;
+        }
+        flush_all(&compacting.log);
+        compacting.cq.flush();
+        drop(tail);
+        drop(compacting);
+        // Once the marker is written the compacting directories are complete, a crash from here
+        // on is finished by `recover_compaction` on load.
+        if let Err(e) = fs::File::create(self.compacted_marker()).and_then(|file| file.sync_all()) {
+            error!(
+                "mark compaction of {}-{} done failed: {}",
+                self.topic, self.queue_id, e
+            );
+            self.drop_compacting();
+            return false;
+        }
+        files.cq.destroy();
+        files.log.destroy();
+        self.switch_compacted();
+        match self.open_files() {
+            Some(reopened) => *files = reopened,
+            None => error!(
+                "reopen compaction log of {}-{} failed",
+                self.topic, self.queue_id
+            ),
+        }
+        true
+    }
+
+    fn append_records(
+        &self,
+        target: &mut CompactionFiles,
+        source: &LogSnapshot,
+        records: &[LogRecord],
+    ) -> bool {
+        for record in records {
+            let appended = source
+                .read_bytes(record.pos, record.size)
+                .is_some_and(|message| {
+                    append_record(
+                        &mut target.log,
+                        &mut target.cq,
+                        &message,
+                        record.queue_offset,
+                    )
+                });
+            if !appended {
+                error!(
+                    "compact {}-{} failed at queue offset {}",
+                    self.topic, self.queue_id, record.queue_offset
+                );
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Finishes or drops a compaction interrupted by a crash. Without the marker the compacting
+    /// directories may be incomplete and the live ones are untouched, with it the compacting
+    /// directories are complete and replace whatever is left of the live ones.
+    fn recover_compaction(&self) {
+        if Path::new(&self.compacted_marker()).exists() {
+            warn!(
+                "finish interrupted compaction of {}-{}",
+                self.topic, self.queue_id
+            );
+            self.switch_compacted();
+        } else {
+            self.drop_compacting();
+        }
+    }
+
+    /// Moves the compacting directories in place of the live ones and removes the marker. The
+    /// consume queue goes first, a crash in between leaves the old log with the new consume
+    /// queue, which is then rebuilt from the log if the switch is not finished on load.
+    fn switch_compacted(&self) {
+        switch_compacted_dir(&self.cq_path);
+        switch_compacted_dir(&self.log_path);
+        if let Err(e) = fs::remove_file(self.compacted_marker()) {
+            error!(
+                "remove compaction marker of {}-{} failed: {}",
+                self.topic, self.queue_id, e
+            );
+        }
+    }
+
+    fn drop_compacting(&self) {
+        let _ = fs::remove_dir_all(compacting_path(&self.log_path));
+        let _ = fs::remove_dir_all(compacting_path(&self.cq_path));
+    }
+
+    fn compacted_marker(&self) -> String {
+        format!("{}{}", self.log_path, COMPACTED_SUFFIX)
+    }
+
+    pub fn flush(&self) {
+        let files = self.files.lock();
+        flush_all(&files.log);
+        files.cq.flush();
+    }
+}
+
+impl From<&LogRecord> for SparseCqUnit {
+    fn from(record: &LogRecord) -> Self {
+        SparseCqUnit {
+            pos: record.pos,
+            size: record.size,
+            queue_offset: record.queue_offset,
+        }
+    }
+}
+
+fn compacting_path(path: &str) -> String {
+    format!("{path}{COMPACTING_SUFFIX}")
+}
+
+/// Replaces `path` with its compacting directory, nothing is left to do when the compacting
+/// directory is already gone.
+fn switch_compacted_dir(path: &str) {
+    let compacting = compacting_path(path);
+    if !Path::new(&compacting).exists() {
+        return;
+    }
+    if Path::new(path).exists() {
+        if let Err(e) = fs::remove_dir_all(path) {
+            error!("remove {} failed: {}", path, e);
+            return;
+        }
+    }
+    if let Err(e) = fs::rename(&compacting, path) {
+        error!("move {} to {} failed: {}", compacting, path, e);
+    }
+}
+
+fn append_record(
+    log: &mut MappedFileQueue,
+    cq: &mut SparseConsumeQueue,
+    message: &[u8],
+    queue_offset: i64,
+) -> bool {
+    let file_size = log.mapped_file_size as usize;
+    if message.len() + END_FILE_MIN_BLANK_LENGTH > file_size {
+        error!(
+            "message of {} bytes does not fit in a compaction log file of {} bytes",
+            message.len(),
+            file_size
+        );
+        return false;
+    }
+    let Some(mut mapped_file) = log.get_last_mapped_file_mut_start_offset(0, true) else {
+        return false;
+    };
+    let wrote_position = mapped_file.get_wrote_position() as usize;
+    if wrote_position + message.len() + END_FILE_MIN_BLANK_LENGTH > file_size {
+        let mut blank = BytesMut::zeroed(file_size - wrote_position);
+        let mut header = &mut blank[..];
+        header.put_i32((file_size - wrote_position) as i32);
+        header.put_i32(BLANK_MAGIC_CODE);
+        if !mapped_file.append_message_bytes(&blank) {
+            return false;
+        }
+        mapped_file = match log.get_last_mapped_file_mut_start_offset(0, true) {
+            Some(mapped_file) => mapped_file,
+            None => return false,
+        };
+    }
+    let pos = mapped_file.get_file_from_offset() as i64 + mapped_file.get_wrote_position() as i64;
+    mapped_file.append_message_bytes(message)
+        && cq.put(SparseCqUnit {
+            pos,
+            size: message.len() as i32,
+            queue_offset,
+        })
+}
+
+/// Walks the log from offset `from`, returns the messages found and the offset right after the
+/// last of them.
+fn scan_log(
+    mapped_files: &[Arc<DefaultMappedFile>],
+    file_size: usize,
+    from: i64,
+) -> (Vec<LogRecord>, i64) {
+    let mut records = Vec::new();
+    let mut end_offset = from;
+    'files: for mapped_file in mapped_files {
+        let file_from_offset = mapped_file.get_file_from_offset() as i64;
+        if file_from_offset + file_size as i64 <= from {
+            continue;
+        }
+        let mut position = (from - file_from_offset).max(0) as usize;
+        end_offset = file_from_offset + position as i64;
+        while position + END_FILE_MIN_BLANK_LENGTH <= file_size {
+            let Some(mut header) = mapped_file.get_bytes(position, END_FILE_MIN_BLANK_LENGTH)
+            else {
+                break 'files;
+            };
+            let size = header.get_i32();
+            let magic = header.get_i32();
+            if magic == BLANK_MAGIC_CODE {
+                end_offset = file_from_offset + file_size as i64;
+                continue 'files;
+            }
+            let valid = (magic == MESSAGE_MAGIC_CODE || magic == MESSAGE_MAGIC_CODE_V2)
+                && size as usize > QUEUE_OFFSET_POSITION + 8
+                && position + size as usize <= file_size;
+            if !valid {
+                break 'files;
+            }
+            let Some(mut queue_offset) = mapped_file.get_bytes(position + QUEUE_OFFSET_POSITION, 8)
+            else {
+                break 'files;
+            };
+            records.push(LogRecord {
+                pos: file_from_offset + position as i64,
+                size,
+                queue_offset: queue_offset.get_i64(),
+            });
+            position += size as usize;
+            end_offset = file_from_offset + position as i64;
+        }
+    }
+    (records, end_offset)
+}
+
+fn read_bytes(log: &MappedFileQueue, pos: i64, size: i32) -> Option<Bytes> {
+    let mapped_file = log.find_mapped_file_by_offset(pos, false)?;
+    let position = pos - mapped_file.get_file_from_offset() as i64;
+    mapped_file.get_bytes(position as usize, size as usize)
+}
+
+fn message_key(mut message: Bytes) -> Option<CheetahString> {
+    message_decoder::decode(&mut message, true, false, false, false, false)?
+        .get_keys()
+        .filter(|keys| !keys.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rocketmq_common::common::message::message_ext_broker_inner::MessageExtBrokerInner;
+    use rocketmq_common::common::message::message_single::Message;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::kv::compaction_store::CompactionStore;
+    use crate::message_encoder::message_ext_encoder::MessageExtEncoder;
+
+    fn store_config(dir: &TempDir) -> MessageStoreConfig {
+        MessageStoreConfig {
+            store_path_root_dir: dir.path().to_string_lossy().into_owned().into(),
+            compaction_mapped_file_size: 1024,
+            compaction_cq_mapped_file_size: 5 * 20,
+            ..Default::default()
+        }
+    }
+
+    fn encoded(queue_offset: i64, key: Option<&str>) -> Bytes {
+        let mut message = Message::new("compact-topic", format!("body-{queue_offset}").as_bytes());
+        if let Some(key) = key {
+            message.set_keys(key.into());
+        }
+        let mut msg_inner = MessageExtBrokerInner {
+            properties_string: message_decoder::message_properties_to_string(
+                message.get_properties(),
+            ),
+            ..Default::default()
+        };
+        msg_inner.message_ext_inner.set_message_inner(message);
+        msg_inner.message_ext_inner.set_queue_offset(queue_offset);
+        let mut encoder = MessageExtEncoder::new(Arc::new(MessageStoreConfig::default()));
+        encoder.encode(&msg_inner);
+        encoder.get_encoder_buffer()
+    }
+
+    fn queue_offsets(result: &GetMessageResult) -> Vec<u64> {
+        result.message_queue_offset().clone()
+    }
+
+    #[test]
+    fn compaction_keeps_latest_message_per_key() {
+        let dir = TempDir::new().unwrap();
+        let config = store_config(&dir);
+        let log = CompactionLog::new(&config, "compact-topic".into(), 0);
+        assert!(log.load());
+        let keys = [
+            Some("k1"),
+            Some("k2"),
+            Some("k1"),
+            None,
+            Some("k2"),
+            Some("k1"),
+        ];
+        for (queue_offset, key) in keys.iter().enumerate() {
+            assert!(log.put_message(&encoded(queue_offset as i64, *key), queue_offset as i64));
+        }
+        let result = log.get_message(0, 32, 1024 * 1024);
+        assert_eq!(result.status(), Some(GetMessageStatus::Found));
+        assert_eq!(queue_offsets(&result), vec![0, 1, 2, 3, 4, 5]);
+
+        assert_eq!(log.compact(), 3);
+        assert_eq!(log.get_min_offset(), 3);
+        assert_eq!(log.get_max_offset(), 6);
+        let result = log.get_message(0, 32, 1024 * 1024);
+        assert_eq!(result.status(), Some(GetMessageStatus::OffsetTooSmall));
+        assert_eq!(result.next_begin_offset(), 3);
+        let result = log.get_message(3, 32, 1024 * 1024);
+        assert_eq!(queue_offsets(&result), vec![3, 4, 5]);
+        assert_eq!(result.next_begin_offset(), 6);
+        assert_eq!(log.compact(), 0);
+    }
+
+    #[test]
+    fn reload_restores_log_and_skips_redispatched_messages() {
+        let dir = TempDir::new().unwrap();
+        let config = store_config(&dir);
+        {
+            let log = CompactionLog::new(&config, "compact-topic".into(), 1);
+            assert!(log.load());
+            // enough messages to roll both the log and the consume queue files
+            for queue_offset in 0..20 {
+                let key = format!("k{}", queue_offset % 4);
+                assert!(log.put_message(&encoded(queue_offset, Some(&key)), queue_offset));
+            }
+            assert_eq!(log.compact(), 16);
+            assert!(log.put_message(&encoded(20, Some("k0")), 20));
+            log.flush();
+        }
+        let log = CompactionLog::new(&config, "compact-topic".into(), 1);
+        assert!(log.load());
+        assert!(log.put_message(&encoded(19, Some("k3")), 19));
+        let result = log.get_message(0, 32, 1024 * 1024);
+        assert_eq!(result.status(), Some(GetMessageStatus::OffsetTooSmall));
+        let result = log.get_message(16, 32, 1024 * 1024);
+        assert_eq!(queue_offsets(&result), vec![16, 17, 18, 19, 20]);
+        let result = log.get_message(17, 2, 1024 * 1024);
+        assert_eq!(queue_offsets(&result), vec![17, 18]);
+        assert_eq!(result.next_begin_offset(), 19);
+    }
+
+    #[test]
+    fn messages_put_during_compaction_are_carried_over() {
+        let dir = TempDir::new().unwrap();
+        let config = store_config(&dir);
+        let log = CompactionLog::new(&config, "compact-topic".into(), 3);
+        assert!(log.load());
+        for queue_offset in 0..4 {
+            assert!(log.put_message(&encoded(queue_offset, Some("k")), queue_offset));
+        }
+        let snapshot = LogSnapshot::take(&log.files.lock().log, 0);
+        let (retained, removed) = log.retained_records(&snapshot);
+        assert_eq!(removed, 3);
+        let compacting = log.write_compacting(&snapshot, &retained).unwrap();
+        // the files lock is free while the compacting files are written
+        assert!(log.put_message(&encoded(4, Some("k")), 4));
+        assert!(log.put_message(&encoded(5, None), 5));
+        let snapshot_end = snapshot.end_offset;
+        drop(snapshot);
+        assert!(log.switch_to_compacting(compacting, snapshot_end));
+        assert_eq!(log.get_min_offset(), 3);
+        let result = log.get_message(3, 32, 1024 * 1024);
+        assert_eq!(queue_offsets(&result), vec![3, 4, 5]);
+
+        assert_eq!(log.compact(), 1);
+        let result = log.get_message(4, 32, 1024 * 1024);
+        assert_eq!(queue_offsets(&result), vec![4, 5]);
+    }
+
+    /// Fills a log, writes its compacting directories and lets `crash` stop the switch at some
+    /// point, then loads the log again.
+    fn load_after_interrupted_compaction(
+        config: &MessageStoreConfig,
+        queue_id: i32,
+        crash: impl FnOnce(&CompactionLog),
+    ) -> CompactionLog {
+        {
+            let log = CompactionLog::new(config, "compact-topic".into(), queue_id);
+            assert!(log.load());
+            let keys = [
+                Some("k1"),
+                Some("k2"),
+                Some("k1"),
+                None,
+                Some("k2"),
+                Some("k1"),
+            ];
+            for (queue_offset, key) in keys.iter().enumerate() {
+                assert!(log.put_message(&encoded(queue_offset as i64, *key), queue_offset as i64));
+            }
+            log.flush();
+            let snapshot = LogSnapshot::take(&log.files.lock().log, 0);
+            let (retained, removed) = log.retained_records(&snapshot);
+            assert_eq!(removed, 3);
+            assert!(log.write_compacting(&snapshot, &retained).is_some());
+            drop(snapshot);
+            crash(&log);
+        }
+        let log = CompactionLog::new(config, "compact-topic".into(), queue_id);
+        assert!(log.load());
+        assert!(!Path::new(&log.compacted_marker()).exists());
+        assert!(!Path::new(&compacting_path(&log.log_path)).exists());
+        assert!(!Path::new(&compacting_path(&log.cq_path)).exists());
+        log
+    }
+
+    fn mark_compacted(log: &CompactionLog) {
+        fs::File::create(log.compacted_marker()).unwrap();
+    }
+
+    fn assert_compacted(log: &CompactionLog) {
+        assert_eq!(log.get_min_offset(), 3);
+        assert_eq!(log.get_max_offset(), 6);
+        let result = log.get_message(3, 32, 1024 * 1024);
+        assert_eq!(queue_offsets(&result), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn recover_drops_compaction_interrupted_before_marker() {
+        let dir = TempDir::new().unwrap();
+        let config = store_config(&dir);
+        let log = load_after_interrupted_compaction(&config, 0, |_| {});
+        assert_eq!(log.get_min_offset(), 0);
+        let result = log.get_message(0, 32, 1024 * 1024);
+        assert_eq!(queue_offsets(&result), vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn recover_switches_compaction_interrupted_after_marker() {
+        let dir = TempDir::new().unwrap();
+        let config = store_config(&dir);
+        let log = load_after_interrupted_compaction(&config, 0, mark_compacted);
+        assert_compacted(&log);
+    }
+
+    #[test]
+    fn recover_switches_log_when_only_consume_queue_was_switched() {
+        let dir = TempDir::new().unwrap();
+        let config = store_config(&dir);
+        let log = load_after_interrupted_compaction(&config, 0, |log| {
+            mark_compacted(log);
+            switch_compacted_dir(&log.cq_path);
+        });
+        assert_compacted(&log);
+    }
+
+    #[test]
+    fn recover_switches_log_removed_before_rename() {
+        let dir = TempDir::new().unwrap();
+        let config = store_config(&dir);
+        let log = load_after_interrupted_compaction(&config, 0, |log| {
+            mark_compacted(log);
+            switch_compacted_dir(&log.cq_path);
+            fs::remove_dir_all(&log.log_path).unwrap();
+        });
+        assert_compacted(&log);
+    }
+
+    #[test]
+    fn recover_removes_marker_left_after_switch() {
+        let dir = TempDir::new().unwrap();
+        let config = store_config(&dir);
+        let log = load_after_interrupted_compaction(&config, 0, |log| {
+            mark_compacted(log);
+            switch_compacted_dir(&log.cq_path);
+            switch_compacted_dir(&log.log_path);
+        });
+        assert_compacted(&log);
+    }
+
+    #[test]
+    fn compaction_store_loads_queue_left_only_with_compacting_dirs() {
+        let dir = TempDir::new().unwrap();
+        let config = store_config(&dir);
+        {
+            let log = CompactionLog::new(&config, "compact-topic".into(), 2);
+            assert!(log.load());
+            for queue_offset in 0..4 {
+                assert!(log.put_message(&encoded(queue_offset, Some("k")), queue_offset));
+            }
+            log.flush();
+            let snapshot = LogSnapshot::take(&log.files.lock().log, 0);
+            let (retained, _) = log.retained_records(&snapshot);
+            assert!(log.write_compacting(&snapshot, &retained).is_some());
+            drop(snapshot);
+            mark_compacted(&log);
+            fs::remove_dir_all(&log.cq_path).unwrap();
+            fs::remove_dir_all(&log.log_path).unwrap();
+        }
+        let store = CompactionStore::new(Arc::new(config));
+        assert!(store.load(true));
+        let result = store
+            .get_message(
+                &"group".into(),
+                &"compact-topic".into(),
+                2,
+                3,
+                32,
+                1024 * 1024,
+            )
+            .unwrap();
+        assert_eq!(queue_offsets(&result), vec![3]);
+    }
+}
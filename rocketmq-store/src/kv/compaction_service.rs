@@ -14,21 +14,298 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::collections::HashMap;
+use std::sync::atomic::AtomicI64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Buf;
+use cheetah_string::CheetahString;
+use rocketmq_common::common::attribute::cleanup_policy::CleanupPolicy;
+use rocketmq_common::common::config::TopicConfig;
+use rocketmq_common::common::message::message_decoder;
+use rocketmq_common::common::message::MessageTrait;
+use rocketmq_common::utils::file_utils;
+use rocketmq_common::CleanupPolicyUtils::get_delete_policy;
+use rocketmq_rust::ArcMut;
+use tokio::sync::mpsc;
+use tokio::sync::watch;
 use tracing::error;
+use tracing::info;
+use tracing::warn;
+
+use crate::base::commit_log_dispatcher::CommitLogDispatcher;
+use crate::base::dispatch_request::DispatchRequest;
+use crate::config::message_store_config::MessageStoreConfig;
+use crate::kv::compaction_store::CompactionStore;
+use crate::log_file::commit_log;
+use crate::log_file::commit_log::CommitLog;
+use crate::log_file::commit_log::BLANK_MAGIC_CODE;
+use crate::store_path_config_helper::get_store_path_compaction_position;
+
+/// Where a dispatched message of a compacted topic lives in the commit log.
+struct CompactionRequest {
+    topic: CheetahString,
+    queue_id: i32,
+    commit_log_offset: i64,
+    msg_size: i32,
+    consume_queue_offset: i64,
+}
 
-#[derive(Default, Clone)]
-pub struct CompactionService {}
+/// Copies the messages of compacted topics from the commit log into the compaction store and
+/// compacts the store periodically.
+///
+/// Requests are queued in memory, the commit log offset up to which they are handled is saved
+/// with the compaction store. On start the commit log is replayed from that offset, so requests
+/// lost on shutdown or crash are handled again.
+#[derive(Clone)]
+pub struct CompactionService {
+    message_store_config: Arc<MessageStoreConfig>,
+    compaction_store: Arc<CompactionStore>,
+    commit_log: ArcMut<CommitLog>,
+    topic_config_table: Arc<parking_lot::Mutex<HashMap<CheetahString, TopicConfig>>>,
+    request_tx: mpsc::UnboundedSender<CompactionRequest>,
+    request_rx: Arc<parking_lot::Mutex<Option<mpsc::UnboundedReceiver<CompactionRequest>>>>,
+    /// Commit log offset right after the last message handed to the compaction store.
+    put_offset: Arc<AtomicI64>,
+    shutdown_tx: Arc<watch::Sender<bool>>,
+}
 
 impl CompactionService {
+    pub fn new(
+        message_store_config: Arc<MessageStoreConfig>,
+        compaction_store: Arc<CompactionStore>,
+        commit_log: ArcMut<CommitLog>,
+        topic_config_table: Arc<parking_lot::Mutex<HashMap<CheetahString, TopicConfig>>>,
+    ) -> Self {
+        let (request_tx, request_rx) = mpsc::unbounded_channel();
+        let (shutdown_tx, _) = watch::channel(false);
+        Self {
+            message_store_config,
+            compaction_store,
+            commit_log,
+            topic_config_table,
+            request_tx,
+            request_rx: Arc::new(parking_lot::Mutex::new(Some(request_rx))),
+            put_offset: Arc::new(AtomicI64::new(0)),
+            shutdown_tx: Arc::new(shutdown_tx),
+        }
+    }
+
     pub fn load(&mut self, exit_ok: bool) -> bool {
-        error!(
-            "[unimplemented]load compaction service, exit ok: {}",
-            exit_ok
-        );
+        if !self.compaction_store.load(exit_ok) {
+            return false;
+        }
+        let position_path = self.position_path();
+        let position = match file_utils::file_to_string(&position_path) {
+            Ok(position) => position,
+            Err(e) => {
+                error!("read compaction position {} failed: {}", position_path, e);
+                return false;
+            }
+        };
+        if !position.is_empty() {
+            match position.trim().parse::<i64>() {
+                Ok(put_offset) => self.put_offset.store(put_offset, Ordering::Release),
+                Err(e) => warn!(
+                    "compaction position {} is invalid, replay the whole commit log: {}",
+                    position_path, e
+                ),
+            }
+        }
         true
     }
 
+    pub fn start(&self) {
+        let Some(mut request_rx) = self.request_rx.lock().take() else {
+            warn!("compaction service already started");
+            return;
+        };
+        let service = self.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+        tokio::spawn(async move {
+            let replay = service.clone();
+            if let Err(e) = tokio::task::spawn_blocking(move || replay.replay()).await {
+                error!("replay commit log for compaction failed: {}", e);
+            }
+            loop {
+                tokio::select! {
+                    request = request_rx.recv() => {
+                        let Some(request) = request else {
+                            break;
+                        };
+                        service.handle_request(request);
+                    }
+                    _ = shutdown_rx.changed() => break,
+                }
+            }
+        });
+
+        let service = self.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+        let period =
+            Duration::from_millis(self.message_store_config.compaction_schedule_internal as u64);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.tick().await;
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        let compaction = service.clone();
+                        let removed = tokio::task::spawn_blocking(move || {
+                            let removed = compaction.compaction_store.compact_all();
+                            compaction.persist_position();
+                            removed
+                        })
+                        .await
+                        .unwrap_or_default();
+                        if removed > 0 {
+                            info!("compaction removed {} messages", removed);
+                        }
+                    }
+                    _ = shutdown_rx.changed() => break,
+                }
+            }
+        });
+        info!("compaction service started");
+    }
+
+    pub fn put_request(&self, dispatch_request: &DispatchRequest) {
+        let _ = self.request_tx.send(CompactionRequest {
+            topic: dispatch_request.topic.clone(),
+            queue_id: dispatch_request.queue_id,
+            commit_log_offset: dispatch_request.commit_log_offset,
+            msg_size: dispatch_request.msg_size,
+            consume_queue_offset: dispatch_request.consume_queue_offset,
+        });
+    }
+
     pub fn shutdown(&self) {
-        error!("[unimplemented]shutdown compaction service");
+        self.shutdown_tx.send_replace(true);
+        self.persist_position();
+        info!("compaction service shutdown");
+    }
+
+    fn is_compacted_topic(&self, topic: &CheetahString) -> bool {
+        get_delete_policy(self.topic_config_table.lock().get(topic)) == CleanupPolicy::COMPACTION
+    }
+
+    fn handle_request(&self, request: CompactionRequest) {
+        // replayed from the commit log already
+        if request.commit_log_offset < self.put_offset.load(Ordering::Acquire) {
+            return;
+        }
+        let message = self
+            .commit_log
+            .get_message(request.commit_log_offset, request.msg_size)
+            .and_then(|result| result.get_bytes());
+        match message {
+            Some(message) => {
+                self.compaction_store.put_message(
+                    &request.topic,
+                    request.queue_id,
+                    &message,
+                    request.consume_queue_offset,
+                );
+            }
+            None => warn!(
+                "message of {}-{} at commit log offset {} is gone, skip compaction",
+                request.topic, request.queue_id, request.commit_log_offset
+            ),
+        }
+        self.put_offset.fetch_max(
+            request.commit_log_offset + request.msg_size as i64,
+            Ordering::AcqRel,
+        );
+    }
+
+    /// Hands the messages of compacted topics from the saved position to the end of the commit
+    /// log to the compaction store. Messages already in the store are skipped by it.
+    fn replay(&self) {
+        let mut offset = self
+            .put_offset
+            .load(Ordering::Acquire)
+            .max(self.commit_log.get_min_offset());
+        let from = offset;
+        while offset < self.commit_log.get_max_offset() && !*self.shutdown_tx.borrow() {
+            let Some(mut result) = self.commit_log.get_data(offset) else {
+                break;
+            };
+            let mut bytes = result.get_bytes().unwrap_or_default();
+            result.release();
+            let mut next_file = false;
+            while commit_log::starts_with_complete_record(&bytes) {
+                let mut header = &bytes[..8];
+                let size = header.get_i32();
+                if header.get_i32() == BLANK_MAGIC_CODE {
+                    next_file = true;
+                    break;
+                }
+                let message = bytes.split_to(size as usize);
+                let decoded =
+                    message_decoder::decode(&mut message.clone(), true, false, false, false, false);
+                if let Some(msg) = decoded {
+                    if self.is_compacted_topic(msg.get_topic()) {
+                        self.compaction_store.put_message(
+                            msg.get_topic(),
+                            msg.queue_id(),
+                            &message,
+                            msg.queue_offset(),
+                        );
+                    }
+                }
+                offset += size as i64;
+                self.put_offset.fetch_max(offset, Ordering::AcqRel);
+            }
+            if !next_file {
+                break;
+            }
+            offset = self.commit_log.roll_next_file(offset);
+        }
+        if offset > from {
+            info!(
+                "replay commit log for compaction from {} to {}",
+                from, offset
+            );
+        }
+    }
+
+    /// Saves the commit log offset up to which messages are in the compaction store, after
+    /// flushing them.
+    fn persist_position(&self) {
+        let put_offset = self.put_offset.load(Ordering::Acquire);
+        self.compaction_store.flush();
+        let position_path = self.position_path();
+        if let Err(e) = file_utils::string_to_file(&put_offset.to_string(), &position_path) {
+            error!("save compaction position {} failed: {}", position_path, e);
+        }
+    }
+
+    fn position_path(&self) -> String {
+        get_store_path_compaction_position(self.message_store_config.store_path_root_dir.as_str())
+    }
+}
+
+/// Hands the messages of topics with the [`CleanupPolicy::COMPACTION`] policy to the
+/// compaction service.
+pub struct CommitLogDispatcherCompaction {
+    compaction_service: CompactionService,
+}
+
+impl CommitLogDispatcherCompaction {
+    pub fn new(compaction_service: CompactionService) -> Self {
+        Self { compaction_service }
+    }
+}
+
+impl CommitLogDispatcher for CommitLogDispatcherCompaction {
+    fn dispatch(&self, dispatch_request: &mut DispatchRequest) {
+        if self
+            .compaction_service
+            .is_compacted_topic(&dispatch_request.topic)
+        {
+            self.compaction_service.put_request(dispatch_request);
+        }
     }
 }
@@ -14,20 +14,114 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Arc;
+
 use cheetah_string::CheetahString;
+use parking_lot::RwLock;
+use tracing::error;
+use tracing::info;
 
 use crate::base::get_message_result::GetMessageResult;
+use crate::base::message_status_enum::GetMessageStatus;
+use crate::config::message_store_config::MessageStoreConfig;
+use crate::kv::compaction_log::CompactionLog;
+use crate::kv::compaction_log::COMPACTED_SUFFIX;
+use crate::kv::compaction_log::COMPACTING_SUFFIX;
+use crate::store_path_config_helper::get_store_path_compaction_log;
 
-pub struct CompactionStore;
+/// Holds the compaction logs of the compacted topics and serves pulls from them.
+pub struct CompactionStore {
+    message_store_config: Arc<MessageStoreConfig>,
+    compaction_logs: RwLock<HashMap<(CheetahString, i32), Arc<CompactionLog>>>,
+}
 
 impl CompactionStore {
-    pub fn new() -> Self {
-        CompactionStore
+    pub fn new(message_store_config: Arc<MessageStoreConfig>) -> Self {
+        CompactionStore {
+            message_store_config,
+            compaction_logs: RwLock::new(HashMap::new()),
+        }
     }
-}
 
-#[allow(unused_variables)]
-impl CompactionStore {
+    /// Loads every `topic/queueId` directory under the compaction log path.
+    pub fn load(&self, exit_ok: bool) -> bool {
+        let root =
+            get_store_path_compaction_log(self.message_store_config.store_path_root_dir.as_str());
+        info!("load compaction store from {}, exit ok: {}", root, exit_ok);
+        let Ok(topic_dirs) = fs::read_dir(&root) else {
+            return true;
+        };
+        let mut compaction_logs = self.compaction_logs.write();
+        for topic_dir in topic_dirs.filter_map(Result::ok) {
+            let topic = CheetahString::from_string(topic_dir.file_name().to_string_lossy().into());
+            let Ok(queue_dirs) = fs::read_dir(topic_dir.path()) else {
+                continue;
+            };
+            for queue_dir in queue_dirs.filter_map(Result::ok) {
+                // an interrupted compaction may have left only the compacting directory and its
+                // marker, they still name the queue
+                let file_name = queue_dir.file_name().to_string_lossy().into_owned();
+                let Ok(queue_id) = file_name
+                    .trim_end_matches(COMPACTING_SUFFIX)
+                    .trim_end_matches(COMPACTED_SUFFIX)
+                    .parse::<i32>()
+                else {
+                    continue;
+                };
+                if compaction_logs.contains_key(&(topic.clone(), queue_id)) {
+                    continue;
+                }
+                let compaction_log =
+                    CompactionLog::new(&self.message_store_config, topic.clone(), queue_id);
+                if !compaction_log.load() {
+                    error!("load compaction log of {}-{} failed", topic, queue_id);
+                    return false;
+                }
+                compaction_logs.insert((topic.clone(), queue_id), Arc::new(compaction_log));
+            }
+        }
+        true
+    }
+
+    fn get_or_create(&self, topic: &CheetahString, queue_id: i32) -> Option<Arc<CompactionLog>> {
+        let key = (topic.clone(), queue_id);
+        if let Some(compaction_log) = self.compaction_logs.read().get(&key) {
+            return Some(compaction_log.clone());
+        }
+        let mut compaction_logs = self.compaction_logs.write();
+        if let Some(compaction_log) = compaction_logs.get(&key) {
+            return Some(compaction_log.clone());
+        }
+        let compaction_log =
+            CompactionLog::new(&self.message_store_config, topic.clone(), queue_id);
+        if !compaction_log.load() {
+            return None;
+        }
+        let compaction_log = Arc::new(compaction_log);
+        compaction_logs.insert(key, compaction_log.clone());
+        Some(compaction_log)
+    }
+
+    /// Appends a message, in the commit log format, to the compaction log of its queue.
+    pub fn put_message(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+        message: &[u8],
+        queue_offset: i64,
+    ) -> bool {
+        match self.get_or_create(topic, queue_id) {
+            Some(compaction_log) => compaction_log.put_message(message, queue_offset),
+            None => {
+                error!("create compaction log of {}-{} failed", topic, queue_id);
+                false
+            }
+        }
+    }
+
+    #[allow(unused_variables)]
     pub fn get_message(
         &self,
         group: &CheetahString,
@@ -37,6 +131,41 @@ impl CompactionStore {
         max_msg_nums: i32,
         max_total_msg_size: i32,
     ) -> Option<GetMessageResult> {
-        None
+        let compaction_log = self
+            .compaction_logs
+            .read()
+            .get(&(topic.clone(), queue_id))
+            .cloned();
+        match compaction_log {
+            Some(compaction_log) => {
+                Some(compaction_log.get_message(offset, max_msg_nums, max_total_msg_size))
+            }
+            None => {
+                let mut result = GetMessageResult::new();
+                result.set_status(Some(GetMessageStatus::NoMessageInQueue));
+                result.set_next_begin_offset(0);
+                Some(result)
+            }
+        }
+    }
+
+    /// Compacts every compaction log, returns how many messages were removed.
+    pub fn compact_all(&self) -> usize {
+        let compaction_logs: Vec<Arc<CompactionLog>> =
+            self.compaction_logs.read().values().cloned().collect();
+        compaction_logs
+            .iter()
+            .map(|compaction_log| compaction_log.compact())
+            .sum()
+    }
+
+    pub fn flush(&self) {
+        for compaction_log in self.compaction_logs.read().values() {
+            compaction_log.flush();
+        }
+    }
+
+    pub fn shutdown(&self) {
+        self.flush();
     }
 }
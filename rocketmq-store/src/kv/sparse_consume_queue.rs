@@ -0,0 +1,217 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use bytes::Buf;
+use bytes::BufMut;
+use bytes::BytesMut;
+use tracing::info;
+
+use crate::consume_queue::mapped_file_queue::MappedFileQueue;
+use crate::log_file::mapped_file::MappedFile;
+
+/// Size of one unit: physical offset(8) + size(4) + queue offset(8).
+pub(crate) const SPARSE_CQ_UNIT_SIZE: i64 = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SparseCqUnit {
+    pub pos: i64,
+    pub size: i32,
+    pub queue_offset: i64,
+}
+
+/// Consume queue over a compacted log.
+///
+/// Compaction leaves holes in the queue offsets, so unlike the regular consume queue a unit can
+/// not be located from the queue offset directly. Each unit records the queue offset of its
+/// message and units are kept in queue offset order, lookups are binary searches.
+pub(crate) struct SparseConsumeQueue {
+    mapped_file_queue: MappedFileQueue,
+}
+
+impl SparseConsumeQueue {
+    pub fn new(store_path: String, mapped_file_size: u64) -> Self {
+        // keep units from spanning two files
+        let mapped_file_size = mapped_file_size - mapped_file_size % SPARSE_CQ_UNIT_SIZE as u64;
+        Self {
+            mapped_file_queue: MappedFileQueue::new(store_path, mapped_file_size, None),
+        }
+    }
+
+    pub fn load(&mut self) -> bool {
+        self.mapped_file_queue.load()
+    }
+
+    pub fn recover(&mut self) {
+        let mapped_file_size = self.mapped_file_queue.mapped_file_size as i64;
+        let mut process_offset = 0;
+        for mapped_file in self.mapped_file_queue.get_mapped_files().read().iter() {
+            let mut mapped_file_offset = 0;
+            while mapped_file_offset < mapped_file_size {
+                match read_unit(
+                    mapped_file
+                        .get_bytes(mapped_file_offset as usize, SPARSE_CQ_UNIT_SIZE as usize),
+                ) {
+                    Some(_) => mapped_file_offset += SPARSE_CQ_UNIT_SIZE,
+                    None => break,
+                }
+            }
+            process_offset = mapped_file.get_file_from_offset() as i64 + mapped_file_offset;
+            if mapped_file_offset < mapped_file_size {
+                break;
+            }
+        }
+        info!(
+            "recover sparse consume queue {} to offset {}",
+            self.mapped_file_queue.store_path, process_offset
+        );
+        self.mapped_file_queue.set_flushed_where(process_offset);
+        self.mapped_file_queue.set_committed_where(process_offset);
+        self.mapped_file_queue.truncate_dirty_files(process_offset);
+    }
+
+    pub fn put(&mut self, unit: SparseCqUnit) -> bool {
+        let Some(mapped_file) = self
+            .mapped_file_queue
+            .get_last_mapped_file_mut_start_offset(0, true)
+        else {
+            return false;
+        };
+        let mut buffer = BytesMut::with_capacity(SPARSE_CQ_UNIT_SIZE as usize);
+        buffer.put_i64(unit.pos);
+        buffer.put_i32(unit.size);
+        buffer.put_i64(unit.queue_offset);
+        mapped_file.append_message_bytes(&buffer)
+    }
+
+    /// Number of units in the queue.
+    pub fn len(&self) -> i64 {
+        self.mapped_file_queue.get_max_offset() / SPARSE_CQ_UNIT_SIZE
+    }
+
+    pub fn get(&self, index: i64) -> Option<SparseCqUnit> {
+        if index < 0 || index >= self.len() {
+            return None;
+        }
+        let offset = index * SPARSE_CQ_UNIT_SIZE;
+        let mapped_file = self
+            .mapped_file_queue
+            .find_mapped_file_by_offset(offset, false)?;
+        let pos = offset - mapped_file.get_file_from_offset() as i64;
+        read_unit(mapped_file.get_bytes(pos as usize, SPARSE_CQ_UNIT_SIZE as usize))
+    }
+
+    pub fn first(&self) -> Option<SparseCqUnit> {
+        self.get(0)
+    }
+
+    pub fn last(&self) -> Option<SparseCqUnit> {
+        self.get(self.len() - 1)
+    }
+
+    /// Index of the first unit whose queue offset is not less than `queue_offset`.
+    pub fn lower_bound(&self, queue_offset: i64) -> i64 {
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match self.get(mid) {
+                Some(unit) if unit.queue_offset < queue_offset => low = mid + 1,
+                _ => high = mid,
+            }
+        }
+        low
+    }
+
+    pub fn flush(&self) {
+        flush_all(&self.mapped_file_queue);
+    }
+
+    pub fn destroy(&mut self) {
+        self.mapped_file_queue.destroy();
+    }
+}
+
+/// Flushes every file of `mapped_file_queue`, [`MappedFileQueue::flush`] stops at the end of
+/// the file being flushed.
+pub(crate) fn flush_all(mapped_file_queue: &MappedFileQueue) {
+    loop {
+        let flushed_where = mapped_file_queue.get_flushed_where();
+        mapped_file_queue.flush(0);
+        if mapped_file_queue.get_flushed_where() == flushed_where {
+            break;
+        }
+    }
+}
+
+fn read_unit(bytes: Option<bytes::Bytes>) -> Option<SparseCqUnit> {
+    let mut bytes = bytes?;
+    let unit = SparseCqUnit {
+        pos: bytes.get_i64(),
+        size: bytes.get_i32(),
+        queue_offset: bytes.get_i64(),
+    };
+    (unit.pos >= 0 && unit.size > 0).then_some(unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn unit(queue_offset: i64) -> SparseCqUnit {
+        SparseCqUnit {
+            pos: queue_offset * 100,
+            size: 100,
+            queue_offset,
+        }
+    }
+
+    #[test]
+    fn lower_bound_skips_compacted_offsets() {
+        let dir = TempDir::new().unwrap();
+        let mut cq = SparseConsumeQueue::new(dir.path().to_string_lossy().into_owned(), 4 * 20);
+        for queue_offset in [1, 4, 5, 9, 12] {
+            assert!(cq.put(unit(queue_offset)));
+        }
+        assert_eq!(cq.len(), 5);
+        assert_eq!(cq.first(), Some(unit(1)));
+        assert_eq!(cq.last(), Some(unit(12)));
+        assert_eq!(cq.lower_bound(0), 0);
+        assert_eq!(cq.lower_bound(2), 1);
+        assert_eq!(cq.lower_bound(9), 3);
+        assert_eq!(cq.lower_bound(13), 5);
+        assert_eq!(cq.get(cq.lower_bound(6)), Some(unit(9)));
+    }
+
+    #[test]
+    fn recover_stops_at_last_written_unit() {
+        let dir = TempDir::new().unwrap();
+        let store_path = dir.path().to_string_lossy().into_owned();
+        {
+            let mut cq = SparseConsumeQueue::new(store_path.clone(), 4 * 20);
+            for queue_offset in 0..6 {
+                assert!(cq.put(unit(queue_offset)));
+            }
+            cq.flush();
+        }
+        let mut cq = SparseConsumeQueue::new(store_path, 4 * 20);
+        assert!(cq.load());
+        cq.recover();
+        assert_eq!(cq.len(), 6);
+        assert!(cq.put(unit(6)));
+        assert_eq!(cq.last(), Some(unit(6)));
+    }
+}
@@ -24,7 +24,7 @@ pub mod consume_queue;
 pub mod filter;
 pub mod ha;
 pub mod hook;
-mod index;
+pub mod index;
 mod kv;
 pub mod log_file;
 pub(crate) mod message_encoder;
@@ -36,5 +36,6 @@ pub mod stats;
 pub mod store;
 pub mod store_error;
 pub mod store_path_config_helper;
+pub mod tiered;
 pub mod timer;
 pub mod utils;
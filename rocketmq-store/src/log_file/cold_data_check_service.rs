@@ -14,11 +14,61 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
-#[derive(Default)]
-pub struct ColdDataCheckService;
+
+use std::sync::Arc;
+
+use crate::config::message_store_config::MessageStoreConfig;
+use crate::utils::store_util::TOTAL_PHYSICAL_MEMORY_SIZE;
+
+/// Estimates whether commit log data is still resident in the page cache.
+///
+/// Data within `access_message_in_memory_hot_ratio` percent of physical memory from the
+/// commit log tail is considered hot; anything older is considered cold.
+pub struct ColdDataCheckService {
+    message_store_config: Arc<MessageStoreConfig>,
+}
 
 impl ColdDataCheckService {
-    pub fn is_data_in_page_cache(&self) -> bool {
-        true
+    pub fn new(message_store_config: Arc<MessageStoreConfig>) -> Self {
+        Self {
+            message_store_config,
+        }
+    }
+
+    pub fn is_data_in_page_cache(&self, offset_py: i64, max_offset_py: i64) -> bool {
+        if !self.message_store_config.cold_data_flow_control_enable {
+            return true;
+        }
+        !self.check_in_cold_area_by_commit_offset(offset_py, max_offset_py)
+    }
+
+    pub fn check_in_cold_area_by_commit_offset(&self, offset_py: i64, max_offset_py: i64) -> bool {
+        let hot_area = (*TOTAL_PHYSICAL_MEMORY_SIZE as f64)
+            * (self.message_store_config.access_message_in_memory_hot_ratio as f64 / 100.0);
+        (max_offset_py - offset_py) as f64 > hot_area
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn data_in_page_cache_when_flow_control_disabled() {
+        let service = ColdDataCheckService::new(Arc::new(MessageStoreConfig::default()));
+        assert!(service.is_data_in_page_cache(0, i64::MAX));
+    }
+
+    #[test]
+    fn cold_area_depends_on_distance_from_tail() {
+        let config = MessageStoreConfig {
+            cold_data_flow_control_enable: true,
+            ..Default::default()
+        };
+        let service = ColdDataCheckService::new(Arc::new(config));
+        assert!(service.is_data_in_page_cache(1000, 1024));
+        assert!(!service.check_in_cold_area_by_commit_offset(1000, 1024));
+        assert!(service.check_in_cold_area_by_commit_offset(0, i64::MAX));
+        assert!(!service.is_data_in_page_cache(0, i64::MAX));
     }
 }
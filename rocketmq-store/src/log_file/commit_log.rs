@@ -19,7 +19,9 @@ use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::mem;
+use std::sync::atomic::AtomicI64;
 use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use bytes::Buf;
@@ -71,15 +73,18 @@ use crate::base::swappable::Swappable;
 use crate::base::topic_queue_lock::TopicQueueLock;
 use crate::config::message_store_config::MessageStoreConfig;
 use crate::consume_queue::mapped_file_queue::MappedFileQueue;
+use crate::ha::auto_switch::auto_switch_ha_service::AutoSwitchHAService;
+use crate::ha::ha_service::HAService;
 use crate::log_file::cold_data_check_service::ColdDataCheckService;
 use crate::log_file::flush_manager_impl::defalut_flush_manager::DefaultFlushManager;
+use crate::log_file::flush_manager_impl::group_commit_request::GroupCommitRequest;
 use crate::log_file::mapped_file::default_mapped_file_impl::DefaultMappedFile;
 use crate::log_file::mapped_file::MappedFile;
 use crate::message_encoder::message_ext_encoder::MessageExtEncoder;
 use crate::message_store::local_file_message_store::CommitLogDispatcherDefault;
 use crate::message_store::local_file_message_store::LocalFileMessageStore;
 use crate::queue::consume_queue_store::ConsumeQueueStoreTrait;
-use crate::queue::local_file_consume_queue_store::ConsumeQueueStore;
+use crate::queue::consume_queue_store_impl::ConsumeQueueStoreImpl;
 use crate::store_error::StoreError;
 
 // Message's MAGIC CODE daa320a7
@@ -188,13 +193,13 @@ pub struct CommitLog {
     enabled_append_prop_crc: bool,
     local_file_message_store: Option<ArcMut<LocalFileMessageStore>>,
     dispatcher: ArcMut<CommitLogDispatcherDefault>,
-    confirm_offset: i64,
+    confirm_offset: AtomicI64,
     store_checkpoint: Arc<StoreCheckpoint>,
     append_message_callback: Arc<DefaultAppendMessageCallback>,
     put_message_lock: Arc<tokio::sync::Mutex<()>>,
     topic_queue_lock: Arc<TopicQueueLock>,
     topic_config_table: Arc<parking_lot::Mutex<HashMap<CheetahString, TopicConfig>>>,
-    consume_queue_store: ConsumeQueueStore,
+    consume_queue_store: ConsumeQueueStoreImpl,
     flush_manager: Arc<tokio::sync::Mutex<DefaultFlushManager>>,
     begin_time_in_lock: Arc<AtomicU64>,
     cold_data_check_service: Arc<ColdDataCheckService>,
@@ -207,7 +212,7 @@ impl CommitLog {
         dispatcher: ArcMut<CommitLogDispatcherDefault>,
         store_checkpoint: Arc<StoreCheckpoint>,
         topic_config_table: Arc<parking_lot::Mutex<HashMap<CheetahString, TopicConfig>>>,
-        consume_queue_store: ConsumeQueueStore,
+        consume_queue_store: ConsumeQueueStoreImpl,
     ) -> Self {
         let enabled_append_prop_crc = message_store_config.enabled_append_prop_crc;
         let store_path = message_store_config.get_store_path_commit_log();
@@ -224,7 +229,7 @@ impl CommitLog {
             enabled_append_prop_crc,
             local_file_message_store: None,
             dispatcher,
-            confirm_offset: -1,
+            confirm_offset: AtomicI64::new(-1),
             store_checkpoint: store_checkpoint.clone(),
             append_message_callback: Arc::new(DefaultAppendMessageCallback::new(
                 message_store_config.clone(),
@@ -237,12 +242,12 @@ impl CommitLog {
             topic_config_table,
             consume_queue_store,
             flush_manager: Arc::new(tokio::sync::Mutex::new(DefaultFlushManager::new(
-                message_store_config,
+                message_store_config.clone(),
                 mapped_file_queue,
                 store_checkpoint,
             ))),
             begin_time_in_lock: Arc::new(AtomicU64::new(0)),
-            cold_data_check_service: Arc::new(Default::default()),
+            cold_data_check_service: Arc::new(ColdDataCheckService::new(message_store_config)),
         }
     }
 }
@@ -289,15 +294,17 @@ impl CommitLog {
                     mmap_file.select_mapped_buffer(pos as i32, size);
                 if let Some(ref mut result) = select_mapped_buffer_result {
                     result.mapped_file = Some(mmap_file);
-                    result.is_in_cache = self.cold_data_check_service.is_data_in_page_cache();
+                    result.is_in_cache = self
+                        .cold_data_check_service
+                        .is_data_in_page_cache(offset, self.get_max_offset());
                 }
                 select_mapped_buffer_result
             }
         }
     }
 
-    pub fn set_confirm_offset(&mut self, phy_offset: i64) {
-        self.confirm_offset = phy_offset;
+    pub fn set_confirm_offset(&self, phy_offset: i64) {
+        self.confirm_offset.store(phy_offset, Ordering::Release);
         self.store_checkpoint
             .set_confirm_phy_offset(phy_offset as u64);
     }
@@ -340,10 +347,23 @@ impl CommitLog {
         } else {
             0
         };
-        let need_ack_nums = self.message_store_config.in_sync_replicas;
+        let mut need_ack_nums = self.message_store_config.in_sync_replicas as i32;
         let need_handle_ha = self.need_handle_ha(&msg_batch.message_ext_broker_inner);
         if need_handle_ha && self.broker_config.enable_controller_mode {
-            unimplemented!("controller mode not support HA")
+            let in_sync_replicas = self
+                .local_file_message_store
+                .as_ref()
+                .and_then(|message_store| message_store.get_ha_service())
+                .map_or(1, |ha_service| {
+                    ha_service.in_sync_replicas_nums(curr_offset as i64)
+                });
+            if (in_sync_replicas as usize) < self.message_store_config.min_in_sync_replicas {
+                return PutMessageResult::new_default(PutMessageStatus::InSyncReplicasNotEnough);
+            }
+            if self.message_store_config.all_ack_in_sync_state_set {
+                // -1 means all ack in SyncStateSet
+                need_ack_nums = mix_all::ALL_ACK_IN_SYNC_STATE_SET;
+            }
         } else if need_handle_ha && self.broker_config.enable_slave_acting_master {
             unimplemented!("slave acting master not support HA")
         }
@@ -546,10 +566,23 @@ impl CommitLog {
         } else {
             0
         };
-        let need_ack_nums = self.message_store_config.in_sync_replicas;
+        let mut need_ack_nums = self.message_store_config.in_sync_replicas as i32;
         let need_handle_ha = self.need_handle_ha(&msg);
         if need_handle_ha && self.broker_config.enable_controller_mode {
-            unimplemented!("controller mode not support HA")
+            let in_sync_replicas = self
+                .local_file_message_store
+                .as_ref()
+                .and_then(|message_store| message_store.get_ha_service())
+                .map_or(1, |ha_service| {
+                    ha_service.in_sync_replicas_nums(curr_offset as i64)
+                });
+            if (in_sync_replicas as usize) < self.message_store_config.min_in_sync_replicas {
+                return PutMessageResult::new_default(PutMessageStatus::InSyncReplicasNotEnough);
+            }
+            if self.message_store_config.all_ack_in_sync_state_set {
+                // -1 means all ack in SyncStateSet
+                need_ack_nums = mix_all::ALL_ACK_IN_SYNC_STATE_SET;
+            }
         } else if need_handle_ha && self.broker_config.enable_slave_acting_master {
             unimplemented!("slave acting master not support HA")
         }
@@ -713,7 +746,7 @@ impl CommitLog {
         &self,
         mut put_message_result: PutMessageResult,
         msg: MessageExtBrokerInner,
-        need_ack_nums: u32,
+        need_ack_nums: i32,
         need_handle_ha: bool,
     ) -> PutMessageResult {
         let append_message_result = put_message_result.append_message_result().unwrap();
@@ -769,15 +802,35 @@ impl CommitLog {
     async fn handle_ha(
         &self,
         put_message_result: &AppendMessageResult,
-        need_ack_nums: u32,
+        need_ack_nums: i32,
     ) -> PutMessageStatus {
-        if need_ack_nums <= 1 {
+        if self.broker_config.enable_controller_mode && (0..=1).contains(&need_ack_nums) {
+            // The write is acknowledged by the master alone.
             return PutMessageStatus::PutOk;
         }
+        let Some(ha_service) = self
+            .local_file_message_store
+            .as_ref()
+            .and_then(|message_store| message_store.get_ha_service())
+        else {
+            return PutMessageStatus::PutOk;
+        };
+        let next_offset = put_message_result.wrote_offset + put_message_result.wrote_bytes as i64;
+        if (0..=1).contains(&need_ack_nums) && !ha_service.is_slave_ok(next_offset) {
+            // A SYNC_MASTER needs at least one slave to acknowledge the write
+            return PutMessageStatus::SlaveNotAvailable;
+        }
 
-        //HA service to do unimplemented
-
-        PutMessageStatus::PutOk
+        // Wait enough acks from different slaves
+        let mut request = GroupCommitRequest::new_with_ack_nums(
+            next_offset,
+            self.message_store_config.slave_timeout as u64,
+            need_ack_nums,
+        );
+        let future = request.future();
+        ha_service.put_request(request);
+        ha_service.get_wait_notify_object().wakeup_all();
+        future.await.unwrap_or(PutMessageStatus::FlushSlaveTimeout)
     }
 
     async fn handle_disk_flush(
@@ -803,6 +856,12 @@ impl CommitLog {
         if self.message_store_config.duplication_enable {
             return false;
         }
+        if self.broker_config.enable_controller_mode {
+            // The role is assigned by the controller, not by the configuration.
+            return self
+                .auto_switch_ha_service()
+                .is_some_and(|ha_service| ha_service.is_master());
+        }
         if BrokerRole::SyncMaster != self.message_store_config.broker_role {
             // No need to check ha in async or slave broker
             return false;
@@ -919,7 +978,7 @@ impl CommitLog {
             }
             process_offset += mapped_file_offset;
             if broker_config.enable_controller_mode {
-                unimplemented!();
+                self.correct_confirm_offset(process_offset as i64);
             } else {
                 self.set_confirm_offset(last_valid_msg_phy_offset as i64);
             }
@@ -975,13 +1034,73 @@ impl CommitLog {
     //Fetch and compute the newest confirmOffset.
     pub fn get_confirm_offset(&self) -> i64 {
         if self.broker_config.enable_controller_mode {
-            unimplemented!()
+            if let Some(ha_service) = self.auto_switch_ha_service() {
+                if ha_service.is_master() {
+                    if ha_service.get_local_sync_state_set().len() == 1
+                        || !self.message_store_config.all_ack_in_sync_state_set
+                    {
+                        return self.get_max_offset();
+                    }
+                    // First time it will compute the confirmOffset.
+                    if self.confirm_offset.load(Ordering::Acquire) < 0 {
+                        self.set_confirm_offset(ha_service.compute_confirm_offset());
+                        info!(
+                            "Init the confirmOffset to {}.",
+                            self.confirm_offset.load(Ordering::Acquire)
+                        );
+                    }
+                }
+            }
+            return self.confirm_offset.load(Ordering::Acquire);
         } else if self.broker_config.duplication_enable {
-            return self.confirm_offset;
+            return self.confirm_offset.load(Ordering::Acquire);
         }
         self.get_max_offset()
     }
 
+    fn auto_switch_ha_service(&self) -> Option<&AutoSwitchHAService> {
+        self.local_file_message_store
+            .as_ref()?
+            .get_ha_service()?
+            .get_auto_switch_ha_service()
+    }
+
+    /// Keeps the confirm offset loaded from the checkpoint inside the recovered commit log,
+    /// in controller mode it is not reset to the last message.
+    fn correct_confirm_offset(&self, max_valid_offset: i64) {
+        let confirm_offset = self.confirm_offset.load(Ordering::Acquire);
+        let min_offset = self.get_min_offset();
+        if confirm_offset < min_offset {
+            error!(
+                "confirmOffset {} is less than minPhyOffset {}, correct confirmOffset to \
+                 minPhyOffset",
+                confirm_offset, min_offset
+            );
+            self.set_confirm_offset(min_offset);
+        } else if confirm_offset > max_valid_offset {
+            error!(
+                "confirmOffset {} is larger than processOffset {}, correct confirmOffset to \
+                 processOffset",
+                confirm_offset, max_valid_offset
+            );
+            self.set_confirm_offset(max_valid_offset);
+        }
+    }
+
+    /// Truncates the commit log after `phy_offset`.
+    pub fn truncate_dirty_files(&mut self, phy_offset: i64) {
+        if phy_offset <= self.mapped_file_queue.get_flushed_where() {
+            self.mapped_file_queue.set_flushed_where(phy_offset);
+        }
+        if phy_offset <= self.mapped_file_queue.get_committed_where() {
+            self.mapped_file_queue.set_committed_where(phy_offset);
+        }
+        self.mapped_file_queue.truncate_dirty_files(phy_offset);
+        if self.confirm_offset.load(Ordering::Acquire) > phy_offset {
+            self.set_confirm_offset(phy_offset);
+        }
+    }
+
     pub async fn recover_abnormally(
         &mut self,
         max_phy_offset_of_consume_queue: i64,
@@ -1108,8 +1227,7 @@ impl CommitLog {
 
             process_offset += mapped_file_offset;
             if broker_config.enable_controller_mode {
-                println!("TODO: finishCommitLogDispatch:{last_confirm_valid_msg_phy_offset}",);
-                unimplemented!();
+                self.correct_confirm_offset(last_confirm_valid_msg_phy_offset as i64);
             } else {
                 self.set_confirm_offset(last_valid_msg_phy_offset as i64);
             }
@@ -1141,6 +1259,10 @@ impl CommitLog {
         }
     }
 
+    pub(crate) fn cold_data_check_service(&self) -> &ColdDataCheckService {
+        &self.cold_data_check_service
+    }
+
     pub fn get_max_offset(&self) -> i64 {
         self.mapped_file_queue.get_max_offset()
     }
@@ -1158,6 +1280,30 @@ impl CommitLog {
         }
     }
 
+    /// Deletes commit log files older than `expired_time` millis, see
+    /// [`MappedFileQueue::delete_expired_file_by_time`].
+    pub fn delete_expired_file(
+        &self,
+        expired_time: i64,
+        delete_files_interval: u64,
+        interval_forcibly: u64,
+        clean_immediately: bool,
+        delete_file_batch_max: usize,
+    ) -> i32 {
+        self.mapped_file_queue.delete_expired_file_by_time(
+            expired_time,
+            delete_files_interval,
+            interval_forcibly,
+            clean_immediately,
+            delete_file_batch_max,
+        )
+    }
+
+    pub fn retry_delete_first_file(&self, interval_forcibly: u64) -> bool {
+        self.mapped_file_queue
+            .retry_delete_first_file(interval_forcibly)
+    }
+
     pub fn roll_next_file(&self, offset: i64) -> i64 {
         let mapped_file_size = self.message_store_config.mapped_file_size_commit_log as i64;
         offset + mapped_file_size - (offset % mapped_file_size)
@@ -1246,7 +1392,22 @@ impl CommitLog {
         data_start: i32,
         data_length: i32,
     ) -> Result<bool, StoreError> {
-        unimplemented!("append_data not implemented")
+        // Only the HA client of a slave appends raw data, so it never races with put_message
+        let mapped_file = self
+            .mapped_file_queue
+            .mut_from_ref()
+            .get_last_mapped_file_mut_start_offset(start_offset as u64, true);
+        match mapped_file {
+            None => {
+                error!("appendData getLastMappedFile error {}", start_offset);
+                Ok(false)
+            }
+            Some(mapped_file) => Ok(mapped_file.append_message_offset_length(
+                data,
+                data_start as usize,
+                data_length as usize,
+            )),
+        }
     }
 
     pub fn set_local_file_message_store(
@@ -1257,6 +1418,18 @@ impl CommitLog {
     }
 }
 
+/// Returns whether `bytes` starts with a whole record, a message or the blank at the end of a
+/// file, so that [`check_message_and_return_size`] can decode it without running out of
+/// data. Replication may leave a partially received message at the tail of a slave.
+pub fn starts_with_complete_record(bytes: &[u8]) -> bool {
+    if bytes.len() < 8 {
+        return false;
+    }
+    let total_size = i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    let magic_code = i32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+    magic_code == BLANK_MAGIC_CODE || (total_size > 0 && bytes.len() >= total_size as usize)
+}
+
 pub fn check_message_and_return_size(
     bytes: &mut Bytes,
     check_crc: bool,
@@ -15,17 +15,20 @@
  * limitations under the License.
  */
 use std::sync::atomic::AtomicI32;
+use std::sync::atomic::Ordering;
 
 use rocketmq_common::TimeUtils::get_current_nano;
+use tokio::sync::oneshot;
 
 use crate::base::message_status_enum::PutMessageStatus;
 
 #[derive(Debug)]
-pub(crate) struct GroupCommitRequest {
+pub struct GroupCommitRequest {
     pub(crate) next_offset: i64,
     pub(crate) flush_ok: Option<PutMessageStatus>,
     pub(crate) ack_nums: AtomicI32,
     pub(crate) dead_line: u64,
+    pub(crate) response: Option<oneshot::Sender<PutMessageStatus>>,
 }
 
 impl Default for GroupCommitRequest {
@@ -35,6 +38,7 @@ impl Default for GroupCommitRequest {
             flush_ok: None,
             ack_nums: AtomicI32::new(1),
             dead_line: 0,
+            response: None,
         }
     }
 }
@@ -48,4 +52,44 @@ impl GroupCommitRequest {
             ..Self::default()
         }
     }
+
+    pub(crate) fn new_with_ack_nums(next_offset: i64, timeout_millis: u64, ack_nums: i32) -> Self {
+        let request = Self::new(next_offset, timeout_millis);
+        request.ack_nums.store(ack_nums, Ordering::Release);
+        request
+    }
+
+    #[inline]
+    pub(crate) fn get_ack_nums(&self) -> i32 {
+        self.ack_nums.load(Ordering::Acquire)
+    }
+
+    /// Returns the receiver that is completed by [`GroupCommitRequest::wakeup_customer`].
+    pub(crate) fn future(&mut self) -> oneshot::Receiver<PutMessageStatus> {
+        let (tx, rx) = oneshot::channel();
+        self.response = Some(tx);
+        rx
+    }
+
+    pub(crate) fn wakeup_customer(&mut self, status: PutMessageStatus) {
+        self.flush_ok = Some(status);
+        if let Some(response) = self.response.take() {
+            let _ = response.send(status);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn wakeup_customer_completes_future() {
+        let mut request = GroupCommitRequest::new_with_ack_nums(1024, 1000, 2);
+        assert_eq!(request.get_ack_nums(), 2);
+        let future = request.future();
+        request.wakeup_customer(PutMessageStatus::FlushSlaveTimeout);
+        assert_eq!(future.await.unwrap(), PutMessageStatus::FlushSlaveTimeout);
+        assert_eq!(request.flush_ok, Some(PutMessageStatus::FlushSlaveTimeout));
+    }
 }
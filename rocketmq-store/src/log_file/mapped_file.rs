@@ -371,7 +371,7 @@ pub trait MappedFile {
     /// Returns the timestamp of the last modification to the store.
     ///
     /// # Returns
-    /// A `i64` representing the timestamp of the last modification.
+    /// A `u64` representing the last modification time in milliseconds since the Unix epoch.
     fn get_last_modified_timestamp(&self) -> u64;
 
     /// Retrieves data from the store starting at the specified position and of the specified size.
@@ -25,6 +25,7 @@ use std::collections::VecDeque;
 use std::error::Error;
 use std::fs;
 use std::future::Future;
+use std::io::Read;
 use std::net::IpAddr;
 use std::ops::Deref;
 use std::path::Path;
@@ -93,6 +94,8 @@ use crate::config::message_store_config::MessageStoreConfig;
 use crate::config::store_path_config_helper::get_store_path_batch_consume_queue;
 use crate::config::store_path_config_helper::get_store_path_consume_queue_ext;
 use crate::filter::MessageFilter;
+use crate::ha::auto_switch::auto_switch_ha_service::AutoSwitchHAService;
+use crate::ha::default_ha_service::DefaultHAService;
 use crate::ha::general_ha_service::GeneralHAService;
 use crate::ha::ha_service::HAService;
 use crate::hook::put_message_hook::BoxedPutMessageHook;
@@ -100,6 +103,7 @@ use crate::hook::put_message_hook::PutMessageHook;
 use crate::hook::send_message_back_hook::SendMessageBackHook;
 use crate::index::index_dispatch::CommitLogDispatcherBuildIndex;
 use crate::index::index_service::IndexService;
+use crate::kv::compaction_service::CommitLogDispatcherCompaction;
 use crate::kv::compaction_service::CompactionService;
 use crate::kv::compaction_store::CompactionStore;
 use crate::log_file::commit_log;
@@ -107,15 +111,23 @@ use crate::log_file::commit_log::CommitLog;
 use crate::log_file::mapped_file::MappedFile;
 use crate::log_file::MAX_PULL_MSG_SIZE;
 use crate::queue::build_consume_queue::CommitLogDispatcherBuildConsumeQueue;
+#[cfg(feature = "rocksdb_cq")]
+use crate::queue::build_consume_queue::CommitLogDispatcherBuildRocksdbConsumeQueue;
+use crate::queue::consume_queue::ConsumeQueueTrait;
 use crate::queue::consume_queue_store::ConsumeQueueStoreTrait;
-use crate::queue::local_file_consume_queue_store::ConsumeQueueStore;
+use crate::queue::consume_queue_store_impl::ConsumeQueueStoreImpl;
+#[cfg(feature = "rocksdb_cq")]
+use crate::queue::rocksdb_consume_queue_store::RocksDBConsumeQueueStore;
 use crate::queue::ArcConsumeQueue;
+#[cfg(feature = "rocksdb_cq")]
+use crate::queue::CqUnit;
 use crate::stats::broker_stats_manager::BrokerStatsManager;
 use crate::store::running_flags::RunningFlags;
 use crate::store_error::StoreError;
 use crate::store_path_config_helper::get_abort_file;
 use crate::store_path_config_helper::get_store_checkpoint;
 use crate::store_path_config_helper::get_store_path_consume_queue;
+use crate::tiered::tiered_metadata::TieredMetadataStore;
 use crate::timer::timer_message_store::TimerMessageStore;
 use crate::utils::store_util::TOTAL_PHYSICAL_MEMORY_SIZE;
 
@@ -131,7 +143,11 @@ pub struct LocalFileMessageStore {
     master_flushed_offset: Arc<AtomicI64>,
     index_service: IndexService,
     allocate_mapped_file_service: Arc<AllocateMappedFileService>,
-    consume_queue_store: ConsumeQueueStore,
+    consume_queue_store: ConsumeQueueStoreImpl,
+    /// Written next to the file consume queues when `rocksdb_cq_double_write_enable` is on and
+    /// the file consume queues are in use.
+    #[cfg(feature = "rocksdb_cq")]
+    rocksdb_consume_queue_store: Option<RocksDBConsumeQueueStore>,
     dispatcher: ArcMut<CommitLogDispatcherDefault>,
     broker_init_max_offset: Arc<AtomicI64>,
     state_machine_version: Arc<AtomicI64>,
@@ -141,6 +157,7 @@ pub struct LocalFileMessageStore {
     clean_commit_log_service: Arc<CleanCommitLogService>,
     correct_logic_offset_service: Arc<CorrectLogicOffsetService>,
     clean_consume_queue_service: Arc<CleanConsumeQueueService>,
+    local_file_retention: LocalFileRetention,
     broker_stats_manager: Option<Arc<BrokerStatsManager>>,
     message_arriving_listener:
         Option<Arc<Box<dyn MessageArrivingListener + Sync + Send + 'static>>>,
@@ -185,7 +202,7 @@ impl LocalFileMessageStore {
             CommitLogDispatcherBuildIndex::new(index_service.clone(), message_store_config.clone()),
         );
         let consume_queue_store =
-            ConsumeQueueStore::new(message_store_config.clone(), broker_config.clone());
+            ConsumeQueueStoreImpl::new(message_store_config.clone(), broker_config.clone());
         let build_consume_queue: Arc<dyn CommitLogDispatcher> = Arc::new(
             CommitLogDispatcherBuildConsumeQueue::new(consume_queue_store.clone()),
         );
@@ -194,6 +211,22 @@ impl LocalFileMessageStore {
             dispatcher_vec: vec![build_consume_queue, build_index],
         });
 
+        #[cfg(feature = "rocksdb_cq")]
+        let rocksdb_consume_queue_store = if message_store_config.rocksdb_cq_double_write_enable
+            && !consume_queue_store.is_rocksdb()
+        {
+            let rocksdb_consume_queue_store =
+                RocksDBConsumeQueueStore::new(message_store_config.clone());
+            dispatcher.mut_from_ref().add_dispatcher(Arc::new(
+                CommitLogDispatcherBuildRocksdbConsumeQueue::new(
+                    rocksdb_consume_queue_store.clone(),
+                ),
+            ));
+            Some(rocksdb_consume_queue_store)
+        } else {
+            None
+        };
+
         let commit_log = ArcMut::new(CommitLog::new(
             message_store_config.clone(),
             broker_config.clone(),
@@ -203,6 +236,23 @@ impl LocalFileMessageStore {
             consume_queue_store.clone(),
         ));
 
+        let compaction_store = Arc::new(CompactionStore::new(message_store_config.clone()));
+        let compaction_service =
+            if message_store_config.enable_compaction {
+                let compaction_service = CompactionService::new(
+                    message_store_config.clone(),
+                    compaction_store.clone(),
+                    commit_log.clone(),
+                    topic_config_table.clone(),
+                );
+                dispatcher.mut_from_ref().add_dispatcher(Arc::new(
+                    CommitLogDispatcherCompaction::new(compaction_service.clone()),
+                ));
+                Some(compaction_service)
+            } else {
+                None
+            };
+
         ensure_dir_ok(message_store_config.store_path_root_dir.as_str());
         ensure_dir_ok(Self::get_store_path_physic(&message_store_config).as_str());
         ensure_dir_ok(Self::get_store_path_logic(&message_store_config).as_str());
@@ -212,6 +262,45 @@ impl LocalFileMessageStore {
             message_store_config.transient_store_pool_size,
             message_store_config.mapped_file_size_commit_log,
         );
+        let ha_service = if !message_store_config.enable_dleger_commit_log
+            && !message_store_config.duplication_enable
+        {
+            if broker_config.enable_controller_mode {
+                Some(ArcMut::new(
+                    GeneralHAService::new_with_auto_switch_ha_service(AutoSwitchHAService::new(
+                        message_store_config.clone(),
+                    )),
+                ))
+            } else {
+                Some(ArcMut::new(GeneralHAService::new_with_default_ha_service(
+                    DefaultHAService::new(message_store_config.clone()),
+                )))
+            }
+        } else {
+            None
+        };
+        let local_file_retention = LocalFileRetention::default();
+        let clean_commit_log_service = Arc::new(CleanCommitLogService::new(
+            message_store_config.clone(),
+            commit_log.clone(),
+            running_flags.clone(),
+            local_file_retention.clone(),
+        ));
+        let correct_logic_offset_service = Arc::new(CorrectLogicOffsetService::new(
+            message_store_config.clone(),
+            commit_log.clone(),
+            consume_queue_store.clone(),
+            local_file_retention.clone(),
+        ));
+        let clean_consume_queue_service = Arc::new(CleanConsumeQueueService::new(
+            message_store_config.clone(),
+            commit_log.clone(),
+            consume_queue_store.clone(),
+            index_service.clone(),
+            #[cfg(feature = "rocksdb_cq")]
+            rocksdb_consume_queue_store.clone(),
+            local_file_retention.clone(),
+        ));
         Self {
             message_store_config: message_store_config.clone(),
             broker_config,
@@ -219,12 +308,14 @@ impl LocalFileMessageStore {
             topic_config_table,
             // message_store_runtime: Some(RocketMQRuntime::new_multi(10, "message-store-thread")),
             commit_log,
-            compaction_service: Default::default(),
+            compaction_service,
             store_checkpoint: Some(store_checkpoint),
             master_flushed_offset: Arc::new(AtomicI64::new(-1)),
             index_service,
             allocate_mapped_file_service: Arc::new(AllocateMappedFileService::new()),
             consume_queue_store,
+            #[cfg(feature = "rocksdb_cq")]
+            rocksdb_consume_queue_store,
             dispatcher,
             broker_init_max_offset: Arc::new(AtomicI64::new(-1)),
             state_machine_version: Arc::new(AtomicI64::new(0)),
@@ -236,18 +327,19 @@ impl LocalFileMessageStore {
                 message_store_config,
                 inner: None,
             },
-            clean_commit_log_service: Arc::new(CleanCommitLogService {}),
-            correct_logic_offset_service: Arc::new(CorrectLogicOffsetService {}),
-            clean_consume_queue_service: Arc::new(CleanConsumeQueueService {}),
+            clean_commit_log_service,
+            correct_logic_offset_service,
+            clean_consume_queue_service,
+            local_file_retention,
             broker_stats_manager,
             message_arriving_listener: None,
             notify_message_arrive_in_batch,
             store_stats_service: Arc::new(StoreStatsService::new(Some(identity))),
-            compaction_store: Arc::new(CompactionStore),
+            compaction_store,
             timer_message_store: None,
             transient_store_pool,
             message_store_arc: None,
-            ha_service: None,
+            ha_service,
             flush_consume_queue_service: FlushConsumeQueueService,
             delay_level_table: ArcMut::new(delay_level_table),
             max_delay_level,
@@ -285,6 +377,23 @@ impl LocalFileMessageStore {
             .set_message_store(message_store_arc);
     }
 
+    pub(crate) fn get_reput_from_offset(&self) -> i64 {
+        self.reput_message_service.reput_from_offset()
+    }
+
+    pub(crate) fn roll_next_file(&self, offset: i64) -> i64 {
+        self.commit_log.roll_next_file(offset)
+    }
+
+    pub(crate) fn update_confirm_offset(&self, phy_offset: i64) {
+        self.commit_log.set_confirm_offset(phy_offset);
+    }
+
+    pub(crate) fn set_state_machine_version(&self, state_machine_version: i64) {
+        self.state_machine_version
+            .store(state_machine_version, Ordering::SeqCst);
+    }
+
     pub fn delay_level_table(&self) -> &ArcMut<BTreeMap<i32, i64>> {
         &self.delay_level_table
     }
@@ -428,7 +537,7 @@ impl LocalFileMessageStore {
         self.consume_queue_store.truncate_dirty(phy_offset);
     }*/
 
-    pub fn consume_queue_store_mut(&mut self) -> &mut ConsumeQueueStore {
+    pub fn consume_queue_store_mut(&mut self) -> &mut ConsumeQueueStoreImpl {
         &mut self.consume_queue_store
     }
 
@@ -453,7 +562,9 @@ impl LocalFileMessageStore {
             let mut interval =
                 tokio::time::interval(Duration::from_millis(clean_resource_interval));
             loop {
-                clean_commit_log_service_arc.run();
+                let service = clean_commit_log_service_arc.clone();
+                // deleting files sleeps between batches, keep it off the async workers
+                let _ = tokio::task::spawn_blocking(move || service.run()).await;
                 interval.tick().await;
             }
         });
@@ -489,11 +600,42 @@ impl LocalFileMessageStore {
             let mut interval =
                 tokio::time::interval(Duration::from_millis(clean_resource_interval));
             loop {
-                correct_logic_offset_service_arc.run();
-                clean_consume_queue_service_arc.run();
+                let correct_logic_offset_service = correct_logic_offset_service_arc.clone();
+                let clean_consume_queue_service = clean_consume_queue_service_arc.clone();
+                let _ = tokio::task::spawn_blocking(move || {
+                    correct_logic_offset_service.run();
+                    clean_consume_queue_service.run();
+                })
+                .await;
                 interval.tick().await;
             }
         });
+
+        #[cfg(feature = "rocksdb_cq")]
+        if let Some(rocksdb_consume_queue_store) = self.rocksdb_consume_queue_store.clone() {
+            let clean_interval_min = self
+                .message_store_config
+                .clean_rocksdb_dirty_cq_interval_min;
+            if clean_interval_min > 0 {
+                let topic_config_table = self.topic_config_table.clone();
+                tokio::spawn(async move {
+                    let period = Duration::from_secs(60 * clean_interval_min as u64);
+                    let mut interval = tokio::time::interval(period);
+                    interval.tick().await;
+                    loop {
+                        interval.tick().await;
+                        let store = rocksdb_consume_queue_store.clone();
+                        let topic_config_table = topic_config_table.clone();
+                        let _ = tokio::task::spawn_blocking(move || {
+                            store.clean_dirty_consume_queues(|topic| {
+                                topic_config_table.lock().contains_key(topic)
+                            });
+                        })
+                        .await;
+                    }
+                });
+            }
+        }
     }
 
     fn check_self(&self) {
@@ -584,10 +726,133 @@ fn is_the_batch_full(
     }
 }
 
+#[cfg(feature = "rocksdb_cq")]
+impl LocalFileMessageStore {
+    fn diff_rocksdb_consume_queues(
+        &self,
+        rocksdb_consume_queue_store: &RocksDBConsumeQueueStore,
+        topic: &CheetahString,
+    ) -> CheetahString {
+        let mut diff_result = String::new();
+        if !topic.trim().is_empty() {
+            if let Some(queue_map) = self.consume_queue_store.find_consume_queue_map(topic) {
+                diff_consume_queues_for_topic(
+                    rocksdb_consume_queue_store,
+                    topic,
+                    &queue_map,
+                    &mut diff_result,
+                    true,
+                );
+            }
+            return CheetahString::from_string(diff_result);
+        }
+        let topics = self
+            .consume_queue_store
+            .get_consume_queue_table()
+            .lock()
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>();
+        for topic in topics {
+            let Some(queue_map) = self.consume_queue_store.find_consume_queue_map(&topic) else {
+                continue;
+            };
+            if !diff_consume_queues_for_topic(
+                rocksdb_consume_queue_store,
+                &topic,
+                &queue_map,
+                &mut diff_result,
+                false,
+            ) {
+                break;
+            }
+        }
+        CheetahString::from_string(diff_result)
+    }
+}
+
+/// Walks every queue of `topic` from the RocksDB min offset up to the file max offset and
+/// records the first unit that is missing or differs. Returns false once a difference is found.
+#[cfg(feature = "rocksdb_cq")]
+fn diff_consume_queues_for_topic(
+    rocksdb_consume_queue_store: &RocksDBConsumeQueueStore,
+    topic: &CheetahString,
+    queue_map: &HashMap<i32, ArcConsumeQueue>,
+    diff_result: &mut String,
+    print_detail: bool,
+) -> bool {
+    fn format_unit(unit: Option<&CqUnit>) -> String {
+        match unit {
+            None => "null".to_string(),
+            Some(unit) => format!(
+                "CqUnit{{queueOffset={}, size={}, pos={}, batchNum={}, tagsCode={}}}",
+                unit.queue_offset, unit.size, unit.pos, unit.batch_num, unit.tags_code
+            ),
+        }
+    }
+
+    for (queue_id, file_cq) in queue_map {
+        let kv_cq = rocksdb_consume_queue_store.find_or_create_consume_queue(topic, *queue_id);
+        if print_detail {
+            diff_result.push_str(&format!(
+                "[topic: {}, queue: {}] \n  kvEarliest : {} |  kvLatest : {} \n fileEarliest: {} \
+                 | fileLatest: {} \n",
+                topic,
+                queue_id,
+                format_unit(kv_cq.get_earliest_unit().as_ref()),
+                format_unit(kv_cq.get_latest_unit().as_ref()),
+                format_unit(file_cq.get_earliest_unit().as_ref()),
+                format_unit(file_cq.get_latest_unit().as_ref()),
+            ));
+        }
+        let file_max_offset = file_cq.get_max_offset_in_queue();
+        for offset in kv_cq.get_min_offset_in_queue()..file_max_offset {
+            let file_unit = file_cq
+                .get_cq_unit_and_store_time(offset)
+                .map(|(unit, _)| unit);
+            let kv_unit = kv_cq
+                .get_cq_unit_and_store_time(offset)
+                .map(|(unit, _)| unit);
+            let equal = match (&kv_unit, &file_unit) {
+                (Some(kv), Some(file)) => {
+                    kv.queue_offset == file.queue_offset
+                        && kv.size == file.size
+                        && kv.pos == file.pos
+                        && kv.batch_num == file.batch_num
+                        && kv.tags_code == file.tags_code
+                }
+                _ => false,
+            };
+            if !equal {
+                let diff_info = format!(
+                    "[topic: {}, queue: {}, offset: {}] \n kv   : {}  \n file : {}  \n",
+                    topic,
+                    queue_id,
+                    offset,
+                    format_unit(kv_unit.as_ref()),
+                    format_unit(file_unit.as_ref()),
+                );
+                error!("{}", diff_info);
+                diff_result.push_str(&diff_info);
+                return false;
+            }
+        }
+    }
+    true
+}
+
 #[allow(unused_variables)]
 #[allow(unused_assignments)]
 impl MessageStore for LocalFileMessageStore {
     async fn load(&mut self) -> bool {
+        #[cfg(not(feature = "rocksdb_cq"))]
+        if self.message_store_config.is_enable_rocksdb_store() {
+            error!(
+                "store type RocksDB needs rocketmq-store built with the rocksdb_cq feature, \
+                 refusing to load the message store"
+            );
+            return false;
+        }
         let last_exit_ok = !self.is_temp_file_exist();
         info!(
             "last shutdown {}, store path root dir: {}",
@@ -605,6 +870,10 @@ impl MessageStore for LocalFileMessageStore {
         }
         // load Consume Queue-- init Consume log mapped file queue
         result &= self.consume_queue_store.load();
+        #[cfg(feature = "rocksdb_cq")]
+        if let Some(rocksdb_consume_queue_store) = self.rocksdb_consume_queue_store.as_mut() {
+            result &= rocksdb_consume_queue_store.load();
+        }
 
         if self.message_store_config.enable_compaction {
             result &= self.compaction_service.as_mut().unwrap().load(last_exit_ok);
@@ -673,7 +942,14 @@ impl MessageStore for LocalFileMessageStore {
         self.flush_consume_queue_service.start();
         self.commit_log.start();
         self.consume_queue_store.start();
+        #[cfg(feature = "rocksdb_cq")]
+        if let Some(rocksdb_consume_queue_store) = self.rocksdb_consume_queue_store.as_ref() {
+            rocksdb_consume_queue_store.start();
+        }
         self.store_stats_service.start();
+        if let Some(compaction_service) = self.compaction_service.as_ref() {
+            compaction_service.start();
+        }
 
         if let Some(ha_service) = self.ha_service.as_mut() {
             ha_service.start().map_err(|e| {
@@ -709,8 +985,9 @@ impl MessageStore for LocalFileMessageStore {
                 compaction_service.shutdown();
             }
 
-            if self.message_store_config.rocksdb_cq_double_write_enable {
-                // this.rocksDBMessageStore.consumeQueueStore.shutdown();
+            #[cfg(feature = "rocksdb_cq")]
+            if let Some(rocksdb_consume_queue_store) = self.rocksdb_consume_queue_store.as_ref() {
+                rocksdb_consume_queue_store.shutdown();
             }
             self.flush_consume_queue_service.shutdown();
             self.allocate_mapped_file_service.shutdown();
@@ -730,6 +1007,10 @@ impl MessageStore for LocalFileMessageStore {
 
     fn destroy(&mut self) {
         self.consume_queue_store.destroy();
+        #[cfg(feature = "rocksdb_cq")]
+        if let Some(rocksdb_consume_queue_store) = self.rocksdb_consume_queue_store.as_ref() {
+            rocksdb_consume_queue_store.destroy();
+        }
         self.commit_log.destroy();
         self.index_service.destroy();
         self.delete_file(get_abort_file(
@@ -859,7 +1140,6 @@ impl MessageStore for LocalFileMessageStore {
         let topic_config = self.get_topic_config(topic);
         let policy = get_delete_policy(topic_config.as_ref());
         if policy == CleanupPolicy::COMPACTION && self.message_store_config.enable_compaction {
-            //not implemented will be implemented in the future
             return self.compaction_store.get_message(
                 group,
                 topic,
@@ -1474,15 +1754,33 @@ impl MessageStore for LocalFileMessageStore {
     }*/
 
     fn update_ha_master_address(&self, new_addr: &CheetahString) {
-        todo!()
+        if let Some(ha_service) = self.ha_service.as_ref() {
+            ha_service.update_ha_master_address(new_addr.as_str());
+        }
     }
 
     fn update_master_address(&self, new_addr: &CheetahString) {
-        todo!()
+        if let Some(ha_service) = self.ha_service.as_ref() {
+            ha_service.update_master_address(new_addr.as_str());
+        }
     }
 
     fn slave_fall_behind_much(&self) -> i64 {
-        todo!()
+        match self.ha_service.as_ref() {
+            Some(ha_service)
+                if !self.message_store_config.duplication_enable
+                    && !self.broker_config.enable_controller_mode =>
+            {
+                self.commit_log.get_max_offset()
+                    - ha_service
+                        .get_push_to_slave_max_offset()
+                        .load(Ordering::Acquire)
+            }
+            _ => {
+                warn!("haServer is null or the broker role is not supported");
+                -1
+            }
+        }
     }
 
     fn delete_topics(&mut self, delete_topics: Vec<&CheetahString>) -> i32 {
@@ -1570,6 +1868,35 @@ impl MessageStore for LocalFileMessageStore {
         self.check_in_mem_by_commit_offset(start_offset_py, size as i32)
     }
 
+    fn is_msg_in_cold_area(
+        &self,
+        group: &CheetahString,
+        topic: &CheetahString,
+        queue_id: i32,
+        offset: i64,
+    ) -> bool {
+        if !self.message_store_config.cold_data_flow_control_enable {
+            return false;
+        }
+        let Some(consume_queue) = self.get_consume_queue(topic, queue_id) else {
+            return false;
+        };
+        match consume_queue.get(offset) {
+            Some(cq_unit) => self
+                .commit_log
+                .cold_data_check_service()
+                .check_in_cold_area_by_commit_offset(cq_unit.pos, self.commit_log.get_max_offset()),
+            None => {
+                warn!(
+                    "is_msg_in_cold_area cq unit not found, group: {}, topic: {}, queue_id: {}, \
+                     offset: {}",
+                    group, topic, queue_id, offset
+                );
+                false
+            }
+        }
+    }
+
     fn check_in_store_by_consume_offset(
         &self,
         topic: &CheetahString,
@@ -1580,8 +1907,7 @@ impl MessageStore for LocalFileMessageStore {
     }
 
     fn dispatch_behind_bytes(&self) -> i64 {
-        error!("DefaultMessageStore#dispatchBehindBytes: not implemented");
-        0
+        self.reput_message_service.behind()
     }
 
     fn flush(&self) -> i64 {
@@ -1597,7 +1923,7 @@ impl MessageStore for LocalFileMessageStore {
     }
 
     fn get_confirm_offset(&self) -> i64 {
-        todo!()
+        self.commit_log.get_confirm_offset()
     }
 
     fn set_confirm_offset(&mut self, phy_offset: i64) {
@@ -1712,6 +2038,10 @@ impl MessageStore for LocalFileMessageStore {
         todo!()
     }
 
+    fn get_ha_service(&self) -> Option<&ArcMut<GeneralHAService>> {
+        self.ha_service.as_ref()
+    }
+
     fn get_allocate_mapped_file_service(&self) -> Arc<AllocateMappedFileService> {
         todo!()
     }
@@ -1774,7 +2104,7 @@ impl MessageStore for LocalFileMessageStore {
     }
 
     fn get_master_flushed_offset(&self) -> i64 {
-        todo!()
+        self.master_flushed_offset.load(Ordering::Acquire)
     }
 
     fn get_broker_init_max_offset(&self) -> i64 {
@@ -1795,11 +2125,53 @@ impl MessageStore for LocalFileMessageStore {
     }
 
     fn truncate_files(&self, offset_to_truncate: i64) -> Result<bool, StoreError> {
-        todo!()
+        if offset_to_truncate >= self.get_max_phy_offset() {
+            info!(
+                "no need to truncate files, truncate offset is {}, max physical offset is {}",
+                offset_to_truncate,
+                self.get_max_phy_offset()
+            );
+            return Ok(true);
+        }
+        if !self.is_offset_aligned(offset_to_truncate) {
+            error!(
+                "offset {} is not align, truncate failed, need manual fix",
+                offset_to_truncate
+            );
+            return Ok(false);
+        }
+        self.truncate_dirty_logic_files(offset_to_truncate);
+        self.commit_log
+            .mut_from_ref()
+            .truncate_dirty_files(offset_to_truncate);
+        self.consume_queue_store
+            .clone()
+            .recover_offset_table(self.commit_log.get_min_offset());
+        if self.reput_message_service.reput_from_offset() > offset_to_truncate {
+            self.reput_message_service
+                .update_reput_from_offset(offset_to_truncate);
+        }
+        Ok(true)
     }
 
     fn is_offset_aligned(&self, offset: i64) -> bool {
-        todo!()
+        let Some(mut result) = self.get_commit_log_data(offset) else {
+            return true;
+        };
+        let mut bytes = result.get_bytes().unwrap_or_default();
+        let aligned = commit_log::starts_with_complete_record(&bytes)
+            && commit_log::check_message_and_return_size(
+                &mut bytes,
+                false,
+                false,
+                false,
+                &self.message_store_config,
+                self.max_delay_level,
+                self.delay_level_table.as_ref(),
+            )
+            .success;
+        result.release();
+        aligned
     }
 
     fn get_put_message_hook_list(&self) -> Vec<Arc<dyn PutMessageHook>> {
@@ -1882,6 +2254,17 @@ impl MessageStore for LocalFileMessageStore {
             .recover_offset_table(min_phy_offset);
     }
 
+    fn check_rocksdb_cq_write_progress(&self, topic: &CheetahString) -> CheetahString {
+        #[cfg(feature = "rocksdb_cq")]
+        if let Some(rocksdb_consume_queue_store) = self.rocksdb_consume_queue_store.as_ref() {
+            return self.diff_rocksdb_consume_queues(rocksdb_consume_queue_store, topic);
+        }
+        let _ = topic;
+        CheetahString::from_static_str(
+            "rocksdbCQWriteEnable is false, checkRocksdbCqWriteProgressCommand is invalid",
+        )
+    }
+
     fn notify_message_arrive_if_necessary(&self, dispatch_request: &mut DispatchRequest) {
         if self.broker_config.long_polling_enable && self.message_arriving_listener.is_some() {
             self.message_arriving_listener.as_ref().unwrap().arriving(
@@ -1901,6 +2284,11 @@ impl MessageStore for LocalFileMessageStore {
     fn set_put_message_hook(&mut self, put_message_hook: BoxedPutMessageHook) {
         self.put_message_hook_list.push(put_message_hook);
     }
+
+    fn set_tiered_metadata(&mut self, tiered_metadata: Arc<TieredMetadataStore>) {
+        self.local_file_retention
+            .set_tiered_metadata(tiered_metadata);
+    }
 }
 
 #[derive(Default)]
@@ -1999,6 +2387,26 @@ impl ReputMessageService {
         self.reput_from_offset = Some(Arc::new(AtomicI64::new(reput_from_offset)));
     }
 
+    pub fn reput_from_offset(&self) -> i64 {
+        self.reput_from_offset
+            .as_ref()
+            .map_or(0, |offset| offset.load(Ordering::Relaxed))
+    }
+
+    /// Moves the dispatch position, used after the commit log has been truncated.
+    pub fn update_reput_from_offset(&self, reput_from_offset: i64) {
+        if let Some(offset) = self.reput_from_offset.as_ref() {
+            offset.store(reput_from_offset, Ordering::SeqCst);
+        }
+    }
+
+    /// Bytes in the commit log that have not been dispatched yet.
+    pub fn behind(&self) -> i64 {
+        self.inner.as_ref().map_or(0, |inner| {
+            inner.get_reput_end_offset() - inner.reput_from_offset()
+        })
+    }
+
     pub fn start(
         &mut self,
         commit_log: ArcMut<CommitLog>,
@@ -2177,11 +2585,6 @@ impl ReputMessageServiceInner {
                             self.reput_from_offset
                                 .fetch_add(size as i64, Ordering::AcqRel);
                             read_size += size;
-                            if !self.message_store_config.duplication_enable
-                                && self.message_store_config.broker_role == BrokerRole::Slave
-                            {
-                                unimplemented!()
-                            }
                         }
                         std::cmp::Ordering::Equal => {
                             self.reput_from_offset.store(
@@ -2232,31 +2635,463 @@ impl ReputMessageServiceInner {
     }
 }
 
-struct CleanCommitLogService {}
+/// Decides which local files the clean up services may delete. Once a tiered store sets its
+/// metadata, a sealed file is only deleted after it has been uploaded to the backend.
+#[derive(Clone, Default)]
+struct LocalFileRetention {
+    tiered_metadata: Arc<parking_lot::RwLock<Option<Arc<TieredMetadataStore>>>>,
+}
+
+impl LocalFileRetention {
+    fn set_tiered_metadata(&self, tiered_metadata: Arc<TieredMetadataStore>) {
+        *self.tiered_metadata.write() = Some(tiered_metadata);
+    }
+
+    /// How many of the oldest commit log files may be deleted at most: the files before the
+    /// first one that is not in the backend yet.
+    fn commit_log_delete_batch(
+        &self,
+        commit_log: &CommitLog,
+        mapped_file_size: i64,
+        delete_file_batch_max: usize,
+    ) -> usize {
+        let Some(tiered_metadata) = self.tiered_metadata.read().clone() else {
+            return delete_file_batch_max;
+        };
+        let max_offset = commit_log.get_max_offset();
+        let mut base_offset = commit_log.get_min_offset();
+        let mut uploaded = 0;
+        while uploaded < delete_file_batch_max
+            && base_offset >= 0
+            && base_offset + mapped_file_size <= max_offset
+            && tiered_metadata.contains_commit_log_segment(base_offset)
+        {
+            uploaded += 1;
+            base_offset += mapped_file_size;
+        }
+        uploaded
+    }
+
+    /// Commit log offset below which the consume queue files of `topic`-`queue_id` may be
+    /// deleted. That is `min_commit_log_offset`, unless a sealed file is not in the backend
+    /// yet: its first unit then keeps it and every newer file.
+    fn consume_queue_delete_offset(
+        &self,
+        root_dir: &str,
+        topic: &CheetahString,
+        queue_id: i32,
+        min_commit_log_offset: i64,
+    ) -> i64 {
+        let Some(tiered_metadata) = self.tiered_metadata.read().clone() else {
+            return min_commit_log_offset;
+        };
+        let queue_dir = PathBuf::from(get_store_path_consume_queue(root_dir))
+            .join(topic.as_str())
+            .join(queue_id.to_string());
+        let Ok(entries) = fs::read_dir(queue_dir) else {
+            return min_commit_log_offset;
+        };
+        let mut files: Vec<(i64, PathBuf)> = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let base_offset = entry.file_name().to_str()?.parse().ok()?;
+                Some((base_offset, entry.path()))
+            })
+            .collect();
+        files.sort_unstable_by_key(|(base_offset, _)| *base_offset);
+        // the newest file is never deleted
+        files.pop();
+        let not_uploaded = files.into_iter().find(|(base_offset, _)| {
+            !tiered_metadata.contains_consume_queue_segment(topic, queue_id, *base_offset)
+        });
+        match not_uploaded {
+            Some((_, path)) => {
+                let mut first_unit_pos = [0u8; 8];
+                match fs::File::open(&path)
+                    .and_then(|mut file| file.read_exact(&mut first_unit_pos))
+                {
+                    Ok(()) => i64::from_be_bytes(first_unit_pos).min(min_commit_log_offset),
+                    Err(e) => {
+                        warn!("read consume queue file {} failed: {}", path.display(), e);
+                        0
+                    }
+                }
+            }
+            None => min_commit_log_offset,
+        }
+    }
+}
+
+/// Max times a manual deletion request keeps triggering the commit log clean up.
+const MAX_MANUAL_DELETE_FILE_TIMES: i32 = 20;
+
+/// Deletes expired commit log files on schedule (`delete_when`), when disk usage exceeds the
+/// configured watermarks, or on manual request.
+struct CleanCommitLogService {
+    message_store_config: Arc<MessageStoreConfig>,
+    commit_log: ArcMut<CommitLog>,
+    running_flags: Arc<RunningFlags>,
+    last_redelete_timestamp: AtomicI64,
+    manual_delete_file_several_times: AtomicI32,
+    clean_immediately: AtomicBool,
+    local_file_retention: LocalFileRetention,
+}
 
 impl CleanCommitLogService {
+    fn new(
+        message_store_config: Arc<MessageStoreConfig>,
+        commit_log: ArcMut<CommitLog>,
+        running_flags: Arc<RunningFlags>,
+        local_file_retention: LocalFileRetention,
+    ) -> Self {
+        Self {
+            message_store_config,
+            commit_log,
+            running_flags,
+            last_redelete_timestamp: AtomicI64::new(0),
+            manual_delete_file_several_times: AtomicI32::new(0),
+            clean_immediately: AtomicBool::new(false),
+            local_file_retention,
+        }
+    }
+
     fn run(&self) {
-        error!("clean commit log service run unimplemented!")
+        self.delete_expired_files();
+        self.redelete_hanged_file();
     }
 
     fn execute_delete_files_manually(&self) {
-        error!("execute delete files manually unimplemented!")
+        self.manual_delete_file_several_times
+            .store(MAX_MANUAL_DELETE_FILE_TIMES, Ordering::Release);
+        info!("executeDeleteFilesManually was invoked");
+    }
+
+    fn delete_expired_files(&self) {
+        let config = &self.message_store_config;
+        let is_time_up = self.is_time_to_delete();
+        let is_usage_exceeds_threshold = self.is_space_to_delete();
+        let manual_delete_file_several_times = self
+            .manual_delete_file_several_times
+            .load(Ordering::Acquire);
+        let is_manual_delete = manual_delete_file_several_times > 0;
+        if !(is_time_up || is_usage_exceeds_threshold || is_manual_delete) {
+            return;
+        }
+        if is_manual_delete {
+            self.manual_delete_file_several_times
+                .fetch_sub(1, Ordering::AcqRel);
+        }
+        let clean_at_once =
+            config.clean_file_forcibly_enable && self.clean_immediately.load(Ordering::Acquire);
+        info!(
+            "begin to delete before {} hours file. isTimeUp: {} isUsageExceedsThreshold: {} \
+             manualDeleteFileSeveralTimes: {} cleanAtOnce: {} deleteFileBatchMax: {}",
+            config.file_reserved_time,
+            is_time_up,
+            is_usage_exceeds_threshold,
+            manual_delete_file_several_times,
+            clean_at_once,
+            config.delete_file_batch_max
+        );
+        let delete_file_batch_max = self.local_file_retention.commit_log_delete_batch(
+            &self.commit_log,
+            config.mapped_file_size_commit_log as i64,
+            config.delete_file_batch_max,
+        );
+        if delete_file_batch_max == 0 {
+            info!("the oldest commit log file is not in the tiered backend yet, keep it");
+            return;
+        }
+        let file_reserved_time = config.file_reserved_time as i64 * 60 * 60 * 1000;
+        let delete_count = self.commit_log.delete_expired_file(
+            file_reserved_time,
+            config.delete_commit_log_files_interval as u64,
+            config.destroy_mapped_file_interval_forcibly as u64,
+            clean_at_once,
+            delete_file_batch_max,
+        );
+        if delete_count > 0 {
+            info!("delete {} expired commit log files", delete_count);
+        } else if is_usage_exceeds_threshold {
+            warn!("disk space will be full soon, but delete file failed.");
+        }
+    }
+
+    fn redelete_hanged_file(&self) {
+        let interval = self.message_store_config.redelete_hanged_file_interval as i64;
+        let current_timestamp = get_current_millis() as i64;
+        if current_timestamp - self.last_redelete_timestamp.load(Ordering::Acquire) > interval {
+            self.last_redelete_timestamp
+                .store(current_timestamp, Ordering::Release);
+            self.commit_log.retry_delete_first_file(
+                self.message_store_config
+                    .destroy_mapped_file_interval_forcibly as u64,
+            );
+        }
+    }
+
+    fn is_time_to_delete(&self) -> bool {
+        let when = self.message_store_config.delete_when.as_str();
+        if util_all::is_it_time_to_do(when) {
+            info!("it's time to reclaim disk space, {}", when);
+            return true;
+        }
+        false
+    }
+
+    fn is_space_to_delete(&self) -> bool {
+        self.clean_immediately.store(false, Ordering::Release);
+        let config = &self.message_store_config;
+        let warning_level_ratio = config.disk_space_warning_level_ratio as f64 / 100.0;
+        let clean_forcibly_ratio = config.disk_space_clean_forcibly_ratio as f64 / 100.0;
+
+        let commit_log_store_path = LocalFileMessageStore::get_store_path_physic(config);
+        let mut min_physic_ratio = 100.0;
+        let mut min_store_path = "";
+        for store_path_physic in commit_log_store_path
+            .trim()
+            .split(mix_all::MULTI_PATH_SPLITTER.as_str())
+        {
+            let physic_ratio = util_all::get_disk_partition_space_used_percent(store_path_physic);
+            if min_physic_ratio > physic_ratio {
+                min_physic_ratio = physic_ratio;
+                min_store_path = store_path_physic;
+            }
+        }
+        if min_physic_ratio > warning_level_ratio {
+            if self.running_flags.get_and_make_disk_full() {
+                error!(
+                    "physic disk maybe full soon {}, so mark disk full, storePathPhysic={}",
+                    min_physic_ratio, min_store_path
+                );
+            }
+            self.clean_immediately.store(true, Ordering::Release);
+            return true;
+        } else if min_physic_ratio > clean_forcibly_ratio {
+            self.clean_immediately.store(true, Ordering::Release);
+            return true;
+        } else if !self.running_flags.get_and_make_disk_ok() {
+            info!(
+                "physic disk space OK {}, so mark disk ok, storePathPhysic={}",
+                min_physic_ratio, min_store_path
+            );
+        }
+
+        let store_path_logics = LocalFileMessageStore::get_store_path_logic(config);
+        let logics_ratio = util_all::get_disk_partition_space_used_percent(&store_path_logics);
+        if logics_ratio > warning_level_ratio {
+            if self.running_flags.get_and_make_logic_disk_full() {
+                error!(
+                    "logics disk maybe full soon {}, so mark disk full",
+                    logics_ratio
+                );
+            }
+            self.clean_immediately.store(true, Ordering::Release);
+            return true;
+        } else if logics_ratio > clean_forcibly_ratio {
+            self.clean_immediately.store(true, Ordering::Release);
+            return true;
+        } else if !self.running_flags.get_and_make_logic_disk_ok() {
+            info!("logics disk space OK {}, so mark disk ok", logics_ratio);
+        }
+
+        let ratio = config.disk_max_used_space_ratio as f64 / 100.0;
+        if min_physic_ratio < 0.0 || min_physic_ratio > ratio {
+            info!(
+                "commitLog disk maybe full soon, so reclaim space, {}",
+                min_physic_ratio
+            );
+            return true;
+        }
+        if logics_ratio < 0.0 || logics_ratio > ratio {
+            info!(
+                "consumeQueue disk maybe full soon, so reclaim space, {}",
+                logics_ratio
+            );
+            return true;
+        }
+        false
     }
 }
 
-struct CleanConsumeQueueService {}
+/// Deletes consume queue and index files that only reference already deleted commit log data.
+struct CleanConsumeQueueService {
+    message_store_config: Arc<MessageStoreConfig>,
+    commit_log: ArcMut<CommitLog>,
+    consume_queue_store: ConsumeQueueStoreImpl,
+    index_service: IndexService,
+    #[cfg(feature = "rocksdb_cq")]
+    rocksdb_consume_queue_store: Option<RocksDBConsumeQueueStore>,
+    last_physical_min_offset: AtomicI64,
+    local_file_retention: LocalFileRetention,
+}
 
 impl CleanConsumeQueueService {
+    fn new(
+        message_store_config: Arc<MessageStoreConfig>,
+        commit_log: ArcMut<CommitLog>,
+        consume_queue_store: ConsumeQueueStoreImpl,
+        index_service: IndexService,
+        #[cfg(feature = "rocksdb_cq")] rocksdb_consume_queue_store: Option<
+            RocksDBConsumeQueueStore,
+        >,
+        local_file_retention: LocalFileRetention,
+    ) -> Self {
+        Self {
+            message_store_config,
+            commit_log,
+            consume_queue_store,
+            index_service,
+            #[cfg(feature = "rocksdb_cq")]
+            rocksdb_consume_queue_store,
+            last_physical_min_offset: AtomicI64::new(0),
+            local_file_retention,
+        }
+    }
+
     fn run(&self) {
-        error!("clean consume queue service run unimplemented!")
+        self.delete_expired_files();
+    }
+
+    fn delete_expired_files(&self) {
+        let delete_logics_files_interval = self
+            .message_store_config
+            .delete_consume_queue_files_interval as u64;
+        let min_offset = self.commit_log.get_min_offset();
+        if min_offset <= self.last_physical_min_offset.load(Ordering::Acquire) {
+            return;
+        }
+
+        let root_dir = self.message_store_config.store_path_root_dir.as_str();
+        let mut kept_for_upload = false;
+        let consume_queue_table = self
+            .consume_queue_store
+            .get_consume_queue_table()
+            .lock()
+            .clone();
+        for queues in consume_queue_table.values() {
+            for logic in queues.values() {
+                let logic = &**logic.as_ref();
+                let delete_offset = self.local_file_retention.consume_queue_delete_offset(
+                    root_dir,
+                    logic.get_topic(),
+                    logic.get_queue_id(),
+                    min_offset,
+                );
+                kept_for_upload |= delete_offset < min_offset;
+                let delete_count = self
+                    .consume_queue_store
+                    .delete_expired_file(logic, delete_offset);
+                if delete_count > 0 && delete_logics_files_interval > 0 {
+                    thread::sleep(Duration::from_millis(delete_logics_files_interval));
+                }
+            }
+        }
+        // files kept for the upload are deleted by a later round at the same min offset
+        if !kept_for_upload {
+            self.last_physical_min_offset
+                .store(min_offset, Ordering::Release);
+        }
+        self.index_service.delete_expired_file(min_offset as u64);
+        #[cfg(feature = "rocksdb_cq")]
+        if let Some(rocksdb_consume_queue_store) = self.rocksdb_consume_queue_store.as_ref() {
+            rocksdb_consume_queue_store.clean_expired_consume_queues(min_offset);
+        }
     }
 }
 
-struct CorrectLogicOffsetService {}
+/// Moves the min offset of consume queues forward past commit log data that has been deleted.
+struct CorrectLogicOffsetService {
+    message_store_config: Arc<MessageStoreConfig>,
+    commit_log: ArcMut<CommitLog>,
+    consume_queue_store: ConsumeQueueStoreImpl,
+    last_force_correct_time: AtomicI64,
+    local_file_retention: LocalFileRetention,
+}
 
 impl CorrectLogicOffsetService {
+    fn new(
+        message_store_config: Arc<MessageStoreConfig>,
+        commit_log: ArcMut<CommitLog>,
+        consume_queue_store: ConsumeQueueStoreImpl,
+        local_file_retention: LocalFileRetention,
+    ) -> Self {
+        Self {
+            message_store_config,
+            commit_log,
+            consume_queue_store,
+            last_force_correct_time: AtomicI64::new(-1),
+            local_file_retention,
+        }
+    }
+
     fn run(&self) {
-        error!("correct logic offset service run unimplemented!")
+        self.correct_logic_min_offset();
+    }
+
+    fn correct_logic_min_offset(&self) {
+        let min_phy_offset = self.commit_log.get_min_offset();
+        if min_phy_offset < 0 {
+            return;
+        }
+        let force_interval = self
+            .message_store_config
+            .correct_logic_min_offset_force_interval as i64;
+        let now = get_current_millis() as i64;
+        let force_correct = force_interval > 0
+            && now - self.last_force_correct_time.load(Ordering::Acquire) > force_interval;
+        if force_correct {
+            self.last_force_correct_time.store(now, Ordering::Release);
+        }
+        let sleep_interval = self
+            .message_store_config
+            .correct_logic_min_offset_sleep_interval as u64;
+
+        let consume_queue_table = self
+            .consume_queue_store
+            .get_consume_queue_table()
+            .lock()
+            .clone();
+        for queues in consume_queue_table.values() {
+            for logic in queues.values() {
+                let logic = &**logic.as_ref();
+                if force_correct || self.need_correct(logic, min_phy_offset) {
+                    let delete_offset = self.local_file_retention.consume_queue_delete_offset(
+                        self.message_store_config.store_path_root_dir.as_str(),
+                        logic.get_topic(),
+                        logic.get_queue_id(),
+                        min_phy_offset,
+                    );
+                    self.consume_queue_store
+                        .delete_expired_file(logic, delete_offset);
+                    if sleep_interval > 0 {
+                        thread::sleep(Duration::from_millis(sleep_interval));
+                    }
+                }
+            }
+        }
+    }
+
+    fn need_correct(&self, logic: &dyn ConsumeQueueTrait, min_phy_offset: i64) -> bool {
+        // The first file was destroyed once but is still alive, delete it again.
+        if self.consume_queue_store.is_first_file_exist(logic)
+            && !self.consume_queue_store.is_first_file_available(logic)
+        {
+            error!(
+                "CorrectLogicOffsetService.needCorrect. first file not available, trigger \
+                 correct. topic:{}, queue:{}, maxPhyOffset in queue:{}, minPhyOffset in commit \
+                 log:{}",
+                logic.get_topic(),
+                logic.get_queue_id(),
+                logic.get_max_physic_offset(),
+                min_phy_offset
+            );
+            return true;
+        }
+        match logic.get_earliest_unit() {
+            Some(cq_unit) => cq_unit.pos < min_phy_offset,
+            None => logic.get_min_offset_in_queue() < logic.get_max_offset_in_queue(),
+        }
     }
 }
 
@@ -2311,3 +3146,415 @@ pub fn parse_delay_level(level_string: &str) -> (BTreeMap<i32, i64>, i32) {
 
     (delay_level_table, max_delay_level)
 }
+
+#[cfg(test)]
+mod tests {
+    use rocketmq_common::common::attribute::Attribute;
+    use rocketmq_common::common::message::message_decoder;
+    use rocketmq_common::common::message::message_single::Message;
+    use rocketmq_common::common::message::MessageTrait;
+    use rocketmq_common::TopicAttributes::TopicAttributes;
+
+    use super::*;
+    use crate::base::store_enum::StoreType;
+    use crate::config::flush_disk_type::FlushDiskType;
+    use crate::queue::single_consume_queue::CQ_STORE_UNIT_SIZE;
+    use crate::store_path_config_helper::get_store_path_compaction_position;
+    use crate::tiered::tiered_metadata::SegmentMetadata;
+
+    const TOPIC: &str = "CleanTopic";
+    const CQ_UNITS_PER_FILE: usize = 4;
+
+    async fn new_store(root: &Path) -> ArcMut<LocalFileMessageStore> {
+        open_store(root, StoreType::LocalFile).await
+    }
+
+    async fn open_store(root: &Path, store_type: StoreType) -> ArcMut<LocalFileMessageStore> {
+        let mut store = build_store(root, store_type);
+        assert!(store.load().await);
+        store
+    }
+
+    fn build_store(root: &Path, store_type: StoreType) -> ArcMut<LocalFileMessageStore> {
+        build_store_with_config(store_config(root, store_type), HashMap::new())
+    }
+
+    fn store_config(root: &Path, store_type: StoreType) -> MessageStoreConfig {
+        MessageStoreConfig {
+            store_path_root_dir: root.to_string_lossy().into_owned().into(),
+            store_type,
+            flush_disk_type: FlushDiskType::AsyncFlush,
+            mapped_file_size_commit_log: 1024,
+            mapped_file_size_consume_queue: CQ_UNITS_PER_FILE * CQ_STORE_UNIT_SIZE as usize,
+            max_hash_slot_num: 64,
+            max_index_num: 256,
+            ..MessageStoreConfig::default()
+        }
+    }
+
+    fn build_store_with_config(
+        message_store_config: MessageStoreConfig,
+        topic_config_table: HashMap<CheetahString, TopicConfig>,
+    ) -> ArcMut<LocalFileMessageStore> {
+        let mut store = ArcMut::new(LocalFileMessageStore::new(
+            Arc::new(message_store_config),
+            Arc::new(BrokerConfig::default()),
+            Arc::new(parking_lot::Mutex::new(topic_config_table)),
+            None,
+            false,
+        ));
+        let store_clone = store.clone();
+        store.set_message_store_arc(store_clone);
+        store
+    }
+
+    /// Puts `count` messages and returns the commit log offset of each of them.
+    async fn put_messages(store: &mut ArcMut<LocalFileMessageStore>, count: usize) -> Vec<i64> {
+        let mut phy_offsets = Vec::with_capacity(count);
+        for i in 0..count {
+            let mut message = Message::new(TOPIC, format!("body-{i:04}").as_bytes());
+            message.set_keys(format!("key-{i}").into());
+            let mut msg = MessageExtBrokerInner {
+                properties_string: message_decoder::message_properties_to_string(
+                    message.get_properties(),
+                ),
+                ..MessageExtBrokerInner::default()
+            };
+            msg.message_ext_inner.set_message_inner(message);
+            msg.message_ext_inner
+                .set_born_host("127.0.0.1:10000".parse().unwrap());
+            msg.message_ext_inner
+                .set_store_host("127.0.0.1:10911".parse().unwrap());
+            let result = store.put_message(msg).await;
+            assert_eq!(result.put_message_status(), PutMessageStatus::PutOk);
+            phy_offsets.push(result.append_message_result().unwrap().wrote_offset);
+        }
+        phy_offsets
+    }
+
+    /// Builds the consume queue and index the way the reput service does.
+    async fn dispatch(store: &ArcMut<LocalFileMessageStore>) {
+        let mut reput = ReputMessageServiceInner {
+            reput_from_offset: Arc::new(AtomicI64::new(0)),
+            commit_log: store.commit_log.clone(),
+            message_store_config: store.message_store_config.clone(),
+            dispatcher: store.dispatcher.clone(),
+            notify_message_arrive_in_batch: true,
+            message_store: store.clone(),
+        };
+        reput.do_reput().await;
+        assert_eq!(reput.reput_from_offset(), store.get_max_phy_offset());
+    }
+
+    /// Deletes the oldest commit log file and runs both consume queue clean up services.
+    fn delete_first_commit_log_file(store: &ArcMut<LocalFileMessageStore>) -> i64 {
+        assert_eq!(store.commit_log.delete_expired_file(0, 0, 1000, true, 1), 1);
+        store.correct_logic_offset_service.run();
+        store.clean_consume_queue_service.run();
+        store.commit_log.get_min_offset()
+    }
+
+    fn commit_log_file_count(store: &ArcMut<LocalFileMessageStore>) -> usize {
+        let store_path_physic =
+            LocalFileMessageStore::get_store_path_physic(&store.message_store_config);
+        fs::read_dir(store_path_physic).unwrap().count()
+    }
+
+    fn consume_queue_file_offsets(root: &Path) -> Vec<u64> {
+        let queue_dir = PathBuf::from(get_store_path_consume_queue(
+            root.to_string_lossy().as_ref(),
+        ))
+        .join(TOPIC)
+        .join("0");
+        let mut offsets: Vec<u64> = fs::read_dir(queue_dir)
+            .unwrap()
+            .map(|entry| {
+                entry
+                    .unwrap()
+                    .file_name()
+                    .to_string_lossy()
+                    .parse()
+                    .unwrap()
+            })
+            .collect();
+        offsets.sort();
+        offsets
+    }
+
+    /// Consume queue files expected to survive a clean up at `min_phy_offset`: a file goes once
+    /// its last unit points below the commit log, and the newest file always stays.
+    fn expected_consume_queue_files(phy_offsets: &[i64], min_phy_offset: i64) -> Vec<u64> {
+        let chunks: Vec<&[i64]> = phy_offsets.chunks(CQ_UNITS_PER_FILE).collect();
+        let first_kept = chunks
+            .iter()
+            .position(|units| *units.last().unwrap() >= min_phy_offset)
+            .unwrap_or(chunks.len() - 1);
+        (first_kept..chunks.len())
+            .map(|file| (file * CQ_UNITS_PER_FILE * CQ_STORE_UNIT_SIZE as usize) as u64)
+            .collect()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn compaction_replays_requests_lost_on_restart() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let message_store_config = MessageStoreConfig {
+            enable_compaction: true,
+            ..store_config(temp_dir.path(), StoreType::LocalFile)
+        };
+        let mut topic_config = TopicConfig::new(TOPIC);
+        topic_config.attributes.insert(
+            TopicAttributes::cleanup_policy_attribute().name().into(),
+            CleanupPolicy::COMPACTION.to_string().into(),
+        );
+        let topic_config_table = HashMap::from([(TOPIC.into(), topic_config)]);
+        {
+            let mut store =
+                build_store_with_config(message_store_config.clone(), topic_config_table.clone());
+            assert!(store.load().await);
+            put_messages(&mut store, 6).await;
+            // the compaction service is not running, the requests are only queued in memory
+            dispatch(&store).await;
+        }
+
+        let mut store = build_store_with_config(message_store_config, topic_config_table);
+        assert!(store.load().await);
+        let compaction_service = store.compaction_service.clone().unwrap();
+        compaction_service.start();
+        let topic = CheetahString::from_static_str(TOPIC);
+        let mut queue_offsets = Vec::new();
+        for _ in 0..100 {
+            let result = store
+                .compaction_store
+                .get_message(&"group".into(), &topic, 0, 0, 32, 1024 * 1024)
+                .unwrap();
+            queue_offsets = result.message_queue_offset().clone();
+            if queue_offsets.len() == 6 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert_eq!(queue_offsets, (0..6).collect::<Vec<u64>>());
+
+        compaction_service.shutdown();
+        let position = fs::read_to_string(get_store_path_compaction_position(
+            temp_dir.path().to_string_lossy().as_ref(),
+        ))
+        .unwrap();
+        assert_eq!(position, store.get_max_phy_offset().to_string());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn clean_services_follow_the_commit_log_min_offset() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut store = new_store(temp_dir.path()).await;
+        let phy_offsets = put_messages(&mut store, 30).await;
+        dispatch(&store).await;
+        let commit_log_files = commit_log_file_count(&store);
+        assert!(commit_log_files >= 4, "{commit_log_files} commit log files");
+        let topic = CheetahString::from_static_str(TOPIC);
+        assert_eq!(store.get_min_offset_in_queue(&topic, 0), 0);
+        assert_eq!(store.get_max_offset_in_queue(&topic, 0), 30);
+
+        // Two rounds: the second one searches the new min offset from the previous one.
+        for _ in 0..2 {
+            let min_phy_offset = delete_first_commit_log_file(&store);
+            let first_live = phy_offsets
+                .iter()
+                .position(|offset| *offset >= min_phy_offset)
+                .unwrap();
+            assert!(first_live > 0);
+            // the message right before the min offset is gone, the first live one is kept
+            assert!(phy_offsets[first_live - 1] < min_phy_offset);
+            assert_eq!(store.get_min_offset_in_queue(&topic, 0), first_live as i64);
+            assert_eq!(
+                consume_queue_file_offsets(temp_dir.path()),
+                expected_consume_queue_files(&phy_offsets, min_phy_offset)
+            );
+            // the only index file is still being written
+            assert!(store.index_service.get_total_size() > 0);
+        }
+        assert_eq!(store.get_max_offset_in_queue(&topic, 0), 30);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn clean_services_wait_for_tiered_upload() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let message_store_config = MessageStoreConfig {
+            file_reserved_time: 0,
+            delete_commit_log_files_interval: 0,
+            ..store_config(temp_dir.path(), StoreType::LocalFile)
+        };
+        let mut store = build_store_with_config(message_store_config, HashMap::new());
+        assert!(store.load().await);
+        let phy_offsets = put_messages(&mut store, 30).await;
+        dispatch(&store).await;
+        let tiered_metadata = Arc::new(TieredMetadataStore::new(
+            temp_dir.path().join("tiered").join("metadata.json"),
+        ));
+        store.set_tiered_metadata(tiered_metadata.clone());
+        let commit_log_files = commit_log_file_count(&store);
+        let consume_queue_files = consume_queue_file_offsets(temp_dir.path());
+        let clean = |store: &ArcMut<LocalFileMessageStore>| {
+            store
+                .clean_commit_log_service
+                .execute_delete_files_manually();
+            store.clean_commit_log_service.run();
+            store.correct_logic_offset_service.run();
+            store.clean_consume_queue_service.run();
+        };
+        let segment = |base_offset: i64| SegmentMetadata {
+            key: base_offset.to_string(),
+            base_offset,
+            size: 0,
+            last_modified: 0,
+        };
+
+        // nothing is uploaded, nothing is deleted
+        clean(&store);
+        assert_eq!(commit_log_file_count(&store), commit_log_files);
+        assert_eq!(store.commit_log.get_min_offset(), 0);
+
+        // only the first commit log file is uploaded
+        tiered_metadata.add_commit_log_segment(segment(0));
+        clean(&store);
+        assert_eq!(commit_log_file_count(&store), commit_log_files - 1);
+        let min_phy_offset = store.commit_log.get_min_offset();
+        assert_eq!(min_phy_offset, 1024);
+        // the consume queue files are not uploaded yet
+        assert_eq!(
+            consume_queue_file_offsets(temp_dir.path()),
+            consume_queue_files
+        );
+
+        let expected = expected_consume_queue_files(&phy_offsets, min_phy_offset);
+        assert!(expected.len() < consume_queue_files.len());
+        let topic = CheetahString::from_static_str(TOPIC);
+        for base_offset in &consume_queue_files {
+            tiered_metadata.add_consume_queue_segment(&topic, 0, segment(*base_offset as i64));
+        }
+        clean(&store);
+        assert_eq!(consume_queue_file_offsets(temp_dir.path()), expected);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn correct_min_offset_moves_past_a_fully_expired_queue() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut store = new_store(temp_dir.path()).await;
+        put_messages(&mut store, 6).await;
+        dispatch(&store).await;
+        // All messages of the queue live in the first commit log files, fill more files with
+        // another topic so that they can expire.
+        let commit_log_files = commit_log_file_count(&store);
+        let other = Message::new("OtherTopic", &[0u8; 256]);
+        while commit_log_file_count(&store) < commit_log_files + 2 {
+            let mut msg = MessageExtBrokerInner {
+                properties_string: message_decoder::message_properties_to_string(
+                    other.get_properties(),
+                ),
+                ..MessageExtBrokerInner::default()
+            };
+            msg.message_ext_inner.set_message_inner(other.clone());
+            msg.message_ext_inner
+                .set_born_host("127.0.0.1:10000".parse().unwrap());
+            msg.message_ext_inner
+                .set_store_host("127.0.0.1:10911".parse().unwrap());
+            assert!(store.put_message(msg).await.is_ok());
+        }
+        dispatch(&store).await;
+        for _ in 0..commit_log_files {
+            delete_first_commit_log_file(&store);
+        }
+
+        let topic = CheetahString::from_static_str(TOPIC);
+        // nothing readable is left, the min offset points right after the last unit
+        assert_eq!(store.get_min_offset_in_queue(&topic, 0), 6);
+        assert_eq!(store.get_max_offset_in_queue(&topic, 0), 6);
+        assert_eq!(consume_queue_file_offsets(temp_dir.path()), vec![80]);
+    }
+
+    #[cfg(not(feature = "rocksdb_cq"))]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn rocksdb_store_type_fails_to_load_without_rocksdb_feature() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut store = build_store(temp_dir.path(), StoreType::RocksDB);
+        assert!(!store.load().await);
+    }
+
+    #[cfg(feature = "rocksdb_cq")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn rocksdb_store_type_keeps_consume_queues_in_rocksdb() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let topic = CheetahString::from_static_str(TOPIC);
+        {
+            let mut store = open_store(temp_dir.path(), StoreType::RocksDB).await;
+            assert!(store.consume_queue_store.is_rocksdb());
+            let phy_offsets = put_messages(&mut store, 10).await;
+            dispatch(&store).await;
+            assert_eq!(store.get_min_offset_in_queue(&topic, 0), 0);
+            assert_eq!(store.get_max_offset_in_queue(&topic, 0), 10);
+            let consume_queue = store.find_consume_queue(&topic, 0).unwrap();
+            assert_eq!(consume_queue.get_max_physic_offset(), phy_offsets[9]);
+            let result = store
+                .get_message(&"group".into(), &topic, 0, 2, 32, None)
+                .await
+                .unwrap();
+            assert_eq!(result.status(), Some(GetMessageStatus::Found));
+            assert_eq!(result.message_count(), 8);
+            assert_eq!(result.next_begin_offset(), 10);
+            // no file consume queue is written
+            assert!(!Path::new(&get_store_path_consume_queue(
+                temp_dir.path().to_string_lossy().as_ref()
+            ))
+            .join(TOPIC)
+            .exists());
+            ConsumeQueueStoreTrait::shutdown(&store.consume_queue_store);
+        }
+
+        // queue offsets are recovered from RocksDB and new messages continue after them
+        let mut store = open_store(temp_dir.path(), StoreType::RocksDB).await;
+        assert_eq!(store.get_min_offset_in_queue(&topic, 0), 0);
+        assert_eq!(store.get_max_offset_in_queue(&topic, 0), 10);
+        let mut message = Message::new(TOPIC, b"body-after-restart");
+        message.set_keys("key-after-restart".into());
+        let mut msg = MessageExtBrokerInner {
+            properties_string: message_decoder::message_properties_to_string(
+                message.get_properties(),
+            ),
+            ..MessageExtBrokerInner::default()
+        };
+        msg.message_ext_inner.set_message_inner(message);
+        msg.message_ext_inner
+            .set_born_host("127.0.0.1:10000".parse().unwrap());
+        msg.message_ext_inner
+            .set_store_host("127.0.0.1:10911".parse().unwrap());
+        let result = store.put_message(msg).await;
+        assert_eq!(result.put_message_status(), PutMessageStatus::PutOk);
+        assert_eq!(result.append_message_result().unwrap().logics_offset, 10);
+        ConsumeQueueStoreTrait::shutdown(&store.consume_queue_store);
+    }
+
+    #[cfg(feature = "rocksdb_cq")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn rocksdb_store_type_keeps_lmq_offsets_apart() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut store = open_store(temp_dir.path(), StoreType::RocksDB).await;
+        let mut topic_queue_table = HashMap::new();
+        topic_queue_table.insert(CheetahString::from_static_str("%LMQ%lmq-0"), 5);
+        topic_queue_table.insert(CheetahString::from_static_str("NormalTopic-0"), 3);
+        store
+            .consume_queue_store_mut()
+            .set_topic_queue_table(topic_queue_table);
+        let consume_queue_store = &store.consume_queue_store;
+        assert_eq!(consume_queue_store.get_lmq_queue_offset("%LMQ%lmq-0"), 5);
+        assert_eq!(consume_queue_store.get_lmq_queue_offset("NormalTopic-0"), 0);
+        assert_eq!(
+            consume_queue_store.get_max_offset(&"NormalTopic".into(), 0),
+            Some(3)
+        );
+        assert_eq!(
+            consume_queue_store.get_max_offset(&"%LMQ%lmq".into(), 0),
+            Some(0)
+        );
+        ConsumeQueueStoreTrait::shutdown(&store.consume_queue_store);
+    }
+}
@@ -28,11 +28,18 @@ mod batch_consume_queue;
 pub mod build_consume_queue;
 pub mod consume_queue;
 mod consume_queue_ext;
+#[cfg(feature = "rocksdb_cq")]
+mod consume_queue_rocksdb_storage;
 pub mod consume_queue_store;
+pub mod consume_queue_store_impl;
 mod file_queue_life_cycle;
 pub mod local_file_consume_queue_store;
 mod queue_offset_operator;
 pub mod referred_iterator;
+#[cfg(feature = "rocksdb_cq")]
+pub mod rocksdb_consume_queue;
+#[cfg(feature = "rocksdb_cq")]
+pub mod rocksdb_consume_queue_store;
 pub mod single_consume_queue;
 
 pub type ArcConsumeQueue = ArcMut<Box<dyn ConsumeQueueTrait>>;
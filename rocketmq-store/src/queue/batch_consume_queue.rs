@@ -18,25 +18,38 @@
 use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::sync::atomic::AtomicI64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
+use bytes::Buf;
+use bytes::BufMut;
 use cheetah_string::CheetahString;
 use rocketmq_common::common::attribute::cq_type::CQType;
 use rocketmq_common::common::boundary_type::BoundaryType;
+use rocketmq_common::common::broker::broker_role::BrokerRole;
 use rocketmq_common::common::message::message_ext_broker_inner::MessageExtBrokerInner;
+use rocketmq_common::common::message::MessageConst;
+use rocketmq_common::common::message::MessageTrait;
+use rocketmq_common::common::sys_flag::message_sys_flag::MessageSysFlag;
+use rocketmq_common::MessageDecoder;
+use tracing::error;
 use tracing::info;
+use tracing::warn;
 
 use crate::base::dispatch_request::DispatchRequest;
+use crate::base::store_checkpoint::StoreCheckpoint;
 use crate::base::swappable::Swappable;
 use crate::config::message_store_config::MessageStoreConfig;
 use crate::consume_queue::mapped_file_queue::MappedFileQueue;
 use crate::filter::MessageFilter;
 use crate::log_file::mapped_file::default_mapped_file_impl::DefaultMappedFile;
+use crate::log_file::mapped_file::MappedFile;
 use crate::queue::consume_queue::ConsumeQueueTrait;
 use crate::queue::queue_offset_operator::QueueOffsetOperator;
 use crate::queue::referred_iterator::ReferredIterator;
 use crate::queue::CqUnit;
 use crate::queue::FileQueueLifeCycle;
+use crate::store::running_flags::RunningFlags;
 
 const CQ_STORE_UNIT_SIZE: i32 = 46;
 const MSG_TAG_OFFSET_INDEX: i32 = 12;
@@ -63,7 +76,8 @@ const INVALID_POS: i32 = -1;
 pub struct BatchConsumeQueue {
     message_store_config: Arc<MessageStoreConfig>,
     mapped_file_queue: MappedFileQueue,
-    //message_store: Arc<RwLock<dyn MessageStore>>,
+    store_checkpoint: Arc<StoreCheckpoint>,
+    running_flags: Arc<RunningFlags>,
     topic: CheetahString,
     queue_id: i32,
     byte_buffer_item: Vec<u8>,
@@ -74,12 +88,15 @@ pub struct BatchConsumeQueue {
     max_offset_in_queue: Arc<AtomicI64>,
     min_offset_in_queue: Arc<AtomicI64>,
     commit_log_size: i32,
-    offset_cache: Arc<parking_lot::RwLock<BTreeMap<i64, DefaultMappedFile>>>,
-    time_cache: Arc<parking_lot::RwLock<BTreeMap<i64, DefaultMappedFile>>>,
+    /// First msg base offset of every file, used to locate the file of a queue offset.
+    offset_cache: Arc<parking_lot::RwLock<BTreeMap<i64, Arc<DefaultMappedFile>>>>,
+    /// First store time of every file, used to locate the file of a timestamp.
+    time_cache: Arc<parking_lot::RwLock<BTreeMap<i64, Arc<DefaultMappedFile>>>>,
 }
 
 impl BatchConsumeQueue {
     #[inline]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         topic: CheetahString,
         queue_id: i32,
@@ -87,6 +104,8 @@ impl BatchConsumeQueue {
         mapped_file_size: usize,
         subfolder: Option<CheetahString>,
         message_store_config: Arc<MessageStoreConfig>,
+        store_checkpoint: Arc<StoreCheckpoint>,
+        running_flags: Arc<RunningFlags>,
     ) -> Self {
         let commit_log_size = message_store_config.mapped_file_size_commit_log;
 
@@ -111,11 +130,13 @@ impl BatchConsumeQueue {
             )
         };
 
-        let byte_buffer_item = vec![0u8; CQ_STORE_UNIT_SIZE as usize];
+        let byte_buffer_item = Vec::with_capacity(CQ_STORE_UNIT_SIZE as usize);
 
         BatchConsumeQueue {
             message_store_config,
             mapped_file_queue,
+            store_checkpoint,
+            running_flags,
             topic,
             queue_id,
             byte_buffer_item,
@@ -132,7 +153,299 @@ impl BatchConsumeQueue {
     }
 }
 
-#[allow(unused_variables)]
+impl BatchConsumeQueue {
+    pub fn put_batch_message_position_info(
+        &mut self,
+        offset: i64,
+        size: i32,
+        tags_code: i64,
+        store_time: i64,
+        msg_base_offset: i64,
+        batch_size: i16,
+    ) -> bool {
+        if offset <= self.get_max_physic_offset() {
+            warn!(
+                "Build batch consume queue repeatedly, maxMsgPhyOffsetInCommitLog:{} offset:{} \
+                 Topic: {} QID: {}",
+                self.get_max_physic_offset(),
+                offset,
+                self.topic,
+                self.queue_id
+            );
+            return true;
+        }
+
+        self.byte_buffer_item.clear();
+        self.byte_buffer_item.put_i64(offset);
+        self.byte_buffer_item.put_i32(size);
+        self.byte_buffer_item.put_i64(tags_code);
+        self.byte_buffer_item.put_i64(store_time);
+        self.byte_buffer_item.put_i64(msg_base_offset);
+        self.byte_buffer_item.put_i16(batch_size);
+        self.byte_buffer_item.put_i32(INVALID_POS);
+        // reserved
+        self.byte_buffer_item.put_i32(0);
+
+        let expect_logic_offset = self.mapped_file_queue.get_max_offset();
+        let Some(mapped_file) = self
+            .mapped_file_queue
+            .get_last_mapped_file_mut_start_offset(expect_logic_offset as u64, true)
+        else {
+            return false;
+        };
+        let is_new_file = Self::is_new_file(&mapped_file);
+        if !mapped_file.append_message_bytes(&self.byte_buffer_item) {
+            return false;
+        }
+        self.max_msg_phy_offset_in_commit_log
+            .store(offset, Ordering::SeqCst);
+        self.max_offset_in_queue
+            .store(msg_base_offset + batch_size as i64, Ordering::SeqCst);
+        // only the first time need to correct the min offset, the rest is done by the
+        // correct logic offset service
+        if mapped_file.is_first_create_in_queue()
+            && self.min_offset_in_queue.load(Ordering::Acquire) == -1
+        {
+            self.revise_min_offset_in_queue();
+        }
+        if is_new_file {
+            self.cache_bcq(msg_base_offset, store_time, mapped_file);
+        }
+        true
+    }
+
+    #[inline]
+    fn is_new_file(mapped_file: &DefaultMappedFile) -> bool {
+        mapped_file.get_read_position() < CQ_STORE_UNIT_SIZE
+    }
+
+    fn cache_bcq(
+        &self,
+        msg_base_offset: i64,
+        store_time: i64,
+        mapped_file: Arc<DefaultMappedFile>,
+    ) {
+        if !self.message_store_config.search_bcq_by_cache_enable {
+            return;
+        }
+        self.offset_cache
+            .write()
+            .insert(msg_base_offset, mapped_file.clone());
+        self.time_cache.write().insert(store_time, mapped_file);
+    }
+
+    fn refresh_cache(&self) {
+        if !self.message_store_config.search_bcq_by_cache_enable {
+            return;
+        }
+        let mut offset_cache = BTreeMap::new();
+        let mut time_cache = BTreeMap::new();
+        for mapped_file in self.mapped_file_queue.get_mapped_files().read().iter() {
+            if let Some((unit, store_time)) = Self::get_min_unit(mapped_file) {
+                offset_cache.insert(unit.queue_offset, mapped_file.clone());
+                time_cache.insert(store_time, mapped_file.clone());
+            }
+        }
+        *self.offset_cache.write() = offset_cache;
+        *self.time_cache.write() = time_cache;
+    }
+
+    /// Reads the unit stored at `pos` of `mapped_file`, returning it with its store time.
+    fn read_unit(mapped_file: &DefaultMappedFile, pos: i32) -> Option<(CqUnit, i64)> {
+        if pos < 0 || pos + CQ_STORE_UNIT_SIZE > mapped_file.get_read_position() {
+            return None;
+        }
+        let mut bytes = mapped_file.get_bytes(pos as usize, CQ_STORE_UNIT_SIZE as usize)?;
+        let offset = bytes.get_i64();
+        let size = bytes.get_i32();
+        let tags_code = bytes.get_i64();
+        let store_time = bytes.get_i64();
+        let msg_base_offset = bytes.get_i64();
+        let batch_size = bytes.get_i16();
+        let compacted_offset = bytes.get_i32();
+        if offset < 0 || size <= 0 || msg_base_offset < 0 || batch_size <= 0 {
+            return None;
+        }
+        let cq_unit = CqUnit {
+            queue_offset: msg_base_offset,
+            size,
+            pos: offset,
+            batch_num: batch_size,
+            tags_code,
+            compacted_offset,
+            ..CqUnit::default()
+        };
+        Some((cq_unit, store_time))
+    }
+
+    #[inline]
+    fn read_i64(mapped_file: &DefaultMappedFile, pos: i32) -> i64 {
+        mapped_file
+            .get_bytes(pos as usize, 8)
+            .map_or(-1, |mut bytes| bytes.get_i64())
+    }
+
+    #[inline]
+    fn unit_count(mapped_file: &DefaultMappedFile) -> i32 {
+        mapped_file.get_read_position() / CQ_STORE_UNIT_SIZE
+    }
+
+    #[inline]
+    fn get_min_unit(mapped_file: &DefaultMappedFile) -> Option<(CqUnit, i64)> {
+        Self::read_unit(mapped_file, 0)
+    }
+
+    #[inline]
+    fn get_max_unit(mapped_file: &DefaultMappedFile) -> Option<(CqUnit, i64)> {
+        Self::read_unit(
+            mapped_file,
+            (Self::unit_count(mapped_file) - 1) * CQ_STORE_UNIT_SIZE,
+        )
+    }
+
+    /// Index of the first unit in `mapped_file` for which `pred` on the i64 stored at `shift`
+    /// is true, or the unit count when there is none.
+    fn partition_point(
+        mapped_file: &DefaultMappedFile,
+        shift: i32,
+        pred: impl Fn(i64) -> bool,
+    ) -> i32 {
+        let (mut low, mut high) = (0, Self::unit_count(mapped_file));
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if pred(Self::read_i64(
+                mapped_file,
+                mid * CQ_STORE_UNIT_SIZE + shift,
+            )) {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        low
+    }
+
+    fn revise_min_offset_in_queue(&self) {
+        let Some(first_mapped_file) = self.mapped_file_queue.get_first_mapped_file() else {
+            self.max_offset_in_queue.store(0, Ordering::SeqCst);
+            self.min_offset_in_queue.store(-1, Ordering::SeqCst);
+            self.min_logic_offset.store(-1, Ordering::SeqCst);
+            info!(
+                "reviseMinOffsetInQueue found firstMappedFile null, topic:{} queue:{}",
+                self.topic, self.queue_id
+            );
+            return;
+        };
+        self.min_logic_offset.store(
+            first_mapped_file.get_file_from_offset() as i64,
+            Ordering::SeqCst,
+        );
+        let min_offset =
+            Self::get_min_unit(&first_mapped_file).map_or(-1, |(unit, _)| unit.queue_offset);
+        self.min_offset_in_queue.store(min_offset, Ordering::SeqCst);
+    }
+
+    fn revise_max_offset_in_queue(&self) {
+        let mapped_files = self.mapped_file_queue.get_mapped_files();
+        let mapped_files = mapped_files.read();
+        // the last file may have just been created, fall back to the one before it
+        let max_unit = mapped_files
+            .iter()
+            .rev()
+            .take(2)
+            .find_map(|mapped_file| Self::get_max_unit(mapped_file));
+        let max_offset = max_unit.map_or(0, |(unit, _)| unit.queue_offset + unit.batch_num as i64);
+        self.max_offset_in_queue.store(max_offset, Ordering::SeqCst);
+    }
+
+    fn revise_max_and_min_offset_in_queue(&self) {
+        self.revise_min_offset_in_queue();
+        self.revise_max_offset_in_queue();
+    }
+
+    /// Finds the file whose units cover `msg_offset`.
+    fn search_file_by_offset(&self, msg_offset: i64) -> Option<Arc<DefaultMappedFile>> {
+        if self.message_store_config.search_bcq_by_cache_enable {
+            if let Some((_, mapped_file)) =
+                self.offset_cache.read().range(..=msg_offset).next_back()
+            {
+                return Some(mapped_file.clone());
+            }
+        }
+        self.search_file(|mapped_file| {
+            Self::get_min_unit(mapped_file).map(|(unit, _)| unit.queue_offset <= msg_offset)
+        })
+    }
+
+    /// Finds the last file whose first unit was stored at or before `timestamp`, or the first
+    /// file when every unit is younger.
+    fn search_file_by_time(&self, timestamp: i64) -> Option<Arc<DefaultMappedFile>> {
+        if self.message_store_config.search_bcq_by_cache_enable {
+            if let Some((_, mapped_file)) = self.time_cache.read().range(..=timestamp).next_back() {
+                return Some(mapped_file.clone());
+            }
+        }
+        self.search_file(|mapped_file| {
+            Self::get_min_unit(mapped_file).map(|(_, store_time)| store_time <= timestamp)
+        })
+    }
+
+    /// Returns the last file for which `starts_before` holds, or the first file when it holds
+    /// for none. Files without units are skipped.
+    fn search_file(
+        &self,
+        starts_before: impl Fn(&DefaultMappedFile) -> Option<bool>,
+    ) -> Option<Arc<DefaultMappedFile>> {
+        let mapped_files = self.mapped_file_queue.get_mapped_files();
+        let mapped_files = mapped_files.read();
+        mapped_files
+            .iter()
+            .rev()
+            .find(|mapped_file| starts_before(mapped_file).unwrap_or(false))
+            .or_else(|| mapped_files.first())
+            .cloned()
+    }
+
+    /// Locates the unit holding `msg_offset`, which may point inside a batch.
+    fn get_batch_msg_index(&self, msg_offset: i64) -> Option<(Arc<DefaultMappedFile>, i32)> {
+        let min_offset = self.get_min_offset_in_queue();
+        let msg_offset = msg_offset.max(min_offset);
+        if msg_offset >= self.get_max_offset_in_queue() {
+            return None;
+        }
+        let mapped_file = if msg_offset <= min_offset {
+            self.mapped_file_queue.get_first_mapped_file()?
+        } else {
+            self.search_file_by_offset(msg_offset)?
+        };
+        let after = Self::partition_point(&mapped_file, MSG_BASE_OFFSET_INDEX, |base| {
+            base > msg_offset
+        });
+        let index = (after - 1).max(0);
+        let (unit, _) = Self::read_unit(&mapped_file, index * CQ_STORE_UNIT_SIZE)?;
+        if msg_offset >= unit.queue_offset + unit.batch_num as i64 {
+            return None;
+        }
+        Some((mapped_file, index * CQ_STORE_UNIT_SIZE))
+    }
+
+    fn get_unit_and_store_time(&self, index: i64) -> Option<(CqUnit, i64)> {
+        let (mapped_file, pos) = self.get_batch_msg_index(index)?;
+        Self::read_unit(&mapped_file, pos)
+    }
+
+    fn get_last_unit(&self) -> Option<(CqUnit, i64)> {
+        let mapped_file = self.mapped_file_queue.get_last_mapped_file()?;
+        Self::get_max_unit(&mapped_file)
+    }
+
+    fn set_positions(mapped_file: &DefaultMappedFile, pos: i32) {
+        mapped_file.set_wrote_position(pos);
+        mapped_file.set_committed_position(pos);
+        mapped_file.set_flushed_position(pos);
+    }
+}
+
 impl FileQueueLifeCycle for BatchConsumeQueue {
     #[inline]
     fn load(&mut self) -> bool {
@@ -144,166 +457,398 @@ impl FileQueueLifeCycle for BatchConsumeQueue {
             if result { "OK" } else { "Failed" },
             self.mapped_file_queue.get_mapped_files_size()
         );
+        if result {
+            self.refresh_cache();
+        }
         result
     }
 
-    #[inline]
     fn recover(&mut self) {
-        todo!()
+        let binding = self.mapped_file_queue.get_mapped_files();
+        let mapped_files = binding.read().clone();
+        if mapped_files.is_empty() {
+            return;
+        }
+        let mapped_file_size = self.mapped_file_size as i64;
+        let mut index = mapped_files.len().saturating_sub(3);
+        let mut mapped_file = &mapped_files[index];
+        let mut process_offset = mapped_file.get_file_from_offset() as i64;
+        let mut mapped_file_offset = 0i64;
+        loop {
+            for pos in (0..mapped_file_size).step_by(CQ_STORE_UNIT_SIZE as usize) {
+                let Some(mut bytes) =
+                    mapped_file.get_bytes(pos as usize, CQ_STORE_UNIT_SIZE as usize)
+                else {
+                    break;
+                };
+                let offset = bytes.get_i64();
+                let size = bytes.get_i32();
+                let _tags_code = bytes.get_i64();
+                let _store_time = bytes.get_i64();
+                let msg_base_offset = bytes.get_i64();
+                let batch_size = bytes.get_i16();
+                if offset >= 0 && size > 0 && msg_base_offset >= 0 && batch_size > 0 {
+                    mapped_file_offset = pos + CQ_STORE_UNIT_SIZE as i64;
+                    self.max_msg_phy_offset_in_commit_log
+                        .store(offset, Ordering::SeqCst);
+                } else {
+                    info!(
+                        "Recover current batch consume queue file over, file:{} offset:{} size:{} \
+                         msgBaseOffset:{} batchSize:{} mappedFileOffset:{}",
+                        mapped_file.get_file_name(),
+                        offset,
+                        size,
+                        msg_base_offset,
+                        batch_size,
+                        mapped_file_offset
+                    );
+                    break;
+                }
+            }
+            if mapped_file_offset == mapped_file_size {
+                index += 1;
+                if index >= mapped_files.len() {
+                    info!(
+                        "Recover last batch consume queue file over, last mapped file:{}",
+                        mapped_file.get_file_name()
+                    );
+                    break;
+                }
+                mapped_file = &mapped_files[index];
+                process_offset = mapped_file.get_file_from_offset() as i64;
+                mapped_file_offset = 0;
+                info!(
+                    "Recover next batch consume queue file: {}",
+                    mapped_file.get_file_name()
+                );
+            } else {
+                break;
+            }
+        }
+        process_offset += mapped_file_offset;
+        self.mapped_file_queue.set_flushed_where(process_offset);
+        self.mapped_file_queue.set_committed_where(process_offset);
+        self.mapped_file_queue.truncate_dirty_files(process_offset);
+        self.revise_max_and_min_offset_in_queue();
+        self.refresh_cache();
     }
 
     #[inline]
     fn check_self(&self) {
-        todo!()
+        self.mapped_file_queue.check_self();
     }
 
     #[inline]
     fn flush(&self, flush_least_pages: i32) -> bool {
-        todo!()
+        self.mapped_file_queue.flush(flush_least_pages)
     }
 
     #[inline]
     fn destroy(&mut self) {
-        todo!()
+        self.max_msg_phy_offset_in_commit_log
+            .store(-1, Ordering::SeqCst);
+        self.min_logic_offset.store(0, Ordering::SeqCst);
+        self.max_offset_in_queue.store(0, Ordering::SeqCst);
+        self.min_offset_in_queue.store(-1, Ordering::SeqCst);
+        self.mapped_file_queue.destroy();
+        self.offset_cache.write().clear();
+        self.time_cache.write().clear();
     }
 
-    #[inline]
     fn truncate_dirty_logic_files(&mut self, max_commit_log_pos: i64) {
-        todo!()
+        let logic_file_size = self.mapped_file_size as i32;
+        self.max_msg_phy_offset_in_commit_log
+            .store(max_commit_log_pos - 1, Ordering::SeqCst);
+        'files: while let Some(mapped_file) = self.mapped_file_queue.get_last_mapped_file() {
+            Self::set_positions(&mapped_file, 0);
+            for pos in (0..logic_file_size).step_by(CQ_STORE_UNIT_SIZE as usize) {
+                let Some(mut bytes) =
+                    mapped_file.get_bytes(pos as usize, CQ_STORE_UNIT_SIZE as usize)
+                else {
+                    break 'files;
+                };
+                let offset = bytes.get_i64();
+                let size = bytes.get_i32();
+                let _tags_code = bytes.get_i64();
+                let _store_time = bytes.get_i64();
+                let msg_base_offset = bytes.get_i64();
+                let batch_size = bytes.get_i16();
+                if pos == 0 {
+                    if offset >= max_commit_log_pos {
+                        self.mapped_file_queue.delete_last_mapped_file();
+                        continue 'files;
+                    }
+                } else if offset < 0
+                    || size <= 0
+                    || msg_base_offset < 0
+                    || batch_size <= 0
+                    || offset >= max_commit_log_pos
+                {
+                    break 'files;
+                }
+                let next_pos = pos + CQ_STORE_UNIT_SIZE;
+                Self::set_positions(&mapped_file, next_pos);
+                self.max_msg_phy_offset_in_commit_log
+                    .store(offset, Ordering::SeqCst);
+                if next_pos == logic_file_size {
+                    break 'files;
+                }
+            }
+            break;
+        }
+        self.revise_max_and_min_offset_in_queue();
+        self.refresh_cache();
+        info!(
+            "Truncate batch consume queue {}-{} by max commit log pos {}, min offset in queue {}, \
+             max offset in queue {}",
+            self.topic,
+            self.queue_id,
+            max_commit_log_pos,
+            self.get_min_offset_in_queue(),
+            self.get_max_offset_in_queue()
+        );
     }
 
     #[inline]
     fn delete_expired_file(&self, min_commit_log_pos: i64) -> i32 {
-        todo!()
+        let count = self
+            .mapped_file_queue
+            .delete_expired_file_by_offset(min_commit_log_pos, CQ_STORE_UNIT_SIZE);
+        self.correct_min_offset(min_commit_log_pos);
+        count
     }
 
-    #[inline]
+    /// Returns the first queue offset of the file after the one holding `next_begin_offset`,
+    /// or the max offset when that file is the last one.
     fn roll_next_file(&self, next_begin_offset: i64) -> i64 {
-        todo!()
+        let Some(current) = self.search_file_by_offset(next_begin_offset) else {
+            return self.get_max_offset_in_queue();
+        };
+        let mapped_files = self.mapped_file_queue.get_mapped_files();
+        let mapped_files = mapped_files.read();
+        mapped_files
+            .iter()
+            .skip_while(|mapped_file| !Arc::ptr_eq(mapped_file, &current))
+            .skip(1)
+            .find_map(|mapped_file| Self::get_min_unit(mapped_file))
+            .map_or(self.get_max_offset_in_queue(), |(unit, _)| {
+                unit.queue_offset
+            })
     }
 
     #[inline]
     fn is_first_file_available(&self) -> bool {
-        todo!()
+        self.mapped_file_queue.is_first_file_available()
     }
 
     #[inline]
     fn is_first_file_exist(&self) -> bool {
-        todo!()
+        self.mapped_file_queue.is_first_file_exist()
     }
 }
 
 impl Swappable for BatchConsumeQueue {
+    // mapped files can't be swapped out yet, nothing to do
     #[inline]
     fn swap_map(
         &self,
-        reserve_num: i32,
-        force_swap_interval_ms: i64,
-        normal_swap_interval_ms: i64,
+        _reserve_num: i32,
+        _force_swap_interval_ms: i64,
+        _normal_swap_interval_ms: i64,
     ) {
-        todo!()
     }
 
     #[inline]
-    fn clean_swapped_map(&self, force_clean_swap_interval_ms: i64) {
-        todo!()
-    }
+    fn clean_swapped_map(&self, _force_clean_swap_interval_ms: i64) {}
 }
 
 impl ConsumeQueueTrait for BatchConsumeQueue {
     #[inline]
     fn get_topic(&self) -> &CheetahString {
-        todo!()
+        &self.topic
     }
 
     #[inline]
     fn get_queue_id(&self) -> i32 {
-        todo!()
+        self.queue_id
     }
 
     #[inline]
     fn get(&self, index: i64) -> Option<CqUnit> {
-        todo!()
+        self.get_unit_and_store_time(index).map(|(unit, _)| unit)
     }
 
     #[inline]
     fn get_cq_unit_and_store_time(&self, index: i64) -> Option<(CqUnit, i64)> {
-        todo!()
+        self.get_unit_and_store_time(index)
     }
 
     #[inline]
     fn get_earliest_unit_and_store_time(&self) -> Option<(CqUnit, i64)> {
-        todo!()
+        self.get_unit_and_store_time(self.get_min_offset_in_queue())
     }
 
     #[inline]
     fn get_earliest_unit(&self) -> Option<CqUnit> {
-        todo!()
+        self.get(self.get_min_offset_in_queue())
     }
 
     #[inline]
     fn get_latest_unit(&self) -> Option<CqUnit> {
-        todo!()
+        self.get_last_unit().map(|(unit, _)| unit)
     }
 
     #[inline]
     fn get_last_offset(&self) -> i64 {
-        todo!()
+        self.get_last_unit()
+            .map_or(-1, |(unit, _)| unit.pos + unit.size as i64)
     }
 
     #[inline]
     fn get_min_offset_in_queue(&self) -> i64 {
-        todo!()
+        // -1 marks an empty queue, which still starts at 0
+        self.min_offset_in_queue.load(Ordering::Acquire).max(0)
     }
 
     #[inline]
     fn get_max_offset_in_queue(&self) -> i64 {
-        todo!()
+        self.max_offset_in_queue.load(Ordering::Acquire)
     }
 
     #[inline]
     fn get_message_total_in_queue(&self) -> i64 {
-        todo!()
+        self.get_max_offset_in_queue() - self.get_min_offset_in_queue()
     }
 
     #[inline]
     fn get_offset_in_queue_by_time(&self, timestamp: i64) -> i64 {
-        todo!()
+        self.get_offset_in_queue_by_time_with_boundary(timestamp, BoundaryType::Lower)
     }
 
     #[inline]
     fn get_max_physic_offset(&self) -> i64 {
-        todo!()
+        self.max_msg_phy_offset_in_commit_log.load(Ordering::SeqCst)
     }
 
     #[inline]
     fn get_min_logic_offset(&self) -> i64 {
-        todo!()
+        self.min_logic_offset.load(Ordering::Acquire)
     }
 
     #[inline]
     fn get_cq_type(&self) -> CQType {
-        todo!()
+        CQType::BatchCQ
     }
 
     #[inline]
     fn get_total_size(&self) -> i64 {
-        todo!()
+        self.mapped_file_queue.get_mapped_files_size() as i64 * self.mapped_file_size as i64
     }
 
     #[inline]
     fn get_unit_size(&self) -> i32 {
-        todo!()
+        CQ_STORE_UNIT_SIZE
     }
 
-    #[inline]
     fn correct_min_offset(&self, min_commit_log_offset: i64) {
-        todo!()
+        if self.get_min_logic_offset() >= self.mapped_file_queue.get_max_offset() {
+            info!(
+                "BatchConsumeQueue[Topic={}, queue-id={}] contains no valid entries",
+                self.topic, self.queue_id
+            );
+            return;
+        }
+        let Some(last_mapped_file) = self.mapped_file_queue.get_last_mapped_file() else {
+            return;
+        };
+        // the last file is always kept, if its last unit is expired the whole queue is
+        if let Some((unit, _)) = Self::get_max_unit(&last_mapped_file) {
+            if unit.pos < min_commit_log_offset {
+                self.min_logic_offset.store(
+                    last_mapped_file.get_file_from_offset() as i64
+                        + last_mapped_file.get_read_position() as i64,
+                    Ordering::SeqCst,
+                );
+                self.min_offset_in_queue
+                    .store(self.get_max_offset_in_queue(), Ordering::SeqCst);
+                info!(
+                    "BatchConsumeQueue[topic={}, queue-id={}] contains no valid entries. \
+                     Min-offset is assigned as: {}.",
+                    self.topic,
+                    self.queue_id,
+                    self.get_min_offset_in_queue()
+                );
+                self.refresh_cache();
+                return;
+            }
+        }
+
+        let mapped_files = self.mapped_file_queue.get_mapped_files();
+        let mapped_files = mapped_files.read().clone();
+        for mapped_file in mapped_files.iter() {
+            let index = Self::partition_point(mapped_file, 0, |offset_py| {
+                offset_py >= min_commit_log_offset
+            });
+            if let Some((unit, _)) = Self::read_unit(mapped_file, index * CQ_STORE_UNIT_SIZE) {
+                self.min_logic_offset.store(
+                    mapped_file.get_file_from_offset() as i64 + (index * CQ_STORE_UNIT_SIZE) as i64,
+                    Ordering::SeqCst,
+                );
+                self.min_offset_in_queue
+                    .store(unit.queue_offset, Ordering::SeqCst);
+                break;
+            }
+        }
+        self.refresh_cache();
     }
 
-    #[inline]
     fn put_message_position_info_wrapper(&mut self, request: &DispatchRequest) {
-        todo!()
+        // a plain message in a batch topic is indexed as a batch of one at its queue offset
+        let (msg_base_offset, batch_size) = if request.msg_base_offset >= 0 {
+            (request.msg_base_offset, request.batch_size)
+        } else {
+            (request.consume_queue_offset, 1)
+        };
+        if msg_base_offset < 0 || batch_size <= 0 {
+            warn!(
+                "[NOTIFYME]unexpected dispatch request in batch consume queue topic:{} queue:{} \
+                 commitlog offset:{}",
+                self.topic, self.queue_id, request.commit_log_offset
+            );
+            return;
+        }
+        let max_retries = 30;
+        let can_write = self.running_flags.is_cq_writeable();
+        let mut i = 0;
+        while i < max_retries && can_write {
+            if self.put_batch_message_position_info(
+                request.commit_log_offset,
+                request.msg_size,
+                request.tags_code,
+                request.store_timestamp,
+                msg_base_offset,
+                batch_size,
+            ) {
+                if self.message_store_config.broker_role == BrokerRole::Slave {
+                    self.store_checkpoint
+                        .set_physic_msg_timestamp(request.store_timestamp as u64);
+                }
+                self.store_checkpoint
+                    .set_logics_msg_timestamp(request.store_timestamp as u64);
+                return;
+            }
+            warn!(
+                "[BUG]put commit log position info to batch consume queue {}:{} {} failed, retry \
+                 {} times",
+                self.topic, self.queue_id, request.commit_log_offset, i
+            );
+            i += 1;
+        }
+        error!(
+            "[BUG]batch consume queue can not write, {} {}",
+            self.topic, self.queue_id
+        );
+        self.running_flags.make_logics_queue_error();
     }
 
     #[inline]
@@ -313,7 +858,10 @@ impl ConsumeQueueTrait for BatchConsumeQueue {
         msg: &MessageExtBrokerInner,
         message_num: i16,
     ) {
-        todo!()
+        queue_offset_assigner.increase_batch_queue_offset(
+            &CheetahString::from_string(format!("{}-{}", self.topic, self.queue_id)),
+            message_num,
+        );
     }
 
     #[inline]
@@ -322,25 +870,73 @@ impl ConsumeQueueTrait for BatchConsumeQueue {
         queue_offset_operator: &QueueOffsetOperator,
         msg: &mut MessageExtBrokerInner,
     ) {
-        todo!()
+        let queue_offset = queue_offset_operator.get_batch_queue_offset(
+            &CheetahString::from_string(format!("{}-{}", self.topic, self.queue_id)),
+        );
+        if MessageSysFlag::check(msg.sys_flag(), MessageSysFlag::INNER_BATCH_FLAG) {
+            msg.put_property(
+                CheetahString::from_static_str(MessageConst::PROPERTY_INNER_BASE),
+                CheetahString::from_string(queue_offset.to_string()),
+            );
+            msg.properties_string =
+                MessageDecoder::message_properties_to_string(msg.get_properties());
+        }
+        msg.message_ext_inner.queue_offset = queue_offset;
     }
 
-    #[inline]
     fn estimate_message_count(&self, from: i64, to: i64, filter: &dyn MessageFilter) -> i64 {
-        todo!()
+        let from = from.max(self.get_min_offset_in_queue());
+        let to = to.min(self.get_max_offset_in_queue() - 1);
+        if from > to {
+            return 0;
+        }
+        let max_scan = self.message_store_config.max_consume_queue_scan;
+        let mut next = from;
+        let mut scanned_units = 0usize;
+        let mut sampled = 0i64;
+        let mut matched = 0i64;
+        'scan: while next <= to {
+            let Some(iterator) = self.iterate_from(next) else {
+                break;
+            };
+            let mut moved = false;
+            for unit in iterator {
+                if unit.queue_offset > to || (max_scan > 0 && scanned_units >= max_scan) {
+                    break 'scan;
+                }
+                scanned_units += 1;
+                sampled += unit.batch_num as i64;
+                if filter.is_matched_by_consume_queue(unit.get_valid_tags_code_as_long(), None) {
+                    matched += unit.batch_num as i64;
+                }
+                next = unit.queue_offset + unit.batch_num as i64;
+                moved = true;
+            }
+            if !moved {
+                break;
+            }
+        }
+        if sampled == 0 {
+            return 0;
+        }
+        matched * (to - from + 1) / sampled
     }
 
-    #[inline]
     fn iterate_from(&self, start_index: i64) -> Option<Box<dyn ReferredIterator<CqUnit>>> {
-        todo!()
+        let (mapped_file, pos) = self.get_batch_msg_index(start_index)?;
+        Some(Box::new(BatchConsumeQueueIterator {
+            end: mapped_file.get_read_position(),
+            mapped_file,
+            position: pos,
+        }))
     }
 
     fn iterate_from_with_count(
         &self,
         start_index: i64,
-        count: i32,
+        _count: i32,
     ) -> Option<Box<dyn ReferredIterator<CqUnit>>> {
-        todo!()
+        self.iterate_from(start_index)
     }
 
     fn get_offset_in_queue_by_time_with_boundary(
@@ -348,6 +944,167 @@ impl ConsumeQueueTrait for BatchConsumeQueue {
         timestamp: i64,
         boundary_type: BoundaryType,
     ) -> i64 {
-        todo!()
+        let min = self.get_min_offset_in_queue();
+        let max = self.get_max_offset_in_queue();
+        if min >= max {
+            return 0;
+        }
+        let Some(mapped_file) = self.search_file_by_time(timestamp) else {
+            return 0;
+        };
+        let offset = match boundary_type {
+            BoundaryType::Lower => {
+                let index = Self::partition_point(&mapped_file, MSG_STORE_TIME_OFFSET_INDEX, |t| {
+                    t >= timestamp
+                });
+                match Self::read_unit(&mapped_file, index * CQ_STORE_UNIT_SIZE) {
+                    Some((unit, _)) => unit.queue_offset,
+                    // everything in the file is older, the answer is the next unit
+                    None => Self::get_max_unit(&mapped_file)
+                        .map_or(max, |(unit, _)| unit.queue_offset + unit.batch_num as i64),
+                }
+            }
+            BoundaryType::Upper => {
+                let after = Self::partition_point(&mapped_file, MSG_STORE_TIME_OFFSET_INDEX, |t| {
+                    t > timestamp
+                });
+                Self::read_unit(&mapped_file, (after - 1).max(0) * CQ_STORE_UNIT_SIZE)
+                    .map_or(min, |(unit, _)| unit.queue_offset)
+            }
+        };
+        offset.clamp(min, max)
+    }
+}
+
+/// Walks the units of one batch consume queue file starting at `position`.
+struct BatchConsumeQueueIterator {
+    mapped_file: Arc<DefaultMappedFile>,
+    position: i32,
+    end: i32,
+}
+
+impl ReferredIterator<CqUnit> for BatchConsumeQueueIterator {
+    fn release(&mut self) {}
+
+    fn next_and_release(&mut self) -> Option<Self::Item> {
+        self.next()
+    }
+}
+
+impl Iterator for BatchConsumeQueueIterator {
+    type Item = CqUnit;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position + CQ_STORE_UNIT_SIZE > self.end {
+            return None;
+        }
+        let (unit, _) = BatchConsumeQueue::read_unit(&self.mapped_file, self.position)?;
+        self.position += CQ_STORE_UNIT_SIZE;
+        Some(unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNITS_PER_FILE: usize = 4;
+
+    fn new_queue(root: &std::path::Path) -> BatchConsumeQueue {
+        let store_checkpoint = Arc::new(StoreCheckpoint::new(root.join("checkpoint")).unwrap());
+        BatchConsumeQueue::new(
+            CheetahString::from_static_str("BatchTopic"),
+            0,
+            CheetahString::from_string(root.join("batchconsumequeue").to_string_lossy().into()),
+            CQ_STORE_UNIT_SIZE as usize * UNITS_PER_FILE,
+            None,
+            Arc::new(MessageStoreConfig::default()),
+            store_checkpoint,
+            Arc::new(RunningFlags::new()),
+        )
+    }
+
+    // ten batches of ten messages each, message `i` stored at commit log offset `i * 100`
+    fn put_batches(queue: &mut BatchConsumeQueue) {
+        for i in 0..10 {
+            queue.put_message_position_info_wrapper(&DispatchRequest {
+                commit_log_offset: i * 100,
+                msg_size: 100,
+                store_timestamp: 1000 + i * 10,
+                msg_base_offset: i * 10,
+                batch_size: 10,
+                ..DispatchRequest::default()
+            });
+        }
+    }
+
+    #[test]
+    fn put_and_get_units() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut queue = new_queue(temp_dir.path());
+        put_batches(&mut queue);
+
+        assert_eq!(queue.mapped_file_queue.get_mapped_files_size(), 3);
+        assert_eq!(queue.get_min_offset_in_queue(), 0);
+        assert_eq!(queue.get_max_offset_in_queue(), 100);
+        assert_eq!(queue.get_max_physic_offset(), 900);
+        assert_eq!(queue.get_last_offset(), 1000);
+
+        // an offset inside a batch resolves to the unit of that batch
+        let unit = queue.get(57).unwrap();
+        assert_eq!(unit.queue_offset, 50);
+        assert_eq!(unit.batch_num, 10);
+        assert_eq!(unit.pos, 500);
+        assert!(queue.get(100).is_none());
+
+        let offsets: Vec<i64> = queue
+            .iterate_from(35)
+            .unwrap()
+            .map(|unit| unit.queue_offset)
+            .collect();
+        assert_eq!(offsets, vec![30]);
+        assert_eq!(queue.roll_next_file(35), 40);
+        assert_eq!(queue.roll_next_file(95), 100);
+    }
+
+    #[test]
+    fn offset_by_time() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut queue = new_queue(temp_dir.path());
+        put_batches(&mut queue);
+
+        assert_eq!(queue.get_offset_in_queue_by_time(1045), 50);
+        assert_eq!(
+            queue.get_offset_in_queue_by_time_with_boundary(1045, BoundaryType::Upper),
+            40
+        );
+        assert_eq!(queue.get_offset_in_queue_by_time(0), 0);
+        assert_eq!(queue.get_offset_in_queue_by_time(5000), 100);
+    }
+
+    #[test]
+    fn recover_and_truncate() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        {
+            let mut queue = new_queue(temp_dir.path());
+            put_batches(&mut queue);
+            queue.flush(0);
+        }
+
+        let mut queue = new_queue(temp_dir.path());
+        assert!(queue.load());
+        queue.recover();
+        assert_eq!(queue.get_min_offset_in_queue(), 0);
+        assert_eq!(queue.get_max_offset_in_queue(), 100);
+        assert_eq!(queue.get_max_physic_offset(), 900);
+
+        queue.truncate_dirty_logic_files(450);
+        assert_eq!(queue.get_max_offset_in_queue(), 50);
+        assert_eq!(queue.get_max_physic_offset(), 400);
+        assert!(queue.get(50).is_none());
+
+        queue.correct_min_offset(250);
+        assert_eq!(queue.get_min_offset_in_queue(), 30);
+        assert_eq!(queue.get_message_total_in_queue(), 20);
     }
 }
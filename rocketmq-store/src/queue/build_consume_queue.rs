@@ -19,14 +19,16 @@ use rocketmq_common::common::sys_flag::message_sys_flag::MessageSysFlag;
 use crate::base::commit_log_dispatcher::CommitLogDispatcher;
 use crate::base::dispatch_request::DispatchRequest;
 use crate::queue::consume_queue_store::ConsumeQueueStoreInterface;
-use crate::queue::local_file_consume_queue_store::ConsumeQueueStore;
+use crate::queue::consume_queue_store_impl::ConsumeQueueStoreImpl;
+#[cfg(feature = "rocksdb_cq")]
+use crate::queue::rocksdb_consume_queue_store::RocksDBConsumeQueueStore;
 
 pub struct CommitLogDispatcherBuildConsumeQueue {
-    consume_queue_store: ConsumeQueueStore,
+    consume_queue_store: ConsumeQueueStoreImpl,
 }
 
 impl CommitLogDispatcherBuildConsumeQueue {
-    pub fn new(consume_queue_store: ConsumeQueueStore) -> Self {
+    pub fn new(consume_queue_store: ConsumeQueueStoreImpl) -> Self {
         Self {
             consume_queue_store,
         }
@@ -45,3 +47,32 @@ impl CommitLogDispatcher for CommitLogDispatcherBuildConsumeQueue {
         }
     }
 }
+
+/// Writes dispatched messages to the RocksDB consume queues next to the file consume queues.
+#[cfg(feature = "rocksdb_cq")]
+pub struct CommitLogDispatcherBuildRocksdbConsumeQueue {
+    consume_queue_store: RocksDBConsumeQueueStore,
+}
+
+#[cfg(feature = "rocksdb_cq")]
+impl CommitLogDispatcherBuildRocksdbConsumeQueue {
+    pub fn new(consume_queue_store: RocksDBConsumeQueueStore) -> Self {
+        Self {
+            consume_queue_store,
+        }
+    }
+}
+
+#[cfg(feature = "rocksdb_cq")]
+impl CommitLogDispatcher for CommitLogDispatcherBuildRocksdbConsumeQueue {
+    fn dispatch(&self, dispatch_request: &mut DispatchRequest) {
+        let tran_type = MessageSysFlag::get_transaction_value(dispatch_request.sys_flag);
+        match tran_type {
+            MessageSysFlag::TRANSACTION_NOT_TYPE | MessageSysFlag::TRANSACTION_COMMIT_TYPE => {
+                self.consume_queue_store
+                    .put_message_position_info_wrapper(dispatch_request);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,477 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::fs;
+
+use bytes::Buf;
+use bytes::BufMut;
+use bytes::Bytes;
+use cheetah_string::CheetahString;
+use parking_lot::RwLock;
+use rocksdb::ColumnFamily;
+use rocksdb::ColumnFamilyDescriptor;
+use rocksdb::Direction;
+use rocksdb::IteratorMode;
+use rocksdb::Options;
+use rocksdb::WriteBatch;
+use rocksdb::DB;
+use tracing::error;
+use tracing::info;
+
+use crate::queue::CqUnit;
+
+/// Column family holding the consume queue units.
+const DEFAULT_COLUMN_FAMILY: &str = "default";
+/// Column family holding the min/max offsets of every queue.
+const OFFSET_COLUMN_FAMILY: &str = "offset";
+
+const CTRL_1: u8 = 1;
+const MAX_BYTES: &[u8] = b"max";
+const MIN_BYTES: &[u8] = b"min";
+
+/// Size of a unit value: commit log offset, message size, tags code and store timestamp.
+pub const ROCKSDB_CQ_UNIT_SIZE: i32 = 28;
+
+/// Size of an offset value: commit log offset and consume queue offset.
+const OFFSET_VALUE_SIZE: usize = 16;
+
+/// Min or max offset entry of a queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OffsetEntry {
+    pub phy_offset: i64,
+    pub cq_offset: i64,
+}
+
+/// Embedded key-value storage of the RocksDB consume queues.
+///
+/// Unit keys are `topic_len | topic | CTRL_1 | queue_id | CTRL_1 | cq_offset` and offset keys
+/// end with `max` or `min` instead of the consume queue offset. Integers are big-endian so the
+/// units of a queue are sorted by their consume queue offset.
+pub struct ConsumeQueueRocksDBStorage {
+    store_path: String,
+    db: RwLock<Option<DB>>,
+}
+
+impl ConsumeQueueRocksDBStorage {
+    pub fn new(store_path: String) -> Self {
+        Self {
+            store_path,
+            db: RwLock::new(None),
+        }
+    }
+
+    pub fn start(&self) -> bool {
+        let mut db = self.db.write();
+        if db.is_some() {
+            return true;
+        }
+        let mut options = Options::default();
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+        let column_families = [DEFAULT_COLUMN_FAMILY, OFFSET_COLUMN_FAMILY]
+            .into_iter()
+            .map(|name| ColumnFamilyDescriptor::new(name, Options::default()));
+        match DB::open_cf_descriptors(&options, &self.store_path, column_families) {
+            Ok(opened) => {
+                info!(
+                    "open rocksdb consume queue storage, path={}",
+                    self.store_path
+                );
+                *db = Some(opened);
+                true
+            }
+            Err(e) => {
+                error!(
+                    "open rocksdb consume queue storage failed, path={}, error={}",
+                    self.store_path, e
+                );
+                false
+            }
+        }
+    }
+
+    pub fn shutdown(&self) {
+        if let Some(db) = self.db.write().take() {
+            if let Err(e) = db.flush() {
+                error!("flush rocksdb consume queue storage failed: {}", e);
+            }
+        }
+    }
+
+    pub fn destroy(&self) {
+        self.shutdown();
+        if let Err(e) = fs::remove_dir_all(&self.store_path) {
+            error!(
+                "delete rocksdb consume queue storage failed, path={}, error={}",
+                self.store_path, e
+            );
+        }
+    }
+
+    pub fn flush(&self) -> bool {
+        self.with_db("flush", |db, _, _| db.flush()).is_some()
+    }
+
+    /// Writes a unit and moves the max offset of its queue in one batch.
+    #[allow(clippy::too_many_arguments)]
+    pub fn put_unit(
+        &self,
+        topic: &str,
+        queue_id: i32,
+        cq_offset: i64,
+        phy_offset: i64,
+        size: i32,
+        tags_code: i64,
+        store_timestamp: i64,
+    ) -> bool {
+        self.with_db("put", |db, cq_cf, offset_cf| {
+            let mut batch = WriteBatch::default();
+            batch.put_cf(
+                cq_cf,
+                build_cq_key(topic, queue_id, cq_offset),
+                build_cq_value(phy_offset, size, tags_code, store_timestamp),
+            );
+            batch.put_cf(
+                offset_cf,
+                build_offset_key(topic, queue_id, MAX_BYTES),
+                build_offset_value(phy_offset, cq_offset),
+            );
+            db.write(batch)
+        })
+        .is_some()
+    }
+
+    /// Returns the unit at `cq_offset` with its store timestamp.
+    pub fn get_unit(&self, topic: &str, queue_id: i32, cq_offset: i64) -> Option<(CqUnit, i64)> {
+        self.with_db("get", |db, cq_cf, _| {
+            db.get_cf(cq_cf, build_cq_key(topic, queue_id, cq_offset))
+        })
+        .flatten()
+        .and_then(|value| parse_cq_value(cq_offset, &value))
+    }
+
+    /// Returns at most `num` raw units starting at `start_index`, keyed by consume queue offset.
+    pub fn range_query(
+        &self,
+        topic: &str,
+        queue_id: i32,
+        start_index: i64,
+        num: i32,
+    ) -> Vec<(i64, Bytes)> {
+        if num <= 0 {
+            return vec![];
+        }
+        let prefix = build_queue_prefix(topic, queue_id);
+        let start_key = build_cq_key(topic, queue_id, start_index);
+        self.with_db("range query", |db, cq_cf, _| {
+            let mut units = Vec::with_capacity(num as usize);
+            let iter = db.iterator_cf(cq_cf, IteratorMode::From(&start_key, Direction::Forward));
+            for item in iter {
+                let (key, value) = item?;
+                if units.len() >= num as usize || !key.starts_with(&prefix) {
+                    break;
+                }
+                let mut suffix = &key[prefix.len()..];
+                units.push((suffix.get_i64(), Bytes::copy_from_slice(&value)));
+            }
+            Ok(units)
+        })
+        .unwrap_or_default()
+    }
+
+    pub fn get_max_offset(&self, topic: &str, queue_id: i32) -> Option<OffsetEntry> {
+        self.get_offset(topic, queue_id, MAX_BYTES)
+    }
+
+    /// Returns the min offset entry of the queue, or its first unit if it was never corrected.
+    pub fn get_min_offset(&self, topic: &str, queue_id: i32) -> Option<OffsetEntry> {
+        if let Some(entry) = self.get_offset(topic, queue_id, MIN_BYTES) {
+            return Some(entry);
+        }
+        self.range_query(topic, queue_id, 0, 1)
+            .into_iter()
+            .next()
+            .and_then(|(cq_offset, value)| parse_cq_value(cq_offset, &value))
+            .map(|(unit, _)| OffsetEntry {
+                phy_offset: unit.pos,
+                cq_offset: unit.queue_offset,
+            })
+    }
+
+    /// Moves the min offset of the queue to `entry` and drops the units below it.
+    pub fn update_min_offset(&self, topic: &str, queue_id: i32, entry: OffsetEntry) -> bool {
+        self.with_db("update min offset", |db, cq_cf, offset_cf| {
+            let mut batch = WriteBatch::default();
+            batch.delete_range_cf(
+                cq_cf,
+                build_cq_key(topic, queue_id, 0),
+                build_cq_key(topic, queue_id, entry.cq_offset),
+            );
+            batch.put_cf(
+                offset_cf,
+                build_offset_key(topic, queue_id, MIN_BYTES),
+                build_offset_value(entry.phy_offset, entry.cq_offset),
+            );
+            db.write(batch)
+        })
+        .is_some()
+    }
+
+    /// Drops the units from `cq_offset` on and moves the max offset back to `new_max`.
+    pub fn truncate_from(
+        &self,
+        topic: &str,
+        queue_id: i32,
+        cq_offset: i64,
+        new_max: Option<OffsetEntry>,
+    ) -> bool {
+        self.with_db("truncate", |db, cq_cf, offset_cf| {
+            let mut batch = WriteBatch::default();
+            batch.delete_range_cf(
+                cq_cf,
+                build_cq_key(topic, queue_id, cq_offset),
+                build_cq_key(topic, queue_id, i64::MAX),
+            );
+            let max_key = build_offset_key(topic, queue_id, MAX_BYTES);
+            match new_max {
+                Some(entry) => batch.put_cf(
+                    offset_cf,
+                    max_key,
+                    build_offset_value(entry.phy_offset, entry.cq_offset),
+                ),
+                None => batch.delete_cf(offset_cf, max_key),
+            }
+            db.write(batch)
+        })
+        .is_some()
+    }
+
+    /// Drops every unit and offset of the queue.
+    pub fn delete_queue(&self, topic: &str, queue_id: i32) -> bool {
+        self.with_db("delete queue", |db, cq_cf, offset_cf| {
+            let mut batch = WriteBatch::default();
+            batch.delete_range_cf(
+                cq_cf,
+                build_cq_key(topic, queue_id, 0),
+                build_cq_key(topic, queue_id, i64::MAX),
+            );
+            batch.delete_cf(offset_cf, build_offset_key(topic, queue_id, MAX_BYTES));
+            batch.delete_cf(offset_cf, build_offset_key(topic, queue_id, MIN_BYTES));
+            db.write(batch)
+        })
+        .is_some()
+    }
+
+    /// Returns the max offset entry of every queue in the storage.
+    pub fn get_all_max_offsets(&self) -> Vec<(CheetahString, i32, OffsetEntry)> {
+        self.with_db("iterate offsets", |db, _, offset_cf| {
+            let mut offsets = Vec::new();
+            for item in db.iterator_cf(offset_cf, IteratorMode::Start) {
+                let (key, value) = item?;
+                let Some((topic, queue_id, suffix)) = parse_offset_key(&key) else {
+                    continue;
+                };
+                if suffix != MAX_BYTES {
+                    continue;
+                }
+                if let Some(entry) = parse_offset_value(&value) {
+                    offsets.push((topic, queue_id, entry));
+                }
+            }
+            Ok(offsets)
+        })
+        .unwrap_or_default()
+    }
+
+    fn get_offset(&self, topic: &str, queue_id: i32, suffix: &[u8]) -> Option<OffsetEntry> {
+        self.with_db("get offset", |db, _, offset_cf| {
+            db.get_cf(offset_cf, build_offset_key(topic, queue_id, suffix))
+        })
+        .flatten()
+        .and_then(|value| parse_offset_value(&value))
+    }
+
+    fn with_db<T>(
+        &self,
+        operation: &str,
+        f: impl FnOnce(&DB, &ColumnFamily, &ColumnFamily) -> Result<T, rocksdb::Error>,
+    ) -> Option<T> {
+        let db = self.db.read();
+        let db = db.as_ref()?;
+        let (Some(cq_cf), Some(offset_cf)) = (
+            db.cf_handle(DEFAULT_COLUMN_FAMILY),
+            db.cf_handle(OFFSET_COLUMN_FAMILY),
+        ) else {
+            error!("rocksdb consume queue storage misses its column families");
+            return None;
+        };
+        match f(db, cq_cf, offset_cf) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                error!("rocksdb consume queue {} failed: {}", operation, e);
+                None
+            }
+        }
+    }
+}
+
+fn build_queue_prefix(topic: &str, queue_id: i32) -> Vec<u8> {
+    let topic = topic.as_bytes();
+    let mut key = Vec::with_capacity(4 + topic.len() + 1 + 4 + 1 + 8);
+    key.put_i32(topic.len() as i32);
+    key.put_slice(topic);
+    key.put_u8(CTRL_1);
+    key.put_i32(queue_id);
+    key.put_u8(CTRL_1);
+    key
+}
+
+fn build_cq_key(topic: &str, queue_id: i32, cq_offset: i64) -> Vec<u8> {
+    let mut key = build_queue_prefix(topic, queue_id);
+    key.put_i64(cq_offset);
+    key
+}
+
+fn build_offset_key(topic: &str, queue_id: i32, suffix: &[u8]) -> Vec<u8> {
+    let mut key = build_queue_prefix(topic, queue_id);
+    key.put_slice(suffix);
+    key
+}
+
+fn parse_offset_key(key: &[u8]) -> Option<(CheetahString, i32, &[u8])> {
+    let mut buf = key;
+    if buf.remaining() < 4 {
+        return None;
+    }
+    let topic_len = buf.get_i32() as usize;
+    if buf.remaining() < topic_len + 1 + 4 + 1 {
+        return None;
+    }
+    let topic = CheetahString::from_string(String::from_utf8_lossy(&buf[..topic_len]).into_owned());
+    buf.advance(topic_len + 1);
+    let queue_id = buf.get_i32();
+    buf.advance(1);
+    Some((topic, queue_id, buf))
+}
+
+fn build_cq_value(phy_offset: i64, size: i32, tags_code: i64, store_timestamp: i64) -> Vec<u8> {
+    let mut value = Vec::with_capacity(ROCKSDB_CQ_UNIT_SIZE as usize);
+    value.put_i64(phy_offset);
+    value.put_i32(size);
+    value.put_i64(tags_code);
+    value.put_i64(store_timestamp);
+    value
+}
+
+/// Decodes a unit value into the unit and its store timestamp.
+pub fn parse_cq_value(cq_offset: i64, value: &[u8]) -> Option<(CqUnit, i64)> {
+    if value.len() < ROCKSDB_CQ_UNIT_SIZE as usize {
+        return None;
+    }
+    let mut buf = value;
+    let pos = buf.get_i64();
+    let size = buf.get_i32();
+    let tags_code = buf.get_i64();
+    let store_timestamp = buf.get_i64();
+    Some((
+        CqUnit {
+            queue_offset: cq_offset,
+            size,
+            pos,
+            tags_code,
+            ..CqUnit::default()
+        },
+        store_timestamp,
+    ))
+}
+
+fn build_offset_value(phy_offset: i64, cq_offset: i64) -> Vec<u8> {
+    let mut value = Vec::with_capacity(OFFSET_VALUE_SIZE);
+    value.put_i64(phy_offset);
+    value.put_i64(cq_offset);
+    value
+}
+
+fn parse_offset_value(value: &[u8]) -> Option<OffsetEntry> {
+    if value.len() < OFFSET_VALUE_SIZE {
+        return None;
+    }
+    let mut buf = value;
+    Some(OffsetEntry {
+        phy_offset: buf.get_i64(),
+        cq_offset: buf.get_i64(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cq_keys_sort_by_queue_offset() {
+        let low = build_cq_key("TopicTest", 1, 9);
+        let high = build_cq_key("TopicTest", 1, 10);
+        assert!(low < high);
+        assert!(low.starts_with(&build_queue_prefix("TopicTest", 1)));
+        assert!(!low.starts_with(&build_queue_prefix("TopicTest", 2)));
+    }
+
+    #[test]
+    fn offset_key_round_trip() {
+        let key = build_offset_key("TopicTest", 3, MAX_BYTES);
+        let (topic, queue_id, suffix) = parse_offset_key(&key).unwrap();
+        assert_eq!(topic, "TopicTest");
+        assert_eq!(queue_id, 3);
+        assert_eq!(suffix, MAX_BYTES);
+    }
+
+    #[test]
+    fn put_get_and_correct_units() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let storage =
+            ConsumeQueueRocksDBStorage::new(temp_dir.path().to_string_lossy().into_owned());
+        assert!(storage.start());
+        for cq_offset in 0..5 {
+            assert!(storage.put_unit("TopicTest", 0, cq_offset, cq_offset * 100, 100, 7, 1000));
+        }
+        let (unit, store_timestamp) = storage.get_unit("TopicTest", 0, 3).unwrap();
+        assert_eq!(unit.pos, 300);
+        assert_eq!(unit.queue_offset, 3);
+        assert_eq!(store_timestamp, 1000);
+        assert_eq!(storage.range_query("TopicTest", 0, 1, 10).len(), 4);
+        assert_eq!(
+            storage.get_max_offset("TopicTest", 0),
+            Some(OffsetEntry {
+                phy_offset: 400,
+                cq_offset: 4
+            })
+        );
+        assert_eq!(storage.get_min_offset("TopicTest", 0).unwrap().cq_offset, 0);
+
+        let min = OffsetEntry {
+            phy_offset: 200,
+            cq_offset: 2,
+        };
+        assert!(storage.update_min_offset("TopicTest", 0, min));
+        assert!(storage.get_unit("TopicTest", 0, 1).is_none());
+        assert_eq!(storage.get_min_offset("TopicTest", 0), Some(min));
+        assert_eq!(storage.get_all_max_offsets().len(), 1);
+
+        assert!(storage.delete_queue("TopicTest", 0));
+        assert!(storage.get_max_offset("TopicTest", 0).is_none());
+        storage.shutdown();
+    }
+}
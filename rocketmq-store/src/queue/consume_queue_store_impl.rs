@@ -0,0 +1,286 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use cheetah_string::CheetahString;
+use rocketmq_common::common::boundary_type::BoundaryType;
+use rocketmq_common::common::broker::broker_config::BrokerConfig;
+use rocketmq_common::common::message::message_ext_broker_inner::MessageExtBrokerInner;
+use rocketmq_rust::ArcMut;
+
+use crate::base::dispatch_request::DispatchRequest;
+use crate::config::message_store_config::MessageStoreConfig;
+use crate::message_store::local_file_message_store::LocalFileMessageStore;
+use crate::queue::consume_queue::ConsumeQueueTrait;
+use crate::queue::consume_queue_store::ConsumeQueueStoreTrait;
+use crate::queue::local_file_consume_queue_store::ConsumeQueueStore;
+#[cfg(feature = "rocksdb_cq")]
+use crate::queue::rocksdb_consume_queue_store::RocksDBConsumeQueueStore;
+use crate::queue::ArcConsumeQueue;
+use crate::queue::ConsumeQueueTable;
+use crate::queue::CqUnit;
+
+/// The consume queue store picked by `MessageStoreConfig::store_type`.
+#[derive(Clone)]
+pub enum ConsumeQueueStoreImpl {
+    LocalFile(ConsumeQueueStore),
+    #[cfg(feature = "rocksdb_cq")]
+    RocksDB(RocksDBConsumeQueueStore),
+}
+
+macro_rules! delegate {
+    ($self:ident, $store:ident => $call:expr) => {
+        match $self {
+            ConsumeQueueStoreImpl::LocalFile($store) => $call,
+            #[cfg(feature = "rocksdb_cq")]
+            ConsumeQueueStoreImpl::RocksDB($store) => $call,
+        }
+    };
+}
+
+impl ConsumeQueueStoreImpl {
+    /// Without the `rocksdb_cq` feature file consume queues are always built, and
+    /// `LocalFileMessageStore::load` refuses to start a store configured for RocksDB.
+    pub fn new(
+        message_store_config: Arc<MessageStoreConfig>,
+        broker_config: Arc<BrokerConfig>,
+    ) -> Self {
+        #[cfg(feature = "rocksdb_cq")]
+        if message_store_config.is_enable_rocksdb_store() {
+            return ConsumeQueueStoreImpl::RocksDB(RocksDBConsumeQueueStore::new(
+                message_store_config,
+            ));
+        }
+        ConsumeQueueStoreImpl::LocalFile(ConsumeQueueStore::new(
+            message_store_config,
+            broker_config,
+        ))
+    }
+
+    /// Returns true when consume queues are kept in RocksDB instead of files.
+    pub fn is_rocksdb(&self) -> bool {
+        !matches!(self, ConsumeQueueStoreImpl::LocalFile(_))
+    }
+
+    pub fn set_message_store(&mut self, message_store: ArcMut<LocalFileMessageStore>) {
+        match self {
+            ConsumeQueueStoreImpl::LocalFile(store) => store.set_message_store(message_store),
+            #[cfg(feature = "rocksdb_cq")]
+            ConsumeQueueStoreImpl::RocksDB(_) => {}
+        }
+    }
+}
+
+impl ConsumeQueueStoreTrait for ConsumeQueueStoreImpl {
+    fn start(&self) {
+        delegate!(self, store => store.start())
+    }
+
+    fn load(&mut self) -> bool {
+        delegate!(self, store => store.load())
+    }
+
+    fn load_after_destroy(&self) -> bool {
+        delegate!(self, store => store.load_after_destroy())
+    }
+
+    async fn recover(&self) {
+        delegate!(self, store => store.recover().await)
+    }
+
+    async fn recover_concurrently(&self) -> bool {
+        delegate!(self, store => store.recover_concurrently().await)
+    }
+
+    fn shutdown(&self) -> bool {
+        delegate!(self, store => store.shutdown())
+    }
+
+    fn destroy(&self) {
+        delegate!(self, store => store.destroy())
+    }
+
+    fn destroy_queue(&self, consume_queue: &dyn ConsumeQueueTrait) {
+        delegate!(self, store => store.destroy_queue(consume_queue))
+    }
+
+    fn flush(&self, consume_queue: &dyn ConsumeQueueTrait, flush_least_pages: i32) -> bool {
+        delegate!(self, store => store.flush(consume_queue, flush_least_pages))
+    }
+
+    async fn clean_expired(&self, min_phy_offset: i64) {
+        delegate!(self, store => store.clean_expired(min_phy_offset).await)
+    }
+
+    fn check_self(&self) {
+        delegate!(self, store => ConsumeQueueStoreTrait::check_self(store))
+    }
+
+    fn delete_expired_file(
+        &self,
+        consume_queue: &dyn ConsumeQueueTrait,
+        min_commit_log_pos: i64,
+    ) -> i32 {
+        delegate!(self, store => store.delete_expired_file(consume_queue, min_commit_log_pos))
+    }
+
+    fn is_first_file_available(&self, consume_queue: &dyn ConsumeQueueTrait) -> bool {
+        delegate!(self, store => store.is_first_file_available(consume_queue))
+    }
+
+    fn is_first_file_exist(&self, consume_queue: &dyn ConsumeQueueTrait) -> bool {
+        delegate!(self, store => store.is_first_file_exist(consume_queue))
+    }
+
+    fn roll_next_file(&self, consume_queue: &dyn ConsumeQueueTrait, offset: i64) -> i64 {
+        delegate!(self, store => store.roll_next_file(consume_queue, offset))
+    }
+
+    fn truncate_dirty(&self, offset_to_truncate: i64) {
+        delegate!(self, store => store.truncate_dirty(offset_to_truncate))
+    }
+
+    fn put_message_position_info_wrapper_with_cq(
+        &self,
+        consume_queue: &mut dyn ConsumeQueueTrait,
+        request: &DispatchRequest,
+    ) {
+        delegate!(self, store => store.put_message_position_info_wrapper_with_cq(consume_queue, request))
+    }
+
+    fn put_message_position_info_wrapper(&self, request: &DispatchRequest) {
+        delegate!(self, store => store.put_message_position_info_wrapper(request))
+    }
+
+    async fn range_query(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+        start_index: i64,
+        num: i32,
+    ) -> Vec<Bytes> {
+        delegate!(self, store => store.range_query(topic, queue_id, start_index, num).await)
+    }
+
+    async fn get(&self, topic: &CheetahString, queue_id: i32, start_index: i64) -> Bytes {
+        delegate!(self, store => store.get(topic, queue_id, start_index).await)
+    }
+
+    fn get_consume_queue_table(&self) -> Arc<ConsumeQueueTable> {
+        delegate!(self, store => store.get_consume_queue_table())
+    }
+
+    fn assign_queue_offset(&self, msg: &mut MessageExtBrokerInner) {
+        delegate!(self, store => store.assign_queue_offset(msg))
+    }
+
+    fn increase_queue_offset(&self, msg: &MessageExtBrokerInner, message_num: i16) {
+        delegate!(self, store => store.increase_queue_offset(msg, message_num))
+    }
+
+    fn increase_lmq_offset(&self, queue_key: &str, message_num: i16) {
+        delegate!(self, store => store.increase_lmq_offset(queue_key, message_num))
+    }
+
+    fn get_lmq_queue_offset(&self, queue_key: &str) -> i64 {
+        delegate!(self, store => store.get_lmq_queue_offset(queue_key))
+    }
+
+    fn recover_offset_table(&mut self, min_phy_offset: i64) {
+        delegate!(self, store => store.recover_offset_table(min_phy_offset))
+    }
+
+    fn set_topic_queue_table(&mut self, topic_queue_table: HashMap<CheetahString, i64>) {
+        delegate!(self, store => store.set_topic_queue_table(topic_queue_table))
+    }
+
+    fn remove_topic_queue_table(&mut self, topic: &CheetahString, queue_id: i32) {
+        delegate!(self, store => store.remove_topic_queue_table(topic, queue_id))
+    }
+
+    fn get_topic_queue_table(&self) -> HashMap<CheetahString, i64> {
+        delegate!(self, store => store.get_topic_queue_table())
+    }
+
+    fn get_max_phy_offset_in_consume_queue(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+    ) -> Option<i64> {
+        delegate!(self, store => store.get_max_phy_offset_in_consume_queue(topic, queue_id))
+    }
+
+    fn get_max_offset(&self, topic: &CheetahString, queue_id: i32) -> Option<i64> {
+        delegate!(self, store => store.get_max_offset(topic, queue_id))
+    }
+
+    fn get_max_phy_offset_in_consume_queue_global(&self) -> i64 {
+        delegate!(self, store => store.get_max_phy_offset_in_consume_queue_global())
+    }
+
+    fn get_min_offset_in_queue(&self, topic: &CheetahString, queue_id: i32) -> i64 {
+        delegate!(self, store => store.get_min_offset_in_queue(topic, queue_id))
+    }
+
+    fn get_max_offset_in_queue(&self, topic: &CheetahString, queue_id: i32) -> i64 {
+        delegate!(self, store => store.get_max_offset_in_queue(topic, queue_id))
+    }
+
+    fn get_offset_in_queue_by_time(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+        timestamp: i64,
+        boundary_type: BoundaryType,
+    ) -> i64 {
+        delegate!(self, store => store.get_offset_in_queue_by_time(topic, queue_id, timestamp, boundary_type))
+    }
+
+    fn find_or_create_consume_queue(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+    ) -> ArcConsumeQueue {
+        delegate!(self, store => store.find_or_create_consume_queue(topic, queue_id))
+    }
+
+    fn find_consume_queue_map(
+        &self,
+        topic: &CheetahString,
+    ) -> Option<HashMap<i32, ArcConsumeQueue>> {
+        delegate!(self, store => store.find_consume_queue_map(topic))
+    }
+
+    fn get_total_size(&self) -> i64 {
+        delegate!(self, store => store.get_total_size())
+    }
+
+    fn get_store_time(&self, cq_unit: &CqUnit) -> i64 {
+        delegate!(self, store => store.get_store_time(cq_unit))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
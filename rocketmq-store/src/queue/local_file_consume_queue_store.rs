@@ -36,7 +36,9 @@ use tracing::error;
 use tracing::info;
 
 use crate::base::dispatch_request::DispatchRequest;
+use crate::base::message_store::MessageStoreInner;
 use crate::config::message_store_config::MessageStoreConfig;
+use crate::log_file::commit_log;
 use crate::message_store::local_file_message_store::LocalFileMessageStore;
 use crate::queue::batch_consume_queue::BatchConsumeQueue;
 use crate::queue::consume_queue::ConsumeQueueTrait;
@@ -93,6 +95,65 @@ impl ConsumeQueueStore {
         self.inner.message_store = Some(message_store);
     }
 
+    /// Moves the queue offsets past messages that are in the commit log but beyond the confirm
+    /// offset, they have not been dispatched to the consume queues yet.
+    fn compensate_for_ha(&self, cq_offset_table: &mut HashMap<CheetahString, i64>) {
+        let Some(message_store) = self.inner.message_store.as_ref() else {
+            return;
+        };
+        let confirm_offset = message_store.get_confirm_offset();
+        let mut start_read_offset = if confirm_offset == -1 {
+            0
+        } else {
+            confirm_offset
+        };
+        let max_phy_offset = message_store.get_max_phy_offset();
+        info!(
+            "Correct unsubmitted offset...StartReadOffset = {}",
+            start_read_offset
+        );
+        while start_read_offset < max_phy_offset {
+            let Some(mut result) = message_store.get_commit_log_data(start_read_offset) else {
+                break;
+            };
+            let mut bytes = result.get_bytes().unwrap_or_default();
+            let mut finished = false;
+            while start_read_offset < max_phy_offset {
+                if !commit_log::starts_with_complete_record(&bytes) {
+                    finished = true;
+                    break;
+                }
+                let dispatch_request = commit_log::check_message_and_return_size(
+                    &mut bytes,
+                    false,
+                    false,
+                    true,
+                    &self.inner.message_store_config,
+                    message_store.max_delay_level(),
+                    message_store.delay_level_table_ref(),
+                );
+                if !dispatch_request.success {
+                    finished = true;
+                    break;
+                }
+                if dispatch_request.msg_size == 0 {
+                    start_read_offset = message_store.roll_next_file(start_read_offset);
+                    break;
+                }
+                let key = CheetahString::from_string(format!(
+                    "{}-{}",
+                    dispatch_request.topic, dispatch_request.queue_id
+                ));
+                cq_offset_table.insert(key, dispatch_request.consume_queue_offset + 1);
+                start_read_offset += dispatch_request.msg_size as i64;
+            }
+            result.release();
+            if finished {
+                break;
+            }
+        }
+    }
+
     pub fn check_self(&self, consume_queue: &dyn ConsumeQueueTrait) {
         let life_cycle =
             self.get_life_cycle(consume_queue.get_topic(), consume_queue.get_queue_id());
@@ -233,19 +294,27 @@ impl ConsumeQueueStoreTrait for ConsumeQueueStore {
         consume_queue: &dyn ConsumeQueueTrait,
         min_commit_log_pos: i64,
     ) -> i32 {
-        todo!()
+        let file_queue_life_cycle =
+            self.get_life_cycle(consume_queue.get_topic(), consume_queue.get_queue_id());
+        file_queue_life_cycle.delete_expired_file(min_commit_log_pos)
     }
 
     fn is_first_file_available(&self, consume_queue: &dyn ConsumeQueueTrait) -> bool {
-        todo!()
+        let file_queue_life_cycle =
+            self.get_life_cycle(consume_queue.get_topic(), consume_queue.get_queue_id());
+        file_queue_life_cycle.is_first_file_available()
     }
 
     fn is_first_file_exist(&self, consume_queue: &dyn ConsumeQueueTrait) -> bool {
-        todo!()
+        let file_queue_life_cycle =
+            self.get_life_cycle(consume_queue.get_topic(), consume_queue.get_queue_id());
+        file_queue_life_cycle.is_first_file_exist()
     }
 
     fn roll_next_file(&self, consume_queue: &dyn ConsumeQueueTrait, offset: i64) -> i64 {
-        todo!()
+        let file_queue_life_cycle =
+            self.get_life_cycle(consume_queue.get_topic(), consume_queue.get_queue_id());
+        file_queue_life_cycle.roll_next_file(offset)
     }
 
     fn truncate_dirty(&self, offset_to_truncate: i64) {
@@ -331,7 +400,7 @@ impl ConsumeQueueStoreTrait for ConsumeQueueStore {
         if self.inner.message_store_config.duplication_enable
             || self.inner.broker_config.enable_controller_mode
         {
-            unimplemented!()
+            self.compensate_for_ha(&mut cq_offset_table);
         }
         self.set_topic_queue_table(cq_offset_table);
         self.set_batch_topic_queue_table(bcq_offset_table);
@@ -450,6 +519,8 @@ impl ConsumeQueueStoreTrait for ConsumeQueueStore {
                         .mapper_file_size_batch_consume_queue,
                     None,
                     self.inner.message_store_config.clone(),
+                    message_store.get_store_checkpoint_arc(),
+                    message_store.get_running_flags_arc(),
                 ))),
             }
         });
@@ -667,6 +738,7 @@ impl ConsumeQueueStore {
                 ArcMut::new(Box::new(consume_queue))
             }
             CQType::BatchCQ => {
+                let ms_ref = self.inner.message_store.as_ref().unwrap();
                 let consume_queue = BatchConsumeQueue::new(
                     topic.clone(),
                     queue_id,
@@ -676,6 +748,8 @@ impl ConsumeQueueStore {
                         .mapper_file_size_batch_consume_queue,
                     None,
                     self.inner.message_store_config.clone(),
+                    ms_ref.get_store_checkpoint_arc(),
+                    ms_ref.get_running_flags_arc(),
                 );
                 ArcMut::new(Box::new(consume_queue))
             }
@@ -19,6 +19,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use cheetah_string::CheetahString;
+use rocketmq_common::common::mix_all;
 use tracing::info;
 
 pub struct QueueOffsetOperator {
@@ -144,7 +145,7 @@ impl QueueOffsetOperator {
     pub fn set_lmq_topic_queue_table(&self, lmq_topic_queue_table: HashMap<CheetahString, i64>) {
         let mut table = HashMap::new();
         for (key, value) in lmq_topic_queue_table.iter() {
-            if key.contains("lmq") {
+            if mix_all::is_lmq(Some(key.as_str())) {
                 table.insert(key.clone(), *value);
             }
         }
@@ -234,4 +235,23 @@ mod tests {
 
         assert_eq!(operator.get_queue_offset("new_key".into()), 10);
     }
+
+    #[test]
+    fn set_lmq_topic_queue_table_keeps_lmq_keys_only() {
+        let operator = QueueOffsetOperator::new();
+        let mut new_table = HashMap::new();
+        new_table.insert("%LMQ%lmq-topic-0".into(), 7);
+        new_table.insert("lmq-like-topic-0".into(), 3);
+
+        operator.set_lmq_topic_queue_table(new_table);
+
+        assert_eq!(
+            operator.get_lmq_offset(&CheetahString::from_static_str("%LMQ%lmq-topic-0")),
+            7
+        );
+        assert_eq!(
+            operator.get_lmq_offset(&CheetahString::from_static_str("lmq-like-topic-0")),
+            0
+        );
+    }
 }
@@ -0,0 +1,414 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::sync::Arc;
+
+use cheetah_string::CheetahString;
+use rocketmq_common::common::attribute::cq_type::CQType;
+use rocketmq_common::common::boundary_type::BoundaryType;
+use rocketmq_common::common::message::message_ext_broker_inner::MessageExtBrokerInner;
+use tracing::info;
+
+use crate::base::dispatch_request::DispatchRequest;
+use crate::base::swappable::Swappable;
+use crate::config::message_store_config::MessageStoreConfig;
+use crate::filter::MessageFilter;
+use crate::queue::consume_queue::ConsumeQueueTrait;
+use crate::queue::consume_queue_rocksdb_storage::parse_cq_value;
+use crate::queue::consume_queue_rocksdb_storage::ConsumeQueueRocksDBStorage;
+use crate::queue::consume_queue_rocksdb_storage::OffsetEntry;
+use crate::queue::consume_queue_rocksdb_storage::ROCKSDB_CQ_UNIT_SIZE;
+use crate::queue::file_queue_life_cycle::FileQueueLifeCycle;
+use crate::queue::queue_offset_operator::QueueOffsetOperator;
+use crate::queue::referred_iterator::ReferredIterator;
+use crate::queue::CqUnit;
+
+/// Consume queue whose units live in [`ConsumeQueueRocksDBStorage`] instead of mapped files.
+///
+/// There are no files behind the queue, so the file life cycle operations only keep the
+/// min/max offsets in the storage consistent with the commit log.
+pub struct RocksDBConsumeQueue {
+    message_store_config: Arc<MessageStoreConfig>,
+    storage: Arc<ConsumeQueueRocksDBStorage>,
+    topic: CheetahString,
+    queue_id: i32,
+}
+
+impl RocksDBConsumeQueue {
+    pub fn new(
+        message_store_config: Arc<MessageStoreConfig>,
+        storage: Arc<ConsumeQueueRocksDBStorage>,
+        topic: CheetahString,
+        queue_id: i32,
+    ) -> Self {
+        Self {
+            message_store_config,
+            storage,
+            topic,
+            queue_id,
+        }
+    }
+
+    fn get_store_time(&self, index: i64) -> Option<i64> {
+        self.get_cq_unit_and_store_time(index)
+            .map(|(_, store_time)| store_time)
+    }
+
+    /// Returns the first offset in `[low, high)` whose unit satisfies `pred`, the predicate
+    /// must be monotone over the queue.
+    fn partition_point(
+        &self,
+        mut low: i64,
+        mut high: i64,
+        pred: impl Fn(&CqUnit, i64) -> bool,
+    ) -> i64 {
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match self.get_cq_unit_and_store_time(mid) {
+                Some((unit, store_time)) if !pred(&unit, store_time) => low = mid + 1,
+                _ => high = mid,
+            }
+        }
+        low
+    }
+}
+
+impl Swappable for RocksDBConsumeQueue {
+    fn swap_map(
+        &self,
+        _reserve_num: i32,
+        _force_swap_interval_ms: i64,
+        _normal_swap_interval_ms: i64,
+    ) {
+    }
+
+    fn clean_swapped_map(&self, _force_clean_swap_interval_ms: i64) {}
+}
+
+impl FileQueueLifeCycle for RocksDBConsumeQueue {
+    fn load(&mut self) -> bool {
+        true
+    }
+
+    fn recover(&mut self) {}
+
+    fn check_self(&self) {}
+
+    fn flush(&self, _flush_least_pages: i32) -> bool {
+        self.storage.flush()
+    }
+
+    fn destroy(&mut self) {
+        self.storage.delete_queue(&self.topic, self.queue_id);
+    }
+
+    fn truncate_dirty_logic_files(&mut self, max_commit_log_pos: i64) {
+        let min = self.get_min_offset_in_queue();
+        let max = self.get_max_offset_in_queue();
+        if min >= max {
+            return;
+        }
+        let truncate_from = self.partition_point(min, max, |unit, _| {
+            unit.pos + unit.size as i64 > max_commit_log_pos
+        });
+        if truncate_from >= max {
+            return;
+        }
+        let new_max = self.get(truncate_from - 1).map(|unit| OffsetEntry {
+            phy_offset: unit.pos,
+            cq_offset: unit.queue_offset,
+        });
+        info!(
+            "truncate rocksdb consume queue, topic={}, queue_id={}, from={}",
+            self.topic, self.queue_id, truncate_from
+        );
+        self.storage
+            .truncate_from(&self.topic, self.queue_id, truncate_from, new_max);
+    }
+
+    fn delete_expired_file(&self, min_commit_log_pos: i64) -> i32 {
+        self.correct_min_offset(min_commit_log_pos);
+        0
+    }
+
+    fn roll_next_file(&self, next_begin_offset: i64) -> i64 {
+        next_begin_offset
+    }
+
+    fn is_first_file_available(&self) -> bool {
+        true
+    }
+
+    fn is_first_file_exist(&self) -> bool {
+        true
+    }
+}
+
+impl ConsumeQueueTrait for RocksDBConsumeQueue {
+    fn get_topic(&self) -> &CheetahString {
+        &self.topic
+    }
+
+    fn get_queue_id(&self) -> i32 {
+        self.queue_id
+    }
+
+    fn iterate_from(&self, start_index: i64) -> Option<Box<dyn ReferredIterator<CqUnit>>> {
+        self.iterate_from_with_count(
+            start_index,
+            self.message_store_config.max_msgs_num_batch as i32,
+        )
+    }
+
+    fn iterate_from_with_count(
+        &self,
+        start_index: i64,
+        count: i32,
+    ) -> Option<Box<dyn ReferredIterator<CqUnit>>> {
+        let max_offset = self.get_max_offset_in_queue();
+        if start_index >= max_offset || count <= 0 {
+            return None;
+        }
+        let num = (max_offset - start_index).min(count as i64) as i32;
+        let units = self
+            .storage
+            .range_query(&self.topic, self.queue_id, start_index, num)
+            .into_iter()
+            .filter_map(|(cq_offset, value)| parse_cq_value(cq_offset, &value))
+            .map(|(unit, _)| unit)
+            .collect::<Vec<_>>();
+        Some(Box::new(RocksDBConsumeQueueIterator {
+            units: units.into_iter(),
+        }))
+    }
+
+    fn get(&self, index: i64) -> Option<CqUnit> {
+        self.get_cq_unit_and_store_time(index).map(|(unit, _)| unit)
+    }
+
+    fn get_cq_unit_and_store_time(&self, index: i64) -> Option<(CqUnit, i64)> {
+        if index < 0 {
+            return None;
+        }
+        self.storage.get_unit(&self.topic, self.queue_id, index)
+    }
+
+    fn get_earliest_unit_and_store_time(&self) -> Option<(CqUnit, i64)> {
+        self.get_cq_unit_and_store_time(self.get_min_offset_in_queue())
+    }
+
+    fn get_earliest_unit(&self) -> Option<CqUnit> {
+        self.get(self.get_min_offset_in_queue())
+    }
+
+    fn get_latest_unit(&self) -> Option<CqUnit> {
+        self.get(self.get_max_offset_in_queue() - 1)
+    }
+
+    fn get_last_offset(&self) -> i64 {
+        self.get_max_physic_offset()
+    }
+
+    fn get_min_offset_in_queue(&self) -> i64 {
+        self.storage
+            .get_min_offset(&self.topic, self.queue_id)
+            .map_or(0, |entry| entry.cq_offset)
+    }
+
+    fn get_max_offset_in_queue(&self) -> i64 {
+        self.storage
+            .get_max_offset(&self.topic, self.queue_id)
+            .map_or(0, |entry| entry.cq_offset + 1)
+    }
+
+    fn get_message_total_in_queue(&self) -> i64 {
+        self.get_max_offset_in_queue() - self.get_min_offset_in_queue()
+    }
+
+    fn get_offset_in_queue_by_time(&self, timestamp: i64) -> i64 {
+        self.get_offset_in_queue_by_time_with_boundary(timestamp, BoundaryType::Lower)
+    }
+
+    fn get_offset_in_queue_by_time_with_boundary(
+        &self,
+        timestamp: i64,
+        boundary_type: BoundaryType,
+    ) -> i64 {
+        let min = self.get_min_offset_in_queue();
+        let max = self.get_max_offset_in_queue();
+        if min >= max {
+            return 0;
+        }
+        let (Some(min_time), Some(max_time)) =
+            (self.get_store_time(min), self.get_store_time(max - 1))
+        else {
+            return 0;
+        };
+        if timestamp < min_time {
+            return min;
+        }
+        if timestamp > max_time {
+            return max;
+        }
+        match boundary_type {
+            BoundaryType::Lower => {
+                self.partition_point(min, max, |_, store_time| store_time >= timestamp)
+            }
+            BoundaryType::Upper => {
+                let after = self.partition_point(min, max, |_, store_time| store_time > timestamp);
+                (after - 1).max(min)
+            }
+        }
+    }
+
+    fn get_max_physic_offset(&self) -> i64 {
+        self.storage
+            .get_max_offset(&self.topic, self.queue_id)
+            .map_or(-1, |entry| entry.phy_offset)
+    }
+
+    fn get_min_logic_offset(&self) -> i64 {
+        self.get_min_offset_in_queue()
+    }
+
+    fn get_cq_type(&self) -> CQType {
+        CQType::RocksDBCQ
+    }
+
+    fn get_total_size(&self) -> i64 {
+        self.get_message_total_in_queue() * ROCKSDB_CQ_UNIT_SIZE as i64
+    }
+
+    fn get_unit_size(&self) -> i32 {
+        ROCKSDB_CQ_UNIT_SIZE
+    }
+
+    fn correct_min_offset(&self, min_commit_log_offset: i64) {
+        let min = self.get_min_offset_in_queue();
+        let max = self.get_max_offset_in_queue();
+        if min >= max {
+            return;
+        }
+        let new_min = self.partition_point(min, max, |unit, _| unit.pos >= min_commit_log_offset);
+        if new_min <= min {
+            return;
+        }
+        // keep the last unit so the queue still knows its max offset
+        let new_min = new_min.min(max - 1);
+        if let Some(unit) = self.get(new_min) {
+            self.storage.update_min_offset(
+                &self.topic,
+                self.queue_id,
+                OffsetEntry {
+                    phy_offset: unit.pos,
+                    cq_offset: new_min,
+                },
+            );
+            info!(
+                "correct rocksdb consume queue min offset, topic={}, queue_id={}, min_offset={}",
+                self.topic, self.queue_id, new_min
+            );
+        }
+    }
+
+    fn put_message_position_info_wrapper(&mut self, request: &DispatchRequest) {
+        self.storage.put_unit(
+            &self.topic,
+            self.queue_id,
+            request.consume_queue_offset,
+            request.commit_log_offset,
+            request.msg_size,
+            request.tags_code,
+            request.store_timestamp,
+        );
+    }
+
+    fn assign_queue_offset(
+        &self,
+        queue_offset_operator: &QueueOffsetOperator,
+        msg: &mut MessageExtBrokerInner,
+    ) {
+        let queue_offset = queue_offset_operator.get_queue_offset(CheetahString::from_string(
+            format!("{}-{}", msg.topic(), msg.queue_id()),
+        ));
+        msg.message_ext_inner.queue_offset = queue_offset;
+    }
+
+    fn increase_queue_offset(
+        &self,
+        queue_offset_assigner: &QueueOffsetOperator,
+        msg: &MessageExtBrokerInner,
+        message_num: i16,
+    ) {
+        queue_offset_assigner.increase_queue_offset(
+            CheetahString::from_string(format!("{}-{}", msg.topic(), msg.queue_id())),
+            message_num,
+        );
+    }
+
+    fn estimate_message_count(&self, from: i64, to: i64, filter: &dyn MessageFilter) -> i64 {
+        let from = from.max(self.get_min_offset_in_queue());
+        let to = to.min(self.get_max_offset_in_queue() - 1);
+        if from > to {
+            return 0;
+        }
+        let total = to - from + 1;
+        let max_scan = self.message_store_config.max_consume_queue_scan as i64;
+        let scan = if max_scan > 0 {
+            total.min(max_scan)
+        } else {
+            total
+        };
+        let mut matched = 0i64;
+        let mut sampled = 0i64;
+        for (cq_offset, value) in
+            self.storage
+                .range_query(&self.topic, self.queue_id, from, scan as i32)
+        {
+            let Some((unit, _)) = parse_cq_value(cq_offset, &value) else {
+                continue;
+            };
+            sampled += 1;
+            if filter.is_matched_by_consume_queue(unit.get_valid_tags_code_as_long(), None) {
+                matched += 1;
+            }
+        }
+        if sampled == 0 {
+            return 0;
+        }
+        matched * total / sampled
+    }
+}
+
+struct RocksDBConsumeQueueIterator {
+    units: std::vec::IntoIter<CqUnit>,
+}
+
+impl ReferredIterator<CqUnit> for RocksDBConsumeQueueIterator {
+    fn release(&mut self) {}
+
+    fn next_and_release(&mut self) -> Option<Self::Item> {
+        self.next()
+    }
+}
+
+impl Iterator for RocksDBConsumeQueueIterator {
+    type Item = CqUnit;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.units.next()
+    }
+}
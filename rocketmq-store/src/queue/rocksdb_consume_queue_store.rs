@@ -0,0 +1,392 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use cheetah_string::CheetahString;
+use rocketmq_common::common::boundary_type::BoundaryType;
+use rocketmq_common::common::message::message_ext_broker_inner::MessageExtBrokerInner;
+use rocketmq_common::common::mix_all;
+use tracing::info;
+
+use crate::base::dispatch_request::DispatchRequest;
+use crate::config::message_store_config::MessageStoreConfig;
+use crate::queue::consume_queue::ConsumeQueueTrait;
+use crate::queue::consume_queue_rocksdb_storage::ConsumeQueueRocksDBStorage;
+use crate::queue::consume_queue_store::ConsumeQueueStoreTrait;
+use crate::queue::queue_offset_operator::QueueOffsetOperator;
+use crate::queue::rocksdb_consume_queue::RocksDBConsumeQueue;
+use crate::queue::ArcConsumeQueue;
+use crate::queue::ConsumeQueueTable;
+use crate::queue::CqUnit;
+use crate::store_path_config_helper::get_store_path_rocksdb_consume_queue;
+
+/// Consume queue store keeping every queue in one RocksDB instance.
+///
+/// A broker with many queues needs one open file per file consume queue, here all queues share
+/// the handles of a single embedded key-value store.
+#[derive(Clone)]
+pub struct RocksDBConsumeQueueStore {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    message_store_config: Arc<MessageStoreConfig>,
+    storage: Arc<ConsumeQueueRocksDBStorage>,
+    queue_offset_operator: QueueOffsetOperator,
+    consume_queue_table: Arc<ConsumeQueueTable>,
+}
+
+impl RocksDBConsumeQueueStore {
+    pub fn new(message_store_config: Arc<MessageStoreConfig>) -> Self {
+        let storage = ConsumeQueueRocksDBStorage::new(get_store_path_rocksdb_consume_queue(
+            message_store_config.store_path_root_dir.as_str(),
+        ));
+        Self {
+            inner: Arc::new(Inner {
+                message_store_config,
+                storage: Arc::new(storage),
+                queue_offset_operator: QueueOffsetOperator::new(),
+                consume_queue_table: Arc::new(Default::default()),
+            }),
+        }
+    }
+
+    /// Moves the min offset of every queue past the commit log data that has been deleted.
+    pub fn clean_expired_consume_queues(&self, min_phy_offset: i64) {
+        let consume_queue_table = self.inner.consume_queue_table.lock().clone();
+        for queues in consume_queue_table.values() {
+            for consume_queue in queues.values() {
+                consume_queue.correct_min_offset(min_phy_offset);
+            }
+        }
+    }
+
+    /// Drops the queues of topics for which `topic_exists` returns false.
+    pub fn clean_dirty_consume_queues(&self, topic_exists: impl Fn(&CheetahString) -> bool) {
+        for (topic, queue_id, _) in self.inner.storage.get_all_max_offsets() {
+            if topic_exists(&topic) {
+                continue;
+            }
+            info!(
+                "clean dirty rocksdb consume queue, topic={}, queue_id={}",
+                topic, queue_id
+            );
+            self.remove_consume_queue(&topic, queue_id);
+        }
+    }
+
+    fn remove_consume_queue(&self, topic: &CheetahString, queue_id: i32) {
+        self.inner.storage.delete_queue(topic, queue_id);
+        self.inner.queue_offset_operator.remove(topic, queue_id);
+        let mut consume_queue_table = self.inner.consume_queue_table.lock();
+        if let Some(queues) = consume_queue_table.get_mut(topic) {
+            queues.remove(&queue_id);
+            if queues.is_empty() {
+                consume_queue_table.remove(topic);
+            }
+        }
+    }
+}
+
+impl ConsumeQueueStoreTrait for RocksDBConsumeQueueStore {
+    fn start(&self) {
+        info!("RocksDB consume queue store start");
+    }
+
+    fn load(&mut self) -> bool {
+        if !self.inner.storage.start() {
+            return false;
+        }
+        for (topic, queue_id, _) in self.inner.storage.get_all_max_offsets() {
+            self.find_or_create_consume_queue(&topic, queue_id);
+        }
+        info!("load rocksdb consume queue store all over, OK");
+        true
+    }
+
+    fn load_after_destroy(&self) -> bool {
+        // `destroy` closed the storage, the queues start over in a fresh one
+        self.inner.storage.start()
+    }
+
+    async fn recover(&self) {}
+
+    async fn recover_concurrently(&self) -> bool {
+        true
+    }
+
+    fn shutdown(&self) -> bool {
+        self.inner.storage.shutdown();
+        true
+    }
+
+    fn destroy(&self) {
+        self.inner.storage.destroy();
+        self.inner.consume_queue_table.lock().clear();
+    }
+
+    fn destroy_queue(&self, consume_queue: &dyn ConsumeQueueTrait) {
+        self.remove_consume_queue(consume_queue.get_topic(), consume_queue.get_queue_id());
+    }
+
+    fn flush(&self, _consume_queue: &dyn ConsumeQueueTrait, _flush_least_pages: i32) -> bool {
+        self.inner.storage.flush()
+    }
+
+    async fn clean_expired(&self, min_phy_offset: i64) {
+        self.clean_expired_consume_queues(min_phy_offset);
+    }
+
+    fn check_self(&self) {}
+
+    fn delete_expired_file(
+        &self,
+        consume_queue: &dyn ConsumeQueueTrait,
+        min_commit_log_pos: i64,
+    ) -> i32 {
+        self.find_or_create_consume_queue(consume_queue.get_topic(), consume_queue.get_queue_id())
+            .delete_expired_file(min_commit_log_pos)
+    }
+
+    fn is_first_file_available(&self, _consume_queue: &dyn ConsumeQueueTrait) -> bool {
+        true
+    }
+
+    fn is_first_file_exist(&self, _consume_queue: &dyn ConsumeQueueTrait) -> bool {
+        true
+    }
+
+    fn roll_next_file(&self, _consume_queue: &dyn ConsumeQueueTrait, offset: i64) -> i64 {
+        offset
+    }
+
+    fn truncate_dirty(&self, offset_to_truncate: i64) {
+        let consume_queue_table = self.inner.consume_queue_table.lock().clone();
+        for queues in consume_queue_table.values() {
+            for consume_queue in queues.values() {
+                let mut consume_queue = consume_queue.clone();
+                consume_queue.truncate_dirty_logic_files(offset_to_truncate);
+            }
+        }
+    }
+
+    fn put_message_position_info_wrapper_with_cq(
+        &self,
+        consume_queue: &mut dyn ConsumeQueueTrait,
+        request: &DispatchRequest,
+    ) {
+        consume_queue.put_message_position_info_wrapper(request);
+    }
+
+    fn put_message_position_info_wrapper(&self, request: &DispatchRequest) {
+        let mut consume_queue = self.find_or_create_consume_queue(&request.topic, request.queue_id);
+        self.put_message_position_info_wrapper_with_cq(consume_queue.as_mut().as_mut(), request);
+    }
+
+    async fn range_query(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+        start_index: i64,
+        num: i32,
+    ) -> Vec<Bytes> {
+        self.inner
+            .storage
+            .range_query(topic, queue_id, start_index, num)
+            .into_iter()
+            .map(|(_, value)| value)
+            .collect()
+    }
+
+    async fn get(&self, topic: &CheetahString, queue_id: i32, start_index: i64) -> Bytes {
+        self.inner
+            .storage
+            .range_query(topic, queue_id, start_index, 1)
+            .into_iter()
+            .next()
+            .filter(|(cq_offset, _)| *cq_offset == start_index)
+            .map(|(_, value)| value)
+            .unwrap_or_default()
+    }
+
+    fn get_consume_queue_table(&self) -> Arc<ConsumeQueueTable> {
+        self.inner.consume_queue_table.clone()
+    }
+
+    fn assign_queue_offset(&self, msg: &mut MessageExtBrokerInner) {
+        let consume_queue = self.find_or_create_consume_queue(msg.get_topic(), msg.queue_id());
+        consume_queue.assign_queue_offset(&self.inner.queue_offset_operator, msg);
+    }
+
+    fn increase_queue_offset(&self, msg: &MessageExtBrokerInner, message_num: i16) {
+        let consume_queue = self.find_or_create_consume_queue(msg.get_topic(), msg.queue_id());
+        consume_queue.increase_queue_offset(&self.inner.queue_offset_operator, msg, message_num);
+    }
+
+    fn increase_lmq_offset(&self, queue_key: &str, message_num: i16) {
+        self.inner
+            .queue_offset_operator
+            .increase_lmq_offset(&CheetahString::from_slice(queue_key), message_num);
+    }
+
+    fn get_lmq_queue_offset(&self, queue_key: &str) -> i64 {
+        self.inner
+            .queue_offset_operator
+            .get_lmq_offset(&CheetahString::from_slice(queue_key))
+    }
+
+    fn recover_offset_table(&mut self, min_phy_offset: i64) {
+        let topic_queue_table = self.get_topic_queue_table();
+        self.clean_expired_consume_queues(min_phy_offset);
+        self.set_topic_queue_table(topic_queue_table);
+    }
+
+    fn set_topic_queue_table(&mut self, topic_queue_table: HashMap<CheetahString, i64>) {
+        let (lmq_topic_queue_table, topic_queue_table): (HashMap<_, _>, HashMap<_, _>) =
+            topic_queue_table
+                .into_iter()
+                .partition(|(key, _)| mix_all::is_lmq(Some(key.as_str())));
+        self.inner
+            .queue_offset_operator
+            .set_topic_queue_table(topic_queue_table);
+        self.inner
+            .queue_offset_operator
+            .set_lmq_topic_queue_table(lmq_topic_queue_table);
+    }
+
+    fn remove_topic_queue_table(&mut self, topic: &CheetahString, queue_id: i32) {
+        self.remove_consume_queue(topic, queue_id);
+    }
+
+    fn get_topic_queue_table(&self) -> HashMap<CheetahString, i64> {
+        self.inner
+            .storage
+            .get_all_max_offsets()
+            .into_iter()
+            .map(|(topic, queue_id, entry)| {
+                (
+                    CheetahString::from_string(format!("{topic}-{queue_id}")),
+                    entry.cq_offset + 1,
+                )
+            })
+            .collect()
+    }
+
+    fn get_max_phy_offset_in_consume_queue(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+    ) -> Option<i64> {
+        self.inner
+            .storage
+            .get_max_offset(topic, queue_id)
+            .map(|entry| entry.phy_offset)
+    }
+
+    fn get_max_offset(&self, topic: &CheetahString, queue_id: i32) -> Option<i64> {
+        Some(
+            self.inner
+                .queue_offset_operator
+                .current_queue_offset(&format!("{topic}-{queue_id}").into()),
+        )
+    }
+
+    fn get_max_phy_offset_in_consume_queue_global(&self) -> i64 {
+        self.inner
+            .storage
+            .get_all_max_offsets()
+            .into_iter()
+            .map(|(_, _, entry)| entry.phy_offset)
+            .max()
+            .unwrap_or(-1)
+    }
+
+    fn get_min_offset_in_queue(&self, topic: &CheetahString, queue_id: i32) -> i64 {
+        self.find_or_create_consume_queue(topic, queue_id)
+            .get_min_offset_in_queue()
+    }
+
+    fn get_max_offset_in_queue(&self, topic: &CheetahString, queue_id: i32) -> i64 {
+        self.find_or_create_consume_queue(topic, queue_id)
+            .get_max_offset_in_queue()
+    }
+
+    fn get_offset_in_queue_by_time(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+        timestamp: i64,
+        boundary_type: BoundaryType,
+    ) -> i64 {
+        self.find_or_create_consume_queue(topic, queue_id)
+            .get_offset_in_queue_by_time_with_boundary(timestamp, boundary_type)
+    }
+
+    fn find_or_create_consume_queue(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+    ) -> ArcConsumeQueue {
+        let mut consume_queue_table = self.inner.consume_queue_table.lock();
+        consume_queue_table
+            .entry(topic.clone())
+            .or_default()
+            .entry(queue_id)
+            .or_insert_with(|| {
+                ArcConsumeQueue::new(Box::new(RocksDBConsumeQueue::new(
+                    self.inner.message_store_config.clone(),
+                    self.inner.storage.clone(),
+                    topic.clone(),
+                    queue_id,
+                )))
+            })
+            .clone()
+    }
+
+    fn find_consume_queue_map(
+        &self,
+        topic: &CheetahString,
+    ) -> Option<HashMap<i32, ArcConsumeQueue>> {
+        self.inner.consume_queue_table.lock().get(topic).cloned()
+    }
+
+    fn get_total_size(&self) -> i64 {
+        let consume_queue_table = self.inner.consume_queue_table.lock().clone();
+        consume_queue_table
+            .values()
+            .flat_map(|queues| queues.values())
+            .map(|consume_queue| consume_queue.get_total_size())
+            .sum()
+    }
+
+    fn get_store_time(&self, _cq_unit: &CqUnit) -> i64 {
+        // units do not know their queue, the store time is read along with the unit through
+        // `ConsumeQueueTrait::get_cq_unit_and_store_time`
+        -1
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
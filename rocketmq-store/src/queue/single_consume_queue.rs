@@ -485,22 +485,27 @@ impl<MS: MessageStore> FileQueueLifeCycle for ConsumeQueue<MS> {
 
     #[inline]
     fn delete_expired_file(&self, min_commit_log_pos: i64) -> i32 {
-        todo!()
+        let count = self
+            .mapped_file_queue
+            .delete_expired_file_by_offset(min_commit_log_pos, CQ_STORE_UNIT_SIZE);
+        self.correct_min_offset(min_commit_log_pos);
+        count
     }
 
     #[inline]
     fn roll_next_file(&self, next_begin_offset: i64) -> i64 {
-        todo!()
+        let total_units_in_file = (self.mapped_file_size / CQ_STORE_UNIT_SIZE) as i64;
+        next_begin_offset + total_units_in_file - next_begin_offset % total_units_in_file
     }
 
     #[inline]
     fn is_first_file_available(&self) -> bool {
-        todo!()
+        self.mapped_file_queue.is_first_file_available()
     }
 
     #[inline]
     fn is_first_file_exist(&self) -> bool {
-        todo!()
+        self.mapped_file_queue.is_first_file_exist()
     }
 }
 
@@ -535,10 +540,7 @@ impl<MS: MessageStore> ConsumeQueueTrait for ConsumeQueue<MS> {
 
     #[inline]
     fn get(&self, index: i64) -> Option<CqUnit> {
-        match self.iterate_from(index) {
-            None => None,
-            Some(value) => None,
-        }
+        self.iterate_from(index)?.next_and_release()
     }
 
     #[inline]
@@ -553,12 +555,12 @@ impl<MS: MessageStore> ConsumeQueueTrait for ConsumeQueue<MS> {
 
     #[inline]
     fn get_earliest_unit(&self) -> Option<CqUnit> {
-        todo!()
+        self.get(self.get_min_offset_in_queue())
     }
 
     #[inline]
     fn get_latest_unit(&self) -> Option<CqUnit> {
-        todo!()
+        self.get(self.get_max_offset_in_queue() - 1)
     }
 
     #[inline]
@@ -613,7 +615,9 @@ impl<MS: MessageStore> ConsumeQueueTrait for ConsumeQueue<MS> {
 
     #[inline]
     fn correct_min_offset(&self, min_commit_log_offset: i64) {
-        if min_commit_log_offset >= self.mapped_file_queue.get_max_offset() {
+        // Check if the consume queue is the state of deprecation.
+        if self.min_logic_offset.load(Ordering::Acquire) >= self.mapped_file_queue.get_max_offset()
+        {
             info!(
                 "ConsumeQueue[Topic={}, queue-id={}] contains no valid entries",
                 self.topic, self.queue_id
@@ -623,46 +627,38 @@ impl<MS: MessageStore> ConsumeQueueTrait for ConsumeQueue<MS> {
         // Check whether the consume queue maps no valid data at all. This check may cost 1 IO
         // operation. The rationale is that consume queue always preserves the last file. In
         // case there are many deprecated topics, This check would save a lot of efforts.
-        let last_mapped_file = self.mapped_file_queue.get_last_mapped_file();
-        if last_mapped_file.is_none() {
-            return;
-        }
-        let last_mapped_file = last_mapped_file.unwrap();
-        let max_readable_position = last_mapped_file.get_read_position();
-        let mut last_record = last_mapped_file.select_mapped_buffer(
-            max_readable_position - CQ_STORE_UNIT_SIZE,
-            CQ_STORE_UNIT_SIZE,
-        );
-        if let Some(ref mut result) = last_record {
-            result.mapped_file = Some(last_mapped_file.clone());
-        }
-        if let Some(last_record) = last_record {
-            let mut bytes = last_record
-                .mapped_file
-                .as_ref()
-                .unwrap()
-                .get_bytes(last_record.start_offset as usize, last_record.size as usize)
-                .unwrap();
-            let commit_log_offset = bytes.get_i64();
-            if commit_log_offset < min_commit_log_offset {
-                self.min_logic_offset.store(
-                    max_readable_position as i64 + last_mapped_file.get_file_from_offset() as i64,
-                    Ordering::SeqCst,
-                );
-                info!(
-                    "ConsumeQueue[topic={}, queue-id={}] contains no valid entries. Min-offset is \
-                     assigned as: {}.",
-                    self.topic,
-                    self.queue_id,
-                    self.get_min_offset_in_queue()
-                );
-                return;
+        if let Some(last_mapped_file) = self.mapped_file_queue.get_last_mapped_file() {
+            let max_readable_position = last_mapped_file.get_read_position();
+            if let Some(last_record) = last_mapped_file.select_mapped_buffer(
+                max_readable_position - CQ_STORE_UNIT_SIZE,
+                CQ_STORE_UNIT_SIZE,
+            ) {
+                let commit_log_offset = last_record
+                    .get_bytes_ref()
+                    .map_or(-1, |bytes| bytes.as_ref().get_i64());
+                last_mapped_file.release();
+                if commit_log_offset < min_commit_log_offset {
+                    // Keep the largest known consume offset, even if this consume queue contains
+                    // no valid entries at all. Let min logic offset point to a future slot.
+                    self.min_logic_offset.store(
+                        max_readable_position as i64
+                            + last_mapped_file.get_file_from_offset() as i64,
+                        Ordering::SeqCst,
+                    );
+                    info!(
+                        "ConsumeQueue[topic={}, queue-id={}] contains no valid entries. \
+                         Min-offset is assigned as: {}.",
+                        self.topic,
+                        self.queue_id,
+                        self.get_min_offset_in_queue()
+                    );
+                    return;
+                }
             }
         }
 
-        let mapped_file = self.mapped_file_queue.get_first_mapped_file();
         let mut min_ext_addr = 1i64;
-        if let Some(mapped_file) = mapped_file {
+        if let Some(mapped_file) = self.mapped_file_queue.get_first_mapped_file() {
             // Search from previous min logical offset. Typically, a consume queue file segment
             // contains 300,000 entries searching from previous position saves
             // significant amount of comparisons and IOs
@@ -673,36 +669,35 @@ impl<MS: MessageStore> ConsumeQueueTrait for ConsumeQueue<MS> {
                 intact = false;
                 start = 0;
             }
-            if start > mapped_file.get_file_size() as i64 {
+            if start > mapped_file.get_read_position() as i64 {
                 error!(
                     "[Bug][InconsistentState] ConsumeQueue file {} should have been deleted",
                     mapped_file.get_file_name()
                 );
                 return;
             }
-            let result = mapped_file.select_mapped_buffer_with_position(start as i32);
-            if result.is_none() {
+            let Some(result) = mapped_file.select_mapped_buffer_with_position(start as i32) else {
                 warn!(
                     "[Bug] Failed to scan consume queue entries from file on correcting min \
                      offset: {}",
                     mapped_file.get_file_name()
                 );
                 return;
-            }
-            let mut result = result.unwrap();
-            result.mapped_file = Some(mapped_file);
-            if result.size == 0 {
+            };
+            let buffer = result.get_bytes().unwrap_or_default();
+            mapped_file.release();
+            // No valid consume entries
+            if buffer.len() < CQ_STORE_UNIT_SIZE as usize {
                 debug!(
                     "ConsumeQueue[topic={}, queue-id={}] contains no valid entries",
                     self.topic, self.queue_id
                 );
                 return;
             }
-            let mapped = result.mapped_file.as_ref().unwrap();
-            let commit_log_offset = mapped
-                .get_bytes(result.start_offset as usize, 8)
-                .unwrap()
-                .get_i64();
+            let offset_py_at = |position: usize| (&buffer[position..position + 8]).get_i64();
+            // Verify whether the previous value is still valid or not before conducting binary
+            // search
+            let commit_log_offset = offset_py_at(0);
             if intact && commit_log_offset >= min_commit_log_offset {
                 info!(
                     "Abort correction as previous min-offset points to {}, which is greater than \
@@ -711,40 +706,43 @@ impl<MS: MessageStore> ConsumeQueueTrait for ConsumeQueue<MS> {
                 );
                 return;
             }
+            // Starting from here, we need to search the first consume queue entry whose
+            // commit log offset is not less than the min commit log offset.
+            let unit_size = CQ_STORE_UNIT_SIZE as usize;
             let mut low = 0;
-            let mut high = result.size - CQ_STORE_UNIT_SIZE;
-            loop {
-                if high - low <= CQ_STORE_UNIT_SIZE {
-                    break;
-                }
-                let mid = (low + high) / 2 / CQ_STORE_UNIT_SIZE * CQ_STORE_UNIT_SIZE;
-                let commit_log_offset = mapped.get_bytes(mid as usize, 8).unwrap().get_i64();
-
-                match commit_log_offset.cmp(&min_commit_log_offset) {
+            let mut high = buffer.len() / unit_size * unit_size - unit_size;
+            while high - low > unit_size {
+                let mid = (low + high) / 2 / unit_size * unit_size;
+                match offset_py_at(mid).cmp(&min_commit_log_offset) {
                     std::cmp::Ordering::Greater => high = mid,
                     std::cmp::Ordering::Equal => {
                         low = mid;
                         high = mid;
-                        break;
                     }
                     std::cmp::Ordering::Less => low = mid,
                 }
             }
-            let mut i = low;
-            while i <= high {
-                let offset_py = mapped.get_bytes(i as usize, 8).unwrap().get_i64();
-                let tags_code = mapped.get_bytes((i + 12) as usize, 8).unwrap().get_i64();
-                if offset_py >= min_commit_log_offset {
+            // Examine the last one or two entries
+            for position in (low..=high).step_by(unit_size) {
+                if offset_py_at(position) >= min_commit_log_offset {
                     self.min_logic_offset.store(
-                        mapped.get_file_from_offset() as i64 + i as i64 + start,
+                        mapped_file.get_file_from_offset() as i64 + start + position as i64,
                         Ordering::SeqCst,
                     );
+                    info!(
+                        "Compute logical min offset: {}, topic: {}, queueId: {}",
+                        self.get_min_offset_in_queue(),
+                        self.topic,
+                        self.queue_id
+                    );
+                    let tags_code = (&buffer[position + 12..position + 20]).get_i64();
+                    // This maybe not take effect, when not every consume queue has an extended
+                    // file.
                     if ConsumeQueue::<MS>::is_ext_addr(tags_code) {
                         min_ext_addr = tags_code;
                     }
                     break;
                 }
-                i += CQ_STORE_UNIT_SIZE;
             }
         }
 
@@ -788,12 +786,12 @@ impl<MS: MessageStore> ConsumeQueueTrait for ConsumeQueue<MS> {
                 request.consume_queue_offset,
             ) {
                 let message_store_config = self.message_store.get_message_store_config();
+                let store_checkpoint = self.message_store.get_store_checkpoint();
                 if message_store_config.broker_role == BrokerRole::Slave
                     || message_store_config.enable_dledger_commit_log
                 {
-                    unimplemented!("slave or dledger commit log not support")
+                    store_checkpoint.set_physic_msg_timestamp(request.store_timestamp as u64);
                 }
-                let store_checkpoint = self.message_store.get_store_checkpoint();
                 store_checkpoint.set_logics_msg_timestamp(request.store_timestamp as u64);
                 //if (MultiDispatchUtils.checkMultiDispatchQueue(this.messageStore.
                 // getMessageStoreConfig(), request)) {
@@ -916,9 +914,12 @@ impl Iterator for ConsumeQueueIterator {
                 if self.counter * CQ_STORE_UNIT_SIZE >= value.size {
                     return None;
                 }
-                let mmp = value.mapped_file.as_ref().unwrap().get_mapped_file();
-                let start =
-                    value.start_offset as usize + (self.counter * CQ_STORE_UNIT_SIZE) as usize;
+                let mapped_file = value.mapped_file.as_ref().unwrap();
+                let mmp = mapped_file.get_mapped_file();
+                // start_offset is a logical offset, the mapped buffer is relative to the file
+                let physical_offset =
+                    value.start_offset as i64 + (self.counter * CQ_STORE_UNIT_SIZE) as i64;
+                let start = (physical_offset - mapped_file.get_file_from_offset() as i64) as usize;
                 self.counter += 1;
                 let end = start + CQ_STORE_UNIT_SIZE as usize;
                 let mut bytes = Bytes::copy_from_slice(&mmp[start..end]);
@@ -926,7 +927,7 @@ impl Iterator for ConsumeQueueIterator {
                 let size = bytes.get_i32();
                 let tags_code = bytes.get_i64();
                 let mut cq_unit = CqUnit {
-                    queue_offset: start as i64 / CQ_STORE_UNIT_SIZE as i64,
+                    queue_offset: physical_offset / CQ_STORE_UNIT_SIZE as i64,
                     size,
                     pos,
                     tags_code,
@@ -29,6 +29,9 @@ pub enum StoreError {
 
     #[error("General store error: {0}")]
     General(String),
+
+    #[error("Tiered storage error: {0}")]
+    Tiered(String),
 }
 
 #[derive(Debug, Error)]
@@ -75,4 +78,10 @@ mod tests {
             "General store error: An error occurred"
         );
     }
+
+    #[test]
+    fn tiered_storage_error() {
+        let error = StoreError::Tiered("upload failed".to_string());
+        assert_eq!(format!("{}", error), "Tiered storage error: upload failed");
+    }
 }
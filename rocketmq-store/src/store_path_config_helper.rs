@@ -38,6 +38,52 @@ pub fn get_store_path_batch_consume_queue(root_dir: &str) -> String {
         .into_owned()
 }
 
+pub fn get_store_path_rocksdb_consume_queue(root_dir: &str) -> String {
+    PathBuf::from(root_dir)
+        .join("rocksdb")
+        .join("consumequeue")
+        .to_string_lossy()
+        .into_owned()
+}
+
+pub fn get_store_path_compaction_log(root_dir: &str) -> String {
+    PathBuf::from(root_dir)
+        .join("compaction")
+        .join("compactionLog")
+        .to_string_lossy()
+        .into_owned()
+}
+
+pub fn get_store_path_compaction_consume_queue(root_dir: &str) -> String {
+    PathBuf::from(root_dir)
+        .join("compaction")
+        .join("compactionCq")
+        .to_string_lossy()
+        .into_owned()
+}
+
+pub fn get_store_path_compaction_position(root_dir: &str) -> String {
+    PathBuf::from(root_dir)
+        .join("compaction")
+        .join("position")
+        .to_string_lossy()
+        .into_owned()
+}
+
+pub fn get_store_path_timer_log(root_dir: &str) -> String {
+    PathBuf::from(root_dir)
+        .join("timerlog")
+        .to_string_lossy()
+        .into_owned()
+}
+
+pub fn get_store_path_timer_wheel(root_dir: &str) -> String {
+    PathBuf::from(root_dir)
+        .join("timerwheel")
+        .to_string_lossy()
+        .into_owned()
+}
+
 pub fn get_store_path_index(root_dir: &str) -> String {
     PathBuf::from(root_dir)
         .join("index")
@@ -104,6 +150,44 @@ mod tests {
                 .to_string_lossy()
                 .into_owned()
         );
+        assert_eq!(
+            get_store_path_rocksdb_consume_queue(root_dir),
+            PathBuf::from(root_dir)
+                .join("rocksdb")
+                .join("consumequeue")
+                .to_string_lossy()
+                .into_owned()
+        );
+        assert_eq!(
+            get_store_path_compaction_log(root_dir),
+            PathBuf::from(root_dir)
+                .join("compaction")
+                .join("compactionLog")
+                .to_string_lossy()
+                .into_owned()
+        );
+        assert_eq!(
+            get_store_path_compaction_consume_queue(root_dir),
+            PathBuf::from(root_dir)
+                .join("compaction")
+                .join("compactionCq")
+                .to_string_lossy()
+                .into_owned()
+        );
+        assert_eq!(
+            get_store_path_timer_log(root_dir),
+            PathBuf::from(root_dir)
+                .join("timerlog")
+                .to_string_lossy()
+                .into_owned()
+        );
+        assert_eq!(
+            get_store_path_timer_wheel(root_dir),
+            PathBuf::from(root_dir)
+                .join("timerwheel")
+                .to_string_lossy()
+                .into_owned()
+        );
         assert_eq!(
             get_store_path_index(root_dir),
             PathBuf::from(root_dir)
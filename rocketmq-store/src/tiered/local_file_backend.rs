@@ -0,0 +1,131 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::io::ErrorKind;
+use std::io::SeekFrom;
+use std::path::Path;
+use std::path::PathBuf;
+
+use bytes::Bytes;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncSeekExt;
+
+use crate::store_error::StoreError;
+use crate::tiered::tiered_storage_backend::TieredStorageBackend;
+
+/// Keeps offloaded segments as plain files below a root directory, typically a mount of
+/// cheaper, larger disks. Object keys map to relative paths.
+pub struct LocalFileBackend {
+    root_dir: PathBuf,
+}
+
+impl LocalFileBackend {
+    pub fn new(root_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            root_dir: root_dir.into(),
+        }
+    }
+
+    #[inline]
+    fn object_path(&self, key: &str) -> PathBuf {
+        self.root_dir.join(key)
+    }
+}
+
+fn io_error(action: &str, key: &str, error: std::io::Error) -> StoreError {
+    StoreError::Tiered(format!("{action} {key} failed: {error}"))
+}
+
+impl TieredStorageBackend for LocalFileBackend {
+    async fn put_file(&self, key: &str, source: &Path) -> Result<(), StoreError> {
+        let path = self.object_path(key);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| io_error("create dir for", key, e))?;
+        }
+        // write aside and rename, so readers never see a partial object
+        let tmp_path = path.with_extension("tmp");
+        tokio::fs::copy(source, &tmp_path)
+            .await
+            .map_err(|e| io_error("copy", key, e))?;
+        tokio::fs::rename(&tmp_path, &path)
+            .await
+            .map_err(|e| io_error("rename", key, e))
+    }
+
+    async fn get_object_range(
+        &self,
+        key: &str,
+        position: u64,
+        length: u64,
+    ) -> Result<Bytes, StoreError> {
+        let mut file = tokio::fs::File::open(self.object_path(key))
+            .await
+            .map_err(|e| io_error("open", key, e))?;
+        file.seek(SeekFrom::Start(position))
+            .await
+            .map_err(|e| io_error("seek", key, e))?;
+        let mut buffer = vec![0u8; length as usize];
+        file.read_exact(&mut buffer)
+            .await
+            .map_err(|e| io_error("read", key, e))?;
+        Ok(Bytes::from(buffer))
+    }
+
+    async fn object_size(&self, key: &str) -> Result<Option<u64>, StoreError> {
+        match tokio::fs::metadata(self.object_path(key)).await {
+            Ok(metadata) => Ok(Some(metadata.len())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(io_error("stat", key, e)),
+        }
+    }
+
+    async fn delete_object(&self, key: &str) -> Result<(), StoreError> {
+        match tokio::fs::remove_file(self.object_path(key)).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(io_error("delete", key, e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn put_read_and_delete_objects() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let backend = LocalFileBackend::new(temp_dir.path());
+        let key = "broker-a/commitlog/00000000000000000000";
+        let source = temp_dir.path().join("source");
+        std::fs::write(&source, b"0123456789").unwrap();
+
+        assert_eq!(backend.object_size(key).await.unwrap(), None);
+        backend.put_file(key, &source).await.unwrap();
+        assert_eq!(backend.object_size(key).await.unwrap(), Some(10));
+        assert_eq!(
+            backend.get_object_range(key, 3, 4).await.unwrap(),
+            Bytes::from_static(b"3456")
+        );
+        assert!(backend.get_object_range(key, 8, 4).await.is_err());
+
+        backend.delete_object(key).await.unwrap();
+        backend.delete_object(key).await.unwrap();
+        assert_eq!(backend.object_size(key).await.unwrap(), None);
+    }
+}
@@ -0,0 +1,306 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::UNIX_EPOCH;
+
+use cheetah_string::CheetahString;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_common::UtilAll::offset_to_file_name;
+use tokio::sync::Notify;
+use tracing::info;
+use tracing::warn;
+
+use crate::store_error::StoreError;
+use crate::tiered::tiered_metadata::SegmentMetadata;
+use crate::tiered::tiered_metadata::TieredMetadataStore;
+use crate::tiered::tiered_storage_backend::TieredStorageBackend;
+use crate::tiered::tiered_store_config::TieredStoreConfig;
+
+/// Periodically uploads sealed commit log and consume queue files to the backend and drops
+/// offloaded segments past their reserved time.
+///
+/// A file is sealed once a newer file exists next to it, as both logs only ever append to
+/// their last file.
+pub(crate) struct SegmentUploadService<B> {
+    tiered_config: Arc<TieredStoreConfig>,
+    commit_log_dir: PathBuf,
+    consume_queue_dir: PathBuf,
+    backend: Arc<B>,
+    metadata: Arc<TieredMetadataStore>,
+    shutdown: Notify,
+}
+
+impl<B: TieredStorageBackend> SegmentUploadService<B> {
+    pub fn new(
+        tiered_config: Arc<TieredStoreConfig>,
+        commit_log_dir: PathBuf,
+        consume_queue_dir: PathBuf,
+        backend: Arc<B>,
+        metadata: Arc<TieredMetadataStore>,
+    ) -> Self {
+        Self {
+            tiered_config,
+            commit_log_dir,
+            consume_queue_dir,
+            backend,
+            metadata,
+            shutdown: Notify::new(),
+        }
+    }
+
+    pub fn start(self: Arc<Self>) {
+        let interval = Duration::from_millis(self.tiered_config.upload_interval_ms.max(1));
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        self.upload_sealed_segments().await;
+                        self.delete_expired_segments().await;
+                    }
+                    _ = self.shutdown.notified() => {
+                        break;
+                    }
+                }
+            }
+            info!("SegmentUploadService shutdown now......");
+        });
+    }
+
+    pub fn shutdown(&self) {
+        self.shutdown.notify_one();
+    }
+
+    /// Uploads every sealed file that is not in the backend yet, returning how many were
+    /// uploaded.
+    pub async fn upload_sealed_segments(&self) -> usize {
+        let broker_name = &self.tiered_config.broker_name;
+        let mut uploaded = 0;
+        for (base_offset, path) in sealed_files(&self.commit_log_dir).await {
+            if self.metadata.contains_commit_log_segment(base_offset) {
+                continue;
+            }
+            let key = format!(
+                "{broker_name}/commitlog/{}",
+                offset_to_file_name(base_offset as u64)
+            );
+            match self.upload(&path, key, base_offset).await {
+                Ok(segment) => {
+                    self.metadata.add_commit_log_segment(segment);
+                    uploaded += 1;
+                }
+                Err(e) => warn!("upload commit log file {} failed: {}", path.display(), e),
+            }
+        }
+
+        for (topic, queue_id, queue_dir) in queue_dirs(&self.consume_queue_dir).await {
+            for (base_offset, path) in sealed_files(&queue_dir).await {
+                if self
+                    .metadata
+                    .contains_consume_queue_segment(&topic, queue_id, base_offset)
+                {
+                    continue;
+                }
+                let key = format!(
+                    "{broker_name}/consumequeue/{topic}/{queue_id}/{}",
+                    offset_to_file_name(base_offset as u64)
+                );
+                match self.upload(&path, key, base_offset).await {
+                    Ok(segment) => {
+                        self.metadata
+                            .add_consume_queue_segment(&topic, queue_id, segment);
+                        uploaded += 1;
+                    }
+                    Err(e) => warn!("upload consume queue file {} failed: {}", path.display(), e),
+                }
+            }
+        }
+
+        if uploaded > 0 {
+            if let Err(e) = self.metadata.persist() {
+                warn!("persist tiered metadata failed: {}", e);
+            }
+            info!(
+                "uploaded {} sealed segments to the tiered backend",
+                uploaded
+            );
+        }
+        uploaded
+    }
+
+    async fn upload(
+        &self,
+        path: &Path,
+        key: String,
+        base_offset: i64,
+    ) -> Result<SegmentMetadata, StoreError> {
+        let io_error =
+            |e: std::io::Error| StoreError::Tiered(format!("read {} failed: {e}", path.display()));
+        let metadata = tokio::fs::metadata(path).await.map_err(io_error)?;
+        let last_modified = metadata
+            .modified()
+            .map_err(io_error)?
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as u64);
+        let size = metadata.len() as i64;
+        self.backend.put_file(&key, path).await?;
+        Ok(SegmentMetadata {
+            key,
+            base_offset,
+            size,
+            last_modified,
+        })
+    }
+
+    /// Deletes offloaded segments older than the reserved time, returning how many were
+    /// deleted.
+    pub async fn delete_expired_segments(&self) -> usize {
+        let reserved_millis = self.tiered_config.file_reserved_time_hours * 60 * 60 * 1000;
+        if reserved_millis == 0 {
+            return 0;
+        }
+        let expired = self
+            .metadata
+            .remove_expired(get_current_millis().saturating_sub(reserved_millis));
+        if expired.is_empty() {
+            return 0;
+        }
+        for segment in &expired {
+            if let Err(e) = self.backend.delete_object(&segment.key).await {
+                warn!(
+                    "delete expired tiered segment {} failed: {}",
+                    segment.key, e
+                );
+            }
+        }
+        if let Err(e) = self.metadata.persist() {
+            warn!("persist tiered metadata failed: {}", e);
+        }
+        info!(
+            "deleted {} expired segments from the tiered backend",
+            expired.len()
+        );
+        expired.len()
+    }
+}
+
+/// Files of `dir` named by their start offset, in offset order, without the last one.
+async fn sealed_files(dir: &Path) -> Vec<(i64, PathBuf)> {
+    let mut files = Vec::new();
+    let Ok(mut entries) = tokio::fs::read_dir(dir).await else {
+        return files;
+    };
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let offset = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<i64>().ok());
+        if let Some(offset) = offset {
+            files.push((offset, entry.path()));
+        }
+    }
+    files.sort_unstable_by_key(|(offset, _)| *offset);
+    files.pop();
+    files
+}
+
+/// Every `topic/queue_id` directory below the consume queue root.
+async fn queue_dirs(consume_queue_dir: &Path) -> Vec<(CheetahString, i32, PathBuf)> {
+    let mut dirs = Vec::new();
+    let Ok(mut topics) = tokio::fs::read_dir(consume_queue_dir).await else {
+        return dirs;
+    };
+    while let Ok(Some(topic)) = topics.next_entry().await {
+        let Ok(mut queues) = tokio::fs::read_dir(topic.path()).await else {
+            continue;
+        };
+        let topic_name = CheetahString::from_string(topic.file_name().to_string_lossy().into());
+        while let Ok(Some(queue)) = queues.next_entry().await {
+            if let Some(queue_id) = queue
+                .file_name()
+                .to_str()
+                .and_then(|name| name.parse().ok())
+            {
+                dirs.push((topic_name.clone(), queue_id, queue.path()));
+            }
+        }
+    }
+    dirs
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::*;
+    use crate::tiered::local_file_backend::LocalFileBackend;
+
+    fn write_file(dir: &Path, offset: u64, content: &[u8]) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join(offset_to_file_name(offset)), content).unwrap();
+    }
+
+    #[tokio::test]
+    async fn uploads_sealed_files_once() {
+        let store_dir = tempfile::tempdir().unwrap();
+        let backend_dir = tempfile::tempdir().unwrap();
+        let commit_log_dir = store_dir.path().join("commitlog");
+        let consume_queue_dir = store_dir.path().join("consumequeue");
+        write_file(&commit_log_dir, 0, b"sealed-0");
+        write_file(&commit_log_dir, 8, b"sealed-8");
+        write_file(&commit_log_dir, 16, b"writing");
+        let queue_dir = consume_queue_dir.join("TopicTest").join("1");
+        write_file(&queue_dir, 0, &[1; 40]);
+        write_file(&queue_dir, 40, &[2; 20]);
+
+        let metadata = Arc::new(TieredMetadataStore::new(
+            store_dir.path().join("tiered").join("metadata.json"),
+        ));
+        let service = SegmentUploadService::new(
+            Arc::new(TieredStoreConfig::default()),
+            commit_log_dir,
+            consume_queue_dir,
+            Arc::new(LocalFileBackend::new(backend_dir.path())),
+            metadata.clone(),
+        );
+
+        assert_eq!(service.upload_sealed_segments().await, 3);
+        assert_eq!(service.upload_sealed_segments().await, 0);
+
+        let segment = metadata.commit_log_segment(10).unwrap();
+        assert_eq!(
+            segment.key,
+            "DefaultBrokerName/commitlog/00000000000000000008"
+        );
+        assert_eq!(
+            service
+                .backend
+                .get_object_range(&segment.key, 0, 8)
+                .await
+                .unwrap(),
+            Bytes::from_static(b"sealed-8")
+        );
+        assert!(metadata.commit_log_segment(16).is_none());
+        let topic = CheetahString::from_static_str("TopicTest");
+        assert_eq!(metadata.min_consume_queue_offset(&topic, 1), Some(0));
+        assert!(metadata.consume_queue_segment(&topic, 1, 40).is_none());
+        assert!(metadata.path().exists());
+    }
+}
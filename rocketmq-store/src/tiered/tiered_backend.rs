@@ -0,0 +1,99 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::path::Path;
+
+use bytes::Bytes;
+
+use crate::config::message_store_config::MessageStoreConfig;
+use crate::store_error::StoreError;
+use crate::tiered::local_file_backend::LocalFileBackend;
+use crate::tiered::s3_backend::S3Backend;
+use crate::tiered::s3_backend::S3BackendConfig;
+use crate::tiered::tiered_storage_backend::TieredStorageBackend;
+use crate::tiered::tiered_store_config::TieredBackendType;
+
+/// The backend picked by `tiered_backend_type`, so the broker can build a
+/// [`TieredMessageStore`](crate::tiered::tiered_message_store::TieredMessageStore) without
+/// knowing the backend at compile time.
+pub enum TieredBackend {
+    LocalFile(LocalFileBackend),
+    S3(S3Backend),
+}
+
+impl TieredBackend {
+    pub fn new(message_store_config: &MessageStoreConfig) -> Result<Self, StoreError> {
+        let backend = match message_store_config.tiered_backend_type {
+            TieredBackendType::LocalFile => TieredBackend::LocalFile(LocalFileBackend::new(
+                message_store_config.get_tiered_store_file_path(),
+            )),
+            TieredBackendType::S3 => TieredBackend::S3(S3Backend::new(S3BackendConfig {
+                endpoint: message_store_config
+                    .tiered_object_store_endpoint
+                    .to_string(),
+                region: message_store_config.tiered_object_store_region.to_string(),
+                bucket: message_store_config.tiered_object_store_bucket.to_string(),
+                access_key: message_store_config
+                    .tiered_object_store_access_key
+                    .to_string(),
+                secret_key: message_store_config
+                    .tiered_object_store_secret_key
+                    .to_string(),
+                path_style: message_store_config.tiered_object_store_path_style,
+                request_timeout_ms: message_store_config.tiered_object_store_request_timeout_ms,
+                connect_timeout_ms: message_store_config.tiered_object_store_connect_timeout_ms,
+            })?),
+        };
+        Ok(backend)
+    }
+}
+
+impl TieredStorageBackend for TieredBackend {
+    async fn put_file(&self, key: &str, path: &Path) -> Result<(), StoreError> {
+        match self {
+            TieredBackend::LocalFile(backend) => backend.put_file(key, path).await,
+            TieredBackend::S3(backend) => backend.put_file(key, path).await,
+        }
+    }
+
+    async fn get_object_range(
+        &self,
+        key: &str,
+        position: u64,
+        length: u64,
+    ) -> Result<Bytes, StoreError> {
+        match self {
+            TieredBackend::LocalFile(backend) => {
+                backend.get_object_range(key, position, length).await
+            }
+            TieredBackend::S3(backend) => backend.get_object_range(key, position, length).await,
+        }
+    }
+
+    async fn object_size(&self, key: &str) -> Result<Option<u64>, StoreError> {
+        match self {
+            TieredBackend::LocalFile(backend) => backend.object_size(key).await,
+            TieredBackend::S3(backend) => backend.object_size(key).await,
+        }
+    }
+
+    async fn delete_object(&self, key: &str) -> Result<(), StoreError> {
+        match self {
+            TieredBackend::LocalFile(backend) => backend.delete_object(key).await,
+            TieredBackend::S3(backend) => backend.delete_object(key).await,
+        }
+    }
+}
@@ -0,0 +1,890 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::any::Any;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use bytes::Buf;
+use bytes::Bytes;
+use bytes::BytesMut;
+use cheetah_string::CheetahString;
+use rocketmq_common::common::boundary_type::BoundaryType;
+use rocketmq_common::common::message::message_batch::MessageExtBatch;
+use rocketmq_common::common::message::message_ext::MessageExt;
+use rocketmq_common::common::message::message_ext_broker_inner::MessageExtBrokerInner;
+use rocketmq_common::common::system_clock::SystemClock;
+use rocketmq_rust::ArcMut;
+use tracing::warn;
+
+use crate::base::allocate_mapped_file_service::AllocateMappedFileService;
+use crate::base::commit_log_dispatcher::CommitLogDispatcher;
+use crate::base::dispatch_request::DispatchRequest;
+use crate::base::get_message_result::GetMessageResult;
+use crate::base::message_result::AppendMessageResult;
+use crate::base::message_result::PutMessageResult;
+use crate::base::message_status_enum::GetMessageStatus;
+use crate::base::message_store::MessageStore;
+use crate::base::query_message_result::QueryMessageResult;
+use crate::base::select_result::SelectMappedBufferResult;
+use crate::base::store_checkpoint::StoreCheckpoint;
+use crate::base::store_stats_service::StoreStatsService;
+use crate::base::transient_store_pool::TransientStorePool;
+use crate::config::message_store_config::MessageStoreConfig;
+use crate::filter::MessageFilter;
+use crate::ha::general_ha_service::GeneralHAService;
+use crate::hook::put_message_hook::BoxedPutMessageHook;
+use crate::hook::put_message_hook::PutMessageHook;
+use crate::hook::send_message_back_hook::SendMessageBackHook;
+use crate::log_file::commit_log::CommitLog;
+use crate::log_file::mapped_file::MappedFile;
+use crate::log_file::MAX_PULL_MSG_SIZE;
+use crate::queue::single_consume_queue::CQ_STORE_UNIT_SIZE;
+use crate::queue::ArcConsumeQueue;
+use crate::stats::broker_stats_manager::BrokerStatsManager;
+use crate::store::running_flags::RunningFlags;
+use crate::store_error::StoreError;
+use crate::store_path_config_helper::get_store_path_consume_queue;
+use crate::tiered::segment_upload_service::SegmentUploadService;
+use crate::tiered::tiered_metadata::TieredMetadataStore;
+use crate::tiered::tiered_storage_backend::TieredStorageBackend;
+use crate::tiered::tiered_store_config::TieredStoreConfig;
+use crate::timer::timer_message_store::TimerMessageStore;
+
+/// A [`MessageStore`] decorator that offloads sealed commit log and consume queue files to a
+/// [`TieredStorageBackend`], so data can be kept far longer than the local disk allows.
+///
+/// Writes and everything else go to the wrapped store. Pulls for queue offsets that are no
+/// longer on local disk are served from the backend, and the queue minimum offsets
+/// reported include the offloaded data. Synchronous lookups by commit log offset only see
+/// local data.
+pub struct TieredMessageStore<MS, B> {
+    next: ArcMut<MS>,
+    backend: Arc<B>,
+    metadata: Arc<TieredMetadataStore>,
+    upload_service: Arc<SegmentUploadService<B>>,
+}
+
+impl<MS: MessageStore, B: TieredStorageBackend> TieredMessageStore<MS, B> {
+    pub fn new(mut next: ArcMut<MS>, backend: B, tiered_config: TieredStoreConfig) -> Self {
+        let message_store_config = next.get_message_store_config();
+        let root_dir = message_store_config.store_path_root_dir.as_str();
+        let metadata = Arc::new(TieredMetadataStore::new(
+            PathBuf::from(root_dir).join("tiered").join("metadata.json"),
+        ));
+        let backend = Arc::new(backend);
+        let upload_service = Arc::new(SegmentUploadService::new(
+            Arc::new(tiered_config),
+            PathBuf::from(message_store_config.get_store_path_commit_log()),
+            PathBuf::from(get_store_path_consume_queue(root_dir)),
+            backend.clone(),
+            metadata.clone(),
+        ));
+        // the local store keeps files until they are uploaded
+        next.set_tiered_metadata(metadata.clone());
+        Self {
+            next,
+            backend,
+            metadata,
+            upload_service,
+        }
+    }
+
+    #[inline]
+    pub fn next(&self) -> &MS {
+        &self.next
+    }
+
+    #[inline]
+    pub fn next_mut(&mut self) -> &mut MS {
+        &mut self.next
+    }
+
+    #[inline]
+    pub fn backend(&self) -> &Arc<B> {
+        &self.backend
+    }
+
+    /// Uploads sealed files right away instead of waiting for the next round, returning how
+    /// many were uploaded.
+    pub async fn upload_sealed_segments(&self) -> usize {
+        self.upload_service.upload_sealed_segments().await
+    }
+
+    /// First queue offset of `topic`-`queue_id` that is kept in the backend.
+    fn tiered_min_offset_in_queue(&self, topic: &CheetahString, queue_id: i32) -> Option<i64> {
+        self.metadata
+            .min_consume_queue_offset(topic, queue_id)
+            .map(|offset| offset / CQ_STORE_UNIT_SIZE as i64)
+    }
+
+    /// Reads messages of a queue offset whose consume queue unit only lives in the backend,
+    /// or returns `None` when the backend doesn't hold it either.
+    #[allow(clippy::too_many_arguments)]
+    async fn get_message_from_tiered(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+        offset: i64,
+        max_msg_nums: i32,
+        max_total_msg_size: i32,
+        message_filter: Option<&Arc<Box<dyn MessageFilter>>>,
+    ) -> Option<GetMessageResult> {
+        let unit_size = CQ_STORE_UNIT_SIZE as i64;
+        let segment = self
+            .metadata
+            .consume_queue_segment(topic, queue_id, offset * unit_size)?;
+        let position = offset * unit_size - segment.base_offset;
+        let max_filter_units = (self.next.get_message_store_config().max_filter_message_size
+            / CQ_STORE_UNIT_SIZE)
+            .max(max_msg_nums) as i64;
+        let unit_count = ((segment.size - position) / unit_size).min(max_filter_units);
+        let units = match self
+            .backend
+            .get_object_range(
+                &segment.key,
+                position as u64,
+                (unit_count * unit_size) as u64,
+            )
+            .await
+        {
+            Ok(units) => units,
+            Err(e) => {
+                warn!("read tiered consume queue {} failed: {}", segment.key, e);
+                return None;
+            }
+        };
+
+        let mut result = GetMessageResult::new();
+        let mut status = GetMessageStatus::NoMatchedMessage;
+        let mut next_begin_offset = offset;
+        for mut unit in units.chunks_exact(CQ_STORE_UNIT_SIZE as usize) {
+            let offset_py = unit.get_i64();
+            let size_py = unit.get_i32();
+            let tags_code = unit.get_i64();
+            if offset_py < 0 || size_py <= 0 {
+                break;
+            }
+            if result.message_count() >= max_msg_nums
+                || (result.message_count() > 0
+                    && result.buffer_total_size() + size_py > max_total_msg_size)
+            {
+                break;
+            }
+            let queue_offset = next_begin_offset;
+            next_begin_offset += 1;
+            if let Some(filter) = message_filter {
+                if !filter.is_matched_by_consume_queue(Some(tags_code), None) {
+                    continue;
+                }
+            }
+            let Some(select_result) = self.read_commit_log(offset_py, size_py).await else {
+                if result.buffer_total_size() == 0 {
+                    status = GetMessageStatus::MessageWasRemoving;
+                }
+                continue;
+            };
+            if let Some(filter) = message_filter {
+                if !filter.is_matched_by_commit_log(Some(select_result.get_buffer()), None) {
+                    continue;
+                }
+            }
+            result.add_message(select_result, queue_offset as u64, 1);
+            status = GetMessageStatus::Found;
+        }
+
+        result.set_status(Some(status));
+        result.set_next_begin_offset(next_begin_offset);
+        result.set_min_offset(self.get_min_offset_in_queue(topic, queue_id));
+        result.set_max_offset(self.next.get_max_offset_in_queue(topic, queue_id));
+        Some(result)
+    }
+
+    /// Reads one message, from local disk if it is still there and from the backend
+    /// otherwise.
+    async fn read_commit_log(&self, offset: i64, size: i32) -> Option<SelectMappedBufferResult> {
+        if offset >= self.next.get_min_phy_offset() {
+            return self
+                .next
+                .select_one_message_by_offset_with_size(offset, size);
+        }
+        let segment = self.metadata.commit_log_segment(offset)?;
+        match self
+            .backend
+            .get_object_range(
+                &segment.key,
+                (offset - segment.base_offset) as u64,
+                size as u64,
+            )
+            .await
+        {
+            Ok(bytes) => Some(SelectMappedBufferResult {
+                start_offset: offset as u64,
+                bytes: Some(bytes),
+                size,
+                mapped_file: None,
+                is_in_cache: false,
+            }),
+            Err(e) => {
+                warn!("read tiered commit log {} failed: {}", segment.key, e);
+                None
+            }
+        }
+    }
+}
+
+impl<MS: MessageStore, B: TieredStorageBackend> MessageStore for TieredMessageStore<MS, B> {
+    async fn load(&mut self) -> bool {
+        self.next.load().await
+    }
+
+    fn start(&mut self) -> Result<(), StoreError> {
+        self.next.start()?;
+        self.upload_service.clone().start();
+        Ok(())
+    }
+
+    fn shutdown(&mut self) {
+        self.upload_service.shutdown();
+        self.next.shutdown()
+    }
+
+    fn destroy(&mut self) {
+        self.next.destroy()
+    }
+
+    async fn put_message(&mut self, msg: MessageExtBrokerInner) -> PutMessageResult {
+        self.next.put_message(msg).await
+    }
+
+    async fn put_messages(&mut self, message_ext_batch: MessageExtBatch) -> PutMessageResult {
+        self.next.put_messages(message_ext_batch).await
+    }
+
+    async fn get_message(
+        &self,
+        group: &CheetahString,
+        topic: &CheetahString,
+        queue_id: i32,
+        offset: i64,
+        max_msg_nums: i32,
+        message_filter: Option<Arc<Box<dyn MessageFilter>>>,
+    ) -> Option<GetMessageResult> {
+        self.get_message_with_size_limit(
+            group,
+            topic,
+            queue_id,
+            offset,
+            max_msg_nums,
+            MAX_PULL_MSG_SIZE,
+            message_filter,
+        )
+        .await
+    }
+
+    async fn get_message_with_size_limit(
+        &self,
+        group: &CheetahString,
+        topic: &CheetahString,
+        queue_id: i32,
+        offset: i64,
+        max_msg_nums: i32,
+        max_total_msg_size: i32,
+        message_filter: Option<Arc<Box<dyn MessageFilter>>>,
+    ) -> Option<GetMessageResult> {
+        if offset < self.next.get_min_offset_in_queue(topic, queue_id) {
+            if let Some(result) = self
+                .get_message_from_tiered(
+                    topic,
+                    queue_id,
+                    offset,
+                    max_msg_nums,
+                    max_total_msg_size,
+                    message_filter.as_ref(),
+                )
+                .await
+            {
+                return Some(result);
+            }
+        }
+        self.next
+            .get_message_with_size_limit(
+                group,
+                topic,
+                queue_id,
+                offset,
+                max_msg_nums,
+                max_total_msg_size,
+                message_filter,
+            )
+            .await
+    }
+
+    fn get_max_offset_in_queue(&self, topic: &CheetahString, queue_id: i32) -> i64 {
+        self.next.get_max_offset_in_queue(topic, queue_id)
+    }
+
+    fn get_max_offset_in_queue_committed(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+        committed: bool,
+    ) -> i64 {
+        self.next
+            .get_max_offset_in_queue_committed(topic, queue_id, committed)
+    }
+
+    fn get_min_offset_in_queue(&self, topic: &CheetahString, queue_id: i32) -> i64 {
+        let local_min = self.next.get_min_offset_in_queue(topic, queue_id);
+        self.tiered_min_offset_in_queue(topic, queue_id)
+            .map_or(local_min, |tiered_min| tiered_min.min(local_min))
+    }
+
+    fn get_timer_message_store(&self) -> Option<&Arc<TimerMessageStore>> {
+        self.next.get_timer_message_store()
+    }
+
+    fn set_timer_message_store(&mut self, timer_message_store: Arc<TimerMessageStore>) {
+        self.next.set_timer_message_store(timer_message_store)
+    }
+
+    fn get_commit_log_offset_in_queue(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+        consume_queue_offset: i64,
+    ) -> i64 {
+        self.next
+            .get_commit_log_offset_in_queue(topic, queue_id, consume_queue_offset)
+    }
+
+    fn get_offset_in_queue_by_time(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+        timestamp: i64,
+    ) -> i64 {
+        self.next
+            .get_offset_in_queue_by_time(topic, queue_id, timestamp)
+    }
+
+    fn get_offset_in_queue_by_time_with_boundary(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+        timestamp: i64,
+        boundary_type: BoundaryType,
+    ) -> i64 {
+        self.next.get_offset_in_queue_by_time_with_boundary(
+            topic,
+            queue_id,
+            timestamp,
+            boundary_type,
+        )
+    }
+
+    fn look_message_by_offset(&self, commit_log_offset: i64) -> Option<MessageExt> {
+        self.next.look_message_by_offset(commit_log_offset)
+    }
+
+    fn look_message_by_offset_with_size(
+        &self,
+        commit_log_offset: i64,
+        size: i32,
+    ) -> Option<MessageExt> {
+        self.next
+            .look_message_by_offset_with_size(commit_log_offset, size)
+    }
+
+    fn select_one_message_by_offset(
+        &self,
+        commit_log_offset: i64,
+    ) -> Option<SelectMappedBufferResult> {
+        self.next.select_one_message_by_offset(commit_log_offset)
+    }
+
+    fn select_one_message_by_offset_with_size(
+        &self,
+        commit_log_offset: i64,
+        msg_size: i32,
+    ) -> Option<SelectMappedBufferResult> {
+        self.next
+            .select_one_message_by_offset_with_size(commit_log_offset, msg_size)
+    }
+
+    fn get_running_data_info(&self) -> String {
+        self.next.get_running_data_info()
+    }
+
+    fn get_timing_message_count(&self, topic: &CheetahString) -> i64 {
+        self.next.get_timing_message_count(topic)
+    }
+
+    fn get_runtime_info(&self) -> HashMap<String, String> {
+        self.next.get_runtime_info()
+    }
+
+    fn get_max_phy_offset(&self) -> i64 {
+        self.next.get_max_phy_offset()
+    }
+
+    fn get_min_phy_offset(&self) -> i64 {
+        self.next.get_min_phy_offset()
+    }
+
+    fn get_earliest_message_time(&self, topic: &CheetahString, queue_id: i32) -> i64 {
+        self.next.get_earliest_message_time(topic, queue_id)
+    }
+
+    fn get_earliest_message_time_store(&self) -> i64 {
+        self.next.get_earliest_message_time_store()
+    }
+
+    fn get_message_store_timestamp(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+        consume_queue_offset: i64,
+    ) -> i64 {
+        self.next
+            .get_message_store_timestamp(topic, queue_id, consume_queue_offset)
+    }
+
+    async fn get_message_store_timestamp_async(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+        consume_queue_offset: i64,
+    ) -> Result<i64, StoreError> {
+        self.next
+            .get_message_store_timestamp_async(topic, queue_id, consume_queue_offset)
+            .await
+    }
+
+    fn get_message_total_in_queue(&self, topic: &CheetahString, queue_id: i32) -> i64 {
+        self.next.get_max_offset_in_queue(topic, queue_id)
+            - self.get_min_offset_in_queue(topic, queue_id)
+    }
+
+    fn get_commit_log_data(&self, offset: i64) -> Option<SelectMappedBufferResult> {
+        self.next.get_commit_log_data(offset)
+    }
+
+    fn get_bulk_commit_log_data(
+        &self,
+        offset: i64,
+        size: i32,
+    ) -> Option<Vec<SelectMappedBufferResult>> {
+        self.next.get_bulk_commit_log_data(offset, size)
+    }
+
+    fn append_to_commit_log(
+        &self,
+        start_offset: i64,
+        data: &[u8],
+        data_start: i32,
+        data_length: i32,
+    ) -> Result<bool, StoreError> {
+        self.next
+            .append_to_commit_log(start_offset, data, data_start, data_length)
+    }
+
+    fn execute_delete_files_manually(&self) {
+        self.next.execute_delete_files_manually()
+    }
+
+    async fn query_message(
+        &self,
+        topic: &CheetahString,
+        key: &CheetahString,
+        max_num: i32,
+        begin: i64,
+        end: i64,
+    ) -> Option<QueryMessageResult> {
+        self.next
+            .query_message(topic, key, max_num, begin, end)
+            .await
+    }
+
+    fn update_ha_master_address(&self, new_addr: &CheetahString) {
+        self.next.update_ha_master_address(new_addr)
+    }
+
+    fn update_master_address(&self, new_addr: &CheetahString) {
+        self.next.update_master_address(new_addr)
+    }
+
+    fn slave_fall_behind_much(&self) -> i64 {
+        self.next.slave_fall_behind_much()
+    }
+
+    fn delete_topics(&mut self, delete_topics: Vec<&CheetahString>) -> i32 {
+        self.next.delete_topics(delete_topics)
+    }
+
+    fn clean_unused_topic(&self, retain_topics: &HashSet<String>) -> i32 {
+        self.next.clean_unused_topic(retain_topics)
+    }
+
+    fn clean_expired_consumer_queue(&self) {
+        self.next.clean_expired_consumer_queue()
+    }
+
+    fn check_in_mem_by_consume_offset(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+        consume_offset: i64,
+        batch_size: i32,
+    ) -> bool {
+        self.next
+            .check_in_mem_by_consume_offset(topic, queue_id, consume_offset, batch_size)
+    }
+
+    fn is_msg_in_cold_area(
+        &self,
+        group: &CheetahString,
+        topic: &CheetahString,
+        queue_id: i32,
+        offset: i64,
+    ) -> bool {
+        self.next
+            .is_msg_in_cold_area(group, topic, queue_id, offset)
+    }
+
+    fn check_in_store_by_consume_offset(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+        consume_offset: i64,
+    ) -> bool {
+        self.next
+            .check_in_store_by_consume_offset(topic, queue_id, consume_offset)
+    }
+
+    fn dispatch_behind_bytes(&self) -> i64 {
+        self.next.dispatch_behind_bytes()
+    }
+
+    fn flush(&self) -> i64 {
+        self.next.flush()
+    }
+
+    fn get_flushed_where(&self) -> i64 {
+        self.next.get_flushed_where()
+    }
+
+    fn reset_write_offset(&self, phy_offset: i64) -> bool {
+        self.next.reset_write_offset(phy_offset)
+    }
+
+    fn get_confirm_offset(&self) -> i64 {
+        self.next.get_confirm_offset()
+    }
+
+    fn set_confirm_offset(&mut self, phy_offset: i64) {
+        self.next.set_confirm_offset(phy_offset)
+    }
+
+    fn is_os_page_cache_busy(&self) -> bool {
+        self.next.is_os_page_cache_busy()
+    }
+
+    fn lock_time_millis(&self) -> i64 {
+        self.next.lock_time_millis()
+    }
+
+    fn is_transient_store_pool_deficient(&self) -> bool {
+        self.next.is_transient_store_pool_deficient()
+    }
+
+    fn get_dispatcher_list(&self) -> &[Arc<dyn CommitLogDispatcher>] {
+        self.next.get_dispatcher_list()
+    }
+
+    fn add_dispatcher(&mut self, dispatcher: Arc<dyn CommitLogDispatcher>) {
+        self.next.add_dispatcher(dispatcher)
+    }
+
+    fn add_first_dispatcher(&mut self, dispatcher: Arc<dyn CommitLogDispatcher>) {
+        self.next.add_first_dispatcher(dispatcher)
+    }
+
+    fn get_consume_queue(&self, topic: &CheetahString, queue_id: i32) -> Option<ArcConsumeQueue> {
+        self.next.get_consume_queue(topic, queue_id)
+    }
+
+    fn find_consume_queue(&self, topic: &CheetahString, queue_id: i32) -> Option<ArcConsumeQueue> {
+        self.next.find_consume_queue(topic, queue_id)
+    }
+
+    fn get_broker_stats_manager(&self) -> Option<&Arc<BrokerStatsManager>> {
+        self.next.get_broker_stats_manager()
+    }
+
+    fn on_commit_log_append<MF: MappedFile>(
+        &self,
+        msg: &MessageExtBrokerInner,
+        result: &AppendMessageResult,
+        commit_log_file: &MF,
+    ) {
+        self.next.on_commit_log_append(msg, result, commit_log_file)
+    }
+
+    fn on_commit_log_dispatch<MF: MappedFile>(
+        &self,
+        dispatch_request: &DispatchRequest,
+        do_dispatch: bool,
+        commit_log_file: &MF,
+        is_recover: bool,
+        is_file_end: bool,
+    ) -> Result<(), StoreError> {
+        self.next.on_commit_log_dispatch(
+            dispatch_request,
+            do_dispatch,
+            commit_log_file,
+            is_recover,
+            is_file_end,
+        )
+    }
+
+    fn finish_commit_log_dispatch(&self) {
+        self.next.finish_commit_log_dispatch()
+    }
+
+    fn get_message_store_config(&self) -> &MessageStoreConfig {
+        self.next.get_message_store_config()
+    }
+
+    fn get_store_stats_service(&self) -> Arc<StoreStatsService> {
+        self.next.get_store_stats_service()
+    }
+
+    fn get_store_checkpoint(&self) -> &StoreCheckpoint {
+        self.next.get_store_checkpoint()
+    }
+
+    fn get_store_checkpoint_arc(&self) -> Arc<StoreCheckpoint> {
+        self.next.get_store_checkpoint_arc()
+    }
+
+    fn get_system_clock(&self) -> Arc<SystemClock> {
+        self.next.get_system_clock()
+    }
+
+    fn get_commit_log(&self) -> Arc<CommitLog> {
+        self.next.get_commit_log()
+    }
+
+    fn get_running_flags(&self) -> &RunningFlags {
+        self.next.get_running_flags()
+    }
+
+    fn get_running_flags_arc(&self) -> Arc<RunningFlags> {
+        self.next.get_running_flags_arc()
+    }
+
+    fn get_transient_store_pool(&self) -> Arc<TransientStorePool> {
+        self.next.get_transient_store_pool()
+    }
+
+    fn get_ha_service(&self) -> Option<&ArcMut<GeneralHAService>> {
+        self.next.get_ha_service()
+    }
+
+    fn get_allocate_mapped_file_service(&self) -> Arc<AllocateMappedFileService> {
+        self.next.get_allocate_mapped_file_service()
+    }
+
+    fn truncate_dirty_logic_files(&self, phy_offset: i64) {
+        self.next.truncate_dirty_logic_files(phy_offset)
+    }
+
+    fn unlock_mapped_file<MF: MappedFile>(&self, unlock_mapped_file: &MF) {
+        self.next.unlock_mapped_file(unlock_mapped_file)
+    }
+
+    fn get_queue_store(&self) -> &dyn Any {
+        self.next.get_queue_store()
+    }
+
+    fn is_sync_disk_flush(&self) -> bool {
+        self.next.is_sync_disk_flush()
+    }
+
+    fn is_sync_master(&self) -> bool {
+        self.next.is_sync_master()
+    }
+
+    fn assign_offset(&self, msg: &mut MessageExtBrokerInner) -> Result<(), StoreError> {
+        self.next.assign_offset(msg)
+    }
+
+    fn increase_offset(&self, msg: &MessageExtBrokerInner, message_num: i16) {
+        self.next.increase_offset(msg, message_num)
+    }
+
+    fn get_master_store_in_process<M: MessageStore>(&self) -> Option<Arc<M>> {
+        self.next.get_master_store_in_process::<M>()
+    }
+
+    fn set_master_store_in_process<M: MessageStore>(&self, master_store_in_process: Arc<M>) {
+        self.next
+            .set_master_store_in_process::<M>(master_store_in_process)
+    }
+
+    fn get_data(&self, offset: i64, size: i32, byte_buffer: &mut BytesMut) -> bool {
+        self.next.get_data(offset, size, byte_buffer)
+    }
+
+    fn set_alive_replica_num_in_group(&self, alive_replica_nums: i32) {
+        self.next.set_alive_replica_num_in_group(alive_replica_nums)
+    }
+
+    fn get_alive_replica_num_in_group(&self) -> i32 {
+        self.next.get_alive_replica_num_in_group()
+    }
+
+    fn wakeup_ha_client(&self) {
+        self.next.wakeup_ha_client()
+    }
+
+    fn get_master_flushed_offset(&self) -> i64 {
+        self.next.get_master_flushed_offset()
+    }
+
+    fn get_broker_init_max_offset(&self) -> i64 {
+        self.next.get_broker_init_max_offset()
+    }
+
+    fn set_master_flushed_offset(&self, master_flushed_offset: i64) {
+        self.next.set_master_flushed_offset(master_flushed_offset)
+    }
+
+    fn set_broker_init_max_offset(&mut self, broker_init_max_offset: i64) {
+        self.next.set_broker_init_max_offset(broker_init_max_offset)
+    }
+
+    fn calc_delta_checksum(&self, from: i64, to: i64) -> Vec<u8> {
+        self.next.calc_delta_checksum(from, to)
+    }
+
+    fn truncate_files(&self, offset_to_truncate: i64) -> Result<bool, StoreError> {
+        self.next.truncate_files(offset_to_truncate)
+    }
+
+    fn is_offset_aligned(&self, offset: i64) -> bool {
+        self.next.is_offset_aligned(offset)
+    }
+
+    fn get_put_message_hook_list(&self) -> Vec<Arc<dyn PutMessageHook>> {
+        self.next.get_put_message_hook_list()
+    }
+
+    fn set_send_message_back_hook(&self, send_message_back_hook: Arc<dyn SendMessageBackHook>) {
+        self.next.set_send_message_back_hook(send_message_back_hook)
+    }
+
+    fn get_send_message_back_hook(&self) -> Option<Arc<dyn SendMessageBackHook>> {
+        self.next.get_send_message_back_hook()
+    }
+
+    fn get_last_file_from_offset(&self) -> i64 {
+        self.next.get_last_file_from_offset()
+    }
+
+    fn get_last_mapped_file(&self, start_offset: i64) -> bool {
+        self.next.get_last_mapped_file(start_offset)
+    }
+
+    fn set_physical_offset(&self, phy_offset: i64) {
+        self.next.set_physical_offset(phy_offset)
+    }
+
+    fn is_mapped_files_empty(&self) -> bool {
+        self.next.is_mapped_files_empty()
+    }
+
+    fn get_state_machine_version(&self) -> i64 {
+        self.next.get_state_machine_version()
+    }
+
+    fn check_message_and_return_size(
+        &self,
+        bytes: &mut Bytes,
+        check_crc: bool,
+        check_dup_info: bool,
+        read_body: bool,
+    ) -> DispatchRequest {
+        self.next
+            .check_message_and_return_size(bytes, check_crc, check_dup_info, read_body)
+    }
+
+    fn remain_transient_store_buffer_numbs(&self) -> i32 {
+        self.next.remain_transient_store_buffer_numbs()
+    }
+
+    fn remain_how_many_data_to_commit(&self) -> i64 {
+        self.next.remain_how_many_data_to_commit()
+    }
+
+    fn remain_how_many_data_to_flush(&self) -> i64 {
+        self.next.remain_how_many_data_to_flush()
+    }
+
+    fn is_shutdown(&self) -> bool {
+        self.next.is_shutdown()
+    }
+
+    fn is_tiered_message_store(&self) -> bool {
+        true
+    }
+
+    fn estimate_message_count(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+        from: i64,
+        to: i64,
+        filter: &dyn MessageFilter,
+    ) -> i64 {
+        self.next
+            .estimate_message_count(topic, queue_id, from, to, filter)
+    }
+
+    fn recover_topic_queue_table(&mut self) {
+        self.next.recover_topic_queue_table()
+    }
+
+    fn check_rocksdb_cq_write_progress(&self, topic: &CheetahString) -> CheetahString {
+        self.next.check_rocksdb_cq_write_progress(topic)
+    }
+
+    fn notify_message_arrive_if_necessary(&self, dispatch_request: &mut DispatchRequest) {
+        self.next
+            .notify_message_arrive_if_necessary(dispatch_request)
+    }
+
+    fn set_put_message_hook(&mut self, put_message_hook: BoxedPutMessageHook) {
+        self.next.set_put_message_hook(put_message_hook)
+    }
+
+    fn set_tiered_metadata(&mut self, tiered_metadata: Arc<TieredMetadataStore>) {
+        self.next.set_tiered_metadata(tiered_metadata)
+    }
+}
@@ -0,0 +1,256 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use cheetah_string::CheetahString;
+use parking_lot::RwLock;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::store_error::StoreError;
+
+/// A commit log or consume queue file that has been uploaded to the backend.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SegmentMetadata {
+    /// Object key in the backend.
+    pub key: String,
+    /// Offset of the first byte, i.e. the file name of the segment.
+    pub base_offset: i64,
+    pub size: i64,
+    /// Last modification time of the local file, in milliseconds.
+    pub last_modified: u64,
+}
+
+impl SegmentMetadata {
+    #[inline]
+    pub fn contains(&self, offset: i64) -> bool {
+        offset >= self.base_offset && offset < self.base_offset + self.size
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TieredMetadata {
+    commit_log: BTreeMap<i64, SegmentMetadata>,
+    consume_queue: BTreeMap<CheetahString, BTreeMap<i32, BTreeMap<i64, SegmentMetadata>>>,
+}
+
+/// Remembers which segments live in the backend, persisted as JSON next to the local store.
+pub struct TieredMetadataStore {
+    path: PathBuf,
+    metadata: RwLock<TieredMetadata>,
+}
+
+fn floor_segment(
+    segments: &BTreeMap<i64, SegmentMetadata>,
+    offset: i64,
+) -> Option<SegmentMetadata> {
+    segments
+        .range(..=offset)
+        .next_back()
+        .map(|(_, segment)| segment)
+        .filter(|segment| segment.contains(offset))
+        .cloned()
+}
+
+impl TieredMetadataStore {
+    /// Loads the metadata at `path`, starting empty when there is none.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let metadata = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                warn!("parse tiered metadata {} failed: {}", path.display(), e);
+                TieredMetadata::default()
+            }),
+            Err(_) => TieredMetadata::default(),
+        };
+        Self {
+            path,
+            metadata: RwLock::new(metadata),
+        }
+    }
+
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn persist(&self) -> Result<(), StoreError> {
+        let content = serde_json::to_string(&*self.metadata.read())
+            .map_err(|e| StoreError::Tiered(format!("encode tiered metadata failed: {e}")))?;
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                StoreError::Tiered(format!("create dir {} failed: {e}", parent.display()))
+            })?;
+        }
+        let tmp_path = self.path.with_extension("tmp");
+        std::fs::write(&tmp_path, content)
+            .and_then(|_| std::fs::rename(&tmp_path, &self.path))
+            .map_err(|e| {
+                StoreError::Tiered(format!(
+                    "write tiered metadata {} failed: {e}",
+                    self.path.display()
+                ))
+            })
+    }
+
+    /// The commit log segment holding physical offset `offset`.
+    pub fn commit_log_segment(&self, offset: i64) -> Option<SegmentMetadata> {
+        floor_segment(&self.metadata.read().commit_log, offset)
+    }
+
+    pub fn contains_commit_log_segment(&self, base_offset: i64) -> bool {
+        self.metadata.read().commit_log.contains_key(&base_offset)
+    }
+
+    pub fn add_commit_log_segment(&self, segment: SegmentMetadata) {
+        self.metadata
+            .write()
+            .commit_log
+            .insert(segment.base_offset, segment);
+    }
+
+    /// The consume queue segment of `topic`-`queue_id` holding logic offset `offset`.
+    pub fn consume_queue_segment(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+        offset: i64,
+    ) -> Option<SegmentMetadata> {
+        let metadata = self.metadata.read();
+        let segments = metadata.consume_queue.get(topic)?.get(&queue_id)?;
+        floor_segment(segments, offset)
+    }
+
+    pub fn contains_consume_queue_segment(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+        base_offset: i64,
+    ) -> bool {
+        self.metadata
+            .read()
+            .consume_queue
+            .get(topic)
+            .and_then(|queues| queues.get(&queue_id))
+            .is_some_and(|segments| segments.contains_key(&base_offset))
+    }
+
+    pub fn add_consume_queue_segment(
+        &self,
+        topic: &CheetahString,
+        queue_id: i32,
+        segment: SegmentMetadata,
+    ) {
+        self.metadata
+            .write()
+            .consume_queue
+            .entry(topic.clone())
+            .or_default()
+            .entry(queue_id)
+            .or_default()
+            .insert(segment.base_offset, segment);
+    }
+
+    /// Logic offset of the first offloaded consume queue unit of `topic`-`queue_id`.
+    pub fn min_consume_queue_offset(&self, topic: &CheetahString, queue_id: i32) -> Option<i64> {
+        self.metadata
+            .read()
+            .consume_queue
+            .get(topic)?
+            .get(&queue_id)?
+            .keys()
+            .next()
+            .copied()
+    }
+
+    /// Forgets every segment last modified before `expire_before` and returns them.
+    pub fn remove_expired(&self, expire_before: u64) -> Vec<SegmentMetadata> {
+        let mut metadata = self.metadata.write();
+        let mut expired = Vec::new();
+        metadata.commit_log.retain(|_, segment| {
+            let keep = segment.last_modified >= expire_before;
+            if !keep {
+                expired.push(segment.clone());
+            }
+            keep
+        });
+        for queues in metadata.consume_queue.values_mut() {
+            for segments in queues.values_mut() {
+                segments.retain(|_, segment| {
+                    let keep = segment.last_modified >= expire_before;
+                    if !keep {
+                        expired.push(segment.clone());
+                    }
+                    keep
+                });
+            }
+            queues.retain(|_, segments| !segments.is_empty());
+        }
+        metadata
+            .consume_queue
+            .retain(|_, queues| !queues.is_empty());
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(base_offset: i64, last_modified: u64) -> SegmentMetadata {
+        SegmentMetadata {
+            key: format!("segment-{base_offset}"),
+            base_offset,
+            size: 100,
+            last_modified,
+        }
+    }
+
+    #[test]
+    fn lookup_persist_and_expire_segments() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("tiered").join("metadata.json");
+        let topic = CheetahString::from_static_str("TopicTest");
+        let metadata = TieredMetadataStore::new(&path);
+        metadata.add_commit_log_segment(segment(0, 10));
+        metadata.add_commit_log_segment(segment(100, 20));
+        metadata.add_consume_queue_segment(&topic, 1, segment(200, 10));
+        metadata.persist().unwrap();
+
+        let metadata = TieredMetadataStore::new(&path);
+        assert_eq!(metadata.commit_log_segment(150), Some(segment(100, 20)));
+        assert_eq!(metadata.commit_log_segment(200), None);
+        assert!(metadata.contains_commit_log_segment(0));
+        assert_eq!(
+            metadata.consume_queue_segment(&topic, 1, 240),
+            Some(segment(200, 10))
+        );
+        assert_eq!(metadata.consume_queue_segment(&topic, 2, 240), None);
+        assert_eq!(metadata.min_consume_queue_offset(&topic, 1), Some(200));
+
+        let expired = metadata.remove_expired(15);
+        assert_eq!(expired.len(), 2);
+        assert_eq!(metadata.commit_log_segment(50), None);
+        assert_eq!(metadata.min_consume_queue_offset(&topic, 1), None);
+        assert!(metadata.contains_commit_log_segment(100));
+    }
+}
@@ -0,0 +1,46 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::path::Path;
+
+use bytes::Bytes;
+
+use crate::store_error::StoreError;
+
+/// An object store that sealed commit log and consume queue segments are offloaded to.
+///
+/// Objects are written once and never modified, so backends only need whole-object writes
+/// and ranged reads.
+#[trait_variant::make(TieredStorageBackend: Send)]
+pub trait TieredStorageBackendInner: Sync + 'static {
+    /// Stores the content of the file at `path` under `key`, replacing any existing object.
+    /// The file is streamed, so segments are never held in memory as a whole.
+    async fn put_file(&self, key: &str, path: &Path) -> Result<(), StoreError>;
+
+    /// Reads `length` bytes of the object `key` starting at `position`.
+    async fn get_object_range(
+        &self,
+        key: &str,
+        position: u64,
+        length: u64,
+    ) -> Result<Bytes, StoreError>;
+
+    /// Returns the size of the object `key`, or `None` if it does not exist.
+    async fn object_size(&self, key: &str) -> Result<Option<u64>, StoreError>;
+
+    /// Deletes the object `key`, deleting a missing object is not an error.
+    async fn delete_object(&self, key: &str) -> Result<(), StoreError>;
+}
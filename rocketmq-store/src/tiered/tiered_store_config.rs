@@ -0,0 +1,79 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use cheetah_string::CheetahString;
+use serde::Deserialize;
+
+mod defaults {
+    use cheetah_string::CheetahString;
+
+    pub fn broker_name() -> CheetahString {
+        CheetahString::from_static_str("DefaultBrokerName")
+    }
+
+    pub fn upload_interval_ms() -> u64 {
+        10_000
+    }
+
+    pub fn file_reserved_time_hours() -> u64 {
+        72
+    }
+}
+
+/// Settings of [`TieredMessageStore`](crate::tiered::tiered_message_store::TieredMessageStore).
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TieredStoreConfig {
+    /// Prefix of every object key, keeps brokers sharing a bucket apart.
+    #[serde(default = "defaults::broker_name")]
+    pub broker_name: CheetahString,
+
+    /// How often sealed segments are looked for and uploaded.
+    #[serde(default = "defaults::upload_interval_ms")]
+    pub upload_interval_ms: u64,
+
+    /// How long offloaded segments are kept in the backend, 0 keeps them forever.
+    #[serde(default = "defaults::file_reserved_time_hours")]
+    pub file_reserved_time_hours: u64,
+}
+
+impl Default for TieredStoreConfig {
+    fn default() -> Self {
+        Self {
+            broker_name: defaults::broker_name(),
+            upload_interval_ms: defaults::upload_interval_ms(),
+            file_reserved_time_hours: defaults::file_reserved_time_hours(),
+        }
+    }
+}
+
+/// Object store that [`TieredBackend`](crate::tiered::tiered_backend::TieredBackend) offloads
+/// to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize)]
+pub enum TieredBackendType {
+    #[default]
+    LocalFile,
+    S3,
+}
+
+impl TieredBackendType {
+    pub fn get_backend_type(&self) -> &'static str {
+        match self {
+            TieredBackendType::LocalFile => "LocalFile",
+            TieredBackendType::S3 => "S3",
+        }
+    }
+}
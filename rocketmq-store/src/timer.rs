@@ -16,5 +16,8 @@
  */
 
 pub mod slot;
+pub mod timer_checkpoint;
+pub mod timer_log;
 pub mod timer_message_store;
 pub mod timer_metrics;
+pub mod timer_wheel;
@@ -0,0 +1,161 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::fs::OpenOptions;
+use std::path::Path;
+use std::sync::atomic::AtomicI64;
+use std::sync::atomic::Ordering;
+
+use memmap2::MmapMut;
+use rocketmq_common::UtilAll::ensure_dir_ok;
+use tracing::info;
+
+const CHECKPOINT_SIZE: usize = 8 * 4;
+
+/// Progress of the timer message store, persisted so timer messages survive restarts.
+///
+/// * `last_read_time_ms`: every slot before it has been delivered.
+/// * `last_timer_log_flush_pos`: the timer log is flushed up to here.
+/// * `last_timer_queue_offset`: every message of the timer topic before it is in the wheel.
+/// * `master_timer_queue_offset`: queue offset reported by the master, for slaves.
+pub struct TimerCheckpoint {
+    mmap: parking_lot::Mutex<MmapMut>,
+    last_read_time_ms: AtomicI64,
+    last_timer_log_flush_pos: AtomicI64,
+    last_timer_queue_offset: AtomicI64,
+    master_timer_queue_offset: AtomicI64,
+}
+
+impl TimerCheckpoint {
+    pub fn new<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        if let Some(parent) = path.as_ref().parent() {
+            ensure_dir_ok(parent.to_string_lossy().as_ref());
+        }
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path.as_ref())?;
+        let exists = file.metadata()?.len() >= CHECKPOINT_SIZE as u64;
+        if !exists {
+            file.set_len(CHECKPOINT_SIZE as u64)?;
+        }
+        let mmap = unsafe { MmapMut::map_mut(&file)? };
+        let read =
+            |index: usize| i64::from_be_bytes(mmap[index * 8..index * 8 + 8].try_into().unwrap());
+        let checkpoint = Self {
+            last_read_time_ms: AtomicI64::new(read(0)),
+            last_timer_log_flush_pos: AtomicI64::new(read(1)),
+            last_timer_queue_offset: AtomicI64::new(read(2)),
+            master_timer_queue_offset: AtomicI64::new(read(3)),
+            mmap: parking_lot::Mutex::new(mmap),
+        };
+        if exists {
+            info!(
+                "timer checkpoint {} loaded, lastReadTimeMs: {}, lastTimerLogFlushPos: {}, \
+                 lastTimerQueueOffset: {}, masterTimerQueueOffset: {}",
+                path.as_ref().display(),
+                checkpoint.get_last_read_time_ms(),
+                checkpoint.get_last_timer_log_flush_pos(),
+                checkpoint.get_last_timer_queue_offset(),
+                checkpoint.get_master_timer_queue_offset()
+            );
+        }
+        Ok(checkpoint)
+    }
+
+    pub fn flush(&self) -> std::io::Result<()> {
+        let mut mmap = self.mmap.lock();
+        let values = [
+            self.get_last_read_time_ms(),
+            self.get_last_timer_log_flush_pos(),
+            self.get_last_timer_queue_offset(),
+            self.get_master_timer_queue_offset(),
+        ];
+        for (index, value) in values.iter().enumerate() {
+            mmap[index * 8..index * 8 + 8].copy_from_slice(&value.to_be_bytes());
+        }
+        mmap.flush()
+    }
+
+    pub fn shutdown(&self) -> std::io::Result<()> {
+        self.flush()
+    }
+
+    pub fn get_last_read_time_ms(&self) -> i64 {
+        self.last_read_time_ms.load(Ordering::Acquire)
+    }
+
+    pub fn set_last_read_time_ms(&self, last_read_time_ms: i64) {
+        self.last_read_time_ms
+            .store(last_read_time_ms, Ordering::Release);
+    }
+
+    pub fn get_last_timer_log_flush_pos(&self) -> i64 {
+        self.last_timer_log_flush_pos.load(Ordering::Acquire)
+    }
+
+    pub fn set_last_timer_log_flush_pos(&self, last_timer_log_flush_pos: i64) {
+        self.last_timer_log_flush_pos
+            .store(last_timer_log_flush_pos, Ordering::Release);
+    }
+
+    pub fn get_last_timer_queue_offset(&self) -> i64 {
+        self.last_timer_queue_offset.load(Ordering::Acquire)
+    }
+
+    pub fn set_last_timer_queue_offset(&self, last_timer_queue_offset: i64) {
+        self.last_timer_queue_offset
+            .store(last_timer_queue_offset, Ordering::Release);
+    }
+
+    pub fn get_master_timer_queue_offset(&self) -> i64 {
+        self.master_timer_queue_offset.load(Ordering::Acquire)
+    }
+
+    pub fn set_master_timer_queue_offset(&self, master_timer_queue_offset: i64) {
+        self.master_timer_queue_offset
+            .store(master_timer_queue_offset, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn checkpoint_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config").join("timercheck");
+        {
+            let checkpoint = TimerCheckpoint::new(&path).unwrap();
+            assert_eq!(checkpoint.get_last_read_time_ms(), 0);
+            checkpoint.set_last_read_time_ms(1_700_000_000_000);
+            checkpoint.set_last_timer_log_flush_pos(520);
+            checkpoint.set_last_timer_queue_offset(10);
+            checkpoint.set_master_timer_queue_offset(12);
+            checkpoint.flush().unwrap();
+        }
+        let checkpoint = TimerCheckpoint::new(&path).unwrap();
+        assert_eq!(checkpoint.get_last_read_time_ms(), 1_700_000_000_000);
+        assert_eq!(checkpoint.get_last_timer_log_flush_pos(), 520);
+        assert_eq!(checkpoint.get_last_timer_queue_offset(), 10);
+        assert_eq!(checkpoint.get_master_timer_queue_offset(), 12);
+    }
+}
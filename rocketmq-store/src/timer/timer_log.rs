@@ -0,0 +1,280 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use bytes::Buf;
+use bytes::BufMut;
+use bytes::BytesMut;
+use tracing::info;
+
+use crate::consume_queue::mapped_file_queue::MappedFileQueue;
+use crate::kv::sparse_consume_queue::flush_all;
+use crate::log_file::mapped_file::MappedFile;
+
+/// Size of one unit of the timer log.
+pub const UNIT_SIZE: i32 = 4 + 8 + 4 + 8 + 4 + 8 + 4 + 4 + 8;
+
+/// One entry of the timer log. Format:
+/// ┌──────┬──────────┬───────┬────────────┬──────────────┬───────────┬─────────┬────────────┬──────────┐
+/// │ size │ prev pos │ magic │ write time │ delayed time │ offset py │ size py │ topic hash │
+/// reserved │
+/// ├──────┼──────────┼───────┼────────────┼──────────────┼───────────┼─────────┼────────────┼──────────┤
+/// │4bytes│  8bytes  │4bytes │   8bytes   │    4bytes    │  8bytes   │ 4bytes  │   4bytes   │
+/// 8bytes  │
+/// └──────┴──────────┴───────┴────────────┴──────────────┴───────────┴─────────┴────────────┴──────────┘
+///
+/// `prev_pos` links the units of one slot of the wheel, newest first. The delayed time is
+/// relative to the write time, so `curr_write_time_ms + delayed_time_ms` is the time of the slot
+/// the unit was put in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimerLogUnit {
+    pub prev_pos: i64,
+    pub magic: i32,
+    pub curr_write_time_ms: i64,
+    pub delayed_time_ms: i32,
+    pub offset_py: i64,
+    pub size_py: i32,
+    pub topic_hash: i32,
+}
+
+impl TimerLogUnit {
+    /// Time of the slot this unit was put in.
+    #[inline]
+    pub fn slot_time_ms(&self) -> i64 {
+        self.curr_write_time_ms + self.delayed_time_ms as i64
+    }
+}
+
+/// Append only log of [`TimerLogUnit`]s, the slots of the wheel point into it.
+pub struct TimerLog {
+    mapped_file_queue: MappedFileQueue,
+}
+
+impl TimerLog {
+    pub fn new(store_path: String, mapped_file_size: u64) -> Self {
+        // keep units from spanning two files
+        let mapped_file_size = mapped_file_size - mapped_file_size % UNIT_SIZE as u64;
+        Self {
+            mapped_file_queue: MappedFileQueue::new(store_path, mapped_file_size, None),
+        }
+    }
+
+    pub fn load(&mut self) -> bool {
+        self.mapped_file_queue.load()
+    }
+
+    /// Drops the partly written tail left by a crash, only the last file can have one.
+    pub fn recover(&mut self) {
+        let mapped_file_size = self.mapped_file_queue.mapped_file_size as i64;
+        let Some(mapped_file) = self.mapped_file_queue.get_last_mapped_file() else {
+            return;
+        };
+        let mut mapped_file_offset = 0;
+        while mapped_file_offset < mapped_file_size {
+            match read_unit(mapped_file.get_bytes(mapped_file_offset as usize, UNIT_SIZE as usize))
+            {
+                Some(_) => mapped_file_offset += UNIT_SIZE as i64,
+                None => break,
+            }
+        }
+        let process_offset = mapped_file.get_file_from_offset() as i64 + mapped_file_offset;
+        info!(
+            "recover timer log {} to offset {}",
+            self.mapped_file_queue.store_path, process_offset
+        );
+        self.mapped_file_queue.set_flushed_where(process_offset);
+        self.mapped_file_queue.set_committed_where(process_offset);
+        self.mapped_file_queue.truncate_dirty_files(process_offset);
+    }
+
+    /// Appends `unit` and returns its position, or -1 if no file could be created.
+    pub fn append(&mut self, unit: &TimerLogUnit) -> i64 {
+        let Some(mapped_file) = self
+            .mapped_file_queue
+            .get_last_mapped_file_mut_start_offset(0, true)
+        else {
+            return -1;
+        };
+        let pos =
+            mapped_file.get_file_from_offset() as i64 + mapped_file.get_wrote_position() as i64;
+        let mut buffer = BytesMut::with_capacity(UNIT_SIZE as usize);
+        buffer.put_i32(UNIT_SIZE);
+        buffer.put_i64(unit.prev_pos);
+        buffer.put_i32(unit.magic);
+        buffer.put_i64(unit.curr_write_time_ms);
+        buffer.put_i32(unit.delayed_time_ms);
+        buffer.put_i64(unit.offset_py);
+        buffer.put_i32(unit.size_py);
+        buffer.put_i32(unit.topic_hash);
+        buffer.put_i64(0);
+        if mapped_file.append_message_bytes(&buffer) {
+            pos
+        } else {
+            -1
+        }
+    }
+
+    pub fn get_unit(&self, pos: i64) -> Option<TimerLogUnit> {
+        if pos < 0 || pos + UNIT_SIZE as i64 > self.mapped_file_queue.get_max_offset() {
+            return None;
+        }
+        let mapped_file = self
+            .mapped_file_queue
+            .find_mapped_file_by_offset(pos, false)?;
+        let offset = pos - mapped_file.get_file_from_offset() as i64;
+        read_unit(mapped_file.get_bytes(offset as usize, UNIT_SIZE as usize))
+    }
+
+    pub fn get_min_offset(&self) -> i64 {
+        self.mapped_file_queue
+            .get_first_mapped_file()
+            .map_or(0, |mapped_file| mapped_file.get_file_from_offset() as i64)
+    }
+
+    pub fn get_max_offset(&self) -> i64 {
+        self.mapped_file_queue.get_max_offset()
+    }
+
+    /// Flushes the log and returns the flushed position.
+    pub fn flush(&self) -> i64 {
+        flush_all(&self.mapped_file_queue);
+        self.mapped_file_queue.get_flushed_where()
+    }
+
+    /// Deletes the leading files whose units all point to slots before `slot_time_ms`.
+    ///
+    /// A unit lands at most `max_delay_ms` after its write time, so a file is done with once
+    /// its last unit was written more than `max_delay_ms` before `slot_time_ms`. The file being
+    /// written is always kept.
+    pub fn delete_expired_files(&mut self, slot_time_ms: i64, max_delay_ms: i64) -> usize {
+        let mapped_file_size = self.mapped_file_queue.mapped_file_size as usize;
+        let mapped_files = self.mapped_file_queue.get_mapped_files();
+        let mut deleted = 0;
+        loop {
+            let mapped_file = {
+                let mapped_files = mapped_files.read();
+                if mapped_files.len() <= 1 {
+                    break;
+                }
+                mapped_files[0].clone()
+            };
+            let last_unit = read_unit(
+                mapped_file.get_bytes(mapped_file_size - UNIT_SIZE as usize, UNIT_SIZE as usize),
+            );
+            match last_unit {
+                Some(unit) if unit.curr_write_time_ms + max_delay_ms < slot_time_ms => {}
+                _ => break,
+            }
+            mapped_files.write().remove(0);
+            mapped_file.destroy(1000);
+            info!("delete expired timer log {}", mapped_file.get_file_name());
+            deleted += 1;
+        }
+        deleted
+    }
+
+    pub fn shutdown(&self) {
+        self.flush();
+    }
+
+    pub fn destroy(&mut self) {
+        self.mapped_file_queue.destroy();
+    }
+}
+
+fn read_unit(bytes: Option<bytes::Bytes>) -> Option<TimerLogUnit> {
+    let mut bytes = bytes?;
+    if bytes.get_i32() != UNIT_SIZE {
+        return None;
+    }
+    Some(TimerLogUnit {
+        prev_pos: bytes.get_i64(),
+        magic: bytes.get_i32(),
+        curr_write_time_ms: bytes.get_i64(),
+        delayed_time_ms: bytes.get_i32(),
+        offset_py: bytes.get_i64(),
+        size_py: bytes.get_i32(),
+        topic_hash: bytes.get_i32(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn unit(prev_pos: i64, curr_write_time_ms: i64) -> TimerLogUnit {
+        TimerLogUnit {
+            prev_pos,
+            magic: 1,
+            curr_write_time_ms,
+            delayed_time_ms: 3_000,
+            offset_py: 4096,
+            size_py: 200,
+            topic_hash: 0,
+        }
+    }
+
+    #[test]
+    fn units_are_linked_by_prev_pos() {
+        let dir = TempDir::new().unwrap();
+        let mut timer_log = TimerLog::new(
+            dir.path().to_string_lossy().into_owned(),
+            4 * UNIT_SIZE as u64 + 10,
+        );
+        let mut prev_pos = -1;
+        for i in 0..6 {
+            let pos = timer_log.append(&unit(prev_pos, i * 1000));
+            assert_eq!(pos, i * UNIT_SIZE as i64);
+            prev_pos = pos;
+        }
+        let mut walked = vec![];
+        let mut pos = prev_pos;
+        while let Some(unit) = timer_log.get_unit(pos) {
+            walked.push(unit.curr_write_time_ms);
+            pos = unit.prev_pos;
+        }
+        assert_eq!(walked, vec![5000, 4000, 3000, 2000, 1000, 0]);
+        assert_eq!(timer_log.get_unit(6 * UNIT_SIZE as i64), None);
+
+        assert_eq!(timer_log.delete_expired_files(6_000, 3_000), 0);
+        assert_eq!(timer_log.delete_expired_files(6_000, 2_000), 1);
+        assert_eq!(timer_log.get_min_offset(), 4 * UNIT_SIZE as i64);
+        assert_eq!(timer_log.get_unit(0), None);
+        assert_eq!(
+            timer_log.get_unit(5 * UNIT_SIZE as i64),
+            Some(unit(4 * UNIT_SIZE as i64, 5000))
+        );
+    }
+
+    #[test]
+    fn recover_keeps_written_units() {
+        let dir = TempDir::new().unwrap();
+        let store_path = dir.path().to_string_lossy().into_owned();
+        {
+            let mut timer_log = TimerLog::new(store_path.clone(), 4 * UNIT_SIZE as u64);
+            for i in 0..5 {
+                timer_log.append(&unit(-1, i));
+            }
+            timer_log.flush();
+        }
+        let mut timer_log = TimerLog::new(store_path, 4 * UNIT_SIZE as u64);
+        assert!(timer_log.load());
+        timer_log.recover();
+        assert_eq!(timer_log.get_max_offset(), 5 * UNIT_SIZE as i64);
+        assert_eq!(timer_log.append(&unit(-1, 5)), 5 * UNIT_SIZE as i64);
+    }
+}
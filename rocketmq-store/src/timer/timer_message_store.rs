@@ -14,19 +14,40 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::collections::HashSet;
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicI64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
 
 use cheetah_string::CheetahString;
+use rocketmq_common::common::config_manager::ConfigManager;
+use rocketmq_common::common::message::message_ext::MessageExt;
+use rocketmq_common::common::message::message_ext_broker_inner::MessageExtBrokerInner;
+use rocketmq_common::common::message::message_single;
 use rocketmq_common::common::message::MessageConst;
-use rocketmq_common::common::system_clock::SystemClock;
+use rocketmq_common::common::message::MessageTrait;
+use rocketmq_common::MessageAccessor::MessageAccessor;
+use rocketmq_common::MessageDecoder;
 use rocketmq_common::TimeUtils::get_current_millis;
 use rocketmq_rust::ArcMut;
+use tokio::sync::Notify;
 use tracing::error;
+use tracing::info;
 use tracing::warn;
 
+use crate::base::message_status_enum::PutMessageStatus;
 use crate::base::message_store::MessageStore;
+use crate::config::message_store_config::MessageStoreConfig;
 use crate::message_store::local_file_message_store::LocalFileMessageStore;
+use crate::store_path_config_helper::get_store_path_timer_log;
+use crate::store_path_config_helper::get_store_path_timer_wheel;
+use crate::timer::timer_checkpoint::TimerCheckpoint;
+use crate::timer::timer_log::TimerLog;
+use crate::timer::timer_log::TimerLogUnit;
 use crate::timer::timer_metrics::TimerMetrics;
+use crate::timer::timer_wheel::TimerWheel;
 
 pub const TIMER_TOPIC: &str = concat!("rmq_sys_", "wheel_timer");
 pub const TIMER_OUT_MS: &str = MessageConst::PROPERTY_TIMER_OUT_MS;
@@ -49,45 +70,500 @@ pub const MAGIC_DEFAULT: i32 = 1;
 pub const MAGIC_ROLL: i32 = 1 << 1;
 pub const MAGIC_DELETE: i32 = 1 << 2;
 
+const PUT_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+const ENQUEUE_IDLE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Delivers messages of [`TIMER_TOPIC`] at the time in their [`TIMER_OUT_MS`] property.
+///
+/// Messages are moved from the timer topic into a timing wheel: the wheel has one slot per
+/// `timer_precision_ms`, each slot heads a list of units in the timer log which point back to
+/// the messages in the commit log. The enqueue task appends every new message of the timer
+/// topic to the slot of its delivery time, the dequeue task walks the slots as time passes and
+/// puts their messages back to the real topic.
+///
+/// Delivery times further than `timer_roll_window_slot` slots away are put in the last slot of
+/// the window instead, when that slot is read the message is written to the timer topic again
+/// and rolls on until its time is within the window.
+///
+/// The read time and the consumed queue offset are persisted in the [`TimerCheckpoint`], so
+/// after a restart the wheel is read from where it stopped and the messages of the timer topic
+/// not in the wheel yet are enqueued again.
+#[derive(Clone)]
 pub struct TimerMessageStore {
-    pub curr_read_time_ms: AtomicI64,
-    pub curr_queue_offset: AtomicI64,
-    pub last_enqueue_but_expired_time: u64,
-    pub last_enqueue_but_expired_store_time: u64,
+    pub curr_read_time_ms: Arc<AtomicI64>,
+    pub curr_queue_offset: Arc<AtomicI64>,
+    pub last_enqueue_but_expired_time: Arc<AtomicI64>,
+    pub last_enqueue_but_expired_store_time: Arc<AtomicI64>,
     pub default_message_store: Option<ArcMut<LocalFileMessageStore>>,
-    pub timer_metrics: TimerMetrics,
+    pub timer_metrics: Arc<TimerMetrics>,
+    message_store_config: Arc<MessageStoreConfig>,
+    precision_ms: i64,
+    timer_roll_window_slots: i64,
+    // also serializes writing a slot against moving the read time past it
+    timer_log: Arc<parking_lot::Mutex<TimerLog>>,
+    timer_wheel: Arc<TimerWheel>,
+    timer_checkpoint: Arc<TimerCheckpoint>,
+    curr_write_time_ms: Arc<AtomicI64>,
+    // slots before it are delivered, while curr_read_time_ms moves as soon as a slot is taken
+    committed_read_time_ms: Arc<AtomicI64>,
+    should_running_dequeue: Arc<AtomicBool>,
+    running: Arc<AtomicBool>,
+    shutdown_notify: Arc<Notify>,
 }
 
-impl Clone for TimerMessageStore {
-    fn clone(&self) -> Self {
-        Self {
-            curr_read_time_ms: AtomicI64::new(
-                self.curr_read_time_ms
-                    .load(std::sync::atomic::Ordering::Relaxed),
-            ),
-            curr_queue_offset: AtomicI64::new(
-                self.curr_queue_offset
-                    .load(std::sync::atomic::Ordering::Relaxed),
-            ),
-            last_enqueue_but_expired_time: self.last_enqueue_but_expired_time,
-            last_enqueue_but_expired_store_time: self.last_enqueue_but_expired_store_time,
-            default_message_store: self.default_message_store.clone(),
-            timer_metrics: TimerMetrics,
+impl TimerMessageStore {
+    pub fn new(
+        default_message_store: Option<ArcMut<LocalFileMessageStore>>,
+        message_store_config: Arc<MessageStoreConfig>,
+        timer_checkpoint: TimerCheckpoint,
+        timer_metrics: TimerMetrics,
+    ) -> std::io::Result<Self> {
+        let precision_ms = message_store_config.timer_precision_ms as i64;
+        let slots_total = TIMER_WHEEL_TTL_DAY * DAY_SECS;
+        let mut timer_roll_window_slots = message_store_config.timer_roll_window_slot as i64;
+        if timer_roll_window_slots > (slots_total - TIMER_BLANK_SLOTS) as i64
+            || timer_roll_window_slots < 2
+        {
+            timer_roll_window_slots = (slots_total - TIMER_BLANK_SLOTS) as i64;
+        }
+        let root_dir = message_store_config.store_path_root_dir.as_str();
+        let timer_wheel = TimerWheel::new(
+            get_store_path_timer_wheel(root_dir).as_str(),
+            slots_total,
+            precision_ms as i32,
+        )?;
+        let timer_log = TimerLog::new(
+            get_store_path_timer_log(root_dir),
+            message_store_config.mapped_file_size_timer_log as u64,
+        );
+        Ok(Self {
+            curr_read_time_ms: Arc::new(AtomicI64::new(0)),
+            curr_queue_offset: Arc::new(AtomicI64::new(0)),
+            last_enqueue_but_expired_time: Arc::new(AtomicI64::new(0)),
+            last_enqueue_but_expired_store_time: Arc::new(AtomicI64::new(0)),
+            default_message_store,
+            timer_metrics: Arc::new(timer_metrics),
+            message_store_config,
+            precision_ms,
+            timer_roll_window_slots,
+            timer_log: Arc::new(parking_lot::Mutex::new(timer_log)),
+            timer_wheel: Arc::new(timer_wheel),
+            timer_checkpoint: Arc::new(timer_checkpoint),
+            curr_write_time_ms: Arc::new(AtomicI64::new(0)),
+            committed_read_time_ms: Arc::new(AtomicI64::new(0)),
+            should_running_dequeue: Arc::new(AtomicBool::new(false)),
+            running: Arc::new(AtomicBool::new(false)),
+            shutdown_notify: Arc::new(Notify::new()),
+        })
+    }
+
+    pub fn load(&self) -> bool {
+        let mut result = self.timer_metrics.load();
+        {
+            let mut timer_log = self.timer_log.lock();
+            result &= timer_log.load();
+            timer_log.recover();
         }
+        self.recover();
+        info!(
+            "TimerMessageStore load, result: {}, currReadTimeMs: {}, currQueueOffset: {}",
+            result,
+            self.curr_read_time_ms.load(Ordering::Relaxed),
+            self.curr_queue_offset.load(Ordering::Relaxed)
+        );
+        result
     }
-}
 
-impl TimerMessageStore {
-    pub fn load(&mut self) -> bool {
+    fn recover(&self) {
+        let now = self.format_time_ms(get_current_millis() as i64);
+        let last_read_time_ms = self.timer_checkpoint.get_last_read_time_ms();
+        let read_time_ms = if last_read_time_ms <= 0 {
+            now
+        } else {
+            self.format_time_ms(last_read_time_ms)
+        };
+        let wheel_span_ms = (TIMER_WHEEL_TTL_DAY * DAY_SECS) as i64 * self.precision_ms;
+        if now - read_time_ms > wheel_span_ms {
+            warn!(
+                "timer wheel was last read at {}, longer than the wheel span {}ms ago, timer \
+                 messages of that period are lost",
+                read_time_ms, wheel_span_ms
+            );
+        }
+        self.curr_read_time_ms
+            .store(read_time_ms, Ordering::Release);
+        self.committed_read_time_ms
+            .store(read_time_ms, Ordering::Release);
+        self.curr_write_time_ms.store(now, Ordering::Release);
+        self.curr_queue_offset.store(
+            self.timer_checkpoint.get_last_timer_queue_offset(),
+            Ordering::Release,
+        );
+    }
+
+    pub fn start(&self) {
+        if self.running.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let this = self.clone();
+        tokio::spawn(async move {
+            info!("TimerEnqueueService started");
+            while this.running.load(Ordering::Acquire) {
+                if this.message_store_config.timer_stop_enqueue || !this.enqueue() {
+                    tokio::select! {
+                        _ = this.shutdown_notify.notified() => {}
+                        _ = tokio::time::sleep(ENQUEUE_IDLE_INTERVAL) => {}
+                    }
+                }
+            }
+            info!("TimerEnqueueService end");
+        });
+
+        let this = self.clone();
+        tokio::spawn(async move {
+            info!("TimerDequeueService started");
+            let idle_interval = Duration::from_millis((this.precision_ms / 4).max(1) as u64);
+            while this.running.load(Ordering::Acquire) {
+                if !this.dequeue().await {
+                    tokio::select! {
+                        _ = this.shutdown_notify.notified() => {}
+                        _ = tokio::time::sleep(idle_interval) => {}
+                    }
+                }
+            }
+            info!("TimerDequeueService end");
+        });
+
+        let this = self.clone();
+        tokio::spawn(async move {
+            info!("TimerFlushService started");
+            let flush_interval =
+                Duration::from_millis(this.message_store_config.timer_flush_interval_ms as u64);
+            while this.running.load(Ordering::Acquire) {
+                tokio::select! {
+                    _ = this.shutdown_notify.notified() => {}
+                    _ = tokio::time::sleep(flush_interval) => {}
+                }
+                this.flush();
+            }
+            info!("TimerFlushService end");
+        });
+    }
+
+    /// Moves the new messages of the timer topic into the wheel, returns whether there were any.
+    fn enqueue(&self) -> bool {
+        let Some(message_store) = self.default_message_store.as_ref() else {
+            return false;
+        };
+        let Some(consume_queue) =
+            message_store.get_consume_queue(&CheetahString::from_static_str(TIMER_TOPIC), 0)
+        else {
+            return false;
+        };
+        let mut offset = self.curr_queue_offset.load(Ordering::Acquire);
+        let min_offset = consume_queue.get_min_offset_in_queue();
+        if offset < min_offset {
+            warn!(
+                "timer queue offset {} is less than the min offset {}, messages in between are \
+                 lost",
+                offset, min_offset
+            );
+            offset = min_offset;
+            self.curr_queue_offset.store(offset, Ordering::Release);
+        }
+        let Some(mut iter) = consume_queue.iterate_from(offset) else {
+            return false;
+        };
+        self.curr_write_time_ms.store(
+            self.format_time_ms(get_current_millis() as i64),
+            Ordering::Release,
+        );
+        let mut enqueued = false;
+        while self.running.load(Ordering::Acquire) {
+            let Some(cq_unit) = iter.next() else {
+                break;
+            };
+            match message_store.look_message_by_offset_with_size(cq_unit.pos, cq_unit.size) {
+                Some(msg_ext) => {
+                    let delayed_time = msg_ext
+                        .get_property(&CheetahString::from_static_str(TIMER_OUT_MS))
+                        .and_then(|value| value.parse::<i64>().ok());
+                    match delayed_time {
+                        Some(delayed_time) => {
+                            if !self.do_enqueue(cq_unit.pos, cq_unit.size, delayed_time, &msg_ext) {
+                                error!(
+                                    "enqueue timer message failed, queue offset {}",
+                                    cq_unit.queue_offset
+                                );
+                                break;
+                            }
+                        }
+                        None => warn!(
+                            "timer message without {} at queue offset {}, skip it",
+                            TIMER_OUT_MS, cq_unit.queue_offset
+                        ),
+                    }
+                }
+                None => warn!(
+                    "timer message not found, queue offset {}, offsetPy {}, sizePy {}",
+                    cq_unit.queue_offset, cq_unit.pos, cq_unit.size
+                ),
+            }
+            self.curr_queue_offset.store(
+                cq_unit.queue_offset + cq_unit.batch_num as i64,
+                Ordering::Release,
+            );
+            enqueued = true;
+        }
+        iter.release();
+        enqueued
+    }
+
+    /// Appends the message at `offset_py` to the slot of `delayed_time`.
+    ///
+    /// A message whose slot has already been read goes to the slot being read, one further than
+    /// the roll window goes to the end of the window and is marked to roll.
+    pub fn do_enqueue(
+        &self,
+        offset_py: i64,
+        size_py: i32,
+        delayed_time: i64,
+        msg_ext: &MessageExt,
+    ) -> bool {
+        let is_delete = msg_ext
+            .get_property(&CheetahString::from_static_str(TIMER_DELETE_UNIQUE_KEY))
+            .is_some();
+        let mut magic = MAGIC_DEFAULT;
+        if is_delete {
+            magic |= MAGIC_DELETE;
+        }
+        {
+            let mut timer_log = self.timer_log.lock();
+            let curr_write_time_ms = self.curr_write_time_ms.load(Ordering::Acquire);
+            let curr_read_time_ms = self.curr_read_time_ms.load(Ordering::Acquire);
+            let mut delayed_time = self.format_time_ms(delayed_time);
+            if delayed_time < curr_read_time_ms {
+                self.last_enqueue_but_expired_time
+                    .store(get_current_millis() as i64, Ordering::Relaxed);
+                self.last_enqueue_but_expired_store_time
+                    .store(msg_ext.store_timestamp(), Ordering::Relaxed);
+                delayed_time = curr_read_time_ms;
+            }
+            let roll_window_ms = self.timer_roll_window_slots * self.precision_ms;
+            if delayed_time - curr_write_time_ms >= roll_window_ms {
+                magic |= MAGIC_ROLL;
+                // keep the next roll from landing just short of the delivery time
+                if delayed_time - curr_write_time_ms - roll_window_ms
+                    < self.timer_roll_window_slots / 3 * self.precision_ms
+                {
+                    delayed_time =
+                        curr_write_time_ms + self.timer_roll_window_slots / 2 * self.precision_ms;
+                } else {
+                    delayed_time = curr_write_time_ms + roll_window_ms;
+                }
+            }
+            let slot = self.timer_wheel.get_slot(delayed_time);
+            let pos = timer_log.append(&TimerLogUnit {
+                prev_pos: slot.last_pos,
+                magic,
+                curr_write_time_ms,
+                delayed_time_ms: (delayed_time - curr_write_time_ms) as i32,
+                offset_py,
+                size_py,
+                topic_hash: 0,
+            });
+            if pos == -1 {
+                return false;
+            }
+            self.timer_wheel.put_slot(
+                delayed_time,
+                if slot.first_pos == -1 {
+                    pos
+                } else {
+                    slot.first_pos
+                },
+                pos,
+                if is_delete {
+                    slot.num - 1
+                } else {
+                    slot.num + 1
+                },
+                slot.magic,
+            );
+        }
+        if let Some(real_topic) = msg_ext.get_property(&CheetahString::from_static_str(
+            MessageConst::PROPERTY_REAL_TOPIC,
+        )) {
+            self.timer_metrics
+                .add_and_get(&real_topic, if is_delete { -1 } else { 1 });
+        }
         true
     }
 
-    pub fn start(&mut self) {
-        warn!("TimerMessageStore start unimplemented, do nothing");
+    /// Takes the slot at the read time and moves the read time past it, returns the units of the
+    /// slot oldest first, or `None` if the slot is not due yet.
+    fn take_due_slot(&self, now_ms: i64) -> Option<Vec<TimerLogUnit>> {
+        let timer_log = self.timer_log.lock();
+        let read_time_ms = self.curr_read_time_ms.load(Ordering::Acquire);
+        if read_time_ms > now_ms {
+            return None;
+        }
+        let slot = self.timer_wheel.get_slot(read_time_ms);
+        let mut units = Vec::with_capacity(slot.num.max(0) as usize);
+        let mut pos = slot.last_pos;
+        while pos != -1 {
+            match timer_log.get_unit(pos) {
+                // the log may have been truncated after a crash and the position reused
+                Some(unit) if unit.slot_time_ms() == read_time_ms && unit.prev_pos < pos => {
+                    units.push(unit);
+                    pos = unit.prev_pos;
+                }
+                _ => {
+                    warn!(
+                        "broken unit list of timer slot {} at position {}",
+                        read_time_ms, pos
+                    );
+                    break;
+                }
+            }
+        }
+        units.reverse();
+        self.curr_read_time_ms
+            .store(read_time_ms + self.precision_ms, Ordering::Release);
+        Some(units)
     }
 
-    pub fn is_reject(&self, _deliver_ms: u64) -> bool {
-        false
+    /// Delivers the messages of the slot at the read time, returns whether the slot was due.
+    async fn dequeue(&self) -> bool {
+        if self.message_store_config.timer_stop_dequeue
+            || !self.should_running_dequeue.load(Ordering::Acquire)
+        {
+            return false;
+        }
+        let Some(units) = self.take_due_slot(get_current_millis() as i64) else {
+            return false;
+        };
+        let Some(message_store) = self.default_message_store.as_ref() else {
+            return false;
+        };
+        let mut delete_keys = HashSet::new();
+        let mut messages = Vec::with_capacity(units.len());
+        for unit in units.iter() {
+            let Some(msg_ext) =
+                message_store.look_message_by_offset_with_size(unit.offset_py, unit.size_py)
+            else {
+                warn!(
+                    "timer message not found, offsetPy {}, sizePy {}",
+                    unit.offset_py, unit.size_py
+                );
+                continue;
+            };
+            if unit.magic & MAGIC_DELETE != 0 {
+                if let Some(delete_key) =
+                    msg_ext.get_property(&CheetahString::from_static_str(TIMER_DELETE_UNIQUE_KEY))
+                {
+                    delete_keys.insert(delete_key);
+                }
+                continue;
+            }
+            messages.push((unit.magic, msg_ext));
+        }
+        for (magic, msg_ext) in messages {
+            let real_topic = msg_ext.get_property(&CheetahString::from_static_str(
+                MessageConst::PROPERTY_REAL_TOPIC,
+            ));
+            if let (Some(real_topic), Some(uniq_key)) = (
+                real_topic.as_ref(),
+                msg_ext.get_property(&CheetahString::from_static_str(
+                    MessageConst::PROPERTY_UNIQ_CLIENT_MESSAGE_ID_KEYIDX,
+                )),
+            ) {
+                if delete_keys.contains(&build_delete_key(real_topic, &uniq_key)) {
+                    continue;
+                }
+            }
+            loop {
+                let msg_inner = convert_message(msg_ext.clone(), magic & MAGIC_ROLL != 0);
+                match self.do_put(msg_inner).await {
+                    PUT_NEED_RETRY if self.running.load(Ordering::Acquire) => {
+                        tokio::time::sleep(PUT_RETRY_INTERVAL).await;
+                    }
+                    PUT_NEED_RETRY => return true,
+                    _ => break,
+                }
+            }
+            if let Some(real_topic) = real_topic {
+                self.timer_metrics.add_and_get(&real_topic, -1);
+            }
+        }
+        self.committed_read_time_ms.store(
+            self.curr_read_time_ms.load(Ordering::Acquire),
+            Ordering::Release,
+        );
+        true
+    }
+
+    async fn do_put(&self, msg_inner: MessageExtBrokerInner) -> i32 {
+        let Some(message_store) = self.default_message_store.as_ref() else {
+            return PUT_NO_RETRY;
+        };
+        let result = message_store.mut_from_ref().put_message(msg_inner).await;
+        match result.put_message_status() {
+            PutMessageStatus::PutOk
+            | PutMessageStatus::FlushDiskTimeout
+            | PutMessageStatus::FlushSlaveTimeout
+            | PutMessageStatus::SlaveNotAvailable => PUT_OK,
+            PutMessageStatus::ServiceNotAvailable => PUT_NEED_RETRY,
+            PutMessageStatus::MessageIllegal
+            | PutMessageStatus::PropertiesSizeExceeded
+            | PutMessageStatus::WheelTimerNotEnable
+            | PutMessageStatus::WheelTimerMsgIllegal => PUT_NO_RETRY,
+            status => {
+                warn!("put timer message failed, status: {:?}", status);
+                if self.message_store_config.timer_skip_unknown_error {
+                    PUT_NO_RETRY
+                } else {
+                    PUT_NEED_RETRY
+                }
+            }
+        }
+    }
+
+    /// Flushes the timer log and the wheel, then records the progress in the checkpoint.
+    pub fn flush(&self) {
+        // taken before flushing so everything they cover is on disk
+        let queue_offset = self.curr_queue_offset.load(Ordering::Acquire);
+        let read_time_ms = self.committed_read_time_ms.load(Ordering::Acquire);
+        let flush_pos = self.timer_log.lock().flush();
+        self.timer_wheel.flush();
+        self.timer_checkpoint.set_last_read_time_ms(read_time_ms);
+        self.timer_checkpoint
+            .set_last_timer_log_flush_pos(flush_pos);
+        self.timer_checkpoint
+            .set_last_timer_queue_offset(queue_offset);
+        if let Err(e) = self.timer_checkpoint.flush() {
+            error!("flush timer checkpoint failed: {}", e);
+        }
+        self.timer_metrics.persist();
+        self.timer_log.lock().delete_expired_files(
+            read_time_ms,
+            (self.timer_roll_window_slots + 1) * self.precision_ms,
+        );
+    }
+
+    /// Whether to turn away a message due at `deliver_ms` because its slot is congested.
+    pub fn is_reject(&self, deliver_ms: u64) -> bool {
+        let congest_num = self.timer_wheel.get_num(deliver_ms as i64) as i64;
+        let congest_num_each_slot = self.message_store_config.timer_congest_num_each_slot as i64;
+        if congest_num <= congest_num_each_slot {
+            return false;
+        }
+        if congest_num >= congest_num_each_slot * 2 {
+            return true;
+        }
+        rand::random_range(0..1000) as f64
+            > 1000.0 * (congest_num - congest_num_each_slot) as f64
+                / (congest_num_each_slot as f64 + 0.1)
     }
 
     pub fn get_dequeue_behind(&self) -> i64 {
@@ -95,15 +571,15 @@ impl TimerMessageStore {
     }
 
     pub fn get_dequeue_behind_millis(&self) -> i64 {
-        (SystemClock::now() as i64)
-            - self
-                .curr_read_time_ms
-                .load(std::sync::atomic::Ordering::Relaxed)
+        get_current_millis() as i64 - self.curr_read_time_ms.load(Ordering::Relaxed)
     }
 
     pub fn get_enqueue_behind_millis(&self) -> i64 {
-        if get_current_millis() - self.last_enqueue_but_expired_time < 2000 {
-            ((get_current_millis() - self.last_enqueue_but_expired_store_time) / 1000) as i64
+        let now = get_current_millis() as i64;
+        if now - self.last_enqueue_but_expired_time.load(Ordering::Relaxed) < 2000 {
+            now - self
+                .last_enqueue_but_expired_store_time
+                .load(Ordering::Relaxed)
         } else {
             0
         }
@@ -114,9 +590,7 @@ impl TimerMessageStore {
     }
 
     pub fn get_enqueue_behind_messages(&self) -> i64 {
-        let temp_queue_offset = self
-            .curr_queue_offset
-            .load(std::sync::atomic::Ordering::Relaxed);
+        let temp_queue_offset = self.curr_queue_offset.load(Ordering::Relaxed);
         let consume_queue = self
             .default_message_store
             .as_ref()
@@ -130,7 +604,8 @@ impl TimerMessageStore {
     }
 
     pub fn get_all_congest_num(&self) -> i64 {
-        0
+        self.timer_wheel
+            .get_all_num(self.curr_read_time_ms.load(Ordering::Relaxed))
     }
 
     pub fn get_enqueue_tps(&self) -> f32 {
@@ -141,43 +616,246 @@ impl TimerMessageStore {
         0.0
     }
 
-    pub fn new(default_message_store: Option<ArcMut<LocalFileMessageStore>>) -> Self {
-        Self {
-            curr_read_time_ms: AtomicI64::new(0),
-            curr_queue_offset: AtomicI64::new(0),
-            last_enqueue_but_expired_time: 0,
-            last_enqueue_but_expired_store_time: 0,
-            default_message_store,
-            timer_metrics: TimerMetrics,
+    pub fn set_default_message_store(
+        &mut self,
+        default_message_store: Option<ArcMut<LocalFileMessageStore>>,
+    ) {
+        self.default_message_store = default_message_store;
+    }
+
+    pub fn shutdown(&self) {
+        if !self.running.swap(false, Ordering::AcqRel) {
+            return;
         }
+        self.shutdown_notify.notify_waiters();
+        self.flush();
+        self.timer_log.lock().shutdown();
+        info!("TimerMessageStore shutdown");
     }
 
-    pub fn new_empty() -> Self {
-        Self {
-            curr_read_time_ms: AtomicI64::new(0),
-            curr_queue_offset: AtomicI64::new(0),
-            last_enqueue_but_expired_time: 0,
-            last_enqueue_but_expired_store_time: 0,
-            default_message_store: None,
-            timer_metrics: TimerMetrics,
+    /// Resets the read time to the checkpoint, used before a slave takes over dequeuing.
+    pub fn sync_last_read_time_ms(&self) {
+        let _timer_log = self.timer_log.lock();
+        let last_read_time_ms = self.timer_checkpoint.get_last_read_time_ms();
+        if last_read_time_ms > 0 {
+            self.curr_read_time_ms
+                .store(last_read_time_ms, Ordering::Release);
+            self.committed_read_time_ms
+                .store(last_read_time_ms, Ordering::Release);
         }
     }
 
-    pub fn set_default_message_store(
-        &mut self,
-        default_message_store: Option<ArcMut<LocalFileMessageStore>>,
-    ) {
-        self.default_message_store = default_message_store;
+    pub fn set_should_running_dequeue(&self, should_start: bool) {
+        self.should_running_dequeue
+            .store(should_start, Ordering::Release);
+    }
+
+    #[inline]
+    fn format_time_ms(&self, time_ms: i64) -> i64 {
+        time_ms / self.precision_ms * self.precision_ms
+    }
+}
+
+/// Value of [`TIMER_DELETE_UNIQUE_KEY`] that cancels the timer message `uniq_key` of `topic`.
+pub fn build_delete_key(topic: &str, uniq_key: &str) -> CheetahString {
+    CheetahString::from_string(format!("{topic}+{uniq_key}"))
+}
+
+/// Builds the message to put when a slot is due, back to the timer topic if it has to roll on,
+/// otherwise to its real topic and queue.
+fn convert_message(msg_ext: MessageExt, need_roll: bool) -> MessageExtBrokerInner {
+    let mut inner = MessageExtBrokerInner::default();
+    let sys_flag = msg_ext.sys_flag();
+    let born_timestamp = msg_ext.born_timestamp();
+    let born_host = msg_ext.born_host();
+    let store_host = msg_ext.store_host();
+    let reconsume_times = msg_ext.reconsume_times();
+    let topic = msg_ext.topic().clone();
+    let queue_id = msg_ext.queue_id();
+    let message = msg_ext.message;
+    if let Some(body) = message.body {
+        inner.set_body(body);
+    }
+    inner.set_flag(message.flag);
+    MessageAccessor::set_properties(&mut inner, message.properties);
+    MessageAccessor::put_property(
+        &mut inner,
+        CheetahString::from_static_str(TIMER_DEQUEUE_MS),
+        CheetahString::from_string(get_current_millis().to_string()),
+    );
+    if need_roll {
+        let roll_times = inner
+            .get_property(&CheetahString::from_static_str(TIMER_ROLL_TIMES))
+            .and_then(|value| value.parse::<i32>().ok())
+            .unwrap_or(0);
+        MessageAccessor::put_property(
+            &mut inner,
+            CheetahString::from_static_str(TIMER_ROLL_TIMES),
+            CheetahString::from_string((roll_times + 1).to_string()),
+        );
+        inner.set_topic(topic);
+        inner.message_ext_inner.queue_id = queue_id;
+    } else {
+        if let Some(real_topic) = inner.get_property(&CheetahString::from_static_str(
+            MessageConst::PROPERTY_REAL_TOPIC,
+        )) {
+            inner.set_topic(real_topic);
+        }
+        if let Some(real_queue_id) = inner.get_property(&CheetahString::from_static_str(
+            MessageConst::PROPERTY_REAL_QUEUE_ID,
+        )) {
+            inner.message_ext_inner.queue_id = real_queue_id.parse::<i32>().unwrap_or_default();
+        }
+        MessageAccessor::clear_property(&mut inner, MessageConst::PROPERTY_REAL_TOPIC);
+        MessageAccessor::clear_property(&mut inner, MessageConst::PROPERTY_REAL_QUEUE_ID);
+    }
+    let topic_filter_type = message_single::parse_topic_filter_type(sys_flag);
+    inner.tags_code = MessageExtBrokerInner::tags_string2tags_code(
+        &topic_filter_type,
+        inner.get_tags().as_ref().unwrap_or(&CheetahString::empty()),
+    );
+    inner.properties_string = MessageDecoder::message_properties_to_string(inner.get_properties());
+    inner.message_ext_inner.sys_flag = sys_flag;
+    inner.message_ext_inner.born_timestamp = born_timestamp;
+    inner.message_ext_inner.born_host = born_host;
+    inner.message_ext_inner.store_host = store_host;
+    inner.message_ext_inner.reconsume_times = reconsume_times;
+    inner.set_wait_store_msg_ok(false);
+    inner
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::timer::timer_log::UNIT_SIZE;
+
+    const TOPIC: &str = "TimerTopicA";
+
+    fn new_store(root_dir: &Path) -> TimerMessageStore {
+        let message_store_config = MessageStoreConfig {
+            store_path_root_dir: root_dir.to_string_lossy().into_owned().into(),
+            timer_roll_window_slot: 10,
+            mapped_file_size_timer_log: 64 * UNIT_SIZE as usize,
+            ..Default::default()
+        };
+        TimerMessageStore::new(
+            None,
+            Arc::new(message_store_config),
+            TimerCheckpoint::new(root_dir.join("config").join("timercheck")).unwrap(),
+            TimerMetrics::new(
+                root_dir
+                    .join("config")
+                    .join("timermetrics")
+                    .to_string_lossy()
+                    .into_owned(),
+            ),
+        )
+        .unwrap()
     }
 
-    pub fn shutdown(&mut self) {
-        warn!("TimerMessageStore shutdown unimplemented, do nothing");
+    fn set_time(store: &TimerMessageStore, read_time_ms: i64, write_time_ms: i64) {
+        store
+            .curr_read_time_ms
+            .store(read_time_ms, Ordering::Release);
+        store
+            .committed_read_time_ms
+            .store(read_time_ms, Ordering::Release);
+        store
+            .curr_write_time_ms
+            .store(write_time_ms, Ordering::Release);
     }
 
-    pub fn sync_last_read_time_ms(&mut self) {
-        error!("sync_last_read_time_ms unimplemented");
+    fn timer_message() -> MessageExt {
+        let mut msg_ext = MessageExt::default();
+        msg_ext.put_property(
+            CheetahString::from_static_str(MessageConst::PROPERTY_REAL_TOPIC),
+            CheetahString::from_static_str(TOPIC),
+        );
+        msg_ext
     }
-    pub fn set_should_running_dequeue(&mut self, _should_start: bool) {
-        error!("set_should_running_dequeue unimplemented");
+
+    fn take_offsets(store: &TimerMessageStore, now_ms: i64) -> Option<Vec<(i64, bool)>> {
+        store.take_due_slot(now_ms).map(|units| {
+            units
+                .iter()
+                .map(|unit| (unit.offset_py, unit.magic & MAGIC_ROLL != 0))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn messages_are_taken_when_their_slot_is_due() {
+        let dir = TempDir::new().unwrap();
+        let store = new_store(dir.path());
+        set_time(&store, 1_000_000, 1_000_000);
+
+        assert!(store.do_enqueue(100, 10, 1_003_000, &timer_message()));
+        assert!(store.do_enqueue(200, 10, 1_003_999, &timer_message()));
+        // already expired, goes to the slot being read
+        assert!(store.do_enqueue(300, 10, 500_000, &timer_message()));
+        // beyond the roll window of 10 slots
+        assert!(store.do_enqueue(400, 10, 1_050_000, &timer_message()));
+        assert_eq!(
+            store
+                .timer_metrics
+                .get_timing_count(&CheetahString::from_static_str(TOPIC)),
+            4
+        );
+
+        assert_eq!(take_offsets(&store, 999_999), None);
+        assert_eq!(take_offsets(&store, 1_000_000), Some(vec![(300, false)]));
+        assert_eq!(take_offsets(&store, 1_000_000), None);
+        assert_eq!(take_offsets(&store, 1_001_000), Some(vec![]));
+        assert_eq!(take_offsets(&store, 1_002_000), Some(vec![]));
+        assert_eq!(
+            take_offsets(&store, 1_003_000),
+            Some(vec![(100, false), (200, false)])
+        );
+        for now_ms in (1_004_000..1_010_000).step_by(1000) {
+            assert_eq!(take_offsets(&store, now_ms), Some(vec![]));
+        }
+        assert_eq!(take_offsets(&store, 1_010_000), Some(vec![(400, true)]));
+    }
+
+    #[test]
+    fn progress_survives_restart() {
+        let dir = TempDir::new().unwrap();
+        {
+            let store = new_store(dir.path());
+            assert!(store.load());
+            set_time(&store, 2_000_000, 2_000_000);
+            assert!(store.do_enqueue(100, 10, 2_002_000, &timer_message()));
+            assert_eq!(take_offsets(&store, 2_000_000), Some(vec![]));
+            store.committed_read_time_ms.store(
+                store.curr_read_time_ms.load(Ordering::Acquire),
+                Ordering::Release,
+            );
+            store.curr_queue_offset.store(7, Ordering::Release);
+            store.flush();
+        }
+        let store = new_store(dir.path());
+        assert!(store.load());
+        assert_eq!(store.curr_queue_offset.load(Ordering::Acquire), 7);
+        assert_eq!(store.curr_read_time_ms.load(Ordering::Acquire), 2_001_000);
+        assert_eq!(
+            store
+                .timer_metrics
+                .get_timing_count(&CheetahString::from_static_str(TOPIC)),
+            1
+        );
+        assert_eq!(take_offsets(&store, 2_001_000), Some(vec![]));
+        assert_eq!(take_offsets(&store, 2_002_000), Some(vec![(100, false)]));
+    }
+
+    #[test]
+    fn delete_key_joins_topic_and_uniq_key() {
+        assert_eq!(
+            build_delete_key("TopicA", "7F0000010001"),
+            CheetahString::from_static_str("TopicA+7F0000010001")
+        );
     }
 }
@@ -14,27 +14,125 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+use std::collections::HashMap;
+
 use cheetah_string::CheetahString;
 use rocketmq_common::common::config_manager::ConfigManager;
+use rocketmq_common::utils::serde_json_utils::SerdeJsonUtils;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_remoting::protocol::DataVersion;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::warn;
+
+/// Number of timer messages waiting in the wheel for one topic.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Metric {
+    pub count: i64,
+    pub time_stamp: i64,
+}
 
-pub struct TimerMetrics;
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TimerMetricsSerializeWrapper {
+    timing_count: HashMap<CheetahString, Metric>,
+    data_version: DataVersion,
+}
+
+/// Per topic count of the messages in the timer wheel, persisted as json.
+pub struct TimerMetrics {
+    config_path: String,
+    timing_count: parking_lot::Mutex<HashMap<CheetahString, Metric>>,
+    data_version: parking_lot::Mutex<DataVersion>,
+}
+
+impl TimerMetrics {
+    pub fn new(config_path: impl Into<String>) -> Self {
+        Self {
+            config_path: config_path.into(),
+            timing_count: parking_lot::Mutex::new(HashMap::new()),
+            data_version: parking_lot::Mutex::new(DataVersion::new()),
+        }
+    }
+
+    /// Adds `value` to the count of `topic` and returns the new count.
+    pub fn add_and_get(&self, topic: &CheetahString, value: i64) -> i64 {
+        let mut timing_count = self.timing_count.lock();
+        let metric = timing_count.entry(topic.clone()).or_default();
+        metric.count += value;
+        metric.time_stamp = get_current_millis() as i64;
+        metric.count
+    }
+
+    pub fn get_timing_count(&self, topic: &CheetahString) -> i64 {
+        self.timing_count
+            .lock()
+            .get(topic)
+            .map_or(0, |metric| metric.count)
+    }
+
+    pub fn get_timing_count_table(&self) -> HashMap<CheetahString, Metric> {
+        self.timing_count.lock().clone()
+    }
+
+    pub fn remove_timing_count(&self, topic: &CheetahString) {
+        self.timing_count.lock().remove(topic);
+    }
+}
 
 impl ConfigManager for TimerMetrics {
     fn config_file_path(&self) -> String {
-        todo!()
+        self.config_path.clone()
     }
 
     fn encode_pretty(&self, pretty_format: bool) -> String {
-        todo!()
+        let wrapper = TimerMetricsSerializeWrapper {
+            timing_count: self.timing_count.lock().clone(),
+            data_version: self.data_version.lock().clone(),
+        };
+        if pretty_format {
+            SerdeJsonUtils::to_json_pretty(&wrapper).expect("encode timer metrics failed")
+        } else {
+            SerdeJsonUtils::to_json(&wrapper).expect("encode timer metrics failed")
+        }
     }
 
     fn decode(&self, json_string: &str) {
-        todo!()
+        if json_string.is_empty() {
+            return;
+        }
+        match SerdeJsonUtils::from_json_str::<TimerMetricsSerializeWrapper>(json_string) {
+            Ok(wrapper) => {
+                *self.timing_count.lock() = wrapper.timing_count;
+                self.data_version
+                    .lock()
+                    .assign_new_one(&wrapper.data_version);
+            }
+            Err(e) => warn!("decode timer metrics failed: {}", e),
+        }
     }
 }
 
-impl TimerMetrics {
-    pub fn get_timing_count(&self, key: &CheetahString) -> i64 {
-        unimplemented!("get_timing_count is not implemented")
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_survive_encode_and_decode() {
+        let metrics = TimerMetrics::new("timermetrics");
+        let topic = CheetahString::from_static_str("TopicA");
+        assert_eq!(metrics.add_and_get(&topic, 3), 3);
+        assert_eq!(metrics.add_and_get(&topic, -1), 2);
+        assert_eq!(
+            metrics.get_timing_count(&CheetahString::from_static_str("TopicB")),
+            0
+        );
+
+        let json = metrics.encode_pretty(false);
+        assert!(json.contains("\"timingCount\""));
+        let decoded = TimerMetrics::new("timermetrics");
+        decoded.decode(&json);
+        assert_eq!(decoded.get_timing_count(&topic), 2);
     }
 }
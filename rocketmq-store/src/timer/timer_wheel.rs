@@ -0,0 +1,176 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::fs::OpenOptions;
+use std::path::Path;
+
+use memmap2::MmapMut;
+use rocketmq_common::UtilAll::ensure_dir_ok;
+use tracing::error;
+use tracing::info;
+
+use crate::timer::slot::Slot;
+
+/// The timing wheel, a fixed ring of [`Slot`]s mapped from a file.
+///
+/// The ring holds `slots_total * 2` slots of `precision_ms` each, a slot is addressed by
+/// `time_ms / precision_ms` modulo the ring size. Every slot remembers the time it was written
+/// for, so a slot left over from a previous turn of the ring reads as empty.
+pub struct TimerWheel {
+    slots_total: i32,
+    precision_ms: i32,
+    file_name: String,
+    mmap: parking_lot::Mutex<MmapMut>,
+}
+
+impl TimerWheel {
+    pub fn new(file_name: &str, slots_total: i32, precision_ms: i32) -> std::io::Result<Self> {
+        let path = Path::new(file_name);
+        if let Some(parent) = path.parent() {
+            ensure_dir_ok(parent.to_string_lossy().as_ref());
+        }
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        let wheel_length = slots_total as u64 * 2 * Slot::SIZE as u64;
+        let file_length = file.metadata()?.len();
+        if file_length != wheel_length {
+            if file_length != 0 {
+                info!(
+                    "timer wheel {} length {} does not match the expected {}, resize it",
+                    file_name, file_length, wheel_length
+                );
+            }
+            file.set_len(wheel_length)?;
+        }
+        let mmap = unsafe { MmapMut::map_mut(&file)? };
+        Ok(Self {
+            slots_total,
+            precision_ms,
+            file_name: file_name.to_string(),
+            mmap: parking_lot::Mutex::new(mmap),
+        })
+    }
+
+    pub fn get_slot(&self, time_ms: i64) -> Slot {
+        let slot = self.read_slot(self.get_slot_index(time_ms));
+        if slot.time_ms / self.precision_ms as i64 != time_ms / self.precision_ms as i64 {
+            return Slot::new(-1, -1, -1);
+        }
+        slot
+    }
+
+    pub fn put_slot(&self, time_ms: i64, first_pos: i64, last_pos: i64, num: i32, magic: i32) {
+        let offset = self.get_slot_index(time_ms) * Slot::SIZE as usize;
+        let time_ms = time_ms / self.precision_ms as i64 * self.precision_ms as i64;
+        let mut mmap = self.mmap.lock();
+        let buffer = &mut mmap[offset..offset + Slot::SIZE as usize];
+        buffer[0..8].copy_from_slice(&time_ms.to_be_bytes());
+        buffer[8..16].copy_from_slice(&first_pos.to_be_bytes());
+        buffer[16..24].copy_from_slice(&last_pos.to_be_bytes());
+        buffer[24..28].copy_from_slice(&num.to_be_bytes());
+        buffer[28..32].copy_from_slice(&magic.to_be_bytes());
+    }
+
+    /// Number of messages in the slot of `time_ms`.
+    pub fn get_num(&self, time_ms: i64) -> i32 {
+        self.get_slot(time_ms).num
+    }
+
+    /// Number of messages in all slots of the ring from `time_start_ms` on.
+    pub fn get_all_num(&self, time_start_ms: i64) -> i64 {
+        let slots = self.slots_total as i64 * 2;
+        let first_index = self.get_slot_index(time_start_ms) as i64;
+        let mut all_num = 0;
+        for i in 0..slots {
+            let slot = self.read_slot(((first_index + i) % slots) as usize);
+            let expected = time_start_ms / self.precision_ms as i64 + i;
+            if slot.time_ms / self.precision_ms as i64 == expected {
+                all_num += slot.num as i64;
+            }
+        }
+        all_num
+    }
+
+    pub fn flush(&self) {
+        if let Err(e) = self.mmap.lock().flush() {
+            error!("flush timer wheel {} failed: {}", self.file_name, e);
+        }
+    }
+
+    fn get_slot_index(&self, time_ms: i64) -> usize {
+        ((time_ms / self.precision_ms as i64) % (self.slots_total as i64 * 2)) as usize
+    }
+
+    fn read_slot(&self, index: usize) -> Slot {
+        let offset = index * Slot::SIZE as usize;
+        let mmap = self.mmap.lock();
+        let buffer = &mmap[offset..offset + Slot::SIZE as usize];
+        Slot::new_with_num_magic(
+            i64::from_be_bytes(buffer[0..8].try_into().unwrap()),
+            i64::from_be_bytes(buffer[8..16].try_into().unwrap()),
+            i64::from_be_bytes(buffer[16..24].try_into().unwrap()),
+            i32::from_be_bytes(buffer[24..28].try_into().unwrap()),
+            i32::from_be_bytes(buffer[28..32].try_into().unwrap()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn wheel(dir: &TempDir) -> TimerWheel {
+        let file_name = dir.path().join("timerwheel");
+        TimerWheel::new(file_name.to_str().unwrap(), 10, 1000).unwrap()
+    }
+
+    #[test]
+    fn slot_from_previous_round_reads_as_empty() {
+        let dir = TempDir::new().unwrap();
+        let wheel = wheel(&dir);
+        wheel.put_slot(5_400, 0, 52, 2, 0);
+
+        assert_eq!(
+            wheel.get_slot(5_000),
+            Slot::new_with_num_magic(5_000, 0, 52, 2, 0)
+        );
+        // same index of the ring one round later
+        assert_eq!(wheel.get_slot(25_000), Slot::new(-1, -1, -1));
+        assert_eq!(wheel.get_num(25_000), 0);
+    }
+
+    #[test]
+    fn slots_survive_reopen() {
+        let dir = TempDir::new().unwrap();
+        {
+            let wheel = wheel(&dir);
+            wheel.put_slot(3_000, 0, 0, 1, 0);
+            wheel.put_slot(4_000, 52, 104, 2, 0);
+            wheel.put_slot(100_000, 156, 156, 4, 0);
+            wheel.flush();
+        }
+        let wheel = wheel(&dir);
+        assert_eq!(wheel.get_slot(4_000).last_pos, 104);
+        assert_eq!(wheel.get_all_num(3_000), 3);
+        assert_eq!(wheel.get_all_num(100_000), 4);
+    }
+}
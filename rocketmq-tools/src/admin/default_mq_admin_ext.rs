@@ -25,16 +25,24 @@ use cheetah_string::CheetahString;
 use rocketmq_client_rust::admin::default_mq_admin_ext_impl::DefaultMQAdminExtImpl;
 use rocketmq_client_rust::admin::mq_admin_ext_async::MQAdminExt;
 use rocketmq_client_rust::base::client_config::ClientConfig;
+use rocketmq_client_rust::base::query_result::QueryResult;
 use rocketmq_client_rust::common::admin_tool_result::AdminToolResult;
+use rocketmq_client_rust::consumer::pop_result::PopResult;
 use rocketmq_common::common::base::plain_access_config::PlainAccessConfig;
 use rocketmq_common::common::config::TopicConfig;
 use rocketmq_common::common::message::message_enum::MessageRequestMode;
+use rocketmq_common::common::message::message_ext::MessageExt;
 use rocketmq_common::common::message::message_queue::MessageQueue;
+use rocketmq_common::common::message::MessageTrait;
+use rocketmq_common::common::mix_all;
 use rocketmq_common::common::topic::TopicValidator;
 use rocketmq_error::RocketMQResult;
+use rocketmq_error::RocketmqError;
+use rocketmq_remoting::code::response_code::ResponseCode;
 use rocketmq_remoting::protocol::admin::consume_stats::ConsumeStats;
 use rocketmq_remoting::protocol::admin::topic_stats_table::TopicStatsTable;
 use rocketmq_remoting::protocol::body::broker_body::cluster_info::ClusterInfo;
+use rocketmq_remoting::protocol::body::check_rocksdb_cqwrite_progress_response_body::CheckRocksdbCqWriteProgressResponseBody;
 use rocketmq_remoting::protocol::body::consume_message_directly_result::ConsumeMessageDirectlyResult;
 use rocketmq_remoting::protocol::body::consumer_connection::ConsumerConnection;
 use rocketmq_remoting::protocol::body::consumer_running_info::ConsumerRunningInfo;
@@ -43,6 +51,8 @@ use rocketmq_remoting::protocol::body::kv_table::KVTable;
 use rocketmq_remoting::protocol::body::producer_connection::ProducerConnection;
 use rocketmq_remoting::protocol::body::topic::topic_list::TopicList;
 use rocketmq_remoting::protocol::body::topic_info_wrapper::TopicConfigSerializeWrapper;
+use rocketmq_remoting::protocol::heartbeat::consume_type::ConsumeType;
+use rocketmq_remoting::protocol::heartbeat::message_model::MessageModel;
 use rocketmq_remoting::protocol::heartbeat::subscription_data::SubscriptionData;
 use rocketmq_remoting::protocol::route::topic_route_data::TopicRouteData;
 use rocketmq_remoting::protocol::static_topic::topic_queue_mapping_detail::TopicQueueMappingDetail;
@@ -50,6 +60,9 @@ use rocketmq_remoting::protocol::subscription::subscription_group_config::Subscr
 use rocketmq_remoting::runtime::RPCHook;
 use rocketmq_rust::ArcMut;
 
+use crate::admin::api::message_track::MessageTrack;
+use crate::admin::api::track_type::TrackType;
+
 const ADMIN_EXT_GROUP: &str = "admin_ext_group";
 
 pub struct DefaultMQAdminExt {
@@ -198,6 +211,100 @@ impl DefaultMQAdminExt {
     pub fn client_config_mut(&mut self) -> &mut ArcMut<ClientConfig> {
         &mut self.client_config
     }
+
+    /// Resolves, for every group subscribed to the message's topic, whether the message has
+    /// been consumed by that group.
+    pub async fn message_track_detail(
+        &self,
+        msg: &MessageExt,
+    ) -> RocketMQResult<Vec<MessageTrack>> {
+        let group_list = self
+            .query_topic_consume_by_who(msg.get_topic().clone())
+            .await?;
+        let mut result = Vec::with_capacity(group_list.group_list.len());
+        for group in group_list.group_list {
+            let mut track = MessageTrack {
+                consumer_group: group.to_string(),
+                track_type: Some(TrackType::Unknown),
+                exception_desc: String::new(),
+            };
+            let consumer_connection = match self
+                .examine_consumer_connection_info(group.clone(), None)
+                .await
+            {
+                Ok(consumer_connection) => consumer_connection,
+                Err(RocketmqError::MQClientBrokerError(e)) => {
+                    if e.response_code() == ResponseCode::ConsumerNotOnline as i32 {
+                        track.track_type = Some(TrackType::NotOnline);
+                    }
+                    track.exception_desc = format!(
+                        "CODE:{} DESC:{}",
+                        e.response_code(),
+                        e.error_message().map_or("", |m| m.as_str())
+                    );
+                    result.push(track);
+                    continue;
+                }
+                Err(e) => {
+                    track.exception_desc = e.to_string();
+                    result.push(track);
+                    continue;
+                }
+            };
+            match consumer_connection.get_consume_type() {
+                ConsumeType::ConsumeActively => track.track_type = Some(TrackType::Pull),
+                _ if consumer_connection.get_message_model() == MessageModel::Broadcasting => {
+                    track.track_type = Some(TrackType::ConsumeBroadcasting)
+                }
+                _ => match self.consumed(msg, &group).await {
+                    Ok(true) => {
+                        track.track_type = Some(TrackType::Consumed);
+                        let tags = msg.get_tags().unwrap_or_default();
+                        if let Some(subscription) = consumer_connection
+                            .get_subscription_table()
+                            .get(msg.get_topic())
+                        {
+                            let tags_set = &subscription.tags_set;
+                            if !tags_set.is_empty()
+                                && !tags_set.contains(&tags)
+                                && !tags_set.contains(SubscriptionData::SUB_ALL)
+                            {
+                                track.track_type = Some(TrackType::ConsumedButFiltered);
+                            }
+                        }
+                    }
+                    Ok(false) => track.track_type = Some(TrackType::NotConsumedYet),
+                    Err(e) => track.exception_desc = e.to_string(),
+                },
+            }
+            result.push(track);
+        }
+        Ok(result)
+    }
+
+    async fn consumed(&self, msg: &MessageExt, group: &CheetahString) -> RocketMQResult<bool> {
+        let consume_stats = self
+            .examine_consume_stats(group.clone(), None, None, None, None)
+            .await?;
+        let cluster_info = self.examine_broker_cluster_info().await?;
+        let store_host = msg.store_host().to_string();
+        for (mq, offset_wrapper) in consume_stats.offset_table.iter() {
+            if mq.get_topic() != msg.get_topic().as_str() || mq.get_queue_id() != msg.queue_id() {
+                continue;
+            }
+            let master_addr = cluster_info
+                .broker_addr_table
+                .as_ref()
+                .and_then(|table| table.get(mq.get_broker_name()))
+                .and_then(|broker_data| broker_data.broker_addrs().get(&mix_all::MASTER_ID));
+            if master_addr.is_some_and(|addr| addr.as_str() == store_host)
+                && offset_wrapper.get_consumer_offset() > msg.queue_offset()
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
 }
 
 impl Default for DefaultMQAdminExt {
@@ -222,7 +329,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         broker_container_addr: CheetahString,
         broker_config: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .add_broker_to_container(broker_container_addr, broker_config)
+            .await
     }
 
     async fn remove_broker_from_container(
@@ -232,7 +341,14 @@ impl MQAdminExt for DefaultMQAdminExt {
         broker_name: CheetahString,
         broker_id: u64,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .remove_broker_from_container(
+                broker_container_addr,
+                cluster_name,
+                broker_name,
+                broker_id,
+            )
+            .await
     }
 
     async fn update_broker_config(
@@ -240,14 +356,18 @@ impl MQAdminExt for DefaultMQAdminExt {
         broker_addr: CheetahString,
         properties: HashMap<CheetahString, CheetahString>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .update_broker_config(broker_addr, properties)
+            .await
     }
 
     async fn get_broker_config(
         &self,
         broker_addr: CheetahString,
     ) -> rocketmq_error::RocketMQResult<HashMap<CheetahString, CheetahString>> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .get_broker_config(broker_addr)
+            .await
     }
 
     async fn create_and_update_topic_config(
@@ -255,7 +375,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         addr: CheetahString,
         config: TopicConfig,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .create_and_update_topic_config(addr, config)
+            .await
     }
 
     async fn create_and_update_topic_config_list(
@@ -263,7 +385,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         addr: CheetahString,
         topic_config_list: Vec<TopicConfig>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .create_and_update_topic_config_list(addr, topic_config_list)
+            .await
     }
 
     async fn create_and_update_plain_access_config(
@@ -271,7 +395,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         addr: CheetahString,
         config: PlainAccessConfig,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .create_and_update_plain_access_config(addr, config)
+            .await
     }
 
     async fn delete_plain_access_config(
@@ -279,7 +405,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         addr: CheetahString,
         access_key: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .delete_plain_access_config(addr, access_key)
+            .await
     }
 
     async fn update_global_white_addr_config(
@@ -288,14 +416,18 @@ impl MQAdminExt for DefaultMQAdminExt {
         global_white_addrs: CheetahString,
         acl_file_full_path: Option<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .update_global_white_addr_config(addr, global_white_addrs, acl_file_full_path)
+            .await
     }
 
     async fn examine_broker_cluster_acl_version_info(
         &self,
         addr: CheetahString,
     ) -> rocketmq_error::RocketMQResult<CheetahString> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .examine_broker_cluster_acl_version_info(addr)
+            .await
     }
 
     async fn create_and_update_subscription_group_config(
@@ -303,7 +435,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         addr: CheetahString,
         config: SubscriptionGroupConfig,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .create_and_update_subscription_group_config(addr, config)
+            .await
     }
 
     async fn create_and_update_subscription_group_config_list(
@@ -311,7 +445,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         broker_addr: CheetahString,
         configs: Vec<SubscriptionGroupConfig>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .create_and_update_subscription_group_config_list(broker_addr, configs)
+            .await
     }
 
     async fn examine_subscription_group_config(
@@ -319,7 +455,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         addr: CheetahString,
         group: CheetahString,
     ) -> rocketmq_error::RocketMQResult<SubscriptionGroupConfig> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .examine_subscription_group_config(addr, group)
+            .await
     }
 
     async fn examine_topic_stats(
@@ -327,32 +465,40 @@ impl MQAdminExt for DefaultMQAdminExt {
         topic: CheetahString,
         broker_addr: Option<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<TopicStatsTable> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .examine_topic_stats(topic, broker_addr)
+            .await
     }
 
     async fn examine_topic_stats_concurrent(
         &self,
         topic: CheetahString,
     ) -> AdminToolResult<TopicStatsTable> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .examine_topic_stats_concurrent(topic)
+            .await
     }
 
     async fn fetch_all_topic_list(&self) -> rocketmq_error::RocketMQResult<TopicList> {
-        todo!()
+        self.default_mqadmin_ext_impl.fetch_all_topic_list().await
     }
 
     async fn fetch_topics_by_cluster(
         &self,
         cluster_name: CheetahString,
     ) -> rocketmq_error::RocketMQResult<TopicList> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .fetch_topics_by_cluster(cluster_name)
+            .await
     }
 
     async fn fetch_broker_runtime_stats(
         &self,
         broker_addr: CheetahString,
     ) -> rocketmq_error::RocketMQResult<KVTable> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .fetch_broker_runtime_stats(broker_addr)
+            .await
     }
 
     async fn examine_consume_stats(
@@ -363,11 +509,31 @@ impl MQAdminExt for DefaultMQAdminExt {
         broker_addr: Option<CheetahString>,
         timeout_millis: Option<u64>,
     ) -> rocketmq_error::RocketMQResult<ConsumeStats> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .examine_consume_stats(
+                consumer_group,
+                topic,
+                cluster_name,
+                broker_addr,
+                timeout_millis,
+            )
+            .await
+    }
+
+    async fn check_rocksdb_cq_write_progress(
+        &self,
+        broker_addr: CheetahString,
+        topic: CheetahString,
+    ) -> rocketmq_error::RocketMQResult<CheckRocksdbCqWriteProgressResponseBody> {
+        self.default_mqadmin_ext_impl
+            .check_rocksdb_cq_write_progress(broker_addr, topic)
+            .await
     }
 
     async fn examine_broker_cluster_info(&self) -> rocketmq_error::RocketMQResult<ClusterInfo> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .examine_broker_cluster_info()
+            .await
     }
 
     async fn examine_topic_route_info(
@@ -384,7 +550,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         consumer_group: CheetahString,
         broker_addr: Option<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<ConsumerConnection> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .examine_consumer_connection_info(consumer_group, broker_addr)
+            .await
     }
 
     async fn examine_producer_connection_info(
@@ -392,7 +560,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         producer_group: CheetahString,
         topic: CheetahString,
     ) -> rocketmq_error::RocketMQResult<ProducerConnection> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .examine_producer_connection_info(producer_group, topic)
+            .await
     }
 
     async fn get_name_server_address_list(&self) -> Vec<CheetahString> {
@@ -427,7 +597,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         key: CheetahString,
         value: CheetahString,
     ) {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .put_kv_config(namespace, key, value)
+            .await
     }
 
     async fn get_kv_config(
@@ -435,14 +607,18 @@ impl MQAdminExt for DefaultMQAdminExt {
         namespace: CheetahString,
         key: CheetahString,
     ) -> rocketmq_error::RocketMQResult<CheetahString> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .get_kv_config(namespace, key)
+            .await
     }
 
     async fn get_kv_list_by_namespace(
         &self,
         namespace: CheetahString,
     ) -> rocketmq_error::RocketMQResult<KVTable> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .get_kv_list_by_namespace(namespace)
+            .await
     }
 
     async fn delete_topic(
@@ -450,7 +626,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         topic_name: CheetahString,
         cluster_name: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .delete_topic(topic_name, cluster_name)
+            .await
     }
 
     async fn delete_topic_in_broker(
@@ -458,7 +636,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         addrs: HashSet<CheetahString>,
         topic: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .delete_topic_in_broker(addrs, topic)
+            .await
     }
 
     async fn delete_topic_in_name_server(
@@ -467,7 +647,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         cluster_name: Option<CheetahString>,
         topic: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .delete_topic_in_name_server(addrs, cluster_name, topic)
+            .await
     }
 
     async fn delete_subscription_group(
@@ -476,7 +658,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         group_name: CheetahString,
         remove_offset: Option<bool>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .delete_subscription_group(addr, group_name, remove_offset)
+            .await
     }
 
     async fn create_and_update_kv_config(
@@ -508,7 +692,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         timestamp: u64,
         is_force: bool,
     ) -> rocketmq_error::RocketMQResult<HashMap<MessageQueue, u64>> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .reset_offset_by_timestamp(cluster_name, topic, group, timestamp, is_force)
+            .await
     }
 
     async fn reset_offset_new(
@@ -517,7 +703,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         topic: CheetahString,
         timestamp: u64,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .reset_offset_new(consumer_group, topic, timestamp)
+            .await
     }
 
     async fn get_consume_status(
@@ -526,7 +714,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         group: CheetahString,
         client_addr: CheetahString,
     ) -> rocketmq_error::RocketMQResult<HashMap<CheetahString, HashMap<MessageQueue, u64>>> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .get_consume_status(topic, group, client_addr)
+            .await
     }
 
     async fn create_or_update_order_conf(
@@ -535,28 +725,36 @@ impl MQAdminExt for DefaultMQAdminExt {
         value: CheetahString,
         is_cluster: bool,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .create_or_update_order_conf(key, value, is_cluster)
+            .await
     }
 
     async fn query_topic_consume_by_who(
         &self,
         topic: CheetahString,
     ) -> rocketmq_error::RocketMQResult<GroupList> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .query_topic_consume_by_who(topic)
+            .await
     }
 
     async fn query_topics_by_consumer(
         &self,
         group: CheetahString,
     ) -> rocketmq_error::RocketMQResult<TopicList> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .query_topics_by_consumer(group)
+            .await
     }
 
     async fn query_topics_by_consumer_concurrent(
         &self,
         group: CheetahString,
     ) -> AdminToolResult<TopicList> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .query_topics_by_consumer_concurrent(group)
+            .await
     }
 
     async fn query_subscription(
@@ -564,7 +762,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         group: CheetahString,
         topic: CheetahString,
     ) -> rocketmq_error::RocketMQResult<SubscriptionData> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .query_subscription(group, topic)
+            .await
     }
 
     async fn clean_expired_consumer_queue(
@@ -572,7 +772,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         cluster: Option<CheetahString>,
         addr: Option<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<bool> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .clean_expired_consumer_queue(cluster, addr)
+            .await
     }
 
     async fn delete_expired_commit_log(
@@ -580,7 +782,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         cluster: Option<CheetahString>,
         addr: Option<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<bool> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .delete_expired_commit_log(cluster, addr)
+            .await
     }
 
     async fn clean_unused_topic(
@@ -588,7 +792,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         cluster: Option<CheetahString>,
         addr: Option<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<bool> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .clean_unused_topic(cluster, addr)
+            .await
     }
 
     async fn get_consumer_running_info(
@@ -596,9 +802,10 @@ impl MQAdminExt for DefaultMQAdminExt {
         consumer_group: CheetahString,
         client_id: CheetahString,
         jstack: bool,
-        metrics: Option<bool>,
     ) -> rocketmq_error::RocketMQResult<ConsumerRunningInfo> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .get_consumer_running_info(consumer_group, client_id, jstack)
+            .await
     }
 
     async fn consume_message_directly(
@@ -608,7 +815,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         topic: CheetahString,
         msg_id: CheetahString,
     ) -> rocketmq_error::RocketMQResult<ConsumeMessageDirectlyResult> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .consume_message_directly(consumer_group, client_id, topic, msg_id)
+            .await
     }
 
     async fn consume_message_directly_ext(
@@ -619,7 +828,44 @@ impl MQAdminExt for DefaultMQAdminExt {
         topic: CheetahString,
         msg_id: CheetahString,
     ) -> rocketmq_error::RocketMQResult<ConsumeMessageDirectlyResult> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .consume_message_directly_ext(cluster_name, consumer_group, client_id, topic, msg_id)
+            .await
+    }
+
+    async fn view_message(
+        &self,
+        topic: CheetahString,
+        msg_id: CheetahString,
+    ) -> rocketmq_error::RocketMQResult<MessageExt> {
+        self.default_mqadmin_ext_impl
+            .view_message(topic, msg_id)
+            .await
+    }
+
+    async fn query_message(
+        &self,
+        cluster_name: Option<CheetahString>,
+        topic: CheetahString,
+        msg_id: CheetahString,
+    ) -> rocketmq_error::RocketMQResult<MessageExt> {
+        self.default_mqadmin_ext_impl
+            .query_message(cluster_name, topic, msg_id)
+            .await
+    }
+
+    async fn query_message_by_key(
+        &self,
+        cluster_name: Option<CheetahString>,
+        topic: CheetahString,
+        key: CheetahString,
+        max_num: i32,
+        begin: i64,
+        end: i64,
+    ) -> rocketmq_error::RocketMQResult<QueryResult> {
+        self.default_mqadmin_ext_impl
+            .query_message_by_key(cluster_name, topic, key, max_num, begin, end)
+            .await
     }
 
     async fn clone_group_offset(
@@ -629,21 +875,25 @@ impl MQAdminExt for DefaultMQAdminExt {
         topic: CheetahString,
         is_offline: bool,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .clone_group_offset(src_group, dest_group, topic, is_offline)
+            .await
     }
 
     async fn get_cluster_list(
         &self,
         topic: String,
     ) -> rocketmq_error::RocketMQResult<HashSet<CheetahString>> {
-        todo!()
+        self.default_mqadmin_ext_impl.get_cluster_list(topic).await
     }
 
     async fn get_topic_cluster_list(
         &self,
         topic: String,
     ) -> rocketmq_error::RocketMQResult<HashSet<CheetahString>> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .get_topic_cluster_list(topic)
+            .await
     }
 
     async fn get_all_topic_config(
@@ -651,7 +901,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         broker_addr: CheetahString,
         timeout_millis: u64,
     ) -> rocketmq_error::RocketMQResult<TopicConfigSerializeWrapper> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .get_all_topic_config(broker_addr, timeout_millis)
+            .await
     }
 
     async fn get_user_topic_config(
@@ -660,7 +912,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         special_topic: bool,
         timeout_millis: u64,
     ) -> rocketmq_error::RocketMQResult<TopicConfigSerializeWrapper> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .get_user_topic_config(broker_addr, special_topic, timeout_millis)
+            .await
     }
 
     async fn update_consume_offset(
@@ -670,7 +924,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         mq: MessageQueue,
         offset: u64,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .update_consume_offset(broker_addr, consume_group, mq, offset)
+            .await
     }
 
     async fn update_name_server_config(
@@ -698,7 +954,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         topic: CheetahString,
         msg_id: CheetahString,
     ) -> rocketmq_error::RocketMQResult<bool> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .resume_check_half_message(topic, msg_id)
+            .await
     }
 
     async fn set_message_request_mode(
@@ -730,7 +988,15 @@ impl MQAdminExt for DefaultMQAdminExt {
         queue_id: i32,
         reset_offset: u64,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .reset_offset_by_queue_id(
+                broker_addr,
+                consumer_group,
+                topic_name,
+                queue_id,
+                reset_offset,
+            )
+            .await
     }
 
     async fn examine_topic_config(
@@ -738,7 +1004,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         addr: CheetahString,
         topic: CheetahString,
     ) -> rocketmq_error::RocketMQResult<TopicConfig> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .examine_topic_config(addr, topic)
+            .await
     }
 
     async fn create_static_topic(
@@ -749,7 +1017,34 @@ impl MQAdminExt for DefaultMQAdminExt {
         mapping_detail: TopicQueueMappingDetail,
         force: bool,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .create_static_topic(addr, default_topic, topic_config, mapping_detail, force)
+            .await
+    }
+
+    async fn peek_message(
+        &self,
+        broker_addr: CheetahString,
+        consumer_group: CheetahString,
+        topic: CheetahString,
+        queue_id: i32,
+        max_msg_nums: u32,
+    ) -> rocketmq_error::RocketMQResult<PopResult> {
+        self.default_mqadmin_ext_impl
+            .peek_message(broker_addr, consumer_group, topic, queue_id, max_msg_nums)
+            .await
+    }
+
+    async fn get_polling_info(
+        &self,
+        broker_addr: CheetahString,
+        consumer_group: CheetahString,
+        topic: CheetahString,
+        queue_id: i32,
+    ) -> rocketmq_error::RocketMQResult<i32> {
+        self.default_mqadmin_ext_impl
+            .get_polling_info(broker_addr, consumer_group, topic, queue_id)
+            .await
     }
 
     async fn reset_master_flush_offset(
@@ -757,7 +1052,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         broker_addr: CheetahString,
         master_flush_offset: u64,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .reset_master_flush_offset(broker_addr, master_flush_offset)
+            .await
     }
 
     async fn get_controller_config(
@@ -765,7 +1062,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         controller_servers: Vec<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<HashMap<CheetahString, HashMap<CheetahString, CheetahString>>>
     {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .get_controller_config(controller_servers)
+            .await
     }
 
     async fn update_controller_config(
@@ -773,7 +1072,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         properties: HashMap<CheetahString, CheetahString>,
         controllers: Vec<CheetahString>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .update_controller_config(properties, controllers)
+            .await
     }
 
     async fn clean_controller_broker_data(
@@ -784,7 +1085,15 @@ impl MQAdminExt for DefaultMQAdminExt {
         broker_controller_ids_to_clean: Option<CheetahString>,
         is_clean_living_broker: bool,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .clean_controller_broker_data(
+                controller_addr,
+                cluster_name,
+                broker_name,
+                broker_controller_ids_to_clean,
+                is_clean_living_broker,
+            )
+            .await
     }
 
     async fn update_cold_data_flow_ctr_group_config(
@@ -792,7 +1101,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         broker_addr: CheetahString,
         properties: HashMap<CheetahString, CheetahString>,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .update_cold_data_flow_ctr_group_config(broker_addr, properties)
+            .await
     }
 
     async fn remove_cold_data_flow_ctr_group_config(
@@ -800,14 +1111,18 @@ impl MQAdminExt for DefaultMQAdminExt {
         broker_addr: CheetahString,
         consumer_group: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .remove_cold_data_flow_ctr_group_config(broker_addr, consumer_group)
+            .await
     }
 
     async fn get_cold_data_flow_ctr_info(
         &self,
         broker_addr: CheetahString,
     ) -> rocketmq_error::RocketMQResult<CheetahString> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .get_cold_data_flow_ctr_info(broker_addr)
+            .await
     }
 
     async fn set_commit_log_read_ahead_mode(
@@ -815,7 +1130,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         broker_addr: CheetahString,
         mode: CheetahString,
     ) -> rocketmq_error::RocketMQResult<CheetahString> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .set_commit_log_read_ahead_mode(broker_addr, mode)
+            .await
     }
 
     async fn create_user(
@@ -825,7 +1142,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         password: CheetahString,
         user_type: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .create_user(broker_addr, username, password, user_type)
+            .await
     }
 
     async fn update_user(
@@ -836,7 +1155,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         user_type: CheetahString,
         user_status: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .update_user(broker_addr, username, password, user_type, user_status)
+            .await
     }
 
     async fn delete_user(
@@ -844,7 +1165,9 @@ impl MQAdminExt for DefaultMQAdminExt {
         broker_addr: CheetahString,
         username: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .delete_user(broker_addr, username)
+            .await
     }
 
     async fn create_acl(
@@ -856,7 +1179,16 @@ impl MQAdminExt for DefaultMQAdminExt {
         source_ips: Vec<CheetahString>,
         decision: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .create_acl(
+                broker_addr,
+                subject,
+                resources,
+                actions,
+                source_ips,
+                decision,
+            )
+            .await
     }
 
     async fn update_acl(
@@ -868,7 +1200,16 @@ impl MQAdminExt for DefaultMQAdminExt {
         source_ips: Vec<CheetahString>,
         decision: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .update_acl(
+                broker_addr,
+                subject,
+                resources,
+                actions,
+                source_ips,
+                decision,
+            )
+            .await
     }
 
     async fn delete_acl(
@@ -877,6 +1218,8 @@ impl MQAdminExt for DefaultMQAdminExt {
         subject: CheetahString,
         resource: CheetahString,
     ) -> rocketmq_error::RocketMQResult<()> {
-        todo!()
+        self.default_mqadmin_ext_impl
+            .delete_acl(broker_addr, subject, resource)
+            .await
     }
 }
@@ -14,7 +14,11 @@
  * See the License for the specific language governing permissions and
  * limitations under the License.
  */
+mod command_util;
+mod consumer_commands;
+mod message_commands;
 mod namesrv_commands;
+mod queue_commands;
 mod topic_commands;
 
 use std::sync::Arc;
@@ -66,6 +70,18 @@ pub enum Commands {
     #[command(about = "Topic commands")]
     Topic(topic_commands::TopicCommands),
 
+    #[command(subcommand)]
+    #[command(about = "Consumer commands")]
+    Consumer(consumer_commands::ConsumerCommands),
+
+    #[command(subcommand)]
+    #[command(about = "Message commands")]
+    Message(message_commands::MessageCommands),
+
+    #[command(subcommand)]
+    #[command(about = "Queue commands")]
+    Queue(queue_commands::QueueCommands),
+
     #[command(about = "Category commands show")]
     Show(ClassificationTablePrint),
 }
@@ -75,6 +91,9 @@ impl CommandExecute for Commands {
         match self {
             Commands::NameServer(value) => value.execute(rpc_hook).await,
             Commands::Topic(value) => value.execute(rpc_hook).await,
+            Commands::Consumer(value) => value.execute(rpc_hook).await,
+            Commands::Message(value) => value.execute(rpc_hook).await,
+            Commands::Queue(value) => value.execute(rpc_hook).await,
             Commands::Show(value) => value.execute(rpc_hook).await,
         }
     }
@@ -104,11 +123,66 @@ impl CommandExecute for ClassificationTablePrint {
                 command: "allocateMQ",
                 remark: "Allocate MQ.",
             },
+            Command {
+                category: "Topic",
+                command: "deleteTopic",
+                remark: "Delete topic from broker and NameServer.",
+            },
+            Command {
+                category: "Topic",
+                command: "topicList",
+                remark: "Fetch all topic list from name server.",
+            },
+            Command {
+                category: "Topic",
+                command: "topicRoute",
+                remark: "Examine topic route info.",
+            },
+            Command {
+                category: "Topic",
+                command: "topicStatus",
+                remark: "Examine topic Status info.",
+            },
+            Command {
+                category: "Topic",
+                command: "updateTopic",
+                remark: "Update or create topic.",
+            },
             Command {
                 category: "NameServer",
                 command: "getNamesrvConfig",
                 remark: "Get configs of name server.",
             },
+            Command {
+                category: "Consumer",
+                command: "consumerProgress",
+                remark: "Query consumers's progress, speed.",
+            },
+            Command {
+                category: "Consumer",
+                command: "deleteSubGroup",
+                remark: "Delete subscription group from broker.",
+            },
+            Command {
+                category: "Consumer",
+                command: "pollingInfo",
+                remark: "Query the number of POP long-polling requests parked on a broker.",
+            },
+            Command {
+                category: "Consumer",
+                command: "updateSubGroup",
+                remark: "Update or create subscription group.",
+            },
+            Command {
+                category: "Message",
+                command: "peekMessage",
+                remark: "Peek messages at the head of a queue without consuming them.",
+            },
+            Command {
+                category: "Queue",
+                command: "checkRocksdbCqWriteProgress",
+                remark: "Check the progress of the RocksDB consume queue double write.",
+            },
         ];
         let mut table = Table::new(commands);
         table.with(Style::extended());
@@ -0,0 +1,130 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Helpers shared by the admin sub commands, mirroring `CommandUtil` of the Java mqadmin.
+
+use std::collections::HashSet;
+
+use cheetah_string::CheetahString;
+use rocketmq_client_rust::admin::mq_admin_ext_async::MQAdminExt;
+use rocketmq_common::common::mix_all;
+use rocketmq_error::RocketMQResult;
+use rocketmq_error::RocketmqError;
+
+use crate::admin::default_mq_admin_ext::DefaultMQAdminExt;
+
+const ERROR_MESSAGE: &str =
+    "Make sure the specified clusterName exists or the name server connected to is correct.";
+
+/// Returns the master broker addresses of `cluster_name`.
+pub(crate) async fn fetch_master_addr_by_cluster_name(
+    admin_ext: &DefaultMQAdminExt,
+    cluster_name: &str,
+) -> RocketMQResult<HashSet<CheetahString>> {
+    let cluster_info = admin_ext.examine_broker_cluster_info().await?;
+    let broker_addr_table = cluster_info.broker_addr_table.unwrap_or_default();
+    let Some(broker_names) = cluster_info
+        .cluster_addr_table
+        .as_ref()
+        .and_then(|table| table.get(cluster_name))
+    else {
+        print!("[error] {ERROR_MESSAGE}");
+        return Ok(HashSet::new());
+    };
+    Ok(broker_names
+        .iter()
+        .filter_map(|broker_name| broker_addr_table.get(broker_name))
+        .filter_map(|broker_data| broker_data.broker_addrs().get(&mix_all::MASTER_ID))
+        .cloned()
+        .collect())
+}
+
+/// Returns the master and slave broker addresses of `cluster_name`.
+pub(crate) async fn fetch_master_and_slave_addr_by_cluster_name(
+    admin_ext: &DefaultMQAdminExt,
+    cluster_name: &str,
+) -> RocketMQResult<HashSet<CheetahString>> {
+    let cluster_info = admin_ext.examine_broker_cluster_info().await?;
+    let broker_addr_table = cluster_info.broker_addr_table.unwrap_or_default();
+    let Some(broker_names) = cluster_info
+        .cluster_addr_table
+        .as_ref()
+        .and_then(|table| table.get(cluster_name))
+    else {
+        print!("[error] {ERROR_MESSAGE}");
+        return Ok(HashSet::new());
+    };
+    Ok(broker_names
+        .iter()
+        .filter_map(|broker_name| broker_addr_table.get(broker_name))
+        .flat_map(|broker_data| broker_data.broker_addrs().values().cloned())
+        .collect())
+}
+
+/// Returns the broker names of `cluster_name`.
+pub(crate) async fn fetch_broker_name_by_cluster_name(
+    admin_ext: &DefaultMQAdminExt,
+    cluster_name: &str,
+) -> RocketMQResult<HashSet<CheetahString>> {
+    let cluster_info = admin_ext.examine_broker_cluster_info().await?;
+    match cluster_info
+        .cluster_addr_table
+        .and_then(|mut table| table.remove(cluster_name))
+    {
+        Some(broker_names) if !broker_names.is_empty() => Ok(broker_names),
+        _ => Err(RocketmqError::IllegalArgument(ERROR_MESSAGE.to_string())),
+    }
+}
+
+/// Returns the name of the broker listening on `addr`.
+pub(crate) async fn fetch_broker_name_by_addr(
+    admin_ext: &DefaultMQAdminExt,
+    addr: &str,
+) -> RocketMQResult<CheetahString> {
+    let cluster_info = admin_ext.examine_broker_cluster_info().await?;
+    cluster_info
+        .broker_addr_table
+        .unwrap_or_default()
+        .into_values()
+        .find(|broker_data| {
+            broker_data
+                .broker_addrs()
+                .values()
+                .any(|broker_addr| broker_addr == addr)
+        })
+        .map(|broker_data| broker_data.broker_name().clone())
+        .ok_or_else(|| RocketmqError::IllegalArgument(ERROR_MESSAGE.to_string()))
+}
+
+/// Truncates `value` to at most `size` characters, like `UtilAll.frontStringAtLeast`.
+pub(crate) fn front_string_at_least(value: &str, size: usize) -> &str {
+    match value.char_indices().nth(size) {
+        Some((index, _)) => &value[..index],
+        None => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn front_string_at_least_truncates_long_values() {
+        assert_eq!(front_string_at_least("broker-a", 32), "broker-a");
+        assert_eq!(front_string_at_least("broker-a", 6), "broker");
+    }
+}
@@ -0,0 +1,84 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+mod consumer_progress_sub_command;
+mod delete_sub_group_sub_command;
+mod polling_info_sub_command;
+mod reset_offset_by_time_sub_command;
+mod update_sub_group_sub_command;
+
+use std::sync::Arc;
+
+use clap::Subcommand;
+use rocketmq_error::RocketMQResult;
+use rocketmq_remoting::runtime::RPCHook;
+
+use crate::commands::consumer_commands::consumer_progress_sub_command::ConsumerProgressSubCommand;
+use crate::commands::consumer_commands::delete_sub_group_sub_command::DeleteSubGroupSubCommand;
+use crate::commands::consumer_commands::polling_info_sub_command::PollingInfoSubCommand;
+use crate::commands::consumer_commands::reset_offset_by_time_sub_command::ResetOffsetByTimeSubCommand;
+use crate::commands::consumer_commands::update_sub_group_sub_command::UpdateSubGroupSubCommand;
+use crate::commands::CommandExecute;
+
+#[derive(Subcommand)]
+pub enum ConsumerCommands {
+    #[command(
+        name = "consumerProgress",
+        about = "Query consumers's progress, speed.",
+        long_about = None,
+    )]
+    ConsumerProgress(ConsumerProgressSubCommand),
+
+    #[command(
+        name = "deleteSubGroup",
+        about = "Delete subscription group from broker.",
+        long_about = None,
+    )]
+    DeleteSubGroup(DeleteSubGroupSubCommand),
+
+    #[command(
+        name = "pollingInfo",
+        about = "Query the number of POP long-polling requests parked on a broker.",
+        long_about = None,
+    )]
+    PollingInfo(PollingInfoSubCommand),
+
+    #[command(
+        name = "resetOffsetByTime",
+        about = "Reset consumer offset by timestamp(without client restart).",
+        long_about = None,
+    )]
+    ResetOffsetByTime(ResetOffsetByTimeSubCommand),
+
+    #[command(
+        name = "updateSubGroup",
+        about = "Update or create subscription group.",
+        long_about = None,
+    )]
+    UpdateSubGroup(UpdateSubGroupSubCommand),
+}
+
+impl CommandExecute for ConsumerCommands {
+    async fn execute(&self, rpc_hook: Option<Arc<dyn RPCHook>>) -> RocketMQResult<()> {
+        match self {
+            ConsumerCommands::ConsumerProgress(value) => value.execute(rpc_hook).await,
+            ConsumerCommands::DeleteSubGroup(value) => value.execute(rpc_hook).await,
+            ConsumerCommands::PollingInfo(value) => value.execute(rpc_hook).await,
+            ConsumerCommands::ResetOffsetByTime(value) => value.execute(rpc_hook).await,
+            ConsumerCommands::UpdateSubGroup(value) => value.execute(rpc_hook).await,
+        }
+    }
+}
@@ -0,0 +1,294 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use clap::Parser;
+use rocketmq_client_rust::admin::mq_admin_ext_async::MQAdminExt;
+use rocketmq_common::common::key_builder::KeyBuilder;
+use rocketmq_common::common::mix_all;
+use rocketmq_common::common::mq_version::RocketMqVersion;
+use rocketmq_common::utils::util_all::time_millis_to_human_string2;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_error::RocketMQResult;
+use rocketmq_error::RocketmqError;
+use rocketmq_remoting::protocol::heartbeat::consume_type::ConsumeType;
+use rocketmq_remoting::protocol::heartbeat::message_model::MessageModel;
+use rocketmq_remoting::runtime::RPCHook;
+
+use crate::admin::default_mq_admin_ext::DefaultMQAdminExt;
+use crate::commands::command_util::front_string_at_least;
+use crate::commands::CommandExecute;
+use crate::commands::CommonArgs;
+
+#[derive(Debug, Clone, Parser)]
+pub struct ConsumerProgressSubCommand {
+    #[command(flatten)]
+    common_args: CommonArgs,
+
+    #[arg(short = 'g', long = "groupName", help = "consumer group name")]
+    group_name: Option<String>,
+
+    #[arg(short = 't', long = "topicName", help = "topic name")]
+    topic_name: Option<String>,
+
+    #[arg(
+        short = 'c',
+        long = "cluster",
+        help = "Cluster name or lmq parent topic"
+    )]
+    cluster: Option<String>,
+}
+
+/// One row of the group summary printed when no consumer group is given.
+#[derive(Debug, Default)]
+struct GroupConsumeInfo {
+    group: String,
+    version: i32,
+    count: usize,
+    consume_type: Option<ConsumeType>,
+    message_model: Option<MessageModel>,
+    consume_tps: i64,
+    diff_total: i64,
+}
+
+impl GroupConsumeInfo {
+    fn consume_type_desc(&self) -> &'static str {
+        match (self.count, self.consume_type) {
+            (0, _) => "",
+            (_, Some(ConsumeType::ConsumeActively)) => "PULL",
+            (_, Some(ConsumeType::ConsumePop)) => "POP",
+            _ => "PUSH",
+        }
+    }
+
+    fn message_model_desc(&self) -> String {
+        match (self.count, self.consume_type, self.message_model) {
+            (count, Some(ConsumeType::ConsumePassively), Some(message_model)) if count > 0 => {
+                message_model.to_string()
+            }
+            _ => String::new(),
+        }
+    }
+
+    fn version_desc(&self) -> String {
+        if self.count == 0 {
+            return "OFFLINE".to_string();
+        }
+        RocketMqVersion::try_from(self.version)
+            .map(|version| version.to_string())
+            .unwrap_or_else(|_| self.version.to_string())
+    }
+
+    /// Online groups with more clients come first, then the groups with the largest backlog.
+    fn compare(&self, other: &Self) -> Ordering {
+        other
+            .count
+            .cmp(&self.count)
+            .then_with(|| other.diff_total.cmp(&self.diff_total))
+    }
+}
+
+impl ConsumerProgressSubCommand {
+    async fn print_group_progress(
+        &self,
+        admin_ext: &DefaultMQAdminExt,
+        consumer_group: &str,
+    ) -> RocketMQResult<()> {
+        let consume_stats = admin_ext
+            .examine_consume_stats(
+                consumer_group.into(),
+                self.topic_name.as_deref().map(|topic| topic.trim().into()),
+                self.cluster.as_deref().map(|cluster| cluster.trim().into()),
+                None,
+                None,
+            )
+            .await?;
+        let mut mq_list = consume_stats.offset_table.keys().collect::<Vec<_>>();
+        mq_list.sort();
+
+        println!(
+            "{:<64}  {:<32}  {:<4}  {:<20}  {:<20}  {:<20} {:<20} #LastTime",
+            "#Topic",
+            "#Broker Name",
+            "#QID",
+            "#Broker Offset",
+            "#Consumer Offset",
+            "#Diff",
+            "#Inflight"
+        );
+        let mut diff_total = 0;
+        let mut inflight_total = 0;
+        for mq in mq_list {
+            let offset_wrapper = &consume_stats.offset_table[mq];
+            let diff = offset_wrapper.get_broker_offset() - offset_wrapper.get_consumer_offset();
+            let inflight = offset_wrapper.get_pull_offset() - offset_wrapper.get_consumer_offset();
+            diff_total += diff;
+            inflight_total += inflight;
+            let last_time = if offset_wrapper.get_last_timestamp() == 0 {
+                "N/A".to_string()
+            } else {
+                // yyyy-MM-dd HH:mm:ss
+                let human_time = time_millis_to_human_string2(offset_wrapper.get_last_timestamp());
+                human_time.split(',').next().unwrap_or_default().to_string()
+            };
+            println!(
+                "{:<64}  {:<32}  {:<4}  {:<20}  {:<20}  {:<20} {:<20} {}",
+                front_string_at_least(mq.get_topic(), 64),
+                front_string_at_least(mq.get_broker_name(), 32),
+                mq.get_queue_id(),
+                offset_wrapper.get_broker_offset(),
+                offset_wrapper.get_consumer_offset(),
+                diff,
+                inflight,
+                last_time
+            );
+        }
+        println!();
+        println!("Consume TPS: {:.2}", consume_stats.consume_tps);
+        println!("Consume Diff Total: {diff_total}");
+        println!("Consume Inflight Total: {inflight_total}");
+        Ok(())
+    }
+
+    async fn print_all_groups(&self, admin_ext: &DefaultMQAdminExt) -> RocketMQResult<()> {
+        println!(
+            "{:<64}  {:<6}  {:<24} {:<5}  {:<14}  {:<7}  #Diff Total",
+            "#Group", "#Count", "#Version", "#Type", "#Model", "#TPS"
+        );
+        let topic_list = admin_ext.fetch_all_topic_list().await?;
+        let mut group_consume_infos = Vec::new();
+        for topic in &topic_list.topic_list {
+            if !topic.starts_with(mix_all::RETRY_GROUP_TOPIC_PREFIX) {
+                continue;
+            }
+            let consumer_group = KeyBuilder::parse_group(topic);
+            let mut group_consume_info = GroupConsumeInfo {
+                group: consumer_group.clone(),
+                ..Default::default()
+            };
+            // Offline groups or groups without offsets are still listed, like mqadmin does.
+            if let Ok(consume_stats) = admin_ext
+                .examine_consume_stats(
+                    consumer_group.as_str().into(),
+                    None,
+                    self.cluster.as_deref().map(|cluster| cluster.trim().into()),
+                    None,
+                    None,
+                )
+                .await
+            {
+                group_consume_info.consume_tps = consume_stats.consume_tps as i64;
+                group_consume_info.diff_total = consume_stats.compute_total_diff();
+            }
+            if let Ok(consumer_connection) = admin_ext
+                .examine_consumer_connection_info(consumer_group.as_str().into(), None)
+                .await
+            {
+                group_consume_info.count = consumer_connection.get_connection_set().len();
+                group_consume_info.message_model = Some(consumer_connection.get_message_model());
+                group_consume_info.consume_type = Some(consumer_connection.get_consume_type());
+                group_consume_info.version = consumer_connection.compute_min_version();
+            }
+            group_consume_infos.push(group_consume_info);
+        }
+        group_consume_infos.sort_by(GroupConsumeInfo::compare);
+        for info in group_consume_infos {
+            println!(
+                "{:<64}  {:<6}  {:<24} {:<5}  {:<14}  {:<7}  {}",
+                front_string_at_least(&info.group, 64),
+                info.count,
+                info.version_desc(),
+                info.consume_type_desc(),
+                info.message_model_desc(),
+                info.consume_tps,
+                info.diff_total
+            );
+        }
+        Ok(())
+    }
+}
+
+impl CommandExecute for ConsumerProgressSubCommand {
+    async fn execute(&self, _rpc_hook: Option<Arc<dyn RPCHook>>) -> RocketMQResult<()> {
+        let mut default_mqadmin_ext = DefaultMQAdminExt::new();
+        default_mqadmin_ext
+            .client_config_mut()
+            .set_instance_name(get_current_millis().to_string().into());
+
+        let operation_result = async {
+            if let Some(addr) = &self.common_args.namesrv_addr {
+                default_mqadmin_ext.set_namesrv_addr(addr.trim());
+            }
+            MQAdminExt::start(&mut default_mqadmin_ext)
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand("ConsumerProgressSubCommand".into(), e.to_string())
+                })?;
+
+            match &self.group_name {
+                Some(group_name) => {
+                    self.print_group_progress(&default_mqadmin_ext, group_name.trim())
+                        .await
+                }
+                None => self.print_all_groups(&default_mqadmin_ext).await,
+            }
+            .map_err(|e| {
+                RocketmqError::SubCommand("ConsumerProgressSubCommand".into(), e.to_string())
+            })
+        }
+        .await;
+        MQAdminExt::shutdown(&mut default_mqadmin_ext).await;
+        operation_result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_consume_info_sorts_online_groups_first() {
+        let mut infos = [
+            GroupConsumeInfo {
+                group: "offline".to_string(),
+                diff_total: 100,
+                ..Default::default()
+            },
+            GroupConsumeInfo {
+                group: "small".to_string(),
+                count: 1,
+                diff_total: 1,
+                ..Default::default()
+            },
+            GroupConsumeInfo {
+                group: "large".to_string(),
+                count: 1,
+                diff_total: 10,
+                ..Default::default()
+            },
+        ];
+        infos.sort_by(GroupConsumeInfo::compare);
+        let groups = infos
+            .iter()
+            .map(|info| info.group.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(groups, ["large", "small", "offline"]);
+        assert_eq!(infos[2].version_desc(), "OFFLINE");
+        assert_eq!(infos[2].consume_type_desc(), "");
+    }
+}
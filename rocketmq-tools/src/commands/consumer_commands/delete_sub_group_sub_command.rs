@@ -0,0 +1,154 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::sync::Arc;
+
+use clap::ArgAction;
+use clap::Parser;
+use rocketmq_client_rust::admin::mq_admin_ext_async::MQAdminExt;
+use rocketmq_common::common::mix_all;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_error::RocketMQResult;
+use rocketmq_error::RocketmqError;
+use rocketmq_remoting::runtime::RPCHook;
+
+use crate::admin::default_mq_admin_ext::DefaultMQAdminExt;
+use crate::commands::command_util;
+use crate::commands::topic_commands::delete_topic_sub_command::DeleteTopicSubCommand;
+use crate::commands::CommandExecute;
+use crate::commands::CommonArgs;
+
+#[derive(Debug, Clone, Parser)]
+pub struct DeleteSubGroupSubCommand {
+    #[command(flatten)]
+    common_args: CommonArgs,
+
+    #[arg(
+        short = 'b',
+        long = "brokerAddr",
+        required_unless_present = "cluster_name",
+        conflicts_with = "cluster_name",
+        help = "delete subscription group from which broker"
+    )]
+    broker_addr: Option<String>,
+
+    #[arg(
+        short = 'c',
+        long = "clusterName",
+        help = "delete subscription group from which cluster"
+    )]
+    cluster_name: Option<String>,
+
+    #[arg(
+        short = 'g',
+        long = "groupName",
+        required = true,
+        help = "subscription group name"
+    )]
+    group_name: String,
+
+    #[arg(
+        short = 'r',
+        long = "removeOffset",
+        action = ArgAction::Set,
+        default_value_t = false,
+        help = "remove offset"
+    )]
+    remove_offset: bool,
+}
+
+impl CommandExecute for DeleteSubGroupSubCommand {
+    async fn execute(&self, _rpc_hook: Option<Arc<dyn RPCHook>>) -> RocketMQResult<()> {
+        let mut default_mqadmin_ext = DefaultMQAdminExt::new();
+        default_mqadmin_ext
+            .client_config_mut()
+            .set_instance_name(get_current_millis().to_string().into());
+
+        let operation_result = async {
+            if let Some(addr) = &self.common_args.namesrv_addr {
+                default_mqadmin_ext.set_namesrv_addr(addr.trim());
+            }
+            MQAdminExt::start(&mut default_mqadmin_ext)
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand("DeleteSubGroupSubCommand".into(), e.to_string())
+                })?;
+
+            let group_name = self.group_name.trim();
+            if let Some(broker_addr) = &self.broker_addr {
+                let addr = broker_addr.trim();
+                default_mqadmin_ext
+                    .delete_subscription_group(
+                        addr.into(),
+                        group_name.into(),
+                        Some(self.remove_offset),
+                    )
+                    .await
+                    .map_err(|e| {
+                        RocketmqError::SubCommand("DeleteSubGroupSubCommand".into(), e.to_string())
+                    })?;
+                println!("delete subscription group [{group_name}] from broker [{addr}] success.");
+            } else if let Some(cluster_name) = &self.cluster_name {
+                let cluster_name = cluster_name.trim();
+                let master_set = command_util::fetch_master_addr_by_cluster_name(
+                    &default_mqadmin_ext,
+                    cluster_name,
+                )
+                .await?;
+                for master in master_set {
+                    default_mqadmin_ext
+                        .delete_subscription_group(
+                            master.clone(),
+                            group_name.into(),
+                            Some(self.remove_offset),
+                        )
+                        .await
+                        .map_err(|e| {
+                            RocketmqError::SubCommand(
+                                "DeleteSubGroupSubCommand".into(),
+                                e.to_string(),
+                            )
+                        })?;
+                    println!(
+                        "delete subscription group [{group_name}] from broker [{master}] in \
+                         cluster [{cluster_name}] success."
+                    );
+                }
+
+                // The retry and dead letter topics of the group are removed on a best-effort basis.
+                for topic in [
+                    mix_all::get_retry_topic(group_name),
+                    mix_all::get_dlq_topic(group_name),
+                ] {
+                    if DeleteTopicSubCommand::delete_topic(
+                        &default_mqadmin_ext,
+                        cluster_name,
+                        &topic,
+                    )
+                    .await
+                    .is_err()
+                    {
+                        break;
+                    }
+                }
+            }
+            Ok(())
+        }
+        .await;
+        MQAdminExt::shutdown(&mut default_mqadmin_ext).await;
+        operation_result
+    }
+}
@@ -0,0 +1,107 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::sync::Arc;
+
+use clap::Parser;
+use rocketmq_client_rust::admin::mq_admin_ext_async::MQAdminExt;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_error::RocketMQResult;
+use rocketmq_error::RocketmqError;
+use rocketmq_remoting::runtime::RPCHook;
+
+use crate::admin::default_mq_admin_ext::DefaultMQAdminExt;
+use crate::commands::CommandExecute;
+use crate::commands::CommonArgs;
+
+#[derive(Debug, Clone, Parser)]
+pub struct PollingInfoSubCommand {
+    #[command(flatten)]
+    common_args: CommonArgs,
+
+    #[arg(
+        short = 'b',
+        long = "brokerAddr",
+        required = true,
+        help = "broker address, eg: 127.0.0.1:10911"
+    )]
+    broker_addr: String,
+
+    #[arg(
+        short = 'g',
+        long = "consumerGroup",
+        required = true,
+        help = "consumer group name"
+    )]
+    consumer_group: String,
+
+    #[arg(short = 't', long = "topic", required = true, help = "topic name")]
+    topic: String,
+
+    #[arg(
+        short = 'q',
+        long = "queueId",
+        default_value_t = -1,
+        allow_negative_numbers = true,
+        help = "queue id, -1 queries the requests that pop from all queues"
+    )]
+    queue_id: i32,
+}
+
+impl CommandExecute for PollingInfoSubCommand {
+    async fn execute(&self, _rpc_hook: Option<Arc<dyn RPCHook>>) -> RocketMQResult<()> {
+        let mut default_mqadmin_ext = DefaultMQAdminExt::new();
+        default_mqadmin_ext
+            .client_config_mut()
+            .set_instance_name(get_current_millis().to_string().into());
+
+        let operation_result = async {
+            if let Some(addr) = &self.common_args.namesrv_addr {
+                default_mqadmin_ext.set_namesrv_addr(addr.trim());
+            }
+            MQAdminExt::start(&mut default_mqadmin_ext)
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand("PollingInfoSubCommand".into(), e.to_string())
+                })?;
+
+            let polling_num = default_mqadmin_ext
+                .get_polling_info(
+                    self.broker_addr.trim().into(),
+                    self.consumer_group.trim().into(),
+                    self.topic.trim().into(),
+                    self.queue_id,
+                )
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand("PollingInfoSubCommand".into(), e.to_string())
+                })?;
+            println!(
+                "{} polling request(s) of consumer group[{}] waiting on topic[{}] queue[{}] of \
+                 broker[{}]",
+                polling_num,
+                self.consumer_group.trim(),
+                self.topic.trim(),
+                self.queue_id,
+                self.broker_addr.trim()
+            );
+            Ok(())
+        }
+        .await;
+        MQAdminExt::shutdown(&mut default_mqadmin_ext).await;
+        operation_result
+    }
+}
@@ -0,0 +1,186 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::sync::Arc;
+
+use chrono::Local;
+use chrono::NaiveDateTime;
+use chrono::TimeZone;
+use clap::Parser;
+use rocketmq_client_rust::admin::mq_admin_ext_async::MQAdminExt;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_error::RocketMQResult;
+use rocketmq_error::RocketmqError;
+use rocketmq_remoting::runtime::RPCHook;
+
+use crate::admin::default_mq_admin_ext::DefaultMQAdminExt;
+use crate::commands::CommandExecute;
+use crate::commands::CommonArgs;
+
+const TIMESTAMP_PATTERN: &str = "%Y-%m-%d#%H:%M:%S:%3f";
+
+#[derive(Debug, Clone, Parser)]
+pub struct ResetOffsetByTimeSubCommand {
+    #[command(flatten)]
+    common_args: CommonArgs,
+
+    #[arg(
+        short = 'g',
+        long = "group",
+        required = true,
+        help = "set the consumer group"
+    )]
+    group: String,
+
+    #[arg(short = 't', long = "topic", required = true, help = "set the topic")]
+    topic: String,
+
+    #[arg(
+        short = 's',
+        long = "timestamp",
+        required = true,
+        help = "set the timestamp[now|currentTimeMillis|yyyy-MM-dd#HH:mm:ss:SSS]"
+    )]
+    timestamp: String,
+
+    #[arg(
+        short = 'f',
+        long = "force",
+        default_value_t = true,
+        action = clap::ArgAction::Set,
+        help = "set the force rollback by timestamp switch[true|false]"
+    )]
+    force: bool,
+
+    #[arg(short = 'c', long = "cluster", required = false, help = "Cluster name")]
+    cluster: Option<String>,
+}
+
+impl ResetOffsetByTimeSubCommand {
+    fn parse_timestamp(timestamp: &str) -> Option<u64> {
+        if timestamp == "now" {
+            return Some(get_current_millis());
+        }
+        if let Ok(millis) = timestamp.parse::<u64>() {
+            return Some(millis);
+        }
+        let date = NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_PATTERN).ok()?;
+        let millis = Local
+            .from_local_datetime(&date)
+            .single()?
+            .timestamp_millis();
+        u64::try_from(millis).ok()
+    }
+}
+
+impl CommandExecute for ResetOffsetByTimeSubCommand {
+    async fn execute(&self, _rpc_hook: Option<Arc<dyn RPCHook>>) -> RocketMQResult<()> {
+        let Some(timestamp) = Self::parse_timestamp(self.timestamp.trim()) else {
+            return Err(RocketmqError::SubCommand(
+                "ResetOffsetByTimeSubCommand".into(),
+                format!("illegal timestamp: {}", self.timestamp.trim()),
+            ));
+        };
+
+        let mut default_mqadmin_ext = DefaultMQAdminExt::new();
+        default_mqadmin_ext
+            .client_config_mut()
+            .set_instance_name(get_current_millis().to_string().into());
+
+        let operation_result = async {
+            if let Some(addr) = &self.common_args.namesrv_addr {
+                default_mqadmin_ext.set_namesrv_addr(addr.trim());
+            }
+            MQAdminExt::start(&mut default_mqadmin_ext)
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand("ResetOffsetByTimeSubCommand".into(), e.to_string())
+                })?;
+
+            let offset_table = default_mqadmin_ext
+                .reset_offset_by_timestamp(
+                    self.cluster.as_deref().map(|cluster| cluster.trim().into()),
+                    self.topic.trim().into(),
+                    self.group.trim().into(),
+                    timestamp,
+                    self.force,
+                )
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand("ResetOffsetByTimeSubCommand".into(), e.to_string())
+                })?;
+
+            println!(
+                "rollback consumer offset by specified group[{}], topic[{}], force[{}], \
+                 timestamp(string)[{}], timestamp(long)[{}]",
+                self.group.trim(),
+                self.topic.trim(),
+                self.force,
+                self.timestamp.trim(),
+                timestamp
+            );
+            println!(
+                "{:<40}  {:<40}  {:<40}",
+                "#brokerName", "#queueId", "#offset"
+            );
+            let mut offsets = offset_table.into_iter().collect::<Vec<_>>();
+            offsets.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (mq, offset) in offsets {
+                println!(
+                    "{:<40}  {:<40}  {:<40}",
+                    mq.get_broker_name(),
+                    mq.get_queue_id(),
+                    offset
+                );
+            }
+            Ok(())
+        }
+        .await;
+        MQAdminExt::shutdown(&mut default_mqadmin_ext).await;
+        operation_result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_timestamp_accepts_millis_and_now() {
+        assert_eq!(
+            ResetOffsetByTimeSubCommand::parse_timestamp("1700000000000"),
+            Some(1_700_000_000_000)
+        );
+        assert!(ResetOffsetByTimeSubCommand::parse_timestamp("now").is_some());
+    }
+
+    #[test]
+    fn parse_timestamp_accepts_date_pattern() {
+        let expected = Local
+            .with_ymd_and_hms(2024, 5, 1, 12, 30, 15)
+            .unwrap()
+            .timestamp_millis() as u64
+            + 123;
+        assert_eq!(
+            ResetOffsetByTimeSubCommand::parse_timestamp("2024-05-01#12:30:15:123"),
+            Some(expected)
+        );
+        assert_eq!(
+            ResetOffsetByTimeSubCommand::parse_timestamp("yesterday"),
+            None
+        );
+    }
+}
@@ -0,0 +1,213 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use cheetah_string::CheetahString;
+use clap::ArgAction;
+use clap::Parser;
+use rocketmq_client_rust::admin::mq_admin_ext_async::MQAdminExt;
+use rocketmq_common::common::attribute::attribute_parser::AttributeParser;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_error::RocketMQResult;
+use rocketmq_error::RocketmqError;
+use rocketmq_remoting::protocol::subscription::subscription_group_config::SubscriptionGroupConfig;
+use rocketmq_remoting::runtime::RPCHook;
+
+use crate::admin::default_mq_admin_ext::DefaultMQAdminExt;
+use crate::commands::command_util;
+use crate::commands::CommandExecute;
+use crate::commands::CommonArgs;
+
+#[derive(Debug, Clone, Parser)]
+pub struct UpdateSubGroupSubCommand {
+    #[command(flatten)]
+    common_args: CommonArgs,
+
+    #[arg(
+        short = 'b',
+        long = "brokerAddr",
+        required_unless_present = "cluster_name",
+        conflicts_with = "cluster_name",
+        help = "create subscription group to which broker"
+    )]
+    broker_addr: Option<String>,
+
+    #[arg(
+        short = 'c',
+        long = "clusterName",
+        help = "create subscription group to which cluster"
+    )]
+    cluster_name: Option<String>,
+
+    #[arg(
+        short = 'g',
+        long = "groupName",
+        required = true,
+        help = "consumer group name"
+    )]
+    group_name: String,
+
+    #[arg(
+        short = 's',
+        long = "consumeEnable",
+        action = ArgAction::Set,
+        help = "consume enable"
+    )]
+    consume_enable: Option<bool>,
+
+    #[arg(
+        short = 'm',
+        long = "consumeFromMinEnable",
+        action = ArgAction::Set,
+        help = "from min offset"
+    )]
+    consume_from_min_enable: Option<bool>,
+
+    #[arg(
+        short = 'd',
+        long = "consumeBroadcastEnable",
+        action = ArgAction::Set,
+        help = "broadcast"
+    )]
+    consume_broadcast_enable: Option<bool>,
+
+    #[arg(
+        short = 'o',
+        long = "consumeMessageOrderly",
+        action = ArgAction::Set,
+        help = "consume message orderly"
+    )]
+    consume_message_orderly: Option<bool>,
+
+    #[arg(short = 'q', long = "retryQueueNums", help = "retry queue nums")]
+    retry_queue_nums: Option<i32>,
+
+    #[arg(short = 'r', long = "retryMaxTimes", help = "retry max times")]
+    retry_max_times: Option<i32>,
+
+    #[arg(short = 'i', long = "brokerId", help = "consumer from which broker id")]
+    broker_id: Option<u64>,
+
+    #[arg(
+        short = 'w',
+        long = "whichBrokerWhenConsumeSlowly",
+        help = "which broker id when consume slowly"
+    )]
+    which_broker_when_consume_slowly: Option<u64>,
+
+    #[arg(
+        short = 'a',
+        long = "notifyConsumerIdsChanged",
+        action = ArgAction::Set,
+        help = "notify consumerId changed"
+    )]
+    notify_consumer_ids_changed: Option<bool>,
+
+    #[arg(long = "attributes", help = "attribute(+a=b,+c=d,-e)")]
+    attributes: Option<String>,
+}
+
+impl UpdateSubGroupSubCommand {
+    fn build_subscription_group_config(&self) -> RocketMQResult<SubscriptionGroupConfig> {
+        let mut config = SubscriptionGroupConfig::new(self.group_name.trim().into());
+        if let Some(consume_enable) = self.consume_enable {
+            config.set_consume_enable(consume_enable);
+        }
+        if let Some(consume_from_min_enable) = self.consume_from_min_enable {
+            config.set_consume_from_min_enable(consume_from_min_enable);
+        }
+        if let Some(consume_broadcast_enable) = self.consume_broadcast_enable {
+            config.set_consume_broadcast_enable(consume_broadcast_enable);
+        }
+        if let Some(consume_message_orderly) = self.consume_message_orderly {
+            config.set_consume_message_orderly(consume_message_orderly);
+        }
+        if let Some(retry_queue_nums) = self.retry_queue_nums {
+            config.set_retry_queue_nums(retry_queue_nums);
+        }
+        if let Some(retry_max_times) = self.retry_max_times {
+            config.set_retry_max_times(retry_max_times);
+        }
+        if let Some(broker_id) = self.broker_id {
+            config.set_broker_id(broker_id);
+        }
+        if let Some(which_broker_when_consume_slowly) = self.which_broker_when_consume_slowly {
+            config.set_which_broker_when_consume_slowly(which_broker_when_consume_slowly);
+        }
+        if let Some(notify_consumer_ids_changed) = self.notify_consumer_ids_changed {
+            config.set_notify_consumer_ids_changed_enable(notify_consumer_ids_changed);
+        }
+        if let Some(attributes) = &self.attributes {
+            config.set_attributes(
+                AttributeParser::parse_to_map(attributes.trim())
+                    .map_err(RocketmqError::IllegalArgument)?
+                    .into_iter()
+                    .map(|(key, value)| (CheetahString::from(key), CheetahString::from(value)))
+                    .collect::<HashMap<_, _>>(),
+            );
+        }
+        Ok(config)
+    }
+}
+
+impl CommandExecute for UpdateSubGroupSubCommand {
+    async fn execute(&self, _rpc_hook: Option<Arc<dyn RPCHook>>) -> RocketMQResult<()> {
+        let config = self.build_subscription_group_config()?;
+        let mut default_mqadmin_ext = DefaultMQAdminExt::new();
+        default_mqadmin_ext
+            .client_config_mut()
+            .set_instance_name(get_current_millis().to_string().into());
+
+        let operation_result = async {
+            if let Some(addr) = &self.common_args.namesrv_addr {
+                default_mqadmin_ext.set_namesrv_addr(addr.trim());
+            }
+            MQAdminExt::start(&mut default_mqadmin_ext)
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand("UpdateSubGroupSubCommand".into(), e.to_string())
+                })?;
+
+            let addrs = match (&self.broker_addr, &self.cluster_name) {
+                (Some(broker_addr), _) => vec![broker_addr.trim().into()],
+                (None, Some(cluster_name)) => command_util::fetch_master_addr_by_cluster_name(
+                    &default_mqadmin_ext,
+                    cluster_name.trim(),
+                )
+                .await?
+                .into_iter()
+                .collect(),
+                (None, None) => Vec::new(),
+            };
+            for addr in addrs {
+                default_mqadmin_ext
+                    .create_and_update_subscription_group_config(addr.clone(), config.clone())
+                    .await
+                    .map_err(|e| {
+                        RocketmqError::SubCommand("UpdateSubGroupSubCommand".into(), e.to_string())
+                    })?;
+                println!("create subscription group to {addr} success.");
+            }
+            println!("{config}");
+            Ok(())
+        }
+        .await;
+        MQAdminExt::shutdown(&mut default_mqadmin_ext).await;
+        operation_result
+    }
+}
@@ -0,0 +1,64 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+mod peek_message_sub_command;
+mod query_msg_by_id_sub_command;
+mod query_msg_trace_by_id_sub_command;
+
+use std::sync::Arc;
+
+use clap::Subcommand;
+use rocketmq_error::RocketMQResult;
+use rocketmq_remoting::runtime::RPCHook;
+
+use crate::commands::message_commands::peek_message_sub_command::PeekMessageSubCommand;
+use crate::commands::message_commands::query_msg_by_id_sub_command::QueryMsgByIdSubCommand;
+use crate::commands::message_commands::query_msg_trace_by_id_sub_command::QueryMsgTraceByIdSubCommand;
+use crate::commands::CommandExecute;
+
+#[derive(Subcommand)]
+pub enum MessageCommands {
+    #[command(
+        name = "peekMessage",
+        about = "Peek messages at the head of a queue without consuming them.",
+        long_about = None,
+    )]
+    PeekMessage(PeekMessageSubCommand),
+
+    #[command(
+        name = "queryMsgById",
+        about = "Query message by offset message id or unique key, optionally tracking its consume status.",
+        long_about = None,
+    )]
+    QueryMsgById(QueryMsgByIdSubCommand),
+
+    #[command(
+        name = "queryMsgTraceById",
+        about = "Query the publish and consume traces of a message.",
+        long_about = None,
+    )]
+    QueryMsgTraceById(QueryMsgTraceByIdSubCommand),
+}
+
+impl CommandExecute for MessageCommands {
+    async fn execute(&self, rpc_hook: Option<Arc<dyn RPCHook>>) -> RocketMQResult<()> {
+        match self {
+            MessageCommands::PeekMessage(value) => value.execute(rpc_hook).await,
+            MessageCommands::QueryMsgById(value) => value.execute(rpc_hook).await,
+            MessageCommands::QueryMsgTraceById(value) => value.execute(rpc_hook).await,
+        }
+    }
+}
@@ -0,0 +1,131 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::sync::Arc;
+
+use clap::Parser;
+use rocketmq_client_rust::admin::mq_admin_ext_async::MQAdminExt;
+use rocketmq_common::common::message::MessageTrait;
+use rocketmq_common::utils::util_all::time_millis_to_human_string2;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_error::RocketMQResult;
+use rocketmq_error::RocketmqError;
+use rocketmq_remoting::runtime::RPCHook;
+
+use crate::admin::default_mq_admin_ext::DefaultMQAdminExt;
+use crate::commands::CommandExecute;
+use crate::commands::CommonArgs;
+
+#[derive(Debug, Clone, Parser)]
+pub struct PeekMessageSubCommand {
+    #[command(flatten)]
+    common_args: CommonArgs,
+
+    #[arg(
+        short = 'b',
+        long = "brokerAddr",
+        required = true,
+        help = "broker address, eg: 127.0.0.1:10911"
+    )]
+    broker_addr: String,
+
+    #[arg(
+        short = 'g',
+        long = "consumerGroup",
+        required = true,
+        help = "consumer group whose offset is used as the peek position"
+    )]
+    consumer_group: String,
+
+    #[arg(short = 't', long = "topic", required = true, help = "topic name")]
+    topic: String,
+
+    #[arg(
+        short = 'q',
+        long = "queueId",
+        default_value_t = -1,
+        allow_negative_numbers = true,
+        help = "queue id, a negative value peeks every readable queue"
+    )]
+    queue_id: i32,
+
+    #[arg(
+        short = 'c',
+        long = "count",
+        default_value_t = 32,
+        help = "max number of messages to peek"
+    )]
+    count: u32,
+}
+
+impl CommandExecute for PeekMessageSubCommand {
+    async fn execute(&self, _rpc_hook: Option<Arc<dyn RPCHook>>) -> RocketMQResult<()> {
+        let mut default_mqadmin_ext = DefaultMQAdminExt::new();
+        default_mqadmin_ext
+            .client_config_mut()
+            .set_instance_name(get_current_millis().to_string().into());
+
+        let operation_result = async {
+            if let Some(addr) = &self.common_args.namesrv_addr {
+                default_mqadmin_ext.set_namesrv_addr(addr.trim());
+            }
+            MQAdminExt::start(&mut default_mqadmin_ext)
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand("PeekMessageSubCommand".into(), e.to_string())
+                })?;
+
+            let pop_result = default_mqadmin_ext
+                .peek_message(
+                    self.broker_addr.trim().into(),
+                    self.consumer_group.trim().into(),
+                    self.topic.trim().into(),
+                    self.queue_id,
+                    self.count,
+                )
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand("PeekMessageSubCommand".into(), e.to_string())
+                })?;
+
+            println!(
+                "{:<34}  {:<8}  {:<12}  {:<24}  {:<16}  {:<10}",
+                "#MsgId", "#QID", "#Offset", "#StoreTime", "#Tags", "#BodySize"
+            );
+            let messages = pop_result.msg_found_list.unwrap_or_default();
+            for message in &messages {
+                println!(
+                    "{:<34}  {:<8}  {:<12}  {:<24}  {:<16}  {:<10}",
+                    message.msg_id(),
+                    message.queue_id(),
+                    message.queue_offset(),
+                    time_millis_to_human_string2(message.store_timestamp()),
+                    message.get_tags().unwrap_or_default(),
+                    message.get_body().map_or(0, |body| body.len()),
+                );
+            }
+            println!(
+                "peek {} message(s), {} message(s) left behind the peek position",
+                messages.len(),
+                pop_result.rest_num
+            );
+            Ok(())
+        }
+        .await;
+        MQAdminExt::shutdown(&mut default_mqadmin_ext).await;
+        operation_result
+    }
+}
@@ -0,0 +1,197 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::sync::Arc;
+
+use clap::Parser;
+use rocketmq_client_rust::admin::mq_admin_ext_async::MQAdminExt;
+use rocketmq_common::common::message::message_ext::MessageExt;
+use rocketmq_common::common::message::MessageTrait;
+use rocketmq_common::utils::util_all::time_millis_to_human_string2;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_error::RocketMQResult;
+use rocketmq_error::RocketmqError;
+use rocketmq_remoting::runtime::RPCHook;
+
+use crate::admin::default_mq_admin_ext::DefaultMQAdminExt;
+use crate::commands::CommandExecute;
+use crate::commands::CommonArgs;
+
+#[derive(Debug, Clone, Parser)]
+pub struct QueryMsgByIdSubCommand {
+    #[command(flatten)]
+    common_args: CommonArgs,
+
+    #[arg(
+        short = 'i',
+        long = "msgId",
+        required = true,
+        value_delimiter = ',',
+        help = "message id, either the offset message id or the unique key, separated by commas"
+    )]
+    msg_ids: Vec<String>,
+
+    #[arg(
+        short = 't',
+        long = "topic",
+        default_value = "",
+        help = "topic of the message, required when querying by unique key"
+    )]
+    topic: String,
+
+    #[arg(
+        long = "track",
+        default_value_t = false,
+        help = "print the consume status of the message for every subscribed consumer group"
+    )]
+    track: bool,
+}
+
+impl CommandExecute for QueryMsgByIdSubCommand {
+    async fn execute(&self, _rpc_hook: Option<Arc<dyn RPCHook>>) -> RocketMQResult<()> {
+        let mut default_mqadmin_ext = DefaultMQAdminExt::new();
+        default_mqadmin_ext
+            .client_config_mut()
+            .set_instance_name(get_current_millis().to_string().into());
+
+        let operation_result = async {
+            if let Some(addr) = &self.common_args.namesrv_addr {
+                default_mqadmin_ext.set_namesrv_addr(addr.trim());
+            }
+            MQAdminExt::start(&mut default_mqadmin_ext)
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand("QueryMsgByIdSubCommand".into(), e.to_string())
+                })?;
+
+            for msg_id in self.msg_ids.iter().map(|msg_id| msg_id.trim()) {
+                if msg_id.is_empty() {
+                    continue;
+                }
+                let message = default_mqadmin_ext
+                    .view_message(self.topic.trim().into(), msg_id.into())
+                    .await
+                    .map_err(|e| {
+                        RocketmqError::SubCommand("QueryMsgByIdSubCommand".into(), e.to_string())
+                    })?;
+                print!("{}", render_message(msg_id, &message));
+
+                if self.track {
+                    let tracks = default_mqadmin_ext
+                        .message_track_detail(&message)
+                        .await
+                        .map_err(|e| {
+                            RocketmqError::SubCommand(
+                                "QueryMsgByIdSubCommand".into(),
+                                e.to_string(),
+                            )
+                        })?;
+                    println!();
+                    if tracks.is_empty() {
+                        println!("WARN: No Consumer");
+                        continue;
+                    }
+                    println!(
+                        "{:<32} {:<24} #ExceptionDesc",
+                        "#ConsumerGroup", "#TrackType"
+                    );
+                    for track in tracks {
+                        println!(
+                            "{:<32} {:<24} {}",
+                            track.consumer_group,
+                            track
+                                .track_type
+                                .map_or("None".to_string(), |track_type| track_type.to_string()),
+                            track.exception_desc
+                        );
+                    }
+                }
+                println!();
+            }
+            Ok(())
+        }
+        .await;
+        MQAdminExt::shutdown(&mut default_mqadmin_ext).await;
+        operation_result
+    }
+}
+
+fn render_message(msg_id: &str, message: &MessageExt) -> String {
+    let mut properties: Vec<String> = message
+        .get_properties()
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect();
+    properties.sort();
+    let rows = [
+        ("OffsetID:", msg_id.to_string()),
+        ("Topic:", message.get_topic().to_string()),
+        (
+            "Tags:",
+            format!("[{}]", message.get_tags().unwrap_or_default()),
+        ),
+        (
+            "Keys:",
+            format!("[{}]", message.get_keys().unwrap_or_default()),
+        ),
+        ("Queue ID:", message.queue_id().to_string()),
+        ("Queue Offset:", message.queue_offset().to_string()),
+        ("CommitLog Offset:", message.commit_log_offset().to_string()),
+        ("Reconsume Times:", message.reconsume_times().to_string()),
+        (
+            "Born Timestamp:",
+            time_millis_to_human_string2(message.born_timestamp()),
+        ),
+        (
+            "Store Timestamp:",
+            time_millis_to_human_string2(message.store_timestamp()),
+        ),
+        ("Born Host:", message.born_host().to_string()),
+        ("Store Host:", message.store_host().to_string()),
+        ("System Flag:", message.sys_flag().to_string()),
+        ("Properties:", format!("{{{}}}", properties.join(", "))),
+        (
+            "Message Body:",
+            message.get_body().map_or(String::new(), |body| {
+                String::from_utf8_lossy(body).into_owned()
+            }),
+        ),
+    ];
+    rows.iter()
+        .map(|(name, value)| format!("{name:<20} {value}\n"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[test]
+    fn parse_msg_ids_and_track_flag() {
+        let command = QueryMsgByIdSubCommand::try_parse_from([
+            "queryMsgById",
+            "-i",
+            "7F00000100002A9F0000000000000000,7F00000100002A9F00000000000000FF",
+            "--track",
+        ])
+        .unwrap();
+        assert_eq!(command.msg_ids.len(), 2);
+        assert!(command.track);
+        assert!(command.topic.is_empty());
+    }
+}
@@ -0,0 +1,229 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Arc;
+
+use clap::Parser;
+use rocketmq_client_rust::admin::mq_admin_ext_async::MQAdminExt;
+use rocketmq_client_rust::trace::trace_type::TraceType;
+use rocketmq_client_rust::trace::trace_view::TraceView;
+use rocketmq_common::common::topic::TopicValidator;
+use rocketmq_common::utils::util_all::time_millis_to_human_string2;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_error::RocketMQResult;
+use rocketmq_error::RocketmqError;
+use rocketmq_remoting::runtime::RPCHook;
+
+use crate::admin::default_mq_admin_ext::DefaultMQAdminExt;
+use crate::commands::CommandExecute;
+use crate::commands::CommonArgs;
+
+#[derive(Debug, Clone, Parser)]
+pub struct QueryMsgTraceByIdSubCommand {
+    #[command(flatten)]
+    common_args: CommonArgs,
+
+    #[arg(short = 'i', long = "msgId", required = true, help = "message id")]
+    msg_id: String,
+
+    #[arg(
+        short = 't',
+        long = "traceTopic",
+        default_value = TopicValidator::RMQ_SYS_TRACE_TOPIC,
+        help = "topic the message traces are stored in"
+    )]
+    trace_topic: String,
+
+    #[arg(
+        short = 'b',
+        long = "beginTimestamp",
+        default_value_t = 0,
+        help = "begin of the store time range of the traces, in milliseconds"
+    )]
+    begin_timestamp: i64,
+
+    #[arg(
+        short = 'e',
+        long = "endTimestamp",
+        default_value_t = i64::MAX,
+        help = "end of the store time range of the traces, in milliseconds"
+    )]
+    end_timestamp: i64,
+
+    #[arg(
+        short = 'c',
+        long = "maxNum",
+        default_value_t = 64,
+        help = "max number of trace messages to query"
+    )]
+    max_num: i32,
+}
+
+impl CommandExecute for QueryMsgTraceByIdSubCommand {
+    async fn execute(&self, _rpc_hook: Option<Arc<dyn RPCHook>>) -> RocketMQResult<()> {
+        let mut default_mqadmin_ext = DefaultMQAdminExt::new();
+        default_mqadmin_ext
+            .client_config_mut()
+            .set_instance_name(get_current_millis().to_string().into());
+
+        let operation_result = async {
+            if let Some(addr) = &self.common_args.namesrv_addr {
+                default_mqadmin_ext.set_namesrv_addr(addr.trim());
+            }
+            MQAdminExt::start(&mut default_mqadmin_ext)
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand("QueryMsgTraceByIdSubCommand".into(), e.to_string())
+                })?;
+
+            let msg_id = self.msg_id.trim();
+            let query_result = default_mqadmin_ext
+                .query_message_by_key(
+                    None,
+                    self.trace_topic.trim().into(),
+                    msg_id.into(),
+                    self.max_num,
+                    self.begin_timestamp,
+                    self.end_timestamp,
+                )
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand("QueryMsgTraceByIdSubCommand".into(), e.to_string())
+                })?;
+
+            let trace_views: Vec<TraceView> = query_result
+                .message_list()
+                .iter()
+                .flat_map(|message| TraceView::decode_from_trace_trans_data(msg_id, message))
+                .collect();
+            print!("{}", render_trace_views(&trace_views));
+            Ok(())
+        }
+        .await;
+        MQAdminExt::shutdown(&mut default_mqadmin_ext).await;
+        operation_result
+    }
+}
+
+/// Renders the publish trace followed by the consume traces grouped by consumer group.
+fn render_trace_views(trace_views: &[TraceView]) -> String {
+    let mut output = String::new();
+    let mut consumer_traces: BTreeMap<&str, Vec<&TraceView>> = BTreeMap::new();
+    for trace_view in trace_views {
+        match trace_view.trace_type {
+            Some(TraceType::Pub) => {
+                write_row(
+                    &mut output,
+                    [
+                        "#Type",
+                        "#ProducerGroup",
+                        "#ClientHost",
+                        "#SendTime",
+                        "#CostTimes",
+                        "#Status",
+                    ],
+                );
+                write_trace_row(&mut output, "Pub", trace_view);
+                output.push('\n');
+            }
+            Some(TraceType::SubAfter) => consumer_traces
+                .entry(trace_view.group_name.as_str())
+                .or_default()
+                .push(trace_view),
+            _ => {}
+        }
+    }
+    for traces in consumer_traces.values() {
+        write_row(
+            &mut output,
+            [
+                "#Type",
+                "#ConsumerGroup",
+                "#ClientHost",
+                "#ConsumerTime",
+                "#CostTimes",
+                "#Status",
+            ],
+        );
+        for trace_view in traces {
+            write_trace_row(&mut output, "Sub", trace_view);
+        }
+        output.push('\n');
+    }
+    output
+}
+
+fn write_trace_row(output: &mut String, trace_type: &str, trace_view: &TraceView) {
+    write_row(
+        output,
+        [
+            trace_type,
+            trace_view.group_name.as_str(),
+            trace_view.client_host.as_str(),
+            &time_millis_to_human_string2(trace_view.time_stamp),
+            &format!("{}ms", trace_view.cost_time),
+            trace_view.status.as_str(),
+        ],
+    );
+}
+
+fn write_row(output: &mut String, columns: [&str; 6]) {
+    let _ = writeln!(
+        output,
+        "{:<10} {:<20} {:<20} {:<20} {:<10} {:<10}",
+        columns[0], columns[1], columns[2], columns[3], columns[4], columns[5]
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use cheetah_string::CheetahString;
+
+    use super::*;
+
+    fn trace_view(trace_type: TraceType, group_name: &str, status: &str) -> TraceView {
+        TraceView {
+            group_name: CheetahString::from(group_name),
+            client_host: CheetahString::from("127.0.0.1"),
+            cost_time: 2,
+            status: CheetahString::from(status),
+            trace_type: Some(trace_type),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn render_groups_consume_traces_by_consumer_group() {
+        let output = render_trace_views(&[
+            trace_view(TraceType::SubAfter, "group_b", "failed"),
+            trace_view(TraceType::Pub, "producer_group", "success"),
+            trace_view(TraceType::SubBefore, "group_a", "success"),
+            trace_view(TraceType::SubAfter, "group_a", "success"),
+            trace_view(TraceType::SubAfter, "group_b", "success"),
+        ]);
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[0].starts_with("#Type      #ProducerGroup"));
+        assert!(lines[1].starts_with("Pub        producer_group"));
+        assert!(lines[3].starts_with("#Type      #ConsumerGroup"));
+        assert!(lines[4].starts_with("Sub        group_a"));
+        assert!(lines[7].starts_with("Sub        group_b"));
+        assert!(lines[7].contains("failed"));
+        assert!(lines[8].starts_with("Sub        group_b"));
+        assert_eq!(output.matches("Sub ").count(), 3);
+    }
+}
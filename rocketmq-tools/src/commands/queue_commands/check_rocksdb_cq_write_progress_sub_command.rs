@@ -0,0 +1,117 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::sync::Arc;
+
+use clap::Parser;
+use rocketmq_client_rust::admin::mq_admin_ext_async::MQAdminExt;
+use rocketmq_common::common::mix_all;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_error::RocketMQResult;
+use rocketmq_error::RocketmqError;
+use rocketmq_remoting::runtime::RPCHook;
+
+use crate::admin::default_mq_admin_ext::DefaultMQAdminExt;
+use crate::commands::CommandExecute;
+use crate::commands::CommonArgs;
+
+#[derive(Debug, Clone, Parser)]
+pub struct CheckRocksdbCqWriteProgressSubCommand {
+    #[command(flatten)]
+    common_args: CommonArgs,
+
+    #[arg(short = 'c', long = "cluster", required = true, help = "cluster name")]
+    cluster_name: String,
+
+    #[arg(
+        short = 't',
+        long = "topic",
+        required = false,
+        help = "topic name, all topics are checked when absent"
+    )]
+    topic: Option<String>,
+}
+
+impl CommandExecute for CheckRocksdbCqWriteProgressSubCommand {
+    async fn execute(&self, _rpc_hook: Option<Arc<dyn RPCHook>>) -> RocketMQResult<()> {
+        let mut default_mqadmin_ext = DefaultMQAdminExt::new();
+        default_mqadmin_ext
+            .client_config_mut()
+            .set_instance_name(get_current_millis().to_string().into());
+
+        let operation_result = async {
+            if let Some(addr) = &self.common_args.namesrv_addr {
+                default_mqadmin_ext.set_namesrv_addr(addr.trim());
+            }
+            MQAdminExt::start(&mut default_mqadmin_ext)
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand(
+                        "CheckRocksdbCqWriteProgressSubCommand".into(),
+                        e.to_string(),
+                    )
+                })?;
+
+            let cluster_info = default_mqadmin_ext
+                .examine_broker_cluster_info()
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand(
+                        "CheckRocksdbCqWriteProgressSubCommand".into(),
+                        e.to_string(),
+                    )
+                })?;
+            let cluster_name = self.cluster_name.trim();
+            let Some(broker_names) = cluster_info
+                .cluster_addr_table
+                .as_ref()
+                .and_then(|table| table.get(cluster_name))
+            else {
+                println!("clusterAddrTable is empty");
+                return Ok(());
+            };
+            let topic = self.topic.as_deref().unwrap_or_default().trim();
+            let broker_addr_table = cluster_info.broker_addr_table.unwrap_or_default();
+            for broker_name in broker_names {
+                let Some(master_addr) = broker_addr_table
+                    .get(broker_name)
+                    .and_then(|broker_data| broker_data.broker_addrs().get(&mix_all::MASTER_ID))
+                else {
+                    continue;
+                };
+                let body = default_mqadmin_ext
+                    .check_rocksdb_cq_write_progress(master_addr.clone(), topic.into())
+                    .await
+                    .map_err(|e| {
+                        RocketmqError::SubCommand(
+                            "CheckRocksdbCqWriteProgressSubCommand".into(),
+                            e.to_string(),
+                        )
+                    })?;
+                println!(
+                    "check doubleWriteCq, brokerName : {}, topic : {} \n{}",
+                    broker_name,
+                    topic,
+                    body.diff_result.unwrap_or_default()
+                );
+            }
+            Ok(())
+        }
+        .await;
+        MQAdminExt::shutdown(&mut default_mqadmin_ext).await;
+        operation_result
+    }
+}
@@ -15,6 +15,11 @@
  * limitations under the License.
  */
 mod allocate_mq_sub_command;
+pub(crate) mod delete_topic_sub_command;
+mod topic_list_sub_command;
+mod topic_route_sub_command;
+mod topic_status_sub_command;
+mod update_topic_sub_command;
 
 use std::sync::Arc;
 
@@ -34,12 +39,52 @@ space of the topic when the topic is created. The default value is 1. If you wan
 more memory space, you can use this command to allocate it."#
     )]
     AllocateMQ(allocate_mq_sub_command::AllocateMQSubCommand),
+
+    #[command(
+        name = "deleteTopic",
+        about = "Delete topic from broker and NameServer.",
+        long_about = None,
+    )]
+    DeleteTopic(delete_topic_sub_command::DeleteTopicSubCommand),
+
+    #[command(
+        name = "topicList",
+        about = "Fetch all topic list from name server.",
+        long_about = None,
+    )]
+    TopicList(topic_list_sub_command::TopicListSubCommand),
+
+    #[command(
+        name = "topicRoute",
+        about = "Examine topic route info.",
+        long_about = None,
+    )]
+    TopicRoute(topic_route_sub_command::TopicRouteSubCommand),
+
+    #[command(
+        name = "topicStatus",
+        about = "Examine topic Status info.",
+        long_about = None,
+    )]
+    TopicStatus(topic_status_sub_command::TopicStatusSubCommand),
+
+    #[command(
+        name = "updateTopic",
+        about = "Update or create topic.",
+        long_about = None,
+    )]
+    UpdateTopic(update_topic_sub_command::UpdateTopicSubCommand),
 }
 
 impl CommandExecute for TopicCommands {
     async fn execute(&self, rpc_hook: Option<Arc<dyn RPCHook>>) -> RocketMQResult<()> {
         match self {
             TopicCommands::AllocateMQ(cmd) => cmd.execute(rpc_hook).await,
+            TopicCommands::DeleteTopic(cmd) => cmd.execute(rpc_hook).await,
+            TopicCommands::TopicList(cmd) => cmd.execute(rpc_hook).await,
+            TopicCommands::TopicRoute(cmd) => cmd.execute(rpc_hook).await,
+            TopicCommands::TopicStatus(cmd) => cmd.execute(rpc_hook).await,
+            TopicCommands::UpdateTopic(cmd) => cmd.execute(rpc_hook).await,
         }
     }
 }
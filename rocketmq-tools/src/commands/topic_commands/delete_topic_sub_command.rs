@@ -0,0 +1,100 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use clap::Parser;
+use rocketmq_client_rust::admin::mq_admin_ext_async::MQAdminExt;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_error::RocketMQResult;
+use rocketmq_error::RocketmqError;
+use rocketmq_remoting::runtime::RPCHook;
+
+use crate::admin::default_mq_admin_ext::DefaultMQAdminExt;
+use crate::commands::command_util;
+use crate::commands::CommandExecute;
+use crate::commands::CommonArgs;
+
+#[derive(Debug, Clone, Parser)]
+pub struct DeleteTopicSubCommand {
+    #[command(flatten)]
+    common_args: CommonArgs,
+
+    #[arg(short = 't', long = "topic", required = true, help = "topic name")]
+    topic: String,
+
+    #[arg(
+        short = 'c',
+        long = "clusterName",
+        required = true,
+        help = "delete topic from which cluster"
+    )]
+    cluster_name: String,
+}
+
+impl DeleteTopicSubCommand {
+    /// Deletes `topic` from every broker of `cluster_name` and then from the name servers.
+    pub(crate) async fn delete_topic(
+        admin_ext: &DefaultMQAdminExt,
+        cluster_name: &str,
+        topic: &str,
+    ) -> RocketMQResult<()> {
+        let broker_addr_set =
+            command_util::fetch_master_and_slave_addr_by_cluster_name(admin_ext, cluster_name)
+                .await?;
+        admin_ext
+            .delete_topic_in_broker(broker_addr_set, topic.into())
+            .await?;
+        println!("delete topic [{topic}] from cluster [{cluster_name}] success.");
+
+        admin_ext
+            .delete_topic_in_name_server(HashSet::new(), Some(cluster_name.into()), topic.into())
+            .await?;
+        println!("delete topic [{topic}] from NameServer success.");
+        Ok(())
+    }
+}
+
+impl CommandExecute for DeleteTopicSubCommand {
+    async fn execute(&self, _rpc_hook: Option<Arc<dyn RPCHook>>) -> RocketMQResult<()> {
+        let mut default_mqadmin_ext = DefaultMQAdminExt::new();
+        default_mqadmin_ext
+            .client_config_mut()
+            .set_instance_name(get_current_millis().to_string().into());
+
+        let operation_result = async {
+            if let Some(addr) = &self.common_args.namesrv_addr {
+                default_mqadmin_ext.set_namesrv_addr(addr.trim());
+            }
+            MQAdminExt::start(&mut default_mqadmin_ext)
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand("DeleteTopicSubCommand".into(), e.to_string())
+                })?;
+            Self::delete_topic(
+                &default_mqadmin_ext,
+                self.cluster_name.trim(),
+                self.topic.trim(),
+            )
+            .await
+            .map_err(|e| RocketmqError::SubCommand("DeleteTopicSubCommand".into(), e.to_string()))
+        }
+        .await;
+        MQAdminExt::shutdown(&mut default_mqadmin_ext).await;
+        operation_result
+    }
+}
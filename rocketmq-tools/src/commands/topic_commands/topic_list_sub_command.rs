@@ -0,0 +1,146 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::sync::Arc;
+
+use clap::Parser;
+use rocketmq_client_rust::admin::mq_admin_ext_async::MQAdminExt;
+use rocketmq_common::common::mix_all;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_error::RocketMQResult;
+use rocketmq_error::RocketmqError;
+use rocketmq_remoting::protocol::body::broker_body::cluster_info::ClusterInfo;
+use rocketmq_remoting::runtime::RPCHook;
+
+use crate::admin::default_mq_admin_ext::DefaultMQAdminExt;
+use crate::commands::command_util::front_string_at_least;
+use crate::commands::CommandExecute;
+use crate::commands::CommonArgs;
+
+#[derive(Debug, Clone, Parser)]
+pub struct TopicListSubCommand {
+    #[command(flatten)]
+    common_args: CommonArgs,
+
+    #[arg(
+        short = 'c',
+        long = "clusterModel",
+        help = "print the cluster and consumer groups of every topic"
+    )]
+    cluster_model: bool,
+}
+
+impl TopicListSubCommand {
+    async fn find_topic_belong_to_which_cluster(
+        admin_ext: &DefaultMQAdminExt,
+        topic: &str,
+        cluster_info: &ClusterInfo,
+    ) -> RocketMQResult<String> {
+        let topic_route_data = admin_ext.examine_topic_route_info(topic.into()).await?;
+        let Some(broker_name) = topic_route_data
+            .as_ref()
+            .and_then(|route| route.broker_datas.first())
+            .map(|broker_data| broker_data.broker_name())
+        else {
+            return Ok(String::new());
+        };
+        Ok(cluster_info
+            .cluster_addr_table
+            .iter()
+            .flatten()
+            .find(|(_, broker_names)| broker_names.contains(broker_name))
+            .map(|(cluster_name, _)| cluster_name.to_string())
+            .unwrap_or_default())
+    }
+}
+
+impl CommandExecute for TopicListSubCommand {
+    async fn execute(&self, _rpc_hook: Option<Arc<dyn RPCHook>>) -> RocketMQResult<()> {
+        let mut default_mqadmin_ext = DefaultMQAdminExt::new();
+        default_mqadmin_ext
+            .client_config_mut()
+            .set_instance_name(get_current_millis().to_string().into());
+
+        let operation_result = async {
+            if let Some(addr) = &self.common_args.namesrv_addr {
+                default_mqadmin_ext.set_namesrv_addr(addr.trim());
+            }
+            MQAdminExt::start(&mut default_mqadmin_ext)
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand("TopicListSubCommand".into(), e.to_string())
+                })?;
+
+            let topic_list = default_mqadmin_ext
+                .fetch_all_topic_list()
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand("TopicListSubCommand".into(), e.to_string())
+                })?;
+            if !self.cluster_model {
+                for topic in &topic_list.topic_list {
+                    println!("{topic}");
+                }
+                return Ok(());
+            }
+
+            let cluster_info = default_mqadmin_ext
+                .examine_broker_cluster_info()
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand("TopicListSubCommand".into(), e.to_string())
+                })?;
+            println!(
+                "{:<20}  {:<48}  {:<48}",
+                "#Cluster Name", "#Topic", "#Consumer Group"
+            );
+            for topic in &topic_list.topic_list {
+                if topic.starts_with(mix_all::RETRY_GROUP_TOPIC_PREFIX)
+                    || topic.starts_with(mix_all::DLQ_GROUP_TOPIC_PREFIX)
+                {
+                    continue;
+                }
+                let cluster_name = Self::find_topic_belong_to_which_cluster(
+                    &default_mqadmin_ext,
+                    topic,
+                    &cluster_info,
+                )
+                .await
+                .unwrap_or_default();
+                let mut groups = default_mqadmin_ext
+                    .query_topic_consume_by_who(topic.clone())
+                    .await
+                    .map(|group_list| group_list.group_list.into_iter().collect::<Vec<_>>())
+                    .unwrap_or_default();
+                if groups.is_empty() {
+                    groups.push(Default::default());
+                }
+                for group in groups {
+                    println!(
+                        "{:<20}  {:<48}  {:<48}",
+                        front_string_at_least(&cluster_name, 20),
+                        front_string_at_least(topic, 48),
+                        front_string_at_least(&group, 48)
+                    );
+                }
+            }
+            Ok(())
+        }
+        .await;
+        MQAdminExt::shutdown(&mut default_mqadmin_ext).await;
+        operation_result
+    }
+}
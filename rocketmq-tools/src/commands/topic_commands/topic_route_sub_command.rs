@@ -0,0 +1,133 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use clap::Parser;
+use rocketmq_client_rust::admin::mq_admin_ext_async::MQAdminExt;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_error::RocketMQResult;
+use rocketmq_error::RocketmqError;
+use rocketmq_remoting::protocol::route::topic_route_data::TopicRouteData;
+use rocketmq_remoting::protocol::RemotingSerializable;
+use rocketmq_remoting::runtime::RPCHook;
+
+use crate::admin::default_mq_admin_ext::DefaultMQAdminExt;
+use crate::commands::CommandExecute;
+use crate::commands::CommonArgs;
+
+#[derive(Debug, Clone, Parser)]
+pub struct TopicRouteSubCommand {
+    #[command(flatten)]
+    common_args: CommonArgs,
+
+    #[arg(short = 't', long = "topic", required = true, help = "topic name")]
+    topic: String,
+
+    #[arg(short = 'l', long = "list", help = "Use list format to print data")]
+    list: bool,
+}
+
+impl TopicRouteSubCommand {
+    fn print_data(topic_route_data: &TopicRouteData, use_list_format: bool) -> RocketMQResult<()> {
+        if !use_list_format {
+            println!("{}", topic_route_data.to_json_pretty()?);
+            return Ok(());
+        }
+
+        let queue_data_map = topic_route_data
+            .queue_datas
+            .iter()
+            .map(|queue_data| (queue_data.broker_name(), queue_data))
+            .collect::<HashMap<_, _>>();
+        let mut broker_datas = topic_route_data.broker_datas.iter().collect::<Vec<_>>();
+        broker_datas.sort_by(|a, b| a.broker_name().cmp(b.broker_name()));
+
+        println!(
+            "{:<45} {:<32} {:<50} {:<10} {:<11} {:<5}",
+            "#ClusterName", "#BrokerName", "#BrokerAddrs", "#ReadQueue", "#WriteQueue", "#Perm"
+        );
+        let mut total_read_queue = 0;
+        let mut total_write_queue = 0;
+        for broker_data in broker_datas {
+            let Some(queue_data) = queue_data_map.get(broker_data.broker_name()) else {
+                continue;
+            };
+            total_read_queue += queue_data.read_queue_nums();
+            total_write_queue += queue_data.write_queue_nums();
+            let mut broker_addrs = broker_data.broker_addrs().iter().collect::<Vec<_>>();
+            broker_addrs.sort();
+            let broker_addrs = broker_addrs
+                .iter()
+                .map(|(broker_id, addr)| format!("{broker_id}={addr}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            println!(
+                "{:<45} {:<32} {:<50} {:<10} {:<11} {:<5}",
+                broker_data.cluster(),
+                broker_data.broker_name(),
+                format!("{{{broker_addrs}}}"),
+                queue_data.read_queue_nums(),
+                queue_data.write_queue_nums(),
+                queue_data.perm()
+            );
+        }
+        println!("{}", "-".repeat(158));
+        println!(
+            "{:<45} {:<32} {:<50} {:<10} {:<11} {:<5}",
+            "Total:",
+            queue_data_map.len(),
+            "",
+            total_read_queue,
+            total_write_queue,
+            ""
+        );
+        Ok(())
+    }
+}
+
+impl CommandExecute for TopicRouteSubCommand {
+    async fn execute(&self, _rpc_hook: Option<Arc<dyn RPCHook>>) -> RocketMQResult<()> {
+        let mut default_mqadmin_ext = DefaultMQAdminExt::new();
+        default_mqadmin_ext
+            .client_config_mut()
+            .set_instance_name(get_current_millis().to_string().into());
+
+        let operation_result = async {
+            if let Some(addr) = &self.common_args.namesrv_addr {
+                default_mqadmin_ext.set_namesrv_addr(addr.trim());
+            }
+            MQAdminExt::start(&mut default_mqadmin_ext)
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand("TopicRouteSubCommand".into(), e.to_string())
+                })?;
+
+            let topic_route_data = default_mqadmin_ext
+                .examine_topic_route_info(self.topic.trim().into())
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand("TopicRouteSubCommand".into(), e.to_string())
+                })?
+                .unwrap_or_default();
+            Self::print_data(&topic_route_data, self.list)
+        }
+        .await;
+        MQAdminExt::shutdown(&mut default_mqadmin_ext).await;
+        operation_result
+    }
+}
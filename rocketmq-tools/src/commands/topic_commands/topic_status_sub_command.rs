@@ -0,0 +1,135 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use clap::Parser;
+use rocketmq_client_rust::admin::mq_admin_ext_async::MQAdminExt;
+use rocketmq_common::utils::util_all::time_millis_to_human_string2;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_error::RocketMQResult;
+use rocketmq_error::RocketmqError;
+use rocketmq_remoting::runtime::RPCHook;
+
+use crate::admin::default_mq_admin_ext::DefaultMQAdminExt;
+use crate::commands::command_util::front_string_at_least;
+use crate::commands::CommandExecute;
+use crate::commands::CommonArgs;
+
+#[derive(Debug, Clone, Parser)]
+pub struct TopicStatusSubCommand {
+    #[command(flatten)]
+    common_args: CommonArgs,
+
+    #[arg(short = 't', long = "topic", required = true, help = "topic name")]
+    topic: String,
+
+    #[arg(
+        short = 'c',
+        long = "cluster",
+        help = "cluster name or lmq parent topic"
+    )]
+    cluster: Option<String>,
+}
+
+impl CommandExecute for TopicStatusSubCommand {
+    async fn execute(&self, _rpc_hook: Option<Arc<dyn RPCHook>>) -> RocketMQResult<()> {
+        let mut default_mqadmin_ext = DefaultMQAdminExt::new();
+        default_mqadmin_ext
+            .client_config_mut()
+            .set_instance_name(get_current_millis().to_string().into());
+
+        let operation_result = async {
+            if let Some(addr) = &self.common_args.namesrv_addr {
+                default_mqadmin_ext.set_namesrv_addr(addr.trim());
+            }
+            MQAdminExt::start(&mut default_mqadmin_ext)
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand("TopicStatusSubCommand".into(), e.to_string())
+                })?;
+
+            let topic = self.topic.trim();
+            let offset_table = match &self.cluster {
+                Some(cluster) => {
+                    let cluster = cluster.trim();
+                    let topic_route_data = default_mqadmin_ext
+                        .examine_topic_route_info(topic.into())
+                        .await
+                        .map_err(|e| {
+                            RocketmqError::SubCommand("TopicStatusSubCommand".into(), e.to_string())
+                        })?
+                        .unwrap_or_default();
+                    let mut offset_table = HashMap::new();
+                    for broker_data in &topic_route_data.broker_datas {
+                        if broker_data.cluster() != cluster {
+                            continue;
+                        }
+                        let Some(addr) = broker_data.select_broker_addr() else {
+                            continue;
+                        };
+                        let topic_stats_table = default_mqadmin_ext
+                            .examine_topic_stats(topic.into(), Some(addr))
+                            .await
+                            .map_err(|e| {
+                                RocketmqError::SubCommand(
+                                    "TopicStatusSubCommand".into(),
+                                    e.to_string(),
+                                )
+                            })?;
+                        offset_table.extend(topic_stats_table.get_offset_table());
+                    }
+                    offset_table
+                }
+                None => default_mqadmin_ext
+                    .examine_topic_stats(topic.into(), None)
+                    .await
+                    .map_err(|e| {
+                        RocketmqError::SubCommand("TopicStatusSubCommand".into(), e.to_string())
+                    })?
+                    .get_offset_table(),
+            };
+
+            let mut mq_list = offset_table.keys().collect::<Vec<_>>();
+            mq_list.sort();
+            println!(
+                "{:<32}  {:<4}  {:<20}  {:<20}    #Last Updated",
+                "#Broker Name", "#QID", "#Min Offset", "#Max Offset"
+            );
+            for mq in mq_list {
+                let topic_offset = &offset_table[mq];
+                let human_timestamp = if topic_offset.get_last_update_timestamp() > 0 {
+                    time_millis_to_human_string2(topic_offset.get_last_update_timestamp())
+                } else {
+                    String::new()
+                };
+                println!(
+                    "{:<32}  {:<4}  {:<20}  {:<20}    {}",
+                    front_string_at_least(mq.get_broker_name(), 32),
+                    mq.get_queue_id(),
+                    topic_offset.get_min_offset(),
+                    topic_offset.get_max_offset(),
+                    human_timestamp
+                );
+            }
+            Ok(())
+        }
+        .await;
+        MQAdminExt::shutdown(&mut default_mqadmin_ext).await;
+        operation_result
+    }
+}
@@ -0,0 +1,284 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use cheetah_string::CheetahString;
+use clap::ArgAction;
+use clap::Parser;
+use rocketmq_client_rust::admin::mq_admin_ext_async::MQAdminExt;
+use rocketmq_common::common::attribute::attribute_parser::AttributeParser;
+use rocketmq_common::common::config::TopicConfig;
+use rocketmq_common::common::constant::PermName;
+use rocketmq_common::common::sys_flag::topic_sys_flag;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_error::RocketMQResult;
+use rocketmq_error::RocketmqError;
+use rocketmq_remoting::runtime::RPCHook;
+
+use crate::admin::default_mq_admin_ext::DefaultMQAdminExt;
+use crate::commands::command_util;
+use crate::commands::CommandExecute;
+use crate::commands::CommonArgs;
+
+#[derive(Debug, Clone, Parser)]
+pub struct UpdateTopicSubCommand {
+    #[command(flatten)]
+    common_args: CommonArgs,
+
+    #[arg(
+        short = 'b',
+        long = "brokerAddr",
+        required_unless_present = "cluster_name",
+        conflicts_with = "cluster_name",
+        help = "create topic to which broker"
+    )]
+    broker_addr: Option<String>,
+
+    #[arg(
+        short = 'c',
+        long = "clusterName",
+        help = "create topic to which cluster"
+    )]
+    cluster_name: Option<String>,
+
+    #[arg(short = 't', long = "topic", required = true, help = "topic name")]
+    topic: String,
+
+    #[arg(
+        short = 'r',
+        long = "readQueueNums",
+        default_value_t = 8,
+        help = "set read queue nums"
+    )]
+    read_queue_nums: u32,
+
+    #[arg(
+        short = 'w',
+        long = "writeQueueNums",
+        default_value_t = 8,
+        help = "set write queue nums"
+    )]
+    write_queue_nums: u32,
+
+    #[arg(
+        short = 'p',
+        long = "perm",
+        default_value_t = 6,
+        help = "set topic's permission(2|4|6), intro[2:W 4:R; 6:RW]"
+    )]
+    perm: u32,
+
+    #[arg(
+        short = 'o',
+        long = "order",
+        action = ArgAction::Set,
+        default_value_t = false,
+        help = "set topic's order(true|false)"
+    )]
+    order: bool,
+
+    #[arg(
+        short = 'u',
+        long = "unit",
+        action = ArgAction::Set,
+        default_value_t = false,
+        help = "is unit topic (true|false)"
+    )]
+    unit: bool,
+
+    #[arg(
+        short = 's',
+        long = "hasUnitSub",
+        action = ArgAction::Set,
+        default_value_t = false,
+        help = "has unit sub (true|false)"
+    )]
+    has_unit_sub: bool,
+
+    #[arg(short = 'a', long = "attributes", help = "attribute(+a=b,+c=d,-e)")]
+    attributes: Option<String>,
+}
+
+impl UpdateTopicSubCommand {
+    fn build_topic_config(&self) -> RocketMQResult<TopicConfig> {
+        let mut topic_config = TopicConfig::with_perm(
+            self.topic.trim(),
+            self.read_queue_nums,
+            self.write_queue_nums,
+            self.perm,
+        );
+        topic_config.topic_sys_flag = topic_sys_flag::build_sys_flag(self.unit, self.has_unit_sub);
+        topic_config.order = self.order;
+        if let Some(attributes) = &self.attributes {
+            topic_config.attributes = AttributeParser::parse_to_map(attributes.trim())
+                .map_err(RocketmqError::IllegalArgument)?
+                .into_iter()
+                .map(|(key, value)| (CheetahString::from(key), CheetahString::from(value)))
+                .collect::<HashMap<_, _>>();
+        }
+        Ok(topic_config)
+    }
+}
+
+/// Prints a topic config the way the Java admin tool does. Attributes come from a hash map, so
+/// they are sorted to keep the output stable.
+fn format_topic_config(topic_config: &TopicConfig) -> String {
+    let mut attributes = topic_config
+        .attributes
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>();
+    attributes.sort();
+    format!(
+        "TopicConfig [topicName={}, readQueueNums={}, writeQueueNums={}, perm={}, \
+         topicFilterType={}, topicSysFlag={}, order={}, attributes={{{}}}]",
+        topic_config.topic_name.as_deref().unwrap_or("null"),
+        topic_config.read_queue_nums,
+        topic_config.write_queue_nums,
+        PermName::perm2string(topic_config.perm),
+        topic_config.topic_filter_type,
+        topic_config.topic_sys_flag,
+        topic_config.order,
+        attributes.join(", ")
+    )
+}
+
+impl CommandExecute for UpdateTopicSubCommand {
+    async fn execute(&self, _rpc_hook: Option<Arc<dyn RPCHook>>) -> RocketMQResult<()> {
+        let topic_config = self.build_topic_config()?;
+        let mut default_mqadmin_ext = DefaultMQAdminExt::new();
+        default_mqadmin_ext
+            .client_config_mut()
+            .set_instance_name(get_current_millis().to_string().into());
+
+        let operation_result = async {
+            if let Some(addr) = &self.common_args.namesrv_addr {
+                default_mqadmin_ext.set_namesrv_addr(addr.trim());
+            }
+            MQAdminExt::start(&mut default_mqadmin_ext)
+                .await
+                .map_err(|e| {
+                    RocketmqError::SubCommand("UpdateTopicSubCommand".into(), e.to_string())
+                })?;
+
+            if let Some(broker_addr) = &self.broker_addr {
+                let addr = broker_addr.trim();
+                default_mqadmin_ext
+                    .create_and_update_topic_config(addr.into(), topic_config.clone())
+                    .await
+                    .map_err(|e| {
+                        RocketmqError::SubCommand("UpdateTopicSubCommand".into(), e.to_string())
+                    })?;
+                if self.order {
+                    let broker_name =
+                        command_util::fetch_broker_name_by_addr(&default_mqadmin_ext, addr).await?;
+                    let order_conf = format!("{}:{}", broker_name, topic_config.write_queue_nums);
+                    default_mqadmin_ext
+                        .create_or_update_order_conf(
+                            self.topic.trim().into(),
+                            order_conf.as_str().into(),
+                            false,
+                        )
+                        .await
+                        .map_err(|e| {
+                            RocketmqError::SubCommand("UpdateTopicSubCommand".into(), e.to_string())
+                        })?;
+                    println!(
+                        "set broker orderConf. isOrder={}, orderConf=[{}]",
+                        self.order, order_conf
+                    );
+                }
+                println!("create topic to {addr} success.");
+                println!("{}", format_topic_config(&topic_config));
+            } else if let Some(cluster_name) = &self.cluster_name {
+                let cluster_name = cluster_name.trim();
+                let master_set = command_util::fetch_master_addr_by_cluster_name(
+                    &default_mqadmin_ext,
+                    cluster_name,
+                )
+                .await?;
+                for addr in master_set {
+                    default_mqadmin_ext
+                        .create_and_update_topic_config(addr.clone(), topic_config.clone())
+                        .await
+                        .map_err(|e| {
+                            RocketmqError::SubCommand("UpdateTopicSubCommand".into(), e.to_string())
+                        })?;
+                    println!("create topic to {addr} success.");
+                }
+                if self.order {
+                    let mut broker_names = command_util::fetch_broker_name_by_cluster_name(
+                        &default_mqadmin_ext,
+                        cluster_name,
+                    )
+                    .await?
+                    .into_iter()
+                    .collect::<Vec<_>>();
+                    broker_names.sort();
+                    let order_conf = broker_names
+                        .iter()
+                        .map(|broker_name| {
+                            format!("{}:{}", broker_name, topic_config.write_queue_nums)
+                        })
+                        .collect::<Vec<_>>()
+                        .join(";");
+                    default_mqadmin_ext
+                        .create_or_update_order_conf(
+                            self.topic.trim().into(),
+                            order_conf.as_str().into(),
+                            true,
+                        )
+                        .await
+                        .map_err(|e| {
+                            RocketmqError::SubCommand("UpdateTopicSubCommand".into(), e.to_string())
+                        })?;
+                    print!(
+                        "set cluster orderConf. isOrder={}, orderConf=[{}]",
+                        self.order, order_conf
+                    );
+                }
+                println!("{}", format_topic_config(&topic_config));
+            }
+            Ok(())
+        }
+        .await;
+        MQAdminExt::shutdown(&mut default_mqadmin_ext).await;
+        operation_result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_topic_config_sorts_attributes() {
+        let mut topic_config = TopicConfig::with_queues("test_topic", 8, 4);
+        topic_config.attributes = HashMap::from([
+            ("+queue.type".into(), "BatchCQ".into()),
+            ("+cleanup.policy".into(), "COMPACTION".into()),
+            ("+message.type".into(), "NORMAL".into()),
+        ]);
+        assert_eq!(
+            format_topic_config(&topic_config),
+            "TopicConfig [topicName=test_topic, readQueueNums=8, writeQueueNums=4, perm=RW-, \
+             topicFilterType=SINGLE_TAG, topicSysFlag=0, order=false, \
+             attributes={+cleanup.policy=COMPACTION, +message.type=NORMAL, +queue.type=BatchCQ}]"
+        );
+    }
+}
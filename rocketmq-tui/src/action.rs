@@ -21,4 +21,10 @@ use strum::Display;
 #[derive(Debug, Clone, PartialEq, Serialize, Display, Deserialize)]
 pub enum Action {
     Quit,
+    NextPane,
+    PreviousPane,
+    SelectNext,
+    SelectPrevious,
+    Refresh,
+    FocusSearch,
 }
@@ -0,0 +1,397 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one or more
+ * contributor license agreements.  See the NOTICE file distributed with
+ * this work for additional information regarding copyright ownership.
+ * The ASF licenses this file to You under the Apache License, Version 2.0
+ * (the "License"); you may not use this file except in compliance with
+ * the License.  You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use cheetah_string::CheetahString;
+use rocketmq_client_rust::admin::mq_admin_ext_async::MQAdminExt;
+use rocketmq_common::common::message::message_ext::MessageExt;
+use rocketmq_common::common::message::MessageTrait;
+use rocketmq_common::common::mix_all;
+use rocketmq_common::utils::util_all::time_millis_to_human_string2;
+use rocketmq_common::TimeUtils::get_current_millis;
+use rocketmq_error::RocketMQResult;
+use rocketmq_remoting::protocol::admin::topic_stats_table::TopicStatsTable;
+use rocketmq_remoting::protocol::body::broker_body::cluster_info::ClusterInfo;
+use rocketmq_tools::admin::default_mq_admin_ext::DefaultMQAdminExt;
+use tokio::sync::mpsc;
+
+/// Requests sent from the UI to the dashboard service.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum DashboardRequest {
+    Refresh,
+    TopicOffsets(CheetahString),
+    ViewMessage {
+        topic: CheetahString,
+        msg_id: CheetahString,
+    },
+}
+
+/// Data pushed from the dashboard service back to the UI.
+#[derive(Debug)]
+pub(crate) enum DashboardEvent {
+    Snapshot(ClusterSnapshot),
+    TopicOffsets(CheetahString, Vec<QueueOffsetRow>),
+    Message(Vec<(String, String)>),
+    Error(String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ClusterSnapshot {
+    pub brokers: Vec<BrokerRow>,
+    pub topics: Vec<CheetahString>,
+    pub consumer_groups: Vec<ConsumerGroupRow>,
+    pub refreshed_at: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct BrokerRow {
+    pub cluster: CheetahString,
+    pub broker_name: CheetahString,
+    pub broker_id: u64,
+    pub addr: CheetahString,
+    /// Runtime stats reported by the broker, empty when the broker could not be reached.
+    pub runtime_stats: BTreeMap<CheetahString, CheetahString>,
+}
+
+impl BrokerRow {
+    pub fn stat(&self, key: &str) -> &str {
+        self.runtime_stats
+            .get(key)
+            .map_or("-", |value| value.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct ConsumerGroupRow {
+    pub group: CheetahString,
+    /// `None` when the group has no consume stats, usually because it is offline.
+    pub lag: Option<i64>,
+    pub consume_tps: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct QueueOffsetRow {
+    pub broker_name: CheetahString,
+    pub queue_id: i32,
+    pub min_offset: i64,
+    pub max_offset: i64,
+    pub last_update_timestamp: i64,
+}
+
+/// Background service owning the admin client. It refreshes the cluster snapshot periodically
+/// and answers the on-demand requests of the UI.
+pub(crate) struct DashboardService {
+    admin: DefaultMQAdminExt,
+    refresh_interval: Duration,
+}
+
+impl DashboardService {
+    pub async fn start(namesrv_addr: &str, refresh_interval: Duration) -> RocketMQResult<Self> {
+        let mut admin = DefaultMQAdminExt::new();
+        admin
+            .client_config_mut()
+            .set_instance_name(get_current_millis().to_string().into());
+        admin
+            .client_config_mut()
+            .set_namesrv_addr(namesrv_addr.trim().into());
+        MQAdminExt::start(&mut admin).await?;
+        Ok(Self {
+            admin,
+            refresh_interval,
+        })
+    }
+
+    pub async fn run(
+        mut self,
+        mut requests: mpsc::UnboundedReceiver<DashboardRequest>,
+        events: mpsc::UnboundedSender<DashboardEvent>,
+    ) {
+        let mut interval = tokio::time::interval(self.refresh_interval);
+        loop {
+            let request = tokio::select! {
+                _ = interval.tick() => DashboardRequest::Refresh,
+                request = requests.recv() => match request {
+                    Some(request) => request,
+                    None => break,
+                },
+            };
+            let event = match self.handle(request).await {
+                Ok(event) => event,
+                Err(e) => DashboardEvent::Error(e.to_string()),
+            };
+            if events.send(event).is_err() {
+                break;
+            }
+        }
+        MQAdminExt::shutdown(&mut self.admin).await;
+    }
+
+    async fn handle(&self, request: DashboardRequest) -> RocketMQResult<DashboardEvent> {
+        match request {
+            DashboardRequest::Refresh => Ok(DashboardEvent::Snapshot(self.snapshot().await?)),
+            DashboardRequest::TopicOffsets(topic) => {
+                let topic_stats = self.admin.examine_topic_stats(topic.clone(), None).await?;
+                Ok(DashboardEvent::TopicOffsets(
+                    topic,
+                    queue_offset_rows(&topic_stats),
+                ))
+            }
+            DashboardRequest::ViewMessage { topic, msg_id } => {
+                let message = self.admin.view_message(topic, msg_id.clone()).await?;
+                Ok(DashboardEvent::Message(message_detail_rows(
+                    &msg_id, &message,
+                )))
+            }
+        }
+    }
+
+    async fn snapshot(&self) -> RocketMQResult<ClusterSnapshot> {
+        let cluster_info = self.admin.examine_broker_cluster_info().await?;
+        let mut brokers = broker_rows(&cluster_info);
+        for broker in brokers.iter_mut() {
+            // an unreachable broker must not hide the rest of the cluster
+            if let Ok(kv_table) = self
+                .admin
+                .fetch_broker_runtime_stats(broker.addr.clone())
+                .await
+            {
+                broker.runtime_stats = kv_table.table.into_iter().collect();
+            }
+        }
+
+        let mut topics = self.admin.fetch_all_topic_list().await?.topic_list;
+        topics.sort();
+        let mut consumer_groups = Vec::new();
+        for group in consumer_groups_of_topics(&topics) {
+            let consume_stats = self
+                .admin
+                .examine_consume_stats(group.clone(), None, None, None, None)
+                .await
+                .ok();
+            consumer_groups.push(ConsumerGroupRow {
+                group,
+                lag: consume_stats
+                    .as_ref()
+                    .map(|consume_stats| consume_stats.compute_total_diff()),
+                consume_tps: consume_stats.map_or(0.0, |consume_stats| consume_stats.consume_tps),
+            });
+        }
+        Ok(ClusterSnapshot {
+            brokers,
+            topics,
+            consumer_groups,
+            refreshed_at: get_current_millis(),
+        })
+    }
+}
+
+/// Flattens the cluster info into one row per broker instance, sorted by cluster, broker name
+/// and broker id.
+pub(crate) fn broker_rows(cluster_info: &ClusterInfo) -> Vec<BrokerRow> {
+    let mut rows = Vec::new();
+    let Some(broker_addr_table) = cluster_info.broker_addr_table.as_ref() else {
+        return rows;
+    };
+    for broker_data in broker_addr_table.values() {
+        for (broker_id, addr) in broker_data.broker_addrs() {
+            rows.push(BrokerRow {
+                cluster: CheetahString::from_slice(broker_data.cluster()),
+                broker_name: broker_data.broker_name().clone(),
+                broker_id: *broker_id,
+                addr: addr.clone(),
+                runtime_stats: BTreeMap::new(),
+            });
+        }
+    }
+    rows.sort_by(|a, b| {
+        (&a.cluster, &a.broker_name, a.broker_id).cmp(&(&b.cluster, &b.broker_name, b.broker_id))
+    });
+    rows
+}
+
+/// Every consumer group owns a retry topic, which is how groups are discovered from the topic
+/// list.
+pub(crate) fn consumer_groups_of_topics(topics: &[CheetahString]) -> Vec<CheetahString> {
+    topics
+        .iter()
+        .filter_map(|topic| topic.strip_prefix(mix_all::RETRY_GROUP_TOPIC_PREFIX))
+        .filter(|group| !group.is_empty())
+        .map(CheetahString::from_slice)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+pub(crate) fn queue_offset_rows(topic_stats: &TopicStatsTable) -> Vec<QueueOffsetRow> {
+    let mut rows: Vec<QueueOffsetRow> = topic_stats
+        .get_offset_table()
+        .iter()
+        .map(|(mq, topic_offset)| QueueOffsetRow {
+            broker_name: mq.get_broker_name().clone(),
+            queue_id: mq.get_queue_id(),
+            min_offset: topic_offset.get_min_offset(),
+            max_offset: topic_offset.get_max_offset(),
+            last_update_timestamp: topic_offset.get_last_update_timestamp(),
+        })
+        .collect();
+    rows.sort_by(|a, b| (&a.broker_name, a.queue_id).cmp(&(&b.broker_name, b.queue_id)));
+    rows
+}
+
+pub(crate) fn message_detail_rows(msg_id: &str, message: &MessageExt) -> Vec<(String, String)> {
+    let mut properties: Vec<String> = message
+        .get_properties()
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect();
+    properties.sort();
+    vec![
+        ("Message ID".to_string(), msg_id.to_string()),
+        ("Topic".to_string(), message.get_topic().to_string()),
+        (
+            "Tags".to_string(),
+            message.get_tags().unwrap_or_default().to_string(),
+        ),
+        (
+            "Keys".to_string(),
+            message.get_keys().unwrap_or_default().to_string(),
+        ),
+        ("Queue ID".to_string(), message.queue_id().to_string()),
+        (
+            "Queue Offset".to_string(),
+            message.queue_offset().to_string(),
+        ),
+        (
+            "CommitLog Offset".to_string(),
+            message.commit_log_offset().to_string(),
+        ),
+        (
+            "Reconsume Times".to_string(),
+            message.reconsume_times().to_string(),
+        ),
+        (
+            "Born Timestamp".to_string(),
+            time_millis_to_human_string2(message.born_timestamp()),
+        ),
+        (
+            "Store Timestamp".to_string(),
+            time_millis_to_human_string2(message.store_timestamp()),
+        ),
+        ("Born Host".to_string(), message.born_host().to_string()),
+        ("Store Host".to_string(), message.store_host().to_string()),
+        ("Properties".to_string(), properties.join(", ")),
+        (
+            "Body".to_string(),
+            message.get_body().map_or(String::new(), |body| {
+                String::from_utf8_lossy(body).into_owned()
+            }),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use rocketmq_common::common::message::message_queue::MessageQueue;
+    use rocketmq_remoting::protocol::admin::topic_offset::TopicOffset;
+    use rocketmq_remoting::protocol::route::route_data_view::BrokerData;
+
+    use super::*;
+
+    #[test]
+    fn broker_rows_are_flattened_and_sorted() {
+        let broker_data = |name: &str, addrs: &[(u64, &str)]| {
+            BrokerData::new(
+                CheetahString::from_static_str("DefaultCluster"),
+                CheetahString::from(name),
+                addrs
+                    .iter()
+                    .map(|(id, addr)| (*id, CheetahString::from(*addr)))
+                    .collect(),
+                None,
+            )
+        };
+        let cluster_info = ClusterInfo::new(
+            Some(HashMap::from([
+                (
+                    CheetahString::from("broker-b"),
+                    broker_data("broker-b", &[(0, "127.0.0.1:20911")]),
+                ),
+                (
+                    CheetahString::from("broker-a"),
+                    broker_data(
+                        "broker-a",
+                        &[(1, "127.0.0.1:10921"), (0, "127.0.0.1:10911")],
+                    ),
+                ),
+            ])),
+            None,
+        );
+
+        let rows = broker_rows(&cluster_info);
+        let addrs: Vec<&str> = rows.iter().map(|row| row.addr.as_str()).collect();
+        assert_eq!(
+            addrs,
+            vec!["127.0.0.1:10911", "127.0.0.1:10921", "127.0.0.1:20911"]
+        );
+        assert_eq!(rows[1].broker_id, 1);
+        assert_eq!(rows[0].stat("putTps"), "-");
+    }
+
+    #[test]
+    fn consumer_groups_come_from_retry_topics() {
+        let topics = [
+            CheetahString::from("TopicTest"),
+            CheetahString::from("%RETRY%group_b"),
+            CheetahString::from("%RETRY%group_a"),
+            CheetahString::from("%DLQ%group_a"),
+            CheetahString::from("%RETRY%"),
+        ];
+        assert_eq!(
+            consumer_groups_of_topics(&topics),
+            vec![
+                CheetahString::from("group_a"),
+                CheetahString::from("group_b")
+            ]
+        );
+    }
+
+    #[test]
+    fn queue_offset_rows_are_sorted_by_queue() {
+        let mut offset_table = HashMap::new();
+        for queue_id in [2, 0, 1] {
+            let mut topic_offset = TopicOffset::new();
+            topic_offset.set_max_offset(queue_id as i64 * 10);
+            offset_table.insert(
+                MessageQueue::from_parts("TopicTest", "broker-a", queue_id),
+                topic_offset,
+            );
+        }
+        let mut topic_stats = TopicStatsTable::new();
+        topic_stats.set_offset_table(offset_table);
+
+        let rows = queue_offset_rows(&topic_stats);
+        assert_eq!(
+            rows.iter().map(|row| row.queue_id).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        assert_eq!(rows[2].max_offset, 20);
+    }
+}
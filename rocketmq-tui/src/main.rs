@@ -19,17 +19,55 @@
 #![allow(unused_variables)]
 
 mod action;
+mod dashboard;
 mod rocketmq_tui_app;
 mod ui;
 
+use std::time::Duration;
+
+use clap::Parser;
 use rocketmq_rust::rocketmq;
+use tokio::sync::mpsc;
 
+use crate::dashboard::DashboardService;
 use crate::rocketmq_tui_app::RocketmqTuiApp;
 
+#[derive(Parser)]
+#[command(name = "rocketmq-tui", about = "RocketMQ terminal dashboard", long_about = None)]
+struct Args {
+    #[arg(
+        short = 'n',
+        long = "namesrvAddr",
+        default_value = "127.0.0.1:9876",
+        help = "Name server address list, eg: '192.168.0.1:9876;192.168.0.2:9876'"
+    )]
+    namesrv_addr: String,
+
+    #[arg(
+        short = 'i',
+        long = "interval",
+        default_value_t = 5,
+        help = "seconds between two refreshes of the cluster data"
+    )]
+    refresh_interval: u64,
+}
+
 #[rocketmq::main]
 async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let service = DashboardService::start(
+        &args.namesrv_addr,
+        Duration::from_secs(args.refresh_interval.max(1)),
+    )
+    .await?;
+    let (request_tx, request_rx) = mpsc::unbounded_channel();
+    let (event_tx, event_rx) = mpsc::unbounded_channel();
+    tokio::spawn(service.run(request_rx, event_tx));
+
     let terminal = ratatui::try_init()?;
-    let result = RocketmqTuiApp::default().run(terminal).await;
+    let result = RocketmqTuiApp::with_dashboard(args.namesrv_addr, request_tx, event_rx)
+        .run(terminal)
+        .await;
     ratatui::try_restore()?;
     result
 }
@@ -16,6 +16,7 @@
  */
 use std::time::Duration;
 
+use cheetah_string::CheetahString;
 use ratatui::crossterm::event::Event;
 use ratatui::crossterm::event::EventStream;
 use ratatui::crossterm::event::KeyCode;
@@ -23,24 +24,105 @@ use ratatui::crossterm::event::KeyEventKind;
 use ratatui::layout::Constraint;
 use ratatui::layout::Direction;
 use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
 use ratatui::widgets::Block;
+use ratatui::widgets::Borders;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Row;
+use ratatui::widgets::Table;
+use ratatui::widgets::TableState;
+use ratatui::widgets::Tabs;
 use ratatui::DefaultTerminal;
 use ratatui::Frame;
+use rocketmq_common::utils::util_all::time_millis_to_human_string2;
+use tokio::sync::mpsc;
 use tokio_stream::StreamExt;
 
+use crate::action::Action;
+use crate::dashboard::ClusterSnapshot;
+use crate::dashboard::DashboardEvent;
+use crate::dashboard::DashboardRequest;
+use crate::dashboard::QueueOffsetRow;
 use crate::ui::search_input_widget::SearchInputWidget;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Pane {
+    #[default]
+    Brokers,
+    Topics,
+    ConsumerGroups,
+    BrokerStats,
+    Message,
+}
+
+impl Pane {
+    const ALL: [Pane; 5] = [
+        Pane::Brokers,
+        Pane::Topics,
+        Pane::ConsumerGroups,
+        Pane::BrokerStats,
+        Pane::Message,
+    ];
+
+    fn title(self) -> &'static str {
+        match self {
+            Pane::Brokers => "Clusters & Brokers",
+            Pane::Topics => "Topics",
+            Pane::ConsumerGroups => "Consumer Groups",
+            Pane::BrokerStats => "Broker Stats",
+            Pane::Message => "Message",
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|pane| *pane == self).unwrap_or(0)
+    }
+
+    fn next(self) -> Pane {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    fn previous(self) -> Pane {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
 #[derive(Default)]
 pub struct RocketmqTuiApp {
     should_quit: bool,
     search_input: SearchInputWidget,
+    namesrv_addr: String,
+    pane: Pane,
+    snapshot: ClusterSnapshot,
+    broker_state: TableState,
+    topic_state: TableState,
+    group_state: TableState,
+    topic_offsets: Option<(CheetahString, Vec<QueueOffsetRow>)>,
+    message: Vec<(String, String)>,
+    status: String,
+    requests: Option<mpsc::UnboundedSender<DashboardRequest>>,
+    events: Option<mpsc::UnboundedReceiver<DashboardEvent>>,
 }
 
 impl RocketmqTuiApp {
     pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_dashboard(
+        namesrv_addr: impl Into<String>,
+        requests: mpsc::UnboundedSender<DashboardRequest>,
+        events: mpsc::UnboundedReceiver<DashboardEvent>,
+    ) -> Self {
         Self {
-            should_quit: false,
-            search_input: Default::default(),
+            namesrv_addr: namesrv_addr.into(),
+            status: "loading cluster data...".to_string(),
+            requests: Some(requests),
+            events: Some(events),
+            ..Self::default()
         }
     }
 
@@ -60,97 +142,453 @@ impl RocketmqTuiApp {
         let period = Duration::from_secs_f32(1.0 / Self::FRAMES_PER_SECOND);
         let mut interval = tokio::time::interval(period);
         let mut events = EventStream::new();
+        let mut dashboard_events = self.events.take();
         while !self.should_quit {
             tokio::select! {
                 _ = interval.tick() => { terminal.draw(|frame| self.draw(frame))?; },
                 Some(Ok(event)) = events.next() => self.handle_event(&event),
+                Some(event) = recv(&mut dashboard_events) => self.handle_dashboard_event(event),
             }
         }
         Ok(())
     }
 
     fn handle_event(&mut self, event: &Event) {
-        if let Event::Key(key) = event {
-            if key.kind == KeyEventKind::Press {
-                match key.code {
-                    KeyCode::Char('q') | KeyCode::Esc => self.should_quit = true,
-                    _ => {}
+        let Event::Key(key) = event else {
+            return;
+        };
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        if self.search_input.is_focused() {
+            match key.code {
+                KeyCode::Esc => self.search_input.set_focus(false),
+                KeyCode::Enter => self.submit_search(),
+                code => self.search_input.handle_key_event(code),
+            }
+            return;
+        }
+        let action = match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+            KeyCode::Tab | KeyCode::Right => Action::NextPane,
+            KeyCode::BackTab | KeyCode::Left => Action::PreviousPane,
+            KeyCode::Down | KeyCode::Char('j') => Action::SelectNext,
+            KeyCode::Up | KeyCode::Char('k') => Action::SelectPrevious,
+            KeyCode::Char('r') => Action::Refresh,
+            KeyCode::Char('s') | KeyCode::Char('S') | KeyCode::Char('/') => Action::FocusSearch,
+            _ => return,
+        };
+        self.handle_action(action);
+    }
+
+    fn handle_action(&mut self, action: Action) {
+        match action {
+            Action::Quit => self.quit(),
+            Action::NextPane => self.pane = self.pane.next(),
+            Action::PreviousPane => self.pane = self.pane.previous(),
+            Action::SelectNext | Action::SelectPrevious => {
+                let forward = action == Action::SelectNext;
+                match self.pane {
+                    Pane::Brokers | Pane::BrokerStats => {
+                        move_selection(&mut self.broker_state, self.snapshot.brokers.len(), forward)
+                    }
+                    Pane::Topics => {
+                        move_selection(&mut self.topic_state, self.snapshot.topics.len(), forward);
+                        self.request_topic_offsets();
+                    }
+                    Pane::ConsumerGroups => move_selection(
+                        &mut self.group_state,
+                        self.snapshot.consumer_groups.len(),
+                        forward,
+                    ),
+                    Pane::Message => {}
+                }
+            }
+            Action::Refresh => {
+                self.send(DashboardRequest::Refresh);
+                if self.pane == Pane::Topics {
+                    self.request_topic_offsets();
                 }
             }
+            Action::FocusSearch => self.search_input.set_focus(true),
         }
     }
 
-    fn draw(&self, frame: &mut Frame) {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints(
-                [
-                    Constraint::Percentage(5),
-                    Constraint::Percentage(92),
-                    Constraint::Percentage(3),
-                ]
-                .as_ref(),
-            )
-            .split(frame.area());
+    fn handle_dashboard_event(&mut self, event: DashboardEvent) {
+        match event {
+            DashboardEvent::Snapshot(snapshot) => {
+                clamp_selection(&mut self.broker_state, snapshot.brokers.len());
+                clamp_selection(&mut self.topic_state, snapshot.topics.len());
+                clamp_selection(&mut self.group_state, snapshot.consumer_groups.len());
+                self.status = format!(
+                    "refreshed at {}",
+                    time_millis_to_human_string2(snapshot.refreshed_at as i64)
+                );
+                self.snapshot = snapshot;
+            }
+            DashboardEvent::TopicOffsets(topic, rows) => self.topic_offsets = Some((topic, rows)),
+            DashboardEvent::Message(message) => {
+                self.status = "message loaded".to_string();
+                self.message = message;
+            }
+            DashboardEvent::Error(error) => self.status = format!("error: {error}"),
+        }
+    }
 
-        let search = chunks[0];
-        let middle = chunks[1];
-        let progress_bar = chunks[2];
+    fn submit_search(&mut self) {
+        let Some((topic, msg_id)) = parse_search(self.search_input.get_input()) else {
+            self.status = "usage: [topic] <msgId>".to_string();
+            return;
+        };
+        self.search_input.set_focus(false);
+        self.status = format!("querying message {msg_id}...");
+        self.pane = Pane::Message;
+        self.send(DashboardRequest::ViewMessage { topic, msg_id });
+    }
 
-        let middle_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(30), Constraint::Percentage(70)].as_ref())
-            .split(middle);
+    fn request_topic_offsets(&mut self) {
+        if let Some(topic) = self
+            .topic_state
+            .selected()
+            .and_then(|index| self.snapshot.topics.get(index))
+        {
+            self.send(DashboardRequest::TopicOffsets(topic.clone()));
+        }
+    }
 
-        let command_tree = middle_chunks[0];
-        let middle_right = middle_chunks[1];
+    fn send(&mut self, request: DashboardRequest) {
+        let sent = self
+            .requests
+            .as_ref()
+            .is_some_and(|requests| requests.send(request).is_ok());
+        if !sent {
+            self.status = "dashboard service is not running".to_string();
+        }
+    }
 
-        let middle_right_chunks = Layout::default()
+    fn draw(&mut self, frame: &mut Frame) {
+        let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints(
                 [
-                    Constraint::Percentage(50),
-                    Constraint::Percentage(15),
-                    Constraint::Percentage(35),
+                    Constraint::Length(3),
+                    Constraint::Length(3),
+                    Constraint::Min(0),
+                    Constraint::Length(3),
                 ]
                 .as_ref(),
             )
-            .split(middle_right);
+            .split(frame.area());
 
-        let command_detail = middle_right_chunks[0];
-        let command_args = middle_right_chunks[1];
-        let execute_command_result = middle_right_chunks[2];
+        let search = chunks[0];
+        let tabs = chunks[1];
+        let middle = chunks[2];
+        let status_bar = chunks[3];
 
         frame.render_widget(&self.search_input, search);
         frame.render_widget(
-            Block::default()
-                .borders(ratatui::widgets::Borders::ALL)
-                .title("Command Tree"),
-            command_tree,
-        );
-        frame.render_widget(
-            Block::default()
-                .borders(ratatui::widgets::Borders::ALL)
-                .title("Command Detail"),
-            command_detail,
+            Tabs::new(Pane::ALL.iter().map(|pane| pane.title()))
+                .select(self.pane.index())
+                .highlight_style(highlight_style())
+                .block(bordered("Panes[Tab/Shift+Tab]")),
+            tabs,
         );
+        match self.pane {
+            Pane::Brokers => self.draw_brokers(frame, middle),
+            Pane::Topics => self.draw_topics(frame, middle),
+            Pane::ConsumerGroups => self.draw_consumer_groups(frame, middle),
+            Pane::BrokerStats => self.draw_broker_stats(frame, middle),
+            Pane::Message => self.draw_message(frame, middle),
+        }
         frame.render_widget(
-            Block::default()
-                .borders(ratatui::widgets::Borders::ALL)
-                .title("Command Args"),
-            command_args,
+            Paragraph::new(format!(
+                "namesrv: {} | {} | q: quit, r: refresh, j/k: select, s: search",
+                self.namesrv_addr, self.status
+            ))
+            .block(bordered("Status")),
+            status_bar,
         );
-        frame.render_widget(
-            Block::default()
-                .borders(ratatui::widgets::Borders::ALL)
-                .title("Execute Command Result"),
-            execute_command_result,
+    }
+
+    fn draw_brokers(&mut self, frame: &mut Frame, area: Rect) {
+        let rows = self.snapshot.brokers.iter().map(|broker| {
+            Row::new(vec![
+                broker.cluster.to_string(),
+                broker.broker_name.to_string(),
+                broker.broker_id.to_string(),
+                broker.addr.to_string(),
+                broker.stat("brokerVersionDesc").to_string(),
+                broker.stat("putTps").to_string(),
+                broker.stat("getTransferredTps").to_string(),
+                broker.stat("msgPutTotalTodayNow").to_string(),
+                broker.stat("msgGetTotalTodayNow").to_string(),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(12),
+                Constraint::Percentage(12),
+                Constraint::Percentage(5),
+                Constraint::Percentage(15),
+                Constraint::Percentage(10),
+                Constraint::Percentage(14),
+                Constraint::Percentage(14),
+                Constraint::Percentage(9),
+                Constraint::Percentage(9),
+            ],
+        )
+        .header(header_row([
+            "#Cluster",
+            "#BrokerName",
+            "#BID",
+            "#Addr",
+            "#Version",
+            "#InTPS",
+            "#OutTPS",
+            "#InTotalToday",
+            "#OutTotalToday",
+        ]))
+        .row_highlight_style(highlight_style())
+        .block(bordered("Clusters & Brokers"));
+        frame.render_stateful_widget(table, area, &mut self.broker_state);
+    }
+
+    fn draw_topics(&mut self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(35), Constraint::Percentage(65)].as_ref())
+            .split(area);
+
+        let topics = Table::new(
+            self.snapshot
+                .topics
+                .iter()
+                .map(|topic| Row::new(vec![topic.to_string()])),
+            [Constraint::Percentage(100)],
+        )
+        .header(header_row(["#Topic"]))
+        .row_highlight_style(highlight_style())
+        .block(bordered("Topics"));
+        frame.render_stateful_widget(topics, chunks[0], &mut self.topic_state);
+
+        let (title, rows) = match &self.topic_offsets {
+            Some((topic, rows)) => (format!("Queue Offsets of {topic}"), rows.as_slice()),
+            None => ("Queue Offsets[select a topic]".to_string(), [].as_slice()),
+        };
+        let offsets = Table::new(
+            rows.iter().map(|row| {
+                Row::new(vec![
+                    row.broker_name.to_string(),
+                    row.queue_id.to_string(),
+                    row.min_offset.to_string(),
+                    row.max_offset.to_string(),
+                    if row.last_update_timestamp > 0 {
+                        time_millis_to_human_string2(row.last_update_timestamp)
+                    } else {
+                        "-".to_string()
+                    },
+                ])
+            }),
+            [
+                Constraint::Percentage(25),
+                Constraint::Percentage(10),
+                Constraint::Percentage(17),
+                Constraint::Percentage(17),
+                Constraint::Percentage(31),
+            ],
+        )
+        .header(header_row([
+            "#BrokerName",
+            "#QID",
+            "#MinOffset",
+            "#MaxOffset",
+            "#LastUpdated",
+        ]))
+        .block(bordered(&title));
+        frame.render_widget(offsets, chunks[1]);
+    }
+
+    fn draw_consumer_groups(&mut self, frame: &mut Frame, area: Rect) {
+        let rows = self.snapshot.consumer_groups.iter().map(|group| {
+            Row::new(vec![
+                group.group.to_string(),
+                group
+                    .lag
+                    .map_or("offline".to_string(), |lag| lag.to_string()),
+                format!("{:.2}", group.consume_tps),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(60),
+                Constraint::Percentage(20),
+                Constraint::Percentage(20),
+            ],
+        )
+        .header(header_row(["#Group", "#DiffTotal", "#ConsumeTPS"]))
+        .row_highlight_style(highlight_style())
+        .block(bordered("Consumer Groups"));
+        frame.render_stateful_widget(table, area, &mut self.group_state);
+    }
+
+    fn draw_broker_stats(&mut self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(30), Constraint::Percentage(70)].as_ref())
+            .split(area);
+
+        let brokers = Table::new(
+            self.snapshot
+                .brokers
+                .iter()
+                .map(|broker| Row::new(vec![broker.addr.to_string()])),
+            [Constraint::Percentage(100)],
+        )
+        .header(header_row(["#Addr"]))
+        .row_highlight_style(highlight_style())
+        .block(bordered("Brokers"));
+        frame.render_stateful_widget(brokers, chunks[0], &mut self.broker_state);
+
+        let selected = self
+            .broker_state
+            .selected()
+            .and_then(|index| self.snapshot.brokers.get(index));
+        let stats = Table::new(
+            selected
+                .into_iter()
+                .flat_map(|broker| broker.runtime_stats.iter())
+                .map(|(key, value)| Row::new(vec![key.to_string(), value.to_string()])),
+            [Constraint::Percentage(40), Constraint::Percentage(60)],
+        )
+        .header(header_row(["#Key", "#Value"]))
+        .block(bordered("Runtime Stats"));
+        frame.render_widget(stats, chunks[1]);
+    }
+
+    fn draw_message(&mut self, frame: &mut Frame, area: Rect) {
+        if self.message.is_empty() {
+            frame.render_widget(
+                Paragraph::new("Press s and enter `[topic] <msgId>` to view a message.")
+                    .block(bordered("Message")),
+                area,
+            );
+            return;
+        }
+        let table = Table::new(
+            self.message
+                .iter()
+                .map(|(name, value)| Row::new(vec![name.clone(), value.clone()])),
+            [Constraint::Percentage(20), Constraint::Percentage(80)],
+        )
+        .block(bordered("Message"));
+        frame.render_widget(table, area);
+    }
+}
+
+async fn recv(
+    events: &mut Option<mpsc::UnboundedReceiver<DashboardEvent>>,
+) -> Option<DashboardEvent> {
+    match events {
+        Some(events) => events.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Parses the search input, either `<msgId>` or `<topic> <msgId>`.
+fn parse_search(input: &str) -> Option<(CheetahString, CheetahString)> {
+    let mut parts = input.split_whitespace();
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(msg_id), None, None) => Some((CheetahString::new(), CheetahString::from(msg_id))),
+        (Some(topic), Some(msg_id), None) => {
+            Some((CheetahString::from(topic), CheetahString::from(msg_id)))
+        }
+        _ => None,
+    }
+}
+
+fn move_selection(state: &mut TableState, len: usize, forward: bool) {
+    if len == 0 {
+        state.select(None);
+        return;
+    }
+    let index = match state.selected() {
+        None => 0,
+        Some(index) if forward => (index + 1) % len,
+        Some(index) => (index + len - 1) % len,
+    };
+    state.select(Some(index));
+}
+
+fn clamp_selection(state: &mut TableState, len: usize) {
+    match state.selected() {
+        _ if len == 0 => state.select(None),
+        Some(index) if index >= len => state.select(Some(len - 1)),
+        None => state.select(Some(0)),
+        _ => {}
+    }
+}
+
+fn bordered(title: &str) -> Block<'_> {
+    Block::default().borders(Borders::ALL).title(title)
+}
+
+fn header_row<const N: usize>(titles: [&'static str; N]) -> Row<'static> {
+    Row::new(titles).style(Style::default().add_modifier(Modifier::BOLD))
+}
+
+fn highlight_style() -> Style {
+    Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::REVERSED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_search_accepts_optional_topic() {
+        assert_eq!(
+            parse_search("  7F00000100002A9F0000000000000000 "),
+            Some((
+                CheetahString::new(),
+                CheetahString::from("7F00000100002A9F0000000000000000")
+            ))
         );
-        frame.render_widget(
-            Block::default()
-                .borders(ratatui::widgets::Borders::ALL)
-                .title("Progress Bar"),
-            progress_bar,
+        assert_eq!(
+            parse_search("TopicTest AC11000100002A9F0000000000000001"),
+            Some((
+                CheetahString::from("TopicTest"),
+                CheetahString::from("AC11000100002A9F0000000000000001")
+            ))
         );
+        assert_eq!(parse_search(""), None);
+        assert_eq!(parse_search("a b c"), None);
+    }
+
+    #[test]
+    fn selection_wraps_and_clamps() {
+        let mut state = TableState::default();
+        move_selection(&mut state, 3, false);
+        assert_eq!(state.selected(), Some(0));
+        move_selection(&mut state, 3, false);
+        assert_eq!(state.selected(), Some(2));
+        move_selection(&mut state, 3, true);
+        assert_eq!(state.selected(), Some(0));
+
+        state.select(Some(5));
+        clamp_selection(&mut state, 2);
+        assert_eq!(state.selected(), Some(1));
+        clamp_selection(&mut state, 0);
+        assert_eq!(state.selected(), None);
+    }
+
+    #[test]
+    fn pane_navigation_wraps() {
+        assert_eq!(Pane::Brokers.previous(), Pane::Message);
+        assert_eq!(Pane::Message.next(), Pane::Brokers);
+        assert_eq!(Pane::Topics.next(), Pane::ConsumerGroups);
     }
 }
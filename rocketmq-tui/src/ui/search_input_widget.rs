@@ -88,7 +88,7 @@ impl Widget for &SearchInputWidget {
 
         let block = Block::default()
             .borders(ratatui::widgets::Borders::ALL)
-            .title("Search[Press s/S to focus]")
+            .title("Search Message[Press s/S to focus, Enter to query]")
             .border_style(style);
         block.render(area, buf);
 